@@ -6,6 +6,7 @@ use std::ptr;
 use std::slice;
 
 use crate::h5type::{hvl_t, CompoundType, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor};
+use crate::references::{is_null_reference, Reference, HOBJ_REF_SIZE};
 use crate::string::{VarLenAscii, VarLenUnicode};
 
 fn read_raw<T: Copy>(buf: &[u8]) -> T {
@@ -688,6 +689,132 @@ impl<'a> From<DynString<'a>> for DynValue<'a> {
     }
 }
 
+/// A reference of any kind, held as its raw bytes.
+///
+/// Resolving a reference requires the HDF5 library, so dynamically-typed values only expose its
+/// bytes (see [`references`](crate::references) for their layouts). Cloning a reference copies
+/// its bytes and dropping it does nothing; in particular, standard (`H5R_ref_t`) references are
+/// not destroyed.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct DynReference<'a> {
+    kind: Reference,
+    buf: &'a [u8],
+}
+
+impl<'a> DynReference<'a> {
+    pub fn new(kind: Reference, buf: &'a [u8]) -> Self {
+        Self { kind, buf }
+    }
+
+    /// Returns the kind of the reference.
+    pub fn kind(&self) -> Reference {
+        self.kind
+    }
+
+    /// Returns the raw bytes of the reference.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// Returns `true` if the reference does not point anywhere.
+    pub fn is_null(&self) -> bool {
+        is_null_reference(self.buf)
+    }
+}
+
+unsafe impl DynClone for DynReference<'_> {
+    fn dyn_clone(&mut self, out: &mut [u8]) {
+        out.copy_from_slice(self.buf);
+    }
+}
+
+impl Debug for DynReference<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.kind {
+            Reference::Object => "ObjectRef",
+            Reference::Region => "RegionRef",
+            Reference::Std => "Ref",
+        };
+        if self.is_null() {
+            return write!(f, "{name}(null)");
+        }
+        match (self.kind, <[u8; HOBJ_REF_SIZE]>::try_from(self.buf)) {
+            (Reference::Object, Ok(addr)) => write!(f, "{name}({:#x})", u64::from_ne_bytes(addr)),
+            (Reference::Std, _) => write!(f, "{name}(..)"),
+            _ => {
+                write!(f, "{name}(")?;
+                self.buf.iter().try_for_each(|b| write!(f, "{b:02x}"))?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl Display for DynReference<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+impl<'a> From<DynReference<'a>> for DynValue<'a> {
+    fn from(value: DynReference<'a>) -> Self {
+        DynValue::Reference(value)
+    }
+}
+
+/// Opaque bytes with their tag; unit values have no bytes.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct DynOpaque<'a> {
+    tag: &'a str,
+    buf: &'a [u8],
+}
+
+impl<'a> DynOpaque<'a> {
+    pub fn new(tag: &'a str, buf: &'a [u8]) -> Self {
+        Self { tag, buf }
+    }
+
+    /// Returns the tag of the opaque type.
+    pub fn tag(&self) -> &'a str {
+        self.tag
+    }
+
+    /// Returns the bytes of the value.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+}
+
+unsafe impl DynClone for DynOpaque<'_> {
+    fn dyn_clone(&mut self, out: &mut [u8]) {
+        out.copy_from_slice(self.buf);
+    }
+}
+
+impl Debug for DynOpaque<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.tag)?;
+        if !self.buf.is_empty() {
+            write!(f, "(")?;
+            self.buf.iter().try_for_each(|b| write!(f, "{b:02x}"))?;
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for DynOpaque<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+impl<'a> From<DynOpaque<'a>> for DynValue<'a> {
+    fn from(value: DynOpaque<'a>) -> Self {
+        DynValue::Opaque(value)
+    }
+}
+
 /// A borrowed value with dynamic type.
 #[derive(PartialEq)]
 pub enum DynValue<'a> {
@@ -696,6 +823,8 @@ pub enum DynValue<'a> {
     Compound(DynCompound<'a>),
     Array(DynArray<'a>),
     String(DynString<'a>),
+    Reference(DynReference<'a>),
+    Opaque(DynOpaque<'a>),
 }
 
 impl<'a> DynValue<'a> {
@@ -711,14 +840,30 @@ impl<'a> DynValue<'a> {
             Enum(ref tp) => DynEnum::new(tp, DynInteger::read(buf, tp.signed, tp.size)).into(),
             Compound(ref tp) => DynCompound::new(tp, buf).into(),
             FixedArray(ref tp, n) => DynArray::new(tp, buf, Some(*n)).into(),
+            FixedArrayNd(ref tp, ref dims) => {
+                DynArray::new(tp, buf, Some(dims.iter().product())).into()
+            }
             VarLenArray(ref tp) => DynArray::new(tp, buf, None).into(),
             FixedAscii(_) => DynFixedString::new(buf, false).into(),
             FixedUnicode(_) => DynFixedString::new(buf, true).into(),
             VarLenAscii => DynVarLenString::new(buf, false).into(),
             VarLenUnicode => DynVarLenString::new(buf, true).into(),
-            Reference(_x) => todo!(),
+            Reference(kind) => DynReference::new(*kind, buf).into(),
+            Opaque(ref tp) => DynOpaque::new(&tp.tag, buf).into(),
         }
     }
+
+    /// Copies the variable-length data of the value into new allocations owned by `out`.
+    ///
+    /// # Safety
+    ///
+    /// `out` must hold a bitwise copy of the value's buffer and be aligned for its type; the
+    /// pointers to variable-length data in `out` are replaced by pointers to the copies, which
+    /// are allocated with the allocator of this crate.
+    #[doc(hidden)]
+    pub unsafe fn deep_copy_into(mut self, out: &mut [u8]) {
+        self.dyn_clone(out);
+    }
 }
 
 unsafe impl DynDrop for DynValue<'_> {
@@ -740,6 +885,8 @@ unsafe impl DynClone for DynValue<'_> {
             Self::Compound(x) => x.dyn_clone(out),
             Self::Array(x) => x.dyn_clone(out),
             Self::String(x) => x.dyn_clone(out),
+            Self::Reference(x) => x.dyn_clone(out),
+            Self::Opaque(x) => x.dyn_clone(out),
         }
     }
 }
@@ -752,6 +899,8 @@ impl Debug for DynValue<'_> {
             Self::Compound(x) => Debug::fmt(&x, f),
             Self::Array(x) => Debug::fmt(&x, f),
             Self::String(x) => Debug::fmt(&x, f),
+            Self::Reference(x) => Debug::fmt(&x, f),
+            Self::Opaque(x) => Debug::fmt(&x, f),
         }
     }
 }
@@ -1075,6 +1224,49 @@ mod tests {
         assert_ne!(val2, val1);
     }
 
+    #[test]
+    fn test_dyn_value_reference() {
+        #[derive(Clone, Copy)]
+        #[repr(transparent)]
+        struct ObjRef(u64);
+
+        unsafe impl H5Type for ObjRef {
+            fn type_descriptor() -> TypeDescriptor {
+                TypeDescriptor::Reference(crate::Reference::Object)
+            }
+        }
+
+        let refs = OwnedDynValue::new(VarLenArray::from_slice(&[ObjRef(0x320), ObjRef(0)]));
+        assert_eq!(refs.clone(), refs);
+        assert_eq!(format!("{}", refs), "[ObjectRef(0x320), ObjectRef(null)]");
+        let DynValue::Array(array) = refs.get() else { panic!("expected an array") };
+        let kinds: Vec<_> = array
+            .iter()
+            .map(|value| match value {
+                DynValue::Reference(r) => (r.kind(), r.is_null(), r.as_bytes().len()),
+                _ => panic!("expected a reference"),
+            })
+            .collect();
+        let object = crate::Reference::Object;
+        assert_eq!(kinds, [(object, false, 8), (object, true, 8)]);
+
+        let region =
+            DynReference::new(crate::Reference::Region, &[1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0]);
+        assert_eq!(format!("{:?}", region), "RegionRef(010000000000000002000000)");
+    }
+
+    #[test]
+    fn test_dyn_value_opaque() {
+        let unit = TypeDescriptor::unit("Marker");
+        assert_eq!(format!("{}", DynValue::new(&unit, &[])), "Marker");
+        let opaque = TypeDescriptor::Opaque(crate::OpaqueType { size: 2, tag: "pair".into() });
+        let value = DynValue::new(&opaque, &[0xab, 1]);
+        assert_eq!(format!("{}", value), "pair(ab01)");
+        let DynValue::Opaque(value) = value else { panic!("expected an opaque value") };
+        assert_eq!((value.tag(), value.as_bytes()), ("pair", &[0xab, 1][..]));
+        assert_eq!(OwnedDynValue::new(()).to_string(), "()");
+    }
+
     #[test]
     fn test_dyn_value_display() {
         let val1 = OwnedDynValue::new(big_struct_1());
@@ -16,6 +16,11 @@ pub(crate) struct hvl_t {
 
 /// A valid integer size.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "usize", into = "usize")
+)]
 pub enum IntSize {
     /// 1 byte.
     U1 = 1,
@@ -44,8 +49,27 @@ impl IntSize {
     }
 }
 
+impl TryFrom<usize> for IntSize {
+    type Error = String;
+
+    fn try_from(size: usize) -> Result<Self, Self::Error> {
+        Self::from_int(size).ok_or_else(|| format!("invalid integer size: {size}"))
+    }
+}
+
+impl From<IntSize> for usize {
+    fn from(size: IntSize) -> Self {
+        size as _
+    }
+}
+
 /// A valid floating-point number size.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "usize", into = "usize")
+)]
 pub enum FloatSize {
     /// 2 bytes.
     #[cfg(feature = "f16")]
@@ -73,8 +97,23 @@ impl FloatSize {
     }
 }
 
+impl TryFrom<usize> for FloatSize {
+    type Error = String;
+
+    fn try_from(size: usize) -> Result<Self, Self::Error> {
+        Self::from_int(size).ok_or_else(|| format!("invalid floating-point size: {size}"))
+    }
+}
+
+impl From<FloatSize> for usize {
+    fn from(size: FloatSize) -> Self {
+        size as _
+    }
+}
+
 /// A descriptor for an enumeration datatype member.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnumMember {
     /// The name of the member.
     pub name: String,
@@ -84,6 +123,7 @@ pub struct EnumMember {
 
 /// A descriptor for an enumeration datatype.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnumType {
     /// The size of the underlying integer type.
     pub size: IntSize,
@@ -105,8 +145,19 @@ impl EnumType {
     }
 }
 
+/// A descriptor for an opaque datatype.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpaqueType {
+    /// The size in bytes of the datatype, 0 for [unit types](TypeDescriptor::unit).
+    pub size: usize,
+    /// The tag describing the contents (at most 255 bytes long).
+    pub tag: String,
+}
+
 /// A descriptor for a compound datatype field.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CompoundField {
     /// The name of the field.
     pub name: String,
@@ -132,6 +183,7 @@ impl CompoundField {
 
 /// A descriptor for a compound datatype.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CompoundType {
     /// The fields of the datatype.
     pub fields: Vec<CompoundField>,
@@ -140,6 +192,64 @@ pub struct CompoundType {
 }
 
 impl CompoundType {
+    fn from_fields(fields: Vec<(String, TypeDescriptor)>) -> Self {
+        let fields = fields
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, ty))| CompoundField { name, ty, offset: 0, index })
+            .collect();
+        Self { fields, size: 0 }
+    }
+
+    /// Creates a compound type with the given fields laid out in order without padding.
+    pub fn from_fields_packed<S: Into<String>>(fields: Vec<(S, TypeDescriptor)>) -> Self {
+        Self::from_fields(fields.into_iter().map(|(name, ty)| (name.into(), ty)).collect())
+            .to_packed_repr()
+    }
+
+    /// Creates a compound type with the given fields laid out in order like a `#[repr(C)]`
+    /// struct, i.e. with each field (including nested compound types) naturally aligned.
+    pub fn from_fields_aligned<S: Into<String>>(fields: Vec<(S, TypeDescriptor)>) -> Self {
+        Self::from_fields(fields.into_iter().map(|(name, ty)| (name.into(), ty)).collect())
+            .to_c_repr()
+    }
+
+    /// Checks that there are fields, that the field names are unique, that no two fields
+    /// overlap and that all fields fit into the size of the type, recursing into nested
+    /// compound types.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.fields.is_empty() {
+            return Err("compound type has no fields".into());
+        }
+        let mut fields = self.fields.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|f| (f.offset, f.index));
+        for (i, field) in fields.iter().enumerate() {
+            if fields[..i].iter().any(|f| f.name == field.name) {
+                return Err(format!("duplicate compound field '{}'", field.name));
+            }
+            let end = field.offset + field.ty.size();
+            if end > self.size {
+                return Err(format!(
+                    "compound field '{}' ends at byte {} past the type size {}",
+                    field.name, end, self.size
+                ));
+            }
+            if let Some(next) = fields.get(i + 1) {
+                if next.offset < end {
+                    return Err(format!(
+                        "compound fields '{}' and '{}' overlap",
+                        field.name, next.name
+                    ));
+                }
+            }
+            field
+                .ty
+                .validate()
+                .map_err(|err| format!("compound field '{}': {}", field.name, err))?;
+        }
+        Ok(())
+    }
+
     /// Converts `self` to a C struct representation.
     pub fn to_c_repr(&self) -> Self {
         let mut layout = self.clone();
@@ -179,6 +289,7 @@ impl CompoundType {
 
 /// A descriptor for an HDF5 datatype.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TypeDescriptor {
     /// A signed integer.
     Integer(IntSize),
@@ -194,6 +305,9 @@ pub enum TypeDescriptor {
     Compound(CompoundType),
     /// A fixed-length array.
     FixedArray(Box<Self>, usize),
+    /// A fixed-size multi-dimensional array with dimensions in row-major order, such as
+    /// `[[T; M]; N]` with dimensions `[N, M]`.
+    FixedArrayNd(Box<Self>, Vec<usize>),
     /// A fixed-length ASCII string.
     FixedAscii(usize),
     /// A fixed-length UTF-8 string.
@@ -205,6 +319,8 @@ pub enum TypeDescriptor {
     /// A variable-length UTF-8 string.
     VarLenUnicode,
     Reference(Reference),
+    /// Bytes HDF5 does not interpret, identified by a tag.
+    Opaque(OpaqueType),
 }
 
 impl Display for TypeDescriptor {
@@ -226,6 +342,7 @@ impl Display for TypeDescriptor {
             TypeDescriptor::Enum(ref tp) => write!(f, "enum ({})", tp.base_type()),
             TypeDescriptor::Compound(ref tp) => write!(f, "compound ({} fields)", tp.fields.len()),
             TypeDescriptor::FixedArray(ref tp, n) => write!(f, "[{tp}; {n}]"),
+            TypeDescriptor::FixedArrayNd(ref tp, ref dims) => write!(f, "[{tp}; {dims:?}]"),
             TypeDescriptor::FixedAscii(n) => write!(f, "string (len {n})"),
             TypeDescriptor::FixedUnicode(n) => write!(f, "unicode (len {n})"),
             TypeDescriptor::VarLenArray(ref tp) => write!(f, "[{tp}] (var len)"),
@@ -234,11 +351,31 @@ impl Display for TypeDescriptor {
             TypeDescriptor::Reference(Reference::Object) => write!(f, "reference (object)"),
             TypeDescriptor::Reference(Reference::Region) => write!(f, "reference (region)"),
             TypeDescriptor::Reference(Reference::Std) => write!(f, "reference"),
+            TypeDescriptor::Opaque(OpaqueType { size: 0, ref tag }) => write!(f, "unit ({tag})"),
+            TypeDescriptor::Opaque(OpaqueType { size, ref tag }) => {
+                write!(f, "opaque (len {size}, tag {tag})")
+            }
         }
     }
 }
 
 impl TypeDescriptor {
+    /// Returns the descriptor of a unit type, such as `()` or `struct Marker;`, which holds no
+    /// data.
+    ///
+    /// Unit types occupy no memory and are stored as 1-byte opaque values with the given tag
+    /// (e.g. the name of the type): the C and packed representations, which are used for
+    /// datatypes in files, replace them by such opaque types. Reading a unit value reads
+    /// nothing, and attributes of unit types are created with a null dataspace by default.
+    pub fn unit(tag: &str) -> Self {
+        Self::Opaque(OpaqueType { size: 0, tag: tag.to_owned() })
+    }
+
+    /// Returns `true` for the descriptors of [unit types](Self::unit).
+    pub fn is_unit(&self) -> bool {
+        matches!(self, Self::Opaque(OpaqueType { size: 0, .. }))
+    }
+
     /// Returns the size of the [`TypeDescriptor`] variant in bytes
     pub fn size(&self) -> usize {
         match *self {
@@ -248,31 +385,71 @@ impl TypeDescriptor {
             Self::Enum(ref enum_type) => enum_type.size as _,
             Self::Compound(ref compound) => compound.size,
             Self::FixedArray(ref ty, len) => ty.size() * len,
+            Self::FixedArrayNd(ref ty, ref dims) => ty.size() * dims.iter().product::<usize>(),
             Self::FixedAscii(len) | Self::FixedUnicode(len) => len,
             Self::VarLenArray(_) => mem::size_of::<hvl_t>(),
             Self::VarLenAscii | Self::VarLenUnicode => mem::size_of::<*const u8>(),
             Self::Reference(reftyp) => reftyp.size(),
+            Self::Opaque(ref opaque) => opaque.size,
         }
     }
 
-    fn c_alignment(&self) -> usize {
+    /// Returns the alignment in bytes of the C representation of the type.
+    pub fn c_alignment(&self) -> usize {
         match *self {
             Self::Compound(ref compound) => {
                 compound.fields.iter().map(|f| f.ty.c_alignment()).max().unwrap_or(1)
             }
-            Self::FixedArray(ref ty, _) => ty.c_alignment(),
-            Self::FixedAscii(_) | Self::FixedUnicode(_) => 1,
+            Self::FixedArray(ref ty, _) | Self::FixedArrayNd(ref ty, _) => ty.c_alignment(),
+            Self::FixedAscii(_) | Self::FixedUnicode(_) | Self::Opaque(_) => 1,
             Self::VarLenArray(_) => mem::size_of::<usize>(),
+            Self::Reference(Reference::Region) => 1,
+            Self::Reference(Reference::Object | Reference::Std) => 8,
             _ => self.size(),
         }
     }
 
+    /// Checks the layout of nested compound types, see [`CompoundType::validate`].
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            Self::Compound(ref compound) => compound.validate(),
+            Self::FixedArray(ref ty, _)
+            | Self::FixedArrayNd(ref ty, _)
+            | Self::VarLenArray(ref ty) => ty.validate(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the element type and dimensions of a fixed-size array, with nested arrays
+    /// flattened into a single multi-dimensional array, or `None` for other types.
+    ///
+    /// For example, both `[[T; 3]; 4]` as nested [`FixedArray`](Self::FixedArray) descriptors
+    /// and as a [`FixedArrayNd`](Self::FixedArrayNd) descriptor return `(T, [4, 3])`.
+    pub fn array_dims(&self) -> Option<(&Self, Vec<usize>)> {
+        let (mut ty, mut dims) = match *self {
+            Self::FixedArray(ref ty, len) => (&**ty, vec![len]),
+            Self::FixedArrayNd(ref ty, ref dims) => (&**ty, dims.clone()),
+            _ => return None,
+        };
+        while let Some((inner, inner_dims)) = ty.array_dims() {
+            dims.extend(inner_dims);
+            ty = inner;
+        }
+        Some((ty, dims))
+    }
+
     /// Converts `self` to a C-compatible representation.
     pub fn to_c_repr(&self) -> Self {
         match *self {
             Self::Compound(ref compound) => Self::Compound(compound.to_c_repr()),
             Self::FixedArray(ref ty, size) => Self::FixedArray(Box::new(ty.to_c_repr()), size),
+            Self::FixedArrayNd(ref ty, ref dims) => {
+                Self::FixedArrayNd(Box::new(ty.to_c_repr()), dims.clone())
+            }
             Self::VarLenArray(ref ty) => Self::VarLenArray(Box::new(ty.to_c_repr())),
+            Self::Opaque(OpaqueType { size: 0, ref tag }) => {
+                Self::Opaque(OpaqueType { size: 1, tag: tag.clone() })
+            }
             _ => self.clone(),
         }
     }
@@ -282,7 +459,13 @@ impl TypeDescriptor {
         match *self {
             Self::Compound(ref compound) => Self::Compound(compound.to_packed_repr()),
             Self::FixedArray(ref ty, size) => Self::FixedArray(Box::new(ty.to_packed_repr()), size),
+            Self::FixedArrayNd(ref ty, ref dims) => {
+                Self::FixedArrayNd(Box::new(ty.to_packed_repr()), dims.clone())
+            }
             Self::VarLenArray(ref ty) => Self::VarLenArray(Box::new(ty.to_packed_repr())),
+            Self::Opaque(OpaqueType { size: 0, ref tag }) => {
+                Self::Opaque(OpaqueType { size: 1, tag: tag.clone() })
+            }
             _ => self.clone(),
         }
     }
@@ -344,6 +527,14 @@ unsafe impl H5Type for bool {
     }
 }
 
+/// `()` stands for "no data", e.g. for attributes that only mark their parent object.
+unsafe impl H5Type for () {
+    #[inline]
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::unit("()")
+    }
+}
+
 // macro_rules! impl_tuple {
 //     (@second $a:tt $b:tt) => ($b);
 
@@ -396,10 +587,18 @@ unsafe impl H5Type for bool {
 
 // impl_tuple! { A, B, C, D, E, F, G, H, I, J, K, L }
 
+/// Arrays of arrays, such as `[[T; M]; N]`, map to a single multi-dimensional array type
+/// ([`TypeDescriptor::FixedArrayNd`]) with dimensions `[N, M]`, like h5py array dtypes.
 unsafe impl<T: H5Type, const N: usize> H5Type for [T; N] {
     #[inline]
     fn type_descriptor() -> TypeDescriptor {
-        TypeDescriptor::FixedArray(Box::new(<T as H5Type>::type_descriptor()), N)
+        let inner = <T as H5Type>::type_descriptor();
+        match inner.array_dims() {
+            Some((ty, dims)) => {
+                TypeDescriptor::FixedArrayNd(Box::new(ty.clone()), [vec![N], dims].concat())
+            }
+            None => TypeDescriptor::FixedArray(Box::new(inner), N),
+        }
     }
 }
 
@@ -441,7 +640,7 @@ unsafe impl H5Type for VarLenUnicode {
 #[cfg(test)]
 pub mod tests {
     use super::TypeDescriptor as TD;
-    use super::{hvl_t, FloatSize, H5Type, IntSize};
+    use super::{hvl_t, CompoundField, CompoundType, FloatSize, H5Type, IntSize, OpaqueType};
     use crate::array::VarLenArray;
     use crate::string::{FixedAscii, FixedUnicode, VarLenAscii, VarLenUnicode};
     use std::mem;
@@ -489,7 +688,15 @@ pub mod tests {
         type S = [T; 4];
         type T = [u32; 256];
         assert_eq!(T::type_descriptor(), TD::FixedArray(Box::new(TD::Unsigned(IntSize::U4)), 256));
-        assert_eq!(S::type_descriptor(), TD::FixedArray(Box::new(T::type_descriptor()), 4));
+        let nd = TD::FixedArrayNd(Box::new(TD::Unsigned(IntSize::U4)), vec![4, 256]);
+        assert_eq!(S::type_descriptor(), nd);
+        assert_eq!(S::type_descriptor().size(), mem::size_of::<S>());
+        assert_eq!(<[S; 2]>::type_descriptor().array_dims().unwrap().1, [2, 4, 256]);
+        let nested = TD::FixedArray(Box::new(T::type_descriptor()), 4);
+        assert_eq!(nested.array_dims(), nd.array_dims());
+        assert_eq!(nested.size(), nd.size());
+        assert_eq!(nd.to_string(), "[uint32; [4, 256]]");
+        assert_eq!(TD::Boolean.array_dims(), None);
     }
 
     #[test]
@@ -509,6 +716,185 @@ pub mod tests {
         assert_eq!(VarLenUnicode::type_descriptor(), TD::VarLenUnicode);
     }
 
+    #[test]
+    pub fn test_size_conversions() {
+        assert_eq!(IntSize::try_from(4), Ok(IntSize::U4));
+        assert!(IntSize::try_from(3).is_err());
+        assert_eq!(usize::from(IntSize::U8), 8);
+        assert_eq!(FloatSize::try_from(8), Ok(FloatSize::U8));
+        assert!(FloatSize::try_from(1).is_err());
+        assert_eq!(usize::from(FloatSize::U4), 4);
+    }
+
+    fn offsets(compound: &CompoundType) -> Vec<usize> {
+        compound.fields.iter().map(|f| f.offset).collect()
+    }
+
+    #[test]
+    pub fn test_compound_from_fields_aligned() {
+        #[allow(dead_code)]
+        #[repr(C)]
+        struct A {
+            a: u8,
+            b: f64,
+            c: i16,
+            d: [u32; 3],
+            e: u8,
+        }
+        let compound = CompoundType::from_fields_aligned(vec![
+            ("a", u8::type_descriptor()),
+            ("b", f64::type_descriptor()),
+            ("c", i16::type_descriptor()),
+            ("d", <[u32; 3]>::type_descriptor()),
+            ("e", u8::type_descriptor()),
+        ]);
+        assert_eq!(
+            offsets(&compound),
+            [
+                mem::offset_of!(A, a),
+                mem::offset_of!(A, b),
+                mem::offset_of!(A, c),
+                mem::offset_of!(A, d),
+                mem::offset_of!(A, e)
+            ]
+        );
+        assert_eq!(compound.size, mem::size_of::<A>());
+        assert_eq!(TD::Compound(compound.clone()).c_alignment(), mem::align_of::<A>());
+        compound.validate().unwrap();
+
+        #[allow(dead_code)]
+        #[repr(C)]
+        struct B {
+            a: FixedAscii<3>,
+            b: VarLenUnicode,
+            c: bool,
+            d: VarLenArray<u16>,
+            e: f32,
+        }
+        let compound = CompoundType::from_fields_aligned(vec![
+            ("a", FixedAscii::<3>::type_descriptor()),
+            ("b", VarLenUnicode::type_descriptor()),
+            ("c", bool::type_descriptor()),
+            ("d", VarLenArray::<u16>::type_descriptor()),
+            ("e", f32::type_descriptor()),
+        ]);
+        assert_eq!(
+            offsets(&compound),
+            [
+                mem::offset_of!(B, a),
+                mem::offset_of!(B, b),
+                mem::offset_of!(B, c),
+                mem::offset_of!(B, d),
+                mem::offset_of!(B, e)
+            ]
+        );
+        assert_eq!(compound.size, mem::size_of::<B>());
+        compound.validate().unwrap();
+    }
+
+    #[test]
+    pub fn test_compound_from_fields_nested() {
+        #[allow(dead_code)]
+        #[repr(C)]
+        struct Inner {
+            x: u16,
+            y: i64,
+            z: u8,
+        }
+        #[allow(dead_code)]
+        #[repr(C)]
+        struct Outer {
+            a: u8,
+            inner: Inner,
+            b: [Inner; 2],
+            c: u32,
+        }
+        let inner = TD::Compound(CompoundType::from_fields_aligned(vec![
+            ("x", u16::type_descriptor()),
+            ("y", i64::type_descriptor()),
+            ("z", u8::type_descriptor()),
+        ]));
+        assert_eq!(inner.size(), mem::size_of::<Inner>());
+        assert_eq!(inner.c_alignment(), mem::align_of::<Inner>());
+        let outer = CompoundType::from_fields_aligned(vec![
+            ("a", u8::type_descriptor()),
+            ("inner", inner.clone()),
+            ("b", TD::FixedArray(Box::new(inner.clone()), 2)),
+            ("c", u32::type_descriptor()),
+        ]);
+        assert_eq!(
+            offsets(&outer),
+            [
+                mem::offset_of!(Outer, a),
+                mem::offset_of!(Outer, inner),
+                mem::offset_of!(Outer, b),
+                mem::offset_of!(Outer, c)
+            ]
+        );
+        assert_eq!(outer.size, mem::size_of::<Outer>());
+        outer.validate().unwrap();
+
+        let packed = CompoundType::from_fields_packed(vec![
+            ("a", u8::type_descriptor()),
+            ("inner", inner),
+            ("c", u32::type_descriptor()),
+        ]);
+        assert_eq!(offsets(&packed), [0, 1, 12]);
+        assert_eq!(packed.size, 16);
+        packed.validate().unwrap();
+    }
+
+    #[test]
+    pub fn test_compound_validate() {
+        let mut compound = CompoundType::from_fields_packed(vec![
+            ("a", u32::type_descriptor()),
+            ("b", u16::type_descriptor()),
+        ]);
+        compound.validate().unwrap();
+        compound.size = 5;
+        assert!(compound.validate().unwrap_err().contains("'b'"));
+        compound.size = 6;
+        compound.fields[1].offset = 2;
+        assert!(compound.validate().unwrap_err().contains("overlap"));
+        compound.fields[1].offset = 4;
+        compound.fields[1].name = "a".into();
+        assert!(compound.validate().unwrap_err().contains("duplicate"));
+        let mut inner = compound.clone();
+        inner.fields[1].name = "b".into();
+        inner.size = 5;
+        let field = TD::FixedArray(Box::new(TD::Compound(inner)), 2);
+        let outer = CompoundType { fields: vec![CompoundField::new("x", field, 0, 0)], size: 10 };
+        assert!(outer.validate().unwrap_err().contains("compound field 'x'"));
+        let empty = CompoundType { fields: vec![], size: 0 };
+        assert_eq!(empty.validate().unwrap_err(), "compound type has no fields");
+    }
+
+    #[test]
+    pub fn test_unit_types() {
+        let unit = TD::unit("Marker");
+        assert!(unit.is_unit() && <() as H5Type>::type_descriptor().is_unit());
+        assert_eq!((unit.size(), unit.c_alignment()), (0, 1));
+        assert_eq!(unit.to_string(), "unit (Marker)");
+        let stored = TD::Opaque(OpaqueType { size: 1, tag: "Marker".into() });
+        assert_eq!(stored.to_string(), "opaque (len 1, tag Marker)");
+        assert_eq!((unit.to_c_repr(), unit.to_packed_repr()), (stored.clone(), stored.clone()));
+        assert!(!stored.is_unit());
+
+        // unit fields take no space in memory and one byte in the file
+        let memory = CompoundType {
+            fields: vec![
+                CompoundField::new("a", u16::type_descriptor(), 0, 0),
+                CompoundField::new("m", unit, 2, 1),
+                CompoundField::new("b", TD::Boolean, 2, 2),
+            ],
+            size: 4,
+        };
+        memory.validate().unwrap();
+        let file = memory.to_c_repr();
+        assert_eq!(file.fields.iter().map(|f| f.offset).collect::<Vec<_>>(), [0, 2, 3]);
+        assert_eq!((file.fields[1].ty.clone(), file.size), (stored, 4));
+    }
+
     // #[test]
     // pub fn test_tuples() {
     //     type T1 = (u16,);
@@ -15,14 +15,19 @@ pub mod dyn_value;
 mod h5type;
 pub mod references;
 mod string;
+#[cfg(feature = "time")]
+pub mod time;
+#[cfg(feature = "uuid")]
+pub mod uuid;
 
 #[cfg(feature = "complex")]
 mod complex;
 
 pub use self::array::VarLenArray;
-pub use self::dyn_value::{DynValue, OwnedDynValue};
+pub use self::dyn_value::{DynOpaque, DynReference, DynValue, OwnedDynValue};
 pub use self::h5type::{
-    CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor,
+    CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, OpaqueType,
+    TypeDescriptor,
 };
 pub use self::references::Reference;
 pub use self::string::{FixedAscii, FixedUnicode, StringError, VarLenAscii, VarLenUnicode};
@@ -1,11 +1,32 @@
 //! Types for references.
+//!
+//! The in-memory layouts of the reference types are fixed by the HDF5 C API, so code that only
+//! moves references around (e.g. format conversion layers) can handle them as plain bytes:
+//!
+//! - `hobj_ref_t` (object references before 1.12) is the file address of the object, a
+//!   native-endian 64-bit integer.
+//! - `hdset_reg_ref_t` (region references before 1.12) is a 12-byte heap id.
+//! - `H5R_ref_t` (references since 1.12) is an opaque 64-byte blob that may hold pointers into
+//!   the library. It is only meaningful within the library session that created it; it is
+//!   converted to a portable form by HDF5 itself when written to a file.
+//!
+//! In all cases a null reference has all bytes set to zero, see [`is_null_reference`].
 
 /// HDF5 reference type sizes (fixed by HDF5 specification)
 pub const HOBJ_REF_SIZE: usize = 8; // haddr_t (64-bit)
 pub const HDSET_REG_REF_SIZE: usize = 12; // haddr_t + 4 bytes
 pub const H5R_REF_SIZE: usize = 64; // H5R_ref_t (1.12+)
 
+/// Returns `true` if the raw bytes of a reference of any kind denote a null reference.
+///
+/// HDF5 uses all-zero bytes for references that don't point anywhere, e.g. elements of
+/// reference datasets that were never written.
+pub fn is_null_reference(bytes: &[u8]) -> bool {
+    bytes.iter().all(|&b| b == 0)
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Reference {
     Object,
     Region,
@@ -21,3 +42,18 @@ impl Reference {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_null_reference() {
+        assert!(is_null_reference(&[0; HOBJ_REF_SIZE]));
+        assert!(is_null_reference(&[0; H5R_REF_SIZE]));
+        let mut bytes = [0; H5R_REF_SIZE];
+        bytes[63] = 1;
+        assert!(!is_null_reference(&bytes));
+        assert!(!is_null_reference(&1_u64.to_ne_bytes()));
+    }
+}
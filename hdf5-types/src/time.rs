@@ -0,0 +1,345 @@
+//! Timestamp encodings.
+//!
+//! HDF5's own `TIME` datatype class is effectively deprecated and not understood by most
+//! readers, so timestamps are stored either as 64-bit signed integers counting nanoseconds
+//! since the Unix epoch, or as fixed-length ASCII ISO-8601 strings in UTC
+//! (e.g. `1969-12-31T23:59:59.5Z`).
+//!
+//! Time types such as [`SystemTime`] have no fixed HDF5 representation (and no stable memory
+//! layout), so they don't implement [`H5Type`](crate::H5Type) directly; instead, they implement
+//! [`Timestamp`] and are converted to and from one of the encodings in [`TimeEncoding`].
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{FixedAscii, IntSize, TypeDescriptor};
+
+/// Maximum length of an ISO-8601 timestamp string.
+pub const ISO8601_LEN: usize = 32;
+
+/// Fixed-length string type used to store ISO-8601 timestamps.
+pub type Iso8601 = FixedAscii<ISO8601_LEN>;
+
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Errors that can occur when encoding or decoding timestamps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TimeError {
+    /// The timestamp can't be represented as nanoseconds since the Unix epoch.
+    OutOfRange,
+    /// The string is not a valid ISO-8601 timestamp.
+    InvalidFormat(String),
+}
+
+impl StdError for TimeError {}
+
+impl fmt::Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TimeError::OutOfRange => {
+                write!(f, "time error: timestamp out of range for nanoseconds since the epoch")
+            }
+            TimeError::InvalidFormat(s) => {
+                write!(f, "time error: invalid ISO-8601 timestamp {s:?}")
+            }
+        }
+    }
+}
+
+/// The way timestamps are stored in a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TimeEncoding {
+    /// 64-bit signed integer, nanoseconds since 1970-01-01T00:00:00Z.
+    #[default]
+    EpochNanos,
+    /// Fixed-length ASCII ISO-8601 string in UTC, see [`Iso8601`].
+    Iso8601,
+}
+
+static TIME_ENCODING: AtomicU8 = AtomicU8::new(0);
+
+impl TimeEncoding {
+    /// Returns the type descriptor of the stored representation.
+    pub fn type_descriptor(self) -> TypeDescriptor {
+        match self {
+            TimeEncoding::EpochNanos => TypeDescriptor::Integer(IntSize::U8),
+            TimeEncoding::Iso8601 => TypeDescriptor::FixedAscii(ISO8601_LEN),
+        }
+    }
+
+    /// Returns the encoding used when creating new timestamp attributes and datasets.
+    pub fn current() -> Self {
+        match TIME_ENCODING.load(Ordering::Relaxed) {
+            0 => TimeEncoding::EpochNanos,
+            _ => TimeEncoding::Iso8601,
+        }
+    }
+
+    /// Sets the encoding used when creating new timestamp attributes and datasets.
+    ///
+    /// This is a process-wide setting; reading accepts either encoding regardless.
+    pub fn set_current(self) {
+        TIME_ENCODING.store(self as u8, Ordering::Relaxed);
+    }
+}
+
+/// A point in time that can be stored using one of the [`TimeEncoding`]s.
+pub trait Timestamp: Sized {
+    /// Returns the number of nanoseconds since the Unix epoch (negative before 1970).
+    fn to_unix_nanos(&self) -> Result<i64, TimeError>;
+
+    /// Creates a timestamp from the number of nanoseconds since the Unix epoch.
+    fn from_unix_nanos(nanos: i64) -> Self;
+
+    /// Formats the timestamp as an ISO-8601 string in UTC.
+    fn to_iso8601(&self) -> Result<Iso8601, TimeError> {
+        format_iso8601(self.to_unix_nanos()?)
+    }
+
+    /// Parses an ISO-8601 timestamp.
+    fn from_iso8601(s: &str) -> Result<Self, TimeError> {
+        parse_iso8601(s).map(Self::from_unix_nanos)
+    }
+}
+
+impl Timestamp for SystemTime {
+    fn to_unix_nanos(&self) -> Result<i64, TimeError> {
+        match self.duration_since(UNIX_EPOCH) {
+            Ok(after) => i64::try_from(after.as_nanos()).map_err(|_| TimeError::OutOfRange),
+            Err(before) => i128::try_from(before.duration().as_nanos())
+                .ok()
+                .and_then(|nanos| i64::try_from(-nanos).ok())
+                .ok_or(TimeError::OutOfRange),
+        }
+    }
+
+    fn from_unix_nanos(nanos: i64) -> Self {
+        let offset = Duration::from_nanos(nanos.unsigned_abs());
+        if nanos >= 0 {
+            UNIX_EPOCH + offset
+        } else {
+            UNIX_EPOCH - offset
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Timestamp for chrono::DateTime<chrono::Utc> {
+    fn to_unix_nanos(&self) -> Result<i64, TimeError> {
+        self.timestamp_nanos_opt().ok_or(TimeError::OutOfRange)
+    }
+
+    fn from_unix_nanos(nanos: i64) -> Self {
+        chrono::DateTime::from_timestamp_nanos(nanos)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Timestamp for chrono::NaiveDateTime {
+    fn to_unix_nanos(&self) -> Result<i64, TimeError> {
+        self.and_utc().to_unix_nanos()
+    }
+
+    fn from_unix_nanos(nanos: i64) -> Self {
+        chrono::DateTime::from_timestamp_nanos(nanos).naive_utc()
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Proleptic Gregorian date for a number of days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Formats nanoseconds since the Unix epoch as an ISO-8601 string in UTC.
+///
+/// The fractional part is omitted for whole seconds and has trailing zeros trimmed otherwise.
+pub fn format_iso8601(nanos: i64) -> Result<Iso8601, TimeError> {
+    let secs = nanos.div_euclid(NANOS_PER_SEC);
+    let frac = nanos.rem_euclid(NANOS_PER_SEC);
+    let (year, month, day) = civil_from_days(secs.div_euclid(SECS_PER_DAY));
+    let tod = secs.rem_euclid(SECS_PER_DAY);
+    let (hour, minute, second) = (tod / 3600, tod / 60 % 60, tod % 60);
+    let mut out = format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}");
+    if frac != 0 {
+        let digits = format!("{frac:09}");
+        out.push('.');
+        out.push_str(digits.trim_end_matches('0'));
+    }
+    out.push('Z');
+    Iso8601::from_ascii(&out).map_err(|_| TimeError::OutOfRange)
+}
+
+/// Parses an ISO-8601 timestamp into nanoseconds since the Unix epoch.
+///
+/// Accepts `YYYY-MM-DDTHH:MM:SS` (or with a space instead of `T`), followed by an optional
+/// fraction of up to nine digits and an optional `Z` or `±HH:MM` offset; timestamps without
+/// an offset are taken to be in UTC.
+pub fn parse_iso8601(s: &str) -> Result<i64, TimeError> {
+    let invalid = || TimeError::InvalidFormat(s.to_owned());
+    let bytes = s.trim().as_bytes();
+    let num = |range: std::ops::Range<usize>| -> Result<i64, TimeError> {
+        let digits = bytes.get(range).ok_or_else(invalid)?;
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return Err(invalid());
+        }
+        Ok(digits.iter().fold(0, |acc, &b| acc * 10 + i64::from(b - b'0')))
+    };
+    let sep = |pos: usize, allowed: &[u8]| -> Result<(), TimeError> {
+        match bytes.get(pos) {
+            Some(b) if allowed.contains(b) => Ok(()),
+            _ => Err(invalid()),
+        }
+    };
+
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    sep(4, b"-")?;
+    sep(7, b"-")?;
+    sep(10, b"T ")?;
+    let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+    sep(13, b":")?;
+    sep(16, b":")?;
+    let days_in_month = match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return Err(invalid()),
+    };
+    if day < 1 || day > days_in_month || hour > 23 || minute > 59 || second > 59 {
+        return Err(invalid());
+    }
+
+    let mut pos = 19;
+    let mut frac = 0;
+    if bytes.get(pos) == Some(&b'.') {
+        let len = bytes[pos + 1..].iter().take_while(|b| b.is_ascii_digit()).count();
+        if len == 0 || len > 9 {
+            return Err(invalid());
+        }
+        frac = num(pos + 1..pos + 1 + len)? * 10_i64.pow((9 - len) as u32);
+        pos += 1 + len;
+    }
+    let offset = match bytes.get(pos) {
+        None => 0,
+        Some(b'Z') if pos + 1 == bytes.len() => 0,
+        Some(&sign @ (b'+' | b'-')) if pos + 6 == bytes.len() => {
+            sep(pos + 3, b":")?;
+            let offset = num(pos + 1..pos + 3)? * 3600 + num(pos + 4..pos + 6)? * 60;
+            if sign == b'+' {
+                offset
+            } else {
+                -offset
+            }
+        }
+        _ => return Err(invalid()),
+    };
+
+    let secs =
+        days_from_civil(year, month, day) * SECS_PER_DAY + hour * 3600 + minute * 60 + second
+            - offset;
+    let nanos = i128::from(secs) * i128::from(NANOS_PER_SEC) + i128::from(frac);
+    i64::try_from(nanos).map_err(|_| TimeError::OutOfRange)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(nanos: i64, expected: &str) {
+        let s = format_iso8601(nanos).unwrap();
+        assert_eq!(s.as_str(), expected);
+        assert_eq!(parse_iso8601(expected).unwrap(), nanos);
+    }
+
+    #[test]
+    fn test_iso8601_roundtrip() {
+        roundtrip(0, "1970-01-01T00:00:00Z");
+        roundtrip(1, "1970-01-01T00:00:00.000000001Z");
+        roundtrip(-1, "1969-12-31T23:59:59.999999999Z");
+        roundtrip(-500_000_000, "1969-12-31T23:59:59.5Z");
+        roundtrip(951_782_400 * NANOS_PER_SEC + 250_000_000, "2000-02-29T00:00:00.25Z");
+        roundtrip(-2_208_988_800 * NANOS_PER_SEC, "1900-01-01T00:00:00Z");
+        roundtrip(i64::MIN, "1677-09-21T00:12:43.145224192Z");
+        roundtrip(i64::MAX, "2262-04-11T23:47:16.854775807Z");
+    }
+
+    #[test]
+    fn test_iso8601_parse() {
+        assert_eq!(parse_iso8601("1970-01-01 00:00:01").unwrap(), NANOS_PER_SEC);
+        assert_eq!(parse_iso8601("1970-01-01T01:00:00+01:00").unwrap(), 0);
+        assert_eq!(parse_iso8601("1969-12-31T23:00:00-01:00").unwrap(), 0);
+        assert_eq!(parse_iso8601(" 1970-01-01T00:00:00.1Z ").unwrap(), 100_000_000);
+        for s in [
+            "",
+            "1970-01-01",
+            "1970-13-01T00:00:00Z",
+            "1970-02-29T00:00:00Z",
+            "1970-01-01T24:00:00Z",
+            "1970-01-01T00:00:00.Z",
+            "1970-01-01T00:00:00.1234567891Z",
+            "1970-01-01T00:00:00ZZ",
+            "1970-01-01T00:00:00+0100",
+        ] {
+            assert!(parse_iso8601(s).is_err(), "{s:?}");
+        }
+        assert_eq!(parse_iso8601("9999-01-01T00:00:00Z"), Err(TimeError::OutOfRange));
+    }
+
+    #[test]
+    fn test_system_time() {
+        for nanos in [0, 1, -1, -1_500_000_000, 1_700_000_000_123_456_789, i64::MIN, i64::MAX] {
+            let t = SystemTime::from_unix_nanos(nanos);
+            assert_eq!(t.to_unix_nanos().unwrap(), nanos);
+            assert_eq!(SystemTime::from_iso8601(t.to_iso8601().unwrap().as_str()).unwrap(), t);
+        }
+        let far = UNIX_EPOCH + Duration::from_secs(300 * 365 * 86_400);
+        assert_eq!(far.to_unix_nanos(), Err(TimeError::OutOfRange));
+    }
+
+    #[test]
+    fn test_encoding() {
+        assert_eq!(TimeEncoding::EpochNanos.type_descriptor().size(), 8);
+        assert_eq!(TimeEncoding::Iso8601.type_descriptor().size(), ISO8601_LEN);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono() {
+        use chrono::{DateTime, NaiveDate, Utc};
+
+        let naive =
+            NaiveDate::from_ymd_opt(1969, 7, 20).unwrap().and_hms_nano_opt(20, 17, 40, 5).unwrap();
+        let nanos = naive.to_unix_nanos().unwrap();
+        assert_eq!(nanos, -14_182_940 * NANOS_PER_SEC + 5);
+        assert_eq!(naive.to_iso8601().unwrap().as_str(), "1969-07-20T20:17:40.000000005Z");
+        assert_eq!(DateTime::<Utc>::from_unix_nanos(nanos), naive.and_utc());
+        assert_eq!(
+            chrono::NaiveDateTime::from_iso8601("1969-07-20T20:17:40.000000005Z"),
+            Ok(naive)
+        );
+        let far = NaiveDate::from_ymd_opt(3000, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+        assert_eq!(far.to_unix_nanos(), Err(TimeError::OutOfRange));
+    }
+}
@@ -0,0 +1,171 @@
+//! UUID encodings.
+//!
+//! [`Uuid`] implements [`H5Type`] as a 16-byte opaque type tagged [`UUID_TAG`], which keeps
+//! the values compact and lets other readers tell them apart from arbitrary bytes. For
+//! readers that don't understand opaque types, UUIDs can be stored as fixed-length ASCII
+//! strings in their hyphenated form (e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`) instead,
+//! either by using [`UuidString`] in place of [`Uuid`] or by choosing the
+//! [`UuidEncoding`] used when creating new attributes and datasets.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+pub use ::uuid::Uuid;
+
+use crate::{FixedAscii, H5Type, OpaqueType, StringError, TypeDescriptor};
+
+/// Tag of the opaque type storing UUIDs.
+pub const UUID_TAG: &str = "UUID";
+
+/// Length of a hyphenated UUID string.
+pub const UUID_STRING_LEN: usize = 36;
+
+unsafe impl H5Type for Uuid {
+    #[inline]
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::Opaque(OpaqueType { size: 16, tag: UUID_TAG.to_owned() })
+    }
+}
+
+/// A UUID stored as a fixed-length ASCII string in its hyphenated form.
+///
+/// Values created from a [`Uuid`] are always valid; values read from a file may not be, so
+/// converting them back to a [`Uuid`] can fail.
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct UuidString(FixedAscii<UUID_STRING_LEN>);
+
+impl UuidString {
+    /// Creates the string representation of `uuid`.
+    pub fn new(uuid: Uuid) -> Self {
+        let mut buf = [0; UUID_STRING_LEN];
+        let s = uuid.hyphenated().encode_lower(&mut buf);
+        // the hyphenated form is always ASCII and exactly `UUID_STRING_LEN` bytes long
+        Self(unsafe { FixedAscii::from_ascii_unchecked(s) })
+    }
+
+    /// Returns the string as stored.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Parses the stored string.
+    pub fn uuid(&self) -> Result<Uuid, ::uuid::Error> {
+        Uuid::parse_str(self.as_str())
+    }
+}
+
+unsafe impl H5Type for UuidString {
+    #[inline]
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::FixedAscii(UUID_STRING_LEN)
+    }
+}
+
+impl From<Uuid> for UuidString {
+    fn from(uuid: Uuid) -> Self {
+        Self::new(uuid)
+    }
+}
+
+impl TryFrom<UuidString> for Uuid {
+    type Error = ::uuid::Error;
+
+    fn try_from(s: UuidString) -> Result<Self, Self::Error> {
+        s.uuid()
+    }
+}
+
+impl FromStr for UuidString {
+    type Err = StringError;
+
+    /// Stores `s` as is, without checking that it is a valid UUID.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FixedAscii::from_ascii(s).map(Self)
+    }
+}
+
+impl fmt::Debug for UuidString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for UuidString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The way UUIDs are stored in a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UuidEncoding {
+    /// 16-byte opaque type tagged [`UUID_TAG`], the type of [`Uuid`].
+    #[default]
+    Opaque,
+    /// Fixed-length ASCII hyphenated string, the type of [`UuidString`].
+    String,
+}
+
+static UUID_ENCODING: AtomicU8 = AtomicU8::new(0);
+
+impl UuidEncoding {
+    /// Returns the type descriptor of the stored representation.
+    pub fn type_descriptor(self) -> TypeDescriptor {
+        match self {
+            UuidEncoding::Opaque => Uuid::type_descriptor(),
+            UuidEncoding::String => UuidString::type_descriptor(),
+        }
+    }
+
+    /// Returns the encoding used when creating new UUID attributes and datasets.
+    pub fn current() -> Self {
+        match UUID_ENCODING.load(Ordering::Relaxed) {
+            0 => UuidEncoding::Opaque,
+            _ => UuidEncoding::String,
+        }
+    }
+
+    /// Sets the encoding used when creating new UUID attributes and datasets.
+    ///
+    /// This is a process-wide setting; reading accepts either encoding regardless.
+    pub fn set_current(self) {
+        UUID_ENCODING.store(self as u8, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use super::*;
+
+    const SAMPLE: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+    #[test]
+    fn test_uuid_layout() {
+        assert_eq!(mem::size_of::<Uuid>(), Uuid::type_descriptor().size());
+        assert_eq!(mem::align_of::<Uuid>(), 1);
+        assert_eq!(mem::size_of::<UuidString>(), UuidString::type_descriptor().size());
+        assert_eq!(mem::size_of::<[Uuid; 3]>(), <[Uuid; 3]>::type_descriptor().size());
+        assert_eq!(UuidEncoding::default().type_descriptor(), Uuid::type_descriptor());
+        assert_eq!(UuidEncoding::String.type_descriptor(), TypeDescriptor::FixedAscii(36));
+    }
+
+    #[test]
+    fn test_uuid_string() {
+        let uuid = Uuid::parse_str(SAMPLE).unwrap();
+        let s = UuidString::from(uuid);
+        assert_eq!(s.as_str(), SAMPLE);
+        assert_eq!(s.to_string(), SAMPLE);
+        assert_eq!(format!("{s:?}"), format!("{SAMPLE:?}"));
+        assert_eq!(Uuid::try_from(s).unwrap(), uuid);
+        assert_eq!(UuidString::new(Uuid::nil()).as_str(), "00000000-0000-0000-0000-000000000000");
+        assert_eq!(UuidString::new(Uuid::max()).uuid().unwrap(), Uuid::max());
+
+        let invalid: UuidString = "not a uuid".parse().unwrap();
+        assert!(invalid.uuid().is_err());
+        assert!(format!("{SAMPLE}0").parse::<UuidString>().is_err());
+    }
+}
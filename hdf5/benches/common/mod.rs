@@ -0,0 +1,95 @@
+//! Helpers shared by the benchmarks.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde_json::{json, Map, Value};
+
+use hdf5_rt as hdf5;
+
+/// Loads the HDF5 library, returning `false` (after printing why) if it is not available.
+pub fn library_available() -> bool {
+    match hdf5::raw::init(None) {
+        Ok(()) => true,
+        Err(err) => {
+            eprintln!("skipping benchmarks, HDF5 library not available: {err}");
+            false
+        }
+    }
+}
+
+/// Creates a new in-memory file so that results are not bound by the disk.
+pub fn new_in_memory_file() -> hdf5::File {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let name =
+        format!("bench-{}-{}.h5", std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed));
+    hdf5::File::create_in_memory(&name).expect("failed to create in-memory file")
+}
+
+/// Directory criterion writes its results to, resolved the same way criterion does by default.
+fn criterion_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("CRITERION_HOME") {
+        PathBuf::from(dir)
+    } else if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+        PathBuf::from(dir).join("criterion")
+    } else {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("target").join("criterion")
+    }
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+fn collect(dir: &Path, groups: &[&str], out: &mut Map<String, Value>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let new = path.join("new");
+        if let (Some(bench), Some(est)) =
+            (read_json(&new.join("benchmark.json")), read_json(&new.join("estimates.json")))
+        {
+            let group = bench["group_id"].as_str().unwrap_or_default();
+            if groups.contains(&group) {
+                let id = bench["full_id"].as_str().unwrap_or_default().to_owned();
+                let result = json!({
+                    "mean_ns": est["mean"]["point_estimate"],
+                    "median_ns": est["median"]["point_estimate"],
+                    "std_dev_ns": est["std_dev"]["point_estimate"],
+                    "throughput": bench["throughput"],
+                });
+                out.insert(id, result);
+            }
+        } else {
+            collect(&path, groups, out);
+        }
+    }
+}
+
+/// Gathers the latest results of the given benchmark groups into a single JSON file,
+/// `<criterion dir>/<name>-summary.json`, so that runs can be diffed easily.
+pub fn dump_results(name: &str, groups: &[&str]) {
+    let dir = criterion_dir();
+    let mut results = Map::new();
+    collect(&dir, groups, &mut results);
+    let summary = json!({
+        "bench": name,
+        "hdf5_version": hdf5::raw::hdf5_version()
+            .map(|v| format!("{}.{}.{}", v.major, v.minor, v.micro)),
+        "results": results,
+    });
+    let path = dir.join(format!("{name}-summary.json"));
+    let written = serde_json::to_vec_pretty(&summary)
+        .map_err(|err| err.to_string())
+        .and_then(|bytes| fs::write(&path, bytes).map_err(|err| err.to_string()));
+    match written {
+        Ok(()) => println!("results written to {}", path.display()),
+        Err(err) => eprintln!("unable to write {}: {err}", path.display()),
+    }
+}
@@ -0,0 +1,39 @@
+//! Pure call overhead of the runtime-loaded FFI layer.
+//!
+//! Run with `cargo bench --bench ffi_overhead`.
+
+mod common;
+
+use std::hint::black_box;
+
+use criterion::Criterion;
+
+use hdf5_rt as hdf5;
+
+const GROUP: &str = "ffi_overhead";
+
+fn bench_ffi_overhead(c: &mut Criterion) {
+    let file = common::new_in_memory_file();
+    let id = file.id();
+    let mut group = c.benchmark_group(GROUP);
+    group.bench_function("H5Iis_valid", |b| {
+        b.iter(|| unsafe { hdf5::sys::h5i::H5Iis_valid(black_box(id)) });
+    });
+    group.bench_function("H5Iis_valid_locked", |b| {
+        b.iter(|| hdf5::raw::sync(|| unsafe { hdf5::sys::h5i::H5Iis_valid(black_box(id)) }));
+    });
+    group.bench_function("Object::is_valid", |b| {
+        b.iter(|| black_box(&file).is_valid());
+    });
+    group.finish();
+}
+
+fn main() {
+    if !common::library_available() {
+        return;
+    }
+    let mut c = Criterion::default().configure_from_args();
+    bench_ffi_overhead(&mut c);
+    c.final_summary();
+    common::dump_results(GROUP, &[GROUP]);
+}
@@ -0,0 +1,153 @@
+//! Dataset and attribute I/O through the high-level API.
+//!
+//! Run with `cargo bench --bench io`. All files are created with the core driver and no
+//! backing store, so results are not bound by the disk.
+
+mod common;
+
+use std::hint::black_box;
+
+use criterion::{BatchSize, Criterion, Throughput};
+use ndarray::{s, Array1, Array2};
+
+use hdf5_rt as hdf5;
+
+const GROUPS: &[&str] =
+    &["small_dataset", "large_read", "chunked_deflate", "attr_churn", "hyperslab"];
+
+/// Create, write and read back 1000 datasets of 1 KiB each.
+fn bench_small_dataset(c: &mut Criterion) {
+    const COUNT: usize = 1000;
+    let data = Array1::from_iter(0..256_u32);
+    let mut group = c.benchmark_group("small_dataset");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(COUNT as u64));
+    group.bench_function("create_write_read_1k", |b| {
+        b.iter_batched(
+            common::new_in_memory_file,
+            |file| {
+                for i in 0..COUNT {
+                    let ds = file.new_dataset::<u32>().shape(256).create(format!("ds{i}").as_str());
+                    let ds = ds.unwrap();
+                    ds.write(&data).unwrap();
+                    black_box(ds.read_1d::<u32>().unwrap());
+                }
+                file
+            },
+            BatchSize::PerIteration,
+        );
+    });
+    group.finish();
+}
+
+/// Read a 256 MiB contiguous dataset in one call.
+fn bench_large_read(c: &mut Criterion) {
+    const LEN: usize = 256 * 1024 * 1024 / 8;
+    let file = common::new_in_memory_file();
+    let ds = file.new_dataset::<f64>().shape(LEN).create("large").unwrap();
+    ds.write(&Array1::from_iter((0..LEN).map(|i| i as f64))).unwrap();
+    let mut group = c.benchmark_group("large_read");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes((LEN * 8) as u64));
+    group.bench_function("contiguous_256mib", |b| {
+        b.iter(|| black_box(ds.read_raw::<f64>().unwrap()));
+    });
+    group.finish();
+}
+
+/// Write and read a 4 MiB chunked dataset, deflate-compressed if the filter is available.
+fn bench_chunked_deflate(c: &mut Criterion) {
+    const LEN: usize = 1024 * 1024;
+    let deflate = hdf5::filters::deflate_available();
+    let data = Array1::from_iter((0..LEN).map(|i| (i % 1000) as i32));
+    let create = |file: &hdf5::File| {
+        let builder = file.new_dataset::<i32>().shape(LEN).chunk(64 * 1024);
+        let builder = if deflate { builder.deflate(4) } else { builder };
+        builder.create("chunked").unwrap()
+    };
+    let mut group = c.benchmark_group("chunked_deflate");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes((LEN * 4) as u64));
+    group.bench_function("write", |b| {
+        b.iter_batched(
+            || {
+                let file = common::new_in_memory_file();
+                let ds = create(&file);
+                (file, ds)
+            },
+            |(file, ds)| {
+                ds.write(&data).unwrap();
+                (file, ds)
+            },
+            BatchSize::PerIteration,
+        );
+    });
+    let file = common::new_in_memory_file();
+    let ds = create(&file);
+    ds.write(&data).unwrap();
+    group.bench_function("read", |b| {
+        b.iter(|| black_box(ds.read_raw::<i32>().unwrap()));
+    });
+    group.finish();
+}
+
+/// Create, read and delete 10k scalar attributes.
+fn bench_attr_churn(c: &mut Criterion) {
+    const COUNT: usize = 10_000;
+    let file = common::new_in_memory_file();
+    let target = file.create_group("target").unwrap();
+    let names: Vec<String> = (0..COUNT).map(|i| format!("attr{i}")).collect();
+    let mut group = c.benchmark_group("attr_churn");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(COUNT as u64));
+    group.bench_function("create_read_delete_10k", |b| {
+        b.iter(|| {
+            for (i, name) in names.iter().enumerate() {
+                let attr = target.new_attr::<i64>().create(name.as_str()).unwrap();
+                attr.write_scalar(&(i as i64)).unwrap();
+            }
+            for name in &names {
+                black_box(target.attr(name).unwrap().read_scalar::<i64>().unwrap());
+            }
+            for name in &names {
+                target.delete_attr(name).unwrap();
+            }
+        });
+    });
+    group.finish();
+}
+
+/// Read row blocks, column blocks and strided selections of a 1024x1024 dataset.
+fn bench_hyperslab(c: &mut Criterion) {
+    let file = common::new_in_memory_file();
+    let data = Array2::from_shape_fn((1024, 1024), |(i, j)| (i * 1024 + j) as f32);
+    let ds = file.new_dataset_builder().chunk((128, 128)).with_data(&data).create("2d").unwrap();
+    let mut group = c.benchmark_group("hyperslab");
+    group.bench_function("rows", |b| {
+        b.iter(|| black_box(ds.read_slice_2d::<f32, _>(s![100..164, ..]).unwrap()));
+    });
+    group.bench_function("columns", |b| {
+        b.iter(|| black_box(ds.read_slice_2d::<f32, _>(s![.., 100..164]).unwrap()));
+    });
+    group.bench_function("strided", |b| {
+        b.iter(|| black_box(ds.read_slice_2d::<f32, _>(s![..;8, ..;8]).unwrap()));
+    });
+    group.bench_function("single_element", |b| {
+        b.iter(|| black_box(ds.read_slice_1d::<f32, _>(s![512, 512..513]).unwrap()));
+    });
+    group.finish();
+}
+
+fn main() {
+    if !common::library_available() {
+        return;
+    }
+    let mut c = Criterion::default().configure_from_args();
+    bench_small_dataset(&mut c);
+    bench_large_read(&mut c);
+    bench_chunked_deflate(&mut c);
+    bench_attr_churn(&mut c);
+    bench_hyperslab(&mut c);
+    c.final_summary();
+    common::dump_results("io", GROUPS);
+}
@@ -0,0 +1,86 @@
+//! Heap allocations of small sliced reads.
+//!
+//! Run with `cargo bench --bench slice_allocs`. The HDF5 library allocates through `malloc`,
+//! which is not counted here, so the counts are the overhead of the Rust layer per read:
+//! dataspaces, selections and the result array. Datasets of rank above 8 do not fit the
+//! inline dimension buffers and show the allocations they would otherwise cost; the difference
+//! is asserted in `tests/test_slice_allocs.rs`.
+
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use ndarray::{s, Array2, ArrayD, IxDyn, SliceInfo, SliceInfoElem};
+
+/// Counts the allocations made through the global allocator.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const READS: u64 = 100_000;
+
+/// Runs `read` [`READS`] times and prints the allocations and time per read.
+fn measure<F: FnMut()>(name: &str, mut read: F) {
+    read();
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..READS {
+        read();
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!(
+        "{name:<10} {:>6.2} allocations/read {:>9.0} ns/read",
+        allocations as f64 / READS as f64,
+        elapsed.as_nanos() as f64 / READS as f64
+    );
+}
+
+fn main() {
+    if !common::library_available() {
+        return;
+    }
+    let file = common::new_in_memory_file();
+    let data = Array2::from_shape_fn((16, 16), |(i, j)| (i * 16 + j) as i32);
+    let ds = file.new_dataset_builder().with_data(&data).create("small").unwrap();
+    measure("block", || {
+        black_box(ds.read_slice_2d::<i32, _>(s![2..6, 3..9]).unwrap());
+    });
+    measure("strided", || {
+        black_box(ds.read_slice_2d::<i32, _>(s![..;3, 1..;2]).unwrap());
+    });
+    measure("element", || {
+        black_box(ds.read_slice_1d::<i32, _>(s![5, 7..8]).unwrap());
+    });
+
+    let rank10 = ArrayD::from_shape_vec(IxDyn(&[2; 10]), (0..1024).collect()).unwrap();
+    let ds = file.new_dataset_builder().with_data(&rank10).create("rank10").unwrap();
+    let mut elems = vec![SliceInfoElem::from(..); 10];
+    elems[0] = SliceInfoElem::Index(1);
+    let slice: SliceInfo<_, IxDyn, IxDyn> = SliceInfo::try_from(elems).unwrap();
+    measure("rank10", || {
+        black_box(ds.read_slice::<i32, _, IxDyn>(slice.clone()).unwrap());
+    });
+}
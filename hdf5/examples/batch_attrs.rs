@@ -0,0 +1,77 @@
+//! Micro-benchmark: writing many scalar attributes with and without `hdf5_rt::batch`, and
+//! with `Location::write_attrs`, and the cost of the locking alone.
+//!
+//! Run with `cargo run --release --example batch_attrs [-- <count>]`.
+
+use std::time::{Duration, Instant};
+
+use hdf5_rt::types::OwnedDynValue;
+use hdf5_rt::{File, Group, Result};
+
+fn write_attrs(group: &Group, count: usize) -> Result<()> {
+    for i in 0..count {
+        let attr = group.new_attr::<i64>().create(format!("attr{i}").as_str())?;
+        attr.write_scalar(&(i as i64))?;
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Unbatched,
+    Batched,
+    WriteAttrs,
+}
+
+fn run(file: &File, name: &str, count: usize, mode: Mode) -> Result<Duration> {
+    let group = file.create_group(name)?;
+    let names: Vec<_> = (0..count).map(|i| format!("attr{i}")).collect();
+    let attrs: Vec<_> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.as_str(), OwnedDynValue::from(i as i64)))
+        .collect();
+    let start = Instant::now();
+    match mode {
+        Mode::Unbatched => write_attrs(&group, count)?,
+        Mode::Batched => hdf5_rt::batch(|| write_attrs(&group, count))?,
+        Mode::WriteAttrs => group.write_attrs(&attrs)?,
+    }
+    Ok(start.elapsed())
+}
+
+/// Times `count` empty calls to `sync()`, which only take the library lock.
+fn time_sync_calls(count: usize) -> Duration {
+    let start = Instant::now();
+    for i in 0..count {
+        hdf5_rt::raw::sync(|| std::hint::black_box(i));
+    }
+    start.elapsed()
+}
+
+fn main() -> Result<()> {
+    let count = std::env::args().nth(1).and_then(|s| s.parse().ok()).unwrap_or(10_000);
+    let dir = tempfile::tempdir().map_err(|e| e.to_string())?;
+    let file = File::create(dir.path().join("batch_attrs.h5"))?;
+
+    let unbatched = run(&file, "unbatched", count, Mode::Unbatched)?;
+    let batched = run(&file, "batched", count, Mode::Batched)?;
+    let write_attrs = run(&file, "write_attrs", count, Mode::WriteAttrs)?;
+
+    println!("{count} scalar attributes");
+    println!("  without batch: {:>10.3?}", unbatched);
+    println!("  with batch:    {:>10.3?}", batched);
+    println!("  write_attrs:   {:>10.3?}", write_attrs);
+    println!("  speedup:       {:>10.2}x", unbatched.as_secs_f64() / batched.as_secs_f64());
+    println!(
+        "  speedup (write_attrs): {:>5.2}x",
+        unbatched.as_secs_f64() / write_attrs.as_secs_f64()
+    );
+
+    let unbatched = time_sync_calls(count);
+    let batched = hdf5_rt::batch(|| time_sync_calls(count));
+    println!("{count} empty sync() calls (locking only)");
+    println!("  without batch: {:>10.3?}", unbatched);
+    println!("  with batch:    {:>10.3?}", batched);
+    Ok(())
+}
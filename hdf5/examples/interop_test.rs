@@ -3,13 +3,21 @@
 //! Usage:
 //!   cargo run --example interop_test --features runtime-loading -- \
 //!     --hdf5-lib /path/to/libhdf5.dylib \
-//!     --mode read|write \
-//!     --file /path/to/test.h5
+//!     --mode read|write|conformance-write|conformance-verify \
+//!     --file /path/to/test.h5 \
+//!     [--cases scalar_attrs,compound] [--json]
+//!
+//! The `read` and `write` modes check a small fixed layout. The conformance modes write or
+//! verify the cases of `hdf5_rt::conformance` (all of them, or the ones named by `--cases`);
+//! with `--json`, they print a single JSON object with the conformance version and the
+//! result of every case instead of text, for the test harnesses of other languages to diff.
 //!
 //! Note: The runtime-loading feature allows specifying the HDF5 library path at runtime,
 //! but currently requires the default `link` feature for compilation.
 
 use clap::{Parser, ValueEnum};
+use hdf5_rt::conformance::{self, CaseStatus};
+use serde_json::json;
 use std::path::PathBuf;
 use std::process::ExitCode;
 
@@ -28,6 +36,14 @@ struct Args {
     /// Path to HDF5 test file
     #[arg(long)]
     file: PathBuf,
+
+    /// Conformance cases to write or verify (all by default)
+    #[arg(long, value_delimiter = ',')]
+    cases: Vec<String>,
+
+    /// Print the results of the conformance modes as JSON
+    #[arg(long)]
+    json: bool,
 }
 
 #[derive(Clone, ValueEnum)]
@@ -36,6 +52,10 @@ enum Mode {
     Read,
     /// Write file for Julia/Python to read
     Write,
+    /// Write the conformance cases
+    ConformanceWrite,
+    /// Verify the conformance cases and report the result of each
+    ConformanceVerify,
 }
 
 fn main() -> ExitCode {
@@ -43,19 +63,24 @@ fn main() -> ExitCode {
 
     // Initialize HDF5 with the provided library path
     let lib_path = args.hdf5_lib.to_string_lossy();
-    if let Err(e) = hdf5_rt::sys::init(Some(&lib_path)) {
+    if let Err(e) = hdf5_rt::raw::init(Some(&lib_path)) {
         eprintln!("Failed to initialize HDF5: {}", e);
         return ExitCode::FAILURE;
     }
 
-    println!("HDF5 library loaded from: {}", lib_path);
+    if !args.json {
+        println!("HDF5 library loaded from: {}", lib_path);
+    }
 
     let result = match args.mode {
         Mode::Read => read_test_file(&args.file),
         Mode::Write => write_test_file(&args.file),
+        Mode::ConformanceWrite => write_conformance(&args),
+        Mode::ConformanceVerify => verify_conformance(&args),
     };
 
     match result {
+        Ok(()) if args.json => ExitCode::SUCCESS,
         Ok(()) => {
             println!("SUCCESS");
             ExitCode::SUCCESS
@@ -68,21 +93,13 @@ fn main() -> ExitCode {
 }
 
 fn read_test_file(path: &PathBuf) -> hdf5_rt::Result<()> {
-    use hdf5_rt::types::{FixedUnicode, VarLenUnicode};
     use hdf5_rt::File;
 
     let file = File::open(path)?;
 
-    // Read scalar attribute from root group
-    // Try variable-length first, fall back to fixed-length
+    // Read scalar attribute from root group (variable-length or fixed-length)
     let root = file.group("/")?;
-    let attr = root.attr("test_attr")?;
-    let attr_str = if let Ok(val) = attr.read_scalar::<VarLenUnicode>() {
-        val.as_str().to_string()
-    } else {
-        let val: FixedUnicode<32> = attr.read_scalar()?;
-        val.as_str().trim_end_matches('\0').to_string()
-    };
+    let attr_str = root.attr("test_attr")?.read_string()?;
     assert_eq!(attr_str, "hello from julia/python", "Attribute mismatch");
     println!("  Attribute 'test_attr': {}", attr_str);
 
@@ -101,8 +118,7 @@ fn read_test_file(path: &PathBuf) -> hdf5_rt::Result<()> {
 
     // Read string dataset (as variable-length unicode strings)
     let ds_str = file.dataset("strings")?;
-    let str_data: Vec<hdf5_rt::types::VarLenUnicode> = ds_str.read_raw()?;
-    let str_values: Vec<&str> = str_data.iter().map(|s| s.as_str()).collect();
+    let str_values = ds_str.read_strings()?;
     assert_eq!(str_values, vec!["foo", "bar", "baz"], "String dataset mismatch");
     println!("  Dataset 'strings': {:?}", str_values);
 
@@ -112,15 +128,13 @@ fn read_test_file(path: &PathBuf) -> hdf5_rt::Result<()> {
 fn write_test_file(path: &PathBuf) -> hdf5_rt::Result<()> {
     use hdf5_rt::types::VarLenUnicode;
     use hdf5_rt::File;
-    use std::str::FromStr;
 
     let file = File::create(path)?;
 
     // Write scalar attribute to root group
     let root = file.group("/")?;
     let attr = root.new_attr::<VarLenUnicode>().create("test_attr")?;
-    let attr_value = VarLenUnicode::from_str("hello from rust").unwrap();
-    attr.write_scalar(&attr_value)?;
+    attr.write_str("hello from rust")?;
 
     // Write 1D integer dataset
     let int_data = vec![10i64, 20, 30, 40, 50];
@@ -131,12 +145,59 @@ fn write_test_file(path: &PathBuf) -> hdf5_rt::Result<()> {
     file.new_dataset::<f64>().shape([2, 3]).create("matrix")?.write(&float_data)?;
 
     // Write string dataset
-    let str_data: Vec<VarLenUnicode> = vec!["rust", "test", "data"]
-        .into_iter()
-        .map(|s| VarLenUnicode::from_str(s).unwrap())
-        .collect();
-    file.new_dataset::<VarLenUnicode>().shape([3]).create("strings")?.write(&str_data)?;
+    file.new_dataset_builder().with_data_str(["rust", "test", "data"]).create("strings")?;
+
+    file.flush()?;
+    Ok(())
+}
+
+fn selected_cases(args: &Args) -> hdf5_rt::Result<Vec<&'static conformance::Case>> {
+    if args.cases.is_empty() {
+        Ok(conformance::cases().iter().collect())
+    } else {
+        conformance::select(&args.cases)
+    }
+}
 
+fn write_conformance(args: &Args) -> hdf5_rt::Result<()> {
+    let cases = selected_cases(args)?;
+    let file = hdf5_rt::File::create(&args.file)?;
+    conformance::write_cases(&file, &cases)?;
     file.flush()?;
+    let names: Vec<_> = cases.iter().map(|case| case.name()).collect();
+    if args.json {
+        let output = json!({ "version": conformance::CONFORMANCE_VERSION, "written": names });
+        println!("{output}");
+    } else {
+        println!(
+            "  Wrote conformance cases (version {}): {}",
+            conformance::CONFORMANCE_VERSION,
+            names.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn verify_conformance(args: &Args) -> hdf5_rt::Result<()> {
+    let cases = selected_cases(args)?;
+    let file = hdf5_rt::File::open(&args.file)?;
+    let results = conformance::verify_cases(&file, &cases);
+    if args.json {
+        let version = conformance::file_version(&file).ok();
+        let results: Vec<_> = results
+            .iter()
+            .map(|r| json!({ "name": r.name, "status": r.status.as_str(), "detail": r.detail }))
+            .collect();
+        let output = json!({ "version": conformance::CONFORMANCE_VERSION, "file_version": version, "results": results });
+        println!("{output}");
+    } else {
+        for result in &results {
+            println!("  {:<16} {} {}", result.name, result.status, result.detail);
+        }
+    }
+    let failed = results.iter().filter(|r| r.status == CaseStatus::Fail).count();
+    if failed > 0 {
+        return Err(format!("{failed} of {} conformance cases failed", results.len()).into());
+    }
     Ok(())
 }
@@ -69,15 +69,20 @@ pub trait ObjectClass: Sized {
 
     fn debug_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // TODO: this can moved out if/when specialization lands in stable
-        h5lock!({
-            if !self.handle().is_valid_user_id() {
-                write!(f, "<HDF5 {}: invalid id>", Self::NAME)
-            } else if let Some(d) = self.short_repr() {
-                write!(f, "<HDF5 {}: {}>", Self::NAME, d)
-            } else {
-                write!(f, "<HDF5 {}>", Self::NAME)
-            }
-        })
+        // the description is built under the lock, but written outside of it
+        match h5lock!(self.handle().is_valid_user_id().then(|| self.short_repr())) {
+            None => write!(f, "<HDF5 {}: invalid id>", Self::NAME),
+            Some(Some(d)) => write!(f, "<HDF5 {}: {}>", Self::NAME, d),
+            Some(None) => write!(f, "<HDF5 {}>", Self::NAME),
+        }
+    }
+
+    fn display_fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match h5lock!(self.handle().is_valid_user_id().then(|| self.short_repr())) {
+            None => write!(f, "<closed HDF5 object>"),
+            Some(Some(d)) => write!(f, "<HDF5 {} {}>", Self::NAME, d),
+            Some(None) => write!(f, "<HDF5 {}>", Self::NAME),
+        }
     }
 }
 
@@ -98,3 +103,14 @@ pub trait ObjectClass: Sized {
 pub unsafe fn from_id<T: ObjectClass>(id: hid_t) -> Result<T> {
     T::from_id(id)
 }
+
+/// Gives up ownership of an object, returning its identifier.
+///
+/// The identifier stays open, the caller becomes responsible for closing it, e.g. by passing it
+/// back to [`from_id`] or by closing it via the C API.
+pub fn into_id<T: ObjectClass>(obj: T) -> hid_t {
+    let id = obj.handle().id();
+    // the reference of the dropped handle is handed over to the caller
+    obj.handle().incref();
+    id
+}
@@ -0,0 +1,528 @@
+//! A versioned suite of conformance cases checking that files written by this crate and by
+//! other HDF5 bindings (e.g. h5py or HDF5.jl) can be read by each other, enabled by the
+//! `conformance` feature.
+//!
+//! Every [`Case`] writes its objects into a group named after it and can verify them again;
+//! [`write_all`] and [`verify_all`] run all [`cases`], and [`write_cases`] and
+//! [`verify_cases`] a subset chosen with [`select`]. The file records the
+//! [`CONFORMANCE_VERSION`] it was written with in a `conformance_version` attribute (`u32`)
+//! of the root group, and cases added in later versions are skipped when verifying older
+//! files. The `interop_test` example runs the suite from the command line, optionally with
+//! JSON output for the test harnesses of other languages.
+//!
+//! # Cases
+//!
+//! | Name | Since | Contents |
+//! |------|-------|----------|
+//! | `scalar_attrs` | 1 | scalar attributes named after their type: `u8` = 8, `u16` = 16, .., `i8` = -8, .., `f32` = 1.5, `f64` = -2.25, `bool` = true, `fixed_ascii` (`FixedAscii<8>`) = `"ascii"`, `fixed_unicode` (`FixedUnicode<16>`) = `"ünïcode"`, `varlen_ascii` = `"varlen ascii"`, `varlen_unicode` = `"varlen ünïcode"` |
+//! | `vlen_strings` | 1 | `ascii` (`VarLenAscii`) = `["", "a", "hello world"]`, `unicode` (`VarLenUnicode`) = `["", "ü", "日本語", "emoji 🎉"]` |
+//! | `fixed_strings` | 1 | `ascii` (`FixedAscii<8>`) = `["", "abc", "12345678"]`, `unicode` (`FixedUnicode<16>`) = `["", "ü", "日本語"]` |
+//! | `compound` | 1 | `records` of `{id: i32, x: f64, name: VarLenUnicode}` = `[{1, 0.5, "one"}, {2, -1.25, "two"}, {3, 1e10, "trois"}]` |
+//! | `references` | 1 | `a` (`i32`) = `[1, 2, 3]`, `b` (`f64`) = `[4.0]`, `refs` (`ObjectReference1`) = `[a, b, a]` |
+//! | `layouts` | 1 | `f64` datasets with element `k` = `k / 2`: `contiguous` and `compact` of shape `[2, 3]`, `chunked` of shape `[4, 6]` with chunks `[2, 3]` |
+//! | `filters` | 1 | `i32` datasets of `0..100` with chunks of 10: `shuffle_fletcher32`, and `deflate` (level 4) if deflate is available |
+//! | `empty_datasets` | 1 | `empty_1d` (`f64`, shape `[0]`), `empty_2d` (`i32`, shape `[3, 0]`), `scalar` (`f64`) = 42.0 |
+//! | `unicode_names` | 1 | group `grüße` with dataset `данные` (`i32`) = `[1, 2, 3]` with attribute `名前` (`VarLenUnicode`) = `"値"` |
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::conformance::{self, CaseStatus};
+//! use hdf5_rt::File;
+//!
+//! conformance::write_all(&File::create("conformance.h5")?)?;
+//! for result in conformance::verify_all(&File::open("conformance.h5")?) {
+//!     assert_eq!(result.status, CaseStatus::Pass, "{}: {}", result.name, result.detail);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Debug, Display};
+use std::mem;
+
+use hdf5_types::{
+    CompoundField, CompoundType, FixedAscii, FixedUnicode, TypeDescriptor, VarLenAscii,
+    VarLenUnicode,
+};
+
+use crate::filters::deflate_available;
+use crate::hl::plist::dataset_create::Layout;
+use crate::internal_prelude::*;
+use crate::{ObjectReference1, ReferencedObject};
+
+/// The version of the case list; cases record the version they were added in.
+pub const CONFORMANCE_VERSION: u32 = 1;
+
+/// The name of the root group attribute holding the version a file was written with.
+pub const VERSION_ATTR: &str = "conformance_version";
+
+/// The outcome of verifying a conformance case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CaseStatus {
+    /// The objects of the case have the expected types, shapes and values.
+    Pass,
+    /// The objects of the case are missing or differ from what is expected.
+    Fail,
+    /// The case was added after the version the file was written with.
+    Skip,
+}
+
+impl CaseStatus {
+    /// Returns the lowercase name of the status, as used in machine-readable output.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail => "fail",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+impl Display for CaseStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The result of verifying one conformance case.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CaseResult {
+    /// The name of the case.
+    pub name: String,
+    /// Whether the case passed.
+    pub status: CaseStatus,
+    /// Why the case failed or was skipped; empty if it passed.
+    pub detail: String,
+}
+
+/// A named conformance case; see the [module documentation](self) for the list.
+#[derive(Clone, Copy)]
+pub struct Case {
+    name: &'static str,
+    since: u32,
+    write: fn(&Group) -> Result<()>,
+    verify: fn(&Group) -> Result<()>,
+}
+
+impl Debug for Case {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Case").field("name", &self.name).field("since", &self.since).finish()
+    }
+}
+
+impl Case {
+    /// Returns the name of the case, which is also the name of its group.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the version of the case list the case was added in.
+    pub fn since(&self) -> u32 {
+        self.since
+    }
+
+    /// Creates the group of the case in `file` and writes its objects.
+    pub fn write(&self, file: &File) -> Result<()> {
+        file.create_group(self.name)
+            .and_then(|group| (self.write)(&group))
+            .with_context(|| format!("writing conformance case '{}'", self.name))
+    }
+
+    /// Verifies the objects of the case in a file written with the given version.
+    pub fn verify(&self, file: &File, version: u32) -> CaseResult {
+        let (status, detail) = if self.since > version {
+            let detail = format!("added in version {}, file has version {version}", self.since);
+            (CaseStatus::Skip, detail)
+        } else {
+            match file.group(self.name).and_then(|group| (self.verify)(&group)) {
+                Ok(()) => (CaseStatus::Pass, String::new()),
+                Err(err) => (CaseStatus::Fail, err.to_string()),
+            }
+        };
+        CaseResult { name: self.name.into(), status, detail }
+    }
+}
+
+static CASES: &[Case] = &[
+    Case { name: "scalar_attrs", since: 1, write: write_scalar_attrs, verify: verify_scalar_attrs },
+    Case { name: "vlen_strings", since: 1, write: write_vlen_strings, verify: verify_vlen_strings },
+    Case {
+        name: "fixed_strings",
+        since: 1,
+        write: write_fixed_strings,
+        verify: verify_fixed_strings,
+    },
+    Case { name: "compound", since: 1, write: write_compound, verify: verify_compound },
+    Case { name: "references", since: 1, write: write_references, verify: verify_references },
+    Case { name: "layouts", since: 1, write: write_layouts, verify: verify_layouts },
+    Case { name: "filters", since: 1, write: write_filters, verify: verify_filters },
+    Case { name: "empty_datasets", since: 1, write: write_empty, verify: verify_empty },
+    Case {
+        name: "unicode_names",
+        since: 1,
+        write: write_unicode_names,
+        verify: verify_unicode_names,
+    },
+];
+
+/// Returns all conformance cases, in the order they are written and verified.
+pub fn cases() -> &'static [Case] {
+    CASES
+}
+
+/// Returns the cases with the given names, in the order of the names.
+pub fn select<S: AsRef<str>>(names: &[S]) -> Result<Vec<&'static Case>> {
+    names
+        .iter()
+        .map(|name| {
+            let name = name.as_ref();
+            CASES.iter().find(|case| case.name == name).ok_or_else(|| {
+                let known: Vec<_> = CASES.iter().map(|case| case.name).collect();
+                format!("unknown conformance case '{name}' (known: {})", known.join(", ")).into()
+            })
+        })
+        .collect()
+}
+
+/// Writes all cases into `file`; see [`write_cases`].
+pub fn write_all(file: &File) -> Result<()> {
+    write_cases(file, &CASES.iter().collect::<Vec<_>>())
+}
+
+/// Writes the given cases into `file` and records the [`CONFORMANCE_VERSION`].
+pub fn write_cases(file: &File, cases: &[&Case]) -> Result<()> {
+    if !file.attr_names()?.iter().any(|name| name == VERSION_ATTR) {
+        file.new_attr::<u32>().create(VERSION_ATTR)?.write_scalar(&CONFORMANCE_VERSION)?;
+    }
+    cases.iter().try_for_each(|case| case.write(file))
+}
+
+/// Verifies all cases in `file`; see [`verify_cases`].
+pub fn verify_all(file: &File) -> Vec<CaseResult> {
+    verify_cases(file, &CASES.iter().collect::<Vec<_>>())
+}
+
+/// Verifies the given cases in `file`, skipping the ones added after the version the file
+/// was written with.
+///
+/// If the version cannot be read, all cases fail with the reason.
+pub fn verify_cases(file: &File, cases: &[&Case]) -> Vec<CaseResult> {
+    match file_version(file) {
+        Ok(version) => cases.iter().map(|case| case.verify(file, version)).collect(),
+        Err(err) => cases
+            .iter()
+            .map(|case| CaseResult {
+                name: case.name.into(),
+                status: CaseStatus::Fail,
+                detail: err.to_string(),
+            })
+            .collect(),
+    }
+}
+
+/// Returns the version of the case list `file` was written with.
+pub fn file_version(file: &File) -> Result<u32> {
+    file.attr(VERSION_ATTR)
+        .and_then(|attr| attr.read_scalar::<u32>())
+        .with_context(|| format!("reading the '{VERSION_ATTR}' attribute"))
+}
+
+fn check_attr<T: H5Type + PartialEq + Debug>(
+    loc: &Location,
+    name: &str,
+    expected: &T,
+) -> Result<()> {
+    let value = loc.attr(name)?.read_scalar::<T>()?;
+    ensure!(&value == expected, "attribute '{}': expected {:?}, found {:?}", name, expected, value);
+    Ok(())
+}
+
+fn check_dataset<T: H5Type + PartialEq + Debug>(
+    group: &Group,
+    name: &str,
+    shape: &[usize],
+    expected: &[T],
+) -> Result<Dataset> {
+    let ds = group.dataset(name)?;
+    let path = ds.name();
+    let found = ds.shape()?;
+    ensure!(found == shape, "dataset '{}': expected shape {:?}, found {:?}", path, shape, found);
+    let values = ds.read_raw::<T>()?;
+    ensure!(
+        values.len() == expected.len(),
+        "dataset '{}': expected {} elements, found {}",
+        path,
+        expected.len(),
+        values.len()
+    );
+    if let Some(i) = values.iter().zip(expected).position(|(value, expected)| value != expected) {
+        fail!(
+            "dataset '{}' element {}: expected {:?}, found {:?}",
+            path,
+            i,
+            expected[i],
+            values[i]
+        );
+    }
+    Ok(ds)
+}
+
+fn ascii<const N: usize>(s: &str) -> Result<FixedAscii<N>> {
+    FixedAscii::from_ascii(s).map_err(|err| err.to_string().into())
+}
+
+fn unicode<T: std::str::FromStr<Err = hdf5_types::StringError>>(s: &str) -> Result<T> {
+    s.parse().map_err(|err: hdf5_types::StringError| err.to_string().into())
+}
+
+macro_rules! numeric_attrs {
+    ($m:ident) => {
+        $m!(u8: 8, u16: 16, u32: 32, u64: 64, i8: -8, i16: -16, i32: -32, i64: -64, f32: 1.5, f64: -2.25)
+    };
+}
+
+fn string_attrs() -> Result<(FixedAscii<8>, FixedUnicode<16>, VarLenAscii, VarLenUnicode)> {
+    let varlen_ascii = VarLenAscii::from_ascii("varlen ascii").map_err(|err| err.to_string())?;
+    Ok((ascii("ascii")?, unicode("ünïcode")?, varlen_ascii, unicode("varlen ünïcode")?))
+}
+
+fn write_scalar_attrs(group: &Group) -> Result<()> {
+    macro_rules! write {
+        ($($ty:ident: $value:expr),+) => {$(
+            group.new_attr::<$ty>().create(stringify!($ty))?.write_scalar(&($value as $ty))?;
+        )+};
+    }
+    numeric_attrs!(write);
+    group.new_attr::<bool>().create("bool")?.write_scalar(&true)?;
+    let (fixed_ascii, fixed_unicode, varlen_ascii, varlen_unicode) = string_attrs()?;
+    group.new_attr::<FixedAscii<8>>().create("fixed_ascii")?.write_scalar(&fixed_ascii)?;
+    group.new_attr::<FixedUnicode<16>>().create("fixed_unicode")?.write_scalar(&fixed_unicode)?;
+    group.new_attr::<VarLenAscii>().create("varlen_ascii")?.write_scalar(&varlen_ascii)?;
+    group.new_attr::<VarLenUnicode>().create("varlen_unicode")?.write_scalar(&varlen_unicode)
+}
+
+fn verify_scalar_attrs(group: &Group) -> Result<()> {
+    macro_rules! verify {
+        ($($ty:ident: $value:expr),+) => {$(
+            check_attr::<$ty>(group, stringify!($ty), &($value as $ty))?;
+        )+};
+    }
+    numeric_attrs!(verify);
+    check_attr(group, "bool", &true)?;
+    let (fixed_ascii, fixed_unicode, varlen_ascii, varlen_unicode) = string_attrs()?;
+    check_attr(group, "fixed_ascii", &fixed_ascii)?;
+    check_attr(group, "fixed_unicode", &fixed_unicode)?;
+    check_attr(group, "varlen_ascii", &varlen_ascii)?;
+    check_attr(group, "varlen_unicode", &varlen_unicode)
+}
+
+fn vlen_strings() -> Result<(Vec<VarLenAscii>, Vec<VarLenUnicode>)> {
+    let ascii = ["", "a", "hello world"]
+        .iter()
+        .map(|s| VarLenAscii::from_ascii(s).map_err(|err| err.to_string().into()))
+        .collect::<Result<_>>()?;
+    let unicode =
+        ["", "ü", "日本語", "emoji 🎉"].iter().map(|s| unicode(s)).collect::<Result<_>>()?;
+    Ok((ascii, unicode))
+}
+
+fn write_vlen_strings(group: &Group) -> Result<()> {
+    let (ascii, unicode) = vlen_strings()?;
+    group.new_dataset_builder().with_data(&ascii).create("ascii")?;
+    group.new_dataset_builder().with_data(&unicode).create("unicode")?;
+    Ok(())
+}
+
+fn verify_vlen_strings(group: &Group) -> Result<()> {
+    let (ascii, unicode) = vlen_strings()?;
+    check_dataset(group, "ascii", &[ascii.len()], &ascii)?;
+    check_dataset(group, "unicode", &[unicode.len()], &unicode)?;
+    Ok(())
+}
+
+fn fixed_strings() -> Result<(Vec<FixedAscii<8>>, Vec<FixedUnicode<16>>)> {
+    let ascii = ["", "abc", "12345678"].iter().map(|s| ascii(s)).collect::<Result<_>>()?;
+    let unicode = ["", "ü", "日本語"].iter().map(|s| unicode(s)).collect::<Result<_>>()?;
+    Ok((ascii, unicode))
+}
+
+fn write_fixed_strings(group: &Group) -> Result<()> {
+    let (ascii, unicode) = fixed_strings()?;
+    group.new_dataset_builder().with_data(&ascii).create("ascii")?;
+    group.new_dataset_builder().with_data(&unicode).create("unicode")?;
+    Ok(())
+}
+
+fn verify_fixed_strings(group: &Group) -> Result<()> {
+    let (ascii, unicode) = fixed_strings()?;
+    check_dataset(group, "ascii", &[ascii.len()], &ascii)?;
+    check_dataset(group, "unicode", &[unicode.len()], &unicode)?;
+    Ok(())
+}
+
+/// The element type of the `compound/records` dataset.
+#[derive(Clone, Debug, PartialEq)]
+#[repr(C)]
+struct Record {
+    id: i32,
+    x: f64,
+    name: VarLenUnicode,
+}
+
+unsafe impl H5Type for Record {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<i32>("id", mem::offset_of!(Record, id), 0),
+                CompoundField::typed::<f64>("x", mem::offset_of!(Record, x), 1),
+                CompoundField::typed::<VarLenUnicode>("name", mem::offset_of!(Record, name), 2),
+            ],
+            size: mem::size_of::<Record>(),
+        })
+    }
+}
+
+fn records() -> Result<Vec<Record>> {
+    [(1, 0.5, "one"), (2, -1.25, "two"), (3, 1e10, "trois")]
+        .iter()
+        .map(|&(id, x, name)| Ok(Record { id, x, name: unicode(name)? }))
+        .collect()
+}
+
+fn write_compound(group: &Group) -> Result<()> {
+    group.new_dataset_builder().with_data(&records()?).create("records")?;
+    Ok(())
+}
+
+fn verify_compound(group: &Group) -> Result<()> {
+    let records = records()?;
+    check_dataset(group, "records", &[records.len()], &records)?;
+    Ok(())
+}
+
+const REFERENCE_TARGETS: [&str; 3] = ["a", "b", "a"];
+
+fn write_references(group: &Group) -> Result<()> {
+    group.new_dataset_builder().with_data(&[1_i32, 2, 3]).create("a")?;
+    group.new_dataset_builder().with_data(&[4.0_f64]).create("b")?;
+    let refs = REFERENCE_TARGETS
+        .iter()
+        .map(|name| group.reference::<ObjectReference1>(name))
+        .collect::<Result<Vec<_>>>()?;
+    group.new_dataset_builder().with_data(&refs).create("refs")?;
+    Ok(())
+}
+
+fn verify_references(group: &Group) -> Result<()> {
+    check_dataset(group, "a", &[3], &[1_i32, 2, 3])?;
+    check_dataset(group, "b", &[1], &[4.0_f64])?;
+    let refs = group.dataset("refs")?.read_raw::<ObjectReference1>()?;
+    ensure!(refs.len() == REFERENCE_TARGETS.len(), "expected 3 references, found {}", refs.len());
+    for (i, (reference, target)) in refs.iter().zip(REFERENCE_TARGETS).enumerate() {
+        let expected = format!("{}/{target}", group.name());
+        match group.dereference(reference)? {
+            ReferencedObject::Dataset(ds) if ds.name() == expected => {}
+            ReferencedObject::Dataset(ds) => {
+                fail!("reference {}: expected '{}', found '{}'", i, expected, ds.name())
+            }
+            _ => fail!("reference {}: expected dataset '{}'", i, expected),
+        }
+    }
+    Ok(())
+}
+
+fn halves(n: usize) -> Vec<f64> {
+    (0..n).map(|k| k as f64 / 2.0).collect()
+}
+
+fn write_layouts(group: &Group) -> Result<()> {
+    let data = halves(6);
+    let builder = group.new_dataset::<f64>().shape((2, 3));
+    builder.clone().layout(Layout::Contiguous).create("contiguous")?.write_raw(&data)?;
+    builder.layout(Layout::Compact).create("compact")?.write_raw(&data)?;
+    let chunked = group.new_dataset::<f64>().shape((4, 6)).chunk((2, 3)).create("chunked")?;
+    chunked.write_raw(&halves(24))
+}
+
+fn verify_layouts(group: &Group) -> Result<()> {
+    let cases = [
+        ("contiguous", Layout::Contiguous, [2, 3]),
+        ("compact", Layout::Compact, [2, 3]),
+        ("chunked", Layout::Chunked, [4, 6]),
+    ];
+    for (name, layout, shape) in cases {
+        let ds = check_dataset(group, name, &shape, &halves(shape[0] * shape[1]))?;
+        ensure!(
+            ds.layout() == layout,
+            "dataset '{}': expected layout {:?}, found {:?}",
+            ds.name(),
+            layout,
+            ds.layout()
+        );
+    }
+    let chunk = group.dataset("chunked")?.chunk();
+    ensure!(
+        chunk == Some(vec![2, 3]),
+        "dataset 'chunked': expected chunks [2, 3], found {:?}",
+        chunk
+    );
+    Ok(())
+}
+
+fn write_filters(group: &Group) -> Result<()> {
+    let data: Vec<i32> = (0..100).collect();
+    let builder = group.new_dataset_builder().with_data(&data).chunk(10);
+    builder.clone().shuffle().fletcher32().create("shuffle_fletcher32")?;
+    if deflate_available() {
+        builder.deflate(4).create("deflate")?;
+    }
+    Ok(())
+}
+
+fn verify_filters(group: &Group) -> Result<()> {
+    let data: Vec<i32> = (0..100).collect();
+    let mut names = vec!["shuffle_fletcher32"];
+    if group.link_exists("deflate") {
+        names.push("deflate");
+    }
+    for name in names {
+        let ds = check_dataset(group, name, &[100], &data)?;
+        ensure!(!ds.filters().is_empty(), "dataset '{}' has no filters", ds.name());
+    }
+    Ok(())
+}
+
+fn write_empty(group: &Group) -> Result<()> {
+    group.new_dataset::<f64>().shape(0).create("empty_1d")?;
+    group.new_dataset::<i32>().shape((3, 0)).create("empty_2d")?;
+    group.new_dataset::<f64>().create("scalar")?.write_scalar(&42.0)
+}
+
+fn verify_empty(group: &Group) -> Result<()> {
+    check_dataset::<f64>(group, "empty_1d", &[0], &[])?;
+    check_dataset::<i32>(group, "empty_2d", &[3, 0], &[])?;
+    let ds = group.dataset("scalar")?;
+    ensure!(ds.is_scalar(), "dataset '{}' is not scalar (shape {:?})", ds.name(), ds.shape());
+    let value = ds.read_scalar::<f64>()?;
+    ensure!(value == 42.0, "dataset '{}': expected 42.0, found {}", ds.name(), value);
+    Ok(())
+}
+
+fn write_unicode_names(group: &Group) -> Result<()> {
+    let ds = group
+        .create_group("grüße")?
+        .new_dataset_builder()
+        .with_data(&[1_i32, 2, 3])
+        .create("данные")?;
+    ds.new_attr::<VarLenUnicode>().create("名前")?.write_scalar(&unicode::<VarLenUnicode>("値")?)
+}
+
+fn verify_unicode_names(group: &Group) -> Result<()> {
+    let ds = check_dataset(&group.group("grüße")?, "данные", &[3], &[1_i32, 2, 3])?;
+    check_attr(&ds, "名前", &unicode::<VarLenUnicode>("値")?)
+}
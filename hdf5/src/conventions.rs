@@ -0,0 +1,132 @@
+//! Names and definitions of the conventions this crate maintains in files.
+//!
+//! These are plain constants so that implementations in other languages can follow the
+//! same conventions and interoperate with files written by this crate.
+
+/// Name of the scalar `u64` attribute holding the content checksum of a dataset.
+pub const CHECKSUM_ATTR: &str = "_checksum_xxh3";
+
+/// Algorithm of the checksum stored in [`CHECKSUM_ATTR`]: 64-bit XXH3 with seed 0
+/// (`XXH3_64bits`), computed over all elements of the dataset in row-major order, each
+/// encoded with the dataset's file datatype in little-endian byte order. Datasets of
+/// variable-length or reference types cannot be checksummed.
+pub const CHECKSUM_ALGORITHM: &str = "xxh3_64";
+
+/// Name of the scalar `u64` attribute marking a group written from a list of values by
+/// [`to_group`](crate::to_group) and holding the list length. The members of such a group
+/// are named with indices zero-padded to the width of the largest index (`"00"` to `"11"`
+/// for a list of 12 values).
+pub const LIST_LEN_ATTR: &str = "_list_len";
+
+/// Default path of the group holding named views (slices) of datasets, written by
+/// [`Dataset::save_view`](crate::Dataset::save_view). Each view is a subgroup named after the
+/// view and holding the [`VIEW_TARGET_ATTR`], [`VIEW_START_ATTR`], [`VIEW_COUNT_ATTR`] and
+/// [`VIEW_STRIDE_ATTR`] attributes, and optionally [`VIEW_REGION_ATTR`].
+pub const VIEWS_GROUP: &str = "views";
+
+/// Name of the variable-length string attribute holding the absolute path of the dataset a
+/// view refers to.
+pub const VIEW_TARGET_ATTR: &str = "target";
+
+/// Name of the 1-D `u64` attribute holding the first selected index along each axis of a view.
+pub const VIEW_START_ATTR: &str = "start";
+
+/// Name of the 1-D `u64` attribute holding the number of selected indices along each axis of
+/// a view.
+pub const VIEW_COUNT_ATTR: &str = "count";
+
+/// Name of the 1-D `u64` attribute holding the distance between selected indices along each
+/// axis of a view.
+pub const VIEW_STRIDE_ATTR: &str = "stride";
+
+/// Name of the optional scalar attribute holding a standard (HDF5 1.12+) region reference to
+/// the same selection as a view's other attributes. Readers use it when present.
+pub const VIEW_REGION_ATTR: &str = "region";
+
+/// Name of the scalar variable-length UTF-8 string attribute holding the physical units of a
+/// dataset, as used by CF-compliant tools such as xarray.
+pub const UNITS_ATTR: &str = "units";
+
+/// Name of the 1-D variable-length UTF-8 string attribute holding one label per axis of a
+/// dataset, with empty strings for unlabeled axes. This is the attribute written by the HDF5
+/// dimension scales API (`H5DSset_label`), which h5py exposes as `dataset.dims[i].label`.
+pub const DIMENSION_LABELS_ATTR: &str = "DIMENSION_LABELS";
+
+/// Name of the scalar variable-length UTF-8 string attribute holding the NeXus base class of a
+/// group, e.g. `NXentry` or `NXdata`.
+pub const NX_CLASS_ATTR: &str = "NX_class";
+
+/// Separator between the name of a versioned dataset and its version number. Version `n` of
+/// the dataset `name` in a group is the member `"{name}@{n}"`, with `n` written in decimal
+/// without leading zeros and starting at 1, and the member `name` is a soft link to the
+/// latest version (see [`versioning`](crate::versioning)).
+pub const VERSION_SEPARATOR: &str = "@";
+
+/// Suffix of the name of the scalar `u32` group attribute holding the number of versions ever
+/// written of a versioned dataset, i.e. the latest version number, which is not decreased when
+/// old versions are pruned. For the dataset `name`, the attribute is named `"{name}@count"`.
+pub const VERSION_COUNT_ATTR_SUFFIX: &str = "@count";
+
+/// Name of the dataset holding the rows of a shard in a shard file. Shard `i` of the sharded
+/// dataset `name` is the file `"{name}.{i}.h5"`, with `i` written in decimal without leading
+/// zeros (see [`sharding`](crate::sharding)).
+pub const SHARD_DATASET: &str = "data";
+
+/// Name of the scalar `u32` attribute of a [`SHARD_DATASET`] holding the index of the shard,
+/// from 0 to the number of shards minus one.
+pub const SHARD_INDEX_ATTR: &str = "_shard_index";
+
+/// Name of the scalar `u32` attribute of a [`SHARD_DATASET`] holding the total number of
+/// shards of the sharded dataset.
+pub const SHARD_COUNT_ATTR: &str = "_shard_count";
+
+/// Name of the 1-D `u64` attribute of a [`SHARD_DATASET`] holding the first row and the end
+/// (exclusive) of the rows of the sharded dataset stored in the shard. The shard dataset has
+/// exactly that many rows along its first axis.
+pub const SHARD_ROWS_ATTR: &str = "_shard_rows";
+
+/// Name of the optional scalar `u64` attribute, on a dataset or on the root group of its file,
+/// holding the generation of a file written under SWMR. A writer that recreates the file
+/// stores a different value, so that [`PollingReader`](crate::swmr::PollingReader) can tell
+/// the new file from the old one.
+pub const SWMR_GENERATION_ATTR: &str = "_swmr_generation";
+
+/// Name of the root group attribute holding the provenance of a NetCDF-4 file, written by
+/// the netCDF-C library. Its presence identifies the file as NetCDF-4 (see
+/// [`netcdf_compat`](crate::netcdf_compat)).
+pub const NC_PROPERTIES_ATTR: &str = "_NCProperties";
+
+/// Name of the attribute holding the dimension scales attached to each axis of a dataset, as
+/// written by the HDF5 dimension scales API (`H5DSattach_scale`): a 1-D attribute with one
+/// element per axis, each a variable-length array of object references to the scales.
+pub const DIMENSION_LIST_ATTR: &str = "DIMENSION_LIST";
+
+/// Name of the attribute of a dimension scale listing the datasets it is attached to, as
+/// written by the HDF5 dimension scales API: a 1-D attribute of compounds of an object
+/// reference to the dataset and the index of the axis.
+pub const REFERENCE_LIST_ATTR: &str = "REFERENCE_LIST";
+
+/// Name of the string attribute marking a dataset as a dimension scale when it holds
+/// [`DIMENSION_SCALE_CLASS`].
+pub const CLASS_ATTR: &str = "CLASS";
+
+/// Value of the [`CLASS_ATTR`] attribute of dimension scales.
+pub const DIMENSION_SCALE_CLASS: &str = "DIMENSION_SCALE";
+
+/// Name of the string attribute holding the name of a dimension scale.
+pub const DIMENSION_SCALE_NAME_ATTR: &str = "NAME";
+
+/// Prefix of the [`DIMENSION_SCALE_NAME_ATTR`] of the datasets the netCDF-C library creates
+/// for dimensions without a coordinate variable, followed by padding and the dimension length.
+/// Such datasets hold no data.
+pub const NETCDF_DIMENSION_NAME_PREFIX: &str =
+    "This is a netCDF dimension but not a netCDF variable.";
+
+/// Prefix of the hidden root group members used by [`staging`](crate::staging) to stage the
+/// contents of a checkpoint before it is linked into place. The prefix is followed by a
+/// unique hexadecimal identifier.
+pub const STAGING_PREFIX: &str = ".staging-";
+
+/// Suffix appended to the name of a staging group for the previous group at the final path
+/// of a checkpoint while it is replaced; it is deleted once the commit completes.
+pub const STAGING_REPLACED_SUFFIX: &str = "-replaced";
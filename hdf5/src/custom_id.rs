@@ -0,0 +1,178 @@
+//! Identifiers of user-defined types, for attaching foreign objects to HDF5 identifiers.
+//!
+//! HDF5 can hand out identifiers for objects it knows nothing about, so that callbacks which
+//! only receive an `hid_t` (e.g. from another language binding) can recover the object.
+//! [`CustomIdType`] registers such an identifier type for values of a Rust type:
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::custom_id::CustomIdType;
+//!
+//! let mut states = CustomIdType::<String>::new()?;
+//! let id = states.register(Box::new("state".to_owned()))?;
+//! assert_eq!(states.get(id).map(String::as_str), Some("state"));
+//! assert_eq!(*states.unregister(id).unwrap(), "state");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Dropping the type drops the values still registered with it. Values still registered
+//! when the library is shut down are leaked, since shared references to them may still be
+//! alive.
+
+use std::cell::Cell;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::internal_prelude::*;
+use crate::sys::h5i::{
+    H5I_type_raw_t, H5Idestroy_type, H5Iobject_verify, H5Iregister, H5Iregister_type,
+    H5Iremove_verify,
+};
+
+/// Number of hash buckets requested when registering a type (ignored since HDF5 1.12).
+const HASH_SIZE: usize = 64;
+
+thread_local! {
+    /// Set while this thread destroys an identifier type, so that the free callbacks called
+    /// by the library drop the values instead of leaking them.
+    static DESTROYING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Free callback of the identifier types, called by the library with the lock held.
+unsafe extern "C" fn free_value<T>(obj: *mut c_void, _request: *mut *mut c_void) -> herr_t {
+    if obj.is_null() || !DESTROYING.with(Cell::get) {
+        return 0;
+    }
+    let value = unsafe { Box::from_raw(obj.cast::<T>()) };
+    // unwinding into the library would abort the process
+    match panic::catch_unwind(AssertUnwindSafe(|| drop(value))) {
+        Ok(()) => 0,
+        Err(_) => -1,
+    }
+}
+
+/// A user-defined identifier type holding boxed values of type `T`.
+///
+/// Registering a value hands its ownership to the library until it is
+/// [unregistered](Self::unregister) or the type is dropped. Lookups only need a shared
+/// reference, so they may happen from several threads at once; unregistering needs an
+/// exclusive one, which keeps the values borrowed from [`get`](Self::get) alive.
+pub struct CustomIdType<T: Send + Sync + 'static> {
+    raw: H5I_type_raw_t,
+    marker: PhantomData<Box<T>>,
+}
+
+impl<T: Send + Sync + 'static> Debug for CustomIdType<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomIdType").field("raw", &self.raw).finish()
+    }
+}
+
+impl<T: Send + Sync + 'static> CustomIdType<T> {
+    /// Registers a new identifier type with the library.
+    pub fn new() -> Result<Self> {
+        let raw = h5call!(H5Iregister_type(HASH_SIZE, 0, Some(free_value::<T>)))?;
+        Ok(Self { raw, marker: PhantomData })
+    }
+
+    /// Returns the raw value of the identifier type.
+    pub fn raw(&self) -> H5I_type_raw_t {
+        self.raw
+    }
+
+    /// Registers `value` and returns its new identifier.
+    pub fn register(&self, value: Box<T>) -> Result<hid_t> {
+        let obj = Box::into_raw(value);
+        h5call!(H5Iregister(self.raw, obj.cast_const().cast())).inspect_err(|_| {
+            // the library did not take the value
+            drop(unsafe { Box::from_raw(obj) });
+        })
+    }
+
+    /// Returns the value registered as `id`, or `None` if `id` is not an identifier of this
+    /// type (including identifiers of other types and invalid ones).
+    pub fn get(&self, id: hid_t) -> Option<&T> {
+        let obj = h5lock!(H5Iobject_verify(id, self.raw));
+        // the value stays registered while `self` is borrowed, see `unregister`
+        unsafe { obj.cast::<T>().as_ref() }
+    }
+
+    /// Removes the identifier `id` and returns its value, or `None` if `id` is not an
+    /// identifier of this type.
+    pub fn unregister(&mut self, id: hid_t) -> Option<Box<T>> {
+        let obj = h5lock!(H5Iremove_verify(id, self.raw));
+        (!obj.is_null()).then(|| unsafe { Box::from_raw(obj.cast::<T>()) })
+    }
+}
+
+impl<T: Send + Sync + 'static> Drop for CustomIdType<T> {
+    fn drop(&mut self) {
+        h5lock!({
+            DESTROYING.with(|destroying| destroying.set(true));
+            H5Idestroy_type(self.raw);
+            DESTROYING.with(|destroying| destroying.set(false));
+        });
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    use super::CustomIdType;
+    use crate::internal_prelude::*;
+
+    struct Tracked {
+        value: u32,
+        drops: Arc<AtomicUsize>,
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.drops.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    pub fn test_custom_id_type() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let tracked = |value| Box::new(Tracked { value, drops: Arc::clone(&drops) });
+        let mut ids_type = CustomIdType::<Tracked>::new().unwrap();
+        let ids: Vec<_> = (0..4).map(|i| ids_type.register(tracked(i)).unwrap()).collect();
+        assert_eq!(ids_type.get(ids[2]).unwrap().value, 2);
+
+        thread::scope(|scope| {
+            let ids_type = &ids_type;
+            for (i, &id) in ids.iter().enumerate() {
+                scope.spawn(move || assert_eq!(ids_type.get(id).unwrap().value, i as u32));
+            }
+        });
+
+        let removed = ids_type.unregister(ids[0]).unwrap();
+        assert_eq!(removed.value, 0);
+        assert!(ids_type.get(ids[0]).is_none());
+        assert!(ids_type.unregister(ids[0]).is_none());
+        drop(removed);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+
+        // identifiers of other types are rejected without touching their objects
+        let other = CustomIdType::<Tracked>::new().unwrap();
+        let other_id = other.register(tracked(10)).unwrap();
+        assert!(ids_type.get(other_id).is_none());
+        assert!(ids_type.unregister(other_id).is_none());
+        assert_eq!(other.get(other_id).unwrap().value, 10);
+        let file = File::create_in_memory("custom_id.h5").unwrap();
+        assert!(ids_type.get(file.id()).is_none());
+        assert!(ids_type.get(H5I_INVALID_HID).is_none());
+
+        // dropping the types drops the remaining values
+        drop(ids_type);
+        assert_eq!(drops.load(Ordering::SeqCst), 4);
+        drop(other);
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
+    }
+}
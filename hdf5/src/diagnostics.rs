@@ -0,0 +1,228 @@
+//! Opt-in tracking of open object handles, to find handle leaks in long-running processes.
+//!
+//! Once [`enable`]d, every handle created by this crate is recorded along with the object
+//! type, the object name at creation time (for files, groups, datasets and named datatypes),
+//! the creation time and, with the `backtrace` crate feature, a backtrace of the creation
+//! site. [`snapshot`] aggregates the objects that are still open. Objects opened before
+//! tracking was enabled are not recorded.
+//!
+//! While disabled, tracking costs a single atomic load per handle creation and drop.
+//!
+//! With tracking enabled, the error returned by [`File::try_close`] for a file with open
+//! objects lists the oldest of them.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::diagnostics;
+//!
+//! diagnostics::enable();
+//! // ... run the workload ...
+//! let snapshot = diagnostics::snapshot();
+//! println!("{} open datasets", snapshot.count("dataset"));
+//! for object in &snapshot.oldest {
+//!     println!("{object}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "backtrace")]
+use std::backtrace::Backtrace;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "backtrace")]
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::time::SystemTime;
+
+use parking_lot::Mutex;
+
+use crate::sys::h5f::{
+    H5Fget_name, H5Fget_obj_count, H5Fget_obj_ids, H5F_OBJ_ATTR, H5F_OBJ_DATASET, H5F_OBJ_DATATYPE,
+    H5F_OBJ_GROUP,
+};
+use crate::sys::h5i::{H5Iget_name, H5Iget_type, H5Iis_valid};
+
+use crate::internal_prelude::*;
+
+/// The number of objects listed in [`Snapshot::oldest`] by [`snapshot`].
+pub const SNAPSHOT_OLDEST: usize = 10;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// The registry is only accessed while holding the library lock, so that recording a handle
+// (which queries the library) cannot deadlock with a snapshot.
+static REGISTRY: LazyLock<Mutex<HashMap<hid_t, LiveObject>>> = LazyLock::new(Default::default);
+
+/// Starts recording the handles created by this crate.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Release);
+}
+
+/// Stops recording handles and forgets the recorded ones.
+pub fn disable() {
+    h5lock!({
+        ENABLED.store(false, Ordering::Release);
+        REGISTRY.lock().clear();
+    });
+}
+
+/// Returns `true` if handles are being recorded.
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// An open object recorded while tracking was enabled.
+#[derive(Clone, Debug)]
+pub struct LiveObject {
+    /// The object identifier.
+    pub id: hid_t,
+    /// The object type, e.g. `"dataset"` or `"property list"`.
+    pub kind: &'static str,
+    /// The object name when the first handle was created, if it has one.
+    pub name: Option<String>,
+    /// The time the first handle was created.
+    pub created: SystemTime,
+    /// The number of live handles to the object created by this crate.
+    pub handles: usize,
+    /// Where the first handle was created.
+    #[cfg(feature = "backtrace")]
+    pub backtrace: Arc<Backtrace>,
+}
+
+impl Display for LiveObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(name) = &self.name {
+            write!(f, " '{name}'")?;
+        }
+        let age = self.created.elapsed().unwrap_or_default();
+        write!(f, " (id {}, open for {:.1}s)", self.id, age.as_secs_f64())
+    }
+}
+
+/// Open objects recorded while tracking was enabled, as returned by [`snapshot`].
+#[derive(Clone, Debug, Default)]
+pub struct Snapshot {
+    /// The number of open objects per object type.
+    pub counts: BTreeMap<&'static str, usize>,
+    /// The oldest open objects, oldest first.
+    pub oldest: Vec<LiveObject>,
+}
+
+impl Snapshot {
+    /// Returns the number of open objects of the given type, e.g. `"dataset"`.
+    pub fn count(&self, kind: &str) -> usize {
+        self.counts.get(kind).copied().unwrap_or(0)
+    }
+
+    /// Returns the total number of open objects.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+/// Returns the open objects recorded while tracking was enabled, listing the
+/// [`SNAPSHOT_OLDEST`] oldest ones.
+pub fn snapshot() -> Snapshot {
+    snapshot_oldest(SNAPSHOT_OLDEST)
+}
+
+/// Returns the open objects recorded while tracking was enabled, listing the `n` oldest ones.
+pub fn snapshot_oldest(n: usize) -> Snapshot {
+    h5lock!({
+        let mut registry = REGISTRY.lock();
+        // handles released without being dropped (e.g. by `File::close`) are pruned here
+        registry.retain(|&id, _| H5Iis_valid(id) == 1);
+        let mut snapshot = Snapshot::default();
+        for object in registry.values() {
+            *snapshot.counts.entry(object.kind).or_default() += 1;
+        }
+        snapshot.oldest = oldest(registry.values(), n);
+        snapshot
+    })
+}
+
+fn oldest<'a>(objects: impl Iterator<Item = &'a LiveObject>, n: usize) -> Vec<LiveObject> {
+    let mut objects: Vec<_> = objects.cloned().collect();
+    objects.sort_by_key(|object| (object.created, object.id));
+    objects.truncate(n);
+    objects
+}
+
+/// Returns the `n` oldest recorded objects among the open objects of the file `file_id`.
+pub(crate) fn oldest_in_file(file_id: hid_t, n: usize) -> Vec<LiveObject> {
+    if !is_enabled() {
+        return vec![];
+    }
+    h5lock!({
+        let types = H5F_OBJ_DATASET | H5F_OBJ_GROUP | H5F_OBJ_DATATYPE | H5F_OBJ_ATTR;
+        let Ok(count) = h5call!(H5Fget_obj_count(file_id, types)) else {
+            return vec![];
+        };
+        let mut ids = vec![0; count as usize];
+        if h5call!(H5Fget_obj_ids(file_id, types, ids.len(), ids.as_mut_ptr())).is_err() {
+            return vec![];
+        }
+        let registry = REGISTRY.lock();
+        oldest(ids.iter().filter_map(|id| registry.get(id)), n)
+    })
+}
+
+/// Records a new handle to `id`.
+pub(crate) fn record(id: hid_t) {
+    h5lock!({
+        if let Some(object) = REGISTRY.lock().get_mut(&id) {
+            object.handles += 1;
+            return;
+        }
+        let id_type = crate::sys::convert_h5i_type(H5Iget_type(id));
+        let name = match id_type {
+            H5I_GROUP | H5I_DATASET | H5I_DATATYPE => get_h5_str(|m, s| H5Iget_name(id, m, s)).ok(),
+            H5I_FILE => get_h5_str(|m, s| H5Fget_name(id, m, s)).ok(),
+            _ => None,
+        };
+        let object = LiveObject {
+            id,
+            kind: kind_name(id_type),
+            name: name.filter(|name| !name.is_empty()),
+            created: SystemTime::now(),
+            handles: 1,
+            #[cfg(feature = "backtrace")]
+            backtrace: Arc::new(Backtrace::force_capture()),
+        };
+        REGISTRY.lock().insert(id, object);
+    });
+}
+
+/// Records that a handle to `id` was dropped.
+pub(crate) fn release(id: hid_t) {
+    h5lock!({
+        let mut registry = REGISTRY.lock();
+        if let Some(object) = registry.get_mut(&id) {
+            object.handles -= 1;
+            if object.handles == 0 {
+                registry.remove(&id);
+            }
+        }
+    });
+}
+
+pub(crate) fn kind_name(id_type: H5I_type_t) -> &'static str {
+    match id_type {
+        H5I_FILE => "file",
+        H5I_GROUP => "group",
+        H5I_DATATYPE => "datatype",
+        H5I_DATASPACE => "dataspace",
+        H5I_DATASET => "dataset",
+        H5I_ATTR => "attribute",
+        H5I_GENPROP_CLS => "property list class",
+        H5I_GENPROP_LST => "property list",
+        H5I_ERROR_CLASS | H5I_ERROR_MSG | H5I_ERROR_STACK => "error",
+        _ => "other",
+    }
+}
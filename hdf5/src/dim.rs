@@ -1,5 +1,14 @@
 use std::slice;
 
+use smallvec::SmallVec;
+
+use crate::internal_prelude::*;
+
+/// Dimension buffers passed to and from the library (shapes, and the start, stride, count and
+/// block arrays of hyperslabs). The rank of a dataspace is at most 32 (`H5S_MAX_RANK`); up to
+/// 8 dimensions are kept on the stack, so that the common case does not allocate.
+pub(crate) type DimVec = SmallVec<[hsize_t; 8]>;
+
 /// A scalar integer type used by `Dimension` trait for indexing.
 pub type Ix = usize;
 
@@ -97,8 +106,117 @@ impl Dimension for Ix {
     }
 }
 
+/// Converts dimensions to the `hsize_t` values taken by the library, which are 64-bit on all
+/// targets.
+pub(crate) fn to_hsize(dims: &[Ix]) -> DimVec {
+    dims.iter().map(|&dim| dim as hsize_t).collect()
+}
+
+fn narrow<T: TryFrom<hsize_t>>(dim: hsize_t) -> Result<T> {
+    T::try_from(dim).map_err(|_| {
+        format!("dimension {} does not fit into {} bits", dim, 8 * std::mem::size_of::<T>()).into()
+    })
+}
+
+/// Converts a dimension returned by the library, failing if it does not fit into [`Ix`]
+/// (which can only happen on 32-bit targets).
+pub(crate) fn hsize_to_ix(dim: hsize_t) -> Result<Ix> {
+    narrow(dim)
+}
+
+/// Converts dimensions returned by the library, see [`hsize_to_ix`].
+pub(crate) fn to_ix(dims: &[hsize_t]) -> Result<Vec<Ix>> {
+    dims.iter().map(|&dim| hsize_to_ix(dim)).collect()
+}
+
+/// Returns the number of elements of a shape given as `hsize_t` values, failing if it
+/// overflows.
+pub(crate) fn checked_product(dims: &[hsize_t]) -> Result<u64> {
+    dims.iter()
+        .try_fold(1_u64, |acc, &dim| acc.checked_mul(dim))
+        .ok_or_else(|| format!("number of elements of shape {:?} overflows 64 bits", dims).into())
+}
+
+/// Returns the number of elements of a shape, failing if it overflows [`Ix`].
+pub(crate) fn checked_size(dims: &[Ix]) -> Result<Ix> {
+    dims.iter().try_fold(1, |acc: Ix, &dim| acc.checked_mul(dim)).ok_or_else(|| {
+        format!("number of elements of shape {:?} overflows {} bits", dims, Ix::BITS).into()
+    })
+}
+
+/// Returns the size in bytes of a buffer of `len` elements of `elem_size` bytes, failing if it
+/// exceeds the largest possible allocation (`isize::MAX` bytes).
+pub(crate) fn checked_buf_size(len: Ix, elem_size: usize) -> Result<usize> {
+    len.checked_mul(elem_size).filter(|&size| size <= isize::MAX as usize).ok_or_else(|| {
+        format!(
+            "a buffer of {} elements of {} bytes exceeds the maximum allocation size",
+            len, elem_size
+        )
+        .into()
+    })
+}
+
+/// Allocates an empty vector with room for exactly `len` elements of `T`, failing instead of
+/// aborting if the buffer is larger than the largest possible allocation or cannot be allocated.
+pub(crate) fn alloc_buf<T>(len: Ix) -> Result<Vec<T>> {
+    let size = checked_buf_size(len, std::mem::size_of::<T>())?;
+    let mut buf = Vec::new();
+    buf.try_reserve_exact(len)
+        .map_err(|err| format!("cannot allocate a buffer of {} bytes: {}", size, err))?;
+    Ok(buf)
+}
+
 #[cfg(test)]
 pub mod tests {
+    use super::{
+        alloc_buf, checked_buf_size, checked_product, checked_size, hsize_to_ix, narrow, to_hsize,
+        to_ix,
+    };
+
+    #[test]
+    pub fn test_hsize_conversions() {
+        let big = u64::from(u32::MAX) + 1;
+        assert_eq!(to_hsize(&[0, 3, usize::MAX]).as_slice(), [0, 3, usize::MAX as u64]);
+        assert_eq!(to_ix(&[0, 3]).unwrap(), [0, 3]);
+        assert_eq!(hsize_to_ix(7).unwrap(), 7);
+        // 32-bit targets are checked through the generic helper
+        assert_eq!(narrow::<u32>(u64::from(u32::MAX)).unwrap(), u32::MAX);
+        assert_err!(narrow::<u32>(big), "dimension 4294967296 does not fit into 32 bits");
+        if cfg!(target_pointer_width = "64") {
+            assert_eq!(to_ix(&[big]).unwrap(), [big as usize]);
+        } else {
+            assert!(to_ix(&[big]).is_err());
+        }
+    }
+
+    #[test]
+    pub fn test_checked_products() {
+        assert_eq!(checked_product(&[]).unwrap(), 1);
+        assert_eq!(checked_product(&[0, u64::MAX]).unwrap(), 0);
+        assert_eq!(checked_product(&[u64::MAX]).unwrap(), u64::MAX);
+        assert_eq!(checked_product(&[1 << 32, (1 << 32) - 1]).unwrap(), u64::MAX - (1 << 32) + 1);
+        assert_err!(checked_product(&[1 << 32, 1 << 32]), "overflows 64 bits");
+        assert_err!(checked_product(&[2, 3, u64::MAX / 2]), "overflows 64 bits");
+
+        assert_eq!(checked_size(&[]).unwrap(), 1);
+        assert_eq!(checked_size(&[3, 0, usize::MAX]).unwrap(), 0);
+        assert_eq!(checked_size(&[usize::MAX, 1]).unwrap(), usize::MAX);
+        assert_err!(checked_size(&[usize::MAX, 2]), "number of elements of shape");
+
+        let max = isize::MAX as usize;
+        assert_eq!(checked_buf_size(0, 8).unwrap(), 0);
+        assert_eq!(checked_buf_size(usize::MAX, 0).unwrap(), 0);
+        assert_eq!(checked_buf_size(max, 1).unwrap(), max);
+        assert_eq!(checked_buf_size(max / 8, 8).unwrap(), max / 8 * 8);
+        assert_err!(checked_buf_size(max + 1, 1), "exceeds the maximum allocation size");
+        assert_err!(checked_buf_size(max / 4, 8), "exceeds the maximum allocation size");
+        assert_err!(checked_buf_size(usize::MAX, 2), "exceeds the maximum allocation size");
+
+        assert_eq!(alloc_buf::<u64>(0).unwrap().capacity(), 0);
+        assert!(alloc_buf::<u64>(10).unwrap().capacity() >= 10);
+        assert_err!(alloc_buf::<u64>(max / 4), "exceeds the maximum allocation size");
+    }
+
     // compile-time test
     #[allow(dead_code)]
     pub fn slice_as_shape(shape: &[usize]) {
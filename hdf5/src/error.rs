@@ -5,6 +5,7 @@ use std::io;
 use std::ops::Deref;
 use std::panic;
 use std::ptr::{self, addr_of_mut};
+use std::sync::LazyLock;
 
 use ndarray::ShapeError;
 
@@ -12,7 +13,15 @@ use crate::sys::h5e::{
     H5E_auto2_t, H5E_error2_t, H5Eget_current_stack, H5Eget_msg, H5Eprint2, H5Eset_auto2, H5Ewalk2,
     H5E_DEFAULT, H5E_WALK_DOWNWARD,
 };
-
+use crate::sys::Version;
+
+use crate::globals::{
+    H5E_ALREADYEXISTS, H5E_ARGS, H5E_ATTR, H5E_BADTYPE, H5E_BADVALUE, H5E_BTREE, H5E_CANTCONVERT,
+    H5E_CANTLOCKFILE, H5E_CANTOPENFILE, H5E_DATASET, H5E_DATATYPE, H5E_EXISTS, H5E_FILE,
+    H5E_FILEEXISTS, H5E_FILEOPEN, H5E_LINK, H5E_NOENCODER, H5E_NOFILTER, H5E_NOTFOUND, H5E_NOTHDF5,
+    H5E_OHDR, H5E_PLINE, H5E_READERROR, H5E_STORAGE, H5E_SYM, H5E_TRUNCATED, H5E_VERSION,
+    H5E_WRITEERROR,
+};
 use crate::internal_prelude::*;
 
 /// Silence errors emitted by `hdf5`
@@ -77,13 +86,7 @@ impl ErrorStack {
                 if data.err.is_some() {
                     return 0;
                 }
-                let closure = |e: H5E_error2_t| -> Result<ErrorFrame> {
-                    let (desc, func) = (string_from_cstr(e.desc), string_from_cstr(e.func_name));
-                    let major = get_h5_str(|m, s| H5Eget_msg(e.maj_num, ptr::null_mut(), m, s))?;
-                    let minor = get_h5_str(|m, s| H5Eget_msg(e.min_num, ptr::null_mut(), m, s))?;
-                    Ok(ErrorFrame::new(&desc, &func, &major, &minor))
-                };
-                match closure(*err_desc) {
+                match ErrorFrame::from_raw(&*err_desc) {
                     Ok(frame) => {
                         data.stack.push(frame);
                     }
@@ -115,6 +118,8 @@ pub struct ErrorFrame {
     func: String,
     major: String,
     minor: String,
+    major_id: hid_t,
+    minor_id: hid_t,
     description: String,
 }
 
@@ -125,10 +130,27 @@ impl ErrorFrame {
             func: func.into(),
             major: major.into(),
             minor: minor.into(),
+            major_id: H5I_INVALID_HID,
+            minor_id: H5I_INVALID_HID,
             description: format!("{func}(): {desc}"),
         }
     }
 
+    /// Converts a frame passed by `H5Ewalk2`. The string fields may be null for some frames
+    /// and are then left empty.
+    ///
+    /// # Safety
+    ///
+    /// The non-null string fields of `e` must point to zero-terminated strings.
+    pub(crate) unsafe fn from_raw(e: &H5E_error2_t) -> Result<Self> {
+        let (desc, func) = unsafe { (string_from_cstr(e.desc), string_from_cstr(e.func_name)) };
+        let major = unsafe { get_h5_str(|m, s| H5Eget_msg(e.maj_num, ptr::null_mut(), m, s))? };
+        let minor = unsafe { get_h5_str(|m, s| H5Eget_msg(e.min_num, ptr::null_mut(), m, s))? };
+        let mut frame = Self::new(&desc, &func, &major, &minor);
+        (frame.major_id, frame.minor_id) = (e.maj_num, e.min_num);
+        Ok(frame)
+    }
+
     /// Returns the error description.
     pub fn desc(&self) -> &str {
         self.desc.as_ref()
@@ -144,8 +166,146 @@ impl ErrorFrame {
     pub fn detail(&self) -> Option<String> {
         Some(format!("Error in {}(): {} [{}: {}]", self.func, self.desc, self.major, self.minor))
     }
+
+    /// Returns the ids of the major and minor error codes.
+    pub fn ids(&self) -> (hid_t, hid_t) {
+        (self.major_id, self.minor_id)
+    }
+
+    /// Returns the error category of this frame, or `None` if its codes are not mapped.
+    pub fn kind(&self) -> Option<ErrorKind> {
+        let (major, minor) = self.ids();
+        if major == *H5E_FILE && minor == *H5E_BADVALUE && self.desc.contains("superblock version")
+        {
+            // reported by the library when the superblock is newer than it supports
+            return Some(ErrorKind::FileVersionUnsupported);
+        }
+        let kind = KIND_TABLE.iter().find_map(|&(maj, min, kind)| {
+            (maj.map_or(true, |maj| maj == major) && min == minor).then_some(kind)
+        })?;
+        if minor == *H5E_CANTOPENFILE {
+            // the file drivers report why the file couldn't be opened only via errno
+            match errno_from_desc(&self.desc) {
+                Some(libc::ENOENT) => return Some(ErrorKind::NotFound),
+                Some(libc::EEXIST) => return Some(ErrorKind::AlreadyExists),
+                _ => {}
+            }
+        }
+        Some(kind)
+    }
+}
+
+/// Extracts `N` from the `"errno = N"` part of file driver error descriptions.
+fn errno_from_desc(desc: &str) -> Option<c_int> {
+    let rest = &desc[desc.find("errno = ")? + "errno = ".len()..];
+    let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+/// A category of errors, see [`Error::kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// An object, link, attribute or file could not be found.
+    NotFound,
+    /// An object, link, attribute or file already exists.
+    AlreadyExists,
+    /// The file was opened without write intent.
+    ReadOnly,
+    /// An object has the wrong type, or data cannot be converted between two types.
+    TypeMismatch,
+    /// Stored data failed checksum verification.
+    ChecksumError,
+    /// A file could not be opened or is not a valid HDF5 file.
+    FileAccess,
+    /// A required filter is not available.
+    FilterUnavailable,
+    /// An object uses a format version, or an operation needs a library version, that is not
+    /// supported.
+    VersionUnsupported,
+    /// The file format (e.g. the superblock version) is newer than the loaded library supports.
+    FileVersionUnsupported,
+    /// The file is locked by another process.
+    FileLocked,
+    /// The file is not an HDF5 file.
+    NotHdf5,
+    /// The file is shorter than recorded in its superblock.
+    Truncated,
+    /// A read would allocate more memory than allowed by the read limit.
+    AllocationLimitExceeded,
+    /// The library has been closed by [`raw::shutdown`](crate::raw::shutdown).
+    LibraryShutDown,
+    /// Any other error.
+    Other,
+}
+
+impl ErrorKind {
+    /// Returns a hint on the likely cause of errors of this kind, if there is one.
+    pub fn suggestion(self) -> Option<String> {
+        match self {
+            Self::FileVersionUnsupported => {
+                let version =
+                    crate::raw::hdf5_version().map_or("unknown".into(), |v| v.to_string());
+                Some(format!("file requires a newer HDF5 library; loaded version is {version}"))
+            }
+            Self::FileLocked => Some(
+                "file is locked by another process; if no other process uses it, file locking \
+                 can be disabled with HDF5_USE_FILE_LOCKING=FALSE"
+                    .into(),
+            ),
+            Self::NotHdf5 => Some("file is not an HDF5 file (no HDF5 signature found)".into()),
+            Self::Truncated => Some(
+                "file is truncated; it may be incompletely copied or still being written".into(),
+            ),
+            Self::ReadOnly => {
+                Some("open the file with `File::open_rw()` or `File::append()` to modify it".into())
+            }
+            Self::AllocationLimitExceeded => Some(
+                "read the data in smaller slices with `read_slice()` or stream it chunk by chunk"
+                    .into(),
+            ),
+            _ => None,
+        }
+    }
 }
 
+/// Maps `(major, minor)` error codes to error kinds, `None` matches any major code. The first
+/// matching entry wins, so more specific entries must come first.
+static KIND_TABLE: LazyLock<Vec<(Option<hid_t>, hid_t, ErrorKind)>> = LazyLock::new(|| {
+    use ErrorKind::*;
+    vec![
+        // no conversion path between two datatypes
+        (Some(*H5E_DATATYPE), *H5E_NOTFOUND, TypeMismatch),
+        // filter missing from the pipeline registry
+        (Some(*H5E_PLINE), *H5E_NOTFOUND, FilterUnavailable),
+        (None, *H5E_NOFILTER, FilterUnavailable),
+        (None, *H5E_NOENCODER, FilterUnavailable),
+        // Fletcher32 checksum mismatch
+        (Some(*H5E_STORAGE), *H5E_READERROR, ChecksumError),
+        (Some(*H5E_SYM), *H5E_NOTFOUND, NotFound),
+        (Some(*H5E_LINK), *H5E_NOTFOUND, NotFound),
+        (Some(*H5E_ATTR), *H5E_NOTFOUND, NotFound),
+        (Some(*H5E_OHDR), *H5E_NOTFOUND, NotFound),
+        (Some(*H5E_BTREE), *H5E_NOTFOUND, NotFound),
+        (None, *H5E_EXISTS, AlreadyExists),
+        (None, *H5E_ALREADYEXISTS, AlreadyExists),
+        (None, *H5E_FILEEXISTS, AlreadyExists),
+        // "no write intent on file"
+        (Some(*H5E_ARGS), *H5E_WRITEERROR, ReadOnly),
+        (Some(*H5E_DATASET), *H5E_WRITEERROR, ReadOnly),
+        (Some(*H5E_FILE), *H5E_WRITEERROR, ReadOnly),
+        (None, *H5E_CANTCONVERT, TypeMismatch),
+        (None, *H5E_BADTYPE, TypeMismatch),
+        (Some(*H5E_FILE), *H5E_VERSION, FileVersionUnsupported),
+        (None, *H5E_VERSION, VersionUnsupported),
+        (None, *H5E_CANTLOCKFILE, FileLocked),
+        (None, *H5E_NOTHDF5, NotHdf5),
+        (None, *H5E_TRUNCATED, Truncated),
+        (None, *H5E_CANTOPENFILE, FileAccess),
+        (None, *H5E_FILEOPEN, FileAccess),
+    ]
+});
+
 /// A converted [`ErrorStack`] with methods to access [`ErrorFrame`] data.
 #[derive(Clone, Debug)]
 pub struct ExpandedErrorStack {
@@ -202,6 +362,11 @@ impl ExpandedErrorStack {
     pub fn detail(&self) -> Option<String> {
         self.top().and_then(ErrorFrame::detail)
     }
+
+    /// Returns the error category, taken from the innermost frame with mapped error codes.
+    pub fn kind(&self) -> ErrorKind {
+        self.iter().rev().find_map(ErrorFrame::kind).unwrap_or(ErrorKind::Other)
+    }
 }
 
 /// The error type for HDF5-related functions.
@@ -211,6 +376,41 @@ pub enum Error {
     HDF5(ErrorStack),
     /// A user error occurred in the high-level Rust API (e.g., invalid user input).
     Internal(String),
+    /// A read would allocate more memory than allowed, see
+    /// [`set_max_read_bytes`](crate::set_max_read_bytes).
+    AllocationLimitExceeded {
+        /// The estimated number of bytes the read would allocate.
+        size: u64,
+        /// The limit in effect for the read.
+        limit: u64,
+    },
+    /// A mutating operation was attempted on an object of a file opened without write intent.
+    ReadOnly {
+        /// The name of the file.
+        file: String,
+    },
+    /// An operation was attempted after the library was closed by
+    /// [`raw::shutdown`](crate::raw::shutdown).
+    LibraryShutDown,
+    /// An operation requires a newer version of the library than the loaded one.
+    VersionUnsupported {
+        /// The operation or type that is not supported, e.g. `ObjectReference2`.
+        feature: String,
+        /// The oldest library version supporting it.
+        required: Version,
+        /// The loaded library version, if known.
+        detected: Option<Version>,
+    },
+    /// An error that occurred during the described operation.
+    ///
+    /// The underlying error (including its HDF5 error stack, if any) is available via
+    /// [`source()`](StdError::source).
+    Context {
+        /// The operation that was being performed, e.g. `opening dataset '/x'`.
+        context: String,
+        /// The error that caused the operation to fail.
+        source: Box<Error>,
+    },
 }
 
 /// A type for results generated by HDF5-related functions where the `Err` type is
@@ -227,6 +427,36 @@ impl Error {
             Err(Self::Internal("Could not get errorstack".to_owned()))
         }
     }
+
+    /// Returns the category of this error, derived from the codes of the HDF5 error stack.
+    ///
+    /// Errors raised by the Rust API itself are categorized as [`ErrorKind::Other`], except for
+    /// [`Error::ReadOnly`], [`Error::AllocationLimitExceeded`], [`Error::LibraryShutDown`] and
+    /// [`Error::VersionUnsupported`].
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::HDF5(stack) => stack.clone().expand().map_or(ErrorKind::Other, |s| s.kind()),
+            Self::Internal(_) => ErrorKind::Other,
+            Self::ReadOnly { .. } => ErrorKind::ReadOnly,
+            Self::AllocationLimitExceeded { .. } => ErrorKind::AllocationLimitExceeded,
+            Self::LibraryShutDown => ErrorKind::LibraryShutDown,
+            Self::VersionUnsupported { .. } => ErrorKind::VersionUnsupported,
+            Self::Context { source, .. } => source.kind(),
+        }
+    }
+
+    /// Returns a hint on the likely cause of this error, see [`ErrorKind::suggestion`].
+    pub fn suggestion(&self) -> Option<String> {
+        self.kind().suggestion()
+    }
+
+    /// Returns the innermost error, skipping all contexts.
+    pub fn root_cause(&self) -> &Self {
+        match self {
+            Self::Context { source, .. } => source.root_cause(),
+            _ => self,
+        }
+    }
 }
 
 impl From<&str> for Error {
@@ -249,13 +479,7 @@ impl From<Infallible> for Error {
 
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Self::Internal(ref desc) => f.write_str(desc),
-            Self::HDF5(ref stack) => match stack.clone().expand() {
-                Ok(stack) => f.write_str(stack.description()),
-                Err(_) => f.write_str("Could not get error stack"),
-            },
-        }
+        fmt::Display::fmt(self, f)
     }
 }
 
@@ -263,15 +487,48 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Self::Internal(ref desc) => f.write_str(desc),
+            Self::ReadOnly { ref file } => {
+                write!(f, "file '{file}' is opened read-only")?;
+                match self.suggestion() {
+                    Some(suggestion) => write!(f, "; {suggestion}"),
+                    None => Ok(()),
+                }
+            }
+            Self::AllocationLimitExceeded { size, limit } => {
+                write!(
+                    f,
+                    "read would allocate about {size} bytes, exceeding the limit of {limit} bytes"
+                )?;
+                match self.suggestion() {
+                    Some(suggestion) => write!(f, "; {suggestion}"),
+                    None => Ok(()),
+                }
+            }
+            Self::LibraryShutDown => f.write_str("the HDF5 library has been shut down"),
+            Self::VersionUnsupported { ref feature, required, detected } => {
+                write!(f, "{feature} requires HDF5 {required} or later; loaded version is ")?;
+                match detected {
+                    Some(version) => write!(f, "{version}"),
+                    None => f.write_str("unknown"),
+                }
+            }
             Self::HDF5(ref stack) => match stack.clone().expand() {
                 Ok(stack) => f.write_str(stack.description()),
                 Err(_) => f.write_str("Could not get error stack"),
             },
+            Self::Context { ref context, ref source } => write!(f, "{context}: {source}"),
         }
     }
 }
 
-impl StdError for Error {}
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 impl From<ShapeError> for Error {
     fn from(err: ShapeError) -> Self {
@@ -285,6 +542,27 @@ impl From<Error> for io::Error {
     }
 }
 
+/// Describes the operation that was being performed when an error occurred.
+///
+/// The original error is kept as the source of an [`Error::Context`], which reads as
+/// `"{context}: {original error}"`, so nested contexts compose into a single line going from
+/// the outermost operation to the root cause.
+pub(crate) trait ErrorContext<T> {
+    fn context<C: fmt::Display>(self, context: C) -> Result<T>;
+
+    fn with_context<C: fmt::Display, F: FnOnce() -> C>(self, func: F) -> Result<T>;
+}
+
+impl<T> ErrorContext<T> for Result<T> {
+    fn context<C: fmt::Display>(self, context: C) -> Result<T> {
+        self.with_context(|| context)
+    }
+
+    fn with_context<C: fmt::Display, F: FnOnce() -> C>(self, func: F) -> Result<T> {
+        self.map_err(|err| Error::Context { context: func().to_string(), source: Box::new(err) })
+    }
+}
+
 pub fn h5check<T: H5ErrorCode>(value: T) -> Result<T> {
     H5ErrorCode::h5check(value)
 }
@@ -299,6 +577,10 @@ pub trait H5ErrorCode: Copy {
 
     fn h5check(value: Self) -> Result<Self> {
         if Self::is_err_code(value) {
+            if crate::sys::is_shut_down() {
+                // the call was refused, there is no error stack to query
+                return Err(Error::LibraryShutDown);
+            }
             Err(Error::query().unwrap_or_else(|e| e))
         } else {
             Ok(value)
@@ -337,7 +619,11 @@ pub mod tests {
     use crate::globals::H5P_ROOT;
     use crate::internal_prelude::*;
 
-    use super::ExpandedErrorStack;
+    use super::{errno_from_desc, ErrorFrame, ExpandedErrorStack, StdError};
+    use crate::globals::{
+        H5E_ARGS, H5E_BADRANGE, H5E_BADVALUE, H5E_FILE, H5E_NOTFOUND, H5E_OHDR, H5E_SYM,
+        H5E_VERSION,
+    };
 
     #[test]
     pub fn test_error_stack() {
@@ -349,7 +635,7 @@ pub mod tests {
         .unwrap();
         let stack = match stack {
             Error::HDF5(stack) => stack,
-            Error::Internal(internal) => panic!("Expected hdf5 error, not {}", internal),
+            err => panic!("Expected hdf5 error, not {}", err),
         }
         .expand()
         .unwrap();
@@ -364,7 +650,7 @@ pub mod tests {
         .unwrap();
         let stack = match stack {
             Error::HDF5(stack) => stack,
-            Error::Internal(internal) => panic!("Expected hdf5 error, not {}", internal),
+            err => panic!("Expected hdf5 error, not {}", err),
         }
         .expand()
         .unwrap();
@@ -403,6 +689,29 @@ pub mod tests {
         assert_eq!(empty_stack.len(), 0);
     }
 
+    #[test]
+    pub fn test_error_frame_null_strings() {
+        use crate::sys::h5e::H5E_error2_t;
+
+        // some frames carry no description or function name
+        let raw = H5E_error2_t {
+            cls_id: H5I_INVALID_HID,
+            maj_num: *H5E_SYM,
+            min_num: *H5E_NOTFOUND,
+            line: 0,
+            func_name: std::ptr::null(),
+            file_name: std::ptr::null(),
+            desc: std::ptr::null(),
+        };
+        let frame = h5lock!(ErrorFrame::from_raw(&raw)).unwrap();
+        assert_eq!(frame.desc(), "");
+        assert_eq!(frame.description(), "(): ");
+        assert_eq!(frame.ids(), (*H5E_SYM, *H5E_NOTFOUND));
+        let detail = frame.detail().unwrap();
+        assert!(detail.starts_with("Error in (): ["), "unexpected detail: {detail}");
+        assert_eq!(frame.kind(), Some(ErrorKind::NotFound));
+    }
+
     #[test]
     pub fn test_h5call() {
         let result_no_error = h5call!({
@@ -435,4 +744,139 @@ pub mod tests {
 
         assert!(f2().is_err());
     }
+
+    #[test]
+    pub fn test_error_context() {
+        let err: Result<()> = Err("root cause".into());
+        let err = err.context("inner").with_context(|| format!("outer {}", 1)).unwrap_err();
+        assert_eq!(err.to_string(), "outer 1: inner: root cause");
+        assert_eq!(err.source().unwrap().to_string(), "inner: root cause");
+        assert_eq!(err.root_cause().to_string(), "root cause");
+        assert!(err.root_cause().source().is_none());
+        assert_eq!(Ok::<_, Error>(1).context("unused").unwrap(), 1);
+    }
+
+    #[test]
+    pub fn test_error_context_hdf5() {
+        with_tmp_file(|file| {
+            let err = file.dataset("missing").unwrap_err();
+            assert!(err.to_string().starts_with("opening dataset 'missing': "), "{err}");
+            let source = err.source().and_then(|err| err.downcast_ref::<Error>());
+            assert!(matches!(source, Some(Error::HDF5(_))), "{source:?}");
+            assert_eq!(err.kind(), ErrorKind::NotFound);
+
+            let err = file.create_group("a/b").unwrap_err();
+            assert!(err.to_string().starts_with("creating group 'a/b': "), "{err}");
+            let err = file.attr("missing").unwrap_err();
+            assert!(err.to_string().starts_with("opening attribute 'missing': "), "{err}");
+        })
+    }
+
+    #[test]
+    pub fn test_error_kind() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            file.create_group("a").unwrap();
+            file.new_dataset::<u8>().shape(1).create("ds").unwrap();
+            assert_eq!(file.group("missing").unwrap_err().kind(), ErrorKind::NotFound);
+            assert_eq!(file.attr("missing").unwrap_err().kind(), ErrorKind::NotFound);
+            assert_eq!(file.create_group("a").unwrap_err().kind(), ErrorKind::AlreadyExists);
+            assert_eq!(file.group("ds").unwrap_err().kind(), ErrorKind::TypeMismatch);
+            drop(file);
+
+            let file = File::open(&path).unwrap();
+            let err = file.dataset("ds").unwrap().write(&[1_u8]).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ReadOnly);
+            drop(file);
+
+            assert_eq!(File::create_excl(&path).unwrap_err().kind(), ErrorKind::AlreadyExists);
+            let missing = path.with_extension("missing");
+            assert_eq!(File::open(&missing).unwrap_err().kind(), ErrorKind::NotFound);
+            std::fs::write(&missing, b"not an hdf5 file").unwrap();
+            assert_eq!(File::open(&missing).unwrap_err().kind(), ErrorKind::NotHdf5);
+            std::fs::remove_file(&missing).unwrap();
+        });
+
+        assert_eq!(Error::from("internal").kind(), ErrorKind::Other);
+        // unmapped frames are skipped, an unmapped stack is `Other`
+        let mut frame = ErrorFrame::new("desc", "func", "major", "minor");
+        (frame.major_id, frame.minor_id) = (*H5E_ARGS, *H5E_BADRANGE);
+        assert_eq!(frame.kind(), None);
+        let mut stack = ExpandedErrorStack::new();
+        stack.push(frame.clone());
+        assert_eq!(stack.kind(), ErrorKind::Other);
+        let mut outer = frame.clone();
+        (outer.major_id, outer.minor_id) = (*H5E_SYM, *H5E_NOTFOUND);
+        let mut stack = ExpandedErrorStack::new();
+        stack.push(outer);
+        stack.push(frame);
+        assert_eq!(stack.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    pub fn test_open_error_kinds() {
+        with_tmp_dir(|dir| {
+            let path = dir.join("valid.h5");
+            let file = File::create(&path).unwrap();
+            let data = ndarray::Array1::from_shape_fn(100_000, |i| i as f64);
+            file.new_dataset_builder().with_data(&data).create("data").unwrap();
+            file.close().unwrap();
+            let bytes = std::fs::read(&path).unwrap();
+            let open_copy = |name: &str, contents: &[u8]| {
+                let copy = dir.join(name);
+                std::fs::write(&copy, contents).unwrap();
+                File::open(&copy).unwrap_err()
+            };
+
+            let err = open_copy("truncated.h5", &bytes[..bytes.len() / 2]);
+            assert_eq!(err.kind(), ErrorKind::Truncated);
+            assert!(err.to_string().contains("(file is truncated;"), "{err}");
+
+            let mut corrupt = bytes.clone();
+            corrupt[..8].copy_from_slice(b"\x89HDF\r\n\x1a\x00");
+            let err = open_copy("signature.h5", &corrupt);
+            assert_eq!(err.kind(), ErrorKind::NotHdf5);
+            assert!(err.to_string().contains("(file is not an HDF5 file"), "{err}");
+
+            // superblock version byte follows the signature
+            let mut newer = bytes.clone();
+            newer[8] = 9;
+            let err = open_copy("newer.h5", &newer);
+            assert_eq!(err.kind(), ErrorKind::FileVersionUnsupported);
+            assert!(err.to_string().contains("loaded version is 1."), "{err}");
+
+            let locking = std::env::var("HDF5_USE_FILE_LOCKING").map_or(true, |v| v != "FALSE");
+            #[cfg(unix)]
+            if locking {
+                use std::os::unix::io::AsRawFd;
+                let holder = std::fs::File::open(&path).unwrap();
+                assert_eq!(unsafe { libc::flock(holder.as_raw_fd(), libc::LOCK_EX) }, 0);
+                let err = File::open(&path).unwrap_err();
+                assert_eq!(err.kind(), ErrorKind::FileLocked, "{err}");
+                assert!(err.suggestion().unwrap().contains("locked by another process"));
+                drop(holder);
+            }
+            File::open(&path).unwrap();
+        });
+
+        let mut frame = ErrorFrame::new("bad superblock version number", "f", "File", "Bad value");
+        (frame.major_id, frame.minor_id) = (*H5E_FILE, *H5E_BADVALUE);
+        assert_eq!(frame.kind(), Some(ErrorKind::FileVersionUnsupported));
+        (frame.major_id, frame.minor_id) = (*H5E_FILE, *H5E_VERSION);
+        assert_eq!(frame.kind(), Some(ErrorKind::FileVersionUnsupported));
+        (frame.major_id, frame.minor_id) = (*H5E_OHDR, *H5E_VERSION);
+        assert_eq!(frame.kind(), Some(ErrorKind::VersionUnsupported));
+        assert_eq!(ErrorKind::FileAccess.suggestion(), None);
+        let suggestion = ErrorKind::FileVersionUnsupported.suggestion().unwrap();
+        assert!(suggestion.starts_with("file requires a newer HDF5 library; loaded version is"));
+    }
+
+    #[test]
+    pub fn test_errno_from_desc() {
+        let desc = "unable to open file: name = 'x.h5', errno = 2, error message = 'No such file'";
+        assert_eq!(errno_from_desc(desc), Some(2));
+        assert_eq!(errno_from_desc("errno = 17"), Some(17));
+        assert_eq!(errno_from_desc("unable to open file"), None);
+        assert_eq!(errno_from_desc("errno = ?"), None);
+    }
 }
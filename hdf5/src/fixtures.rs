@@ -0,0 +1,382 @@
+//! Fixture files and deterministic data generators for tests, enabled by the `test-fixtures`
+//! feature.
+//!
+//! [`memory_file`] creates an empty file in memory, [`sample_file`] one with a dataset of
+//! every kind of type this crate supports, and [`Gen`] with [`rng`] generates arrays of
+//! pseudo-random values that are the same for the same seed.
+//!
+//! # Layout of the sample file
+//!
+//! The layout is part of the API and only changes together with [`FIXTURE_VERSION`], so that
+//! tests in other languages can assert against files written by [`sample_file`]. With `n`
+//! the [`len`](FixtureSpec::len) of the spec, `i` running over
+//! `0..`[`datasets_per_type`](FixtureSpec::datasets_per_type) and `k` over `0..n`, every
+//! dataset is one-dimensional with `n` elements:
+//!
+//! | Path | Type | Element `k` |
+//! |------|------|-------------|
+//! | `/numeric/{t}_{i}` for `t` in [`NUMERIC_TYPES`] | `t` | `k + i` |
+//! | `/numeric/bool_{i}` | `bool` | `(k + i)` is odd |
+//! | `/strings/fixed_ascii_{i}` | `FixedAscii<16>` | `"s{i}_{k}"` |
+//! | `/strings/fixed_unicode_{i}` | `FixedUnicode<16>` | `"ü{i}_{k}"` |
+//! | `/strings/varlen_ascii_{i}` | `VarLenAscii` | `"s{i}_{k}"` |
+//! | `/strings/varlen_unicode_{i}` | `VarLenUnicode` | `"ü{i}_{k}"` |
+//! | `/compound/record_{i}` | [`FixtureRecord`] | `{k, k + i / 2, "r{i}_{k}"}` |
+//! | `/vlen/array_{i}` | `VarLenArray<i32>` | `[0, 1, .., (k + i) % 4)` |
+//! | `/references/object_{i}` | `ObjectReference1` | `/numeric/{t}_{i}` with `t` the `k % 10`-th numeric type |
+//! | `/filtered/shuffle_fletcher32_{i}` | `i32` | `k + i`, chunked |
+//! | `/filtered/deflate_{i}` | `i32` | `k + i`, chunked, only if deflate is available |
+//!
+//! The references are only written with [`references`](FixtureSpec::references), the
+//! filtered datasets only with [`filters`](FixtureSpec::filters); the chunks hold
+//! `max(n / 2, 1)` elements. With [`attributes`](FixtureSpec::attributes), the root group has
+//! a `fixture_version` attribute (`u32`, [`FIXTURE_VERSION`]) and every dataset an `index`
+//! attribute (`u64`, `i`) and a `path` attribute (`VarLenUnicode`, its own path).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::fixtures::{self, FixtureSpec, Gen};
+//!
+//! let file = fixtures::sample_file(FixtureSpec::default())?;
+//! assert_eq!(file.dataset("numeric/u16_0")?.read_raw::<u16>()?, (0..8).collect::<Vec<_>>());
+//!
+//! let values: ndarray::ArrayD<f64> = fixtures::gen_arr(&mut fixtures::rng(42), 2);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::Debug;
+use std::iter;
+use std::mem;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use hdf5_types::{
+    CompoundField, CompoundType, FixedAscii, FixedUnicode, TypeDescriptor, VarLenArray,
+    VarLenAscii, VarLenUnicode,
+};
+use ndarray::ArrayD;
+use rand::distr::Alphanumeric;
+/// The generator returned by [`rng`], re-exported so that it can be named without depending
+/// on `rand`.
+pub use rand::rngs::SmallRng;
+/// The trait bound of the generator functions, re-exported for implementing [`Gen`] without
+/// depending on `rand`.
+pub use rand::Rng;
+use rand::SeedableRng;
+
+use crate::filters::deflate_available;
+use crate::internal_prelude::*;
+
+/// The version of the layout written by [`sample_file`].
+pub const FIXTURE_VERSION: u32 = 1;
+
+/// The names of the numeric types of the `/numeric` datasets of the sample file, in order.
+pub const NUMERIC_TYPES: [&str; 10] =
+    ["u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64", "f32", "f64"];
+
+/// Creates an empty file in memory with a name unique within the process.
+pub fn memory_file() -> Result<File> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    File::create_in_memory(&format!("fixture-{}-{n}.h5", process::id()))
+}
+
+/// Returns a random number generator producing the same values for the same seed (and the
+/// same version of `rand`).
+pub fn rng(seed: u64) -> SmallRng {
+    SmallRng::seed_from_u64(seed)
+}
+
+/// What [`sample_file`] writes; see the [module documentation](self) for the layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixtureSpec {
+    /// Number of datasets of every type.
+    pub datasets_per_type: usize,
+    /// Number of elements of every dataset.
+    pub len: usize,
+    /// Whether to write the `fixture_version`, `index` and `path` attributes.
+    pub attributes: bool,
+    /// Whether to write the `/references` datasets.
+    pub references: bool,
+    /// Whether to write the `/filtered` datasets.
+    pub filters: bool,
+}
+
+impl Default for FixtureSpec {
+    fn default() -> Self {
+        Self { datasets_per_type: 1, len: 8, attributes: true, references: true, filters: true }
+    }
+}
+
+/// The element type of the `/compound` datasets of the sample file.
+#[derive(Clone, Debug, PartialEq)]
+#[repr(C)]
+pub struct FixtureRecord {
+    pub index: u64,
+    pub value: f64,
+    pub label: VarLenUnicode,
+}
+
+unsafe impl H5Type for FixtureRecord {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<u64>("index", mem::offset_of!(FixtureRecord, index), 0),
+                CompoundField::typed::<f64>("value", mem::offset_of!(FixtureRecord, value), 1),
+                CompoundField::typed::<VarLenUnicode>(
+                    "label",
+                    mem::offset_of!(FixtureRecord, label),
+                    2,
+                ),
+            ],
+            size: mem::size_of::<FixtureRecord>(),
+        })
+    }
+}
+
+/// Creates a file in memory with the layout described in the [module documentation](self).
+pub fn sample_file(spec: FixtureSpec) -> Result<File> {
+    let file = memory_file()?;
+    if spec.attributes {
+        file.new_attr::<u32>().create("fixture_version")?.write_scalar(&FIXTURE_VERSION)?;
+    }
+    let numeric = file.create_group("numeric")?;
+    let strings_group = file.create_group("strings")?;
+    let compound = file.create_group("compound")?;
+    let vlen = file.create_group("vlen")?;
+    let references = if spec.references { Some(file.create_group("references")?) } else { None };
+    let filtered = if spec.filters { Some(file.create_group("filtered")?) } else { None };
+    let n = spec.len;
+    for i in 0..spec.datasets_per_type {
+        macro_rules! numeric {
+            ($($ty:ident),+) => {$(
+                let data: Vec<$ty> = (0..n).map(|k| (k + i) as $ty).collect();
+                let ds = numeric.new_dataset_builder().with_data(&data)
+                    .create(format!("{}_{i}", stringify!($ty)).as_str())?;
+                annotate(&spec, &ds, i)?;
+            )+};
+        }
+        numeric!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+        let data: Vec<bool> = (0..n).map(|k| (k + i) % 2 == 1).collect();
+        write(&spec, &numeric, &format!("bool_{i}"), &data, i)?;
+
+        let ascii = |k| format!("s{i}_{k}");
+        let unicode = |k| format!("ü{i}_{k}");
+        let data = (0..n).map(|k| FixedAscii::<16>::from_ascii(&ascii(k))).collect();
+        write(&spec, &strings_group, &format!("fixed_ascii_{i}"), &strings(data)?, i)?;
+        let data = (0..n).map(|k| unicode(k).parse::<FixedUnicode<16>>()).collect();
+        write(&spec, &strings_group, &format!("fixed_unicode_{i}"), &strings(data)?, i)?;
+        let data = (0..n).map(|k| VarLenAscii::from_ascii(&ascii(k))).collect();
+        write(&spec, &strings_group, &format!("varlen_ascii_{i}"), &strings(data)?, i)?;
+        let data = (0..n).map(|k| unicode(k).parse::<VarLenUnicode>()).collect();
+        write(&spec, &strings_group, &format!("varlen_unicode_{i}"), &strings(data)?, i)?;
+
+        let data = (0..n)
+            .map(|k| {
+                let label = format!("r{i}_{k}").parse::<VarLenUnicode>();
+                Ok(FixtureRecord {
+                    index: k as _,
+                    value: k as f64 + i as f64 / 2.0,
+                    label: label.map_err(|err| err.to_string())?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        write(&spec, &compound, &format!("record_{i}"), &data, i)?;
+
+        let data: Vec<_> = (0..n)
+            .map(|k| VarLenArray::from_slice(&(0..((k + i) % 4) as i32).collect::<Vec<_>>()))
+            .collect();
+        write(&spec, &vlen, &format!("array_{i}"), &data, i)?;
+
+        if let Some(group) = &references {
+            let data = (0..n)
+                .map(|k| {
+                    let target = format!("/numeric/{}_{i}", NUMERIC_TYPES[k % NUMERIC_TYPES.len()]);
+                    file.reference::<ObjectReference1>(&target)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            write(&spec, group, &format!("object_{i}"), &data, i)?;
+        }
+
+        if let Some(group) = &filtered {
+            let data: Vec<i32> = (0..n).map(|k| (k + i) as _).collect();
+            let chunk = (n / 2).max(1);
+            let builder = group.new_dataset_builder().with_data(&data).chunk(chunk);
+            let ds = builder
+                .shuffle()
+                .fletcher32()
+                .create(format!("shuffle_fletcher32_{i}").as_str())?;
+            annotate(&spec, &ds, i)?;
+            if deflate_available() {
+                let builder = group.new_dataset_builder().with_data(&data).chunk(chunk);
+                let ds = builder.deflate(4).create(format!("deflate_{i}").as_str())?;
+                annotate(&spec, &ds, i)?;
+            }
+        }
+    }
+    Ok(file)
+}
+
+fn strings<S>(values: Vec<Result<S, hdf5_types::StringError>>) -> Result<Vec<S>> {
+    values.into_iter().map(|value| value.map_err(|err| err.to_string().into())).collect()
+}
+
+fn write<T: H5Type>(
+    spec: &FixtureSpec,
+    group: &Group,
+    name: &str,
+    data: &[T],
+    i: usize,
+) -> Result<()> {
+    let ds = group.new_dataset_builder().with_data(data).create(name)?;
+    annotate(spec, &ds, i)
+}
+
+fn annotate(spec: &FixtureSpec, ds: &Dataset, i: usize) -> Result<()> {
+    if spec.attributes {
+        ds.new_attr::<u64>().create("index")?.write_scalar(&(i as u64))?;
+        let path = ds.name().parse::<VarLenUnicode>().map_err(|err| err.to_string())?;
+        ds.new_attr::<VarLenUnicode>().create("path")?.write_scalar(&path)?;
+    }
+    Ok(())
+}
+
+/// Types of which [`gen_vec`] and [`gen_arr`] generate pseudo-random values.
+pub trait Gen: Sized + Debug {
+    /// Generates a value from `rng`; the same generator state yields the same value.
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
+}
+
+macro_rules! impl_gen_primitive {
+    ($($ty:ty),+) => {$(
+        impl Gen for $ty {
+            fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                rng.random()
+            }
+        }
+    )+};
+}
+
+impl_gen_primitive!(u8, u16, u32, u64, i8, i16, i32, i64, bool, f32, f64);
+
+macro_rules! impl_gen_tuple {
+    ($t:ident) => (
+        impl<$t> Gen for ($t,) where $t: Gen {
+            fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                (<$t as Gen>::random(rng),)
+            }
+        }
+    );
+
+    ($t:ident, $($tt:ident),*) => (
+        impl<$t, $($tt),*> Gen for ($t, $($tt),*) where $t: Gen, $($tt: Gen),* {
+            fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                (<$t as Gen>::random(rng), $(<$tt as Gen>::random(rng)),*)
+            }
+        }
+        impl_gen_tuple!($($tt),*);
+    );
+}
+
+impl_gen_tuple! { A, B, C, D, E, F, G, H, I, J, K, L }
+
+#[cfg(feature = "f16")]
+impl Gen for half::f16 {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::from_f32(rng.random())
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T: Debug> Gen for num_complex::Complex<T>
+where
+    rand::distr::StandardUniform: rand::distr::Distribution<T>,
+{
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::new(rng.random(), rng.random())
+    }
+}
+
+/// Generates random ASCII bytes up to 127, of a random length of at most `max_len`.
+fn gen_ascii_bytes<R: Rng + ?Sized>(rng: &mut R, max_len: usize) -> Vec<u8> {
+    let len = rng.random_range(0..=max_len);
+    iter::repeat_with(|| rng.random_range(0..=127)).take(len).collect()
+}
+
+impl<const N: usize> Gen for FixedAscii<N> {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        unsafe { Self::from_ascii_unchecked(&gen_ascii_bytes(rng, N)) }
+    }
+}
+
+impl<const N: usize> Gen for FixedUnicode<N> {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.random_range(0..=N);
+        let mut s = String::new();
+        for _ in 0..len {
+            let c = rng.random::<char>();
+            if c != '\0' {
+                if s.len() + c.len_utf8() >= len {
+                    break;
+                }
+                s.push(c);
+            }
+        }
+        unsafe { Self::from_str_unchecked(s) }
+    }
+}
+
+impl Gen for VarLenAscii {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        unsafe { Self::from_ascii_unchecked(&gen_ascii_bytes(rng, 8)) }
+    }
+}
+
+impl Gen for VarLenUnicode {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.random_range(0..=8);
+        let mut s = String::new();
+        while s.len() < len {
+            let c = rng.random::<char>();
+            if c != '\0' {
+                s.push(c);
+            }
+        }
+        unsafe { Self::from_str_unchecked(s) }
+    }
+}
+
+impl<T: Gen + Copy> Gen for VarLenArray<T> {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.random_range(0..=8);
+        Self::from_slice(&gen_vec::<R, T>(rng, len))
+    }
+}
+
+/// Generates a shape of `ndim` axes of random lengths up to 10.
+pub fn gen_shape<R: Rng + ?Sized>(rng: &mut R, ndim: usize) -> Vec<usize> {
+    iter::repeat_with(|| rng.random_range(0..11)).take(ndim).collect()
+}
+
+/// Generates a random alphanumeric string of length `len`.
+pub fn gen_ascii<R: Rng + ?Sized>(rng: &mut R, len: usize) -> String {
+    iter::repeat_with(|| char::from(rng.sample(Alphanumeric))).take(len).collect()
+}
+
+/// Generates `size` random values.
+pub fn gen_vec<R: Rng + ?Sized, T: Gen>(rng: &mut R, size: usize) -> Vec<T> {
+    iter::repeat_with(|| T::random(rng)).take(size).collect()
+}
+
+/// Generates an array of `ndim` axes with a random shape (see [`gen_shape`]) and values.
+pub fn gen_arr<T, R>(rng: &mut R, ndim: usize) -> ArrayD<T>
+where
+    T: H5Type + Gen,
+    R: Rng + ?Sized,
+{
+    let shape = gen_shape(rng, ndim);
+    ArrayD::from_shape_simple_fn(shape, || T::random(rng))
+}
@@ -0,0 +1,180 @@
+//! Flushing a file periodically from a background thread.
+//!
+//! A long-running writer (e.g. a SWMR writer appending to a log) that never flushes
+//! explicitly only makes its data visible to readers when the library evicts it from its
+//! caches. [`FlushDaemon`] flushes the file at a fixed interval instead:
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use std::time::Duration;
+//!
+//! use hdf5_rt::{File, FlushDaemon};
+//!
+//! let file = File::create("log.h5")?;
+//! let daemon = FlushDaemon::new(Duration::from_secs(1))
+//!     .on_error(|err| eprintln!("flush failed: {err}"))
+//!     .spawn(&file)?;
+//! // ... write to the file ...
+//! daemon.stop()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The daemon does not keep the file open: it stops on its own once all the [`File`] handles
+//! to it are closed, even if objects opened from the file keep it open. Each flush takes the
+//! library lock, so it never runs in the middle of another call into the library.
+
+use std::fmt::{self, Debug};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::internal_prelude::*;
+
+type ErrorCallback = Box<dyn FnMut(Error) + Send>;
+
+/// Configuration of a background thread flushing a file, see the
+/// [module documentation](self).
+pub struct FlushDaemon {
+    interval: Duration,
+    scope: FlushScope,
+    on_error: Option<ErrorCallback>,
+}
+
+impl Debug for FlushDaemon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlushDaemon")
+            .field("interval", &self.interval)
+            .field("scope", &self.scope)
+            .field("on_error", &self.on_error.is_some())
+            .finish()
+    }
+}
+
+impl FlushDaemon {
+    /// Starts flushing `file` with [`FlushScope::Global`] every `interval`, ignoring errors.
+    pub fn start(file: &File, interval: Duration) -> Result<FlushHandle> {
+        Self::new(interval).spawn(file)
+    }
+
+    /// Creates a daemon flushing with [`FlushScope::Global`] every `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, scope: FlushScope::Global, on_error: None }
+    }
+
+    /// Sets the scope of the flushes.
+    pub fn scope(mut self, scope: FlushScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Sets a callback receiving the errors of failed flushes, which are ignored otherwise.
+    ///
+    /// The callback runs on the daemon thread, without holding the library lock.
+    pub fn on_error<F: FnMut(Error) + Send + 'static>(mut self, callback: F) -> Self {
+        self.on_error = Some(Box::new(callback));
+        self
+    }
+
+    /// Starts the daemon thread for `file`.
+    ///
+    /// The first flush happens one interval after starting, and each interval is counted
+    /// from the end of the previous flush.
+    pub fn spawn(self, file: &File) -> Result<FlushHandle> {
+        ensure!(!self.interval.is_zero(), "flush interval must be positive");
+        let target = WeakFile { id: file.id(), fileno: file.object_id()?.fileno() };
+        let flushes = Arc::new(AtomicU64::new(0));
+        let (stop, stopped) = mpsc::channel::<()>();
+        let Self { interval, scope, mut on_error } = self;
+        let counter = Arc::clone(&flushes);
+        let thread = thread::Builder::new()
+            .name("hdf5-flush".into())
+            .spawn(move || {
+                // a message or a disconnected channel both mean stop
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    let res = h5lock!({
+                        let file = target.upgrade()?;
+                        let res = file.flush_scope(scope);
+                        // counted once the flush has returned, so that waiting for the count
+                        // to grow waits for a whole flush
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        Some(res)
+                    });
+                    match res {
+                        None => break,
+                        Some(Err(err)) => {
+                            if let Some(callback) = on_error.as_mut() {
+                                callback(err);
+                            }
+                        }
+                        Some(Ok(())) => {}
+                    }
+                }
+            })
+            .map_err(|err| format!("failed to spawn the flush thread: {err}"))?;
+        Ok(FlushHandle { stop: Some(stop), thread: Some(thread), flushes })
+    }
+}
+
+/// Identifier of a file that is not kept open.
+struct WeakFile {
+    id: hid_t,
+    fileno: u64,
+}
+
+impl WeakFile {
+    /// Returns a new handle to the file if some handle to it is still open; must be called
+    /// with the library lock held so that the file can't be closed in between.
+    fn upgrade(&self) -> Option<File> {
+        let file = File::from_handle(Handle::try_borrow(self.id).ok()?);
+        // guards against the identifier having been reused for another file
+        let same = file.handle().id_type() == H5I_FILE
+            && file.object_id().is_ok_and(|id| id.fileno() == self.fileno);
+        same.then_some(file)
+    }
+}
+
+/// A running [`FlushDaemon`]; dropping it stops the daemon and waits for its thread.
+#[derive(Debug)]
+pub struct FlushHandle {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+    flushes: Arc<AtomicU64>,
+}
+
+impl FlushHandle {
+    /// Returns the number of flushes completed so far, including failed ones.
+    pub fn flush_count(&self) -> u64 {
+        self.flushes.load(Ordering::SeqCst)
+    }
+
+    /// Returns `true` until the daemon thread exits, either when stopped or when the file is
+    /// closed.
+    pub fn is_running(&self) -> bool {
+        self.thread.as_ref().is_some_and(|thread| !thread.is_finished())
+    }
+
+    /// Stops the daemon and waits for its thread to exit. A flush in progress is completed.
+    ///
+    /// Fails if the error callback panicked.
+    pub fn stop(mut self) -> Result<()> {
+        self.shutdown()
+    }
+
+    fn shutdown(&mut self) -> Result<()> {
+        // dropping the sender wakes the thread up
+        drop(self.stop.take());
+        match self.thread.take().map(JoinHandle::join) {
+            Some(Err(_)) => fail!("the flush thread panicked"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for FlushHandle {
+    fn drop(&mut self) {
+        let _ = self.shutdown();
+    }
+}
@@ -1,39 +1,71 @@
 #![allow(dead_code)]
 
 use std::mem;
+use std::ops::Deref;
 use std::sync::LazyLock;
 
 #[cfg(feature = "have-direct")]
 use crate::sys::h5p::H5Pset_fapl_direct;
 use crate::sys::h5p::{
-    H5Pclose, H5Pcreate, H5Pget_driver, H5Pset_fapl_core, H5Pset_fapl_family, H5Pset_fapl_log,
+    H5Pcreate, H5Pget_driver, H5Pset_fapl_core, H5Pset_fapl_family, H5Pset_fapl_log,
     H5Pset_fapl_multi, H5Pset_fapl_sec2, H5Pset_fapl_stdio,
 };
-use crate::sys::{h5e, h5p, h5t};
 
 use crate::internal_prelude::*;
 
-// Runtime-loading mode: use LazyLock with function calls
+/// An identifier exported as a global by the HDF5 library, such as a predefined datatype or a
+/// property list class, loaded on first use.
+///
+/// If the symbol cannot be loaded, [`get`](Self::get) returns an error naming it and the next
+/// access tries again. Dereferencing yields `H5I_INVALID_HID` in that case, so that library
+/// calls given the identifier fail with an HDF5 error instead of panicking.
+pub struct GlobalId {
+    load: fn() -> Result<&'static hid_t, String>,
+}
+
+impl GlobalId {
+    const fn new(load: fn() -> Result<&'static hid_t, String>) -> Self {
+        Self { load }
+    }
+
+    fn try_load(&self) -> Result<&'static hid_t> {
+        // Ensure the library is initialized
+        LazyLock::force(&crate::sync::LIBRARY_INIT);
+        (self.load)().map_err(Error::from)
+    }
+
+    /// Returns the identifier, or an error if the library does not export it.
+    pub fn get(&self) -> Result<hid_t> {
+        self.try_load().copied()
+    }
+}
+
+impl Deref for GlobalId {
+    type Target = hid_t;
+
+    fn deref(&self) -> &hid_t {
+        self.try_load().unwrap_or(&H5I_INVALID_HID)
+    }
+}
+
+// Runtime-loading mode: identifiers are resolved through the loaded library (the module
+// prefix only documents where the symbol is declared)
 macro_rules! link_hid {
-    ($rust_name:ident, $c_name:path) => {
-        pub static $rust_name: LazyLock<hid_t> = LazyLock::new(|| {
-            // Ensure the library is initialized
-            LazyLock::force(&crate::sync::LIBRARY_INIT);
-            $c_name()
-        });
+    ($rust_name:ident, $module:ident::$c_name:ident) => {
+        paste::paste! {
+            pub static $rust_name: GlobalId = GlobalId::new(crate::sys::[<$c_name _try_get>]);
+        }
     };
 }
 
 /// Fetches the driver ID using the workaround from https://github.com/HDFGroup/hdf5/issues/1809
 /// as the _init functions seem to be removed in HDF5 2.0.0
-macro_rules! get_driver {
-    ($set_driver:expr) => {{
-        let fapl = h5call!(H5Pcreate(*H5P_FILE_ACCESS)).expect("should always create FAPL");
-        h5call!($set_driver(fapl)).expect("should always be able to set the driver");
-        let id = h5call!(H5Pget_driver(fapl)).expect("should always be able to extract the driver");
-        h5call!(H5Pclose(fapl)).expect("should always be able to close the FAPL");
-        id
-    }};
+fn get_driver(set_driver: impl FnOnce(hid_t) -> herr_t) -> Result<hid_t> {
+    h5lock!({
+        let fapl = PropertyList::from_id(h5try!(H5Pcreate(H5P_FILE_ACCESS.get()?)))?;
+        h5try!(set_driver(fapl.id()));
+        Ok(h5try!(H5Pget_driver(fapl.id())))
+    })
 }
 
 // Datatypes
@@ -207,6 +239,7 @@ link_hid!(H5E_CANTCOPY, h5e::H5E_CANTCOPY);
 link_hid!(H5E_CANTFREE, h5e::H5E_CANTFREE);
 link_hid!(H5E_ALREADYEXISTS, h5e::H5E_ALREADYEXISTS);
 link_hid!(H5E_CANTLOCK, h5e::H5E_CANTLOCK);
+link_hid!(H5E_CANTLOCKFILE, h5e::H5E_CANTLOCKFILE);
 link_hid!(H5E_CANTUNLOCK, h5e::H5E_CANTUNLOCK);
 link_hid!(H5E_CANTGC, h5e::H5E_CANTGC);
 link_hid!(H5E_CANTGETSIZE, h5e::H5E_CANTGETSIZE);
@@ -319,25 +352,34 @@ pub static H5R_DSET_REG_REF_BUF_SIZE: LazyLock<usize> =
     LazyLock::new(|| mem::size_of::<haddr_t>() + 4);
 
 // File drivers
-pub static H5FD_CORE: LazyLock<hid_t> =
-    LazyLock::new(|| h5lock!(get_driver!(|fapl| H5Pset_fapl_core(fapl, 0, 0))));
-pub static H5FD_SEC2: LazyLock<hid_t> =
-    LazyLock::new(|| h5lock!(get_driver!(|fapl| H5Pset_fapl_sec2(fapl))));
-pub static H5FD_STDIO: LazyLock<hid_t> =
-    LazyLock::new(|| h5lock!(get_driver!(|fapl| H5Pset_fapl_stdio(fapl))));
-pub static H5FD_FAMILY: LazyLock<hid_t> =
-    LazyLock::new(|| h5lock!(get_driver!(|fapl| H5Pset_fapl_family(fapl, 0, 0))));
-pub static H5FD_LOG: LazyLock<hid_t> =
-    LazyLock::new(|| h5lock!(get_driver!(|fapl| H5Pset_fapl_log(fapl, std::ptr::null(), 0, 0))));
+pub static H5FD_CORE: LazyLock<hid_t> = LazyLock::new(|| {
+    get_driver(|fapl| unsafe { H5Pset_fapl_core(fapl, 0, 0) }).unwrap_or(H5I_INVALID_HID)
+});
+pub static H5FD_SEC2: LazyLock<hid_t> = LazyLock::new(|| {
+    get_driver(|fapl| unsafe { H5Pset_fapl_sec2(fapl) }).unwrap_or(H5I_INVALID_HID)
+});
+pub static H5FD_STDIO: LazyLock<hid_t> = LazyLock::new(|| {
+    get_driver(|fapl| unsafe { H5Pset_fapl_stdio(fapl) }).unwrap_or(H5I_INVALID_HID)
+});
+pub static H5FD_FAMILY: LazyLock<hid_t> = LazyLock::new(|| {
+    get_driver(|fapl| unsafe { H5Pset_fapl_family(fapl, 0, 0) }).unwrap_or(H5I_INVALID_HID)
+});
+pub static H5FD_LOG: LazyLock<hid_t> = LazyLock::new(|| {
+    get_driver(|fapl| unsafe { H5Pset_fapl_log(fapl, std::ptr::null(), 0, 0) })
+        .unwrap_or(H5I_INVALID_HID)
+});
 pub static H5FD_MULTI: LazyLock<hid_t> = LazyLock::new(|| {
-    h5lock!(get_driver!(|fapl| H5Pset_fapl_multi(
-        fapl,
-        std::ptr::null(),
-        std::ptr::null(),
-        std::ptr::null(),
-        std::ptr::null(),
-        0
-    )))
+    get_driver(|fapl| unsafe {
+        H5Pset_fapl_multi(
+            fapl,
+            std::ptr::null(),
+            std::ptr::null(),
+            std::ptr::null(),
+            std::ptr::null(),
+            0,
+        )
+    })
+    .unwrap_or(H5I_INVALID_HID)
 });
 
 // MPI-IO file driver (not supported in runtime-loading mode)
@@ -345,8 +387,9 @@ pub static H5FD_MPIO: LazyLock<hid_t> = LazyLock::new(|| H5I_INVALID_HID);
 
 // Direct VFD
 #[cfg(feature = "have-direct")]
-pub static H5FD_DIRECT: LazyLock<hid_t> =
-    LazyLock::new(|| h5lock!(get_driver!(|fapl| H5Pset_fapl_direct(fapl, 0, 0, 0))));
+pub static H5FD_DIRECT: LazyLock<hid_t> = LazyLock::new(|| {
+    get_driver(|fapl| unsafe { H5Pset_fapl_direct(fapl, 0, 0, 0) }).unwrap_or(H5I_INVALID_HID)
+});
 #[cfg(not(feature = "have-direct"))]
 pub static H5FD_DIRECT: LazyLock<hid_t> = LazyLock::new(|| H5I_INVALID_HID);
 
@@ -357,11 +400,13 @@ pub static H5FD_WINDOWS: LazyLock<hid_t> = LazyLock::new(|| *H5FD_SEC2);
 mod tests {
     use std::mem;
 
-    use crate::sys::{h5::haddr_t, h5i::H5I_INVALID_HID};
+    use crate::internal_prelude::*;
+    use crate::plist::{DatasetAccess, DatasetCreate};
+    use crate::sys::{h5::haddr_t, set_failing_symbols};
 
     use super::{
-        H5E_DATASET, H5E_ERR_CLS, H5P_LST_LINK_ACCESS_ID, H5P_ROOT, H5R_DSET_REG_REF_BUF_SIZE,
-        H5R_OBJ_REF_BUF_SIZE, H5T_IEEE_F32BE, H5T_NATIVE_INT,
+        H5E_DATASET, H5E_ERR_CLS, H5P_DATASET_ACCESS, H5P_LST_LINK_ACCESS_ID, H5P_ROOT,
+        H5R_DSET_REG_REF_BUF_SIZE, H5R_OBJ_REF_BUF_SIZE, H5T_IEEE_F32BE, H5T_NATIVE_INT,
     };
 
     #[test]
@@ -378,4 +423,22 @@ mod tests {
         assert_eq!(*H5R_OBJ_REF_BUF_SIZE, mem::size_of::<haddr_t>());
         assert_eq!(*H5R_DSET_REG_REF_BUF_SIZE, mem::size_of::<haddr_t>() + 4);
     }
+
+    #[test]
+    pub fn test_missing_global_symbol() {
+        let symbol = "H5P_CLS_DATASET_ACCESS_ID_g";
+        set_failing_symbols(&[symbol]);
+        let err = H5P_DATASET_ACCESS.get().unwrap_err();
+        assert!(err.to_string().contains(symbol), "{err}");
+        assert_eq!(*H5P_DATASET_ACCESS, H5I_INVALID_HID);
+        let err = DatasetAccess::try_new().unwrap_err();
+        assert!(err.to_string().contains(symbol), "{err}");
+        assert_err!(DatasetAccess::build().finish(), symbol);
+        assert!(DatasetCreate::try_new().is_ok());
+
+        // the symbol is resolved again once it can be loaded
+        set_failing_symbols(&[]);
+        assert!(DatasetAccess::try_new().is_ok());
+        assert_ne!(*H5P_DATASET_ACCESS, H5I_INVALID_HID);
+    }
 }
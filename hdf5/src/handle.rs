@@ -1,20 +1,40 @@
 use std::mem;
+use std::sync::{Arc, OnceLock};
 
-use crate::sys::h5i::{H5I_type_t, H5Idec_ref, H5Iget_ref, H5Iget_type, H5Iinc_ref, H5Iis_valid};
+use parking_lot::Mutex;
 
+use crate::sys::h5f::{H5Fclose, H5Fget_intent, H5Fget_name, H5F_ACC_RDWR};
+use crate::sys::h5i::{
+    H5I_type_t, H5Idec_ref, H5Iget_file_id, H5Iget_ref, H5Iget_type, H5Iinc_ref, H5Iis_valid,
+};
+
+use crate::hl::io_stats::IoRecorder;
 use crate::internal_prelude::*;
 
 /// A handle to an HDF5 object
 #[derive(Debug)]
 pub struct Handle {
     id: hid_t,
+    /// The library generation the handle was created in, see [`raw::shutdown`](crate::raw::shutdown).
+    generation: u64,
+    /// Whether the object belongs to a file opened without write intent, once queried.
+    read_only: OnceLock<bool>,
+    /// I/O statistics recorder, once enabled via `Dataset::enable_io_stats()`.
+    io_stats: OnceLock<Arc<Mutex<IoRecorder>>>,
 }
 
 impl Handle {
     /// Create a handle from object ID, taking ownership of it
     pub fn try_new(id: hid_t) -> Result<Self> {
-        let handle = Self { id };
+        if crate::sys::is_shut_down() {
+            return Err(Error::LibraryShutDown);
+        }
+        let generation = crate::sys::generation();
+        let handle = Self { id, generation, read_only: OnceLock::new(), io_stats: OnceLock::new() };
         if handle.is_valid_user_id() {
+            if crate::diagnostics::is_enabled() {
+                crate::diagnostics::record(id);
+            }
             Ok(handle)
         } else {
             // Drop on an invalid handle could cause closing an unrelated object
@@ -33,16 +53,28 @@ impl Handle {
     }
 
     pub const fn invalid() -> Self {
-        Self { id: H5I_INVALID_HID }
+        Self {
+            id: H5I_INVALID_HID,
+            generation: 0,
+            read_only: OnceLock::new(),
+            io_stats: OnceLock::new(),
+        }
     }
 
     pub const fn id(&self) -> hid_t {
         self.id
     }
 
+    /// Returns `true` if the library has been shut down since the handle was created, in which
+    /// case its identifier must not be passed to the library anymore.
+    #[inline]
+    pub fn is_stale(&self) -> bool {
+        crate::sys::generation() != self.generation
+    }
+
     /// Increment the reference count of the handle
     pub fn incref(&self) {
-        if self.is_valid_user_id() {
+        if !self.is_stale() && self.is_valid_user_id() {
             h5lock!(H5Iinc_ref(self.id));
         }
     }
@@ -53,7 +85,8 @@ impl Handle {
     /// previously called.
     pub fn decref(&self) {
         h5lock!({
-            if self.is_valid_id() {
+            // the identifier was closed along with the library, which mustn't be called anymore
+            if !self.is_stale() && self.is_valid_id() {
                 H5Idec_ref(self.id);
             }
         });
@@ -62,7 +95,7 @@ impl Handle {
     /// Returns `true` if the object has a valid unlocked identifier (`false` for pre-defined
     /// locked identifiers like property list classes).
     pub fn is_valid_user_id(&self) -> bool {
-        h5lock!(H5Iis_valid(self.id)) == 1
+        !self.is_stale() && h5lock!(H5Iis_valid(self.id)) == 1
     }
 
     pub fn is_valid_id(&self) -> bool {
@@ -74,10 +107,50 @@ impl Handle {
         h5call!(H5Iget_ref(self.id)).map(|x| x as _).unwrap_or(0) as _
     }
 
+    /// Returns `true` if the object belongs to a file opened without write intent.
+    ///
+    /// The file intent is queried once and cached for the lifetime of the handle. Objects not
+    /// belonging to a file (e.g. transient datatypes) are never read-only.
+    pub(crate) fn is_in_read_only_file(&self) -> bool {
+        *self.read_only.get_or_init(|| {
+            h5lock!({
+                let Ok(file_id) = h5call!(H5Iget_file_id(self.id)) else {
+                    return false;
+                };
+                let intent = h5get!(H5Fget_intent(file_id): c_uint);
+                H5Fclose(file_id);
+                intent.is_ok_and(|intent| intent & H5F_ACC_RDWR == 0)
+            })
+        })
+    }
+
+    /// Fails with [`Error::ReadOnly`] if the object belongs to a file opened without write
+    /// intent, so that mutating operations fail before calling into the library.
+    pub(crate) fn ensure_writable(&self) -> Result<()> {
+        if self.is_stale() {
+            return Err(Error::LibraryShutDown);
+        }
+        if self.is_in_read_only_file() {
+            let file = h5lock!(get_h5_str(|m, s| H5Fget_name(self.id, m, s))).unwrap_or_default();
+            return Err(Error::ReadOnly { file });
+        }
+        Ok(())
+    }
+
+    /// Returns the I/O statistics recorder, if enabled.
+    pub(crate) fn io_recorder(&self) -> Option<&Arc<Mutex<IoRecorder>>> {
+        self.io_stats.get()
+    }
+
+    /// Enables I/O statistics, keeping the existing recorder if already enabled.
+    pub(crate) fn enable_io_recorder(&self, init: impl FnOnce() -> IoRecorder) {
+        self.io_stats.get_or_init(|| Arc::new(Mutex::new(init())));
+    }
+
     /// Get HDF5 object type as a native enum.
     /// This function handles the enum value differences between HDF5 versions.
     pub fn id_type(&self) -> H5I_type_t {
-        if self.id <= 0 {
+        if self.id <= 0 || self.is_stale() {
             H5I_BADID
         } else {
             let raw = h5lock!(H5Iget_type(self.id));
@@ -89,12 +162,19 @@ impl Handle {
 
 impl Clone for Handle {
     fn clone(&self) -> Self {
-        Self::try_borrow(self.id).unwrap_or_else(|_| Self::invalid())
+        let handle = Self::try_borrow(self.id).unwrap_or_else(|_| Self::invalid());
+        if let Some(recorder) = self.io_stats.get() {
+            let _ = handle.io_stats.set(Arc::clone(recorder));
+        }
+        handle
     }
 }
 
 impl Drop for Handle {
     fn drop(&mut self) {
+        if crate::diagnostics::is_enabled() {
+            crate::diagnostics::release(self.id);
+        }
         h5lock!(self.decref());
     }
 }
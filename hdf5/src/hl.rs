@@ -1,33 +1,60 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod attribute;
+#[cfg(feature = "checksum")]
+pub mod checksum;
 pub mod chunks;
+pub mod columns;
 pub mod container;
+pub mod copy;
 pub mod dataset;
 pub mod dataspace;
 pub mod datatype;
 pub mod extents;
 pub mod file;
+pub mod file_image;
+pub mod file_info;
 pub mod filters;
 pub mod group;
+pub mod io_stats;
 pub mod location;
+pub mod metadata;
 pub mod object;
 pub mod plist;
 pub mod references;
+pub mod row_groups;
 pub mod selection;
+pub mod spec;
+pub mod strings;
+#[cfg(feature = "time")]
+mod time;
+pub mod tree;
+pub mod typed;
+#[cfg(feature = "uuid")]
+mod uuid;
+pub mod views;
 
 pub use self::{
     attribute::{
         Attribute, AttributeBuilder, AttributeBuilderData, AttributeBuilderEmpty,
-        AttributeBuilderEmptyShape,
+        AttributeBuilderEmptyShape, AttributeBuilderIter,
     },
     container::{ByteReader, Container, Reader, Writer},
+    copy::CopyOptions,
     dataset::{
         Dataset, DatasetBuilder, DatasetBuilderData, DatasetBuilderEmpty, DatasetBuilderEmptyShape,
+        DatasetBuilderIter, DatasetBuilderStrings,
     },
     dataspace::Dataspace,
     datatype::{Conversion, Datatype},
-    file::{File, FileBuilder, OpenMode},
-    group::{Group, LinkInfo, LinkType},
-    location::{Location, LocationInfo, LocationToken, LocationType},
+    file::{File, FileBuilder, FlushScope, OpenMode, OpenObject, OpenObjectKind, RepackOptions},
+    file_image::BorrowedImageFile,
+    file_info::FormatInfo,
+    group::{
+        DeterministicOrder, Group, GroupBuilder, GroupMembers, GroupStorageInfo, GroupStorageType,
+        IterationOrder, LinkInfo, LinkType, ObjectHandleKind, TraversalOrder,
+    },
+    location::{Location, LocationInfo, LocationToken, LocationType, ObjectId},
     object::Object,
     plist::PropertyList,
 };
@@ -0,0 +1,374 @@
+//! Conversion to and from Apache Arrow arrays (`arrow` feature).
+
+use std::sync::Arc;
+
+use arrow_array::types::{
+    ArrowPrimitiveType, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+    UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, FixedSizeListArray, LargeStringArray, PrimitiveArray,
+    RecordBatch, RecordBatchOptions, StringArray,
+};
+use arrow_schema::{DataType, Field, Schema};
+use hdf5_types::{FloatSize, IntSize, TypeDescriptor, VarLenAscii, VarLenUnicode};
+
+use crate::internal_prelude::*;
+
+/// Returns the Arrow type that elements of the given type are converted to.
+fn arrow_type(desc: &TypeDescriptor) -> Result<DataType> {
+    use TypeDescriptor as TD;
+    Ok(match desc {
+        TD::Integer(IntSize::U1) => DataType::Int8,
+        TD::Integer(IntSize::U2) => DataType::Int16,
+        TD::Integer(IntSize::U4) => DataType::Int32,
+        TD::Integer(IntSize::U8) => DataType::Int64,
+        TD::Unsigned(IntSize::U1) => DataType::UInt8,
+        TD::Unsigned(IntSize::U2) => DataType::UInt16,
+        TD::Unsigned(IntSize::U4) => DataType::UInt32,
+        TD::Unsigned(IntSize::U8) => DataType::UInt64,
+        TD::Float(FloatSize::U4) => DataType::Float32,
+        TD::Float(FloatSize::U8) => DataType::Float64,
+        TD::Boolean => DataType::Boolean,
+        TD::FixedAscii(_) | TD::FixedUnicode(_) => DataType::Utf8,
+        TD::VarLenAscii | TD::VarLenUnicode => DataType::LargeUtf8,
+        TD::FixedArray(inner, len) if !matches!(**inner, TD::FixedArray(..)) => {
+            let size = i32::try_from(*len).map_err(|_| format!("array too long: {len}"))?;
+            DataType::FixedSizeList(Arc::new(Field::new("item", arrow_type(inner)?, true)), size)
+        }
+        _ => fail!("unsupported type for Arrow conversion: {}", desc),
+    })
+}
+
+/// Returns the HDF5 type that values of the given Arrow type are stored as.
+fn h5_type(dtype: &DataType) -> Result<TypeDescriptor> {
+    use TypeDescriptor as TD;
+    Ok(match dtype {
+        DataType::Int8 => TD::Integer(IntSize::U1),
+        DataType::Int16 => TD::Integer(IntSize::U2),
+        DataType::Int32 => TD::Integer(IntSize::U4),
+        DataType::Int64 => TD::Integer(IntSize::U8),
+        DataType::UInt8 => TD::Unsigned(IntSize::U1),
+        DataType::UInt16 => TD::Unsigned(IntSize::U2),
+        DataType::UInt32 => TD::Unsigned(IntSize::U4),
+        DataType::UInt64 => TD::Unsigned(IntSize::U8),
+        DataType::Float32 => TD::Float(FloatSize::U4),
+        DataType::Float64 => TD::Float(FloatSize::U8),
+        DataType::Boolean => TD::Boolean,
+        DataType::Utf8 | DataType::LargeUtf8 => TD::VarLenUnicode,
+        DataType::FixedSizeList(field, len)
+            if field.data_type().is_primitive() || field.data_type() == &DataType::Boolean =>
+        {
+            TD::FixedArray(Box::new(h5_type(field.data_type())?), *len as _)
+        }
+        _ => fail!("unsupported Arrow type: {}", dtype),
+    })
+}
+
+/// Reads `count` elements starting at `start` as `elem_len` values of `T` each, converting
+/// them to the memory type `mem_type` (whose elements must be laid out as `[T; elem_len]`).
+fn read_raw_block<T: Copy + Default>(
+    ds: &Dataset,
+    mem_type: &Datatype,
+    start: usize,
+    count: usize,
+    elem_len: usize,
+) -> Result<Vec<T>> {
+    let mut buf = vec![T::default(); count * elem_len];
+    if count == 0 {
+        return Ok(buf);
+    }
+    let selection = SliceOrIndex::SliceCount { start, step: 1, count, block: 1 };
+    let fspace = ds.space()?.select(Hyperslab::from(vec![selection]))?;
+    let mspace = Dataspace::try_new(count)?;
+    ds.as_reader().read_into_buf_as(
+        buf.as_mut_ptr().cast(),
+        mem_type,
+        Some(&fspace),
+        Some(&mspace),
+    )?;
+    Ok(buf)
+}
+
+/// Reads a block of elements of the memory type `mem_type`, laid out as `[A::Native; elem_len]`.
+fn read_primitive<A>(
+    ds: &Dataset,
+    mem_type: &Datatype,
+    start: usize,
+    count: usize,
+    elem_len: usize,
+) -> Result<ArrayRef>
+where
+    A: ArrowPrimitiveType,
+    A::Native: Default,
+{
+    let values = read_raw_block::<A::Native>(ds, mem_type, start, count, elem_len)?;
+    Ok(Arc::new(PrimitiveArray::<A>::new(values.into(), None)))
+}
+
+/// Reads the values of a fixed array dataset with memory type `mem_type`, flattened.
+fn read_flat(
+    ds: &Dataset,
+    mem_type: &Datatype,
+    inner: &TypeDescriptor,
+    len: usize,
+    start: usize,
+    count: usize,
+) -> Result<ArrayRef> {
+    macro_rules! flat {
+        ($ty:ty) => {
+            read_primitive::<$ty>(ds, mem_type, start, count, len)?
+        };
+    }
+    Ok(match arrow_type(inner)? {
+        DataType::Int8 => flat!(Int8Type),
+        DataType::Int16 => flat!(Int16Type),
+        DataType::Int32 => flat!(Int32Type),
+        DataType::Int64 => flat!(Int64Type),
+        DataType::UInt8 => flat!(UInt8Type),
+        DataType::UInt16 => flat!(UInt16Type),
+        DataType::UInt32 => flat!(UInt32Type),
+        DataType::UInt64 => flat!(UInt64Type),
+        DataType::Float32 => flat!(Float32Type),
+        DataType::Float64 => flat!(Float64Type),
+        DataType::Boolean => {
+            let values = read_raw_block::<bool>(ds, mem_type, start, count, len)?;
+            Arc::new(BooleanArray::from(values))
+        }
+        _ => fail!("unsupported array element type for Arrow conversion: {}", inner),
+    })
+}
+
+fn read_block(ds: &Dataset, desc: &TypeDescriptor, start: usize, count: usize) -> Result<ArrayRef> {
+    use TypeDescriptor as TD;
+    let dtype = arrow_type(desc)?;
+    let mem_type = Datatype::from_descriptor(desc)?;
+    Ok(match (&dtype, desc) {
+        (DataType::Int8, _) => read_primitive::<Int8Type>(ds, &mem_type, start, count, 1)?,
+        (DataType::Int16, _) => read_primitive::<Int16Type>(ds, &mem_type, start, count, 1)?,
+        (DataType::Int32, _) => read_primitive::<Int32Type>(ds, &mem_type, start, count, 1)?,
+        (DataType::Int64, _) => read_primitive::<Int64Type>(ds, &mem_type, start, count, 1)?,
+        (DataType::UInt8, _) => read_primitive::<UInt8Type>(ds, &mem_type, start, count, 1)?,
+        (DataType::UInt16, _) => read_primitive::<UInt16Type>(ds, &mem_type, start, count, 1)?,
+        (DataType::UInt32, _) => read_primitive::<UInt32Type>(ds, &mem_type, start, count, 1)?,
+        (DataType::UInt64, _) => read_primitive::<UInt64Type>(ds, &mem_type, start, count, 1)?,
+        (DataType::Float32, _) => read_primitive::<Float32Type>(ds, &mem_type, start, count, 1)?,
+        (DataType::Float64, _) => read_primitive::<Float64Type>(ds, &mem_type, start, count, 1)?,
+        (DataType::Boolean, _) => {
+            let values = read_raw_block::<bool>(ds, &mem_type, start, count, 1)?;
+            Arc::new(BooleanArray::from(values))
+        }
+        (_, TD::VarLenUnicode) => {
+            let values = ds.read_slice_1d::<VarLenUnicode, _>(start..start + count)?;
+            Arc::new(LargeStringArray::from_iter_values(values.iter().map(VarLenUnicode::as_str)))
+        }
+        (_, TD::VarLenAscii) => {
+            let values = ds.read_slice_1d::<VarLenAscii, _>(start..start + count)?;
+            Arc::new(LargeStringArray::from_iter_values(values.iter().map(VarLenAscii::as_str)))
+        }
+        (_, TD::FixedAscii(len) | TD::FixedUnicode(len)) => {
+            let bytes = read_raw_block::<u8>(ds, &mem_type, start, count, *len)?;
+            let strings = bytes
+                .chunks(*len)
+                .map(|s| {
+                    let end = s.iter().rposition(|&c| c != 0).map_or(0, |i| i + 1);
+                    std::str::from_utf8(&s[..end]).map_err(|err| format!("invalid string: {err}"))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Arc::new(StringArray::from(strings))
+        }
+        (DataType::FixedSizeList(field, size), TD::FixedArray(inner, len)) => {
+            let values = read_flat(ds, &mem_type, inner, *len, start, count)?;
+            let array = FixedSizeListArray::try_new(field.clone(), *size, values, None)
+                .map_err(|err| err.to_string())?;
+            Arc::new(array)
+        }
+        _ => fail!("unsupported type for Arrow conversion: {}", desc),
+    })
+}
+
+fn ensure_1d(ds: &Dataset) -> Result<usize> {
+    let shape = ds.shape()?;
+    ensure!(shape.len() == 1, "expected a 1-D dataset, got shape {:?}", shape);
+    Ok(shape[0])
+}
+
+/// Iterator over consecutive blocks of a dataset as Arrow arrays, see
+/// [`Dataset::read_arrow_batches`].
+#[derive(Debug)]
+pub struct ArrowBatches<'a> {
+    ds: &'a Dataset,
+    desc: TypeDescriptor,
+    batch_len: usize,
+    pos: usize,
+    len: usize,
+}
+
+impl Iterator for ArrowBatches<'_> {
+    type Item = Result<ArrayRef>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.len {
+            return None;
+        }
+        let count = self.batch_len.min(self.len - self.pos);
+        let batch = read_block(self.ds, &self.desc, self.pos, count);
+        self.pos += count;
+        Some(batch)
+    }
+}
+
+fn write_column(group: &Group, name: &str, column: &ArrayRef) -> Result<()> {
+    ensure!(column.null_count() == 0, "nulls cannot be stored in HDF5 datasets");
+    let desc = h5_type(column.data_type())?;
+    let any = column.as_any();
+    macro_rules! write {
+        ($ty:ty) => {{
+            let values =
+                any.downcast_ref::<PrimitiveArray<$ty>>().ok_or("unexpected array type")?;
+            group.new_dataset_builder().with_data(values.values().as_ref()).create(name)?;
+        }};
+    }
+    match column.data_type() {
+        DataType::Int8 => write!(Int8Type),
+        DataType::Int16 => write!(Int16Type),
+        DataType::Int32 => write!(Int32Type),
+        DataType::Int64 => write!(Int64Type),
+        DataType::UInt8 => write!(UInt8Type),
+        DataType::UInt16 => write!(UInt16Type),
+        DataType::UInt32 => write!(UInt32Type),
+        DataType::UInt64 => write!(UInt64Type),
+        DataType::Float32 => write!(Float32Type),
+        DataType::Float64 => write!(Float64Type),
+        DataType::Boolean => {
+            let values = any.downcast_ref::<BooleanArray>().ok_or("unexpected array type")?;
+            let values: Vec<bool> = values.values().iter().collect();
+            group.new_dataset_builder().with_data(&values).create(name)?;
+        }
+        DataType::Utf8 | DataType::LargeUtf8 => {
+            let strings: Vec<Option<&str>> = if let Some(values) = any.downcast_ref::<StringArray>()
+            {
+                values.iter().collect()
+            } else {
+                let values =
+                    any.downcast_ref::<LargeStringArray>().ok_or("unexpected array type")?;
+                values.iter().collect()
+            };
+            let strings = strings
+                .into_iter()
+                .map(|s| s.unwrap_or_default().parse::<VarLenUnicode>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| err.to_string())?;
+            group.new_dataset_builder().with_data(&strings).create(name)?;
+        }
+        DataType::FixedSizeList(..) => {
+            let list = any.downcast_ref::<FixedSizeListArray>().ok_or("unexpected array type")?;
+            // the child array of a sliced list is sliced as well
+            let values = list.values();
+            ensure!(values.null_count() == 0, "nulls cannot be stored in HDF5 datasets");
+            let ds = group.new_dataset_builder().empty_as(&desc).shape(list.len()).create(name)?;
+            let mem_type = Datatype::from_descriptor(&desc)?;
+            let write =
+                |ptr: *const c_void| ds.as_writer().write_from_buf_as(ptr, &mem_type, None, None);
+            let values = values.as_any();
+            macro_rules! write_flat {
+                ($ty:ty) => {{
+                    let values = values
+                        .downcast_ref::<PrimitiveArray<$ty>>()
+                        .ok_or("unexpected array type")?;
+                    write(values.values().as_ptr().cast())?;
+                }};
+            }
+            match list.value_type() {
+                DataType::Int8 => write_flat!(Int8Type),
+                DataType::Int16 => write_flat!(Int16Type),
+                DataType::Int32 => write_flat!(Int32Type),
+                DataType::Int64 => write_flat!(Int64Type),
+                DataType::UInt8 => write_flat!(UInt8Type),
+                DataType::UInt16 => write_flat!(UInt16Type),
+                DataType::UInt32 => write_flat!(UInt32Type),
+                DataType::UInt64 => write_flat!(UInt64Type),
+                DataType::Float32 => write_flat!(Float32Type),
+                DataType::Float64 => write_flat!(Float64Type),
+                DataType::Boolean => {
+                    let values =
+                        values.downcast_ref::<BooleanArray>().ok_or("unexpected array type")?;
+                    let values: Vec<bool> = values.values().iter().collect();
+                    write(values.as_ptr().cast())?;
+                }
+                dtype => fail!("unsupported Arrow type: {}", dtype),
+            }
+        }
+        dtype => fail!("unsupported Arrow type: {}", dtype),
+    }
+    Ok(())
+}
+
+impl Dataset {
+    /// Reads a 1-D dataset into an Arrow array.
+    ///
+    /// | HDF5 type                     | Arrow type              |
+    /// |-------------------------------|-------------------------|
+    /// | signed/unsigned integers      | `Int8` … `UInt64`       |
+    /// | 32/64-bit floats              | `Float32`, `Float64`    |
+    /// | booleans                      | `Boolean`               |
+    /// | fixed-length strings          | `Utf8`                  |
+    /// | variable-length strings       | `LargeUtf8`             |
+    /// | fixed arrays of the above     | `FixedSizeList`         |
+    pub fn read_arrow(&self) -> Result<ArrayRef> {
+        let len = ensure_1d(self)?;
+        read_block(self, &self.dtype()?.to_descriptor()?, 0, len)
+    }
+
+    /// Returns an iterator reading a 1-D dataset as a sequence of Arrow arrays of at most
+    /// `batch_len` elements each, so that large datasets can be processed without loading them
+    /// into memory at once.
+    pub fn read_arrow_batches(&self, batch_len: usize) -> Result<ArrowBatches<'_>> {
+        ensure!(batch_len > 0, "batch length must be positive");
+        let len = ensure_1d(self)?;
+        let desc = self.dtype()?.to_descriptor()?;
+        arrow_type(&desc)?;
+        Ok(ArrowBatches { ds: self, desc, batch_len, pos: 0, len })
+    }
+}
+
+impl Group {
+    /// Reads the given columns, which must be 1-D datasets of equal length in this group, into
+    /// an Arrow record batch.
+    pub fn read_table(&self, columns: &[&str]) -> Result<RecordBatch> {
+        let mut fields = Vec::with_capacity(columns.len());
+        let mut arrays = Vec::with_capacity(columns.len());
+        let mut num_rows = None;
+        for &name in columns {
+            let array = (|| {
+                let ds = self.dataset(name)?;
+                let len = ensure_1d(&ds)?;
+                if let Some(expected) = num_rows {
+                    ensure!(len == expected, "expected {} rows, got {}", expected, len);
+                }
+                num_rows = Some(len);
+                ds.read_arrow()
+            })()
+            .with_context(|| format!("column '{name}'"))?;
+            fields.push(Field::new(name, array.data_type().clone(), false));
+            arrays.push(array);
+        }
+        let options = RecordBatchOptions::new().with_row_count(Some(num_rows.unwrap_or(0)));
+        RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), arrays, &options)
+            .map_err(|err| err.to_string().into())
+    }
+
+    /// Writes each column of a record batch to a new 1-D dataset named `name_prefix` followed
+    /// by the column name, creating intermediate groups as needed.
+    ///
+    /// Strings are stored as variable-length UTF-8 strings. Arrow nulls have no HDF5
+    /// counterpart, so columns containing nulls cannot be written.
+    pub fn write_table(&self, name_prefix: &str, batch: &RecordBatch) -> Result<()> {
+        for (field, column) in batch.schema().fields().iter().zip(batch.columns()) {
+            let name = format!("{name_prefix}{}", field.name());
+            write_column(self, &name, column)
+                .with_context(|| format!("column '{}'", field.name()))?;
+        }
+        Ok(())
+    }
+}
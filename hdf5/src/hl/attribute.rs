@@ -1,20 +1,27 @@
-use std::fmt::{self, Debug};
+use std::fmt::{self, Debug, Display};
 use std::ops::Deref;
 use std::ptr::addr_of_mut;
 
 use crate::sys::h5a::H5Aget_name;
 use crate::sys::h5p::H5Pcreate;
 use crate::sys::{
-    h5::{H5_index_t, H5_iter_order_t},
-    h5a::{H5A_info_t, H5A_operator2_t, H5Acreate2, H5Adelete, H5Aiterate2},
+    h5::H5_iter_order_t,
+    h5a::{H5A_info_t, H5A_operator2_t, H5Acreate2, H5Adelete, H5Aexists, H5Aiterate2, H5Awrite},
 };
-use hdf5_types::TypeDescriptor;
+use hdf5_types::{OwnedDynValue, TypeDescriptor};
 use ndarray::ArrayView;
 
+use crate::dim::{alloc_buf, checked_buf_size};
 use crate::globals::H5P_ATTRIBUTE_CREATE;
+use crate::hl::container::read_attr_into;
+use crate::hl::dataset::ensure_exhausted;
 use crate::internal_prelude::*;
+use crate::TraversalOrder;
 
 /// Represents the HDF5 attribute object.
+///
+/// Attribute names are not paths: they are used verbatim by all attribute functions, so they
+/// may contain '/', '.', surrounding whitespace and any Unicode characters (but no NUL).
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct Attribute(Handle);
@@ -31,7 +38,9 @@ impl ObjectClass for Attribute {
         &self.0
     }
 
-    // TODO: short_repr()
+    fn short_repr(&self) -> Option<String> {
+        Some(format!("\"{}\": {}", self.name(), self.shape_and_type_repr().ok()?))
+    }
 }
 
 impl Debug for Attribute {
@@ -40,6 +49,12 @@ impl Debug for Attribute {
     }
 }
 
+impl Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display_fmt(f)
+    }
+}
+
 impl Deref for Attribute {
     type Target = Container;
 
@@ -59,6 +74,16 @@ impl Attribute {
 
     /// Returns names of all the members in the group, non-recursively.
     pub fn attr_names(obj: &Location) -> Result<Vec<String>> {
+        Self::attr_names_by(obj, TraversalOrder::Name)
+    }
+
+    /// Returns names of all the attributes on the object in the given order.
+    pub(crate) fn attr_names_by(obj: &Location, order: TraversalOrder) -> Result<Vec<String>> {
+        struct Names {
+            names: Vec<String>,
+            error: Option<Error>,
+        }
+
         unsafe extern "C" fn attributes_callback(
             _id: hid_t,
             attr_name: *const c_char,
@@ -66,30 +91,41 @@ impl Attribute {
             op_data: *mut c_void,
         ) -> herr_t {
             std::panic::catch_unwind(|| {
-                let other_data: &mut Vec<String> =
-                    unsafe { &mut *(op_data.cast::<std::vec::Vec<std::string::String>>()) };
-                // SAFETY: caller guarantees attr_name points to valid UTF-8 C string
-                other_data.push(unsafe { string_from_cstr(attr_name) });
-                0 // Continue iteration
+                let data: &mut Names = unsafe { &mut *(op_data.cast::<Names>()) };
+                match unsafe { name_from_cstr(attr_name, "attribute") } {
+                    Ok(name) => {
+                        data.names.push(name);
+                        0 // Continue iteration
+                    }
+                    Err(err) => {
+                        data.error = Some(err);
+                        -1
+                    }
+                }
             })
             .unwrap_or(-1)
         }
 
         let callback_fn: H5A_operator2_t = Some(attributes_callback);
         let iteration_position: *mut hsize_t = &mut { 0_u64 };
-        let mut result: Vec<String> = Vec::new();
-        let other_data: *mut c_void = addr_of_mut!(result).cast();
+        let capacity = listing_capacity(obj.attr_count()? as u64);
+        let mut data = Names { names: Vec::with_capacity(capacity), error: None };
+        let other_data: *mut c_void = addr_of_mut!(data).cast();
 
-        h5call!(H5Aiterate2(
+        // A single call visits all attributes, collecting their names into `data`.
+        let result = h5call!(H5Aiterate2(
             obj.handle().id(),
-            H5_index_t::H5_INDEX_NAME,
+            order.into(),
             H5_iter_order_t::H5_ITER_INC,
             iteration_position,
             callback_fn,
             other_data
-        ))?;
-
-        Ok(result)
+        ));
+        if let Some(err) = data.error {
+            return Err(err).with_context(|| format!("listing attributes of '{}'", obj.name()));
+        }
+        result?;
+        Ok(data.names)
     }
 }
 
@@ -144,6 +180,24 @@ impl AttributeBuilder {
         }
     }
 
+    /// Sets the data of an attribute of the given shape to the items of `iter`, in C order.
+    ///
+    /// Attributes are written all at once, so the items are collected before the attribute is
+    /// created. Creating the attribute fails, reporting how many elements were consumed, if the
+    /// iterator yields fewer or more elements than the shape holds.
+    pub fn from_iter<S, I>(self, shape: S, iter: I) -> AttributeBuilderIter<I::IntoIter>
+    where
+        S: Into<Extents>,
+        I: IntoIterator,
+        I::Item: H5Type,
+    {
+        AttributeBuilderIter {
+            builder: self.builder,
+            iter: iter.into_iter(),
+            extents: shape.into(),
+        }
+    }
+
     #[inline]
     #[must_use]
     pub fn packed(mut self, packed: bool) -> Self {
@@ -169,9 +223,14 @@ impl AttributeBuilderEmpty {
         }
     }
 
-    /// Creates the attribute.
+    /// Creates a scalar attribute, or an attribute with a null dataspace for
+    /// [unit types](TypeDescriptor::unit) such as `()`, which hold no data.
     pub fn create<'n, T: Into<&'n str>>(self, name: T) -> Result<Attribute> {
-        self.shape(()).create(name)
+        if self.type_desc.is_unit() {
+            self.shape(Extents::Null).create(name)
+        } else {
+            self.shape(()).create(name)
+        }
     }
 
     #[inline]
@@ -261,6 +320,50 @@ where
     }
 }
 
+/// An attribute builder with data taken from an iterator
+#[derive(Clone)]
+pub struct AttributeBuilderIter<I> {
+    builder: AttributeBuilderInner,
+    iter: I,
+    extents: Extents,
+}
+
+impl<I> AttributeBuilderIter<I>
+where
+    I: Iterator,
+    I::Item: H5Type,
+{
+    /// Collects the items of the iterator and creates the attribute holding them.
+    pub fn create<'n, N: Into<&'n str>>(self, name: N) -> Result<Attribute> {
+        let Self { builder, mut iter, extents } = self;
+        let len = extents.size();
+        let mut data = alloc_buf(len)?;
+        data.extend(iter.by_ref().take(len));
+        ensure!(data.len() == len, "iterator yielded {} elements, expected {}", data.len(), len);
+        ensure_exhausted(&mut iter, len)?;
+        let name = name.into();
+        h5lock!({
+            let attr = builder.create(&I::Item::type_descriptor(), name, &extents)?;
+            if extents.is_null() {
+                return Ok(attr);
+            }
+            if let Err(err) = attr.write_raw(data.as_slice()) {
+                builder.try_unlink(name);
+                Err(err)
+            } else {
+                Ok(attr)
+            }
+        })
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn packed(mut self, packed: bool) -> Self {
+        self.builder.packed(packed);
+        self
+    }
+}
+
 #[derive(Clone)]
 /// The true internal dataset builder
 struct AttributeBuilderInner {
@@ -282,33 +385,27 @@ impl AttributeBuilderInner {
         desc: &TypeDescriptor,
         name: &str,
         extents: &Extents,
+    ) -> Result<Attribute> {
+        self.create_staged(desc, name, extents)
+            .with_context(|| format!("creating attribute '{name}'"))
+    }
+
+    unsafe fn create_staged(
+        &self,
+        desc: &TypeDescriptor,
+        name: &str,
+        extents: &Extents,
     ) -> Result<Attribute> {
         // construct in-file type descriptor; convert to packed representation if needed
         let desc = if self.packed { desc.to_packed_repr() } else { desc.to_c_repr() };
 
         let datatype = Datatype::from_descriptor(&desc)?;
         let parent = try_ref_clone!(self.parent);
+        parent.ensure_writable()?;
 
         let dataspace = Dataspace::try_new(extents)?;
-
-        let acpl = PropertyList::from_id(h5call!(H5Pcreate(*H5P_ATTRIBUTE_CREATE))?)?;
-        // Set UTF-8 encoding for the attribute name, as Rust strings are UTF-8.
-        h5call!(crate::sys::h5p::H5Pset_char_encoding(
-            acpl.id(),
-            crate::sys::h5t::H5T_cset_t::H5T_CSET_UTF8
-        ))?;
-
-        let name = to_cstring(name)?;
-        Attribute::from_id(h5try!(H5Acreate2(
-            parent.id(),
-            name.as_ptr(),
-            datatype.id(),
-            dataspace.id(),
-            acpl.id(),
-            // Unused as of v1.14
-            // see more: https://hdfgroup.github.io/hdf5/v1_14/group___h5_a.html#ga4f4e5248c09f689633079ed8afc0b308
-            H5P_DEFAULT,
-        )))
+        let acpl = attr_create_plist()?;
+        create_attr_raw(parent.id(), name, &datatype, &dataspace, &acpl)
     }
 
     fn try_unlink(&self, name: &str) {
@@ -319,6 +416,142 @@ impl AttributeBuilderInner {
     }
 }
 
+/// Creates the attribute creation property list used for all new attributes.
+fn attr_create_plist() -> Result<PropertyList> {
+    let acpl = PropertyList::from_id(h5call!(H5Pcreate(H5P_ATTRIBUTE_CREATE.get()?))?)?;
+    // Set UTF-8 encoding for the attribute name, as Rust strings are UTF-8.
+    h5call!(crate::sys::h5p::H5Pset_char_encoding(
+        acpl.id(),
+        crate::sys::h5t::H5T_cset_t::H5T_CSET_UTF8
+    ))?;
+    Ok(acpl)
+}
+
+fn create_attr_raw(
+    parent: hid_t,
+    name: &str,
+    datatype: &Datatype,
+    dataspace: &Dataspace,
+    acpl: &PropertyList,
+) -> Result<Attribute> {
+    let name = to_cstring(name)?;
+    Attribute::from_id(h5try!(H5Acreate2(
+        parent,
+        name.as_ptr(),
+        datatype.id(),
+        dataspace.id(),
+        acpl.id(),
+        // Unused as of v1.14
+        // see more: https://hdfgroup.github.io/hdf5/v1_14/group___h5_a.html#ga4f4e5248c09f689633079ed8afc0b308
+        H5P_DEFAULT,
+    )))
+}
+
+/// Writes scalar attributes onto `parent` with the library lock acquired once, see
+/// [`Location::write_attrs`]. Returns the number of attributes written.
+pub(crate) fn write_scalar_attrs(
+    parent: &Location,
+    attrs: &[(&str, OwnedDynValue)],
+    only_if_changed: bool,
+) -> Result<usize> {
+    let mut attrs: Vec<_> = attrs.iter().collect();
+    attrs.sort_by_key(|(name, _)| *name);
+    if let Some(pair) = attrs.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+        fail!("duplicate attribute name '{}'", pair[0].0);
+    }
+    crate::sync::batch(|| {
+        parent.handle().ensure_writable()?;
+        let scalar = Dataspace::try_new(Extents::Scalar)?;
+        let acpl = attr_create_plist()?;
+        // (memory type, in-file type) of each distinct type descriptor
+        let mut types: Vec<(&TypeDescriptor, Datatype, Datatype)> = Vec::new();
+        let mut written = 0;
+        for (name, value) in attrs {
+            let desc = value.type_descriptor();
+            let index = match types.iter().position(|(known, ..)| *known == desc) {
+                Some(index) => index,
+                None => {
+                    let mem_type = Datatype::from_descriptor(desc)?;
+                    let file_type = Datatype::from_descriptor(&desc.to_c_repr())?;
+                    types.push((desc, mem_type, file_type));
+                    types.len() - 1
+                }
+            };
+            let (_, mem_type, file_type) = &types[index];
+            let attr = existing_scalar_attr(parent, name, file_type)
+                .with_context(|| format!("opening attribute '{name}'"))?;
+            let attr = match attr {
+                Some(attr) => {
+                    if only_if_changed && read_scalar_value(&attr, mem_type, desc)? == *value {
+                        continue;
+                    }
+                    attr
+                }
+                None => create_attr_raw(parent.id(), name, file_type, &scalar, &acpl)
+                    .with_context(|| format!("creating attribute '{name}'"))?,
+            };
+            // variable-length data is only referenced by the buffer, which does not own it
+            let buf = unsafe { value.get_buf() };
+            h5try!(H5Awrite(attr.id(), mem_type.id(), buf.as_ptr().cast()));
+            written += 1;
+        }
+        Ok(written)
+    })
+}
+
+/// Opens the attribute `name` of `parent` if it is a scalar of type `file_type`, deleting it
+/// if it exists with another type or shape.
+fn existing_scalar_attr(
+    parent: &Location,
+    name: &str,
+    file_type: &Datatype,
+) -> Result<Option<Attribute>> {
+    let c_name = to_cstring(name)?;
+    if h5call!(H5Aexists(parent.id(), c_name.as_ptr()))? <= 0 {
+        return Ok(None);
+    }
+    let attr = parent.attr(name)?;
+    if attr.is_scalar() && attr.dtype()? == *file_type {
+        return Ok(Some(attr));
+    }
+    drop(attr);
+    h5call!(H5Adelete(parent.id(), c_name.as_ptr()))?;
+    Ok(None)
+}
+
+fn read_scalar_value(
+    attr: &Attribute,
+    mem_type: &Datatype,
+    desc: &TypeDescriptor,
+) -> Result<OwnedDynValue> {
+    let mut buf = vec![0_u8; desc.size()];
+    read_attr_into(attr, mem_type, buf.as_mut_ptr().cast())?;
+    // the value takes ownership of the variable-length data allocated by the read
+    Ok(unsafe { OwnedDynValue::from_raw(desc.clone(), buf.into()) })
+}
+
+/// Copies an attribute (including variable-length data) onto another object.
+pub(crate) fn copy_attr(attr: &Attribute, dst: &Location, name: &str) -> Result<()> {
+    let desc = attr.dtype()?.to_descriptor()?;
+    let extents = attr.space()?.extents()?;
+    let new = dst.new_attr_builder().empty_as(&desc).shape(extents).create(name)?;
+    if attr.size()? == 0 {
+        return Ok(());
+    }
+    let mem_type = Datatype::from_descriptor(&desc)?;
+    let size = desc.size();
+    let mut buf = vec![0_u8; checked_buf_size(attr.size()?, size)?];
+    read_attr_into(attr, &mem_type, buf.as_mut_ptr().cast())?;
+    let written = h5call!(H5Awrite(new.id(), mem_type.id(), buf.as_ptr().cast()));
+    if size > 0 {
+        // frees any variable-length data allocated by the read
+        for elem in buf.chunks(size) {
+            drop(unsafe { OwnedDynValue::from_raw(desc.clone(), elem.into()) });
+        }
+    }
+    written.map(|_| ())
+}
+
 #[cfg(test)]
 pub mod attribute_tests {
     use crate::internal_prelude::*;
@@ -330,15 +563,15 @@ pub mod attribute_tests {
     pub fn test_shape_ndim_size() {
         with_tmp_file(|file| {
             let d = file.new_attr::<f32>().shape((2, 3)).create("name1").unwrap();
-            assert_eq!(d.shape(), vec![2, 3]);
-            assert_eq!(d.size(), 6);
+            assert_eq!(d.shape().unwrap(), vec![2, 3]);
+            assert_eq!(d.size().unwrap(), 6);
             assert_eq!(d.ndim(), 2);
             assert_eq!(d.is_scalar(), false);
             assert_eq!(d.name(), "name1");
 
             let d = file.new_attr::<u8>().shape(()).create("name2").unwrap();
-            assert_eq!(d.shape(), vec![]);
-            assert_eq!(d.size(), 1);
+            assert_eq!(d.shape().unwrap(), vec![]);
+            assert_eq!(d.size().unwrap(), 1);
             assert_eq!(d.ndim(), 0);
             assert_eq!(d.is_scalar(), true);
             assert_eq!(d.name(), "name2");
@@ -388,7 +621,7 @@ pub mod attribute_tests {
             attr.as_writer().write(&arr).unwrap();
 
             let read_attr = file.attr("foo").unwrap();
-            assert_eq!(read_attr.shape(), vec![2, 3]);
+            assert_eq!(read_attr.shape().unwrap(), vec![2, 3]);
 
             let arr_dyn: Array2<_> = read_attr.as_reader().read().unwrap();
 
@@ -401,9 +634,9 @@ pub mod attribute_tests {
         with_tmp_file(|file| {
             let attr = file.new_attr::<u32>().shape((1, 2)).create("foo").unwrap();
             assert!(attr.is_valid());
-            assert_eq!(attr.shape(), vec![1, 2]);
+            assert_eq!(attr.shape().unwrap(), vec![1, 2]);
             assert_eq!(attr.name(), "foo");
-            assert_eq!(file.attr("foo").unwrap().shape(), vec![1, 2]);
+            assert_eq!(file.attr("foo").unwrap().shape().unwrap(), vec![1, 2]);
         })
     }
 
@@ -414,17 +647,37 @@ pub mod attribute_tests {
 
             let attr = file.new_attr_builder().with_data(&arr).create("foo").unwrap();
             assert!(attr.is_valid());
-            assert_eq!(attr.shape(), vec![2, 3]);
+            assert_eq!(attr.shape().unwrap(), vec![2, 3]);
             assert_eq!(attr.name(), "foo");
-            assert_eq!(file.attr("foo").unwrap().shape(), vec![2, 3]);
+            assert_eq!(file.attr("foo").unwrap().shape().unwrap(), vec![2, 3]);
 
             let read_attr = file.attr("foo").unwrap();
-            assert_eq!(read_attr.shape(), vec![2, 3]);
+            assert_eq!(read_attr.shape().unwrap(), vec![2, 3]);
             let arr_dyn: Array2<_> = read_attr.as_reader().read().unwrap();
             assert_eq!(arr, arr_dyn.into_dimensionality().unwrap());
         })
     }
 
+    #[test]
+    pub fn test_create_from_iter() {
+        with_tmp_file(|file| {
+            let attr = file.new_attr_builder().from_iter((2, 3), 1..=6_i32).create("foo").unwrap();
+            assert_eq!(attr.shape().unwrap(), vec![2, 3]);
+            let arr: Array2<i32> = attr.as_reader().read().unwrap();
+            assert_eq!(arr, arr2(&[[1, 2, 3], [4, 5, 6]]));
+
+            let attr = file.new_attr_builder().from_iter((), [7.5_f64]).create("scalar").unwrap();
+            assert!(attr.is_scalar());
+            assert_eq!(attr.read_scalar::<f64>().unwrap(), 7.5);
+
+            let err = file.new_attr_builder().from_iter(4, 0..3_u8).create("short").unwrap_err();
+            assert!(err.to_string().contains("yielded 3 elements, expected 4"), "{err}");
+            let err = file.new_attr_builder().from_iter(4, 0..9_u8).create("long").unwrap_err();
+            assert!(err.to_string().contains("more than 4 elements (consumed 5)"), "{err}");
+            assert_eq!(file.attr_names().unwrap(), vec!["foo", "scalar"]);
+        })
+    }
+
     #[test]
     pub fn test_missing() {
         with_tmp_file(|file| {
@@ -441,7 +694,7 @@ pub mod attribute_tests {
             let attr = file.new_attr::<VarLenUnicode>().shape(()).create("foo").unwrap();
             attr.as_writer().write_scalar(&s).unwrap();
             let read_attr = file.attr("foo").unwrap();
-            assert_eq!(read_attr.shape(), []);
+            assert_eq!(read_attr.shape().unwrap(), []);
             let r: VarLenUnicode = read_attr.as_reader().read_scalar().unwrap();
             assert_eq!(r, s);
         })
@@ -459,4 +712,108 @@ pub mod attribute_tests {
             assert!(attr_names.contains(&"bar".to_string()));
         })
     }
+
+    #[test]
+    pub fn test_vlen_array_attrs() {
+        use hdf5_types::{CompoundField, CompoundType, TypeDescriptor, VarLenArray};
+        use std::mem;
+
+        #[repr(C)]
+        struct Record {
+            id: i32,
+            values: VarLenArray<f64>,
+        }
+
+        unsafe impl H5Type for Record {
+            fn type_descriptor() -> TypeDescriptor {
+                TypeDescriptor::Compound(CompoundType {
+                    fields: vec![
+                        CompoundField::typed::<i32>("id", mem::offset_of!(Record, id), 0),
+                        CompoundField::typed::<VarLenArray<f64>>(
+                            "values",
+                            mem::offset_of!(Record, values),
+                            1,
+                        ),
+                    ],
+                    size: mem::size_of::<Record>(),
+                })
+            }
+        }
+
+        with_tmp_file(|file| {
+            let scalar = VarLenArray::from_slice(&[1.5, -2.0, 3.25]);
+            let ragged = [
+                VarLenArray::from_slice(&[1]),
+                VarLenArray::from_slice(&[]),
+                VarLenArray::from([2, 3, 4, 5, 6]),
+            ];
+            let records = [
+                Record { id: 1, values: VarLenArray::from_slice(&[0.5]) },
+                Record { id: 2, values: VarLenArray::from_slice(&[1.0, 2.0, 3.0]) },
+            ];
+            file.new_attr::<VarLenArray<f64>>()
+                .create("scalar")
+                .unwrap()
+                .write_scalar(&scalar)
+                .unwrap();
+            file.new_attr_builder().with_data(&ragged).create("ragged").unwrap();
+            file.new_attr_builder().with_data(&records).create("records").unwrap();
+            // repeated reads surface leaks and double frees of the variable-length data
+            for _ in 0..20 {
+                let attr = file.attr("scalar").unwrap();
+                assert_eq!(attr.read_scalar::<VarLenArray<f64>>().unwrap(), scalar);
+                let attr = file.attr("ragged").unwrap();
+                assert_eq!(attr.shape().unwrap(), [3]);
+                assert_eq!(attr.read_raw::<VarLenArray<i32>>().unwrap(), ragged);
+                let read = file.attr("records").unwrap().read_1d::<Record>().unwrap();
+                assert_eq!(read.len(), 2);
+                for (r, e) in read.iter().zip(&records) {
+                    assert_eq!(r.id, e.id);
+                    assert_eq!(r.values, e.values);
+                }
+            }
+            // and so do repeated writes
+            let attr = file.attr("ragged").unwrap();
+            for i in 0..20 {
+                let data: Vec<VarLenArray<i32>> = (0..3)
+                    .map(|j| VarLenArray::from_slice(&(0..i + j).collect::<Vec<_>>()))
+                    .collect();
+                attr.write_raw(&data).unwrap();
+                assert_eq!(attr.read_raw::<VarLenArray<i32>>().unwrap(), data);
+            }
+        })
+    }
+
+    #[test]
+    pub fn test_attr_names_verbatim() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u8>().shape(1).create("x").unwrap();
+            let names = ["config/version", "/leading", "a.b", ".", "..", " spaced ", "Δt/ψ"];
+            for (i, name) in names.iter().enumerate() {
+                for loc in [&*file as &Location, &ds] {
+                    loc.new_attr::<i32>().create(*name).unwrap().write_scalar(&(i as i32)).unwrap();
+                }
+            }
+            for loc in [&*file as &Location, &ds] {
+                let mut listed = loc.attr_names().unwrap();
+                listed.sort();
+                let mut expected = names.map(str::to_owned).to_vec();
+                expected.sort();
+                assert_eq!(listed, expected);
+                for (i, name) in names.iter().enumerate() {
+                    let attr = loc.attr(name).unwrap();
+                    assert_eq!(attr.name(), *name);
+                    assert_eq!(attr.read_scalar::<i32>().unwrap(), i as i32);
+                }
+                // a name with '/' does not refer to an attribute of another object
+                assert!(loc.attr("config").is_err());
+                assert!(loc.attr("leading").is_err());
+                loc.delete_attr("config/version").unwrap();
+                assert!(loc.attr("config/version").is_err());
+                assert_eq!(loc.attr_count().unwrap(), names.len() - 1);
+            }
+            // names are not trimmed
+            assert!(file.attr("spaced").is_err());
+        })
+    }
 }
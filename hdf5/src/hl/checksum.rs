@@ -0,0 +1,200 @@
+//! Content checksums of datasets, stored in the [`CHECKSUM_ATTR`] attribute.
+
+use std::mem;
+
+use ndarray::ArrayView;
+use xxhash_rust::xxh3::{xxh3_64, Xxh3};
+
+use crate::sys::h5a::H5Aexists;
+use crate::sys::h5d::H5Dread;
+use crate::sys::h5t::{H5T_order_t, H5Tcopy, H5Tset_order};
+
+use crate::conventions::CHECKSUM_ATTR;
+use crate::dim::checked_buf_size;
+use crate::hl::copy::{has_padding, has_pointers};
+use crate::hl::datatype::ByteOrder;
+use crate::internal_prelude::*;
+
+/// Target size of the blocks datasets are read in to compute checksums.
+const BLOCK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Outcome of a successful checksum verification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    /// The stored checksum matches the data.
+    Valid,
+    /// The dataset has no checksum attribute.
+    NotChecksummed,
+}
+
+/// Returns the datatype the checksum bytes are encoded with.
+fn checksum_dtype(dtype: &Datatype) -> Result<Datatype> {
+    let desc = dtype.to_descriptor()?;
+    ensure!(!has_pointers(&desc), "cannot checksum datasets of type {}", desc);
+    if dtype.byte_order() == ByteOrder::BigEndian {
+        h5lock!({
+            let le = Datatype::from_id(h5try!(H5Tcopy(dtype.id())))?;
+            h5try!(H5Tset_order(le.id(), H5T_order_t::H5T_ORDER_LE));
+            Ok(le)
+        })
+    } else {
+        Ok(dtype.clone())
+    }
+}
+
+pub(crate) fn compute_checksum(ds: &Container) -> Result<u64> {
+    ensure!(!ds.is_attr(), "checksums are only supported on datasets");
+    let dtype = checksum_dtype(&ds.dtype()?)?;
+    let space = ds.space()?;
+    let shape = space.shape()?;
+    if shape.is_empty() || space.size()? == 0 {
+        let mut buf = vec![0_u8; checked_buf_size(space.size()?, dtype.size())?];
+        if !buf.is_empty() {
+            h5try!(H5Dread(
+                ds.id(),
+                dtype.id(),
+                H5S_ALL,
+                H5S_ALL,
+                H5P_DEFAULT,
+                buf.as_mut_ptr().cast()
+            ));
+        }
+        return Ok(xxh3_64(&buf));
+    }
+    let row_size = shape[1..].iter().product::<usize>() * dtype.size();
+    let rows = (BLOCK_SIZE / row_size.max(1)).max(1);
+    let mut hasher = Xxh3::new();
+    let mut buf = Vec::<u8>::new();
+    for start in (0..shape[0]).step_by(rows) {
+        let count = rows.min(shape[0] - start);
+        let mut selection = vec![SliceOrIndex::SliceCount { start, step: 1, count, block: 1 }];
+        selection.extend(shape[1..].iter().map(|&n| SliceOrIndex::SliceCount {
+            start: 0,
+            step: 1,
+            count: n,
+            block: 1,
+        }));
+        let fspace = space.select(Selection::from(Hyperslab::from(selection)))?;
+        let mut dims = shape.clone();
+        dims[0] = count;
+        let mspace = Dataspace::try_new(dims.as_slice())?;
+        buf.resize(count * row_size, 0);
+        h5try!(H5Dread(
+            ds.id(),
+            dtype.id(),
+            mspace.id(),
+            fspace.id(),
+            H5P_DEFAULT,
+            buf.as_mut_ptr().cast()
+        ));
+        hasher.update(&buf);
+    }
+    Ok(hasher.digest())
+}
+
+fn stored_checksum(ds: &Container) -> Result<Option<u64>> {
+    let name = to_cstring(CHECKSUM_ATTR)?;
+    if h5call!(H5Aexists(ds.id(), name.as_ptr()))? > 0 {
+        ds.attr(CHECKSUM_ATTR)?.read_scalar::<u64>().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+fn store_checksum(ds: &Dataset, checksum: u64) -> Result<u64> {
+    let attr = match stored_checksum(ds)? {
+        Some(_) => ds.attr(CHECKSUM_ATTR)?,
+        None => ds.new_attr::<u64>().create(CHECKSUM_ATTR)?,
+    };
+    attr.write_scalar(&checksum)?;
+    Ok(checksum)
+}
+
+impl Dataset {
+    /// Writes an n-dimensional array view into the dataset and stores its checksum in the
+    /// [`CHECKSUM_ATTR`] attribute, returning the checksum.
+    ///
+    /// The checksum is computed from the written buffer if its memory type matches the
+    /// checksum encoding (see [`CHECKSUM_ALGORITHM`](crate::conventions::CHECKSUM_ALGORITHM))
+    /// and has no padding; otherwise the data is read back, as in
+    /// [`update_checksum`](Self::update_checksum).
+    pub fn write_with_checksum<'b, A, T, D>(&self, arr: A) -> Result<u64>
+    where
+        A: Into<ArrayView<'b, T, D>>,
+        T: H5Type,
+        D: ndarray::Dimension,
+    {
+        let view = arr.into();
+        self.as_writer().write(view.view())?;
+        let dtype = checksum_dtype(&self.dtype()?)?;
+        let checksum = match view.as_slice() {
+            Some(data)
+                if !has_padding(&T::type_descriptor()) && Datatype::from_type::<T>()? == dtype =>
+            {
+                // SAFETY: the slice is contiguous and the type has neither pointers nor padding,
+                // so its memory holds exactly the bytes that were written, all initialized
+                xxh3_64(unsafe {
+                    std::slice::from_raw_parts(data.as_ptr().cast(), mem::size_of_val(data))
+                })
+            }
+            _ => compute_checksum(self)?,
+        };
+        store_checksum(self, checksum)
+    }
+
+    /// Recomputes the checksum of the dataset's contents and stores it in the
+    /// [`CHECKSUM_ATTR`] attribute, returning the checksum.
+    ///
+    /// This has to be called after writing to a checksummed dataset by other means than
+    /// [`write_with_checksum`](Self::write_with_checksum).
+    pub fn update_checksum(&self) -> Result<u64> {
+        store_checksum(self, compute_checksum(self)?)
+    }
+}
+
+impl Reader<'_> {
+    /// Recomputes the checksum of the dataset and compares it with the stored one.
+    ///
+    /// Returns [`ChecksumStatus::NotChecksummed`] if the dataset has no checksum attribute and
+    /// fails with both values in the message if the checksums differ.
+    pub fn verify_checksum(&self) -> Result<ChecksumStatus> {
+        let obj = self.container();
+        let Some(stored) = stored_checksum(obj)? else {
+            return Ok(ChecksumStatus::NotChecksummed);
+        };
+        let computed = compute_checksum(obj)?;
+        ensure!(
+            stored == computed,
+            "checksum mismatch in '{}': stored {stored:#018x}, computed {computed:#018x}",
+            obj.name()
+        );
+        Ok(ChecksumStatus::Valid)
+    }
+
+    /// Verifies the checksum of the dataset (see [`verify_checksum`](Self::verify_checksum))
+    /// before each read, failing the read on a mismatch.
+    ///
+    /// Each read recomputes the checksum over the whole dataset, regardless of the selection.
+    pub fn verified(mut self) -> Self {
+        self.verify = true;
+        self
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use hdf5_types::VarLenUnicode;
+
+    use super::checksum_dtype;
+    use crate::globals::H5T_STD_I32BE;
+    use crate::internal_prelude::*;
+    use crate::sys::h5t::H5Tcopy;
+
+    #[test]
+    pub fn test_checksum_dtype() {
+        let be = Datatype::from_id(h5lock!(H5Tcopy(*H5T_STD_I32BE))).unwrap();
+        assert_eq!(checksum_dtype(&be).unwrap(), Datatype::from_type::<i32>().unwrap());
+        let vlen = Datatype::from_type::<VarLenUnicode>().unwrap();
+        assert_err!(checksum_dtype(&vlen), "cannot checksum datasets of type");
+    }
+}
@@ -1,9 +1,24 @@
+use std::env;
+use std::fmt::{self, Display};
+
 use crate::internal_prelude::*;
 
 use crate::sys::h5d::{H5Dget_chunk_info, H5Dget_num_chunks};
 
+/// Environment variable that makes dataset builders warn about chunk shapes that are badly
+/// suited to reading whole rows or columns or that exceed the current shape of the dataset
+/// (any value but an empty string or `0`), unless disabled with `warn_on_bad_chunking(false)`.
+///
+/// These checks are also enabled by `strict_chunk(true)`, which makes their findings errors.
+pub const WARN_CHUNKING_ENV: &str = "HDF5_RUST_WARN_CHUNKING";
+
+/// Read amplification above which a dataset builder warns about the chunk shape, see
+/// [`WARN_CHUNKING_ENV`].
+pub const CHUNK_WARN_AMPLIFICATION: f64 = 100.0;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 /// Information on a chunk in a Dataset
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChunkInfo {
     /// Array with a size equal to the dataset’s rank whose elements contain 0-based
     /// logical positions of the chunk’s first element in each dimension.
@@ -62,6 +77,261 @@ pub(crate) fn get_num_chunks(ds: &Dataset) -> Option<usize> {
     }))
 }
 
+/// How a chunk shape suits reading a dataset in blocks of a given shape, as returned by
+/// [`Dataset::chunk_alignment_report`] and [`ChunkReport::compute`].
+///
+/// The dataset is assumed to be read completely, block after block, with the blocks laid out
+/// on a grid starting at the origin; each block decompresses every chunk it touches, without
+/// reusing chunks cached by previous blocks.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChunkReport {
+    /// Shape of the dataset.
+    pub shape: Vec<Ix>,
+    /// Shape of the chunks.
+    pub chunk: Vec<Ix>,
+    /// Shape of the access blocks, clamped to the shape of the dataset.
+    pub access: Vec<Ix>,
+    /// Average number of chunks touched by a block.
+    pub mean_chunks_per_access: f64,
+    /// Largest number of chunks touched by a block.
+    pub max_chunks_per_access: usize,
+    /// Bytes of chunks decompressed divided by the bytes requested, over all blocks.
+    pub read_amplification: f64,
+    /// Bytes of the edge chunks lying outside the dataset.
+    pub edge_waste_bytes: u64,
+    /// Fraction of the bytes of all chunks lying outside the dataset.
+    pub edge_waste_fraction: f64,
+}
+
+/// Per-axis statistics of the blocks of length `access` along an axis of length `len`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct AxisStats {
+    /// Number of blocks along the axis.
+    blocks: u64,
+    /// Sum over the blocks of the chunks touched by each block.
+    visits: u64,
+    /// Largest number of chunks touched by a block.
+    max: u64,
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns the number of chunks of length `chunk` touched by the elements `start..end`.
+fn touched(start: u64, end: u64, chunk: u64) -> u64 {
+    (end - 1) / chunk - start / chunk + 1
+}
+
+fn axis_stats(len: u64, chunk: u64, access: u64) -> AxisStats {
+    let blocks = len.div_ceil(access);
+    // every block touches one chunk, plus one for each chunk boundary strictly inside it,
+    // i.e. each boundary that is not also a block boundary
+    let period = access / gcd(access, chunk) * chunk;
+    let visits = blocks + (len - 1) / chunk - (len - 1) / period;
+    // the offsets of the blocks within a chunk repeat after `period / access` blocks, and
+    // the last, possibly shorter block never touches more chunks than the full ones
+    let max = (0..blocks.min(period / access))
+        .map(|i| touched(i * access, (i * access + access).min(len), chunk))
+        .max()
+        .unwrap_or(0);
+    AxisStats { blocks, visits, max }
+}
+
+impl ChunkReport {
+    /// Computes the report for a dataset of shape `shape` with chunks of shape `chunk` and
+    /// elements of `element_size` bytes read in blocks of shape `access`.
+    ///
+    /// Entries of `access` larger than the dataset are clamped, so `usize::MAX` stands for a
+    /// whole axis: reading whole rows of a 2-D dataset is `[1, usize::MAX]`.
+    pub fn compute(shape: &[Ix], chunk: &[Ix], access: &[Ix], element_size: usize) -> Result<Self> {
+        let ndim = shape.len();
+        ensure!(chunk.len() == ndim, "expected chunk ndim {}, got {}", ndim, chunk.len());
+        ensure!(access.len() == ndim, "expected access ndim {}, got {}", ndim, access.len());
+        ensure!(chunk.iter().all(|&c| c > 0), "chunk dimensions must be positive: {:?}", chunk);
+        ensure!(access.iter().all(|&a| a > 0), "access dimensions must be positive: {:?}", access);
+        let access: Vec<Ix> = access.iter().zip(shape).map(|(&a, &n)| a.min(n)).collect();
+        let mut report = Self {
+            shape: shape.to_vec(),
+            chunk: chunk.to_vec(),
+            access: access.clone(),
+            mean_chunks_per_access: 0.0,
+            max_chunks_per_access: 0,
+            read_amplification: 1.0,
+            edge_waste_bytes: 0,
+            edge_waste_fraction: 0.0,
+        };
+        if shape.contains(&0) {
+            return Ok(report);
+        }
+        let (mut mean, mut max, mut amplification) = (1.0, 1_u64, 1.0);
+        let (mut allocated, mut logical) = (1.0_f64, 1.0_f64);
+        for ((&len, &chunk), &access) in shape.iter().zip(chunk).zip(&access) {
+            let (len, chunk, access) = (len as u64, chunk as u64, access as u64);
+            let stats = axis_stats(len, chunk, access);
+            mean *= stats.visits as f64 / stats.blocks as f64;
+            max = max.saturating_mul(stats.max);
+            amplification *= (stats.visits * chunk) as f64 / len as f64;
+            allocated *= (len.div_ceil(chunk) * chunk) as f64;
+            logical *= len as f64;
+        }
+        report.mean_chunks_per_access = mean;
+        report.max_chunks_per_access = max.try_into().unwrap_or(usize::MAX);
+        report.read_amplification = amplification;
+        report.edge_waste_bytes = ((allocated - logical) * element_size as f64) as u64;
+        report.edge_waste_fraction = 1.0 - logical / allocated;
+        Ok(report)
+    }
+}
+
+impl Display for ChunkReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "chunk {:?} of shape {:?}, access {:?}: {:.1} chunks per access (max {}), \
+             read amplification {:.1}, edge waste {:.1}%",
+            self.chunk,
+            self.shape,
+            self.access,
+            self.mean_chunks_per_access,
+            self.max_chunks_per_access,
+            self.read_amplification,
+            self.edge_waste_fraction * 100.0
+        )
+    }
+}
+
+/// Returns a warning if reading whole rows or whole columns of a dataset of shape `shape`
+/// with chunks of shape `chunk` exceeds [`CHUNK_WARN_AMPLIFICATION`].
+pub(crate) fn bad_chunking_warning(
+    shape: &[Ix],
+    chunk: &[Ix],
+    element_size: usize,
+) -> Option<String> {
+    let ndim = shape.len();
+    if ndim == 0 || shape.contains(&0) {
+        return None;
+    }
+    let mut rows = vec![1; ndim];
+    rows[ndim - 1] = Ix::MAX;
+    let mut columns = vec![1; ndim];
+    columns[0] = Ix::MAX;
+    [("rows", rows), ("columns", columns)].into_iter().find_map(|(pattern, access)| {
+        let report = ChunkReport::compute(shape, chunk, &access, element_size).ok()?;
+        (report.read_amplification > CHUNK_WARN_AMPLIFICATION).then(|| {
+            format!(
+                "Chunk shape {:?} reads {:.0} times the requested data when reading whole \
+                 {pattern} of shape {:?}",
+                chunk, report.read_amplification, shape
+            )
+        })
+    })
+}
+
+/// Returns `true` if [`WARN_CHUNKING_ENV`] is set to anything but an empty string or `0`.
+pub(crate) fn warn_chunking_from_env() -> bool {
+    env::var_os(WARN_CHUNKING_ENV).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+impl Dataset {
+    /// Reports how the chunk shape of the dataset suits reading it in blocks of shape
+    /// `access`, see [`ChunkReport`].
+    ///
+    /// Entries of `access` larger than the dataset are clamped, so `usize::MAX` stands for a
+    /// whole axis. Fails if the dataset is not chunked.
+    pub fn chunk_alignment_report(&self, access_shape: &[Ix]) -> Result<ChunkReport> {
+        let chunk = self.chunk().ok_or_else(|| format!("{} is not chunked", self.describe()))?;
+        ChunkReport::compute(&self.shape()?, &chunk, access_shape, self.dtype()?.size())
+    }
+}
+
 // NOTE: H5Dchunk_iter based iteration (ChunkInfoRef, visit) is not available
 // in runtime-loading mode because H5Dchunk_iter is not included in the runtime bindings.
 // Use chunk_info() with explicit indices instead.
+
+#[cfg(test)]
+pub mod tests {
+    use super::{axis_stats, bad_chunking_warning, AxisStats, ChunkReport};
+    use crate::internal_prelude::*;
+
+    /// Counts by brute force what `axis_stats` computes.
+    fn axis_stats_naive(len: u64, chunk: u64, access: u64) -> AxisStats {
+        let counts: Vec<u64> = (0..len)
+            .step_by(access as usize)
+            .map(|start| {
+                let end = (start + access).min(len);
+                (start..end).map(|i| i / chunk).collect::<std::collections::BTreeSet<_>>().len()
+                    as u64
+            })
+            .collect();
+        AxisStats {
+            blocks: counts.len() as u64,
+            visits: counts.iter().sum(),
+            max: counts.iter().copied().max().unwrap_or(0),
+        }
+    }
+
+    #[test]
+    pub fn test_axis_stats() {
+        for len in 1..40 {
+            for chunk in 1..12 {
+                for access in 1..=len {
+                    let expected = axis_stats_naive(len, chunk, access);
+                    assert_eq!(axis_stats(len, chunk, access), expected, "{len} {chunk} {access}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_chunk_report() {
+        // row-shaped chunks: whole rows are ideal, whole columns touch every chunk
+        let rows = ChunkReport::compute(&[1000, 10000], &[1, 10000], &[1, Ix::MAX], 8).unwrap();
+        assert_eq!(rows.access, [1, 10000]);
+        assert_eq!((rows.mean_chunks_per_access, rows.max_chunks_per_access), (1.0, 1));
+        assert_eq!(rows.read_amplification, 1.0);
+        assert_eq!((rows.edge_waste_bytes, rows.edge_waste_fraction), (0, 0.0));
+        let cols = ChunkReport::compute(&[1000, 10000], &[1, 10000], &[Ix::MAX, 1], 8).unwrap();
+        assert_eq!((cols.mean_chunks_per_access, cols.max_chunks_per_access), (1000.0, 1000));
+        assert_eq!(cols.read_amplification, 10000.0);
+
+        // square chunks: single rows decompress whole chunk rows
+        let report = ChunkReport::compute(&[1000, 1000], &[100, 100], &[1, Ix::MAX], 4).unwrap();
+        assert_eq!(report.max_chunks_per_access, 10);
+        assert_eq!(report.read_amplification, 100.0);
+        let aligned = ChunkReport::compute(&[1000, 1000], &[100, 100], &[100, 100], 4).unwrap();
+        assert_eq!((aligned.max_chunks_per_access, aligned.read_amplification), (1, 1.0));
+
+        // misaligned blocks straddle chunk boundaries
+        let report = ChunkReport::compute(&[100], &[10], &[15], 1).unwrap();
+        // blocks 0..15, 15..30, ...: 2, 2, 2, ..., the last one 90..100 touches 1
+        assert_eq!(report.max_chunks_per_access, 2);
+        assert_eq!(report.mean_chunks_per_access, 13.0 / 7.0);
+        assert!((report.read_amplification - 1.3).abs() < 1e-12);
+
+        // edge chunks
+        let report = ChunkReport::compute(&[10, 10], &[4, 4], &[10, 10], 2).unwrap();
+        assert_eq!(report.max_chunks_per_access, 9);
+        assert_eq!(report.edge_waste_bytes, (144 - 100) * 2);
+        assert!((report.edge_waste_fraction - 44.0 / 144.0).abs() < 1e-12);
+        assert!((report.read_amplification - 1.44).abs() < 1e-12);
+
+        let empty = ChunkReport::compute(&[0, 5], &[1, 5], &[1, 5], 4).unwrap();
+        assert_eq!((empty.max_chunks_per_access, empty.read_amplification), (0, 1.0));
+        assert_err!(ChunkReport::compute(&[4], &[2], &[0], 4), "access dimensions");
+        assert_err!(ChunkReport::compute(&[4], &[2, 2], &[1], 4), "expected chunk ndim 1");
+    }
+
+    #[test]
+    pub fn test_bad_chunking_warning() {
+        let warning = bad_chunking_warning(&[1000, 10000], &[1, 10000], 8).unwrap();
+        assert!(warning.contains("whole columns"), "{warning}");
+        assert!(bad_chunking_warning(&[1000, 1000], &[100, 100], 8).is_none());
+        assert!(bad_chunking_warning(&[1000, 1000], &[1000, 1], 8).unwrap().contains("rows"));
+        assert!(bad_chunking_warning(&[0, 10], &[1, 10], 8).is_none());
+    }
+}
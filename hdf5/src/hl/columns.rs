@@ -0,0 +1,361 @@
+//! Writing and reading the fields of 1-D compound datasets as separate columns.
+
+use std::collections::HashSet;
+use std::mem;
+
+use hdf5_types::{CompoundField, CompoundType, TypeDescriptor};
+
+use crate::dim::alloc_buf;
+use crate::internal_prelude::*;
+
+mod private {
+    use crate::internal_prelude::*;
+
+    pub trait ColumnData {
+        fn data_ptr(&self) -> *const c_void;
+    }
+}
+
+/// A column of values of a single [`H5Type`] with the element type erased, as written by
+/// [`Writer::write_columns`].
+///
+/// The trait is implemented for slices, arrays and vectors of any [`H5Type`], and is sealed.
+pub trait ErasedColumn: private::ColumnData {
+    /// Returns the number of values in the column.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the column holds no values.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the type descriptor of the values.
+    fn type_descriptor(&self) -> TypeDescriptor;
+}
+
+macro_rules! impl_erased_column {
+    ($($ty:ty $(, const $n:ident)?);*) => {$(
+        impl<T: H5Type $(, const $n: usize)?> private::ColumnData for $ty {
+            fn data_ptr(&self) -> *const c_void {
+                self.as_ptr().cast()
+            }
+        }
+
+        impl<T: H5Type $(, const $n: usize)?> ErasedColumn for $ty {
+            fn len(&self) -> usize {
+                <[T]>::len(self)
+            }
+
+            fn type_descriptor(&self) -> TypeDescriptor {
+                T::type_descriptor()
+            }
+        }
+    )*};
+}
+
+impl_erased_column!(&[T]; Vec<T>; [T; N], const N);
+
+/// A tuple of column vectors read by [`Reader::read_columns`], one per field name.
+///
+/// The trait is implemented for tuples of up to eight `Vec<T>` with `T: H5Type`.
+pub trait FromColumns: Sized {
+    /// The number of columns.
+    const LEN: usize;
+
+    #[doc(hidden)]
+    fn read_columns(reader: &Reader, names: &[&str]) -> Result<Self>;
+}
+
+macro_rules! impl_from_columns {
+    ($len:expr; $($ty:ident $idx:tt),+) => {
+        impl<$($ty: H5Type),+> FromColumns for ($(Vec<$ty>,)+) {
+            const LEN: usize = $len;
+
+            fn read_columns(reader: &Reader, names: &[&str]) -> Result<Self> {
+                Ok(($(reader.read_column::<$ty>(names[$idx])?,)+))
+            }
+        }
+    };
+}
+
+impl_from_columns!(1; A 0);
+impl_from_columns!(2; A 0, B 1);
+impl_from_columns!(3; A 0, B 1, C 2);
+impl_from_columns!(4; A 0, B 1, C 2, D 3);
+impl_from_columns!(5; A 0, B 1, C 2, D 3, E 4);
+impl_from_columns!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_from_columns!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_from_columns!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+/// Returns the compound type and the length of a 1-D compound dataset.
+fn compound_rows(obj: &Container) -> Result<(CompoundType, usize)> {
+    ensure!(!obj.is_attr(), "columns cannot be used with attributes");
+    let desc = obj.dtype()?.to_descriptor()?;
+    let TypeDescriptor::Compound(ty) = desc else {
+        fail!("columns require a compound dataset, got {}", desc);
+    };
+    let shape = obj.shape()?;
+    ensure!(shape.len() == 1, "expected a 1-D dataset, got shape {:?}", shape);
+    Ok((ty, shape[0]))
+}
+
+/// Returns a compound memory type holding only the field `name` of type `desc` at offset 0.
+fn member_type(name: &str, desc: TypeDescriptor) -> Result<Datatype> {
+    let size = desc.size();
+    ensure!(size > 0, "field '{}' has a zero-sized type {}", name, desc);
+    let fields = vec![CompoundField::new(name, desc, 0, 0)];
+    Datatype::from_descriptor(&TypeDescriptor::Compound(CompoundType { fields, size }))
+}
+
+impl<'a> Reader<'a> {
+    /// Reads the field `name` of every row of a 1-D compound dataset into a column.
+    ///
+    /// Only the field is transferred, using a memory type that holds just this member; the
+    /// values are converted from the type of the field to `F` as for any other read.
+    pub fn read_column<F: H5Type>(&self, name: &str) -> Result<Vec<F>> {
+        let obj = self.container();
+        let (ty, len) = compound_rows(obj)?;
+        ensure!(
+            ty.fields.iter().any(|field| field.name == name),
+            "no field '{}' in {}",
+            name,
+            obj.describe()
+        );
+        ensure!(mem::size_of::<F>() > 0, "cannot read field '{}' as a zero-sized type", name);
+        let mem_dtype = member_type(name, F::type_descriptor())?;
+        if len == 0 {
+            return Ok(vec![]);
+        }
+        self.check_read_size(len, &mem_dtype)?;
+        let mut buf = alloc_buf::<F>(len)?;
+        self.read_into_buf_as(buf.as_mut_ptr().cast(), &mem_dtype, None, None)
+            .with_context(|| format!("reading field '{name}'"))?;
+        unsafe {
+            buf.set_len(len);
+        };
+        Ok(buf)
+    }
+
+    /// Reads the fields `field_names` of a 1-D compound dataset into a tuple of columns, one
+    /// field at a time, see [`read_column`](Self::read_column).
+    ///
+    /// ```no_run
+    /// # fn main() -> hdf5_rt::Result<()> {
+    /// # let ds = hdf5_rt::File::open("trades.h5")?.dataset("trades")?;
+    /// let (ids, prices): (Vec<u64>, Vec<f64>) = ds.as_reader().read_columns(&["id", "price"])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_columns<C: FromColumns>(&self, field_names: &[&str]) -> Result<C> {
+        ensure!(
+            field_names.len() == C::LEN,
+            "expected {} field names, got {}",
+            C::LEN,
+            field_names.len()
+        );
+        C::read_columns(self, field_names)
+    }
+}
+
+impl<'a> Writer<'a> {
+    /// Allows [`write_columns`](Self::write_columns) to leave members of the dataset without
+    /// a column unchanged, that is, at their fill value if they were never written.
+    pub fn allow_missing_columns(mut self) -> Self {
+        self.allow_missing_columns = true;
+        self
+    }
+
+    /// Writes a 1-D compound dataset from one column per field, given as pairs of a field
+    /// name and the values of the field for every row.
+    ///
+    /// Each column is written on its own with a memory type holding just that member, so the
+    /// rows are never assembled in memory; the values are converted to the type of the field
+    /// as for any other write. All columns must have the length of the dataset, and every
+    /// member of the dataset must have a column unless
+    /// [`allow_missing_columns`](Self::allow_missing_columns) is set.
+    ///
+    /// ```no_run
+    /// # fn main() -> hdf5_rt::Result<()> {
+    /// # let ds = hdf5_rt::File::open_rw("trades.h5")?.dataset("trades")?;
+    /// let (ids, prices) = (vec![1_u64, 2, 3], vec![0.5, 1.5, 2.5]);
+    /// ds.as_writer().write_columns(&[("id", &ids), ("price", &prices)])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_columns(&self, columns: &[(&str, &dyn ErasedColumn)]) -> Result<()> {
+        let obj = self.container();
+        obj.handle().ensure_writable()?;
+        let (ty, len) = compound_rows(obj)?;
+        let mut seen = HashSet::new();
+        for &(name, column) in columns {
+            ensure!(seen.insert(name), "duplicate column '{}'", name);
+            ensure!(
+                ty.fields.iter().any(|field| field.name == name),
+                "no field '{}' in {}",
+                name,
+                obj.describe()
+            );
+            ensure!(
+                column.len() == len,
+                "column '{}' has {} values, expected {} (the length of {})",
+                name,
+                column.len(),
+                len,
+                obj.describe()
+            );
+        }
+        if !self.allow_missing_columns {
+            let mut fields: Vec<_> = ty.fields.iter().collect();
+            fields.sort_by_key(|field| field.index);
+            let missing: Vec<_> = fields
+                .into_iter()
+                .map(|field| field.name.as_str())
+                .filter(|name| !seen.contains(name))
+                .collect();
+            ensure!(
+                missing.is_empty(),
+                "no column for the fields {:?} of {}",
+                missing,
+                obj.describe()
+            );
+        }
+        for &(name, column) in columns {
+            let mem_dtype = member_type(name, column.type_descriptor())?;
+            if len > 0 {
+                self.write_from_buf_as(column.data_ptr(), &mem_dtype, None, None)
+                    .with_context(|| format!("writing field '{name}'"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Container {
+    /// Reads the field `name` of a 1-D compound dataset, see [`Reader::read_column`].
+    pub fn read_column<F: H5Type>(&self, name: &str) -> Result<Vec<F>> {
+        self.as_reader().read_column(name)
+    }
+
+    /// Reads fields of a 1-D compound dataset into a tuple of columns, see
+    /// [`Reader::read_columns`].
+    pub fn read_columns<C: FromColumns>(&self, field_names: &[&str]) -> Result<C> {
+        self.as_reader().read_columns(field_names)
+    }
+
+    /// Writes a 1-D compound dataset from one column per field, see
+    /// [`Writer::write_columns`].
+    pub fn write_columns(&self, columns: &[(&str, &dyn ErasedColumn)]) -> Result<()> {
+        self.as_writer().write_columns(columns)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::mem;
+
+    use hdf5_types::{CompoundField, CompoundType, TypeDescriptor, VarLenUnicode};
+
+    use super::ErasedColumn;
+    use crate::internal_prelude::*;
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    #[repr(C)]
+    struct Reading {
+        sensor: u16,
+        value: f64,
+        label: VarLenUnicode,
+    }
+
+    unsafe impl H5Type for Reading {
+        fn type_descriptor() -> TypeDescriptor {
+            TypeDescriptor::Compound(CompoundType {
+                fields: vec![
+                    CompoundField::typed::<u16>("sensor", mem::offset_of!(Reading, sensor), 0),
+                    CompoundField::typed::<f64>("value", mem::offset_of!(Reading, value), 1),
+                    CompoundField::typed::<VarLenUnicode>(
+                        "label",
+                        mem::offset_of!(Reading, label),
+                        2,
+                    ),
+                ],
+                size: mem::size_of::<Reading>(),
+            })
+        }
+    }
+
+    #[test]
+    pub fn test_write_read_columns() {
+        with_tmp_file(|file| {
+            let sensors: Vec<u16> = (0..100).map(|i| i % 7).collect();
+            let values: Vec<f64> = (0..100).map(|i| f64::from(i) * 1.5).collect();
+            let labels: Vec<VarLenUnicode> =
+                (0..100).map(|i| format!("s{i}").parse().unwrap()).collect();
+            let ds = file.new_dataset::<Reading>().shape(100).create("readings").unwrap();
+            ds.write_columns(&[("label", &labels), ("sensor", &sensors), ("value", &values)])
+                .unwrap();
+
+            let rows = ds.read_raw::<Reading>().unwrap();
+            for (i, row) in rows.iter().enumerate() {
+                assert_eq!((row.sensor, row.value), (sensors[i], values[i]));
+                assert_eq!(row.label, labels[i]);
+            }
+            let (read_values, read_sensors): (Vec<f64>, Vec<u16>) =
+                ds.read_columns(&["value", "sensor"]).unwrap();
+            assert_eq!((read_values, read_sensors), (values.clone(), sensors.clone()));
+            // columns are converted like any other data
+            let wide = ds.read_column::<i64>("sensor").unwrap();
+            assert!(wide.iter().zip(&sensors).all(|(&a, &b)| a == i64::from(b)));
+            assert_eq!(ds.read_column::<VarLenUnicode>("label").unwrap(), labels);
+
+            // a partial write leaves the other members unchanged
+            let doubled: Vec<f64> = values.iter().map(|v| v * 2.0).collect();
+            let writer = ds.as_writer().allow_missing_columns();
+            writer.write_columns(&[("value", &doubled.as_slice())]).unwrap();
+            let rows = ds.read_raw::<Reading>().unwrap();
+            assert!(rows.iter().enumerate().all(|(i, row)| row.value == doubled[i]));
+            assert!(rows.iter().enumerate().all(|(i, row)| row.sensor == sensors[i]));
+
+            let fresh = file.new_dataset::<Reading>().shape(3).create("fresh").unwrap();
+            let writer = fresh.as_writer().allow_missing_columns();
+            writer.write_columns(&[("sensor", &[1_u16, 2, 3])]).unwrap();
+            let rows = fresh.read_raw::<Reading>().unwrap();
+            assert_eq!(rows.iter().map(|row| row.sensor).collect::<Vec<_>>(), [1, 2, 3]);
+            assert!(rows.iter().all(|row| row.value == 0.0));
+        })
+    }
+
+    #[test]
+    pub fn test_columns_errors() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<Reading>().shape(3).create("readings").unwrap();
+            let sensors = [1_u16, 2, 3];
+            let values = vec![0.5; 2];
+            let labels: Vec<VarLenUnicode> = vec!["a".parse().unwrap(); 3];
+            assert_eq!(ErasedColumn::len(&values), 2);
+
+            assert_err!(
+                ds.write_columns(&[("sensor", &sensors), ("value", &values), ("label", &labels)]),
+                "column 'value' has 2 values, expected 3"
+            );
+            assert_err!(
+                ds.write_columns(&[("sensor", &sensors), ("label", &labels)]),
+                "no column for the fields [\"value\"]"
+            );
+            assert_err!(
+                ds.write_columns(&[("sensor", &sensors), ("sensor", &sensors)]),
+                "duplicate column 'sensor'"
+            );
+            assert_err!(ds.write_columns(&[("missing", &sensors)]), "no field 'missing'");
+            assert_err!(ds.read_column::<u16>("missing"), "no field 'missing'");
+            assert_err!(
+                ds.read_columns::<(Vec<u16>, Vec<f64>)>(&["sensor"]),
+                "expected 2 field names, got 1"
+            );
+
+            let plain = file.new_dataset::<u16>().shape(3).create("plain").unwrap();
+            assert_err!(plain.write_columns(&[("sensor", &sensors)]), "require a compound");
+            let matrix = file.new_dataset::<Reading>().shape((3, 1)).create("matrix").unwrap();
+            assert_err!(matrix.read_column::<u16>("sensor"), "expected a 1-D dataset");
+        })
+    }
+}
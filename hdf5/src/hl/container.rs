@@ -1,22 +1,174 @@
 use std::convert::TryInto;
 use std::fmt::{self, Debug};
 use std::io;
+use std::iter;
 use std::mem;
 use std::ops::Deref;
+use std::ptr;
+use std::slice;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use ndarray::{Array, Array1, Array2, ArrayD, ArrayView, ArrayView1};
+use hdf5_types::{DynValue, TypeDescriptor, VarLenAscii, VarLenUnicode};
+use ndarray::{Array, Array1, Array2, ArrayD, ArrayView, ArrayView1, Axis};
 
+use crate::dim::{alloc_buf, checked_buf_size, checked_size, DimVec};
+use crate::hl::filters::explain_read_error;
+use crate::hl::io_stats::IoKind;
+use crate::hl::selection::SelectionExpr;
 use crate::sys::h5a::{H5Aget_space, H5Aget_storage_size, H5Aget_type, H5Aread, H5Awrite};
-use crate::sys::h5d::{H5Dget_space, H5Dget_storage_size, H5Dget_type, H5Dread, H5Dwrite};
+use crate::sys::h5d::{
+    H5Dget_space, H5Dget_storage_size, H5Dget_type, H5Dread, H5Dvlen_reclaim, H5Dwrite,
+};
 use crate::sys::h5p::H5Pcreate;
-
+use crate::sys::h5s::{H5S_sel_type, H5S_seloper_t, H5Sget_select_type, H5Sselect_hyperslab};
+use crate::sys::h5t::H5Treclaim;
+
+use crate::hl::copy::has_pointers;
+use crate::hl::plist::dataset_transfer::{
+    set_conversion_report, ConversionExceptionPolicy, ConversionReport, DatasetTransfer,
+    DEFAULT_BUFFER_SIZE,
+};
 use crate::internal_prelude::*;
 
+/// The process-wide read limit in bytes, `u64::MAX` if unlimited.
+static MAX_READ_BYTES: AtomicU64 = AtomicU64::new(u64::MAX);
+
+/// The number of bytes assumed per variable-length element when estimating the size of a read.
+///
+/// The actual lengths are only known once the data is read, so this errs on the low side.
+const VLEN_ESTIMATE: u64 = 16;
+
+/// Sets the maximum number of bytes a single read may allocate, or removes the limit.
+///
+/// Reads that would allocate more fail with [`Error::AllocationLimitExceeded`] before any
+/// memory is allocated, which protects services from untrusted files declaring huge
+/// datasets. The estimate covers the destination buffer, the type conversion buffer (if the
+/// memory type differs from the file type) and a small amount per variable-length element.
+/// The limit can be overridden per read with [`Reader::max_bytes`]. Reads are unlimited by
+/// default.
+pub fn set_max_read_bytes(limit: Option<u64>) {
+    MAX_READ_BYTES.store(limit.unwrap_or(u64::MAX), Ordering::Relaxed);
+}
+
+/// Returns the limit set with [`set_max_read_bytes`], if any.
+pub fn max_read_bytes() -> Option<u64> {
+    Some(MAX_READ_BYTES.load(Ordering::Relaxed)).filter(|&limit| limit != u64::MAX)
+}
+
+/// Records a dataset request if I/O statistics are enabled on the handle; failures to describe
+/// the selection never fail the request itself.
+fn record_io(obj: &Container, kind: IoKind, fspace: Option<&Dataspace>, element_size: usize) {
+    if let Some(recorder) = obj.handle().io_recorder() {
+        if let Ok(space) = obj.space() {
+            let _ = recorder.lock().record(kind, &space, fspace, element_size);
+        }
+    }
+}
+
+/// Default maximum number of bytes transferred by a single `H5Dread` or `H5Dwrite` call, see
+/// [`Writer::max_request_size`].
+pub const DEFAULT_MAX_REQUEST_SIZE: usize = 1 << 30;
+
+/// Splits the transfer of a dataset selection into parts of at most `max_bytes` along the first
+/// axis, returning the file selection and number of elements of each part, or `None` if the
+/// selection is transferred in a single request.
+///
+/// Each part is the selection intersected with a block of whole rows. Hyperslab selections are
+/// transferred in the order of their coordinates, so the parts are consecutive in memory; point
+/// selections are transferred in the order of their points and are never split. Parts are
+/// never smaller than a row, which may exceed `max_bytes` on its own.
+fn split_request(
+    space: &Dataspace,
+    fspace: Option<&Dataspace>,
+    element_size: usize,
+    max_bytes: Option<usize>,
+) -> Result<Option<Vec<(Dataspace, usize)>>> {
+    let Some(max_bytes) = max_bytes else {
+        return Ok(None);
+    };
+    let shape = space.shape()?;
+    let selection = match fspace {
+        Some(fspace) => fspace.selection_size(),
+        None => space.size()?,
+    };
+    if shape.is_empty() || selection.saturating_mul(element_size) <= max_bytes {
+        return Ok(None);
+    }
+    let base = fspace.unwrap_or(space);
+    if h5lock!(H5Sget_select_type(base.id())) == H5S_sel_type::H5S_SEL_POINTS {
+        return Ok(None);
+    }
+    let row_bytes = shape[1..].iter().product::<usize>().saturating_mul(element_size);
+    let rows = (max_bytes / row_bytes.max(1)).max(1);
+    let ones = DimVec::from_elem(1, shape.len());
+    let mut parts = vec![];
+    for lo in (0..shape[0]).step_by(rows) {
+        let part = base.copy();
+        let start: DimVec = iter::once(lo as _).chain(shape[1..].iter().map(|_| 0)).collect();
+        let block: DimVec = iter::once(rows.min(shape[0] - lo))
+            .chain(shape[1..].iter().copied())
+            .map(|n| n as _)
+            .collect();
+        h5try!(H5Sselect_hyperslab(
+            part.id(),
+            H5S_seloper_t::H5S_SELECT_AND,
+            start.as_ptr(),
+            ones.as_ptr(),
+            ones.as_ptr(),
+            block.as_ptr(),
+        ));
+        let len = part.selection_size();
+        if len > 0 {
+            parts.push((part, len));
+        }
+    }
+    Ok(Some(parts).filter(|parts| parts.len() > 1))
+}
+
+/// Runs `transfer` once with the given file and memory dataspaces, or once per part of a
+/// selection that is larger than `max_bytes`, see [`split_request`]. The memory buffer must
+/// hold the selected elements contiguously, i.e. `mspace` must be a fully selected dataspace
+/// unless both dataspaces are `None`.
+fn transfer_in_parts<F>(
+    obj: &Container,
+    buf: *mut c_void,
+    element_size: usize,
+    fspace: Option<&Dataspace>,
+    mspace: Option<&Dataspace>,
+    max_bytes: Option<usize>,
+    mut transfer: F,
+) -> Result<()>
+where
+    F: FnMut(Option<&Dataspace>, Option<&Dataspace>, *mut c_void) -> Result<()>,
+{
+    let parts = if mspace.is_some() || fspace.is_none() {
+        split_request(&obj.space()?, fspace, element_size, max_bytes)?
+    } else {
+        None
+    };
+    let Some(parts) = parts else {
+        return transfer(fspace, mspace, buf);
+    };
+    let mut offset = 0;
+    for (part, len) in parts {
+        let mspace = Dataspace::try_new(len)?;
+        transfer(Some(&part), Some(&mspace), buf.cast::<u8>().wrapping_add(offset).cast())?;
+        offset += len * element_size;
+    }
+    Ok(())
+}
+
 /// A type for reading data from a [`Container`].
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Reader<'a> {
     obj: &'a Container,
     conv: Conversion,
+    xfer: Option<DatasetTransfer>,
+    max_bytes: Option<u64>,
+    max_request_size: Option<usize>,
+    strict_scalar: bool,
+    #[cfg(feature = "checksum")]
+    pub(crate) verify: bool,
 }
 
 impl<'a> Reader<'a> {
@@ -24,7 +176,20 @@ impl<'a> Reader<'a> {
     ///
     /// Any conversions (including hard/soft) are allowed by default.
     pub fn new(obj: &'a Container) -> Self {
-        Self { obj, conv: Conversion::Soft }
+        Self {
+            obj,
+            conv: Conversion::Soft,
+            xfer: None,
+            max_bytes: None,
+            max_request_size: Some(DEFAULT_MAX_REQUEST_SIZE),
+            strict_scalar: false,
+            #[cfg(feature = "checksum")]
+            verify: false,
+        }
+    }
+
+    pub(crate) fn container(&self) -> &'a Container {
+        self.obj
     }
 
     /// Set maximum allowed conversion level.
@@ -39,36 +204,153 @@ impl<'a> Reader<'a> {
         self
     }
 
+    /// Sets the dataset transfer property list used for reads (ignored for attributes).
+    pub fn transfer_plist(mut self, dxpl: &DatasetTransfer) -> Self {
+        self.xfer = Some(dxpl.clone());
+        self
+    }
+
+    /// A short alias for `transfer_plist()`.
+    pub fn dxpl(self, dxpl: &DatasetTransfer) -> Self {
+        self.transfer_plist(dxpl)
+    }
+
+    /// Sets the maximum number of bytes a read may allocate, overriding the
+    /// [global limit](set_max_read_bytes).
+    pub fn max_bytes(mut self, limit: u64) -> Self {
+        self.max_bytes = Some(limit);
+        self
+    }
+
+    /// Sets the maximum number of bytes read from a dataset by a single `H5Dread` call, or
+    /// `None` to read any selection at once; see [`Writer::max_request_size`].
+    pub fn max_request_size(mut self, size: Option<usize>) -> Self {
+        self.max_request_size = size;
+        self
+    }
+
+    /// If set, [`read_scalar`](Self::read_scalar) only accepts scalar dataspaces and fails
+    /// for datasets/attributes holding a single element in a non-scalar shape such as `(1,)`.
+    pub fn strict_scalar(mut self, strict: bool) -> Self {
+        self.strict_scalar = strict;
+        self
+    }
+
+    /// Fails if reading `npoints` elements as `mem_dtype` would exceed the read limit.
+    pub(crate) fn check_read_size(&self, npoints: usize, mem_dtype: &Datatype) -> Result<()> {
+        let Some(limit) = self.max_bytes.or_else(max_read_bytes) else {
+            return Ok(());
+        };
+        let file_dtype = self.obj.dtype()?;
+        let npoints = npoints as u64;
+        let (mem_size, file_size) = (mem_dtype.size() as u64, file_dtype.size() as u64);
+        let mut size = npoints.saturating_mul(mem_size);
+        if file_dtype != *mem_dtype {
+            // the library converts at most a buffer's worth of elements at a time
+            let buffer_size = match &self.xfer {
+                Some(xfer) if !self.obj.is_attr() => xfer.buffer_size(),
+                _ => DEFAULT_BUFFER_SIZE,
+            };
+            let conv_size = npoints.saturating_mul(mem_size.max(file_size));
+            size = size.saturating_add(conv_size.min(buffer_size as u64));
+        }
+        if has_vlen_data(&mem_dtype.to_descriptor()?) {
+            size = size.saturating_add(npoints.saturating_mul(VLEN_ESTIMATE));
+        }
+        if size > limit {
+            return Err(Error::AllocationLimitExceeded { size, limit })
+                .with_context(|| format!("reading {}", self.obj.describe()));
+        }
+        Ok(())
+    }
+
     fn read_into_buf<T: H5Type>(
         &self,
         buf: *mut T,
         fspace: Option<&Dataspace>,
         mspace: Option<&Dataspace>,
     ) -> Result<()> {
-        let file_dtype = self.obj.dtype()?;
         let mem_dtype = Datatype::from_type::<T>()?;
-        file_dtype.ensure_convertible(&mem_dtype, self.conv)?;
+        if mem::size_of::<T>() == 0 {
+            // unit values hold no data, so there is nothing to read them from
+            return self.obj.dtype()?.ensure_convertible(&mem_dtype, self.conv);
+        }
+        self.read_into_buf_as(buf.cast(), &mem_dtype, fspace, mspace)
+    }
+
+    pub(crate) fn read_into_buf_as(
+        &self,
+        buf: *mut c_void,
+        mem_dtype: &Datatype,
+        fspace: Option<&Dataspace>,
+        mspace: Option<&Dataspace>,
+    ) -> Result<()> {
+        #[cfg(feature = "checksum")]
+        if self.verify {
+            self.verify_checksum()?;
+        }
+        let file_dtype = self.obj.dtype()?;
+        file_dtype.ensure_convertible(mem_dtype, self.conv)?;
         let (obj_id, tp_id) = (self.obj.id(), mem_dtype.id());
 
         if self.obj.is_attr() {
-            h5try!(H5Aread(obj_id, tp_id, buf.cast()));
+            read_attr_into(self.obj, mem_dtype, buf)
+                .with_context(|| format!("reading {}", self.obj.describe()))?;
         } else {
-            let fspace_id = fspace.map_or(H5S_ALL, |f| f.id());
-            let mspace_id = mspace.map_or(H5S_ALL, |m| m.id());
-            let xfer =
-                PropertyList::from_id(h5call!(H5Pcreate(*crate::globals::H5P_DATASET_XFER))?)?;
+            let xfer = match &self.xfer {
+                Some(xfer) => xfer.copy()?,
+                None => DatasetTransfer::try_new()?,
+            };
             // Always use libc allocator for vlen data (HDF5 allocator not available in runtime-loading mode)
-            crate::hl::plist::set_vlen_manager_libc(xfer.id())?;
-            h5try!(H5Dread(obj_id, tp_id, mspace_id, fspace_id, xfer.id(), buf.cast()));
+            crate::hl::plist::dataset_transfer::set_vlen_manager_libc(xfer.id())?;
+            let size = mem_dtype.size();
+            transfer_in_parts(
+                self.obj,
+                buf,
+                size,
+                fspace,
+                mspace,
+                self.max_request_size,
+                |f, m, buf| {
+                    let fspace_id = f.map_or(H5S_ALL, |f| f.id());
+                    let mspace_id = m.map_or(H5S_ALL, |m| m.id());
+                    let read =
+                        h5call!(H5Dread(obj_id, tp_id, mspace_id, fspace_id, xfer.id(), buf));
+                    read.map_err(|err| explain_read_error(self.obj, err))
+                        .with_context(|| format!("reading {}", self.obj.describe()))?;
+                    record_io(self.obj, IoKind::Read, f, size);
+                    Ok(())
+                },
+            )?;
         }
         Ok(())
     }
 
+    /// Reads `count` consecutive elements of a 1-D dataset starting at `start` into a vector.
+    pub(crate) fn read_rows<T: H5Type>(&self, start: usize, count: usize) -> Result<Vec<T>> {
+        ensure!(!self.obj.is_attr(), "Slicing cannot be used on attribute datasets");
+        if count == 0 {
+            return Ok(vec![]);
+        }
+        self.check_read_size(count, &Datatype::from_type::<T>()?)?;
+        let fspace = self.obj.space()?.select(start..start + count)?;
+        let mspace = Dataspace::try_new(count)?;
+        let mut buf = alloc_buf(count)?;
+        self.read_into_buf(buf.as_mut_ptr(), Some(&fspace), Some(&mspace))?;
+        unsafe {
+            buf.set_len(count);
+        };
+        Ok(buf)
+    }
+
     /// Reads a slice of an n-dimensional array.
     /// If the dimensionality `D` has a fixed number of dimensions, it must match the dimensionality of
     /// the slice, after singleton dimensions are dropped.
     /// Use the multi-dimensional slice macro `s![]` from `ndarray` to conveniently create
     /// a multidimensional slice.
+    ///
+    /// Slices with a negative step (like `s![..;-1]`) are read with the equivalent positive
+    /// step and reversed in memory, which costs an extra pass over the result.
     pub fn read_slice<T, S, D>(&self, selection: S) -> Result<Array<T, D>>
     where
         T: H5Type,
@@ -79,15 +361,21 @@ impl<'a> Reader<'a> {
         ensure!(!self.obj.is_attr(), "Slicing cannot be used on attribute datasets");
 
         let selection = selection.try_into()?;
+        let reversed = selection.reversed_out_axes();
         let obj_space = self.obj.space()?;
 
-        let out_shape = selection.out_shape(obj_space.shape())?;
-        let out_size: Ix = out_shape.iter().product();
+        let out_shape = selection.out_shape(obj_space.shape()?)?;
+        let out_size = checked_size(&out_shape)?;
         let fspace = obj_space.select(selection)?;
 
         if let Some(ndim) = D::NDIM {
-            let out_ndim = out_shape.len();
-            ensure!(ndim == out_ndim, "Selection ndim ({}) != array ndim ({})", out_ndim, ndim);
+            ensure!(
+                ndim == out_shape.len(),
+                "reading {}: selection shape {:?} cannot be read into a {}-dimensional array",
+                self.obj.describe(),
+                out_shape,
+                ndim
+            );
         } else {
             let fsize = fspace.selection_size();
             ensure!(
@@ -103,26 +391,63 @@ impl<'a> Reader<'a> {
         } else if obj_space.ndim() == 0 {
             self.read()
         } else {
+            self.check_read_size(out_size, &Datatype::from_type::<T>()?)?;
             let mspace = Dataspace::try_new(&out_shape)?;
-            let mut buf = Vec::with_capacity(out_size);
+            let mut buf = alloc_buf(out_size)?;
             self.read_into_buf(buf.as_mut_ptr(), Some(&fspace), Some(&mspace))?;
             unsafe {
                 buf.set_len(out_size);
             };
-            let arr = ArrayD::from_shape_vec(out_shape, buf)?;
+            let mut arr = ArrayD::from_shape_vec(out_shape, buf)?;
+            for &axis in &reversed {
+                for mut lane in arr.lanes_mut(Axis(axis)) {
+                    let len = lane.len();
+                    for i in 0..len / 2 {
+                        lane.swap(i, len - 1 - i);
+                    }
+                }
+            }
             Ok(arr.into_dimensionality()?)
         }
     }
 
+    /// Reads the elements selected by a [`SelectionExpr`] into a vector.
+    ///
+    /// Combined selections are hyperslabs, whose elements are read in the order of their
+    /// coordinates; a single point selection is read in the order of its points.
+    pub fn read_expr<T: H5Type>(&self, expr: &SelectionExpr) -> Result<Vec<T>> {
+        ensure!(!self.obj.is_attr(), "Slicing cannot be used on attribute datasets");
+        let fspace = expr.apply_to(&self.obj.space()?)?;
+        let size = fspace.selection_size();
+        if size == 0 {
+            self.obj.dtype()?.ensure_convertible(&Datatype::from_type::<T>()?, self.conv)?;
+            return Ok(vec![]);
+        }
+        self.check_read_size(size, &Datatype::from_type::<T>()?)?;
+        let mspace = Dataspace::try_new(size)?;
+        let mut buf = alloc_buf(size)?;
+        self.read_into_buf(buf.as_mut_ptr(), Some(&fspace), Some(&mspace))?;
+        unsafe {
+            buf.set_len(size);
+        };
+        Ok(buf)
+    }
+
     /// Reads a dataset/attribute into an n-dimensional array.
     ///
     /// If the array has a fixed number of dimensions, it must match the dimensionality
     /// of the dataset/attribute.
     pub fn read<T: H5Type, D: ndarray::Dimension>(&self) -> Result<Array<T, D>> {
-        let shape = self.obj.get_shape()?;
+        let shape = self.obj.shape()?;
         if let Some(ndim) = D::NDIM {
-            let obj_ndim = shape.ndim();
-            ensure!(obj_ndim == ndim, "ndim mismatch: expected {}, got {}", ndim, obj_ndim);
+            ensure!(
+                shape.ndim() == ndim,
+                "reading {}: {} shape {:?} cannot be read into a {}-dimensional array",
+                self.obj.describe(),
+                self.obj.kind_name(),
+                shape,
+                ndim
+            );
         }
         let vec = self.read_raw()?;
         let arr = ArrayD::from_shape_vec(shape, vec)?;
@@ -131,8 +456,14 @@ impl<'a> Reader<'a> {
 
     /// Reads a dataset/attribute into a vector in memory order.
     pub fn read_raw<T: H5Type>(&self) -> Result<Vec<T>> {
-        let size = self.obj.space()?.size();
-        let mut vec = Vec::with_capacity(size);
+        let size = self.obj.size()?;
+        if size == 0 {
+            // nothing to read, but the types must still be compatible
+            self.obj.dtype()?.ensure_convertible(&Datatype::from_type::<T>()?, self.conv)?;
+            return Ok(vec![]);
+        }
+        self.check_read_size(size, &Datatype::from_type::<T>()?)?;
+        let mut vec = alloc_buf(size)?;
         self.read_into_buf(vec.as_mut_ptr(), None, None).map(|()| {
             unsafe {
                 vec.set_len(size);
@@ -182,13 +513,159 @@ impl<'a> Reader<'a> {
         self.read()
     }
 
+    /// Reads a dataset into an array with dynamic number of dimensions, counting the values
+    /// that could not be converted exactly to `T`.
+    ///
+    /// Exceptions are counted whatever the [`ConversionExceptionPolicy`] of the transfer
+    /// property list, except with [`Abort`](ConversionExceptionPolicy::Abort), which still
+    /// fails the read. Attributes are read without a transfer property list and cannot
+    /// report conversion exceptions.
+    pub fn read_with_report<T: H5Type>(&self) -> Result<(ArrayD<T>, ConversionReport)> {
+        ensure!(
+            !self.obj.is_attr(),
+            "reading {}: conversion exceptions can only be reported for datasets",
+            self.obj.describe()
+        );
+        let xfer = match &self.xfer {
+            Some(xfer) => xfer.copy()?,
+            None => DatasetTransfer::try_new()?,
+        };
+        let mut report = ConversionReport::default();
+        if xfer.get_on_conversion_exception()? != ConversionExceptionPolicy::Abort {
+            // the pointer stays valid while `xfer` is used, as both live until the read ends
+            set_conversion_report(xfer.id(), ptr::addr_of_mut!(report))?;
+        }
+        let data = Self { xfer: Some(xfer), ..self.clone() }.read_dyn()?;
+        Ok((data, report))
+    }
+
     /// Reads a scalar dataset/attribute.
+    ///
+    /// Datasets/attributes holding exactly one element in any shape (e.g. `(1,)` or `(1, 1)`,
+    /// as written by MATLAB) are read as scalars too, unless [`strict_scalar`] is set.
+    ///
+    /// [`strict_scalar`]: Self::strict_scalar
     pub fn read_scalar<T: H5Type>(&self) -> Result<T> {
-        let obj_ndim = self.obj.get_shape()?.ndim();
-        ensure!(obj_ndim == 0, "ndim mismatch: expected scalar, got {}", obj_ndim);
+        let shape = self.obj.shape()?;
+        if self.strict_scalar {
+            ensure!(
+                shape.is_empty(),
+                "reading {}: {} shape {:?} cannot be read into a scalar (`strict_scalar` is set)",
+                self.obj.describe(),
+                self.obj.kind_name(),
+                shape
+            );
+        }
+        ensure!(
+            is_single_element(&shape),
+            "reading {}: {} shape {:?} cannot be read into a scalar",
+            self.obj.describe(),
+            self.obj.kind_name(),
+            shape
+        );
         let mut val = mem::MaybeUninit::<T>::uninit();
         self.read_into_buf(val.as_mut_ptr(), None, None).map(|()| unsafe { val.assume_init() })
     }
+
+    /// Reads a string dataset/attribute into a vector of strings in memory order.
+    ///
+    /// Works for fixed-length and variable-length strings of either character set. Invalid
+    /// UTF-8 is replaced with `U+FFFD`, and trailing padding of fixed-length strings is removed.
+    pub fn read_strings(&self) -> Result<Vec<String>> {
+        let desc = self.obj.dtype()?.to_descriptor()?;
+        match desc {
+            // the bytes are not validated on read, so they may not be UTF-8 in either case
+            TypeDescriptor::VarLenUnicode => Ok(self
+                .read_raw::<VarLenUnicode>()?
+                .iter()
+                .map(|s| String::from_utf8_lossy(s.as_bytes()).into_owned())
+                .collect()),
+            TypeDescriptor::VarLenAscii => Ok(self
+                .read_raw::<VarLenAscii>()?
+                .iter()
+                .map(|s| String::from_utf8_lossy(s.as_bytes()).into_owned())
+                .collect()),
+            TypeDescriptor::FixedAscii(len) | TypeDescriptor::FixedUnicode(len) => {
+                let size = self.obj.size()?;
+                let mem_dtype = Datatype::from_descriptor(&desc)?;
+                self.check_read_size(size, &mem_dtype)?;
+                let mut buf = vec![0_u8; checked_buf_size(size, len)?];
+                if !buf.is_empty() {
+                    self.read_into_buf_as(buf.as_mut_ptr().cast(), &mem_dtype, None, None)?;
+                }
+                Ok((0..size).map(|i| decode_fixed_str(&buf[i * len..(i + 1) * len])).collect())
+            }
+            _ => fail!("expected a string type, got {}", desc),
+        }
+    }
+}
+
+/// Decodes a null-padded fixed-length string, replacing invalid UTF-8.
+fn decode_fixed_str(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|&c| c == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+/// Returns whether values of the type own variable-length data that can be deep-copied.
+fn has_vlen_data(desc: &TypeDescriptor) -> bool {
+    fn has_references(desc: &TypeDescriptor) -> bool {
+        match desc {
+            TypeDescriptor::Reference(_) => true,
+            TypeDescriptor::FixedArray(ty, _)
+            | TypeDescriptor::FixedArrayNd(ty, _)
+            | TypeDescriptor::VarLenArray(ty) => has_references(ty),
+            TypeDescriptor::Compound(ty) => ty.fields.iter().any(|f| has_references(&f.ty)),
+            _ => false,
+        }
+    }
+    has_pointers(desc) && !has_references(desc)
+}
+
+/// Frees the variable-length data allocated by the library when reading values of type
+/// `dtype` with the dataspace `space` into `buf`.
+pub(crate) fn reclaim_vlen(dtype: &Datatype, space: &Dataspace, buf: *mut c_void) -> Result<()> {
+    if crate::raw::hdf5_version_at_least(1, 12, 0) {
+        h5try!(H5Treclaim(dtype.id(), space.id(), H5P_DEFAULT, buf));
+    } else {
+        h5call!(H5Dvlen_reclaim(dtype.id(), space.id(), H5P_DEFAULT, buf)?)?;
+    }
+    Ok(())
+}
+
+/// Reads the whole attribute `attr` into `buf` as values of type `mem_dtype`.
+///
+/// Unlike dataset reads, attribute reads cannot be given a transfer property list, so the
+/// library allocates the variable-length data itself. That data is copied into allocations
+/// owned by `buf`, made with the same allocator as for datasets (see `set_vlen_manager_libc`),
+/// and the library's allocations are reclaimed.
+pub(crate) fn read_attr_into(
+    attr: &Container,
+    mem_dtype: &Datatype,
+    buf: *mut c_void,
+) -> Result<()> {
+    let desc = mem_dtype.to_descriptor()?;
+    if !has_vlen_data(&desc) {
+        h5try!(H5Aread(attr.id(), mem_dtype.id(), buf));
+        return Ok(());
+    }
+    let space = attr.space()?;
+    let size = desc.size();
+    let len = checked_buf_size(space.size()?, size)?;
+    // u64 storage keeps the pointers in the temporary buffer aligned
+    let mut storage = vec![0_u64; len.div_ceil(8)];
+    let tmp = unsafe { slice::from_raw_parts_mut(storage.as_mut_ptr().cast::<u8>(), len) };
+    h5try!(H5Aread(attr.id(), mem_dtype.id(), tmp.as_mut_ptr().cast()));
+    let out = unsafe { slice::from_raw_parts_mut(buf.cast::<u8>(), len) };
+    out.copy_from_slice(tmp);
+    for (src, dst) in tmp.chunks_exact(size).zip(out.chunks_exact_mut(size)) {
+        unsafe { DynValue::new(&desc, src).deep_copy_into(dst) };
+    }
+    reclaim_vlen(mem_dtype, &space, tmp.as_mut_ptr().cast())
+}
+
+/// Returns `true` for the shape of a scalar or of any other dataspace with exactly one element.
+fn is_single_element(shape: &[Ix]) -> bool {
+    shape.iter().all(|&dim| dim == 1)
 }
 
 /// A type for writing data into a [`Container`].
@@ -196,6 +673,9 @@ impl<'a> Reader<'a> {
 pub struct Writer<'a> {
     obj: &'a Container,
     conv: Conversion,
+    xfer: Option<DatasetTransfer>,
+    max_request_size: Option<usize>,
+    pub(crate) allow_missing_columns: bool,
 }
 
 impl<'a> Writer<'a> {
@@ -203,7 +683,17 @@ impl<'a> Writer<'a> {
     ///
     /// Any conversions (including hard/soft) are allowed by default.
     pub fn new(obj: &'a Container) -> Self {
-        Self { obj, conv: Conversion::Soft }
+        Self {
+            obj,
+            conv: Conversion::Soft,
+            xfer: None,
+            max_request_size: Some(DEFAULT_MAX_REQUEST_SIZE),
+            allow_missing_columns: false,
+        }
+    }
+
+    pub(crate) fn container(&self) -> &'a Container {
+        self.obj
     }
 
     /// Set maximum allowed conversion level.
@@ -218,23 +708,91 @@ impl<'a> Writer<'a> {
         self
     }
 
+    /// Sets the dataset transfer property list used for writes (ignored for attributes).
+    pub fn transfer_plist(mut self, dxpl: &DatasetTransfer) -> Self {
+        self.xfer = Some(dxpl.clone());
+        self
+    }
+
+    /// A short alias for `transfer_plist()`.
+    pub fn dxpl(self, dxpl: &DatasetTransfer) -> Self {
+        self.transfer_plist(dxpl)
+    }
+
+    /// Sets the maximum number of bytes written to a dataset by a single `H5Dwrite` call, or
+    /// `None` to write any selection at once. Defaults to [`DEFAULT_MAX_REQUEST_SIZE`].
+    ///
+    /// Some HDF5 builds and drivers (such as MPI-IO and some Windows configurations) fail on
+    /// requests above 2 GiB. Larger selections are written in parts of whole rows along the
+    /// first axis, one after another; the type conversion buffer of the transfer property list
+    /// is used by each part as it would be by a single request. Point selections are not split.
+    pub fn max_request_size(mut self, size: Option<usize>) -> Self {
+        self.max_request_size = size;
+        self
+    }
+
+    /// Checks that values of type `T` can be written without writing anything, for empty
+    /// destinations.
+    fn ensure_writable_as<T: H5Type>(&self) -> Result<()> {
+        Datatype::from_type::<T>()?.ensure_convertible(&self.obj.dtype()?, self.conv)
+    }
+
     fn write_from_buf<T: H5Type>(
         &self,
         buf: *const T,
         fspace: Option<&Dataspace>,
         mspace: Option<&Dataspace>,
     ) -> Result<()> {
-        let file_dtype = self.obj.dtype()?;
         let mem_dtype = Datatype::from_type::<T>()?;
+        // unit values are stored as one byte each, which the caller's buffer does not hold
+        let zeros;
+        let buf = if mem::size_of::<T>() == 0 {
+            let len = match mspace.or(fspace) {
+                Some(space) => space.selection_size(),
+                None => self.obj.space()?.size()?,
+            };
+            zeros = vec![0_u8; len * mem_dtype.size()];
+            zeros.as_ptr().cast()
+        } else {
+            buf.cast()
+        };
+        self.write_from_buf_as(buf, &mem_dtype, fspace, mspace)
+    }
+
+    pub(crate) fn write_from_buf_as(
+        &self,
+        buf: *const c_void,
+        mem_dtype: &Datatype,
+        fspace: Option<&Dataspace>,
+        mspace: Option<&Dataspace>,
+    ) -> Result<()> {
+        let file_dtype = self.obj.dtype()?;
         mem_dtype.ensure_convertible(&file_dtype, self.conv)?;
         let (obj_id, tp_id) = (self.obj.id(), mem_dtype.id());
 
         if self.obj.is_attr() {
-            h5try!(H5Awrite(obj_id, tp_id, buf.cast()));
+            h5call!(H5Awrite(obj_id, tp_id, buf.cast()))
+                .with_context(|| format!("writing {}", self.obj.describe()))?;
         } else {
-            let fspace_id = fspace.map_or(H5S_ALL, |f| f.id());
-            let mspace_id = mspace.map_or(H5S_ALL, |m| m.id());
-            h5try!(H5Dwrite(obj_id, tp_id, mspace_id, fspace_id, H5P_DEFAULT, buf.cast()));
+            let xfer_id = self.xfer.as_ref().map_or(H5P_DEFAULT, |xfer| xfer.id());
+            let size = mem_dtype.size();
+            let buf = buf.cast_mut().cast();
+            transfer_in_parts(
+                self.obj,
+                buf,
+                size,
+                fspace,
+                mspace,
+                self.max_request_size,
+                |f, m, buf| {
+                    let fspace_id = f.map_or(H5S_ALL, |f| f.id());
+                    let mspace_id = m.map_or(H5S_ALL, |m| m.id());
+                    h5call!(H5Dwrite(obj_id, tp_id, mspace_id, fspace_id, xfer_id, buf))
+                        .with_context(|| format!("writing {}", self.obj.describe()))?;
+                    record_io(self.obj, IoKind::Write, f, size);
+                    Ok(())
+                },
+            )?;
         }
         Ok(())
     }
@@ -244,6 +802,9 @@ impl<'a> Writer<'a> {
     /// If the array has a fixed number of dimensions, it must match the dimensionality of
     /// dataset. Use the multi-dimensional slice macro `s![]` from `ndarray` to conveniently create
     /// a multidimensional slice.
+    ///
+    /// Slices with a negative step (like `s![..;-1]`) are written with the equivalent positive
+    /// step from a reversed copy of `arr`.
     pub fn write_slice<'b, A, T, S, D>(&self, arr: A, selection: S) -> Result<()>
     where
         A: Into<ArrayView<'b, T, D>>,
@@ -252,20 +813,19 @@ impl<'a> Writer<'a> {
         Error: From<S::Error>,
         D: ndarray::Dimension,
     {
+        self.obj.handle().ensure_writable()?;
         ensure!(!self.obj.is_attr(), "Slicing cannot be used on attribute datasets");
 
         let selection = selection.try_into()?;
+        let reversed = selection.reversed_out_axes();
         let obj_space = self.obj.space()?;
 
-        let out_shape = selection.out_shape(obj_space.shape())?;
-        let out_size: Ix = out_shape.iter().product();
+        let out_shape = selection.out_shape(obj_space.shape()?)?;
+        let out_size = checked_size(&out_shape)?;
         let fspace = obj_space.select(selection)?;
-        let view = arr.into();
+        let mut view = arr.into();
 
-        if let Some(ndim) = D::NDIM {
-            let out_ndim = out_shape.len();
-            ensure!(ndim == out_ndim, "Selection ndim ({}) != array ndim ({})", out_ndim, ndim);
-        } else {
+        if D::NDIM.is_none() {
             let fsize = fspace.selection_size();
             ensure!(
                 out_size == fsize,
@@ -273,13 +833,15 @@ impl<'a> Writer<'a> {
                 out_size,
                 fsize
             );
-            ensure!(
-                view.shape() == out_shape.as_slice(),
-                "Shape mismatch: memory ({:?}) != destination ({:?})",
-                view.shape(),
-                out_shape
-            );
         }
+        ensure!(
+            view.shape() == out_shape.as_slice(),
+            "writing {}: data shape {:?} does not match selection shape {:?} (dataset shape {:?})",
+            self.obj.describe(),
+            view.shape(),
+            out_shape,
+            obj_space.shape()
+        );
 
         if out_size == 0 {
             Ok(())
@@ -292,8 +854,16 @@ impl<'a> Writer<'a> {
                 view.is_standard_layout(),
                 "Input array is not in standard layout or non-contiguous"
             );
-
-            self.write_from_buf(view.as_ptr(), Some(&fspace), Some(&mspace))
+            if reversed.is_empty() {
+                return self.write_from_buf(view.as_ptr(), Some(&fspace), Some(&mspace));
+            }
+            for &axis in &reversed {
+                view.invert_axis(Axis(axis));
+            }
+            // bitwise copies in the reversed order; they are never dropped, HDF5 only reads them
+            let buf: Vec<mem::MaybeUninit<T>> =
+                view.iter().map(|elem| mem::MaybeUninit::new(unsafe { ptr::read(elem) })).collect();
+            self.write_from_buf(buf.as_ptr().cast::<T>(), Some(&fspace), Some(&mspace))
         }
     }
 
@@ -307,6 +877,7 @@ impl<'a> Writer<'a> {
         T: H5Type,
         D: ndarray::Dimension,
     {
+        self.obj.handle().ensure_writable()?;
         let view = arr.into();
         ensure!(
             view.is_standard_layout(),
@@ -314,9 +885,17 @@ impl<'a> Writer<'a> {
         );
 
         let src = view.shape();
-        let dst = &*self.obj.get_shape()?;
-        if src != dst {
-            fail!("shape mismatch when writing: memory = {:?}, destination = {:?}", src, dst);
+        let dst = &*self.obj.shape()?;
+        ensure!(
+            src == dst,
+            "writing {}: data shape {:?} does not match {} shape {:?}",
+            self.obj.describe(),
+            src,
+            self.obj.kind_name(),
+            dst
+        );
+        if view.is_empty() {
+            return self.ensure_writable_as::<T>();
         }
 
         self.write_from_buf(view.as_ptr(), None, None)
@@ -332,6 +911,7 @@ impl<'a> Writer<'a> {
         A: Into<ArrayView1<'b, T>>,
         T: H5Type,
     {
+        self.obj.handle().ensure_writable()?;
         let view = arr.into();
         ensure!(
             view.is_standard_layout(),
@@ -339,17 +919,33 @@ impl<'a> Writer<'a> {
         );
 
         let src = view.len();
-        let dst = self.obj.get_shape()?.size();
-        if src != dst {
-            fail!("length mismatch when writing: memory = {:?}, destination = {:?}", src, dst);
+        let dst = checked_size(&self.obj.shape()?)?;
+        ensure!(
+            src == dst,
+            "writing {}: data length {} does not match {} size {}",
+            self.obj.describe(),
+            src,
+            self.obj.kind_name(),
+            dst
+        );
+        if src == 0 {
+            return self.ensure_writable_as::<T>();
         }
         self.write_from_buf(view.as_ptr(), None, None)
     }
 
-    /// Writes a scalar dataset/attribute.
+    /// Writes a scalar dataset/attribute, or the single element of one with a shape such as
+    /// `(1,)` or `(1, 1)`.
     pub fn write_scalar<T: H5Type>(&self, val: &T) -> Result<()> {
-        let ndim = self.obj.get_shape()?.ndim();
-        ensure!(ndim == 0, "ndim mismatch: expected scalar, got {}", ndim);
+        self.obj.handle().ensure_writable()?;
+        let shape = self.obj.shape()?;
+        ensure!(
+            is_single_element(&shape),
+            "writing {}: scalar data does not match {} shape {:?}",
+            self.obj.describe(),
+            self.obj.kind_name(),
+            shape
+        );
         self.write_from_buf(val as *const _, None, None)
     }
 }
@@ -359,6 +955,7 @@ impl<'a> Writer<'a> {
 pub struct ByteReader {
     obj: Container,
     pos: u64,
+    len: usize,
     dt: Datatype,
     obj_space: Dataspace,
     xfer: PropertyList,
@@ -382,15 +979,17 @@ impl ByteReader {
         file_dtype.ensure_convertible(&mem_dtype, Conversion::NoOp)?;
 
         let obj_space = obj.space()?;
-        ensure!(obj_space.shape().len() == 1, "Only rank 1 datasets can be read via ByteReader");
+        let shape = obj_space.shape()?;
+        ensure!(shape.len() == 1, "Only rank 1 datasets can be read via ByteReader");
+        let len = shape[0];
         let xfer = PropertyList::from_id(h5call!(H5Pcreate(*crate::globals::H5P_DATASET_XFER))?)?;
         // Always use libc allocator for vlen data (HDF5 allocator not available in runtime-loading mode)
-        crate::hl::plist::set_vlen_manager_libc(xfer.id())?;
-        Ok(Self { obj, pos: 0, obj_space, dt: mem_dtype, xfer })
+        crate::hl::plist::dataset_transfer::set_vlen_manager_libc(xfer.id())?;
+        Ok(Self { obj, pos: 0, len, obj_space, dt: mem_dtype, xfer })
     }
 
     fn dataset_len(&self) -> usize {
-        self.obj_space.shape()[0]
+        self.len
     }
 
     fn remaining_len(&self) -> usize {
@@ -408,7 +1007,7 @@ impl io::Read for ByteReader {
         let pos = self.pos as usize;
         let amt = std::cmp::min(buf.len(), self.remaining_len());
         let selection = Selection::new(pos..pos + amt);
-        let out_shape = selection.out_shape(self.obj_space.shape())?;
+        let out_shape = selection.out_shape([self.len])?;
         let fspace = self.obj_space.select(selection)?;
         let mspace = Dataspace::try_new(&out_shape)?;
         h5call!(H5Dread(
@@ -493,6 +1092,36 @@ impl Container {
         self.handle().id_type() == H5I_ATTR
     }
 
+    /// Describes the dataset or attribute in error messages, e.g. `dataset '/x'`.
+    pub(crate) fn describe(&self) -> String {
+        if self.is_attr() {
+            let attr: &Attribute = unsafe { self.transmute() };
+            format!("attribute '{}' of '{}'", attr.name(), self.name())
+        } else {
+            format!("dataset '{}'", self.name())
+        }
+    }
+
+    /// Returns "dataset" or "attribute", for use in error messages.
+    pub(crate) fn kind_name(&self) -> &'static str {
+        if self.is_attr() {
+            "attribute"
+        } else {
+            "dataset"
+        }
+    }
+
+    /// Describes the shape and type in the style of h5py, e.g. `shape (100, 3), type float64`.
+    pub(crate) fn shape_and_type_repr(&self) -> Result<String> {
+        let shape = match self.space()?.shape()?.as_slice() {
+            [n] => format!("({n},)"),
+            dims => {
+                format!("({})", dims.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))
+            }
+        };
+        Ok(format!("shape {}, type {}", shape, self.dtype()?))
+    }
+
     /// Creates a reader wrapper for this dataset/attribute, allowing to
     /// set custom type conversion options when reading.
     pub fn as_reader(&self) -> Reader<'_> {
@@ -532,13 +1161,21 @@ impl Container {
     }
 
     #[doc(hidden)]
+    #[deprecated(note = "use `shape()`, which now returns a `Result`")]
     pub fn get_shape(&self) -> Result<Vec<Ix>> {
-        self.space().map(|s| s.shape())
+        self.shape()
     }
 
-    /// Returns the shape of the dataset/attribute.
-    pub fn shape(&self) -> Vec<Ix> {
-        self.space().ok().map_or_else(Vec::new, |s| s.shape())
+    /// Returns the shape of the dataset/attribute, failing if a dimension does not fit into
+    /// [`Ix`] (on 32-bit targets); see [`shape_u64`](Self::shape_u64).
+    pub fn shape(&self) -> Result<Vec<Ix>> {
+        self.space()?.shape()
+    }
+
+    /// Returns the shape of the dataset/attribute as stored by the library, which is valid on
+    /// all targets.
+    pub fn shape_u64(&self) -> Vec<u64> {
+        self.space().ok().map_or_else(Vec::new, |s| s.shape_u64())
     }
 
     /// Returns the number of dimensions in the dataset/attribute.
@@ -546,9 +1183,10 @@ impl Container {
         self.space().ok().map_or(0, |s| s.ndim())
     }
 
-    /// Returns the total number of elements in the dataset/attribute.
-    pub fn size(&self) -> usize {
-        self.shape().iter().product()
+    /// Returns the total number of elements in the dataset/attribute, failing if it does not
+    /// fit into `usize`.
+    pub fn size(&self) -> Result<usize> {
+        self.space()?.size()
     }
 
     /// Returns whether this dataset/attribute is a scalar.
@@ -620,6 +1258,12 @@ impl Container {
         self.as_reader().read_dyn()
     }
 
+    /// Reads a dataset into an array with dynamic number of dimensions, counting the values
+    /// that could not be converted exactly to `T`; see [`Reader::read_with_report`].
+    pub fn read_with_report<T: H5Type>(&self) -> Result<(ArrayD<T>, ConversionReport)> {
+        self.as_reader().read_with_report()
+    }
+
     /// Reads a slice of an n-dimensional array.
     /// If the dimensionality `D` has a fixed number of dimensions, it must match the dimensionality of
     /// the slice, after singleton dimensions are dropped.
@@ -635,11 +1279,25 @@ impl Container {
         self.as_reader().read_slice(selection)
     }
 
-    /// Reads a scalar dataset/attribute.
+    /// Reads the elements selected by a [`SelectionExpr`] into a vector, see
+    /// [`Reader::read_expr`].
+    pub fn read_expr<T: H5Type>(&self, expr: &SelectionExpr) -> Result<Vec<T>> {
+        self.as_reader().read_expr(expr)
+    }
+
+    /// Reads a scalar dataset/attribute, or the single element of one with a shape such as
+    /// `(1,)`; see [`Reader::strict_scalar`] to accept scalar dataspaces only.
     pub fn read_scalar<T: H5Type>(&self) -> Result<T> {
         self.as_reader().read_scalar()
     }
 
+    /// Reads a string dataset/attribute into a vector of strings in memory order.
+    ///
+    /// See [`Reader::read_strings`].
+    pub fn read_strings(&self) -> Result<Vec<String>> {
+        self.as_reader().read_strings()
+    }
+
     /// Writes an n-dimensional array view into a dataset/attribute.
     ///
     /// The shape of the view must match the shape of the dataset/attribute exactly.
@@ -682,7 +1340,8 @@ impl Container {
         self.as_writer().write_slice(arr, selection)
     }
 
-    /// Writes a scalar dataset/attribute.
+    /// Writes a scalar dataset/attribute, or the single element of one with a shape such as
+    /// `(1,)`.
     pub fn write_scalar<T: H5Type>(&self, val: &T) -> Result<()> {
         self.as_writer().write_scalar(val)
     }
@@ -0,0 +1,461 @@
+//! Copying objects, optionally with progress reports and cancellation.
+//!
+//! `H5Ocopy` has no progress callback, so large datasets are copied block by block instead
+//! (one block per allocated chunk, or slabs along the first dimension for contiguous data),
+//! reporting progress between blocks.
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use hdf5_types::TypeDescriptor;
+
+use crate::sys::h5d::H5Dcreate2;
+use crate::sys::h5g::H5Gcreate2;
+use crate::sys::h5o::H5Ocopy;
+use crate::sys::h5p::H5Pget_attr_creation_order;
+use crate::sys::h5t::H5Tcopy;
+
+use crate::hl::attribute::copy_attr;
+use crate::hl::group::LinkTarget;
+use crate::hl::plist::dataset_create::{AttrCreationOrder, Layout};
+use crate::hl::plist::file_create::LinkCreationOrder;
+use crate::hl::plist::object_copy::{ObjectCopy, ObjectCopyBuilder};
+use crate::internal_prelude::*;
+use crate::{DeterministicOrder, IterationOrder, LocationToken};
+
+/// Datasets with at least this many bytes of data are copied block by block by default.
+pub const DEFAULT_PROGRESS_THRESHOLD: usize = 64 * 1024 * 1024;
+
+/// Target size of the blocks contiguous datasets are copied in.
+const CONTIGUOUS_BLOCK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Options for [`Group::copy_object_with_progress`].
+#[derive(Clone, Debug)]
+pub struct CopyOptions {
+    /// Object copy properties used for objects copied with `H5Ocopy`; only
+    /// `copy_without_attr` applies to datasets copied block by block.
+    pub plist: Option<ObjectCopy>,
+    /// Datasets with at least this many bytes of data are copied block by block with
+    /// progress reports; smaller datasets and all other objects are copied with `H5Ocopy`.
+    pub progress_threshold: usize,
+    /// Order in which the attributes of datasets copied block by block are copied.
+    pub order: DeterministicOrder,
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        Self {
+            plist: None,
+            progress_threshold: DEFAULT_PROGRESS_THRESHOLD,
+            order: DeterministicOrder::default(),
+        }
+    }
+}
+
+pub(crate) fn has_pointers(desc: &TypeDescriptor) -> bool {
+    match desc {
+        TypeDescriptor::VarLenArray(_)
+        | TypeDescriptor::VarLenAscii
+        | TypeDescriptor::VarLenUnicode
+        | TypeDescriptor::Reference(_) => true,
+        TypeDescriptor::FixedArray(ty, _) | TypeDescriptor::FixedArrayNd(ty, _) => has_pointers(ty),
+        TypeDescriptor::Compound(ty) => ty.fields.iter().any(|f| has_pointers(&f.ty)),
+        _ => false,
+    }
+}
+
+/// Returns whether the in-memory layout of `desc` has padding bytes, which are uninitialized.
+pub(crate) fn has_padding(desc: &TypeDescriptor) -> bool {
+    match desc {
+        TypeDescriptor::FixedArray(ty, _) | TypeDescriptor::FixedArrayNd(ty, _) => has_padding(ty),
+        TypeDescriptor::Compound(ty) => {
+            let mut fields: Vec<_> = ty.fields.iter().collect();
+            fields.sort_by_key(|f| f.offset);
+            let mut end = 0;
+            for f in fields {
+                if f.offset != end || has_padding(&f.ty) {
+                    return true;
+                }
+                end += f.ty.size();
+            }
+            end != ty.size
+        }
+        _ => false,
+    }
+}
+
+/// Returns the blocks (offset and shape) that hold the data of a dataset.
+fn data_blocks(ds: &Dataset, elem_size: usize) -> Result<Vec<(Vec<Ix>, Vec<Ix>)>> {
+    let shape = ds.shape()?;
+    if let Some(chunk) = ds.chunk() {
+        // only allocated chunks need to be copied, the rest is the fill value
+        let num_chunks = ds.num_chunks().unwrap_or(0);
+        Ok((0..num_chunks)
+            .filter_map(|i| ds.chunk_info(i))
+            .map(|info| {
+                let offset: Vec<Ix> = info.offset.iter().map(|&o| o as _).collect();
+                let dims = (0..shape.len()).map(|d| chunk[d].min(shape[d] - offset[d])).collect();
+                (offset, dims)
+            })
+            .collect())
+    } else {
+        let row_size = shape[1..].iter().product::<usize>() * elem_size;
+        let rows = (CONTIGUOUS_BLOCK_SIZE / row_size.max(1)).max(1);
+        Ok((0..shape[0])
+            .step_by(rows)
+            .map(|start| {
+                let mut offset = vec![0; shape.len()];
+                let mut dims = shape.clone();
+                offset[0] = start;
+                dims[0] = rows.min(shape[0] - start);
+                (offset, dims)
+            })
+            .collect())
+    }
+}
+
+fn copy_dataset_blocks<F>(src: &Dataset, dst: &Dataset, mut progress: F) -> Result<ControlFlow<()>>
+where
+    F: FnMut(u64, u64) -> ControlFlow<()>,
+{
+    let dtype = src.dtype()?;
+    let elem_size = dtype.size();
+    let blocks = data_blocks(src, elem_size)?;
+    let block_bytes = |dims: &[Ix]| (dims.iter().product::<usize>() * elem_size) as u64;
+    let total = blocks.iter().map(|(_, dims)| block_bytes(dims)).sum();
+    let (src_space, dst_space) = (src.space()?, dst.space()?);
+    let mut buf = Vec::<u8>::new();
+    let mut done = 0;
+    for (offset, dims) in blocks {
+        let selection: Vec<SliceOrIndex> = offset
+            .iter()
+            .zip(&dims)
+            .map(|(&start, &count)| SliceOrIndex::SliceCount { start, step: 1, count, block: 1 })
+            .collect();
+        let selection = Selection::from(Hyperslab::from(selection));
+        let mem_space = Dataspace::try_new(dims.as_slice())?;
+        let (src_sel, dst_sel) = (src_space.select(&selection)?, dst_space.select(&selection)?);
+        buf.resize(block_bytes(&dims) as usize, 0);
+        // the file datatype is used as memory type, so the bytes are copied without conversion
+        src.as_reader().read_into_buf_as(
+            buf.as_mut_ptr().cast(),
+            &dtype,
+            Some(&src_sel),
+            Some(&mem_space),
+        )?;
+        dst.as_writer().write_from_buf_as(
+            buf.as_ptr().cast(),
+            &dtype,
+            Some(&dst_sel),
+            Some(&mem_space),
+        )?;
+        done += block_bytes(&dims);
+        if progress(done, total).is_break() {
+            return Ok(ControlFlow::Break(()));
+        }
+    }
+    Ok(ControlFlow::Continue(()))
+}
+
+/// Recursive copy of group members that copies objects reachable through several hard links
+/// only once and links them again.
+///
+/// `H5Ocopy` preserves hard links within a single copied object but not across calls, so
+/// groups are recreated here and only the other objects are copied with `H5Ocopy`. Copied
+/// objects are identified by their file number and token (or address), which also covers
+/// objects reached through expanded external links.
+///
+/// Likewise, each `H5Ocopy` call would copy the committed datatype of a dataset again, so
+/// objects are copied with `H5O_COPY_MERGE_COMMITTED_DTYPE_FLAG`, which makes them use the
+/// matching committed datatype linked in the destination instead. The named datatypes of
+/// each group are copied before its other members, so that datasets share them as in the
+/// source (unless the datatype is linked in a group copied after the dataset).
+pub(crate) struct TreeCopy {
+    plist: ObjectCopy,
+    order: DeterministicOrder,
+    copied: HashMap<(u64, LocationToken), String>,
+}
+
+impl TreeCopy {
+    pub fn new(plist: &ObjectCopy, order: DeterministicOrder) -> Result<Self> {
+        let plist = ObjectCopyBuilder::from_plist(plist)?.merge_committed_dtype(true).finish()?;
+        Ok(Self { plist, order, copied: HashMap::new() })
+    }
+
+    /// Copies all members of `src` into `dst`, which is then treated as the copy of `src`.
+    pub fn copy_members(&mut self, src: &Group, dst: &Group, creation_tracked: bool) -> Result<()> {
+        let info = src.loc_info()?;
+        self.copied.entry((info.fileno, info.token)).or_insert_with(|| dst.name());
+        let order = self.order.traversal(creation_tracked);
+        let mut names =
+            src.iter_visit(IterationOrder::Increasing, order, vec![], |_, name, _, names| {
+                names.push(name.to_owned());
+                true
+            })?;
+        // the sort is stable, so the order is otherwise kept
+        names.sort_by_cached_key(|name| {
+            let info = src.loc_info_by_name(name);
+            !info.is_ok_and(|info| info.loc_type == LocationType::NamedDatatype)
+        });
+        for name in names {
+            self.copy_member(src, dst, &name)
+                .with_context(|| format!("copying '{}'", join_path(&src.name(), &name)))?;
+        }
+        Ok(())
+    }
+
+    fn copy_member(&mut self, src: &Group, dst: &Group, name: &str) -> Result<()> {
+        let resolved = src.loc_info_by_name(name).ok();
+        match src.link_target(name)? {
+            Some(LinkTarget::Soft(path))
+                if !self.plist.expand_soft_links() || resolved.is_none() =>
+            {
+                return dst.link_soft(&path, name);
+            }
+            Some(LinkTarget::External { file, path })
+                if !self.plist.expand_ext_links() || resolved.is_none() =>
+            {
+                return dst.link_external(&file, &path, name);
+            }
+            _ => {}
+        }
+        let Some(info) = resolved else {
+            fail!("unable to resolve link '{}'", name);
+        };
+        let key = (info.fileno, info.token);
+        if let Some(path) = self.copied.get(&key) {
+            return dst.link_hard(path, name);
+        }
+        if info.loc_type != LocationType::Group {
+            let c_name = to_cstring(name)?;
+            h5try!(H5Ocopy(
+                src.id(),
+                c_name.as_ptr(),
+                dst.id(),
+                c_name.as_ptr(),
+                self.plist.id(),
+                H5P_DEFAULT
+            ));
+            self.copied.insert(key, join_path(&dst.name(), name));
+            return Ok(());
+        }
+
+        let group = src.group(name)?;
+        let gcpl = group.gcpl()?;
+        let c_name = to_cstring(name)?;
+        let new = h5lock!(Group::from_id(h5try!(H5Gcreate2(
+            dst.id(),
+            c_name.as_ptr(),
+            H5P_DEFAULT,
+            gcpl.id(),
+            H5P_DEFAULT
+        ))))?;
+        if !self.plist.copy_without_attr() {
+            let attr_order = h5get!(H5Pget_attr_creation_order(gcpl.id()): c_uint)?;
+            let tracked = AttrCreationOrder::from_bits_truncate(attr_order)
+                .contains(AttrCreationOrder::TRACKED);
+            for attr_name in Attribute::attr_names_by(&group, self.order.traversal(tracked))? {
+                copy_attr(&group.attr(&attr_name)?, &new, &attr_name)?;
+            }
+        }
+        let tracked = gcpl.link_creation_order().contains(LinkCreationOrder::TRACKED);
+        self.copy_members(&group, &new, tracked)
+    }
+}
+
+fn join_path(parent: &str, name: &str) -> String {
+    if parent.ends_with('/') {
+        format!("{parent}{name}")
+    } else {
+        format!("{parent}/{name}")
+    }
+}
+
+impl Group {
+    /// Copies the object `name` (recursively, for groups) to `dst_name` in `dst`, which may be
+    /// in another file.
+    pub fn copy_object(
+        &self,
+        name: &str,
+        dst: &Self,
+        dst_name: &str,
+        plist: Option<&ObjectCopy>,
+    ) -> Result<()> {
+        let name = to_cstring(name)?;
+        let dst_name = to_cstring(dst_name)?;
+        let ocpypl = plist.map_or(H5P_DEFAULT, |p| p.id());
+        h5call!(H5Ocopy(self.id(), name.as_ptr(), dst.id(), dst_name.as_ptr(), ocpypl, H5P_DEFAULT))
+            .and(Ok(()))
+    }
+
+    /// Copies the object `name` to `dst_name` in `dst`, reporting progress for large datasets.
+    ///
+    /// Datasets with at least [`CopyOptions::progress_threshold`] bytes of fixed-size data
+    /// that are stored chunked or contiguously (without external files) are created with the
+    /// same datatype, dataspace and creation properties and then copied block by block, calling
+    /// `progress(bytes_done, bytes_total)` after each block; everything else is copied with
+    /// [`copy_object`](Self::copy_object) followed by a single progress report.
+    ///
+    /// Returning [`ControlFlow::Break`] from `progress` cancels the copy: the partially copied
+    /// destination is unlinked and `ControlFlow::Break` is returned. The destination is also
+    /// unlinked if the copy fails.
+    pub fn copy_object_with_progress<F>(
+        &self,
+        name: &str,
+        dst: &Self,
+        dst_name: &str,
+        options: &CopyOptions,
+        mut progress: F,
+    ) -> Result<ControlFlow<()>>
+    where
+        F: FnMut(u64, u64) -> ControlFlow<()>,
+    {
+        let src = match self.loc_type_by_name(name)? {
+            LocationType::Dataset => Some(self.dataset(name)?),
+            _ => None,
+        };
+        let nbytes = match &src {
+            Some(ds) => ds.size()? * ds.dtype()?.size(),
+            None => 0,
+        };
+        let block_copy = src.as_ref().map_or(Ok(false), |ds| -> Result<bool> {
+            let dtype = ds.dtype()?;
+            let dcpl = ds.dcpl()?;
+            Ok(nbytes >= options.progress_threshold
+                && ds.ndim() > 0
+                && matches!(ds.layout(), Layout::Chunked | Layout::Contiguous)
+                && dcpl.external().is_empty()
+                && dtype.to_descriptor().is_ok_and(|desc| !has_pointers(&desc)))
+        })?;
+        let src = match src {
+            Some(src) if block_copy => src,
+            _ => {
+                self.copy_object(name, dst, dst_name, options.plist.as_ref())?;
+                if progress(nbytes as _, nbytes as _).is_break() {
+                    dst.unlink(dst_name)?;
+                    return Ok(ControlFlow::Break(()));
+                }
+                return Ok(ControlFlow::Continue(()));
+            }
+        };
+
+        let c_dst_name = to_cstring(dst_name)?;
+        let new = h5lock!({
+            let dtype = Datatype::from_id(h5try!(H5Tcopy(src.dtype()?.id())))?;
+            let dcpl = src.dcpl()?;
+            let space = src.space()?;
+            let id = h5try!(H5Dcreate2(
+                dst.id(),
+                c_dst_name.as_ptr(),
+                dtype.id(),
+                space.id(),
+                H5P_DEFAULT,
+                dcpl.id(),
+                H5P_DEFAULT
+            ));
+            Dataset::from_id(id)
+        })?;
+        let result = (|| {
+            if !options.plist.as_ref().is_some_and(ObjectCopy::copy_without_attr) {
+                let tracked =
+                    src.dcpl()?.attr_creation_order().contains(AttrCreationOrder::TRACKED);
+                for attr_name in Attribute::attr_names_by(&src, options.order.traversal(tracked))? {
+                    copy_attr(&src.attr(&attr_name)?, &new, &attr_name)?;
+                }
+            }
+            copy_dataset_blocks(&src, &new, &mut progress)
+        })();
+        drop(new);
+        match result {
+            Ok(ControlFlow::Continue(())) => Ok(ControlFlow::Continue(())),
+            Ok(ControlFlow::Break(())) => dst.unlink(dst_name).map(|()| ControlFlow::Break(())),
+            Err(err) => {
+                let _ = dst.unlink(dst_name);
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::ops::ControlFlow;
+
+    use hdf5_types::{CompoundField, CompoundType, H5Type, TypeDescriptor};
+    use ndarray::Array2;
+
+    use super::{has_padding, CopyOptions};
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_has_padding() {
+        let compound = |fields, size| TypeDescriptor::Compound(CompoundType { fields, size });
+        assert!(!has_padding(&<[[u16; 3]; 2]>::type_descriptor()));
+        let packed =
+            vec![CompoundField::typed::<u8>("a", 0, 0), CompoundField::typed::<u16>("b", 1, 1)];
+        assert!(!has_padding(&compound(packed.clone(), 3)));
+        let padded =
+            vec![CompoundField::typed::<u8>("a", 0, 0), CompoundField::typed::<u32>("b", 4, 1)];
+        assert!(has_padding(&compound(padded, 8)));
+        assert!(has_padding(&compound(packed.clone(), 4)));
+        let nested = compound(packed, 4);
+        let outer = vec![CompoundField::new("n", nested, 0, 0)];
+        assert!(has_padding(&compound(outer, 4)));
+    }
+
+    #[test]
+    pub fn test_copy_object_with_progress() {
+        with_tmp_file(|file| {
+            let data = Array2::from_shape_fn((1000, 100), |(i, j)| (i * 100 + j) as f64);
+            let src = file.new_dataset_builder().chunk((100, 100)).with_data(&data);
+            let src = src.create("src").unwrap();
+            src.new_attr::<u32>().create("attr").unwrap().write_scalar(&7).unwrap();
+            let dst = file.create_group("dst").unwrap();
+            let options = CopyOptions { progress_threshold: 1024, ..Default::default() };
+
+            let mut steps = vec![];
+            let flow = file
+                .copy_object_with_progress("src", &dst, "copy", &options, |done, total| {
+                    steps.push((done, total));
+                    ControlFlow::Continue(())
+                })
+                .unwrap();
+            assert_eq!(flow, ControlFlow::Continue(()));
+            assert_eq!(steps.len(), 10);
+            assert!(steps.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 == w[1].1));
+            assert_eq!(steps.last(), Some(&(800_000, 800_000)));
+            let copy = dst.dataset("copy").unwrap();
+            assert_eq!(copy.read_2d::<f64>().unwrap(), data);
+            assert_eq!(copy.chunk(), Some(vec![100, 100]));
+            assert_eq!(copy.attr("attr").unwrap().read_scalar::<u32>().unwrap(), 7);
+
+            // cancelling halfway leaves no destination object behind
+            let mut calls = 0;
+            let flow = file
+                .copy_object_with_progress("src", &dst, "cancelled", &options, |done, total| {
+                    calls += 1;
+                    if done * 2 >= total {
+                        ControlFlow::Break(())
+                    } else {
+                        ControlFlow::Continue(())
+                    }
+                })
+                .unwrap();
+            assert_eq!(flow, ControlFlow::Break(()));
+            assert_eq!(calls, 5);
+            assert!(!dst.link_exists("cancelled"));
+
+            // small datasets are copied in one go
+            let mut steps = vec![];
+            let flow = file
+                .copy_object_with_progress("src", &dst, "small", &Default::default(), |d, t| {
+                    steps.push((d, t));
+                    ControlFlow::Continue(())
+                })
+                .unwrap();
+            assert_eq!(flow, ControlFlow::Continue(()));
+            assert_eq!(steps, [(800_000, 800_000)]);
+            assert_eq!(dst.dataset("small").unwrap().read_2d::<f64>().unwrap(), data);
+        })
+    }
+}
@@ -1,32 +1,41 @@
 //! Interfaces for `Dataset` objects.
 
-use std::fmt::{self, Debug};
+use std::fmt::{self, Debug, Display};
+use std::mem;
 use std::ops::Deref;
+use std::path::Path;
 
+use hdf5_types::{FixedUnicode, VarLenUnicode};
 use ndarray::{self, ArrayView};
 
+use crate::dim::checked_size;
 #[cfg(feature = "zfp")]
 use crate::hl;
+use crate::hl::chunks::{bad_chunking_warning, warn_chunking_from_env, ChunkInfo};
+use crate::hl::datatype::ByteOrder;
 #[cfg(feature = "blosc")]
 use crate::hl::filters::{Blosc, BloscShuffle};
-use crate::hl::filters::{Filter, SZip, ScaleOffset};
+use crate::hl::filters::{Filter, PipelineEntry, SZip, ScaleOffset};
+use crate::hl::group::{check_link_name, link_path_exists};
+use crate::hl::io_stats::{IoRecorder, IoStats};
 #[cfg(all(feature = "1.10.0", feature = "link"))]
 use crate::hl::plist::dataset_access::VirtualView;
-use crate::hl::plist::dataset_access::{DatasetAccess, DatasetAccessBuilder};
+use crate::hl::plist::dataset_access::{ChunkCache, DatasetAccess, DatasetAccessBuilder};
 #[cfg(all(feature = "1.10.0", feature = "link"))]
 use crate::hl::plist::dataset_create::ChunkOpts;
 use crate::hl::plist::dataset_create::{
     AllocTime, AttrCreationOrder, DatasetCreate, DatasetCreateBuilder, FillTime, Layout,
 };
 use crate::hl::plist::link_create::{CharEncoding, LinkCreate, LinkCreateBuilder};
+use crate::hl::strings::{fixed_unicode_strings, varlen_unicode_strings, StringTruncation};
 use crate::internal_prelude::*;
 use crate::sys::h5::HADDR_UNDEF;
 use crate::sys::h5d::{
-    H5Dcreate2, H5Dcreate_anon, H5Dget_access_plist, H5Dget_create_plist, H5Dget_offset,
-    H5Dset_extent,
+    H5D_space_status_t, H5Dcreate2, H5Dcreate_anon, H5Dget_access_plist, H5Dget_chunk_info,
+    H5Dget_create_plist, H5Dget_num_chunks, H5Dget_offset, H5Dget_space_status, H5Dset_extent,
+    H5Dwrite_chunk,
 };
-#[cfg(all(feature = "1.10.0", feature = "link"))]
-use crate::sys::h5d::{H5Dflush, H5Drefresh};
+use crate::sys::h5d::{H5Dflush, H5Dopen2, H5Drefresh};
 use crate::sys::h5l::H5Ldelete;
 use crate::sys::h5p::H5P_DEFAULT;
 use crate::sys::h5z::H5Z_filter_t;
@@ -35,6 +44,14 @@ use hdf5_types::{OwnedDynValue, TypeDescriptor};
 /// Default chunk size when filters are enabled and the chunk size is not specified.
 pub const DEFAULT_CHUNK_SIZE_KB: usize = 64 * 1024;
 
+/// Maximum size of raw data for datasets with compact layout (stored in the object header).
+///
+/// This is the 64 KiB object header message limit minus the layout message's own fields.
+pub const COMPACT_MAX_SIZE: usize = 64 * 1024 - 4;
+
+/// Maximum size of a single chunk in bytes, a hard limit of the library (4 GiB minus one byte).
+pub const CHUNK_MAX_SIZE: usize = u32::MAX as usize;
+
 /// Represents the HDF5 dataset object.
 #[repr(transparent)]
 #[derive(Clone)]
@@ -52,7 +69,29 @@ impl ObjectClass for Dataset {
         &self.0
     }
 
-    // TODO: short_repr()
+    fn validate(&self) -> Result<()> {
+        // the dataset's pipeline may need one of the filters provided by this crate
+        crate::hl::filters::ensure_filters_registered();
+        Ok(())
+    }
+
+    fn short_repr(&self) -> Option<String> {
+        let name = self.name();
+        let mut repr = match name.rsplit('/').next() {
+            Some(basename) if !basename.is_empty() => format!("\"{basename}\": "),
+            _ => "(anonymous): ".to_owned(),
+        };
+        repr.push_str(&self.shape_and_type_repr().ok()?);
+        if let Some(chunk) = self.chunk() {
+            let dims = chunk.iter().map(ToString::to_string).collect::<Vec<_>>();
+            let dims = if dims.len() == 1 { format!("{},", dims[0]) } else { dims.join(", ") };
+            repr.push_str(&format!(", chunks ({dims})"));
+        }
+        for filter in self.filters() {
+            repr.push_str(&format!(", {filter}"));
+        }
+        Some(repr)
+    }
 }
 
 impl Debug for Dataset {
@@ -61,6 +100,12 @@ impl Debug for Dataset {
     }
 }
 
+impl Display for Dataset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display_fmt(f)
+    }
+}
+
 impl Deref for Dataset {
     type Target = Container;
 
@@ -115,11 +160,77 @@ impl Dataset {
         crate::hl::chunks::chunk_info(self, index)
     }
 
+    /// Reads `size` bytes stored at the file address `addr` directly from the file, e.g. the
+    /// raw (possibly filtered) bytes of a chunk found via [`chunk_info`](Self::chunk_info).
+    ///
+    /// The address is relative to the end of the userblock, as reported by the library.
+    /// See [`File::read_file_bytes`] for the supported drivers and caveats.
+    pub fn read_chunk_bytes_at(&self, addr: haddr_t, size: hsize_t) -> Result<Vec<u8>> {
+        ensure!(addr != HADDR_UNDEF, "chunk address is undefined");
+        let file = self.file()?;
+        file.read_file_bytes(file.userblock() + addr, size as _)
+    }
+
+    /// Writes the already filtered bytes of the chunk starting at the element `offset`,
+    /// bypassing the filter pipeline and datatype conversion.
+    ///
+    /// `filter_mask` has a bit set for each filter of the pipeline (by position) that was
+    /// not applied to `data`, so 0 means the data went through the whole pipeline. The
+    /// offset must be a multiple of the chunk shape, and before filtering the data must
+    /// hold a whole chunk of elements of the dataset's datatype, including for partial edge
+    /// chunks.
+    pub fn write_chunk_raw(&self, offset: &[Ix], filter_mask: u32, data: &[u8]) -> Result<()> {
+        self.handle().ensure_writable()?;
+        let chunk = self.chunk().ok_or_else(|| format!("{} is not chunked", self.describe()))?;
+        ensure!(
+            offset.len() == chunk.len(),
+            "chunk offset {:?} doesn't match the dataset rank {}",
+            offset,
+            chunk.len()
+        );
+        ensure!(
+            offset.iter().zip(&chunk).all(|(o, c)| o % c == 0),
+            "chunk offset {:?} is not aligned to the chunk shape {:?}",
+            offset,
+            chunk
+        );
+        let offset = crate::dim::to_hsize(offset);
+        h5call!(H5Dwrite_chunk(
+            self.id(),
+            H5P_DEFAULT,
+            filter_mask,
+            offset.as_ptr(),
+            data.len(),
+            data.as_ptr().cast()
+        ))
+        .and(Ok(()))
+    }
+
     /// Returns the chunk shape if the dataset is chunked.
     pub fn chunk(&self) -> Option<Vec<Ix>> {
         self.dcpl().map_or(None, |pl| pl.chunk())
     }
 
+    /// Starts recording statistics for reads and writes through [`Reader`] and [`Writer`] on
+    /// this dataset handle (and its clones). Calling it again keeps the recorded statistics.
+    ///
+    /// Each request records the bounding box of its file selection, whether it is aligned with
+    /// chunk boundaries and which chunks it touches; see [`IoStats`] for the summary.
+    pub fn enable_io_stats(&self) {
+        self.handle().enable_io_recorder(|| {
+            let element_size = self.dtype().map_or(0, |dtype| dtype.size());
+            IoRecorder::new(self.chunk(), element_size)
+        });
+    }
+
+    /// Returns a summary of the recorded I/O requests, or `None` if
+    /// [`enable_io_stats`](Self::enable_io_stats) was not called on this handle.
+    pub fn io_stats(&self) -> Option<IoStats> {
+        let recorder = self.handle().io_recorder()?;
+        let cache = self.dapl().map_or_else(|_| ChunkCache::default(), |dapl| dapl.chunk_cache());
+        Some(recorder.lock().snapshot(cache))
+    }
+
     /// Visit all chunks
     #[cfg(all(feature = "1.14.0", feature = "link"))]
     pub fn chunks_visit<F>(&self, callback: F) -> Result<()>
@@ -145,10 +256,8 @@ impl Dataset {
 
     /// Resizes the dataset to a new shape.
     pub fn resize<D: Dimension>(&self, shape: D) -> Result<()> {
-        let mut dims: Vec<hsize_t> = vec![];
-        for dim in &shape.dims() {
-            dims.push(*dim as _);
-        }
+        self.handle().ensure_writable()?;
+        let dims = crate::dim::to_hsize(&shape.dims());
         h5try!(H5Dset_extent(self.id(), dims.as_ptr()));
         Ok(())
     }
@@ -158,8 +267,147 @@ impl Dataset {
         self.dcpl().map_or(Vec::default(), |pl| pl.filters())
     }
 
+    /// Returns the identifiers of the filters in the pipeline that are not available.
+    ///
+    /// Data written with such a filter cannot be read back, unless the filter is optional
+    /// and was skipped when the data was written because it was not available then either.
+    pub fn missing_filters(&self) -> Vec<H5Z_filter_t> {
+        h5lock!(self.dcpl().and_then(|pl| Filter::extract_pipeline_entries(pl.id())))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| !Filter::get_info(entry.id).is_available)
+            .map(|entry| entry.id)
+            .collect()
+    }
+
+    /// Returns `true` if every filter in the pipeline is available and configured to
+    /// decode data.
+    ///
+    /// Reading may still succeed if this returns `false`, if the unavailable filters are
+    /// optional and were skipped when the data was written.
+    pub fn can_read(&self) -> bool {
+        h5lock!(self.dcpl().and_then(|pl| Filter::extract_pipeline_entries(pl.id())))
+            .map_or(false, |entries| entries.iter().all(PipelineEntry::can_decode))
+    }
+
+    /// Collects the storage details needed for raw (zero-copy) access to the dataset's bytes.
+    ///
+    /// All of the information is gathered while holding the library lock, so it is consistent
+    /// with respect to other threads using the library.
+    pub fn layout_info(&self) -> Result<LayoutInfo> {
+        h5lock!({
+            let dcpl = self.dcpl()?;
+            let dtype = self.dtype()?;
+            let space = self.space()?;
+            let layout = dcpl.get_layout()?;
+            let shape = space.shape()?;
+            let chunk_shape = if layout == Layout::Chunked { dcpl.get_chunk()? } else { None };
+            let mut chunks = vec![];
+            if chunk_shape.is_some() {
+                let mut n: hsize_t = 0;
+                h5try!(H5Dget_num_chunks(self.id(), space.id(), &mut n));
+                for index in 0..n {
+                    let mut info = ChunkInfo::new(shape.len());
+                    h5try!(H5Dget_chunk_info(
+                        self.id(),
+                        space.id(),
+                        index,
+                        info.offset.as_mut_ptr(),
+                        &mut info.filter_mask,
+                        &mut info.addr,
+                        &mut info.size,
+                    ));
+                    chunks.push(info);
+                }
+            }
+            let offset = match H5Dget_offset(self.id()) as haddr_t {
+                HADDR_UNDEF => None,
+                offset => Some(offset as u64),
+            };
+            Ok(LayoutInfo {
+                layout,
+                element_size: dtype.size(),
+                byte_order: dtype.byte_order(),
+                shape,
+                storage_size: self.storage_size(),
+                offset,
+                chunk_shape,
+                chunks,
+                filtered: !dcpl.get_filters()?.is_empty(),
+            })
+        })
+    }
+
+    /// Returns how much of the dataset's raw data has file space allocated.
+    pub fn space_status(&self) -> Result<SpaceStatus> {
+        let mut status = H5D_space_status_t::default();
+        h5try!(H5Dget_space_status(self.id(), &mut status));
+        Ok(status.into())
+    }
+
+    /// Returns the number of chunks with file space allocated if the dataset is chunked.
+    pub fn allocated_chunk_count(&self) -> Result<Option<usize>> {
+        if !self.is_chunked() {
+            return Ok(None);
+        }
+        h5lock!({
+            let space = self.space()?;
+            let ndim = space.ndim();
+            let mut n: hsize_t = 0;
+            h5try!(H5Dget_num_chunks(self.id(), space.id(), &mut n));
+            let mut count = 0;
+            for index in 0..n {
+                let mut info = ChunkInfo::new(ndim);
+                h5try!(H5Dget_chunk_info(
+                    self.id(),
+                    space.id(),
+                    index,
+                    info.offset.as_mut_ptr(),
+                    &mut info.filter_mask,
+                    &mut info.addr,
+                    &mut info.size,
+                ));
+                if info.addr != HADDR_UNDEF {
+                    count += 1;
+                }
+            }
+            Ok(Some(count))
+        })
+    }
+
+    /// Returns the size of the dataset's elements in bytes, as if fully written and
+    /// unfiltered; compare with [`storage_size`](Container::storage_size) to get the
+    /// compression ratio.
+    pub fn logical_size(&self) -> Result<u64> {
+        let npoints = self.space()?.size()? as u64;
+        npoints
+            .checked_mul(self.dtype()?.size() as u64)
+            .ok_or_else(|| format!("logical size of {} overflows u64", self.describe()).into())
+    }
+
+    /// Summarizes the logical and allocated sizes of the dataset, e.g. to report how sparsely
+    /// a chunked dataset is populated.
+    pub fn usage_summary(&self) -> Result<UsageSummary> {
+        h5lock!({
+            let shape = self.shape()?;
+            let (total_chunks, allocated_chunks) = match self.chunk() {
+                Some(chunk) => {
+                    let total = shape.iter().zip(&chunk).map(|(&n, &c)| n.div_ceil(c.max(1)));
+                    (Some(total.product()), self.allocated_chunk_count()?)
+                }
+                None => (None, None),
+            };
+            Ok(UsageSummary {
+                status: self.space_status()?,
+                logical_size: self.logical_size()?,
+                allocated_size: self.storage_size(),
+                total_chunks,
+                allocated_chunks,
+            })
+        })
+    }
+
     /// Flush the dataset metadata from the metadata cache to the file
-    #[cfg(all(feature = "1.10.0", feature = "link"))]
     pub fn flush(&self) -> Result<()> {
         let id = self.id();
         h5call!(H5Dflush(id))?;
@@ -167,7 +415,6 @@ impl Dataset {
     }
 
     /// Refresh metadata items assosicated with the dataset
-    #[cfg(all(feature = "1.10.0", feature = "link"))]
     pub fn refresh(&self) -> Result<()> {
         let id = self.id();
         h5call!(H5Drefresh(id))?;
@@ -175,6 +422,116 @@ impl Dataset {
     }
 }
 
+/// Storage details of a dataset, as returned by [`Dataset::layout_info()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutInfo {
+    /// Storage layout of the raw data.
+    pub layout: Layout,
+    /// Size of a single element in bytes.
+    pub element_size: usize,
+    /// Byte order of the element type as stored in the file.
+    pub byte_order: ByteOrder,
+    /// Current shape of the dataset.
+    pub shape: Vec<Ix>,
+    /// Number of bytes allocated in the file for raw data.
+    pub storage_size: u64,
+    /// Absolute file offset of the raw data (contiguous and allocated datasets only).
+    pub offset: Option<u64>,
+    /// Chunk shape (chunked datasets only).
+    pub chunk_shape: Option<Vec<Ix>>,
+    /// Allocated chunks, in the order reported by the library (chunked datasets only).
+    pub chunks: Vec<ChunkInfo>,
+    /// Whether the dataset has a non-empty filter pipeline.
+    pub filtered: bool,
+}
+
+impl LayoutInfo {
+    /// Returns the total number of chunks covering the dataset's current shape, if chunked.
+    pub fn num_chunks_total(&self) -> Option<usize> {
+        let chunk_shape = self.chunk_shape.as_ref()?;
+        Some(self.shape.iter().zip(chunk_shape).map(|(&n, &c)| n.div_ceil(c.max(1))).product())
+    }
+
+    /// Returns `true` if all of the dataset's raw data has file space allocated.
+    pub fn is_allocated(&self) -> bool {
+        match self.layout {
+            Layout::Compact => true,
+            Layout::Contiguous => self.offset.is_some() || self.shape.iter().product::<Ix>() == 0,
+            Layout::Chunked => self.num_chunks_total() == Some(self.chunks.len()),
+            Layout::Virtual => false,
+        }
+    }
+
+    /// Returns `true` if the raw bytes in the file can be used directly as native elements.
+    ///
+    /// This requires the data to be contiguous or chunked without filters, stored in the
+    /// native byte order (or having no byte order, e.g. single-byte types), and fully
+    /// allocated. Compact datasets live in the object header and are never zero-copy readable.
+    pub fn is_zero_copy_readable(&self) -> bool {
+        let native_order = match self.byte_order {
+            ByteOrder::LittleEndian => cfg!(target_endian = "little"),
+            ByteOrder::BigEndian => cfg!(target_endian = "big"),
+            ByteOrder::None => true,
+            ByteOrder::Vax | ByteOrder::Mixed => false,
+        };
+        let layout_ok = match self.layout {
+            Layout::Contiguous => true,
+            Layout::Chunked => !self.filtered && self.chunks.iter().all(|c| c.filter_mask == 0),
+            _ => false,
+        };
+        native_order && layout_ok && self.is_allocated()
+    }
+}
+
+/// Allocation state of a dataset's raw data, as returned by [`Dataset::space_status()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpaceStatus {
+    /// No file space has been allocated.
+    NotAllocated,
+    /// Some, but not all, of the file space has been allocated (chunked datasets only).
+    PartiallyAllocated,
+    /// All of the file space has been allocated.
+    Allocated,
+}
+
+impl From<H5D_space_status_t> for SpaceStatus {
+    fn from(status: H5D_space_status_t) -> Self {
+        match status {
+            H5D_space_status_t::H5D_SPACE_STATUS_ALLOCATED => Self::Allocated,
+            H5D_space_status_t::H5D_SPACE_STATUS_PART_ALLOCATED => Self::PartiallyAllocated,
+            _ => Self::NotAllocated,
+        }
+    }
+}
+
+/// Logical and allocated sizes of a dataset, as returned by [`Dataset::usage_summary()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UsageSummary {
+    /// Allocation state of the raw data.
+    pub status: SpaceStatus,
+    /// Size of the dataset's elements in bytes, as if fully written and unfiltered.
+    pub logical_size: u64,
+    /// Number of bytes allocated in the file for raw data.
+    pub allocated_size: u64,
+    /// Total number of chunks covering the dataset's current shape (chunked datasets only).
+    pub total_chunks: Option<usize>,
+    /// Number of chunks with file space allocated (chunked datasets only).
+    pub allocated_chunks: Option<usize>,
+}
+
+impl UsageSummary {
+    /// Returns the fraction of chunks with file space allocated, if chunked.
+    ///
+    /// A chunked dataset with no chunks (i.e. with a zero-sized axis) counts as fully allocated.
+    pub fn allocated_chunk_fraction(&self) -> Option<f64> {
+        let (total, allocated) = (self.total_chunks?, self.allocated_chunks?);
+        Some(if total == 0 { 1.0 } else { allocated as f64 / total as f64 })
+    }
+}
+
 pub struct Maybe<T>(Option<T>);
 
 impl<T> Deref for Maybe<T> {
@@ -248,6 +605,82 @@ impl DatasetBuilder {
             conv: Conversion::Soft,
         }
     }
+
+    /// Sets the data of a 1-D variable-length UTF-8 string dataset.
+    ///
+    /// Strings with internal null characters cannot be stored; creating the dataset then
+    /// fails, naming the offending element.
+    pub fn with_data_str<I, S>(self, data: I) -> DatasetBuilderStrings<VarLenUnicode>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        DatasetBuilderStrings { builder: self.builder, data: varlen_unicode_strings(data) }
+    }
+
+    /// Sets the data of a 1-D fixed-length UTF-8 string dataset of `N` bytes per element.
+    ///
+    /// Strings longer than `N` bytes are handled according to `truncation`; creating the
+    /// dataset fails, naming the offending element, if they are rejected.
+    pub fn with_data_fixed_str<const N: usize, I, S>(
+        self,
+        data: I,
+        truncation: StringTruncation,
+    ) -> DatasetBuilderStrings<FixedUnicode<N>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        DatasetBuilderStrings {
+            builder: self.builder,
+            data: fixed_unicode_strings(data, truncation),
+        }
+    }
+
+    /// Sets the data of a dataset of the given shape to the items of `iter`, in C order.
+    ///
+    /// The data is written in batches of about [`batch_size`](DatasetBuilderIter::batch_size)
+    /// elements (whole rows along the first axis) as the iterator is consumed, so at most one
+    /// batch is held in memory. Unless a chunk shape is set, a non-empty simple dataset is
+    /// chunked with chunks of one batch. Creating the dataset fails, reporting how many
+    /// elements were consumed, if the iterator yields fewer or more elements than the shape
+    /// holds.
+    pub fn from_iter<S, I>(self, shape: S, iter: I) -> DatasetBuilderIter<I::IntoIter>
+    where
+        S: Into<Extents>,
+        I: IntoIterator,
+        I::Item: H5Type,
+    {
+        DatasetBuilderIter {
+            builder: self.builder,
+            iter: iter.into_iter(),
+            extents: Some(shape.into()),
+            batch_size: None,
+        }
+    }
+
+    /// Sets the data of a resizable 1-D dataset to the items of `iter`.
+    ///
+    /// The dataset is created empty with chunks of `chunk` elements and grows by one batch
+    /// (a chunk by default) at a time as the iterator is consumed; its final length is the
+    /// number of items yielded.
+    pub fn from_iter_unbounded<I>(
+        mut self,
+        chunk: usize,
+        iter: I,
+    ) -> DatasetBuilderIter<I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: H5Type,
+    {
+        self.builder.chunk(chunk);
+        DatasetBuilderIter {
+            builder: self.builder,
+            iter: iter.into_iter(),
+            extents: None,
+            batch_size: None,
+        }
+    }
     //
     // #[cfg(feature = "zfp")]
     // pub fn zfp_rate(self, rate: f64) -> Self {
@@ -296,6 +729,12 @@ impl DatasetBuilderEmpty {
     pub fn create<'n, T: Into<Maybe<&'n str>>>(self, name: T) -> Result<Dataset> {
         self.shape(()).create(name)
     }
+
+    /// Opens the scalar dataset `name` if it exists with compatible settings, and creates it
+    /// otherwise, see [`DatasetBuilderEmptyShape::get_or_create()`].
+    pub fn get_or_create(self, name: &str) -> Result<Dataset> {
+        self.shape(()).get_or_create(name)
+    }
 }
 
 #[derive(Clone)]
@@ -310,6 +749,21 @@ impl DatasetBuilderEmptyShape {
     pub fn create<'n, T: Into<Maybe<&'n str>>>(&self, name: T) -> Result<Dataset> {
         h5lock!(self.builder.create(&self.type_desc, name.into().into(), &self.extents))
     }
+
+    /// Opens the dataset `name` if it exists, and creates it like [`create()`](Self::create)
+    /// otherwise.
+    ///
+    /// An existing dataset is only returned if it is compatible with the builder: its datatype
+    /// must be equal, its rank and maximum dimension sizes must match and so must the sizes of
+    /// non-resizable dimensions, and its layout, chunk shape and filters must match those set
+    /// explicitly on the builder (filters count as set if any were added, or if a creation
+    /// property list was set with `set_create_plist()`). Otherwise, the error names the first
+    /// mismatched property.
+    /// The check and the creation are done while holding the library lock, so other threads of
+    /// this process cannot race with them.
+    pub fn get_or_create(&self, name: &str) -> Result<Dataset> {
+        h5lock!(self.builder.get_or_create(&self.type_desc, name, &self.extents))
+    }
 }
 
 #[derive(Clone)]
@@ -360,6 +814,177 @@ where
     }
 }
 
+#[derive(Clone)]
+/// A dataset builder with string data converted to the string type `T`
+pub struct DatasetBuilderStrings<T> {
+    builder: DatasetBuilderInner,
+    data: Result<Vec<T>>,
+}
+
+impl<T: H5Type> DatasetBuilderStrings<T> {
+    pub fn create<'n, N: Into<Maybe<&'n str>>>(&self, name: N) -> Result<Dataset> {
+        let data = self.data.as_ref().map_err(Clone::clone)?;
+        DatasetBuilderData {
+            builder: self.builder.clone(),
+            data: ArrayView::from(data.as_slice()),
+            type_desc: T::type_descriptor(),
+            conv: Conversion::NoOp,
+        }
+        .create(name)
+    }
+}
+
+/// Default number of elements written at a time by [`DatasetBuilderIter`].
+pub const DEFAULT_ITER_BATCH_SIZE: usize = 64 * 1024;
+
+/// A dataset builder with data taken from an iterator
+#[derive(Clone)]
+pub struct DatasetBuilderIter<I> {
+    builder: DatasetBuilderInner,
+    iter: I,
+    extents: Option<Extents>,
+    batch_size: Option<usize>,
+}
+
+impl<I> DatasetBuilderIter<I>
+where
+    I: Iterator,
+    I::Item: H5Type,
+{
+    /// Sets the number of elements collected from the iterator and written at a time.
+    ///
+    /// Defaults to the number of elements in a chunk if a chunk shape is set, and to
+    /// [`DEFAULT_ITER_BATCH_SIZE`] otherwise. Batches are rounded down to whole rows along the
+    /// first axis, but hold at least one row.
+    pub fn batch_size(mut self, size: usize) -> Self {
+        self.batch_size = Some(size.max(1));
+        self
+    }
+
+    /// Creates the dataset and writes the items of the iterator into it.
+    ///
+    /// If writing fails or the iterator does not yield the declared number of elements, the
+    /// dataset is unlinked again.
+    pub fn create<'n, N: Into<Maybe<&'n str>>>(self, name: N) -> Result<Dataset> {
+        let Self { mut builder, mut iter, extents, batch_size } = self;
+        let unbounded = extents.is_none();
+        let name = name.into().into();
+        let batch_size = batch_size.unwrap_or(match &builder.chunk {
+            Some(Chunk::Exact(dims)) => dims.iter().product::<Ix>().max(1),
+            _ => DEFAULT_ITER_BATCH_SIZE,
+        });
+        let extents = match extents {
+            Some(extents) => {
+                let dims = extents.dims();
+                if builder.chunk.is_none() && extents.ndim() > 0 && extents.size() > 0 {
+                    let mut chunk = dims.clone();
+                    chunk[0] = batch_rows(&dims, batch_size).min(dims[0]);
+                    builder.chunk(chunk);
+                }
+                extents
+            }
+            None => Extents::from(SimpleExtents::resizable([0])),
+        };
+        let desc = I::Item::type_descriptor();
+        let ds = h5lock!(builder.create(&desc, name, &extents))?;
+        let result = if unbounded {
+            write_iter(&ds, &mut iter, None, batch_size)
+        } else if extents.is_null() {
+            ensure_exhausted(&mut iter, 0)
+        } else if extents.ndim() == 0 {
+            write_iter_scalar(&ds, &mut iter)
+        } else {
+            write_iter(&ds, &mut iter, Some(&extents.dims()), batch_size)
+        };
+        if let Err(err) = result {
+            builder.try_unlink(name);
+            return Err(err);
+        }
+        Ok(ds)
+    }
+}
+
+/// Returns the number of rows along the first axis of `dims` in a batch of about `batch_size`
+/// elements, which is at least one.
+fn batch_rows(dims: &[Ix], batch_size: usize) -> usize {
+    let row_len = dims.iter().skip(1).product::<Ix>();
+    (batch_size / row_len.max(1)).max(1)
+}
+
+/// Fails if `iter` yields more items, after `len` items were consumed.
+pub(crate) fn ensure_exhausted<I: Iterator>(iter: &mut I, len: usize) -> Result<()> {
+    ensure!(
+        iter.next().is_none(),
+        "iterator yielded more than {} elements (consumed {})",
+        len,
+        len + 1
+    );
+    Ok(())
+}
+
+/// Writes the single item of `iter` into the scalar dataset `ds`.
+fn write_iter_scalar<I>(ds: &Dataset, iter: &mut I) -> Result<()>
+where
+    I: Iterator,
+    I::Item: H5Type,
+{
+    let value = iter.next().ok_or("iterator yielded 0 elements, expected 1")?;
+    ds.as_writer().write_scalar(&value)?;
+    ensure_exhausted(iter, 1)
+}
+
+/// Writes the items of `iter` in C order into the dataset `ds` of the given non-scalar `shape`,
+/// in batches of whole rows along the first axis. If `shape` is `None`, `ds` is 1-D and grows
+/// as needed.
+fn write_iter<I>(ds: &Dataset, iter: &mut I, shape: Option<&[Ix]>, batch_size: usize) -> Result<()>
+where
+    I: Iterator,
+    I::Item: H5Type,
+{
+    let writer = ds.as_writer();
+    let dims = shape.unwrap_or(&[0]);
+    let len = shape.map(checked_size).transpose()?;
+    let row_len = dims.iter().skip(1).product::<Ix>();
+    let rows = batch_rows(dims, batch_size);
+    let mut batch = Vec::with_capacity(len.map_or(rows, |len| len.min(rows * row_len)));
+    let mut written = 0;
+    while len.map_or(true, |len| written < len) {
+        let wanted = len.map_or(rows, |len| (len - written).min(rows * row_len));
+        batch.clear();
+        batch.extend(iter.by_ref().take(wanted));
+        if batch.is_empty() || (len.is_some() && batch.len() < wanted) {
+            written += batch.len();
+            break;
+        }
+        let (start, count) = (written / row_len, batch.len() / row_len);
+        if len.is_none() {
+            ds.resize(start + count)?;
+        }
+        let mut block = dims.to_vec();
+        block[0] = count;
+        let mut selection = vec![SliceOrIndex::SliceCount { start, step: 1, count, block: 1 }];
+        selection.extend(dims[1..].iter().map(|&n| SliceOrIndex::SliceCount {
+            start: 0,
+            step: 1,
+            count: n,
+            block: 1,
+        }));
+        let view = ArrayView::from_shape(block, batch.as_slice())?;
+        writer
+            .write_slice(view, Selection::from(Hyperslab::from(selection)))
+            .with_context(|| format!("writing rows {}..{}", start, start + count))?;
+        written += batch.len();
+        if batch.len() < wanted {
+            break;
+        }
+    }
+    if let Some(len) = len {
+        ensure!(written == len, "iterator yielded {} elements, expected {}", written, len);
+        ensure_exhausted(iter, len)?;
+    }
+    Ok(())
+}
+
 /// Options for how to chunk data.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Chunk {
@@ -417,15 +1042,15 @@ struct DatasetBuilderInner {
     lcpl_builder: LinkCreateBuilder,
     packed: bool,
     chunk: Option<Chunk>,
+    strict_chunk: bool,
+    warn_chunking: Option<bool>,
 }
 
 impl DatasetBuilderInner {
     pub fn new(parent: &Group) -> Self {
         // same as in h5py, disable time tracking by default and enable intermediate groups
-        let mut dcpl = DatasetCreateBuilder::default();
-        dcpl.obj_track_times(false);
-        let mut lcpl = LinkCreateBuilder::default();
-        lcpl.create_intermediate_group(true);
+        let dcpl = DatasetCreateBuilder::default().obj_track_times(false);
+        let lcpl = LinkCreateBuilder::default().create_intermediate_group(true);
 
         Self {
             parent: parent.try_borrow(),
@@ -437,6 +1062,8 @@ impl DatasetBuilderInner {
             lcpl_builder: lcpl,
             packed: false,
             chunk: None,
+            strict_chunk: false,
+            warn_chunking: None,
         }
     }
 
@@ -460,7 +1087,36 @@ impl DatasetBuilderInner {
         };
         let has_filters = self.dcpl_builder.has_filters()
             || self.dcpl_base.as_ref().map_or(false, DatasetCreate::has_filters);
-        let chunking_required = has_filters || extents.is_resizable();
+        let layout = self.dcpl_builder.get_layout_opt();
+        let chunk_requested = matches!(self.chunk, Some(Chunk::Exact(_) | Chunk::MinKB(_)));
+        match layout {
+            Some(Layout::Compact) => {
+                ensure!(!has_filters, "Compact layout is incompatible with filters");
+                ensure!(!chunk_requested, "Compact layout is incompatible with chunking");
+                ensure!(
+                    !extents.is_resizable(),
+                    "Compact layout is incompatible with resizable dimensions (got {})",
+                    extents
+                );
+                let size = extents.size() * dtype.size();
+                ensure!(
+                    size <= COMPACT_MAX_SIZE,
+                    "Compact layout requires at most {} bytes of data, got {} bytes \
+                     ({} elements of {} bytes)",
+                    COMPACT_MAX_SIZE,
+                    size,
+                    extents.size(),
+                    dtype.size()
+                );
+                return Ok(None);
+            }
+            Some(Layout::Contiguous) => {
+                ensure!(!chunk_requested, "Contiguous layout is incompatible with chunking");
+            }
+            _ => {}
+        }
+        let chunking_required =
+            has_filters || extents.is_resizable() || layout == Some(Layout::Chunked);
         let chunking_allowed = extents.size() > 0 || extents.is_resizable();
 
         let chunk = if let Some(chunk) = &self.chunk {
@@ -489,32 +1145,69 @@ impl DatasetBuilderInner {
             let ndim = extents.ndim();
             ensure!(ndim != 0, "Chunking cannot be enabled for 0-dim datasets");
             ensure!(ndim == chunk.len(), "Expected chunk ndim {}, got {}", ndim, chunk.len());
-            let chunk_size = chunk.iter().product::<usize>();
-            ensure!(chunk_size > 0, "All chunk dimensions must be positive, got {:?}", chunk);
-            let dims_ok = extents.iter().zip(chunk).all(|(e, c)| e.max.is_none() || *c <= e.dim);
+            ensure!(
+                chunk.iter().all(|&c| c > 0),
+                "All chunk dimensions must be positive, got {:?}",
+                chunk
+            );
+            // like the library, don't limit chunks of axes without elements
             let no_extent = extents.size() == 0;
+            let dims_ok = extents
+                .iter()
+                .zip(chunk)
+                .all(|(e, c)| e.dim == 0 || e.max.map_or(true, |m| *c <= m));
+            ensure!(dims_ok, "Chunk dimensions ({:?}) exceed data shape ({:?})", chunk, extents);
+            let bytes = chunk.iter().try_fold(dtype.size(), |acc, &c| acc.checked_mul(c));
             ensure!(
-                dims_ok || no_extent,
-                "Chunk dimensions ({:?}) exceed data shape ({:?})",
+                bytes.is_some_and(|bytes| bytes <= CHUNK_MAX_SIZE),
+                "Chunk size of {} bytes (chunk {:?} of {}-byte elements) exceeds the limit of 4 GiB",
+                bytes.map_or_else(|| "more than 2^64".to_owned(), |bytes| bytes.to_string()),
                 chunk,
-                extents
+                dtype.size()
             );
+            // the chunking checks are run if enabled, explicitly or by `strict_chunk` or the
+            // environment, and their findings are errors if strict and warnings otherwise
+            let check =
+                self.warn_chunking.unwrap_or_else(|| self.strict_chunk || warn_chunking_from_env());
+            if check {
+                let mut issues = vec![];
+                // only an explicit chunk shape is a likely mistake, computed shapes are capped
+                let oversized = extents.iter().zip(chunk).any(|(e, c)| *c > e.dim);
+                if oversized && !no_extent && matches!(self.chunk, Some(Chunk::Exact(_))) {
+                    issues.push(format!(
+                        "Chunk dimensions ({chunk:?}) exceed the current data shape ({extents})"
+                    ));
+                }
+                issues.extend(bad_chunking_warning(&extents.dims(), chunk, dtype.size()));
+                for msg in issues {
+                    ensure!(!self.strict_chunk, "{} (rejected by `strict_chunk`)", msg);
+                    eprintln!("warning: {msg}");
+                }
+            }
         }
         Ok(chunk_shape)
     }
 
     fn build_dcpl(&self, dtype: &Datatype, extents: &Extents) -> Result<DatasetCreate> {
-        self.dcpl_builder.validate_filters(dtype.id())?;
+        self.dcpl_builder.validate_filters(dtype).context("validating filters")?;
+        if let Some(fill_value) = self.dcpl_builder.get_fill_value_opt() {
+            Datatype::from_descriptor(fill_value.type_descriptor())
+                .and_then(|fill_dtype| fill_dtype.ensure_convertible(dtype, Conversion::Soft))
+                .with_context(|| format!("checking fill value {fill_value:?}"))?;
+        }
 
         let mut dcpl_builder = self.dcpl_builder.clone();
-        if let Some(chunk) = self.compute_chunk_shape(dtype, extents)? {
-            dcpl_builder.chunk(chunk);
+        let chunk = self
+            .compute_chunk_shape(dtype, extents)
+            .with_context(|| format!("choosing chunk shape for extents {extents}"))?;
+        if let Some(chunk) = chunk {
+            dcpl_builder = dcpl_builder.chunk(chunk);
             if !dcpl_builder.has_fill_time() {
                 // prevent resize glitch (borrowed from h5py)
-                dcpl_builder.fill_time(FillTime::Alloc);
+                dcpl_builder = dcpl_builder.fill_time(FillTime::Alloc);
             }
         } else {
-            dcpl_builder.no_chunk();
+            dcpl_builder = dcpl_builder.no_chunk();
         }
 
         let mut dcpl = match &self.dcpl_base {
@@ -541,31 +1234,129 @@ impl DatasetBuilderInner {
         }
     }
 
+    unsafe fn get_or_create(
+        &self,
+        desc: &TypeDescriptor,
+        name: &str,
+        extents: &Extents,
+    ) -> Result<Dataset> {
+        let parent = try_ref_clone!(self.parent);
+        if !link_path_exists(parent.id(), name)? {
+            return self.create(desc, Some(name), extents);
+        }
+        let c_name = to_cstring(name)?;
+        let ds = h5call!(H5Dopen2(parent.id(), c_name.as_ptr(), H5P_DEFAULT))
+            .and_then(Dataset::from_id)
+            .with_context(|| format!("opening dataset '{name}'"))?;
+        self.check_existing(&ds, desc, extents)
+            .with_context(|| format!("existing dataset '{name}' is incompatible"))?;
+        Ok(ds)
+    }
+
+    /// Checks that an existing dataset matches the builder settings, see
+    /// [`DatasetBuilderEmptyShape::get_or_create()`].
+    fn check_existing(&self, ds: &Dataset, desc: &TypeDescriptor, extents: &Extents) -> Result<()> {
+        let desc = if self.packed { desc.to_packed_repr() } else { desc.to_c_repr() };
+        let found = ds.dtype()?;
+        if found != Datatype::from_descriptor(&desc)? {
+            let found = found.to_descriptor().map_or_else(|_| "unknown".into(), |d| d.to_string());
+            fail!("datatype mismatch: expected {}, found {}", desc, found);
+        }
+
+        let found = ds.space()?.extents()?;
+        let shape_ok = match (extents, &found) {
+            (Extents::Simple(expected), Extents::Simple(found)) => {
+                expected.ndim() == found.ndim()
+                    && expected
+                        .iter()
+                        .zip(found.iter())
+                        .all(|(e, f)| e.max == f.max && (e.max != Some(e.dim) || e.dim == f.dim))
+            }
+            _ => *extents == found,
+        };
+        ensure!(shape_ok, "shape mismatch: expected {}, found {}", extents, found);
+
+        if let Some(layout) = self.dcpl_builder.get_layout_opt() {
+            let found = ds.layout();
+            ensure!(found == layout, "layout mismatch: expected {:?}, found {:?}", layout, found);
+        }
+        match &self.chunk {
+            Some(Chunk::Exact(chunk)) => {
+                let found = ds.chunk();
+                ensure!(
+                    found.as_ref() == Some(chunk),
+                    "chunk shape mismatch: expected {:?}, found {:?}",
+                    chunk,
+                    found
+                );
+            }
+            Some(Chunk::None) => {
+                ensure!(!ds.is_chunked(), "chunk shape mismatch: expected no chunking");
+            }
+            _ => {}
+        }
+
+        // filters count as set if any were added or a base property list was given; the library
+        // may adjust filter parameters when creating the dataset, so filters are compared in
+        // their short form which only keeps user-chosen parameters
+        let base = self.dcpl_base.as_ref().map(DatasetCreate::get_filters).transpose()?;
+        if base.is_none() && self.dcpl_builder.get_filters_ref().is_empty() {
+            return Ok(());
+        }
+        let expected = base.iter().flatten().chain(self.dcpl_builder.get_filters_ref());
+        let expected = expected.map(ToString::to_string).collect::<Vec<_>>();
+        let found = ds.dcpl()?.get_filters()?;
+        let found = found.iter().map(ToString::to_string).collect::<Vec<_>>();
+        ensure!(
+            expected == found,
+            "filters mismatch: expected [{}], found [{}]",
+            expected.join(", "),
+            found.join(", ")
+        );
+        Ok(())
+    }
+
     unsafe fn create(
         &self,
         desc: &TypeDescriptor,
         name: Option<&str>,
         extents: &Extents,
+    ) -> Result<Dataset> {
+        self.create_staged(desc, name, extents).with_context(|| match name {
+            Some(name) => format!("creating dataset '{name}'"),
+            None => "creating anonymous dataset".into(),
+        })
+    }
+
+    unsafe fn create_staged(
+        &self,
+        desc: &TypeDescriptor,
+        name: Option<&str>,
+        extents: &Extents,
     ) -> Result<Dataset> {
         // construct in-file type descriptor; convert to packed representation if needed
         let desc = if self.packed { desc.to_packed_repr() } else { desc.to_c_repr() };
-        let dtype = Datatype::from_descriptor(&desc)?;
+        let dtype = Datatype::from_descriptor(&desc)
+            .with_context(|| format!("creating datatype {desc}"))?;
+
+        // create the dataspace from extents
+        let space =
+            Dataspace::try_new(extents).with_context(|| format!("creating dataspace {extents}"))?;
 
         // construct DAPL and DCPL, validate filters
-        let dapl = self.build_dapl()?;
+        let dapl = self.build_dapl().context("building access property list")?;
         let dcpl = self.build_dcpl(&dtype, extents)?;
 
-        // create the dataspace from extents
-        let space = Dataspace::try_new(extents)?;
-
         // extract all ids and create the dataset
         let parent = try_ref_clone!(self.parent);
+        parent.ensure_writable()?;
         let (pid, dtype_id, space_id, dcpl_id, dapl_id) =
             (parent.id(), dtype.id(), space.id(), dcpl.id(), dapl.id());
         let ds_id = if let Some(name) = name {
             // create named dataset
-            let lcpl = self.build_lcpl()?;
-            let name = to_cstring(name)?;
+            let lcpl = self.build_lcpl().context("building link creation property list")?;
+            check_link_name(pid, name, lcpl.create_intermediate_group())?;
+            let name = to_cstring(name).context("invalid dataset name")?;
             H5Dcreate2(pid, name.as_ptr(), dtype_id, space_id, lcpl.id(), dcpl_id, dapl_id)
         } else {
             // create anonymous dataset
@@ -596,14 +1387,14 @@ impl DatasetBuilderInner {
 
     pub fn with_access_plist<F>(&mut self, func: F)
     where
-        F: Fn(&mut DatasetAccessBuilder) -> &mut DatasetAccessBuilder,
+        F: FnOnce(DatasetAccessBuilder) -> DatasetAccessBuilder,
     {
-        func(&mut self.dapl_builder);
+        self.dapl_builder = func(mem::take(&mut self.dapl_builder));
     }
 
     pub fn with_dapl<F>(&mut self, func: F)
     where
-        F: Fn(&mut DatasetAccessBuilder) -> &mut DatasetAccessBuilder,
+        F: FnOnce(DatasetAccessBuilder) -> DatasetAccessBuilder,
     {
         self.with_access_plist(func);
     }
@@ -614,7 +1405,7 @@ impl DatasetBuilderInner {
         self.with_dapl(|pl| pl.chunk_cache(nslots, nbytes, w0));
     }
 
-    pub fn efile_prefix(&mut self, prefix: &str) {
+    pub fn efile_prefix(&mut self, prefix: impl AsRef<Path>) {
         self.with_dapl(|pl| pl.efile_prefix(prefix));
     }
 
@@ -655,14 +1446,14 @@ impl DatasetBuilderInner {
 
     pub fn with_create_plist<F>(&mut self, func: F)
     where
-        F: Fn(&mut DatasetCreateBuilder) -> &mut DatasetCreateBuilder,
+        F: FnOnce(DatasetCreateBuilder) -> DatasetCreateBuilder,
     {
-        func(&mut self.dcpl_builder);
+        self.dcpl_builder = func(mem::take(&mut self.dcpl_builder));
     }
 
     pub fn with_dcpl<F>(&mut self, func: F)
     where
-        F: Fn(&mut DatasetCreateBuilder) -> &mut DatasetCreateBuilder,
+        F: FnOnce(DatasetCreateBuilder) -> DatasetCreateBuilder,
     {
         self.with_create_plist(func);
     }
@@ -797,7 +1588,7 @@ impl DatasetBuilderInner {
     }
 
     pub fn fill_value<T: Into<OwnedDynValue>>(&mut self, fill_value: T) {
-        self.dcpl_builder.fill_value(fill_value);
+        self.with_dcpl(|pl| pl.fill_value(fill_value));
     }
 
     pub fn no_fill_value(&mut self) {
@@ -812,6 +1603,14 @@ impl DatasetBuilderInner {
         self.chunk = Some(Chunk::MinKB(size));
     }
 
+    pub fn strict_chunk(&mut self, strict: bool) {
+        self.strict_chunk = strict;
+    }
+
+    pub fn warn_on_bad_chunking(&mut self, warn: bool) {
+        self.warn_chunking = Some(warn);
+    }
+
     pub fn no_chunk(&mut self) {
         self.chunk = Some(Chunk::None);
     }
@@ -825,11 +1624,10 @@ impl DatasetBuilderInner {
         self.with_dcpl(|pl| pl.chunk_opts(opts));
     }
 
-    pub fn external(&mut self, name: &str, offset: usize, size: usize) {
+    pub fn external<P: AsRef<Path>>(&mut self, name: P, offset: usize, size: usize) {
         self.with_dcpl(|pl| pl.external(name, offset, size));
     }
 
-    #[cfg(all(feature = "1.10.0", feature = "link"))]
     pub fn virtual_map<F, D, E1, S1, E2, S2>(
         &mut self,
         src_filename: F,
@@ -846,14 +1644,16 @@ impl DatasetBuilderInner {
         E2: Into<Extents>,
         S2: Into<Selection>,
     {
-        self.dcpl_builder.virtual_map(
-            src_filename,
-            src_dataset,
-            src_extents,
-            src_selection,
-            vds_extents,
-            vds_selection,
-        );
+        self.with_dcpl(|pl| {
+            pl.virtual_map(
+                src_filename,
+                src_dataset,
+                src_extents,
+                src_selection,
+                vds_extents,
+                vds_selection,
+            )
+        });
     }
 
     pub fn obj_track_times(&mut self, track_times: bool) {
@@ -890,14 +1690,14 @@ impl DatasetBuilderInner {
 
     pub fn with_link_create_plist<F>(&mut self, func: F)
     where
-        F: Fn(&mut LinkCreateBuilder) -> &mut LinkCreateBuilder,
+        F: FnOnce(LinkCreateBuilder) -> LinkCreateBuilder,
     {
-        func(&mut self.lcpl_builder);
+        self.lcpl_builder = func(mem::take(&mut self.lcpl_builder));
     }
 
     pub fn with_lcpl<F>(&mut self, func: F)
     where
-        F: Fn(&mut LinkCreateBuilder) -> &mut LinkCreateBuilder,
+        F: FnOnce(LinkCreateBuilder) -> LinkCreateBuilder,
     {
         self.with_link_create_plist(func);
     }
@@ -927,11 +1727,13 @@ macro_rules! impl_builder {
             }
 
             #[inline]
+            #[deprecated(note = "builders are consumed by their setters; use the `with_*` methods")]
             pub fn [<$name _plist>](&mut self) -> &mut [<$plist Builder>] {
                 self.builder.[<$name _plist>]()
             }
 
             #[inline]
+            #[deprecated(note = "builders are consumed by their setters; use the `with_*` methods")]
             pub fn $short(&mut self) -> &mut [<$plist Builder>] {
                 self.builder.$short()
             }
@@ -939,7 +1741,7 @@ macro_rules! impl_builder {
             #[inline] #[must_use]
             pub fn [<with_ $name _plist>]<F>(mut self, func: F) -> Self
             where
-                F: Fn(&mut [<$plist Builder>]) -> &mut [<$plist Builder>],
+                F: FnOnce([<$plist Builder>]) -> [<$plist Builder>],
             {
                 self.builder.[<with_ $name _plist>](func); self
             }
@@ -947,7 +1749,7 @@ macro_rules! impl_builder {
             #[inline] #[must_use]
             pub fn [<with_ $short>]<F>(mut self, func: F) -> Self
             where
-                F: Fn(&mut [<$plist Builder>]) -> &mut [<$plist Builder>],
+                F: FnOnce([<$plist Builder>]) -> [<$plist Builder>],
             {
                 self.builder.[<with_ $short>](func); self
             }
@@ -998,7 +1800,7 @@ macro_rules! impl_builder_methods {
         impl_builder!(DatasetAccess: access/dapl);
 
         impl_builder!(DatasetAccess: chunk_cache(nslots: usize, nbytes: usize, w0: f64));
-        impl_builder!(DatasetAccess: efile_prefix(prefix: &str));
+        impl_builder!(DatasetAccess: efile_prefix(prefix: impl AsRef<Path>));
         impl_builder!(#[cfg(all(feature = "1.10.0", feature = "link"))] DatasetAccess: virtual_view(view: VirtualView));
         impl_builder!(#[cfg(all(feature = "1.10.0", feature = "link"))] DatasetAccess: virtual_printf_gap(gap_size: usize));
         impl_builder!(
@@ -1071,12 +1873,13 @@ macro_rules! impl_builder_methods {
         impl_builder!(DatasetCreate: no_fill_value());
         impl_builder!(DatasetCreate: chunk<D: Dimension>(chunk: D));
         impl_builder!(*: chunk_min_kb(size: usize));
+        impl_builder!(*: strict_chunk(strict: bool));
+        impl_builder!(*: warn_on_bad_chunking(warn: bool));
         impl_builder!(DatasetCreate: no_chunk());
         impl_builder!(DatasetCreate: layout(layout: Layout));
         impl_builder!(#[cfg(all(feature = "1.10.0", feature = "link"))] DatasetCreate: chunk_opts(opts: ChunkOpts));
-        impl_builder!(DatasetCreate: external(name: &str, offset: usize, size: usize));
+        impl_builder!(DatasetCreate: external<P: AsRef<Path>>(name: P, offset: usize, size: usize));
         impl_builder!(
-            #[cfg(all(feature = "1.10.0", feature = "link"))]
             DatasetCreate: virtual_map<
                 F: AsRef<str>, D: AsRef<str>,
                 E1: Into<Extents>, S1: Into<Selection>, E2: Into<Extents>, S2: Into<Selection>
@@ -1116,9 +1919,14 @@ impl<'d, T2: H5Type, D2: ndarray::Dimension> DatasetBuilderData<'d, T2, D2> {
     impl_builder_methods!();
 }
 
+/// The following methods are common to all dataset builders.
+impl<T2: H5Type> DatasetBuilderStrings<T2> {
+    impl_builder_methods!();
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{compute_chunk_shape, DatasetBuilder};
+    use super::{compute_chunk_shape, DatasetBuilder, COMPACT_MAX_SIZE};
     use crate::filters::Filter;
     use crate::test::with_tmp_file;
     use crate::{Extent, Result, SimpleExtents};
@@ -1184,6 +1992,362 @@ mod tests {
         assert_eq!(compute_chunk_shape(&e, 51), vec![1, 1, 100]);
     }
 
+    #[test]
+    fn test_layout_info() {
+        use crate::dataset::{AllocTime, Layout};
+        use crate::datatype::ByteOrder;
+        with_tmp_file(|file| {
+            let arr = Array2::<f64>::from_shape_fn((10, 20), |(i, j)| (i * 20 + j) as f64);
+            let ds = file.new_dataset_builder().with_data(&arr).create("contiguous").unwrap();
+            let info = ds.layout_info().unwrap();
+            assert_eq!(info.layout, Layout::Contiguous);
+            assert_eq!(info.element_size, 8);
+            assert_eq!(info.byte_order, ByteOrder::LittleEndian);
+            assert_eq!(info.shape, vec![10, 20]);
+            assert_eq!(info.storage_size, 1600);
+            assert_eq!(info.offset, ds.offset());
+            assert!(info.offset.is_some());
+            assert_eq!(info.chunk_shape, None);
+            assert!(info.chunks.is_empty());
+            assert!(!info.filtered);
+            assert!(info.is_allocated());
+            assert_eq!(info.is_zero_copy_readable(), cfg!(target_endian = "little"));
+
+            let ds = file.new_dataset_builder().chunk((4, 20)).with_data(&arr).create("chunked");
+            let info = ds.unwrap().layout_info().unwrap();
+            assert_eq!(info.layout, Layout::Chunked);
+            assert_eq!(info.offset, None);
+            assert_eq!(info.chunk_shape, Some(vec![4, 20]));
+            assert_eq!(info.num_chunks_total(), Some(3));
+            assert_eq!(info.chunks.len(), 3);
+            assert_eq!(info.chunks[1].offset, vec![4, 0]);
+            assert_eq!(info.chunks.iter().map(|c| c.size).sum::<u64>(), info.storage_size);
+            assert!(!info.filtered);
+            assert_eq!(info.is_zero_copy_readable(), cfg!(target_endian = "little"));
+
+            if crate::filters::deflate_available() {
+                let ds = file.new_dataset_builder().deflate(4).chunk((4, 20)).with_data(&arr);
+                let info = ds.create("deflated").unwrap().layout_info().unwrap();
+                assert_eq!(info.layout, Layout::Chunked);
+                assert_eq!(info.chunks.len(), 3);
+                assert!(info.filtered);
+                assert!(info.storage_size < 1600);
+                assert!(info.is_allocated());
+                assert!(!info.is_zero_copy_readable());
+            }
+
+            let ds = file.new_dataset_builder().layout(Layout::Compact).with_data(&[1_u8, 2, 3]);
+            let info = ds.create("compact").unwrap().layout_info().unwrap();
+            assert_eq!(info.layout, Layout::Compact);
+            assert_eq!(info.element_size, 1);
+            assert_eq!(info.byte_order, ByteOrder::None);
+            assert_eq!(info.storage_size, 3);
+            assert_eq!(info.offset, None);
+            assert!(info.is_allocated());
+            assert!(!info.is_zero_copy_readable());
+
+            let ds = file.new_dataset::<i32>().alloc_time(Some(AllocTime::Late)).shape(100);
+            let info = ds.create("unallocated").unwrap().layout_info().unwrap();
+            assert_eq!(info.layout, Layout::Contiguous);
+            assert_eq!(info.element_size, 4);
+            assert_eq!(info.shape, vec![100]);
+            assert_eq!(info.storage_size, 0);
+            assert_eq!(info.offset, None);
+            assert!(!info.is_allocated());
+            assert!(!info.is_zero_copy_readable());
+
+            let ds = file.new_dataset::<i32>().chunk(10).shape(100).create("no_chunks").unwrap();
+            let info = ds.layout_info().unwrap();
+            assert_eq!(info.num_chunks_total(), Some(10));
+            assert!(info.chunks.is_empty());
+            assert!(!info.is_allocated());
+            assert!(!info.is_zero_copy_readable());
+        })
+    }
+
+    #[test]
+    fn test_usage_summary() {
+        use crate::dataset::{AllocTime, SpaceStatus};
+        use ndarray::s;
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u16>().alloc_time(Some(AllocTime::Late)).chunk((4, 4));
+            let ds = ds.shape((8, 12)).create("sparse").unwrap();
+            assert_eq!(ds.space_status().unwrap(), SpaceStatus::NotAllocated);
+            assert_eq!(ds.allocated_chunk_count().unwrap(), Some(0));
+
+            ds.write_slice(&Array2::<u16>::ones((4, 4)), s![4..8, 0..4]).unwrap();
+            assert_eq!(ds.space_status().unwrap(), SpaceStatus::PartiallyAllocated);
+            assert_eq!(ds.allocated_chunk_count().unwrap(), Some(1));
+            let usage = ds.usage_summary().unwrap();
+            assert_eq!(usage.status, SpaceStatus::PartiallyAllocated);
+            assert_eq!(usage.logical_size, 8 * 12 * 2);
+            assert_eq!(usage.allocated_size, 4 * 4 * 2);
+            assert_eq!((usage.allocated_chunks, usage.total_chunks), (Some(1), Some(6)));
+            assert_eq!(usage.allocated_chunk_fraction(), Some(1.0 / 6.0));
+
+            let ds = file.new_dataset_builder().with_data(&[1_i32, 2, 3]).create("contiguous");
+            let usage = ds.unwrap().usage_summary().unwrap();
+            assert_eq!(usage.status, SpaceStatus::Allocated);
+            assert_eq!((usage.logical_size, usage.allocated_size), (12, 12));
+            assert_eq!(usage.allocated_chunks, None);
+            assert_eq!(usage.allocated_chunk_fraction(), None);
+        })
+    }
+
+    #[test]
+    fn test_display() {
+        use crate::internal_prelude::*;
+        with_tmp_file(|file| {
+            let ds = file
+                .new_dataset::<f64>()
+                .shape((100, 3))
+                .chunk((10, 3))
+                .shuffle()
+                .deflate(5)
+                .create("results/temperature")
+                .unwrap();
+            assert_eq!(
+                ds.to_string(),
+                "<HDF5 dataset \"temperature\": shape (100, 3), type float64, chunks (10, 3), \
+                 shuffle, gzip-5>"
+            );
+            assert_eq!(
+                format!("{ds:?}"),
+                "<HDF5 dataset: \"temperature\": shape (100, 3), type float64, chunks (10, 3), \
+                 shuffle, gzip-5>"
+            );
+            let ds = file.new_dataset::<u8>().shape(7).create("vector").unwrap();
+            assert_eq!(ds.to_string(), "<HDF5 dataset \"vector\": shape (7,), type uint8>");
+            let ds = file.new_dataset::<i32>().create("scalar").unwrap();
+            assert_eq!(ds.to_string(), "<HDF5 dataset \"scalar\": shape (), type int32>");
+            let attr = ds.new_attr::<u16>().shape((2, 2)).create("attr").unwrap();
+            assert_eq!(attr.to_string(), "<HDF5 attribute \"attr\": shape (2, 2), type uint16>");
+
+            let group = file.create_group("empty").unwrap();
+            assert_eq!(group.to_string(), "<HDF5 group \"/empty\" (empty)>");
+            assert_eq!(
+                file.group("results").unwrap().to_string(),
+                "<HDF5 group \"/results\" (1 member)>"
+            );
+
+            drop(group);
+            let closed = Dataset::from_handle(Handle::invalid());
+            assert_eq!(closed.to_string(), "<closed HDF5 object>");
+            assert_eq!(format!("{closed:?}"), "<HDF5 dataset: invalid id>");
+            let ds = file.dataset("scalar").unwrap();
+            h5lock!({
+                h5call!(crate::sys::h5d::H5Dclose(ds.id())).unwrap();
+                assert_eq!(ds.to_string(), "<closed HDF5 object>");
+                assert_eq!(
+                    attr.to_string(),
+                    "<HDF5 attribute \"attr\": shape (2, 2), type uint16>"
+                );
+                drop(ds);
+            });
+        })
+    }
+
+    #[test]
+    fn test_read_chunk_bytes_at() {
+        use crate::internal_prelude::*;
+        with_tmp_path(|path| {
+            let file = File::with_options().with_fcpl(|p| p.userblock(512)).create(&path).unwrap();
+            let data = ndarray::Array2::from_shape_fn((4, 6), |(i, j)| (i * 6 + j) as i32);
+            let ds = file.new_dataset_builder().chunk((2, 3)).with_data(&data).create("x").unwrap();
+            for index in 0..ds.num_chunks().unwrap() {
+                let info = ds.chunk_info(index).unwrap();
+                let (i, j) = (info.offset[0] as usize, info.offset[1] as usize);
+                let chunk = data.slice(ndarray::s![i..i + 2, j..j + 3]);
+                let expected = chunk.iter().flat_map(|v| v.to_ne_bytes()).collect::<Vec<_>>();
+                assert_eq!(ds.read_chunk_bytes_at(info.addr, info.size).unwrap(), expected);
+            }
+            assert_err!(
+                ds.read_chunk_bytes_at(super::HADDR_UNDEF, 4),
+                "chunk address is undefined"
+            );
+
+            let size = file.file_size().unwrap();
+            assert_eq!(size, std::fs::metadata(&path).unwrap().len());
+            assert_eq!(file.read_file_bytes(512, 8).unwrap(), b"\x89HDF\r\n\x1a\n");
+            assert!(file.read_file_bytes(size - 1, 2).is_err());
+
+            let file = File::create_in_memory("read_chunk_bytes_at").unwrap();
+            assert_err!(
+                file.read_file_bytes(0, 8),
+                "reading file bytes requires a single-file disk driver, got 'core'"
+            );
+        })
+    }
+
+    #[test]
+    fn test_compact_layout() {
+        use crate::dataset::Layout;
+        use crate::internal_prelude::*;
+        with_tmp_file(|file| {
+            let arr = ndarray::Array1::from_shape_fn(100, |i| i as f64 * 0.25);
+            let ds = file.new_dataset_builder().layout(Layout::Compact).with_data(&arr);
+            let ds = ds.create("compact").unwrap();
+            assert_eq!(ds.layout(), Layout::Compact);
+            assert!(!ds.is_chunked());
+            assert_eq!(ds.read_1d::<f64>().unwrap(), arr);
+
+            let ds = file.new_dataset::<u8>().layout(Layout::Compact).shape(COMPACT_MAX_SIZE);
+            assert_eq!(ds.create("max").unwrap().layout(), Layout::Compact);
+            let ds = file.new_dataset::<u8>().layout(Layout::Compact).shape(COMPACT_MAX_SIZE + 1);
+            assert_err!(ds.create("max1"), "Compact layout requires at most");
+
+            let ds = file.new_dataset::<f64>().layout(Layout::Compact).shape((100, 100));
+            assert_err!(
+                ds.create("too_big"),
+                "Compact layout requires at most 65532 bytes of data, got 80000 bytes \
+                 (10000 elements of 8 bytes)"
+            );
+            let ds = file.new_dataset::<f64>().layout(Layout::Compact).chunk(10).shape(100);
+            assert_err!(ds.create("chunked"), "Compact layout is incompatible with chunking");
+            let ds = file.new_dataset::<f64>().layout(Layout::Compact).shape(10..);
+            assert_err!(
+                ds.create("resizable"),
+                "Compact layout is incompatible with resizable dimensions"
+            );
+            if crate::filters::deflate_available() {
+                let ds = file.new_dataset::<f64>().layout(Layout::Compact).deflate(3).shape(100);
+                assert_err!(ds.create("deflated"), "Compact layout is incompatible with filters");
+            }
+            let ds = file.new_dataset::<f64>().layout(Layout::Contiguous).chunk(10).shape(100);
+            assert_err!(ds.create("contiguous"), "Contiguous layout is incompatible with chunking");
+            let ds = file.new_dataset::<f64>().layout(Layout::Chunked).shape(100);
+            assert_eq!(ds.create("chunked").unwrap().layout(), Layout::Chunked);
+            assert!(file.member_names().unwrap().iter().all(|n| n != "too_big"));
+        })
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_large_shape() {
+        with_tmp_file(|file| {
+            // storage is allocated late, so nothing close to this size is written
+            let n = 5_000_000_000;
+            let ds = file.new_dataset::<u8>().chunk((1, 1 << 20)).shape((2, n..));
+            let ds = ds.create("large").unwrap();
+            assert_eq!(ds.shape().unwrap(), [2, n]);
+            assert_eq!(ds.size().unwrap(), 2 * n);
+            assert_eq!(ds.space().unwrap().maxdims(), [Some(2), None]);
+            ds.resize((2, n + 1)).unwrap();
+            let ds = file.dataset("large").unwrap();
+            assert_eq!(ds.shape().unwrap(), [2, n + 1]);
+            assert_eq!(ds.dcpl().unwrap().chunk(), Some(vec![1, 1 << 20]));
+        })
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_huge_shape_read() {
+        use crate::Dataspace;
+        use ndarray::s;
+        with_tmp_file(|file| {
+            // 2^63 elements: the count fits into usize, but no buffer can hold them
+            let shape = (1 << 32, 1 << 31);
+            let space = Dataspace::try_new(shape).unwrap();
+            assert_eq!(space.shape_u64(), [1 << 32, 1 << 31]);
+            assert_eq!(space.size().unwrap(), 1 << 63);
+            let ds = file.new_dataset::<u8>().chunk((1, 1 << 20)).shape(shape);
+            let ds = ds.create("huge").unwrap();
+            assert_eq!(ds.shape_u64(), [1 << 32, 1 << 31]);
+            assert_eq!(ds.size().unwrap(), 1 << 63);
+            assert_err!(ds.read_raw::<u8>(), "exceeds the maximum allocation size");
+            assert_err!(ds.read_2d::<u16>(), "exceeds the maximum allocation size");
+            let slice = ds.read_slice_2d::<u16, _>(s![1.., ..]);
+            assert_err!(slice, "exceeds the maximum allocation size");
+            assert_eq!(ds.read_slice_1d::<u8, _>(s![1, ..4]).unwrap().to_vec(), [0; 4]);
+
+            // the number of elements overflows 64 bits
+            let space = Dataspace::try_new((1 << 32, 1 << 32)).unwrap();
+            assert_eq!(space.shape_u64(), [1 << 32, 1 << 32]);
+            assert_err!(space.size(), "overflows 64 bits");
+            assert_eq!(space.size().unwrap(), 0);
+        })
+    }
+
+    #[test]
+    fn test_chunk_validation() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<i32>().shape((10, 5));
+            assert_err!(ds.clone().chunk((0, 5)).create("zero"), "must be positive, got [0, 5]");
+            assert_err!(ds.chunk((20, 5)).create("large"), "([20, 5]) exceed data shape");
+            let ds = file.new_dataset::<i32>().shape((10..=15, 5)).chunk((16, 5));
+            assert_err!(ds.create("max"), "([16, 5]) exceed data shape");
+
+            let ds = file.new_dataset::<u64>().shape((0.., 1 << 13)).chunk((1 << 16, 1 << 13));
+            assert_err!(
+                ds.create("huge"),
+                "Chunk size of 4294967296 bytes (chunk [65536, 8192] of 8-byte elements) exceeds \
+                 the limit of 4 GiB"
+            );
+            let ds = file.new_dataset::<u8>().shape((1.., 1..)).chunk((usize::MAX, 2));
+            assert_err!(ds.create("overflow"), "Chunk size of more than 2^64 bytes");
+
+            // chunks larger than the current shape of resizable axes are checked like the other
+            // chunking issues: rejected if strict, reported if enabled and accepted otherwise
+            let ds = file.new_dataset::<i32>().shape((10.., 5)).chunk((20, 5));
+            assert_eq!(ds.clone().create("oversized").unwrap().chunk(), Some(vec![20, 5]));
+            let warned = ds.clone().warn_on_bad_chunking(true).create("warned").unwrap();
+            assert_eq!(warned.chunk(), Some(vec![20, 5]));
+            assert_err!(
+                ds.clone().strict_chunk(true).create("strict"),
+                "([20, 5]) exceed the current data shape (10.., 5) (rejected by `strict_chunk`)"
+            );
+            let ds = ds.strict_chunk(true).warn_on_bad_chunking(false);
+            assert_eq!(ds.create("unchecked").unwrap().chunk(), Some(vec![20, 5]));
+
+            // datasets without elements accept any chunk shape
+            let ds = file.new_dataset::<i32>().shape((0, 5)).chunk((4, 5)).create("empty").unwrap();
+            assert_eq!((ds.shape().unwrap(), ds.chunk()), (vec![0, 5], Some(vec![4, 5])));
+            let ds = file.new_dataset::<i32>().shape((0.., 5)).chunk((8, 5)).strict_chunk(true);
+            let ds = ds.create("unlimited").unwrap();
+            assert_eq!((ds.shape().unwrap(), ds.chunk()), (vec![0, 5], Some(vec![8, 5])));
+            ds.resize((8, 5)).unwrap();
+            ds.write_raw(&(0..40).collect::<Vec<i32>>()).unwrap();
+            assert_eq!(ds.read_raw::<i32>().unwrap(), (0..40).collect::<Vec<i32>>());
+        })
+    }
+
+    #[test]
+    fn test_create_error_context() {
+        use crate::internal_prelude::*;
+        use crate::types::VarLenUnicode;
+        use std::str::FromStr;
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<i32>().chunk((20, 1)).shape((10, 10));
+            assert_err!(ds.create("chunk"), "creating dataset 'chunk': choosing chunk shape for");
+            assert_err!(ds.create("chunk"), "Chunk dimensions ([20, 1]) exceed data shape");
+
+            let ds = file.new_dataset::<i32>().shuffle().shuffle().chunk(10).shape(100);
+            assert_err!(
+                ds.create("filters"),
+                "creating dataset 'filters': validating filters: Duplicate filters"
+            );
+
+            let fill = VarLenUnicode::from_str("abc").unwrap();
+            let ds = file.new_dataset::<i32>().fill_value(fill).shape(10);
+            assert_err!(ds.create("fill"), "creating dataset 'fill': checking fill value");
+
+            let ds = file.new_dataset::<i32>().shape(10);
+            assert_err!(ds.create("a\0b"), "creating dataset 'a\0b': invalid dataset name");
+            ds.create("dup").unwrap();
+            assert_err!(ds.create("dup"), "creating dataset 'dup': ");
+
+            if crate::filters::deflate_available() {
+                let ds = file.new_dataset::<i32>().deflate(11).chunk(10).shape(100);
+                assert_err!(
+                    ds.create("deflate"),
+                    "creating dataset 'deflate': applying filter Deflate(11): "
+                );
+                assert_err!(ds.create("deflate"), "invalid deflate level");
+            }
+            assert!(!file.link_exists("chunk") && !file.link_exists("deflate"));
+        })
+    }
+
     #[test]
     fn test_read_write_scalar() {
         use crate::internal_prelude::*;
@@ -1201,4 +2365,153 @@ mod tests {
             assert_eq!(val, val_back);
         })
     }
+
+    /// Creates a chunked dataset of 8 integers with a filter that does not have to be available.
+    fn create_with_filter(
+        file: &crate::File,
+        name: &str,
+        id: super::H5Z_filter_t,
+        flags: libc::c_uint,
+    ) -> Result<super::Dataset> {
+        use super::{DatasetCreate, H5Dcreate2, H5P_DEFAULT};
+        use crate::internal_prelude::*;
+        use crate::sys::h5p::H5Pset_filter;
+
+        let dcpl = DatasetCreate::build().chunk(4).finish()?;
+        let dtype = Datatype::from_type::<i32>()?;
+        let space = Dataspace::try_new(8)?;
+        let name = to_cstring(name)?;
+        h5lock!({
+            h5try!(H5Pset_filter(dcpl.id(), id, flags, 0, std::ptr::null()));
+            Dataset::from_id(h5try!(H5Dcreate2(
+                file.id(),
+                name.as_ptr(),
+                dtype.id(),
+                space.id(),
+                H5P_DEFAULT,
+                dcpl.id(),
+                H5P_DEFAULT
+            )))
+        })
+    }
+
+    extern "C" fn passthrough_filter(
+        _flags: libc::c_uint,
+        _cd_nelmts: libc::size_t,
+        _cd_values: *const libc::c_uint,
+        nbytes: libc::size_t,
+        _buf_size: *mut libc::size_t,
+        _buf: *mut *mut libc::c_void,
+    ) -> libc::size_t {
+        nbytes
+    }
+
+    #[test]
+    fn test_missing_filters() {
+        use super::H5Z_filter_t;
+        use crate::internal_prelude::*;
+        use crate::sys::h5z::{
+            H5Z_class2_t, H5Zregister, H5Zunregister, H5Z_CLASS_T_VERS, H5Z_FLAG_MANDATORY,
+            H5Z_FLAG_OPTIONAL,
+        };
+
+        const MISSING: H5Z_filter_t = 54321;
+        const UNREGISTERED: H5Z_filter_t = 54322;
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            // optional filters that are not available are skipped when writing
+            let ds = create_with_filter(&file, "optional", MISSING, H5Z_FLAG_OPTIONAL).unwrap();
+            ds.write(&data).unwrap();
+            assert!(create_with_filter(&file, "mandatory", MISSING, H5Z_FLAG_MANDATORY).is_err());
+
+            let class = H5Z_class2_t {
+                version: H5Z_CLASS_T_VERS,
+                id: UNREGISTERED,
+                encoder_present: 1,
+                decoder_present: 1,
+                name: c"passthrough".as_ptr(),
+                can_apply: None,
+                set_local: None,
+                filter: Some(passthrough_filter),
+            };
+            h5call!(H5Zregister((&class as *const H5Z_class2_t).cast())).unwrap();
+            let ds =
+                create_with_filter(&file, "mandatory", UNREGISTERED, H5Z_FLAG_MANDATORY).unwrap();
+            ds.write(&data).unwrap();
+            assert!(ds.missing_filters().is_empty());
+            assert!(ds.can_read());
+            drop(ds);
+            file.close().unwrap();
+            h5call!(H5Zunregister(UNREGISTERED)).unwrap();
+
+            let file = File::open(&path).unwrap();
+            let ds = file.dataset("optional").unwrap();
+            assert_eq!(ds.missing_filters(), [MISSING]);
+            assert!(!ds.can_read());
+            assert_eq!(ds.read_raw::<i32>().unwrap(), data);
+
+            let ds = file.dataset("mandatory").unwrap();
+            assert_eq!(ds.missing_filters(), [UNREGISTERED]);
+            assert!(!ds.can_read());
+            let err = ds.read_raw::<i32>().unwrap_err().to_string();
+            assert!(
+                err.contains("unavailable filters [54322 ('passthrough', mandatory)]"),
+                "{err}"
+            );
+            assert!(err.starts_with("reading dataset '/mandatory': unavailable"), "{err}");
+        })
+    }
+
+    #[test]
+    pub fn test_shape_mismatch_errors() {
+        use ndarray::{arr1, arr2, s, Array2};
+
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<i32>().shape((4, 3)).create("results/x").unwrap();
+            let err = ds.write(&Array2::<i32>::zeros((3, 4))).unwrap_err().to_string();
+            assert_eq!(
+                err,
+                "writing dataset '/results/x': data shape [3, 4] does not match dataset shape [4, 3]"
+            );
+            let err = ds.write_raw(&[1, 2, 3]).unwrap_err().to_string();
+            assert!(err.contains("data length 3 does not match dataset size 12"), "{err}");
+
+            let err = ds.write_slice(&arr1(&[1, 2]), s![1, ..]).unwrap_err().to_string();
+            assert_eq!(
+                err,
+                "writing dataset '/results/x': data shape [2] does not match selection shape [3] \
+                 (dataset shape [4, 3])"
+            );
+            let err = ds.write_slice(&arr2(&[[1, 2, 3]]), s![1, ..]).unwrap_err().to_string();
+            assert!(err.contains("data shape [1, 3] does not match selection shape [3]"), "{err}");
+            ds.write_slice(&arr1(&[1, 2, 3]), s![1, ..]).unwrap();
+
+            let err = ds.write_scalar(&1).unwrap_err().to_string();
+            assert_eq!(
+                err,
+                "writing dataset '/results/x': scalar data does not match dataset shape [4, 3]"
+            );
+            let err = ds.read_scalar::<i32>().unwrap_err().to_string();
+            assert!(err.contains("dataset shape [4, 3] cannot be read into a scalar"), "{err}");
+            let err = ds.read_1d::<i32>().unwrap_err().to_string();
+            assert_eq!(
+                err,
+                "reading dataset '/results/x': dataset shape [4, 3] cannot be read into a \
+                 1-dimensional array"
+            );
+            let err = ds.read_slice_2d::<i32, _>(s![1, ..]).unwrap_err().to_string();
+            assert!(err.contains("selection shape [3] cannot be read into a 2-dimensional"));
+
+            let attr = ds.new_attr::<f64>().shape(2).create("scale").unwrap();
+            let err = attr.write(&[1.0, 2.0, 3.0]).unwrap_err().to_string();
+            assert_eq!(
+                err,
+                "writing attribute 'scale' of '/results/x': data shape [3] does not match \
+                 attribute shape [2]"
+            );
+            let err = attr.write_scalar(&1.0).unwrap_err().to_string();
+            assert!(err.contains("scalar data does not match attribute shape [2]"), "{err}");
+        })
+    }
 }
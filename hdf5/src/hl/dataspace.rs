@@ -6,10 +6,11 @@ use crate::sys::h5s::H5Sencode;
 
 use crate::sys::h5s::{
     H5S_class_t, H5Scopy, H5Screate, H5Screate_simple, H5Sdecode, H5Sget_select_npoints,
-    H5Sget_simple_extent_dims, H5Sget_simple_extent_ndims, H5Sget_simple_extent_npoints,
-    H5Sget_simple_extent_type, H5Sselect_valid, H5S_UNLIMITED,
+    H5Sget_simple_extent_dims, H5Sget_simple_extent_ndims, H5Sget_simple_extent_type,
+    H5Sselect_valid, H5S_UNLIMITED,
 };
 
+use crate::dim::{checked_product, hsize_to_ix, to_ix, DimVec};
 use crate::hl::extents::{Extent, Extents, Ix};
 use crate::hl::selection::RawSelection;
 use crate::internal_prelude::*;
@@ -54,21 +55,21 @@ impl Deref for Dataspace {
     }
 }
 
-unsafe fn get_shape(space_id: hid_t) -> Result<Vec<Ix>> {
+unsafe fn get_dims(space_id: hid_t) -> Result<DimVec> {
     let ndim = h5check(H5Sget_simple_extent_ndims(space_id))? as usize;
-    let mut dims = vec![0; ndim];
+    let mut dims = DimVec::from_elem(0, ndim);
     h5check(H5Sget_simple_extent_dims(space_id, dims.as_mut_ptr(), ptr::null_mut()))?;
-    Ok(dims.into_iter().map(|x| x as _).collect())
+    Ok(dims)
 }
 
 unsafe fn get_simple_extents(space_id: hid_t) -> Result<SimpleExtents> {
     let ndim = h5check(H5Sget_simple_extent_ndims(space_id))? as usize;
-    let (mut dims, mut maxdims) = (vec![0; ndim], vec![0; ndim]);
+    let (mut dims, mut maxdims) = (DimVec::from_elem(0, ndim), DimVec::from_elem(0, ndim));
     h5check(H5Sget_simple_extent_dims(space_id, dims.as_mut_ptr(), maxdims.as_mut_ptr()))?;
     let mut extents = Vec::with_capacity(ndim);
     for i in 0..ndim {
-        let (dim, max) = (dims[i] as _, maxdims[i]);
-        let max = if max == H5S_UNLIMITED { None } else { Some(max as _) };
+        let (dim, max) = (hsize_to_ix(dims[i])?, maxdims[i]);
+        let max = if max == H5S_UNLIMITED { None } else { Some(hsize_to_ix(max)?) };
         extents.push(Extent::new(dim, max));
     }
     Ok(SimpleExtents::from_vec(extents))
@@ -94,9 +95,16 @@ impl Dataspace {
         h5call!(H5Sget_simple_extent_ndims(self.id())).unwrap_or(0) as _
     }
 
-    /// Returns a vector containing the current size of each dimension.
-    pub fn shape(&self) -> Vec<Ix> {
-        h5lock!(get_shape(self.id())).unwrap_or_default()
+    /// Returns a vector containing the current size of each dimension, failing if a dimension
+    /// does not fit into [`Ix`] (on 32-bit targets); see [`shape_u64`](Self::shape_u64).
+    pub fn shape(&self) -> Result<Vec<Ix>> {
+        to_ix(&h5lock!(get_dims(self.id()))?)
+    }
+
+    /// Returns a vector containing the current size of each dimension as stored by the
+    /// library, which is valid on all targets.
+    pub fn shape_u64(&self) -> Vec<u64> {
+        h5lock!(get_dims(self.id())).map(DimVec::into_vec).unwrap_or_default()
     }
 
     /// Returns a vector containing the current maximum size (if set) of each dimension.
@@ -130,14 +138,17 @@ impl Dataspace {
         h5lock!(H5Sselect_valid(self.id())) > 0
     }
 
-    /// Returns the number of elements in the space.
-    pub fn size(&self) -> usize {
+    /// Returns the number of elements in the space, failing if it does not fit into `usize`.
+    pub fn size(&self) -> Result<usize> {
         match h5lock!(H5Sget_simple_extent_type(self.id())) {
             H5S_class_t::H5S_SIMPLE => {
-                h5call!(H5Sget_simple_extent_npoints(self.id())).unwrap_or(0) as _
+                let npoints = checked_product(&h5lock!(get_dims(self.id()))?)?;
+                usize::try_from(npoints).map_err(|_| {
+                    format!("number of elements {} does not fit into usize", npoints).into()
+                })
             }
-            H5S_class_t::H5S_SCALAR => 1,
-            _ => 0,
+            H5S_class_t::H5S_SCALAR => Ok(1),
+            _ => Ok(0),
         }
     }
 
@@ -175,10 +186,10 @@ impl Dataspace {
             Extents::Null => H5Screate(H5S_class_t::H5S_NULL),
             Extents::Scalar => H5Screate(H5S_class_t::H5S_SCALAR),
             Extents::Simple(ref e) => {
-                let (mut dims, mut maxdims) = (vec![], vec![]);
+                let (mut dims, mut maxdims) = (DimVec::new(), DimVec::new());
                 for extent in e.iter() {
-                    dims.push(extent.dim as _);
-                    maxdims.push(extent.max.map_or(H5S_UNLIMITED, |x| x as _));
+                    dims.push(extent.dim as hsize_t);
+                    maxdims.push(extent.max.map_or(H5S_UNLIMITED, |x| x as hsize_t));
                 }
                 H5Screate_simple(e.ndim() as _, dims.as_ptr(), maxdims.as_ptr())
             }
@@ -202,7 +213,7 @@ impl Dataspace {
 
     /// Returns the number of elements in the dataspace selection.
     pub fn selection_size(&self) -> usize {
-        h5call!(H5Sget_select_npoints(self.id())).ok().map_or(0, |x| x as _)
+        h5call!(H5Sget_select_npoints(self.id())).ok().and_then(|x| x.try_into().ok()).unwrap_or(0)
     }
 
     #[doc(hidden)]
@@ -218,7 +229,7 @@ impl Dataspace {
 
     /// Selects part of the dataspace and returns a new dataspace selection object.
     pub fn select<S: Into<Selection>>(&self, selection: S) -> Result<Self> {
-        let raw_sel = selection.into().into_raw(self.shape())?;
+        let raw_sel = selection.into().into_raw(self.shape()?)?;
         self.select_raw(raw_sel)
     }
 
@@ -250,9 +261,9 @@ mod tests {
     fn test_dataspace_null() -> Result<()> {
         let space = Dataspace::try_new(Extents::Null)?;
         assert_eq!(space.ndim(), 0);
-        assert_eq!(space.shape(), vec![]);
+        assert_eq!(space.shape().unwrap(), vec![]);
         assert_eq!(space.maxdims(), vec![]);
-        assert_eq!(space.size(), 0);
+        assert_eq!(space.size().unwrap(), 0);
         assert!(space.is_null());
         assert_eq!(space.extents()?, Extents::Null);
         Ok(())
@@ -262,9 +273,9 @@ mod tests {
     fn test_dataspace_scalar() -> Result<()> {
         let space = Dataspace::try_new(())?;
         assert_eq!(space.ndim(), 0);
-        assert_eq!(space.shape(), vec![]);
+        assert_eq!(space.shape().unwrap(), vec![]);
         assert_eq!(space.maxdims(), vec![]);
-        assert_eq!(space.size(), 1);
+        assert_eq!(space.size().unwrap(), 1);
         assert!(space.is_scalar());
         assert_eq!(space.extents()?, Extents::Scalar);
         Ok(())
@@ -274,18 +285,18 @@ mod tests {
     fn test_dataspace_simple() -> Result<()> {
         let space = Dataspace::try_new(123)?;
         assert_eq!(space.ndim(), 1);
-        assert_eq!(space.shape(), vec![123]);
+        assert_eq!(space.shape().unwrap(), vec![123]);
         assert_eq!(space.maxdims(), vec![Some(123)]);
-        assert_eq!(space.size(), 123);
+        assert_eq!(space.size().unwrap(), 123);
         assert!(space.is_simple());
         assert_eq!(space.extents()?, Extents::simple(123));
         assert!(!space.is_resizable());
 
         let space = Dataspace::try_new((5, 6..=10, 7..))?;
         assert_eq!(space.ndim(), 3);
-        assert_eq!(space.shape(), vec![5, 6, 7]);
+        assert_eq!(space.shape().unwrap(), vec![5, 6, 7]);
         assert_eq!(space.maxdims(), vec![Some(5), Some(10), None]);
-        assert_eq!(space.size(), 210);
+        assert_eq!(space.size().unwrap(), 210);
         assert!(space.is_simple());
         assert_eq!(space.extents()?, Extents::simple((5, 6..=10, 7..)));
         assert!(space.is_resizable());
@@ -299,7 +310,7 @@ mod tests {
         let space_copy = space.copy();
         assert!(space_copy.is_valid());
         assert_eq!(space_copy.ndim(), space.ndim());
-        assert_eq!(space_copy.shape(), space.shape());
+        assert_eq!(space_copy.shape().unwrap(), space.shape().unwrap());
         assert_eq!(space_copy.maxdims(), space.maxdims());
         Ok(())
     }
@@ -6,18 +6,22 @@ use std::ptr::{addr_of, addr_of_mut};
 
 use crate::sys::h5t::{
     H5T_cdata_t, H5T_class_t, H5T_cset_t, H5T_order_t, H5T_sign_t, H5T_str_t, H5Tarray_create2,
-    H5Tcompiler_conv, H5Tcopy, H5Tcreate, H5Tenum_create, H5Tenum_insert, H5Tequal, H5Tfind,
-    H5Tget_array_dims2, H5Tget_array_ndims, H5Tget_class, H5Tget_cset, H5Tget_member_name,
-    H5Tget_member_offset, H5Tget_member_type, H5Tget_member_value, H5Tget_nmembers, H5Tget_order,
-    H5Tget_sign, H5Tget_size, H5Tget_super, H5Tinsert, H5Tis_variable_str, H5Tset_cset,
-    H5Tset_size, H5Tset_strpad, H5Tvlen_create, H5T_VARIABLE,
+    H5Tcompiler_conv, H5Tcopy, H5Tcreate, H5Tenum_create, H5Tenum_insert, H5Tenum_nameof,
+    H5Tenum_valueof, H5Tequal, H5Tfind, H5Tget_array_dims2, H5Tget_array_ndims, H5Tget_class,
+    H5Tget_cset, H5Tget_member_name, H5Tget_member_offset, H5Tget_member_type, H5Tget_member_value,
+    H5Tget_nmembers, H5Tget_order, H5Tget_sign, H5Tget_size, H5Tget_super, H5Tget_tag, H5Tinsert,
+    H5Tis_variable_str, H5Tset_cset, H5Tset_size, H5Tset_strpad, H5Tset_tag, H5Tvlen_create,
+    H5T_VARIABLE,
 };
 use hdf5_types::{
-    CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor,
+    CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, OpaqueType,
+    TypeDescriptor,
 };
 
 use crate::globals::{H5T_C_S1, H5T_NATIVE_INT, H5T_NATIVE_INT8};
+use crate::hl::references::ensure_std_references;
 use crate::internal_prelude::*;
+use crate::sys::{api_table, ReferenceApi};
 
 #[cfg(target_endian = "big")]
 use crate::globals::{
@@ -31,6 +35,10 @@ use crate::globals::{
     H5T_STD_U16LE, H5T_STD_U32LE, H5T_STD_U64LE, H5T_STD_U8LE,
 };
 
+/// The feature named in version errors when creating the standard reference datatype.
+const STD_REFERENCE_TYPES: &str =
+    "the standard reference datatype (of ObjectReference2, RegionReference and AttributeReference)";
+
 #[cfg(target_endian = "big")]
 macro_rules! be_le {
     ($be:expr, $le:expr) => {
@@ -145,6 +153,7 @@ impl Default for Conversion {
 
 /// The byte order of a datatype.
 #[derive(Copy, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ByteOrder {
     /// Little endian.
     LittleEndian,
@@ -256,9 +265,9 @@ impl Datatype {
                     for idx in 0..h5try!(H5Tget_nmembers(id)) as _ {
                         let mut value: u64 = 0;
                         h5try!(H5Tget_member_value(id, idx, addr_of_mut!(value).cast()));
-                        let name = H5Tget_member_name(id, idx);
-                        members.push(EnumMember { name: string_from_cstr(name), value });
-                        h5_free_memory(name.cast());
+                        let name = h5_string(H5Tget_member_name(id, idx))
+                            .with_context(|| format!("getting the name of enum member {idx}"))?;
+                        members.push(EnumMember { name, value });
                     }
                     let base_dt = Self::from_id(H5Tget_super(id))?;
                     let (size, signed) = match base_dt.to_descriptor()? {
@@ -279,28 +288,30 @@ impl Datatype {
                 H5T_class_t::H5T_COMPOUND => {
                     let mut fields: Vec<CompoundField> = Vec::new();
                     for idx in 0..h5try!(H5Tget_nmembers(id)) as _ {
-                        let name = H5Tget_member_name(id, idx);
+                        let name = h5_string(H5Tget_member_name(id, idx))
+                            .with_context(|| format!("getting the name of field {idx}"))?;
                         let offset = H5Tget_member_offset(id, idx);
                         let ty = Self::from_id(h5try!(H5Tget_member_type(id, idx)))?;
                         fields.push(CompoundField {
-                            name: string_from_cstr(name),
+                            name,
                             ty: ty.to_descriptor()?,
                             offset: offset as _,
                             index: idx as _,
                         });
-                        h5_free_memory(name.cast());
                     }
                     Ok(TD::Compound(CompoundType { fields, size }))
                 }
                 H5T_class_t::H5T_ARRAY => {
                     let base_dt = Self::from_id(H5Tget_super(id))?;
                     let ndims = h5try!(H5Tget_array_ndims(id));
-                    if ndims == 1 {
-                        let mut len: hsize_t = 0;
-                        h5try!(H5Tget_array_dims2(id, addr_of_mut!(len)));
-                        Ok(TD::FixedArray(Box::new(base_dt.to_descriptor()?), len as _))
+                    ensure!(ndims > 0, "invalid number of array dimensions: {}", ndims);
+                    let mut dims: Vec<hsize_t> = vec![0; ndims as _];
+                    h5try!(H5Tget_array_dims2(id, dims.as_mut_ptr()));
+                    let base = Box::new(base_dt.to_descriptor()?);
+                    if let [len] = dims[..] {
+                        Ok(TD::FixedArray(base, crate::dim::hsize_to_ix(len)?))
                     } else {
-                        Err("Multi-dimensional array datatypes are not supported".into())
+                        Ok(TD::FixedArrayNd(base, crate::dim::to_ix(&dims)?))
                     }
                 }
                 H5T_class_t::H5T_STRING => {
@@ -318,14 +329,113 @@ impl Datatype {
                     let base_dt = Self::from_id(H5Tget_super(id))?;
                     Ok(TD::VarLenArray(Box::new(base_dt.to_descriptor()?)))
                 }
+                H5T_class_t::H5T_OPAQUE => {
+                    let tag = h5_string(H5Tget_tag(id)).context("getting the opaque tag")?;
+                    Ok(TD::Opaque(OpaqueType { size, tag }))
+                }
+                H5T_class_t::H5T_REFERENCE => {
+                    use hdf5_types::Reference;
+                    let is = |other: hid_t| H5Tequal(id, other) > 0;
+                    if is(*crate::globals::H5T_STD_REF_OBJ) {
+                        Ok(TD::Reference(Reference::Object))
+                    } else if is(*crate::globals::H5T_STD_REF_DSETREG) {
+                        Ok(TD::Reference(Reference::Region))
+                    } else if api_table().references == ReferenceApi::Standard
+                        && is(*crate::globals::H5T_STD_REF)
+                    {
+                        ensure_std_references(STD_REFERENCE_TYPES)?;
+                        Ok(TD::Reference(Reference::Std))
+                    } else {
+                        fail!("Unsupported reference type of {} bytes", size)
+                    }
+                }
                 _ => Err("Unsupported datatype class".into()),
             }
         })
     }
 
+    /// Returns the names and values of the members of an enum datatype, in definition order.
+    ///
+    /// Values of any base integer type are returned as `i64`; unsigned values above `i64::MAX`
+    /// are an error.
+    pub fn enum_members(&self) -> Result<Vec<(String, i64)>> {
+        let base = self.enum_base()?;
+        h5lock!({
+            let id = self.id();
+            let mut members = vec![];
+            for idx in 0..h5try!(H5Tget_nmembers(id)) as _ {
+                let name = h5_string(H5Tget_member_name(id, idx))
+                    .with_context(|| format!("getting the name of enum member {idx}"))?;
+                let mut buf = [0; 8];
+                h5try!(H5Tget_member_value(id, idx, buf.as_mut_ptr().cast()));
+                let value = base
+                    .decode(buf)
+                    .with_context(|| format!("reading the value of enum member '{name}'"))?;
+                members.push((name, value));
+            }
+            Ok(members)
+        })
+    }
+
+    /// Returns the name of the member of an enum datatype with the given value.
+    pub fn enum_name_of(&self, value: i64) -> Result<String> {
+        let buf = self.enum_base()?.encode(value)?;
+        h5lock!({
+            let id = self.id();
+            // make room for the longest member name, a shorter buffer is an error
+            let mut len = 0;
+            for idx in 0..h5try!(H5Tget_nmembers(id)) as _ {
+                len = len.max(h5_string(H5Tget_member_name(id, idx))?.len());
+            }
+            let mut name = vec![0; len + 1];
+            h5check(H5Tenum_nameof(id, buf.as_ptr().cast(), name.as_mut_ptr(), name.len()))
+                .with_context(|| format!("looking up the enum member with value {value}"))?;
+            Ok(string_from_cstr(name.as_ptr()))
+        })
+    }
+
+    /// Returns the value of the member of an enum datatype with the given name.
+    pub fn enum_value_of(&self, name: &str) -> Result<i64> {
+        let base = self.enum_base()?;
+        let c_name = to_cstring(name)?;
+        let mut buf = [0; 8];
+        h5call!(H5Tenum_valueof(self.id(), c_name.as_ptr(), buf.as_mut_ptr().cast()))
+            .with_context(|| format!("looking up the value of enum member '{name}'"))?;
+        base.decode(buf)
+    }
+
+    fn enum_base(&self) -> Result<EnumBase> {
+        h5lock!({
+            ensure!(H5Tget_class(self.id()) == H5T_class_t::H5T_ENUM, "datatype is not an enum");
+            let base = Self::from_id(h5try!(H5Tget_super(self.id())))?;
+            let size = base.size();
+            ensure!((1..=8).contains(&size), "unsupported enum base type of {} bytes", size);
+            let signed = match H5Tget_sign(base.id()) {
+                H5T_sign_t::H5T_SGN_NONE => false,
+                H5T_sign_t::H5T_SGN_2 => true,
+                _ => fail!("Invalid sign of enum base type"),
+            };
+            let big_endian = match base.byte_order() {
+                ByteOrder::LittleEndian => false,
+                ByteOrder::BigEndian => true,
+                order => fail!("unsupported byte order of enum base type: {:?}", order),
+            };
+            Ok(EnumBase { size, signed, big_endian })
+        })
+    }
+
     /// Creates a datatype from a concrete type.
     pub fn from_type<T: H5Type>() -> Result<Self> {
-        Self::from_descriptor(&<T as H5Type>::type_descriptor())
+        let desc = <T as H5Type>::type_descriptor();
+        if let TypeDescriptor::Compound(ref compound) = desc {
+            ensure!(
+                !compound.fields.is_empty(),
+                "{} is a compound type without fields, which HDF5 cannot store; \
+                 describe unit structs with TypeDescriptor::unit()",
+                std::any::type_name::<T>()
+            );
+        }
+        Self::from_descriptor(&desc)
     }
 
     /// Creates a datatype from a type descriptor.
@@ -406,14 +516,17 @@ impl Datatype {
                     Ok(enum_id)
                 }
                 TD::Compound(ref compound_type) => {
+                    compound_type.validate()?;
+                    // unit fields hold no data in memory; they only exist in the file type
+                    let fields = compound_type.fields.iter().filter(|f| !f.ty.is_unit());
+                    ensure!(fields.clone().next().is_some(), "compound type has only unit fields");
                     let compound_id = h5try!(H5Tcreate(H5T_class_t::H5T_COMPOUND, 1));
-                    for field in &compound_type.fields {
+                    h5try!(H5Tset_size(compound_id, compound_type.size));
+                    for field in fields {
                         let name = to_cstring(field.name.as_ref())?;
                         let field_dt = Self::from_descriptor(&field.ty)?;
-                        h5try!(H5Tset_size(compound_id, field.offset + field.ty.size()));
                         h5try!(H5Tinsert(compound_id, name.as_ptr(), field.offset, field_dt.id()));
                     }
-                    h5try!(H5Tset_size(compound_id, compound_type.size));
                     Ok(compound_id)
                 }
                 TD::FixedArray(ref ty, len) => {
@@ -421,15 +534,38 @@ impl Datatype {
                     let dims = len as hsize_t;
                     Ok(h5try!(H5Tarray_create2(elem_dt.id(), 1, addr_of!(dims))))
                 }
+                TD::FixedArrayNd(ref ty, ref dims) => {
+                    ensure!(
+                        !dims.is_empty() && dims.len() <= 32,
+                        "invalid number of array dimensions: {}",
+                        dims.len()
+                    );
+                    let elem_dt = Self::from_descriptor(ty)?;
+                    let dims: Vec<hsize_t> = dims.iter().map(|&d| d as _).collect();
+                    Ok(h5try!(H5Tarray_create2(elem_dt.id(), dims.len() as _, dims.as_ptr())))
+                }
                 TD::FixedAscii(size) => string_type(Some(size), H5T_cset_t::H5T_CSET_ASCII),
                 TD::FixedUnicode(size) => string_type(Some(size), H5T_cset_t::H5T_CSET_UTF8),
                 TD::VarLenArray(ref ty) => {
                     let elem_dt = Self::from_descriptor(ty)?;
                     Ok(h5try!(H5Tvlen_create(elem_dt.id())))
                 }
+                TD::Opaque(ref opaque) => {
+                    ensure!(
+                        opaque.tag.len() < 256,
+                        "opaque tag of {} bytes is longer than 255 bytes",
+                        opaque.tag.len()
+                    );
+                    let tag = to_cstring(opaque.tag.as_str())?;
+                    // unit types are stored as a single byte
+                    let opaque_id = h5try!(H5Tcreate(H5T_class_t::H5T_OPAQUE, opaque.size.max(1)));
+                    h5try!(H5Tset_tag(opaque_id, tag.as_ptr()));
+                    Ok(opaque_id)
+                }
                 TD::VarLenAscii => string_type(None, H5T_cset_t::H5T_CSET_ASCII),
                 TD::VarLenUnicode => string_type(None, H5T_cset_t::H5T_CSET_UTF8),
                 TD::Reference(hdf5_types::Reference::Std) => {
+                    ensure_std_references(STD_REFERENCE_TYPES)?;
                     Ok(h5try!(H5Tcopy(*crate::globals::H5T_STD_REF)))
                 }
                 TD::Reference(hdf5_types::Reference::Object) => {
@@ -445,6 +581,55 @@ impl Datatype {
     }
 }
 
+/// The base integer type of an enum datatype, which member values are staged in.
+#[derive(Clone, Copy, Debug)]
+struct EnumBase {
+    size: usize,
+    signed: bool,
+    big_endian: bool,
+}
+
+impl EnumBase {
+    /// Reads a member value from the first `size` bytes of `buf`.
+    fn decode(self, buf: [u8; 8]) -> Result<i64> {
+        let mut bytes = [0; 8];
+        bytes[..self.size].copy_from_slice(&buf[..self.size]);
+        if self.big_endian {
+            bytes[..self.size].reverse();
+        }
+        let raw = u64::from_le_bytes(bytes);
+        if self.signed {
+            // sign-extend from the width of the base type
+            let shift = 64 - 8 * self.size as u32;
+            Ok(((raw << shift) as i64) >> shift)
+        } else {
+            i64::try_from(raw).map_err(|_| format!("enum value {raw} does not fit in i64").into())
+        }
+    }
+
+    /// Writes a member value into the first `size` bytes of the returned buffer.
+    fn encode(self, value: i64) -> Result<[u8; 8]> {
+        let bits = 8 * self.size as u32;
+        let fits = if self.signed {
+            bits == 64 || matches!(value >> (bits - 1), 0 | -1)
+        } else {
+            value >= 0 && (bits == 64 || value >> bits == 0)
+        };
+        ensure!(
+            fits,
+            "value {} is out of range of the {}-byte {} base type of the enum",
+            value,
+            self.size,
+            if self.signed { "signed" } else { "unsigned" }
+        );
+        let mut buf = value.to_le_bytes();
+        if self.big_endian {
+            buf[..self.size].reverse();
+        }
+        Ok(buf)
+    }
+}
+
 /// NOTE: tests of public functions are in hdf5/tests/test_datatype.rs
 #[cfg(test)]
 mod tests {
@@ -452,6 +637,24 @@ mod tests {
     use hdf5_types::{FixedAscii, FixedUnicode};
     use pretty_assertions::assert_str_eq;
 
+    #[test]
+    fn test_enum_base_staging() {
+        let i16_be = EnumBase { size: 2, signed: true, big_endian: true };
+        assert_eq!(i16_be.encode(-300).unwrap()[..2], [0xfe, 0xd4]);
+        assert_eq!(i16_be.decode(i16_be.encode(-300).unwrap()).unwrap(), -300);
+        assert!(i16_be.encode(40000).is_err());
+
+        let u8_le = EnumBase { size: 1, signed: false, big_endian: false };
+        assert_eq!(u8_le.decode([255, 1, 2, 3, 4, 5, 6, 7]).unwrap(), 255);
+        assert!(u8_le.encode(-1).is_err());
+        assert!(u8_le.encode(256).is_err());
+
+        let i64_le = EnumBase { size: 8, signed: true, big_endian: false };
+        assert_eq!(i64_le.decode(i64_le.encode(i64::MIN).unwrap()).unwrap(), i64::MIN);
+        let u64_le = EnumBase { size: 8, signed: false, big_endian: false };
+        assert!(u64_le.decode([255; 8]).is_err());
+    }
+
     #[test]
     fn test_ensure_convertible_fail_err_msg() {
         const SIZE: usize = 10;
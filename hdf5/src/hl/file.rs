@@ -1,20 +1,32 @@
-use std::fmt::{self, Debug};
+use std::fmt::{self, Debug, Display};
 use std::mem;
 use std::ops::Deref;
 use std::path::Path;
+use std::ptr;
 
+use crate::sys::h5a::{H5Aclose, H5Aget_name};
+use crate::sys::h5d::H5Dclose;
 use crate::sys::h5f::{
-    H5Fclose, H5Fcreate, H5Fflush, H5Fget_access_plist, H5Fget_create_plist, H5Fget_filesize,
-    H5Fget_freespace, H5Fget_intent, H5Fget_obj_count, H5Fget_obj_ids, H5Fopen, H5F_ACC_DEFAULT,
-    H5F_ACC_EXCL, H5F_ACC_RDONLY, H5F_ACC_RDWR, H5F_ACC_TRUNC, H5F_SCOPE_LOCAL,
+    H5Fclear_elink_file_cache, H5Fclose, H5Fcreate, H5Fflush, H5Fget_access_plist,
+    H5Fget_create_plist, H5Fget_file_image, H5Fget_filesize, H5Fget_freespace, H5Fget_obj_count,
+    H5Fget_obj_ids, H5Fopen, H5Freopen, H5F_ACC_EXCL, H5F_ACC_RDONLY, H5F_ACC_RDWR, H5F_ACC_TRUNC,
+    H5F_OBJ_ATTR, H5F_OBJ_DATASET, H5F_OBJ_DATATYPE, H5F_OBJ_GROUP, H5F_SCOPE_GLOBAL,
+    H5F_SCOPE_LOCAL,
 };
 use crate::sys::h5f::{H5Fstart_swmr_write, H5F_ACC_SWMR_READ};
+use crate::sys::h5g::H5Gclose;
+use crate::sys::h5i::{H5Idec_ref, H5Iget_name, H5Iget_ref, H5Iget_type};
+use crate::sys::h5t::H5Tclose;
 
+use crate::hl::attribute::copy_attr;
+use crate::hl::copy::TreeCopy;
 use crate::hl::plist::{
-    file_access::{FileAccess, FileAccessBuilder},
-    file_create::{FileCreate, FileCreateBuilder},
+    file_access::{CoreDriver, FileAccess, FileAccessBuilder, FileCloseDegree},
+    file_create::{AttrCreationOrder, FileCreate, FileCreateBuilder, LinkCreationOrder},
+    object_copy::ObjectCopy,
 };
 use crate::internal_prelude::*;
+use crate::DeterministicOrder;
 
 /// File opening mode.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -33,6 +45,86 @@ pub enum OpenMode {
     Append,
 }
 
+/// Part of the virtual file flushed by [`File::flush_scope`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FlushScope {
+    /// Only the file itself.
+    #[default]
+    Local,
+    /// The file and the files mounted on it.
+    Global,
+}
+
+/// Largest number of open objects listed by the `Debug` output of a [`File`].
+const DEBUG_MAX_CHILDREN: usize = 8;
+
+/// Types of the objects in a file returned by [`File::open_children`].
+const CHILD_TYPES: c_uint = H5F_OBJ_DATASET | H5F_OBJ_GROUP | H5F_OBJ_DATATYPE | H5F_OBJ_ATTR;
+
+/// The type of an [`OpenObject`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OpenObjectKind {
+    Group,
+    Dataset,
+    Datatype,
+    Attribute,
+}
+
+impl OpenObjectKind {
+    fn from_id_type(id_type: H5I_type_t) -> Option<Self> {
+        match id_type {
+            H5I_GROUP => Some(Self::Group),
+            H5I_DATASET => Some(Self::Dataset),
+            H5I_DATATYPE => Some(Self::Datatype),
+            H5I_ATTR => Some(Self::Attribute),
+            _ => None,
+        }
+    }
+}
+
+impl Display for OpenObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Group => "group",
+            Self::Dataset => "dataset",
+            Self::Datatype => "datatype",
+            Self::Attribute => "attribute",
+        })
+    }
+}
+
+/// An object open in a file, as returned by [`File::open_children`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenObject {
+    /// The object identifier.
+    pub id: hid_t,
+    /// The object type.
+    pub kind: OpenObjectKind,
+    /// The path of the object, or the name of an attribute, if it has one.
+    pub name: Option<String>,
+}
+
+impl Display for OpenObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(name) = &self.name {
+            write!(f, " '{name}'")?;
+        }
+        write!(f, " (id {})", self.id)
+    }
+}
+
+/// Options for [`File::repack_into_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RepackOptions {
+    /// Copy the objects that soft links point to instead of recreating the links.
+    pub expand_soft_links: bool,
+    /// Copy the objects that external links point to instead of recreating the links.
+    pub expand_external_links: bool,
+    /// Order in which group members and attributes are copied.
+    pub order: DeterministicOrder,
+}
+
 /// HDF5 file object.
 #[repr(transparent)]
 #[derive(Clone)]
@@ -61,8 +153,25 @@ impl ObjectClass for File {
 }
 
 impl Debug for File {
+    /// Formats the file name and mode, followed by the objects open in the file if there
+    /// are a few of them, see [`open_children`](Self::open_children).
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.debug_fmt(f)
+        let described = h5lock!(self.is_valid().then(|| (self.short_repr(), self.open_children())));
+        match described {
+            Some((Some(repr), children))
+                if !children.is_empty() && children.len() <= DEBUG_MAX_CHILDREN =>
+            {
+                let children = children.iter().map(ToString::to_string).collect::<Vec<_>>();
+                write!(f, "<HDF5 {}: {}, open: [{}]>", Self::NAME, repr, children.join(", "))
+            }
+            _ => self.debug_fmt(f),
+        }
+    }
+}
+
+impl Display for File {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display_fmt(f)
     }
 }
 
@@ -105,6 +214,64 @@ impl File {
         FileBuilder::new().open_as(filename, mode)
     }
 
+    /// Creates a file that lives only in memory (core driver without a backing store).
+    ///
+    /// The name is not used to access the disk; it only has to be unique among the files
+    /// currently open in the process.
+    pub fn create_in_memory(name: &str) -> Result<Self> {
+        Self::in_memory_with_capacity(name, CoreDriver::default().increment)
+    }
+
+    /// Creates a file that lives only in memory, like [`create_in_memory`], with an initial
+    /// image of `capacity` bytes that grows by `capacity` bytes at a time.
+    ///
+    /// Files of many gigabytes are built much faster with a large capacity, since every
+    /// growth of the image may copy it; see [`FileAccessBuilder::core_options`].
+    ///
+    /// [`create_in_memory`]: Self::create_in_memory
+    pub fn in_memory_with_capacity(name: &str, capacity: usize) -> Result<Self> {
+        FileBuilder::new().with_fapl(|p| p.core_options(capacity, false)).create(name)
+    }
+
+    /// Opens a read-only file from a copy of its contents, as returned by
+    /// [`to_image`](Self::to_image).
+    ///
+    /// As with [`create_in_memory`](Self::create_in_memory), the name is not used to access
+    /// the disk; it only has to be unique among the files currently open in the process.
+    /// To modify the file in memory, open it with [`FileAccessBuilder::core_image`] and
+    /// [`OpenMode::ReadWrite`].
+    pub fn from_image(name: &str, image: &[u8]) -> Result<Self> {
+        FileBuilder::new().with_fapl(|p| p.core_image(image)).open(name)
+    }
+
+    /// Returns a copy of the contents of the file, e.g. of a file created in memory.
+    ///
+    /// The file is flushed first if it is open for writing.
+    pub fn to_image(&self) -> Result<Vec<u8>> {
+        h5lock!({
+            let size = h5try!(H5Fget_file_image(self.id(), ptr::null_mut(), 0));
+            let mut image = vec![0_u8; size as _];
+            h5try!(H5Fget_file_image(self.id(), image.as_mut_ptr().cast(), image.len()));
+            Ok(image)
+        })
+    }
+
+    /// Opens a file read-only `n` times, returning a separate handle for each opening.
+    ///
+    /// This allows each thread to use a handle of its own instead of sharing one, so that
+    /// closing or dropping objects in one thread does not affect the others. Calls into the
+    /// library are still serialized by the crate's lock. Read-only opens only take shared
+    /// file locks, so they do not conflict with each other.
+    ///
+    /// Within a process, the library recognizes that all handles refer to the same file
+    /// and shares the open file and its metadata cache between them. Changes made to the
+    /// file by other processes are not guaranteed to become visible through any of the
+    /// handles; use [`OpenMode::ReadSWMR`] to read a file while it is being written.
+    pub fn open_multi<P: AsRef<Path>>(filename: P, n: usize) -> Result<Vec<Self>> {
+        let filename = filename.as_ref();
+        (0..n).map(|_| Self::open(filename)).collect()
+    }
+
     /// Opens a file with custom file-access and file-creation options.
     pub fn with_options() -> FileBuilder {
         FileBuilder::new()
@@ -122,7 +289,7 @@ impl File {
 
     /// Returns true if the file was opened in a read-only mode.
     pub fn is_read_only(&self) -> bool {
-        h5get!(H5Fget_intent(self.id()): c_uint).unwrap_or(H5F_ACC_DEFAULT) != H5F_ACC_RDWR
+        self.handle().is_in_read_only_file()
     }
 
     /// Returns the userblock size in bytes (or 0 if the file handle is invalid).
@@ -132,12 +299,73 @@ impl File {
 
     /// Flushes the file to the storage medium.
     pub fn flush(&self) -> Result<()> {
-        h5call!(H5Fflush(self.id(), H5F_SCOPE_LOCAL)).and(Ok(()))
+        self.flush_scope(FlushScope::Local)
+    }
+
+    /// Flushes the file, or with [`FlushScope::Global`] the file and all the files mounted
+    /// on it, to the storage medium.
+    pub fn flush_scope(&self, scope: FlushScope) -> Result<()> {
+        let scope = match scope {
+            FlushScope::Local => H5F_SCOPE_LOCAL,
+            FlushScope::Global => H5F_SCOPE_GLOBAL,
+        };
+        h5call!(H5Fflush(self.id(), scope)).and(Ok(()))
+    }
+
+    /// Closes the files held open by the external link file cache of this file.
+    ///
+    /// Files targeted by external links are kept open in the cache after traversal, up to
+    /// the size set with [`FileAccessBuilder::elink_file_cache_size`]; clearing it releases
+    /// their file handles. Files with objects still open are not closed.
+    pub fn clear_external_link_cache(&self) -> Result<()> {
+        h5call!(H5Fclear_elink_file_cache(self.id())).and(Ok(()))
+    }
+
+    /// Returns the file size in bytes.
+    ///
+    /// Unlike [`size`](Self::size), this reports errors instead of returning 0.
+    pub fn file_size(&self) -> Result<u64> {
+        h5get!(H5Fget_filesize(self.id()): hsize_t).map(|size| size as _)
+    }
+
+    /// Reads `len` bytes at byte `offset` of the underlying file, bypassing the library.
+    ///
+    /// The file is opened again read-only with [`std::fs`], so this is only supported with
+    /// drivers that store the file as a single file on disk (sec2, stdio, log and direct);
+    /// it fails for in-memory (core) files and for multi-file drivers.
+    ///
+    /// The bytes on disk may be stale while the library caches metadata or raw data, so a
+    /// writable file is flushed before reading. Changes made through other handles to the
+    /// same file, or concurrently from other threads, may still not be visible. Note that
+    /// `offset` is a position in the file, while addresses reported by the library are
+    /// relative to the end of the userblock (see [`userblock`](Self::userblock)).
+    pub fn read_file_bytes(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let driver = self.fapl()?.driver();
+        ensure!(
+            matches!(driver.name(), "sec2" | "stdio" | "log" | "direct"),
+            "reading file bytes requires a single-file disk driver, got '{}'",
+            driver.name()
+        );
+        if !self.is_read_only() {
+            self.flush()?;
+        }
+        let filename = self.filename();
+        let read = || -> std::io::Result<Vec<u8>> {
+            let mut file = std::fs::File::open(&filename)?;
+            file.seek(SeekFrom::Start(offset))?;
+            let mut buf = vec![0; len];
+            file.read_exact(&mut buf)?;
+            Ok(buf)
+        };
+        read().map_err(|err| {
+            format!("unable to read {len} bytes at offset {offset} of '{filename}': {err}").into()
+        })
     }
 
     /// Returns objects IDs of the contained objects. NOTE: these are borrowed references.
-    #[allow(unused)]
-    fn get_obj_ids(&self, types: c_uint) -> Vec<hid_t> {
+    pub(crate) fn get_obj_ids(&self, types: c_uint) -> Vec<hid_t> {
         h5lock!({
             let count = h5call!(H5Fget_obj_count(self.id(), types)).unwrap_or(0) as size_t;
             if count > 0 {
@@ -154,6 +382,84 @@ impl File {
         })
     }
 
+    /// Returns the groups, datasets, named datatypes and attributes open in the file,
+    /// including those opened through other handles to the same file, in no particular order.
+    ///
+    /// This helps finding out what keeps a file from closing, see also
+    /// [`diagnostics`](crate::diagnostics), which records where the objects were opened.
+    pub fn open_children(&self) -> Vec<OpenObject> {
+        h5lock!(self
+            .get_obj_ids(CHILD_TYPES)
+            .into_iter()
+            .filter_map(|id| {
+                let kind =
+                    OpenObjectKind::from_id_type(crate::sys::convert_h5i_type(H5Iget_type(id)))?;
+                let name = match kind {
+                    OpenObjectKind::Attribute => get_h5_str(|m, s| H5Aget_name(id, s, m)).ok(),
+                    _ => get_h5_str(|m, s| H5Iget_name(id, m, s)).ok(),
+                };
+                Some(OpenObject { id, kind, name: name.filter(|name| !name.is_empty()) })
+            })
+            .collect())
+    }
+
+    /// Closes all the objects open in the file (see [`open_children`](Self::open_children))
+    /// and returns how many were closed.
+    ///
+    /// Each object is closed regardless of how many handles refer to it, so all the handles
+    /// to these objects become invalid: using them fails, and dropping them does nothing.
+    /// This is meant for recovering from leaked handles, e.g. so that a file with the
+    /// [`Semi`](FileCloseDegree::Semi) close degree can be closed.
+    pub fn close_all_children(&self) -> Result<usize> {
+        h5lock!({
+            let mut closed = 0;
+            for id in self.get_obj_ids(CHILD_TYPES) {
+                let close: unsafe extern "C" fn(hid_t) -> herr_t =
+                    match crate::sys::convert_h5i_type(H5Iget_type(id)) {
+                        H5I_GROUP => H5Gclose,
+                        H5I_DATASET => H5Dclose,
+                        H5I_DATATYPE => H5Tclose,
+                        H5I_ATTR => H5Aclose,
+                        _ => continue,
+                    };
+                // release the references of the other handles, so that closing frees the object
+                let refcount = h5call!(H5Iget_ref(id))?;
+                for _ in 1..refcount {
+                    h5call!(H5Idec_ref(id))?;
+                }
+                h5call!(close(id)).with_context(|| format!("closing object {id}"))?;
+                closed += 1;
+            }
+            Ok(closed)
+        })
+    }
+
+    /// Returns a new handle to the same open file, with the same access mode.
+    ///
+    /// Both handles share the underlying open file, so changes made through one are
+    /// immediately visible through the other. The file stays open until all handles (and
+    /// the objects opened through them) are closed, so they can be dropped in any order.
+    /// Files mounted on `self` are not mounted on the new handle.
+    pub fn reopen(&self) -> Result<Self> {
+        h5lock!(Self::from_id(h5try!(H5Freopen(self.id()))))
+    }
+
+    /// Opens the object identified by `id`, which must belong to this file.
+    ///
+    /// Fails if the identifier was taken from another file, or in a session in which the
+    /// file had a different file number; see [`ObjectId`].
+    pub fn open_by_id(&self, id: &ObjectId) -> Result<Location> {
+        let fileno = self.object_id()?.fileno();
+        ensure!(
+            id.fileno() == fileno,
+            "object id belongs to file number {}, not to '{}' (file number {})",
+            id.fileno(),
+            self.filename(),
+            fileno
+        );
+        self.open_by_token(id.token())
+    }
+
     /// Closes the file and invalidates all open handles for contained objects.
     pub fn close(self) -> Result<()> {
         let id = self.id();
@@ -162,6 +468,52 @@ impl File {
         h5call!(H5Fclose(id)).map(|_| ())
     }
 
+    /// Closes the file handle, or returns it along with an error if the file cannot be closed.
+    ///
+    /// Unlike [`close`](Self::close) and dropping the handle, this fails without closing
+    /// anything if the file has the [`Semi`](FileCloseDegree::Semi) close degree and objects
+    /// in it are still open (see [`open_object_count`](Self::open_object_count)); dropping
+    /// the last handle in that case would leave the file open until the process exits. If
+    /// [`diagnostics`](crate::diagnostics) are enabled, the error lists the oldest open objects.
+    pub fn try_close(self) -> Result<(), (Self, Error)> {
+        h5lock!({
+            let check = || -> Result<()> {
+                if self.fapl()?.get_fclose_degree()? == FileCloseDegree::Semi {
+                    let count = self.open_object_count()?;
+                    if count != 0 {
+                        let oldest = crate::diagnostics::oldest_in_file(self.id(), 5);
+                        let oldest = oldest.iter().map(ToString::to_string).collect::<Vec<_>>();
+                        let oldest = if oldest.is_empty() {
+                            String::new()
+                        } else {
+                            format!("; oldest: {}", oldest.join(", "))
+                        };
+                        fail!(
+                            "cannot close file '{}': it has open objects ({}){}",
+                            self.filename(),
+                            count,
+                            oldest
+                        );
+                    }
+                }
+                Ok(())
+            };
+            if let Err(err) = check().and_then(|()| h5call!(H5Fclose(self.id()))) {
+                return Err((self, err));
+            }
+            // the identifier was released by H5Fclose
+            mem::forget(self.0);
+            Ok(())
+        })
+    }
+
+    /// Returns the number of open datasets, groups, named datatypes and attributes in the file,
+    /// including those opened through other handles to the same file.
+    pub fn open_object_count(&self) -> Result<usize> {
+        let types = H5F_OBJ_DATASET | H5F_OBJ_GROUP | H5F_OBJ_DATATYPE | H5F_OBJ_ATTR;
+        h5call!(H5Fget_obj_count(self.id(), types)).map(|count| count as _)
+    }
+
     /// Returns a copy of the file access property list.
     pub fn access_plist(&self) -> Result<FileAccess> {
         h5lock!(FileAccess::from_id(h5try!(H5Fget_access_plist(self.id()))))
@@ -188,6 +540,55 @@ impl File {
         h5call!(H5Fstart_swmr_write(id))?;
         Ok(())
     }
+
+    /// Copies all objects into a new file, which reclaims the space of deleted objects.
+    ///
+    /// This is the same as [`repack_into_with`](Self::repack_into_with) with the default
+    /// options, which keep soft and external links as links.
+    pub fn repack_into<P: AsRef<Path>>(&self, dst: P) -> Result<Self> {
+        self.repack_into_with(dst, &RepackOptions::default())
+    }
+
+    /// Copies all objects into a new file, which reclaims the space of deleted objects.
+    ///
+    /// The new file is created with the same file creation properties (fails if it already
+    /// exists). Groups are recreated with the same creation properties and attributes, and all
+    /// other objects are copied with `H5Ocopy`, which preserves their attributes and filters.
+    /// The links and attributes of each group are processed in the order given by
+    /// [`RepackOptions::order`] (creation order if it is tracked, by default). Objects
+    /// reachable through several hard links are copied once and linked again, so they stay
+    /// shared.
+    ///
+    /// Since objects are always processed in a deterministic order, repacking the same file
+    /// twice with the same options produces byte-identical files, provided that no object in it
+    /// records modification times.
+    pub fn repack_into_with<P: AsRef<Path>>(
+        &self,
+        dst: P,
+        options: &RepackOptions,
+    ) -> Result<Self> {
+        h5lock!({
+            let fcpl = self.fcpl()?;
+            let dst = FileBuilder::new().set_fcpl(&fcpl)?.create_excl(dst)?;
+            let ocpypl = ObjectCopy::build()
+                .expand_soft_links(options.expand_soft_links)
+                .expand_ext_links(options.expand_external_links)
+                .finish()?;
+
+            let attr_order = options
+                .order
+                .traversal(fcpl.attr_creation_order().contains(AttrCreationOrder::TRACKED));
+            for name in Attribute::attr_names_by(self, attr_order)? {
+                copy_attr(&self.attr(&name)?, &dst, &name)
+                    .with_context(|| format!("copying root attribute '{name}'"))?;
+            }
+
+            let tracked = fcpl.link_creation_order().contains(LinkCreationOrder::TRACKED);
+            TreeCopy::new(&ocpypl, options.order)?.copy_members(self, &dst, tracked)?;
+            dst.flush()?;
+            Ok(dst)
+        })
+    }
 }
 
 /// File builder allowing to customize file access/creation property lists.
@@ -229,16 +630,19 @@ impl FileBuilder {
     }
 
     /// Opens a file in a given mode.
+    ///
+    /// On Unix, the bytes of `filename` are passed to the library unchanged, so file names
+    /// need not be valid UTF-8. On other platforms, the file name must be valid Unicode and
+    /// is passed as UTF-8.
     pub fn open_as<P: AsRef<Path>>(&self, filename: P, mode: OpenMode) -> Result<File> {
         let filename = filename.as_ref();
         if mode == OpenMode::Append {
-            if let Ok(file) = self.open_as(filename, OpenMode::ReadWrite) {
-                return Ok(file);
+            match self.open_as(filename, OpenMode::ReadWrite) {
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                result => return result,
             }
         }
-        let filename = to_cstring(
-            filename.to_str().ok_or_else(|| format!("Invalid UTF-8 in file name: {filename:?}"))?,
-        )?;
+        let c_filename = path_to_cstring(filename)?;
         let flags = match mode {
             OpenMode::Read => H5F_ACC_RDONLY,
             OpenMode::ReadSWMR => H5F_ACC_RDONLY | H5F_ACC_SWMR_READ,
@@ -246,110 +650,259 @@ impl FileBuilder {
             OpenMode::Create => H5F_ACC_TRUNC,
             OpenMode::CreateExcl | OpenMode::Append => H5F_ACC_EXCL,
         };
-        let fname_ptr = filename.as_ptr();
+        let fname_ptr = c_filename.as_ptr();
+        let is_open = matches!(mode, OpenMode::Read | OpenMode::ReadSWMR | OpenMode::ReadWrite);
         h5lock!({
             let fapl = self.fapl.finish()?;
-            match mode {
-                OpenMode::Read | OpenMode::ReadWrite => {
-                    File::from_id(h5try!(H5Fopen(fname_ptr, flags, fapl.id())))
-                }
-                OpenMode::ReadSWMR => File::from_id(h5try!(H5Fopen(fname_ptr, flags, fapl.id()))),
-                _ => {
-                    let fcpl = self.fcpl.finish()?;
-                    File::from_id(h5try!(H5Fcreate(fname_ptr, flags, fcpl.id(), fapl.id())))
-                }
+            if is_open {
+                File::from_id(h5try!(H5Fopen(fname_ptr, flags, fapl.id())))
+            } else {
+                let fcpl = self.fcpl.finish()?;
+                File::from_id(h5try!(H5Fcreate(fname_ptr, flags, fcpl.id(), fapl.id())))
             }
         })
+        .map_err(|err| {
+            let action = if is_open { "opening" } else { "creating" };
+            let context = match err.suggestion() {
+                Some(suggestion) => {
+                    format!("{action} file '{}' ({suggestion})", filename.display())
+                }
+                None => format!("{action} file '{}'", filename.display()),
+            };
+            Error::Context { context, source: Box::new(err) }
+        })
     }
 
     // File Access Property List
 
     /// Sets current file access property list to a given one.
-    pub fn set_access_plist(&mut self, fapl: &FileAccess) -> Result<&mut Self> {
-        FileAccessBuilder::from_plist(fapl).map(|fapl| {
-            self.fapl = fapl;
-            self
-        })
+    pub fn set_access_plist(mut self, fapl: &FileAccess) -> Result<Self> {
+        self.fapl = FileAccessBuilder::from_plist(fapl)?;
+        Ok(self)
     }
 
     /// A short alias for `set_access_plist()`.
-    pub fn set_fapl(&mut self, fapl: &FileAccess) -> Result<&mut Self> {
+    pub fn set_fapl(self, fapl: &FileAccess) -> Result<Self> {
         self.set_access_plist(fapl)
     }
 
     /// Returns the builder object for the file access property list.
+    ///
+    /// Its setters can be chained on the returned reference through
+    /// [`FileAccessBuilderMut`](crate::hl::plist::file_access::FileAccessBuilderMut).
+    #[deprecated(note = "builders are consumed by their setters; use `with_access_plist()`")]
     pub fn access_plist(&mut self) -> &mut FileAccessBuilder {
         &mut self.fapl
     }
 
     /// A short alias for `access_plist()`.
+    #[deprecated(note = "builders are consumed by their setters; use `with_fapl()`")]
     pub fn fapl(&mut self) -> &mut FileAccessBuilder {
-        self.access_plist()
+        &mut self.fapl
     }
 
     /// Allows accessing the builder object for the file access property list.
-    pub fn with_access_plist<F>(&mut self, func: F) -> &mut Self
+    pub fn with_access_plist<F>(mut self, func: F) -> Self
     where
-        F: Fn(&mut FileAccessBuilder) -> &mut FileAccessBuilder,
+        F: FnOnce(FileAccessBuilder) -> FileAccessBuilder,
     {
-        func(&mut self.fapl);
+        self.fapl = func(mem::take(&mut self.fapl));
         self
     }
 
     /// A short alias for `with_access_plist()`.
-    pub fn with_fapl<F>(&mut self, func: F) -> &mut Self
+    pub fn with_fapl<F>(self, func: F) -> Self
     where
-        F: Fn(&mut FileAccessBuilder) -> &mut FileAccessBuilder,
+        F: FnOnce(FileAccessBuilder) -> FileAccessBuilder,
     {
         self.with_access_plist(func)
     }
 
+    /// Sets what happens when the file is closed while objects in it are still open.
+    ///
+    /// This is a shortcut for setting [`fclose_degree`](FileAccessBuilder::fclose_degree) in
+    /// the file access property list; see [`FileCloseDegree`] for the options. Note that
+    /// all handles to a file that is already open must use the same close degree.
+    pub fn close_degree(self, degree: FileCloseDegree) -> Self {
+        self.with_fapl(|p| p.fclose_degree(degree))
+    }
+
     // File Creation Property List
 
     /// Sets current file creation property list to a given one.
-    pub fn set_create_plist(&mut self, fcpl: &FileCreate) -> Result<&mut Self> {
-        FileCreateBuilder::from_plist(fcpl).map(|fcpl| {
-            self.fcpl = fcpl;
-            self
-        })
+    pub fn set_create_plist(mut self, fcpl: &FileCreate) -> Result<Self> {
+        self.fcpl = FileCreateBuilder::from_plist(fcpl)?;
+        Ok(self)
     }
 
     /// A short alias for `set_create_plist()`.
-    pub fn set_fcpl(&mut self, fcpl: &FileCreate) -> Result<&mut Self> {
+    pub fn set_fcpl(self, fcpl: &FileCreate) -> Result<Self> {
         self.set_create_plist(fcpl)
     }
 
     /// Returns the builder object for the file creation property list.
+    ///
+    /// Its setters can be chained on the returned reference through
+    /// [`FileCreateBuilderMut`](crate::hl::plist::file_create::FileCreateBuilderMut).
+    #[deprecated(note = "builders are consumed by their setters; use `with_create_plist()`")]
     pub fn create_plist(&mut self) -> &mut FileCreateBuilder {
         &mut self.fcpl
     }
 
     /// A short alias for `create_plist()`.
+    #[deprecated(note = "builders are consumed by their setters; use `with_fcpl()`")]
     pub fn fcpl(&mut self) -> &mut FileCreateBuilder {
-        self.create_plist()
+        &mut self.fcpl
     }
 
     /// Allows accessing the builder object for the file creation property list.
-    pub fn with_create_plist<F>(&mut self, func: F) -> &mut Self
+    pub fn with_create_plist<F>(mut self, func: F) -> Self
     where
-        F: Fn(&mut FileCreateBuilder) -> &mut FileCreateBuilder,
+        F: FnOnce(FileCreateBuilder) -> FileCreateBuilder,
     {
-        func(&mut self.fcpl);
+        self.fcpl = func(mem::take(&mut self.fcpl));
         self
     }
 
     /// A short alias for `with_create_plist()`.
-    pub fn with_fcpl<F>(&mut self, func: F) -> &mut Self
+    pub fn with_fcpl<F>(self, func: F) -> Self
     where
-        F: Fn(&mut FileCreateBuilder) -> &mut FileCreateBuilder,
+        F: FnOnce(FileCreateBuilder) -> FileCreateBuilder,
     {
         self.with_create_plist(func)
     }
 }
 
+/// The `&mut self` setters of [`FileBuilder`].
+///
+/// The setters of the builder consume it and return it; this trait provides them for `&mut`
+/// references to the builder, modifying the builder in place.
+#[deprecated(note = "builders are consumed by their setters; use `builder = builder.setter(..)`")]
+pub trait FileBuilderMut: Sized {
+    /// Calls [`FileBuilder::set_access_plist`] in place.
+    #[deprecated(
+        note = "builders are consumed by their setters; use `builder = builder.setter(..)`"
+    )]
+    fn set_access_plist(self, fapl: &FileAccess) -> Result<Self>;
+
+    /// Calls [`FileBuilder::set_fapl`] in place.
+    #[deprecated(
+        note = "builders are consumed by their setters; use `builder = builder.setter(..)`"
+    )]
+    fn set_fapl(self, fapl: &FileAccess) -> Result<Self>;
+
+    /// Calls [`FileBuilder::with_access_plist`] in place.
+    #[deprecated(
+        note = "builders are consumed by their setters; use `builder = builder.setter(..)`"
+    )]
+    fn with_access_plist<F>(self, func: F) -> Self
+    where
+        F: FnOnce(FileAccessBuilder) -> FileAccessBuilder;
+
+    /// Calls [`FileBuilder::with_fapl`] in place.
+    #[deprecated(
+        note = "builders are consumed by their setters; use `builder = builder.setter(..)`"
+    )]
+    fn with_fapl<F>(self, func: F) -> Self
+    where
+        F: FnOnce(FileAccessBuilder) -> FileAccessBuilder;
+
+    /// Calls [`FileBuilder::close_degree`] in place.
+    #[deprecated(
+        note = "builders are consumed by their setters; use `builder = builder.setter(..)`"
+    )]
+    fn close_degree(self, degree: FileCloseDegree) -> Self;
+
+    /// Calls [`FileBuilder::set_create_plist`] in place.
+    #[deprecated(
+        note = "builders are consumed by their setters; use `builder = builder.setter(..)`"
+    )]
+    fn set_create_plist(self, fcpl: &FileCreate) -> Result<Self>;
+
+    /// Calls [`FileBuilder::set_fcpl`] in place.
+    #[deprecated(
+        note = "builders are consumed by their setters; use `builder = builder.setter(..)`"
+    )]
+    fn set_fcpl(self, fcpl: &FileCreate) -> Result<Self>;
+
+    /// Calls [`FileBuilder::with_create_plist`] in place.
+    #[deprecated(
+        note = "builders are consumed by their setters; use `builder = builder.setter(..)`"
+    )]
+    fn with_create_plist<F>(self, func: F) -> Self
+    where
+        F: FnOnce(FileCreateBuilder) -> FileCreateBuilder;
+
+    /// Calls [`FileBuilder::with_fcpl`] in place.
+    #[deprecated(
+        note = "builders are consumed by their setters; use `builder = builder.setter(..)`"
+    )]
+    fn with_fcpl<F>(self, func: F) -> Self
+    where
+        F: FnOnce(FileCreateBuilder) -> FileCreateBuilder;
+}
+
+#[allow(deprecated)]
+impl FileBuilderMut for &mut FileBuilder {
+    fn set_access_plist(self, fapl: &FileAccess) -> Result<Self> {
+        self.fapl = FileAccessBuilder::from_plist(fapl)?;
+        Ok(self)
+    }
+
+    fn set_fapl(self, fapl: &FileAccess) -> Result<Self> {
+        FileBuilderMut::set_access_plist(self, fapl)
+    }
+
+    fn with_access_plist<F>(self, func: F) -> Self
+    where
+        F: FnOnce(FileAccessBuilder) -> FileAccessBuilder,
+    {
+        self.fapl = func(mem::take(&mut self.fapl));
+        self
+    }
+
+    fn with_fapl<F>(self, func: F) -> Self
+    where
+        F: FnOnce(FileAccessBuilder) -> FileAccessBuilder,
+    {
+        FileBuilderMut::with_access_plist(self, func)
+    }
+
+    fn close_degree(self, degree: FileCloseDegree) -> Self {
+        FileBuilderMut::with_fapl(self, |p| p.fclose_degree(degree))
+    }
+
+    fn set_create_plist(self, fcpl: &FileCreate) -> Result<Self> {
+        self.fcpl = FileCreateBuilder::from_plist(fcpl)?;
+        Ok(self)
+    }
+
+    fn set_fcpl(self, fcpl: &FileCreate) -> Result<Self> {
+        FileBuilderMut::set_create_plist(self, fcpl)
+    }
+
+    fn with_create_plist<F>(self, func: F) -> Self
+    where
+        F: FnOnce(FileCreateBuilder) -> FileCreateBuilder,
+    {
+        self.fcpl = func(mem::take(&mut self.fcpl));
+        self
+    }
+
+    fn with_fcpl<F>(self, func: F) -> Self
+    where
+        F: FnOnce(FileCreateBuilder) -> FileCreateBuilder,
+    {
+        FileBuilderMut::with_create_plist(self, func)
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
+    use crate::hl::plist::file_access::FileAccessBuilder;
+    use crate::hl::plist::file_create::{AttrCreationOrder, LinkCreationOrder};
     use crate::internal_prelude::*;
+    use std::collections::HashMap;
     use std::fs;
     use std::io::{Read, Write};
 
@@ -369,19 +922,87 @@ pub mod tests {
         });
     }
 
+    #[test]
+    pub fn test_read_only_guards() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            let ds = file.new_dataset::<i32>().shape((0..,)).chunk(4).create("ds").unwrap();
+            ds.resize(3).unwrap();
+            ds.write(&[1, 2, 3]).unwrap();
+            file.new_dataset::<i32>().create("scalar").unwrap();
+            let group = file.create_group("g").unwrap();
+            group.new_attr::<u8>().create("a").unwrap().write_scalar(&1).unwrap();
+            file.link_soft("ds", "soft").unwrap();
+            drop((ds, group, file));
+
+            let file = File::open(&path).unwrap();
+            assert!(file.is_read_only());
+            let ds = file.dataset("ds").unwrap();
+            let group = file.group("g").unwrap();
+            let attr = group.attr("a").unwrap();
+            let assert_read_only = |err: Error| {
+                assert_eq!(err.kind(), ErrorKind::ReadOnly);
+                assert!(err.to_string().contains("is opened read-only"));
+            };
+
+            assert_read_only(file.create_group("new").unwrap_err());
+            assert_read_only(group.create_group("new").unwrap_err());
+            assert_read_only(file.new_dataset::<u8>().create("new").unwrap_err());
+            assert_read_only(group.new_attr::<u8>().create("new").unwrap_err());
+            assert_read_only(ds.write(&[4, 5, 6]).unwrap_err());
+            assert_read_only(ds.write_raw(&[4, 5, 6]).unwrap_err());
+            assert_read_only(ds.write_slice(&[4], 0..1).unwrap_err());
+            assert_read_only(file.dataset("scalar").unwrap().write_scalar(&4).unwrap_err());
+            assert_read_only(attr.write_scalar(&2_u8).unwrap_err());
+            assert_read_only(ds.resize(4).unwrap_err());
+            assert_read_only(file.unlink("ds").unwrap_err());
+            assert_read_only(file.delete_recursive("g").unwrap_err());
+            assert_read_only(file.link_soft("ds", "soft2").unwrap_err());
+            assert_read_only(file.link_hard("ds", "hard").unwrap_err());
+            assert_read_only(file.link_external("other.h5", "ds", "ext").unwrap_err());
+            assert_read_only(file.relink("soft", "moved").unwrap_err());
+            assert_read_only(group.delete_attr("a").unwrap_err());
+            assert_read_only(group.clear_attrs().unwrap_err());
+
+            // reads are unaffected
+            assert_eq!(ds.read_raw::<i32>().unwrap(), vec![1, 2, 3]);
+            assert_eq!(attr.read_scalar::<u8>().unwrap(), 1);
+            assert_eq!(file.member_names().unwrap(), vec!["ds", "g", "scalar", "soft"]);
+
+            // copying out of a read-only file is fine, copying into it is not
+            let dst = File::create(path.with_extension("copy")).unwrap();
+            ds.copy_to(&dst, "ds").unwrap();
+            assert_read_only(dst.dataset("ds").unwrap().copy_to(&file, "copy").unwrap_err());
+            drop(dst);
+            fs::remove_file(path.with_extension("copy")).unwrap();
+            drop((attr, group, ds, file));
+
+            // handles from a writable file are not affected
+            let file = File::open_rw(&path).unwrap();
+            assert!(!file.is_read_only());
+            let ds = file.dataset("ds").unwrap();
+            ds.write(&[4, 5, 6]).unwrap();
+            file.group("g").unwrap().new_attr::<u8>().create("b").unwrap();
+            file.unlink("soft").unwrap();
+            assert_eq!(ds.read_raw::<i32>().unwrap(), vec![4, 5, 6]);
+        });
+    }
+
     #[test]
     pub fn test_unable_to_open() {
         with_tmp_dir(|dir| {
-            assert_err_re!(File::open(&dir), "unable to (?:synchronously )?open file");
-            assert_err_re!(File::open_rw(&dir), "unable to (?:synchronously )?open file");
-            assert_err_re!(File::create_excl(&dir), "unable to (?:synchronously )?create file");
-            assert_err_re!(File::create(&dir), "unable to (?:synchronously )?create file");
-            assert_err_re!(File::append(&dir), "unable to (?:synchronously )?create file");
+            // a directory can be opened for reading, but holds no superblock
+            let kind = File::open(&dir).unwrap_err().kind();
+            assert!(matches!(kind, ErrorKind::FileAccess | ErrorKind::NotHdf5), "{kind:?}");
+            assert_eq!(File::open_rw(&dir).unwrap_err().kind(), ErrorKind::FileAccess);
+            assert_eq!(File::create_excl(&dir).unwrap_err().kind(), ErrorKind::AlreadyExists);
+            assert_eq!(File::create(&dir).unwrap_err().kind(), ErrorKind::FileAccess);
+            assert_eq!(File::append(&dir).unwrap_err().kind(), ErrorKind::FileAccess);
         });
         with_tmp_path(|path| {
             fs::File::create(&path).unwrap().write_all(b"foo").unwrap();
             assert!(fs::metadata(&path).is_ok());
-            assert_err_re!(File::open(&path), "unable to (?:synchronously )?open file");
+            assert_eq!(File::open(&path).unwrap_err().kind(), ErrorKind::NotHdf5);
         })
     }
 
@@ -389,10 +1010,8 @@ pub mod tests {
     pub fn test_file_create() {
         with_tmp_path(|path| {
             File::create(&path).unwrap().create_group("foo").unwrap();
-            assert_err_re!(
-                File::create(&path).unwrap().group("foo"),
-                "unable to (?:synchronously )?open group"
-            );
+            let err = File::create(&path).unwrap().group("foo").unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::NotFound);
         });
     }
 
@@ -400,7 +1019,7 @@ pub mod tests {
     pub fn test_file_create_excl() {
         with_tmp_path(|path| {
             File::create_excl(&path).unwrap();
-            assert_err_re!(File::create_excl(&path), "unable to (?:synchronously )?create file");
+            assert_eq!(File::create_excl(&path).unwrap_err().kind(), ErrorKind::AlreadyExists);
         });
     }
 
@@ -418,11 +1037,8 @@ pub mod tests {
             File::create(&path).unwrap().create_group("foo").unwrap();
             let file = File::open(&path).unwrap();
             file.group("foo").unwrap();
-            assert_err_re!(
-                file.create_group("bar"),
-                "unable to (?:synchronously )?create group: no write intent on file"
-            );
-            assert_err!(File::open("/foo/bar/baz"), "unable to open file");
+            assert_eq!(file.create_group("bar").unwrap_err().kind(), ErrorKind::ReadOnly);
+            assert_eq!(File::open("/foo/bar/baz").unwrap_err().kind(), ErrorKind::NotFound);
         });
     }
 
@@ -433,7 +1049,7 @@ pub mod tests {
             let file = File::open_rw(&path).unwrap();
             file.group("foo").unwrap();
             file.create_group("bar").unwrap();
-            assert_err!(File::open_rw("/foo/bar/baz"), "unable to open file");
+            assert_eq!(File::open_rw("/foo/bar/baz").unwrap_err().kind(), ErrorKind::NotFound);
         });
     }
 
@@ -571,6 +1187,107 @@ pub mod tests {
         });
     }
 
+    #[test]
+    fn test_weak_close_across_threads() {
+        use crate::hl::plist::file_access::FileCloseDegree;
+        use std::sync::mpsc;
+        with_tmp_path(|path| {
+            let file =
+                File::with_options().close_degree(FileCloseDegree::Weak).create(&path).unwrap();
+            let ds = file.new_dataset_builder().with_data(&[1, 2, 3]).create("x").unwrap();
+            assert_eq!(file.open_object_count().unwrap(), 1);
+            let (tx, rx) = mpsc::channel::<()>();
+            let thread = std::thread::spawn(move || {
+                rx.recv().unwrap();
+                assert_eq!(ds.read_raw::<i32>().unwrap(), [1, 2, 3]);
+            });
+            // the file stays open while the dataset is alive on the other thread
+            drop(file);
+            assert!(File::create(&path).is_err());
+            tx.send(()).unwrap();
+            thread.join().unwrap();
+            // and is closed when the dataset is dropped
+            File::create(&path).unwrap();
+        })
+    }
+
+    #[test]
+    fn test_semi_try_close() {
+        use crate::hl::plist::file_access::FileCloseDegree;
+        use std::sync::mpsc;
+        with_tmp_path(|path| {
+            let file =
+                File::with_options().close_degree(FileCloseDegree::Semi).create(&path).unwrap();
+            assert_eq!(file.fapl().unwrap().fclose_degree(), FileCloseDegree::Semi);
+            let ds = file.new_dataset_builder().with_data(&[1, 2, 3]).create("x").unwrap();
+            let (tx, rx) = mpsc::channel::<()>();
+            let (done_tx, done_rx) = mpsc::channel::<()>();
+            let thread = std::thread::spawn(move || {
+                rx.recv().unwrap();
+                drop(ds);
+                done_tx.send(()).unwrap();
+            });
+            let (file, err) = file.try_close().unwrap_err();
+            assert!(err.to_string().contains("it has open objects (1)"), "{err}");
+            assert_eq!(file.open_object_count().unwrap(), 1);
+            assert!(file.is_valid());
+            tx.send(()).unwrap();
+            done_rx.recv().unwrap();
+            thread.join().unwrap();
+            assert_eq!(file.open_object_count().unwrap(), 0);
+            file.try_close().map_err(|(_, err)| err).unwrap();
+            File::create(&path).unwrap();
+        })
+    }
+
+    #[test]
+    fn test_close_all_children() {
+        use crate::hl::plist::file_access::FileCloseDegree;
+        with_tmp_path(|path| {
+            let file =
+                File::with_options().close_degree(FileCloseDegree::Semi).create(&path).unwrap();
+            assert!(file.open_children().is_empty());
+            assert_eq!(file.close_all_children().unwrap(), 0);
+            let group = file.create_group("g").unwrap();
+            let ds = group.new_dataset_builder().with_data(&[1, 2, 3]).create("x").unwrap();
+            let attr = ds.new_attr::<u8>().create("a").unwrap();
+            let ds2 = file.dataset("g/x").unwrap();
+
+            let mut children = file.open_children();
+            children.sort_by_key(|child| child.id);
+            let listed = children.iter().map(|c| (c.kind, c.name.as_deref())).collect::<Vec<_>>();
+            // `ds2` opens the dataset again, under an identifier of its own
+            assert_eq!(
+                listed,
+                [
+                    (OpenObjectKind::Group, Some("/g")),
+                    (OpenObjectKind::Dataset, Some("/g/x")),
+                    (OpenObjectKind::Dataset, Some("/g/x")),
+                    (OpenObjectKind::Attribute, Some("a")),
+                ]
+            );
+            assert_eq!(children[1].to_string(), format!("dataset '/g/x' (id {})", ds.id()));
+            assert_eq!(children[2].id, ds2.id());
+            let debug = format!("{file:?}");
+            assert!(
+                debug.starts_with("<HDF5 file: \"")
+                    && debug.contains("(read/write), open: [group '/g'"),
+                "{debug}"
+            );
+
+            assert_eq!(file.close_all_children().unwrap(), 4);
+            assert!(file.open_children().is_empty());
+            assert_eq!(file.open_object_count().unwrap(), 0);
+            assert!(!ds.is_valid() && !ds2.is_valid() && !attr.is_valid() && !group.is_valid());
+            assert!(ds.read_raw::<i32>().is_err());
+            assert!(attr.read_scalar::<u8>().is_err());
+            assert!(!format!("{file:?}").contains("open:"));
+            file.try_close().map_err(|(_, err)| err).unwrap();
+            drop((group, ds, ds2, attr));
+            File::create(&path).unwrap();
+        })
+    }
+
     #[test]
     pub fn test_close_automatic() {
         // File going out of scope should just close its own handle
@@ -600,6 +1317,269 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_create_in_memory() {
+        with_tmp_path(|path| {
+            let name = path.to_str().unwrap();
+            let file = File::create_in_memory(name).unwrap();
+            file.new_dataset::<u8>().shape(10).create("x").unwrap();
+            assert!(fs::metadata(&path).is_err());
+            file.close().unwrap();
+            assert!(fs::metadata(&path).is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_file_image() {
+        use crate::hl::plist::file_access::FileDriver;
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            file.new_dataset_builder().with_data(&[1, 2, 3]).create("x").unwrap();
+            let image = file.to_image().unwrap();
+            file.close().unwrap();
+            assert_eq!(image, fs::read(&path).unwrap());
+
+            let file = File::from_image("image", &image).unwrap();
+            assert_eq!(file.dataset("x").unwrap().read_raw::<i32>().unwrap(), [1, 2, 3]);
+            assert!(file.is_read_only());
+            let fapl = file.fapl().unwrap();
+            assert!(matches!(fapl.driver(), FileDriver::Core(d) if !d.filebacked));
+            assert!(format!("{fapl:?}").contains("initial_image_size"));
+            file.close().unwrap();
+            assert!(fs::metadata("image").is_err());
+
+            // modifications only affect the copy of the image
+            let file =
+                File::with_options().with_fapl(|p| p.core_image(&image)).open_rw("image").unwrap();
+            file.dataset("x").unwrap().write(&[4, 5, 6]).unwrap();
+            file.close().unwrap();
+            let file = File::from_image("image", &image).unwrap();
+            assert_eq!(file.dataset("x").unwrap().read_raw::<i32>().unwrap(), [1, 2, 3]);
+
+            assert!(File::from_image("invalid", b"not an hdf5 file").is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_repack() {
+        use crate::plist::file_create::LinkCreationOrder;
+        use hdf5_types::VarLenUnicode;
+        use ndarray::Array1;
+
+        with_tmp_dir(|dir| {
+            let (src_path, dst_path) = (dir.join("src.h5"), dir.join("dst.h5"));
+            let file = File::with_options()
+                .with_fcpl(|p| p.link_creation_order(LinkCreationOrder::TRACKED))
+                .create(&src_path)
+                .unwrap();
+            file.new_attr::<VarLenUnicode>()
+                .create("title")
+                .unwrap()
+                .write_scalar(&"repack".parse::<VarLenUnicode>().unwrap())
+                .unwrap();
+            // 100 MB of data in 10 groups, half of which get deleted
+            let data = Array1::from_shape_fn(1_250_000, |i| i as f64);
+            for i in (0..10).rev() {
+                let group = file.create_group(&format!("g{i}")).unwrap();
+                group.create_group("nested").unwrap();
+                let ds =
+                    group.new_dataset_builder().with_data(&data).create("nested/data").unwrap();
+                ds.new_attr::<i32>().create("index").unwrap().write_scalar(&i).unwrap();
+            }
+            file.new_dataset_builder()
+                .chunk(1000)
+                .shuffle()
+                .with_data(&Array1::from_elem(10_000, 3_u16))
+                .create("filtered")
+                .unwrap();
+            file.link_hard("g9", "g9_alias").unwrap();
+            file.link_soft("/g9/nested", "soft").unwrap();
+            file.link_soft("/missing", "dangling").unwrap();
+            file.link_external("other.h5", "/x", "ext").unwrap();
+            for i in (0..10).step_by(2) {
+                file.delete_recursive(&format!("g{i}")).unwrap();
+            }
+            file.flush().unwrap();
+            let src_size = fs::metadata(&src_path).unwrap().len();
+
+            let dst = file.repack_into(&dst_path).unwrap();
+            dst.close().unwrap();
+            let dst_size = fs::metadata(&dst_path).unwrap().len();
+            assert!(src_size > 95_000_000, "{src_size}");
+            assert!(dst_size > 49_000_000 && dst_size < 51_000_000, "{dst_size}");
+            assert_err!(file.repack_into(&dst_path), "unable to");
+
+            let dst = File::open(&dst_path).unwrap();
+            let names = dst
+                .iter_visit(
+                    IterationOrder::Increasing,
+                    TraversalOrder::Creation,
+                    vec![],
+                    |_, n, _, v| {
+                        v.push(n.to_owned());
+                        true
+                    },
+                )
+                .unwrap();
+            let expected =
+                ["g9", "g7", "g5", "g3", "g1", "filtered", "g9_alias", "soft", "dangling", "ext"];
+            assert_eq!(names, expected);
+            assert_eq!(
+                dst.attr("title").unwrap().read_scalar::<VarLenUnicode>().unwrap().as_str(),
+                "repack"
+            );
+            for i in (1..10).step_by(2) {
+                let ds = dst.dataset(&format!("g{i}/nested/data")).unwrap();
+                assert_eq!(ds.read_1d::<f64>().unwrap(), data);
+                assert_eq!(ds.attr("index").unwrap().read_scalar::<i32>().unwrap(), i);
+            }
+            let filtered = dst.dataset("filtered").unwrap();
+            assert_eq!(filtered.filters(), file.dataset("filtered").unwrap().filters());
+            assert_eq!(filtered.read_1d::<u16>().unwrap(), Array1::from_elem(10_000, 3_u16));
+            assert_eq!(
+                dst.loc_info_by_name("g9").unwrap().token,
+                dst.loc_info_by_name("g9_alias").unwrap().token
+            );
+            assert_eq!(dst.link_info("soft").unwrap().link_type, LinkType::Soft);
+            assert_eq!(dst.link_info("dangling").unwrap().link_type, LinkType::Soft);
+            assert_eq!(dst.link_info("ext").unwrap().link_type, LinkType::External);
+            dst.dataset("soft/data").unwrap();
+            drop(dst);
+
+            let options = RepackOptions { expand_soft_links: true, ..RepackOptions::default() };
+            let expanded = file.repack_into_with(dir.join("expanded.h5"), &options).unwrap();
+            assert_eq!(expanded.link_info("soft").unwrap().link_type, LinkType::Hard);
+            assert_eq!(expanded.link_info("dangling").unwrap().link_type, LinkType::Soft);
+            assert_eq!(expanded.dataset("soft/data").unwrap().read_1d::<f64>().unwrap(), data);
+        })
+    }
+
+    #[test]
+    pub fn test_repack_shared_objects() {
+        use ndarray::Array1;
+
+        with_tmp_dir(|dir| {
+            let (src_path, dst_path) = (dir.join("src.h5"), dir.join("dst.h5"));
+            let file = File::create(&src_path).unwrap();
+            // 8 MB dataset reachable from two different groups
+            let data = Array1::from_shape_fn(1_000_000, |i| i as f64);
+            file.new_dataset_builder().with_data(&data).create("a/x").unwrap();
+            file.create_group("b").unwrap().link_hard("/a/x", "y").unwrap();
+            file.create_group("b/c").unwrap().link_hard("/a", "a_alias").unwrap();
+
+            let dst = file.repack_into(&dst_path).unwrap();
+            dst.close().unwrap();
+            let dst_size = fs::metadata(&dst_path).unwrap().len();
+            assert!(dst_size > 8_000_000 && dst_size < 9_000_000, "{dst_size}");
+
+            let dst = File::open_rw(&dst_path).unwrap();
+            let token = |path: &str| dst.loc_info_by_name(path).unwrap().token;
+            assert_eq!(token("a/x"), token("b/y"));
+            assert_eq!(token("a"), token("b/c/a_alias"));
+            assert_eq!(token("b/c/a_alias/x"), token("b/y"));
+            assert_eq!(dst.loc_info_by_name("a/x").unwrap().num_links, 2);
+            dst.dataset("a/x").unwrap().write_slice(&[-1.0, -2.0], 10..12).unwrap();
+            let y = dst.dataset("b/y").unwrap().read_1d::<f64>().unwrap();
+            assert_eq!(y.slice(ndarray::s![9..13]).to_vec(), [9.0, -1.0, -2.0, 12.0]);
+        })
+    }
+
+    #[test]
+    pub fn test_repack_committed_datatype() {
+        use crate::sys::h5d::H5Dcreate2;
+        use crate::sys::h5t::H5Tcommit2;
+
+        with_tmp_dir(|dir| {
+            let file = File::create(dir.join("src.h5")).unwrap();
+            file.create_group("g").unwrap();
+            let dtype = Datatype::from_type::<f64>().unwrap();
+            let name = to_cstring("t").unwrap();
+            let default = H5P_DEFAULT;
+            h5call!(H5Tcommit2(file.id(), name.as_ptr(), dtype.id(), default, default, default))
+                .unwrap();
+            let space = Dataspace::try_new(4).unwrap();
+            // by name, "a" and "g" come before "t", which has to be copied first to be shared
+            for path in ["a", "g/b"] {
+                let path = to_cstring(path).unwrap();
+                let id = h5call!(H5Dcreate2(
+                    file.id(),
+                    path.as_ptr(),
+                    dtype.id(),
+                    space.id(),
+                    default,
+                    default,
+                    default
+                ))
+                .unwrap();
+                Dataset::from_id(id).unwrap();
+            }
+
+            let dst = file.repack_into(dir.join("dst.h5")).unwrap();
+            let token = |path: &str| {
+                let dtype = dst.dataset(path).unwrap().dtype().unwrap();
+                dtype.cast::<Location>().unwrap().loc_info().unwrap().token
+            };
+            let shared = dst.loc_info_by_name("t").unwrap().token;
+            assert_eq!(token("a"), shared);
+            assert_eq!(token("g/b"), shared);
+        })
+    }
+
+    #[test]
+    pub fn test_repack_reproducible() {
+        with_tmp_dir(|dir| {
+            let file = FileBuilder::new()
+                .with_fcpl(|p| {
+                    p.link_creation_order(LinkCreationOrder::TRACKED)
+                        .attr_creation_order(AttrCreationOrder::TRACKED)
+                })
+                .create(dir.join("src.h5"))
+                .unwrap();
+            // insertion order depends on the hasher's random state
+            let items: HashMap<String, i32> = (0..20).map(|i| (format!("ds{i:02}"), i)).collect();
+            for (name, &value) in &items {
+                let ds = file.new_dataset_builder().with_data(&[value; 16]).create(name.as_str());
+                ds.unwrap()
+                    .new_attr::<i32>()
+                    .create("value")
+                    .unwrap()
+                    .write_scalar(&value)
+                    .unwrap();
+                file.new_attr::<i32>().create(name.as_str()).unwrap().write_scalar(&value).unwrap();
+            }
+
+            for order in [DeterministicOrder::Creation, DeterministicOrder::Name] {
+                let options = RepackOptions { order, ..RepackOptions::default() };
+                let paths =
+                    [dir.join(format!("{order:?}1.h5")), dir.join(format!("{order:?}2.h5"))];
+                for path in &paths {
+                    file.repack_into_with(path, &options).unwrap().close().unwrap();
+                }
+                assert_eq!(fs::read(&paths[0]).unwrap(), fs::read(&paths[1]).unwrap());
+
+                let dst = File::open(&paths[0]).unwrap();
+                let names = dst
+                    .iter_visit(
+                        IterationOrder::Increasing,
+                        TraversalOrder::Creation,
+                        vec![],
+                        |_, n, _, v| {
+                            v.push(n.to_owned());
+                            true
+                        },
+                    )
+                    .unwrap();
+                let mut sorted = names.clone();
+                sorted.sort();
+                assert_eq!(names == sorted, order == DeterministicOrder::Name);
+                let attrs = (0..names.len())
+                    .map(|i| dst.attr_by_index(i, IterationOrder::Increasing).unwrap().name())
+                    .collect::<Vec<_>>();
+                assert_eq!(attrs, names);
+            }
+        })
+    }
+
     #[test]
     pub fn test_core_fd_filebacked() {
         with_tmp_path(|path| {
@@ -613,6 +1593,101 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_core_fd_filebacked_write_tracking() {
+        with_tmp_path(|path| {
+            let fapl = |p: FileAccessBuilder| p.core_options(64 * 1024, true).write_tracking(4096);
+            let file = FileBuilder::new().with_fapl(fapl).create(&path).unwrap();
+            let ds = file.new_dataset::<u32>().shape(100_000).create("data").unwrap();
+            ds.write_raw(&(0..100_000).collect::<Vec<u32>>()).unwrap();
+            file.close().unwrap();
+            assert!(fs::metadata(&path).is_ok());
+
+            // only the modified pages are written back, which must still persist the change
+            let file = FileBuilder::new().with_fapl(fapl).open_rw(&path).unwrap();
+            file.dataset("data").unwrap().write_slice(&[7_u32; 10], 50_000..50_010).unwrap();
+            file.close().unwrap();
+            let data =
+                File::open(&path).unwrap().dataset("data").unwrap().read_raw::<u32>().unwrap();
+            assert_eq!(data.len(), 100_000);
+            assert_eq!(data[49_999..50_011], [49_999, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 50_010]);
+            assert_eq!(data[99_999], 99_999);
+        })
+    }
+
+    #[test]
+    pub fn test_in_memory_with_capacity() {
+        use ndarray::{s, Array2};
+        // 256 MiB written in blocks of rows, growing the image by 1 MiB or by 64 MiB
+        const MIB: usize = 1024 * 1024;
+        const ROWS: usize = 256;
+        const BLOCK: usize = 16;
+        let block = |i: usize| {
+            let data = (0..BLOCK * MIB).map(|j| ((i * 31 + j * 7) % 251) as u8).collect();
+            Array2::from_shape_vec((BLOCK, MIB), data).unwrap()
+        };
+        let files = [(MIB, "small"), (64 * MIB, "large")].map(|(capacity, name)| {
+            let name = format!("capacity_{name}.h5");
+            let file = File::in_memory_with_capacity(&name, capacity).unwrap();
+            let ds = file.new_dataset::<u8>().shape((ROWS, MIB)).create("data").unwrap();
+            for i in 0..ROWS / BLOCK {
+                ds.write_slice(&block(i), s![i * BLOCK..(i + 1) * BLOCK, ..]).unwrap();
+            }
+            file
+        });
+        let [small, large] = files.map(|file| file.dataset("data").unwrap());
+        for i in 0..ROWS / BLOCK {
+            let rows = s![i * BLOCK..(i + 1) * BLOCK, ..];
+            let expected = block(i);
+            assert_eq!(small.read_slice_2d::<u8, _>(rows).unwrap(), expected);
+            assert_eq!(large.read_slice_2d::<u8, _>(rows).unwrap(), expected);
+        }
+        assert_err!(File::in_memory_with_capacity("zero.h5", 0), "increment must be positive");
+    }
+
+    #[test]
+    pub fn test_non_ascii_paths() {
+        with_tmp_dir(|dir| {
+            let path = dir.join("données-データ.h5");
+            File::create(&path).unwrap().create_group("g").unwrap();
+            File::open(&path).unwrap().group("g").unwrap();
+            let file = File::create(dir.join("ссылки.h5")).unwrap();
+            file.link_external(&path, "g", "ext").unwrap();
+            file.group("ext").unwrap();
+
+            // external storage
+            let raw_data = dir.join("外部-データ.bin");
+            let ds = file.new_dataset::<i32>().shape(4).external(&raw_data, 0, 16);
+            let ds = ds.create("external").unwrap();
+            ds.write(&[1, 2, 3, 4]).unwrap();
+            file.flush().unwrap();
+            assert_eq!(fs::metadata(&raw_data).unwrap().len(), 16);
+            assert_eq!(ds.dcpl().unwrap().external()[0].name, raw_data);
+            assert_eq!(ds.read_raw::<i32>().unwrap(), [1, 2, 3, 4]);
+
+            // on unix, file names are bytes that need not be UTF-8
+            #[cfg(unix)]
+            {
+                use std::ffi::OsStr;
+                use std::os::unix::ffi::OsStrExt;
+                let raw = dir.join(OsStr::from_bytes(b"raw-\xff\xfe.h5"));
+                assert!(raw.to_str().is_none());
+                File::create(&raw).unwrap().create_group("g").unwrap();
+                File::open(&raw).unwrap().group("g").unwrap();
+                file.link_external(&raw, "g", "raw").unwrap();
+                file.group("raw").unwrap();
+                let raw_data = dir.join(OsStr::from_bytes(b"raw-\xff\xfe.bin"));
+                let ds = file.new_dataset::<u8>().shape(2).external(&raw_data, 0, 2);
+                ds.create("raw_external").unwrap().write(&[5, 6]).unwrap();
+                file.flush().unwrap();
+                assert_eq!(fs::read(&raw_data).unwrap(), [5, 6]);
+                fs::remove_file(&raw).unwrap();
+                assert!(File::open(&raw).is_err());
+            }
+            assert_err!(File::create(dir.join("nul\0.h5")), "null byte in path");
+        })
+    }
+
     #[test]
     pub fn test_core_fd_existing_file() {
         with_tmp_path(|path| {
@@ -647,6 +1722,68 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_reopen() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            file.new_dataset_builder().with_data(&[1, 2, 3]).create("x").unwrap();
+            let other = file.reopen().unwrap();
+            assert_ne!(other.id(), file.id());
+            assert!(!other.is_read_only());
+            assert_eq!(other.dataset("x").unwrap().read_raw::<i32>().unwrap(), [1, 2, 3]);
+            other.new_dataset_builder().with_data(&[4]).create("y").unwrap();
+            assert_eq!(file.dataset("y").unwrap().read_raw::<i32>().unwrap(), [4]);
+            drop(file);
+            assert_eq!(other.dataset("x").unwrap().read_raw::<i32>().unwrap(), [1, 2, 3]);
+            let third = other.reopen().unwrap();
+            drop(third);
+            other.close().unwrap();
+
+            let file = File::open(&path).unwrap();
+            let other = file.reopen().unwrap();
+            assert!(other.is_read_only());
+            drop(other);
+            assert_eq!(file.dataset("y").unwrap().read_raw::<i32>().unwrap(), [4]);
+        })
+    }
+
+    #[test]
+    pub fn test_open_multi() {
+        with_tmp_path(|path| {
+            let data = (0..1000).collect::<Vec<i64>>();
+            File::create(&path)
+                .unwrap()
+                .new_dataset_builder()
+                .with_data(&data)
+                .create("x")
+                .unwrap();
+            assert!(File::open_multi(&path, 0).unwrap().is_empty());
+            let files = File::open_multi(&path, 4).unwrap();
+            assert!(files.iter().all(|file| file.is_read_only()));
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = files
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, file)| {
+                        let data = &data;
+                        scope.spawn(move || {
+                            let ds = file.dataset("x").unwrap();
+                            for _ in 0..10 {
+                                assert_eq!(&ds.read_raw::<i64>().unwrap(), data);
+                                let slice = ds.read_slice_1d::<i64, _>(i * 100..i * 100 + 10);
+                                assert_eq!(slice.unwrap().to_vec(), data[i * 100..i * 100 + 10]);
+                            }
+                        })
+                    })
+                    .collect();
+                for handle in handles {
+                    handle.join().unwrap();
+                }
+            });
+            assert!(File::open_multi(path.join("missing"), 2).is_err());
+        })
+    }
+
     #[test]
     pub fn test_debug() {
         with_tmp_dir(|dir| {
@@ -0,0 +1,490 @@
+//! Opening files from in-memory images without copying them.
+//!
+//! [`File::from_image`] copies the image into the library. The functions here instead install
+//! file image callbacks (`H5Pset_file_image_callbacks`) which hand the caller's buffer to the
+//! Core driver as is: the library never copies, reallocates or frees it, and files are always
+//! opened read-only so that it is never written to either.
+//!
+//! The callbacks share an [`ImageState`] with the library, reference counted through the
+//! `udata_copy`/`udata_free` callbacks, plus one reference held by the opening side until the
+//! driver closes the file (and one held by a [`BorrowedImageFile`] until it is dropped).
+//!
+//! A borrowed image cannot outlive its borrow, so if handles or objects opened from a
+//! [`BorrowedImageFile`] are still open when it is dropped, the driver is pointed at a copy of
+//! the image (through `H5Fget_vfd_handle`, which for the Core driver returns its pointer to
+//! the image), which it frees when the file is closed.
+
+use std::any::Any;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
+use std::ops::Deref;
+use std::ptr::{self, NonNull};
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::hl::plist::file_access::{FileAccess, FileCloseDegree};
+use crate::internal_prelude::*;
+use crate::sys::h5f::{
+    H5Fclose, H5Fget_obj_count, H5Fget_vfd_handle, H5Fopen, H5F_ACC_RDONLY, H5F_OBJ_ALL,
+};
+use crate::sys::h5fd::{H5FD_file_image_callbacks_t, H5FD_file_image_op_t};
+use crate::sys::h5i::{H5Idec_ref, H5Iget_ref, H5Iis_valid};
+use crate::sys::h5p::{H5Pset_file_image, H5Pset_file_image_callbacks, H5P_DEFAULT};
+
+use H5FD_file_image_op_t::{
+    H5FD_FILE_IMAGE_OP_FILE_CLOSE, H5FD_FILE_IMAGE_OP_FILE_OPEN,
+    H5FD_FILE_IMAGE_OP_PROPERTY_LIST_CLOSE, H5FD_FILE_IMAGE_OP_PROPERTY_LIST_COPY,
+    H5FD_FILE_IMAGE_OP_PROPERTY_LIST_GET, H5FD_FILE_IMAGE_OP_PROPERTY_LIST_SET,
+};
+
+/// Counter making the names of files opened from images unique.
+static IMAGE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// State of an image shared with the library through the callbacks' `udata`.
+struct ImageState {
+    ptr: *const u8,
+    len: usize,
+    /// Keeps a shared image alive; `None` for borrowed images.
+    _owner: Option<Box<dyn Any + Send + Sync>>,
+    /// References held by the library and by the opening side.
+    refs: AtomicUsize,
+    /// Whether the opening side still holds its reference.
+    opener: AtomicBool,
+    /// Number of files the driver currently has open on the image.
+    open_files: AtomicUsize,
+    /// Set once a borrowed image is given back; the library must no longer access it.
+    released: AtomicBool,
+    /// Copy of a borrowed image the driver reads from after the borrow ended, or null.
+    detached: AtomicPtr<u8>,
+}
+
+impl ImageState {
+    fn new(
+        image: *const u8,
+        len: usize,
+        owner: Option<Box<dyn Any + Send + Sync>>,
+        refs: usize,
+    ) -> NonNull<Self> {
+        let state = Box::new(Self {
+            ptr: image,
+            len,
+            _owner: owner,
+            refs: AtomicUsize::new(refs),
+            opener: AtomicBool::new(true),
+            open_files: AtomicUsize::new(0),
+            released: AtomicBool::new(false),
+            detached: AtomicPtr::new(ptr::null_mut()),
+        });
+        NonNull::from(Box::leak(state))
+    }
+
+    /// Drops a reference, freeing the state with the last one.
+    unsafe fn release(state: *const Self) {
+        if (*state).refs.fetch_sub(1, Ordering::AcqRel) == 1 {
+            drop(Box::from_raw(state.cast_mut()));
+        }
+    }
+
+    /// Drops the reference of the opening side, if still held.
+    unsafe fn release_opener(state: *const Self) {
+        if (*state).opener.swap(false, Ordering::AcqRel) {
+            Self::release(state);
+        }
+    }
+
+    fn is_image(&self, ptr: *const c_void, size: size_t) -> bool {
+        ptr.cast() == self.ptr && size == self.len
+    }
+}
+
+unsafe extern "C" fn image_malloc(
+    size: size_t,
+    op: H5FD_file_image_op_t,
+    udata: *mut c_void,
+) -> *mut c_void {
+    let state = &*udata.cast::<ImageState>();
+    if state.released.load(Ordering::Acquire) || size != state.len {
+        return ptr::null_mut();
+    }
+    match op {
+        H5FD_FILE_IMAGE_OP_PROPERTY_LIST_SET | H5FD_FILE_IMAGE_OP_PROPERTY_LIST_COPY => {
+            state.ptr.cast_mut().cast()
+        }
+        H5FD_FILE_IMAGE_OP_FILE_OPEN => {
+            state.open_files.fetch_add(1, Ordering::AcqRel);
+            state.ptr.cast_mut().cast()
+        }
+        // the caller of H5Pget_file_image owns (and frees) what it gets, so it gets a copy
+        H5FD_FILE_IMAGE_OP_PROPERTY_LIST_GET => libc::malloc(size),
+        _ => ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn image_memcpy(
+    dest: *mut c_void,
+    src: *const c_void,
+    size: size_t,
+    op: H5FD_file_image_op_t,
+    udata: *mut c_void,
+) -> *mut c_void {
+    let state = &*udata.cast::<ImageState>();
+    if state.released.load(Ordering::Acquire) || !state.is_image(src, size) {
+        return ptr::null_mut();
+    }
+    match op {
+        H5FD_FILE_IMAGE_OP_PROPERTY_LIST_SET
+        | H5FD_FILE_IMAGE_OP_PROPERTY_LIST_COPY
+        | H5FD_FILE_IMAGE_OP_FILE_OPEN
+            if state.is_image(dest, size) =>
+        {
+            dest
+        }
+        H5FD_FILE_IMAGE_OP_PROPERTY_LIST_GET if !dest.is_null() => {
+            ptr::copy_nonoverlapping(state.ptr, dest.cast(), size);
+            dest
+        }
+        _ => ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn image_realloc(
+    _ptr: *mut c_void,
+    _size: size_t,
+    _op: H5FD_file_image_op_t,
+    _udata: *mut c_void,
+) -> *mut c_void {
+    // files are opened read-only, the image never grows
+    ptr::null_mut()
+}
+
+unsafe extern "C" fn image_free(
+    ptr: *mut c_void,
+    op: H5FD_file_image_op_t,
+    udata: *mut c_void,
+) -> herr_t {
+    let state = udata.cast::<ImageState>().cast_const();
+    let detached = (*state).detached.load(Ordering::Acquire);
+    let is_copy = !detached.is_null() && ptr.cast() == detached;
+    if !is_copy && !(*state).is_image(ptr, (*state).len) {
+        return -1;
+    }
+    match op {
+        H5FD_FILE_IMAGE_OP_PROPERTY_LIST_CLOSE if !is_copy => 0,
+        H5FD_FILE_IMAGE_OP_FILE_CLOSE => {
+            if is_copy {
+                (*state).detached.store(ptr::null_mut(), Ordering::Release);
+                drop(Box::from_raw(ptr::slice_from_raw_parts_mut(detached, (*state).len)));
+            }
+            if (*state).open_files.fetch_sub(1, Ordering::AcqRel) == 1 {
+                ImageState::release_opener(state);
+            }
+            0
+        }
+        _ => -1,
+    }
+}
+
+unsafe extern "C" fn udata_copy(udata: *mut c_void) -> *mut c_void {
+    (*udata.cast::<ImageState>()).refs.fetch_add(1, Ordering::AcqRel);
+    udata
+}
+
+unsafe extern "C" fn udata_free(udata: *mut c_void) -> herr_t {
+    ImageState::release(udata.cast::<ImageState>().cast_const());
+    0
+}
+
+/// Opens the image described by `state` read-only with the Core driver.
+///
+/// On failure, the reference of the opening side is released.
+fn open_image(state: NonNull<ImageState>, degree: FileCloseDegree) -> Result<File> {
+    let name = format!("<file image {}>", IMAGE_COUNTER.fetch_add(1, Ordering::Relaxed));
+    let fapl = match FileAccess::build().core_filebacked(false).fclose_degree(degree).finish() {
+        Ok(fapl) => fapl,
+        Err(err) => {
+            unsafe { ImageState::release_opener(state.as_ptr()) };
+            return Err(err).context("opening file image");
+        }
+    };
+    let result = h5lock!({
+        let mut callbacks = H5FD_file_image_callbacks_t {
+            image_malloc: Some(image_malloc),
+            image_memcpy: Some(image_memcpy),
+            image_realloc: Some(image_realloc),
+            image_free: Some(image_free),
+            udata_copy: Some(udata_copy),
+            udata_free: Some(udata_free),
+            udata: state.as_ptr().cast(),
+        };
+        // the callbacks have to be set before the image
+        h5try!(H5Pset_file_image_callbacks(fapl.id(), &mut callbacks));
+        let image = unsafe { state.as_ref() };
+        h5try!(H5Pset_file_image(fapl.id(), image.ptr.cast_mut().cast(), image.len));
+        let name = to_cstring(name.as_str())?;
+        File::from_id(h5try!(H5Fopen(name.as_ptr(), H5F_ACC_RDONLY, fapl.id())))
+    });
+    if result.is_err() {
+        // the property list (still open here) references the state if the callbacks were
+        // set, and the opening side does otherwise, so the state is alive
+        unsafe { ImageState::release_opener(state.as_ptr()) };
+    }
+    result.context("opening file image")
+}
+
+/// A read-only file opened from a borrowed image with [`File::from_image_borrowed`].
+///
+/// Dereferences to the [`File`]. Handles cloned out of it and objects opened in it keep
+/// working after it is dropped: in that case the image is copied when it is dropped, and the
+/// copy is freed once they are all closed. Use [`close`](Self::close) to make sure that
+/// nothing is left open instead.
+pub struct BorrowedImageFile<'a> {
+    file: ManuallyDrop<File>,
+    state: NonNull<ImageState>,
+    image: PhantomData<&'a [u8]>,
+}
+
+// the state is only accessed through atomics, and the image is a shared borrow
+unsafe impl Send for BorrowedImageFile<'_> {}
+unsafe impl Sync for BorrowedImageFile<'_> {}
+
+impl Deref for BorrowedImageFile<'_> {
+    type Target = File;
+
+    fn deref(&self) -> &File {
+        &self.file
+    }
+}
+
+impl fmt::Debug for BorrowedImageFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&*self.file, f)
+    }
+}
+
+impl BorrowedImageFile<'_> {
+    /// Closes the file, or returns it along with an error if handles cloned out of it or
+    /// objects opened in it are still open.
+    ///
+    /// On success, the library no longer references the image. On failure nothing is closed,
+    /// so the open handles stay valid.
+    pub fn close(mut self) -> Result<(), (Self, Error)> {
+        let closed = h5lock!({
+            let check = || -> Result<()> {
+                let count = self.open_elsewhere()?;
+                ensure!(
+                    count == 0,
+                    "cannot close file image '{}': {} handles or objects opened from it are \
+                     still open",
+                    self.filename(),
+                    count
+                );
+                h5call!(H5Fclose(self.file.id())).map(|_| ())
+            };
+            check()
+        });
+        if let Err(err) = closed {
+            return Err((self, err));
+        }
+        // the identifier was released by H5Fclose
+        self.release_image();
+        mem::forget(self);
+        Ok(())
+    }
+
+    /// Returns the number of references to the file and its objects besides `self`.
+    fn open_elsewhere(&self) -> Result<usize> {
+        let id = self.file.id();
+        let refs = h5call!(H5Iget_ref(id))? as usize;
+        // the identifiers include this one
+        let ids = h5call!(H5Fget_obj_count(id, H5F_OBJ_ALL))? as usize;
+        Ok(refs.saturating_sub(1) + ids.saturating_sub(1))
+    }
+
+    /// Points the driver at a copy of the image, so that the file can outlive the borrow.
+    fn detach(&self) -> Result<()> {
+        let state = unsafe { self.state.as_ref() };
+        let mut handle: *mut c_void = ptr::null_mut();
+        h5call!(H5Fget_vfd_handle(self.file.id(), H5P_DEFAULT, &mut handle))?;
+        let mem = handle.cast::<*mut c_void>();
+        ensure!(
+            !mem.is_null() && unsafe { *mem }.cast_const().cast() == state.ptr,
+            "the file image driver does not reference the image"
+        );
+        let copy = unsafe { std::slice::from_raw_parts(state.ptr, state.len) };
+        let copy = Box::into_raw(copy.to_vec().into_boxed_slice()).cast::<u8>();
+        state.detached.store(copy, Ordering::Release);
+        unsafe { *mem = copy.cast() };
+        Ok(())
+    }
+
+    /// Gives the borrowed image back and drops the reference held by `self`.
+    fn release_image(&mut self) {
+        // the reference held by `self` keeps the state alive until the end
+        let state = unsafe { self.state.as_ref() };
+        state.released.store(true, Ordering::Release);
+        unsafe { ImageState::release(self.state.as_ptr()) };
+    }
+}
+
+impl Drop for BorrowedImageFile<'_> {
+    fn drop(&mut self) {
+        h5lock!({
+            // if nothing else is open, closing the file stops the driver from using the image
+            if !matches!(self.open_elsewhere(), Ok(0)) && self.detach().is_err() {
+                // the driver cannot be moved off the image, so as a last resort close every
+                // identifier referring to the file, wherever it has been cloned to
+                for id in self.file.get_obj_ids(H5F_OBJ_ALL) {
+                    while id != self.file.id() && H5Iis_valid(id) > 0 && H5Idec_ref(id) > 0 {}
+                }
+                while H5Iget_ref(self.file.id()) > 1 {
+                    H5Idec_ref(self.file.id());
+                }
+            }
+            unsafe { ManuallyDrop::drop(&mut self.file) };
+        });
+        self.release_image();
+    }
+}
+
+impl File {
+    /// Opens a read-only file directly from `image`, without copying it.
+    ///
+    /// Unlike [`from_image`](Self::from_image), the library reads from the caller's buffer:
+    /// the file image callbacks prevent it from copying, reallocating, writing or freeing the
+    /// buffer. The buffer must therefore stay alive and unmodified while the file is open,
+    /// which the returned [`BorrowedImageFile`] enforces by borrowing it: the buffer cannot be
+    /// dropped or mutated before the file is dropped. Handles cloned out of the file and
+    /// objects opened in it may outlive it, at the cost of copying the image when it is
+    /// dropped; [`BorrowedImageFile::close`] fails instead. Property lists obtained from the
+    /// file fail to return the image afterwards.
+    ///
+    /// ```compile_fail
+    /// # use hdf5_rt as hdf5;
+    /// let image = vec![0_u8; 1024];
+    /// let file = hdf5::File::from_image_borrowed(&image).unwrap();
+    /// drop(image); // error: `image` is still borrowed by `file`
+    /// drop(file);
+    /// ```
+    ///
+    /// Use [`from_image_shared`](Self::from_image_shared) to open a file that is not tied to
+    /// a borrow.
+    pub fn from_image_borrowed(image: &[u8]) -> Result<BorrowedImageFile<'_>> {
+        ensure!(!image.is_empty(), "file image is empty");
+        // one reference for the opening side and one for the returned file
+        let state = ImageState::new(image.as_ptr(), image.len(), None, 2);
+        match open_image(state, FileCloseDegree::Weak) {
+            Ok(file) => {
+                Ok(BorrowedImageFile { file: ManuallyDrop::new(file), state, image: PhantomData })
+            }
+            Err(err) => {
+                unsafe { ImageState::release(state.as_ptr()) };
+                Err(err)
+            }
+        }
+    }
+
+    /// Opens a read-only file directly from a shared image, without copying it.
+    ///
+    /// As with [`from_image_borrowed`](Self::from_image_borrowed), the library reads from the
+    /// buffer itself. The `Arc` is kept alive until the library no longer references the
+    /// image, i.e. until the file and all objects and property lists obtained from it are
+    /// closed, and is released afterwards. The buffer must not be modified in the meantime
+    /// (e.g. through interior mutability of `T`).
+    pub fn from_image_shared<T>(image: Arc<T>) -> Result<Self>
+    where
+        T: AsRef<[u8]> + ?Sized + Send + Sync + 'static,
+    {
+        // the contents of the `Arc` stay in place when it is moved into the state
+        let (ptr, len) = ((*image).as_ref().as_ptr(), (*image).as_ref().len());
+        ensure!(len != 0, "file image is empty");
+        let state = ImageState::new(ptr, len, Some(Box::new(image)), 1);
+        open_image(state, FileCloseDegree::Weak)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::sync::Arc;
+
+    use crate::internal_prelude::*;
+
+    fn make_image() -> Vec<u8> {
+        let file = File::create_in_memory("image source").unwrap();
+        file.new_dataset_builder().with_data(&[1, 2, 3]).create("x").unwrap();
+        file.create_group("g")
+            .unwrap()
+            .new_dataset_builder()
+            .with_data(&[4.5])
+            .create("y")
+            .unwrap();
+        let image = file.to_image().unwrap();
+        file.close().unwrap();
+        image
+    }
+
+    #[test]
+    pub fn test_from_image_borrowed() {
+        let image = make_image();
+        let file = File::from_image_borrowed(&image).unwrap();
+        assert!(file.is_read_only());
+        assert_eq!(file.dataset("x").unwrap().read_raw::<i32>().unwrap(), [1, 2, 3]);
+        assert_eq!(file.dataset("g/y").unwrap().read_raw::<f64>().unwrap(), [4.5]);
+        assert_err!(file.new_dataset::<u8>().create("z"), "opened read-only");
+
+        // closing fails while handles cloned out of the file are open, leaving them valid
+        let escaped = (*file).clone();
+        let ds = file.dataset("x").unwrap();
+        let (file, err) = file.close().unwrap_err();
+        assert!(err.to_string().contains("2 handles or objects opened from it are still open"));
+        assert!(escaped.is_valid() && ds.is_valid());
+        drop(escaped);
+        drop(ds);
+        file.close().unwrap();
+
+        // handles outliving the file read from a copy of the image
+        let file = File::from_image_borrowed(&image).unwrap();
+        let escaped = (*file).clone();
+        let ds = file.dataset("x").unwrap();
+        drop(file);
+        drop(image);
+        assert!(escaped.is_valid() && ds.is_valid());
+        assert_eq!(ds.read_raw::<i32>().unwrap(), [1, 2, 3]);
+        assert_eq!(escaped.dataset("g/y").unwrap().read_raw::<f64>().unwrap(), [4.5]);
+        drop(ds);
+        drop(escaped);
+
+        // the buffer is reusable once the file is dropped
+        let image = make_image();
+        for _ in 0..3 {
+            let file = File::from_image_borrowed(&image).unwrap();
+            assert_eq!(file.dataset("x").unwrap().read_raw::<i32>().unwrap(), [1, 2, 3]);
+        }
+        assert!(File::from_image_borrowed(b"not an hdf5 file").is_err());
+        assert_err!(File::from_image_borrowed(&[]), "file image is empty");
+    }
+
+    #[test]
+    pub fn test_from_image_shared() {
+        let image: Arc<[u8]> = make_image().into();
+        let file = File::from_image_shared(Arc::clone(&image)).unwrap();
+        assert!(Arc::strong_count(&image) > 1);
+        let ds = file.dataset("x").unwrap();
+        drop(file);
+        // the dataset keeps the file, and thus the image, alive
+        assert!(Arc::strong_count(&image) > 1);
+        assert_eq!(ds.read_raw::<i32>().unwrap(), [1, 2, 3]);
+        drop(ds);
+        assert_eq!(Arc::strong_count(&image), 1);
+
+        let image = Arc::new(make_image());
+        let file = File::from_image_shared(Arc::clone(&image)).unwrap();
+        assert_eq!(file.dataset("g/y").unwrap().read_raw::<f64>().unwrap(), [4.5]);
+        file.close().unwrap();
+        assert_eq!(Arc::strong_count(&image), 1);
+
+        let invalid = Arc::new(b"not an hdf5 file".to_vec());
+        assert!(File::from_image_shared(Arc::clone(&invalid)).is_err());
+        assert_eq!(Arc::strong_count(&invalid), 1);
+    }
+}
@@ -0,0 +1,349 @@
+//! A read-only summary of the on-disk format and state of an open file.
+
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+
+use crate::sys::h5f::{H5F_info2_t, H5Fget_info2, H5Fget_intent, H5F_ACC_SWMR_READ};
+use crate::sys::h5f::{H5F_ACC_RDWR, H5F_ACC_SWMR_WRITE};
+
+use crate::hl::plist::file_access::LibVerBounds;
+use crate::internal_prelude::*;
+use crate::{IterationOrder, LocationInfo, LocationToken, TraversalOrder};
+
+/// Numbers of objects stored in a file, by kind.
+///
+/// Objects reachable through several hard links are counted once; soft and external
+/// links are not followed. The root group is included in `groups`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectCounts {
+    pub groups: usize,
+    pub datasets: usize,
+    pub named_datatypes: usize,
+}
+
+/// Summary of the format and state of an open file, as returned by [`File::format_info`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormatInfo {
+    /// Name of the file.
+    pub filename: String,
+    /// Version of the superblock (0 and 1 for the original format, 2 and 3 for files
+    /// written with newer library version bounds; 3 is required for SWMR).
+    pub superblock_version: u32,
+    /// Size of the superblock and of its extension in bytes.
+    pub superblock_size: u64,
+    /// Size of the userblock in bytes.
+    pub userblock_size: u64,
+    /// Size of the file in bytes.
+    pub file_size: u64,
+    /// Free space in the file in bytes.
+    pub free_space: u64,
+    /// Name of the file driver (e.g. `"sec2"` or `"core"`), or `"unknown"`.
+    pub driver: String,
+    /// Library format version bounds in effect for writing objects.
+    pub libver_bounds: LibVerBounds,
+    /// Whether the file was opened read-only.
+    pub read_only: bool,
+    /// Whether the file was opened for SWMR reading or writing.
+    pub swmr: bool,
+    /// Numbers of objects stored in the file.
+    pub objects: ObjectCounts,
+}
+
+impl Display for FormatInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mode = if self.read_only { "read-only" } else { "read/write" };
+        let swmr = if self.swmr { ", SWMR" } else { "" };
+        let (low, high) = (self.libver_bounds.low, self.libver_bounds.high);
+        let objects = &self.objects;
+        writeln!(f, "HDF5 file \"{}\"", self.filename)?;
+        writeln!(
+            f,
+            "  superblock: version {} ({} bytes)",
+            self.superblock_version, self.superblock_size
+        )?;
+        writeln!(f, "  userblock:  {} bytes", self.userblock_size)?;
+        writeln!(f, "  size:       {} bytes ({} bytes free)", self.file_size, self.free_space)?;
+        writeln!(f, "  driver:     {}", self.driver)?;
+        writeln!(f, "  libver:     {low:?}..={high:?}")?;
+        writeln!(f, "  mode:       {mode}{swmr}")?;
+        write!(
+            f,
+            "  objects:    {} groups, {} datasets, {} named datatypes",
+            objects.groups, objects.datasets, objects.named_datatypes
+        )
+    }
+}
+
+/// Sizes of the data stored below a group, as returned by [`Group::storage_summary`].
+///
+/// Objects reachable through several hard links are counted once; soft and external links
+/// are not followed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageSummary {
+    /// Numbers of objects, including the group itself.
+    pub objects: ObjectCounts,
+    /// Number of attributes attached to the objects.
+    pub attributes: usize,
+    /// Bytes allocated in the file for the raw data of the datasets.
+    pub dataset_storage: u64,
+    /// Size of the datasets' elements in bytes, as if fully written and unfiltered.
+    pub dataset_logical_size: u64,
+    /// Estimated bytes of attribute data: the sum of the attributes' data sizes, without the
+    /// object headers or dense attribute storage holding them.
+    pub attribute_storage: u64,
+}
+
+/// The space of a file split by use, as returned by [`File::storage_breakdown`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageBreakdown {
+    /// Size of the file in bytes.
+    pub file_size: u64,
+    /// Size of the userblock in bytes.
+    pub userblock: u64,
+    /// Bytes allocated for the raw data of all datasets.
+    pub raw_data: u64,
+    /// Free space in the file in bytes.
+    pub free_space: u64,
+    /// The remaining bytes, holding the superblock, object headers (including attributes),
+    /// group structures and chunk indexes.
+    pub metadata: u64,
+}
+
+/// Calls `f` once for every object reachable from `root` through hard links, except `root`
+/// itself, with the group holding the link, the link name and the object info.
+fn visit_objects<F>(root: &Group, mut f: F) -> Result<()>
+where
+    F: FnMut(&Group, &str, &LocationInfo) -> Result<()>,
+{
+    let mut seen: HashSet<LocationToken> = HashSet::from([root.loc_info()?.token]);
+    let mut pending = vec![root.clone()];
+    while let Some(group) = pending.pop() {
+        let members = group.iter_visit(
+            IterationOrder::Increasing,
+            TraversalOrder::Name,
+            vec![],
+            |group, name, link, members| {
+                if link.link_type == LinkType::Hard {
+                    members.push((name.to_owned(), group.loc_info_by_name(name)));
+                }
+                true
+            },
+        )?;
+        for (name, info) in members {
+            let info = info?;
+            if !seen.insert(info.token) {
+                continue;
+            }
+            f(&group, &name, &info)?;
+            if info.loc_type == LocationType::Group {
+                pending.push(group.group(&name)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Counts the objects reachable from `root` through hard links.
+fn count_objects(root: &Group) -> Result<ObjectCounts> {
+    let mut counts = ObjectCounts { groups: 1, ..ObjectCounts::default() };
+    visit_objects(root, |_, _, info| {
+        match info.loc_type {
+            LocationType::Group => counts.groups += 1,
+            LocationType::Dataset => counts.datasets += 1,
+            LocationType::NamedDatatype => counts.named_datatypes += 1,
+            LocationType::TypeMap => {}
+        }
+        Ok(())
+    })?;
+    Ok(counts)
+}
+
+/// Returns the summed data size of the attributes of `loc`.
+fn attribute_storage(loc: &Location) -> Result<u64> {
+    let mut size = 0;
+    for name in loc.attr_names()? {
+        size += loc.attr(&name)?.storage_size();
+    }
+    Ok(size)
+}
+
+impl Group {
+    /// Sums the storage of the datasets and attributes below the group, recursively, and
+    /// counts the objects and attributes.
+    ///
+    /// This opens every object below the group, so it may be slow for groups with very many
+    /// objects.
+    pub fn storage_summary(&self) -> Result<StorageSummary> {
+        h5lock!({
+            let mut summary = StorageSummary {
+                objects: ObjectCounts { groups: 1, ..ObjectCounts::default() },
+                attributes: self.attr_count()?,
+                attribute_storage: attribute_storage(self)?,
+                ..StorageSummary::default()
+            };
+            visit_objects(self, |group, _, info| {
+                let loc = group.open_by_token(info.token)?;
+                summary.attributes += info.num_attrs;
+                summary.attribute_storage += attribute_storage(&loc)
+                    .with_context(|| format!("reading the attributes of '{}'", loc.name()))?;
+                match ObjectHandleKind::from_location(loc)? {
+                    ObjectHandleKind::Group(_) => summary.objects.groups += 1,
+                    ObjectHandleKind::Dataset(ds) => {
+                        summary.objects.datasets += 1;
+                        summary.dataset_storage += ds.storage_size();
+                        summary.dataset_logical_size += ds.logical_size()?;
+                    }
+                    ObjectHandleKind::Datatype(_) => summary.objects.named_datatypes += 1,
+                }
+                Ok(())
+            })?;
+            Ok(summary)
+        })
+    }
+}
+
+impl File {
+    /// Returns a summary of the file's format (superblock version, driver, library version
+    /// bounds), size, open mode and object counts.
+    ///
+    /// Counting the objects visits every group in the file, so this may be slow for files
+    /// with very many objects.
+    pub fn format_info(&self) -> Result<FormatInfo> {
+        h5lock!({
+            let mut info = H5F_info2_t::default();
+            h5try!(H5Fget_info2(self.id(), &mut info));
+            let intent = h5get!(H5Fget_intent(self.id()): c_uint)?;
+            let fapl = self.fapl()?;
+            Ok(FormatInfo {
+                filename: self.filename(),
+                superblock_version: info.super_.version,
+                superblock_size: info.super_.super_size + info.super_.super_ext_size,
+                userblock_size: self.fcpl()?.userblock(),
+                file_size: self.size(),
+                free_space: self.free_space(),
+                driver: fapl.get_driver().map_or("unknown", |driver| driver.name()).to_owned(),
+                libver_bounds: fapl.get_libver_bounds()?,
+                read_only: intent & H5F_ACC_RDWR == 0,
+                swmr: intent & (H5F_ACC_SWMR_READ | H5F_ACC_SWMR_WRITE) != 0,
+                objects: count_objects(self)?,
+            })
+        })
+    }
+
+    /// Splits the size of the file into the raw data of the datasets, free space, the
+    /// userblock and metadata, e.g. to find out what makes a file larger than expected.
+    ///
+    /// The raw data is summed over all datasets reachable through hard links (see
+    /// [`Group::storage_summary`]) and the metadata is whatever remains of the file size.
+    /// The free space is only tracked while the file is open, so a freshly opened file
+    /// usually reports none and counts it as metadata.
+    pub fn storage_breakdown(&self) -> Result<StorageBreakdown> {
+        h5lock!({
+            let file_size = self.file_size()?;
+            let userblock = self.fcpl()?.userblock();
+            let raw_data = self.storage_summary()?.dataset_storage;
+            let free_space = self.free_space();
+            let metadata = file_size.saturating_sub(userblock + raw_data + free_space);
+            Ok(StorageBreakdown { file_size, userblock, raw_data, free_space, metadata })
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::hl::plist::file_access::LibraryVersion;
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_format_info() {
+        with_tmp_dir(|dir| {
+            let path = dir.join("default.h5");
+            let file = File::create(&path).unwrap();
+            let group = file.create_group("a").unwrap();
+            group.new_dataset::<u32>().shape(10).create("x").unwrap();
+            group.new_dataset::<u32>().create("y").unwrap();
+            file.link_hard("a/x", "x_alias").unwrap();
+            file.link_soft("/a", "soft").unwrap();
+            let info = file.format_info().unwrap();
+            assert_eq!(info.superblock_version, 0);
+            assert_eq!(info.driver, "sec2");
+            assert_eq!(info.libver_bounds.low, LibraryVersion::Earliest);
+            assert!(!info.read_only && !info.swmr);
+            assert_eq!(info.objects.groups, 2);
+            assert_eq!(info.objects.datasets, 2);
+            assert_eq!(info.file_size, file.size());
+            let summary = info.to_string();
+            assert!(summary.contains("superblock: version 0"), "{summary}");
+            assert!(summary.contains("2 groups, 2 datasets"), "{summary}");
+            drop(file);
+
+            let path = dir.join("latest.h5");
+            let file = FileBuilder::new().with_fapl(|p| p.libver_latest()).create(&path).unwrap();
+            let info = file.format_info().unwrap();
+            assert_eq!(info.superblock_version, 3);
+            assert_eq!(info.libver_bounds.low, LibraryVersion::latest());
+            assert_eq!(info.objects.groups, 1);
+            file.close().unwrap();
+
+            let file = File::open_as(&path, OpenMode::ReadSWMR).unwrap();
+            let info = file.format_info().unwrap();
+            assert!(info.read_only && info.swmr);
+            assert!(info.to_string().contains("read-only, SWMR"));
+
+            let file = File::create_in_memory("format_info.h5").unwrap();
+            assert_eq!(file.format_info().unwrap().driver, "core");
+        })
+    }
+
+    #[test]
+    pub fn test_storage_accounting() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            let group = file.create_group("a").unwrap();
+            let plain = group.new_dataset::<f64>().shape((100, 10)).create("plain").unwrap();
+            plain.write(&ndarray::Array2::<f64>::ones((100, 10))).unwrap();
+            assert_eq!(plain.logical_size().unwrap(), 8000);
+            assert_eq!(plain.storage_size(), plain.logical_size().unwrap());
+
+            let chunked = file.new_dataset::<u32>().shape(1000).chunk(100).create("chunked");
+            let chunked = chunked.unwrap();
+            chunked.write_slice(&[7_u32; 100], 0..100).unwrap();
+            assert_eq!(chunked.storage_size(), 400);
+            assert_eq!(chunked.logical_size().unwrap(), 4000);
+            file.new_dataset::<u8>().shape(0).create("empty").unwrap();
+            file.link_hard("a/plain", "alias").unwrap();
+            group.new_attr::<u64>().shape(4).create("attr").unwrap();
+            plain.new_attr::<i16>().create("scalar").unwrap();
+
+            let summary = file.storage_summary().unwrap();
+            assert_eq!(summary.objects.groups, 2);
+            assert_eq!(summary.objects.datasets, 3);
+            assert_eq!(summary.attributes, 2);
+            assert_eq!(summary.dataset_storage, 8400);
+            assert_eq!(summary.dataset_logical_size, 12000);
+            assert_eq!(summary.attribute_storage, 34);
+            let summary = group.storage_summary().unwrap();
+            assert_eq!((summary.objects.groups, summary.objects.datasets), (1, 1));
+            assert_eq!(summary.dataset_storage, 8000);
+
+            let check = |file: &File| {
+                let breakdown = file.storage_breakdown().unwrap();
+                assert_eq!(breakdown.file_size, file.size());
+                assert_eq!(breakdown.raw_data, 8400);
+                assert!(breakdown.metadata > 0);
+                let total = breakdown.userblock
+                    + breakdown.raw_data
+                    + breakdown.free_space
+                    + breakdown.metadata;
+                assert_eq!(total, breakdown.file_size);
+            };
+            check(&file);
+            drop((plain, chunked, group, file));
+            check(&File::open(&path).unwrap());
+        })
+    }
+}
@@ -1,13 +1,18 @@
 use std::collections::HashMap;
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
 use std::ptr::{self, addr_of_mut};
+use std::sync::LazyLock;
 
 #[cfg(feature = "zfp")]
 use crate::globals::{H5E_CALLBACK, H5E_PLIST};
+use crate::sys::h5d::H5Dget_create_plist;
 use crate::sys::h5p::{
     H5Pget_filter2, H5Pget_nfilters, H5Pset_deflate, H5Pset_filter, H5Pset_fletcher32, H5Pset_nbit,
     H5Pset_scaleoffset, H5Pset_shuffle, H5Pset_szip,
 };
-use crate::sys::h5t::H5T_class_t;
+use crate::sys::h5t::{H5T_class_t, H5Tget_class, H5Tget_offset, H5Tget_precision, H5Tget_size};
 use crate::sys::h5z::{
     H5Zfilter_avail, H5Zget_filter_info, H5Z_FILTER_CONFIG_DECODE_ENABLED,
     H5Z_FILTER_CONFIG_ENCODE_ENABLED, H5Z_FILTER_DEFLATE, H5Z_FILTER_FLETCHER32, H5Z_FILTER_NBIT,
@@ -15,6 +20,9 @@ use crate::sys::h5z::{
     H5Z_SO_FLOAT_DSCALE, H5Z_SO_INT, H5_SZIP_EC_OPTION_MASK, H5_SZIP_MAX_PIXELS_PER_BLOCK,
     H5_SZIP_NN_OPTION_MASK,
 };
+use crate::sys::DISABLE_FILTERS_ENV;
+
+use crate::types::TypeDescriptor;
 
 /// A filter identifier.
 pub use crate::sys::h5z::H5Z_filter_t;
@@ -25,14 +33,18 @@ use crate::internal_prelude::*;
 mod blosc;
 #[cfg(feature = "lzf")]
 mod lzf;
+mod user;
 #[cfg(feature = "zfp")]
 pub(crate) mod zfp;
 
+pub use user::{register_user_filter, unregister_user_filter, UserFilter, MAX_USER_FILTERS};
+
 #[cfg(feature = "zfp")]
 use zfp_sys::{zfp_type_zfp_type_double, zfp_type_zfp_type_float};
 
 /// Coding methods for Szip compression.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SZip {
     /// Entropy coding method.
     Entropy,
@@ -42,6 +54,7 @@ pub enum SZip {
 
 /// Scaling methods for scale-offset compression.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScaleOffset {
     /// Integer scaling with some MinBits value.
     Integer(u16),
@@ -162,7 +175,18 @@ mod zfp_impl {
 pub use zfp_impl::*;
 
 /// An HDF5 filter configuration.
+///
+/// With the `serde` feature, filters are serialized as internally tagged maps such as
+/// `{"filter": "deflate", "level": 5}`. Filters that depend on optional crate features (LZF,
+/// Blosc and ZFP) are stored by their HDF5 filter parameters and deserialized as
+/// [`Filter::User`] if the feature is disabled, so that they fail when applied rather than
+/// when parsed.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "FilterRepr", into = "FilterRepr")
+)]
 pub enum Filter {
     /// Gzip compression (deflation) with some compression level.
     Deflate(u8),
@@ -188,6 +212,110 @@ pub enum Filter {
     User(H5Z_filter_t, Vec<c_uint>),
 }
 
+impl fmt::Display for Filter {
+    /// Formats the filter in the style of h5py, e.g. `gzip-5` or `shuffle`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Deflate(level) => write!(f, "gzip-{level}"),
+            Self::Shuffle => write!(f, "shuffle"),
+            Self::Fletcher32 => write!(f, "fletcher32"),
+            Self::SZip(_, _) => write!(f, "szip"),
+            Self::NBit => write!(f, "nbit"),
+            Self::ScaleOffset(_) => write!(f, "scaleoffset"),
+            #[cfg(feature = "lzf")]
+            Self::LZF => write!(f, "lzf"),
+            #[cfg(feature = "blosc")]
+            Self::Blosc(_, _, _) => write!(f, "blosc"),
+            #[cfg(feature = "zfp")]
+            Self::Zfp(_, _, _) => write!(f, "zfp"),
+            Self::User(id, _) => write!(f, "filter {id}"),
+        }
+    }
+}
+
+/// Serialized form of a [`Filter`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(tag = "filter", rename_all = "snake_case")]
+enum FilterRepr {
+    Deflate { level: u8 },
+    Shuffle,
+    Fletcher32,
+    Szip { coding: SZip, pixels_per_block: u8 },
+    Nbit,
+    ScaleOffset { mode: ScaleOffset },
+    Lzf,
+    Blosc { params: Vec<c_uint> },
+    Zfp { params: Vec<c_uint> },
+    User { id: H5Z_filter_t, params: Vec<c_uint> },
+}
+
+#[cfg(feature = "serde")]
+impl From<Filter> for FilterRepr {
+    fn from(filter: Filter) -> Self {
+        match filter {
+            Filter::Deflate(level) => Self::Deflate { level },
+            Filter::Shuffle => Self::Shuffle,
+            Filter::Fletcher32 => Self::Fletcher32,
+            Filter::SZip(coding, pixels_per_block) => Self::Szip { coding, pixels_per_block },
+            Filter::NBit => Self::Nbit,
+            Filter::ScaleOffset(mode) => Self::ScaleOffset { mode },
+            #[cfg(feature = "lzf")]
+            Filter::LZF => Self::Lzf,
+            #[cfg(feature = "blosc")]
+            Filter::Blosc(complib, clevel, shuffle) => {
+                let shuffle = match shuffle {
+                    BloscShuffle::None => blosc::BLOSC_NOSHUFFLE,
+                    BloscShuffle::Byte => blosc::BLOSC_SHUFFLE,
+                    BloscShuffle::Bit => blosc::BLOSC_BITSHUFFLE,
+                };
+                let complib = match complib {
+                    Blosc::BloscLZ => blosc::BLOSC_BLOSCLZ,
+                    #[cfg(feature = "blosc-lz4")]
+                    Blosc::LZ4 => blosc::BLOSC_LZ4,
+                    #[cfg(feature = "blosc-lz4")]
+                    Blosc::LZ4HC => blosc::BLOSC_LZ4HC,
+                    #[cfg(feature = "blosc-snappy")]
+                    Blosc::Snappy => blosc::BLOSC_SNAPPY,
+                    #[cfg(feature = "blosc-zlib")]
+                    Blosc::ZLib => blosc::BLOSC_ZLIB,
+                    #[cfg(feature = "blosc-zstd")]
+                    Blosc::ZStd => blosc::BLOSC_ZSTD,
+                };
+                Self::Blosc { params: vec![0, 0, 0, 0, c_uint::from(clevel), shuffle, complib] }
+            }
+            #[cfg(feature = "zfp")]
+            Filter::Zfp(mode, chunk_dims, n_bytes) => {
+                Self::Zfp { params: Filter::zfp_cdata(mode, &chunk_dims, n_bytes) }
+            }
+            Filter::User(id, params) => Self::User { id, params },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<FilterRepr> for Filter {
+    type Error = Error;
+
+    fn try_from(repr: FilterRepr) -> Result<Self> {
+        Ok(match repr {
+            FilterRepr::Deflate { level } => {
+                ensure!(level <= 9, "invalid deflate level: {}", level);
+                Self::Deflate(level)
+            }
+            FilterRepr::Shuffle => Self::Shuffle,
+            FilterRepr::Fletcher32 => Self::Fletcher32,
+            FilterRepr::Szip { coding, pixels_per_block } => Self::SZip(coding, pixels_per_block),
+            FilterRepr::Nbit => Self::NBit,
+            FilterRepr::ScaleOffset { mode } => Self::ScaleOffset(mode),
+            FilterRepr::Lzf => Self::from_raw(32000, &[])?,
+            FilterRepr::Blosc { params } => Self::from_raw(32001, &params)?,
+            FilterRepr::Zfp { params } => Self::from_raw(32013, &params)?,
+            FilterRepr::User { id, params } => Self::User(id, params),
+        })
+    }
+}
+
 /// Information about whether a filter is available and enabled for encoding/decoding.
 #[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct FilterInfo {
@@ -199,22 +327,176 @@ pub struct FilterInfo {
     pub decode_enabled: bool,
 }
 
+/// An entry of a filter pipeline, as recorded in a dataset creation property list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PipelineEntry {
+    pub id: H5Z_filter_t,
+    pub name: String,
+    pub optional: bool,
+}
+
+impl PipelineEntry {
+    /// Returns `true` if the filter is available and configured to decode data.
+    pub fn can_decode(&self) -> bool {
+        let info = Filter::get_info(self.id);
+        info.is_available && info.decode_enabled
+    }
+}
+
+impl fmt::Display for PipelineEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let flag = if self.optional { "optional" } else { "mandatory" };
+        write!(f, "{} ('{}', {})", self.id, self.name, flag)
+    }
+}
+
+/// Adds the filters of the pipeline of `dataset` that cannot decode data as context to an
+/// error that occurred while reading it, if there are any.
+pub(crate) fn explain_read_error(dataset: &Container, err: Error) -> Error {
+    let entries = h5lock!({
+        let dcpl = PropertyList::from_id(h5try!(H5Dget_create_plist(dataset.id())))?;
+        Filter::extract_pipeline_entries(dcpl.id())
+    });
+    let Ok(entries) = entries else {
+        return err;
+    };
+    let missing = entries.iter().filter(|entry| !entry.can_decode()).collect::<Vec<_>>();
+    if missing.is_empty() {
+        return err;
+    }
+    let missing = missing.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+    Error::Context { context: format!("unavailable filters [{missing}]"), source: Box::new(err) }
+}
+
+/// Filters with registered ids that this crate can provide, as `(id, name)`.
+const KNOWN_FILTERS: &[(H5Z_filter_t, &str)] = &[(32000, "lzf"), (32001, "blosc"), (32013, "zfp")];
+
+/// Identifiers of the filters registered by this crate, filled on first filter use.
+static REGISTERED_FILTERS: LazyLock<Vec<H5Z_filter_t>> = LazyLock::new(register_filters);
+
+/// Registers the filters provided by this crate on first use.
+///
+/// Registration is deferred until a filter is actually used (rather than done at library
+/// initialization), so that `HDF5_PLUGIN_PATH` adjustments made after initialization are
+/// still taken into account when probing for external plugins.
+pub(crate) fn ensure_filters_registered() {
+    // the library lock is taken first so that the lazy initialization never waits on it
+    h5lock!({
+        LazyLock::force(&REGISTERED_FILTERS);
+    });
+}
+
+/// Registers a filter unless the library already provides one with the same id (e.g. a plugin
+/// loaded from `HDF5_PLUGIN_PATH`), returning who provides the filter afterwards.
+fn register_unless_available<F>(id: H5Z_filter_t, name: &str, register: F) -> FilterProvider
+where
+    F: FnOnce() -> Result<(), &'static str>,
+{
+    if h5call!(H5Zfilter_avail(id)).is_ok_and(|avail| avail > 0) {
+        return FilterProvider::External;
+    }
+    match register() {
+        Ok(()) => FilterProvider::Crate,
+        Err(e) => {
+            eprintln!("Error while registering {name} filter: {e}");
+            FilterProvider::Unavailable
+        }
+    }
+}
+
+/// Returns `true` if [`DISABLE_FILTERS_ENV`] is set to anything but an empty string or `0`.
+fn filters_disabled(value: Option<OsString>) -> bool {
+    value.is_some_and(|value| !value.is_empty() && value != "0")
+}
+
 /// This function requires a synchronisation with other calls to `hdf5`
-pub(crate) fn register_filters() {
+fn register_filters() -> Vec<H5Z_filter_t> {
+    #[allow(unused_mut)]
+    let mut registered = Vec::new();
+    if filters_disabled(env::var_os(DISABLE_FILTERS_ENV)) {
+        return registered;
+    }
     #[cfg(feature = "lzf")]
-    if let Err(e) = lzf::register_lzf() {
-        eprintln!("Error while registering LZF filter: {e}");
+    if register_unless_available(lzf::LZF_FILTER_ID, "LZF", lzf::register_lzf)
+        == FilterProvider::Crate
+    {
+        registered.push(lzf::LZF_FILTER_ID);
     }
     #[cfg(feature = "blosc")]
-    if let Err(e) = blosc::register_blosc() {
-        eprintln!("Error while registering Blosc filter: {e}");
+    if register_unless_available(blosc::BLOSC_FILTER_ID, "Blosc", blosc::register_blosc)
+        == FilterProvider::Crate
+    {
+        registered.push(blosc::BLOSC_FILTER_ID);
     }
     #[cfg(feature = "zfp")]
-    if let Err(e) = zfp::register_zfp() {
-        eprintln!("Error while registering ZFP filter: {e}");
+    if register_unless_available(zfp::ZFP_FILTER_ID, "ZFP", zfp::register_zfp)
+        == FilterProvider::Crate
+    {
+        registered.push(zfp::ZFP_FILTER_ID);
+    }
+    registered
+}
+
+/// Who provides a filter, as described by [`registration_report`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterProvider {
+    /// Registered by this crate (with the corresponding crate feature enabled).
+    Crate,
+    /// Provided by the HDF5 library or an external plugin, e.g. one found on
+    /// `HDF5_PLUGIN_PATH`; this crate does not register filters that are already available.
+    External,
+    /// Not available.
+    Unavailable,
+}
+
+impl fmt::Display for FilterProvider {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Crate => "provided by this crate",
+            Self::External => "provided externally",
+            Self::Unavailable => "unavailable",
+        })
+    }
+}
+
+/// Registration status of a filter this crate knows how to provide.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilterRegistration {
+    /// The filter identifier.
+    pub id: H5Z_filter_t,
+    /// The filter name.
+    pub name: &'static str,
+    /// Who provides the filter.
+    pub provider: FilterProvider,
+}
+
+impl fmt::Display for FilterRegistration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.name, self.id, self.provider)
     }
 }
 
+/// Describes, for each of the lzf, blosc and zfp filters, whether it is provided by this crate,
+/// by the library or an external plugin, or unavailable.
+///
+/// This registers the filters of this crate if that has not happened yet.
+pub fn registration_report() -> Vec<FilterRegistration> {
+    ensure_filters_registered();
+    KNOWN_FILTERS
+        .iter()
+        .map(|&(id, name)| {
+            let provider = if !h5call!(H5Zfilter_avail(id)).is_ok_and(|avail| avail > 0) {
+                FilterProvider::Unavailable
+            } else if REGISTERED_FILTERS.contains(&id) {
+                FilterProvider::Crate
+            } else {
+                FilterProvider::External
+            };
+            FilterRegistration { id, name, provider }
+        })
+        .collect()
+}
+
 /// Returns `true` if deflate filter is available.
 pub fn deflate_available() -> bool {
     h5lock!(H5Zfilter_avail(H5Z_FILTER_DEFLATE) == 1)
@@ -234,16 +516,19 @@ pub fn szip_available() -> bool {
 
 /// Returns `true` if LZF filter is available.
 pub fn lzf_available() -> bool {
+    ensure_filters_registered();
     h5lock!(H5Zfilter_avail(32000) == 1)
 }
 
 /// Returns `true` if Blosc filter is available.
 pub fn blosc_available() -> bool {
+    ensure_filters_registered();
     h5lock!(H5Zfilter_avail(32001) == 1)
 }
 
 /// Returns `true` if ZFP filter is available.
 pub fn zfp_available() -> bool {
+    ensure_filters_registered();
     h5lock!(H5Zfilter_avail(32013) == 1)
 }
 
@@ -269,6 +554,7 @@ impl Filter {
 
     /// Returns metadata for the filter with the given identifier.
     pub fn get_info(filter_id: H5Z_filter_t) -> FilterInfo {
+        ensure_filters_registered();
         if !h5call!(H5Zfilter_avail(filter_id)).map(|x| x > 0).unwrap_or_default() {
             return FilterInfo::default();
         }
@@ -534,10 +820,40 @@ impl Filter {
         Ok(Self::blosc(complib, clevel, shuffle))
     }
 
+    /// Returns the mode of a ZFP filter and its two parameters, as stored in filter parameters.
+    #[cfg(feature = "zfp")]
+    fn zfp_mode_params(mode: ZfpMode) -> (c_uint, c_uint, c_uint) {
+        match mode {
+            ZfpMode::FixedRate(rate) => {
+                let bits = rate.to_bits();
+                (1, (bits >> 32) as c_uint, bits as c_uint)
+            }
+            ZfpMode::FixedPrecision(precision) => (2, precision as c_uint, 0),
+            ZfpMode::FixedAccuracy(accuracy) => {
+                let bits = accuracy.to_bits();
+                (3, (bits >> 32) as c_uint, bits as c_uint)
+            }
+            ZfpMode::Reversible => (5, 0, 0),
+        }
+    }
+
+    /// Encodes a ZFP filter configuration in the parameters read by `parse_zfp`: the first two
+    /// chunk dimensions, the element size, the mode and its parameters, then any further chunk
+    /// dimensions.
+    #[cfg(feature = "zfp")]
+    fn zfp_cdata(mode: ZfpMode, chunk_dims: &[usize], n_bytes: u8) -> Vec<c_uint> {
+        let dim = |i: usize| chunk_dims.get(i).map_or(1, |&d| d as c_uint);
+        let (mode, param1, param2) = Self::zfp_mode_params(mode);
+        let mut cdata = vec![0, 0, 0, 0, dim(0), dim(1), n_bytes.into(), mode, param1, param2];
+        cdata.extend(chunk_dims.iter().skip(2).map(|&d| d as c_uint));
+        cdata
+    }
+
     #[cfg(feature = "zfp")]
     fn parse_zfp(cdata: &[c_uint]) -> Result<Self> {
         ensure!(cdata.len() >= 8, "expected at least length 8 cdata for zfp filter");
-        let chunk_dims = cdata[4..6].iter().map(|&x| x as _).collect::<Vec<_>>();
+        let mut chunk_dims = cdata[4..6].iter().map(|&x| x as _).collect::<Vec<_>>();
+        chunk_dims.extend(cdata.iter().skip(10).map(|&x| x as usize));
         let n_bytes = cdata[6] as u8;
         let mode = if cdata.len() >= 8 { cdata[7] } else { 1 };
         let param1 = if cdata.len() >= 9 { cdata[8] } else { 0 };
@@ -710,18 +1026,7 @@ impl Filter {
         };
 
         // Build the Mode Information we need
-        let (mode_val, param1, param2) = match mode {
-            ZfpMode::FixedRate(rate) => {
-                let bits = rate.to_bits();
-                (1, (bits >> 32) as c_uint, bits as c_uint)
-            }
-            ZfpMode::FixedPrecision(precision) => (2, precision as c_uint, 0),
-            ZfpMode::FixedAccuracy(accuracy) => {
-                let bits = accuracy.to_bits();
-                (3, (bits >> 32) as c_uint, bits as c_uint)
-            }
-            ZfpMode::Reversible => (5, 0, 0),
-        };
+        let (mode_val, param1, param2) = Self::zfp_mode_params(mode);
 
         // update values and encode into the header
         let (hdr_cd_values, _) =
@@ -741,6 +1046,7 @@ impl Filter {
     }
 
     pub(crate) fn apply_to_plist(&self, id: hid_t) -> Result<()> {
+        ensure_filters_registered();
         h5try!(match self {
             Self::Deflate(level) => Self::apply_deflate(id, *level),
             Self::Shuffle => Self::apply_shuffle(id),
@@ -788,12 +1094,126 @@ impl Filter {
             Ok(filters)
         })
     }
+
+    pub(crate) fn extract_pipeline_entries(plist_id: hid_t) -> Result<Vec<PipelineEntry>> {
+        let mut name: Vec<c_char> = vec![0; 257];
+        h5lock!({
+            let n_filters = h5try!(H5Pget_nfilters(plist_id));
+            (0..n_filters)
+                .map(|idx| {
+                    let mut flags: c_uint = 0;
+                    let mut cd_nelmts: size_t = 0;
+                    let id = h5try!(H5Pget_filter2(
+                        plist_id,
+                        idx as _,
+                        addr_of_mut!(flags),
+                        addr_of_mut!(cd_nelmts),
+                        ptr::null_mut(),
+                        name.len() as _,
+                        name.as_mut_ptr(),
+                        ptr::null_mut(),
+                    ));
+                    let name = unsafe { string_from_cstr(name.as_ptr()) };
+                    Ok(PipelineEntry { id, name, optional: flags & H5Z_FLAG_OPTIONAL != 0 })
+                })
+                .collect()
+        })
+    }
 }
 
 const COMP_FILTER_IDS: &[H5Z_filter_t] =
     &[H5Z_FILTER_DEFLATE, H5Z_FILTER_SZIP, 32000, 32001, 32013];
 
-pub(crate) fn validate_filters(filters: &[Filter], type_class: H5T_class_t) -> Result<()> {
+/// Bit layout of an integer or floating-point datatype, as used by the nbit and scale-offset
+/// filters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct BitLayout {
+    /// Size of the type in bytes.
+    pub size: usize,
+    /// Number of significant bits.
+    pub precision: usize,
+    /// Offset of the first significant bit.
+    pub offset: usize,
+}
+
+impl BitLayout {
+    /// Returns the bit layout of an atomic datatype; the precision is narrower than the type
+    /// size for integers created with a reduced precision.
+    pub fn of(dtype: &Datatype) -> Result<Self> {
+        h5lock!({
+            let id = dtype.id();
+            // both return zero on failure
+            let (size, precision) = (H5Tget_size(id), H5Tget_precision(id));
+            let offset = h5check(H5Tget_offset(id))? as usize;
+            ensure!(size > 0 && precision > 0, "invalid datatype size or precision");
+            ensure!(
+                offset + precision <= 8 * size,
+                "invalid bit layout: precision {} at offset {} in {} bytes",
+                precision,
+                offset,
+                size
+            );
+            Ok(Self { size, precision, offset })
+        })
+    }
+}
+
+fn validate_nbit(dtype: &Datatype, desc: &TypeDescriptor) -> Result<()> {
+    match desc {
+        TypeDescriptor::Integer(_) | TypeDescriptor::Unsigned(_) | TypeDescriptor::Float(_) => {
+            BitLayout::of(dtype).map(|_| ())
+        }
+        _ => fail!("nbit filter requires an integer or float type, got {}", desc),
+    }
+}
+
+fn validate_scale_offset(
+    dtype: &Datatype,
+    mode: ScaleOffset,
+    type_class: H5T_class_t,
+) -> Result<()> {
+    match type_class {
+        H5T_class_t::H5T_INTEGER | H5T_class_t::H5T_ENUM => match mode {
+            ScaleOffset::FloatDScale(_) => {
+                fail!("Invalid scale-offset mode for integer type: {:?}", mode)
+            }
+            ScaleOffset::Integer(min_bits) => {
+                let layout = BitLayout::of(dtype)?;
+                ensure!(
+                    usize::from(min_bits) <= layout.precision,
+                    "Scale-offset minimum bits {} exceed the precision of the type ({} bits)",
+                    min_bits,
+                    layout.precision
+                );
+            }
+        },
+        H5T_class_t::H5T_FLOAT => match mode {
+            ScaleOffset::Integer(_) => {
+                fail!("Invalid scale-offset mode for float type: {:?}", mode)
+            }
+            ScaleOffset::FloatDScale(factor) => {
+                // scaled values are stored in integers of the same size as the float type
+                let size = BitLayout::of(dtype)?.size;
+                let max_factor = if size <= 4 { 9 } else { 18 };
+                ensure!(
+                    factor <= max_factor,
+                    "Scale-offset decimal scale factor {} is too large for a {}-byte float \
+                     type (at most {})",
+                    factor,
+                    size,
+                    max_factor
+                );
+            }
+        },
+        _ => fail!("Can only use scale-offset with ints/floats, got: {:?}", type_class),
+    }
+    Ok(())
+}
+
+/// Checks that the filter pipeline is valid for the given datatype.
+pub(crate) fn validate_filters(filters: &[Filter], dtype: &Datatype) -> Result<()> {
+    let type_class = h5lock!(H5Tget_class(dtype.id()));
+    let mut desc = None;
     let mut map: HashMap<H5Z_filter_t, &Filter> = HashMap::new();
     let mut comp_filter: Option<&Filter> = None;
 
@@ -812,18 +1232,13 @@ pub(crate) fn validate_filters(filters: &[Filter], type_class: H5T_class_t) -> R
         } else if id == H5Z_FILTER_FLETCHER32 && map.contains_key(&H5Z_FILTER_SCALEOFFSET) {
             fail!("Lossy scale-offset filter before fletcher2 checksum filter");
         } else if let Filter::ScaleOffset(mode) = filter {
-            match type_class {
-                H5T_class_t::H5T_INTEGER | H5T_class_t::H5T_ENUM => {
-                    if let ScaleOffset::FloatDScale(_) = mode {
-                        fail!("Invalid scale-offset mode for integer type: {:?}", mode);
-                    }
-                }
-                H5T_class_t::H5T_FLOAT => {
-                    if let ScaleOffset::Integer(_) = mode {
-                        fail!("Invalid scale-offset mode for float type: {:?}", mode);
-                    }
-                }
-                _ => fail!("Can only use scale-offset with ints/floats, got: {:?}", type_class),
+            validate_scale_offset(dtype, *mode, type_class)?;
+        } else if matches!(filter, Filter::NBit) {
+            if desc.is_none() {
+                desc = Some(dtype.to_descriptor()?);
+            }
+            if let Some(desc) = &desc {
+                validate_nbit(dtype, desc)?;
             }
         } else if matches!(filter, Filter::SZip(_, _)) {
             // https://github.com/h5py/h5py/issues/953
@@ -843,17 +1258,21 @@ pub(crate) fn validate_filters(filters: &[Filter], type_class: H5T_class_t) -> R
 
 #[cfg(test)]
 mod tests {
-    use crate::sys::h5t::H5T_class_t;
-    use ndarray::{Array2, Axis};
+    use ndarray::{Array1, Array2, Axis};
     use std::io::{Seek, SeekFrom};
 
     use super::{
-        blosc_available, deflate_available, lzf_available, szip_available, validate_filters,
-        Filter, FilterInfo, SZip, ScaleOffset,
+        blosc_available, deflate_available, filters_disabled, lzf_available,
+        register_unless_available, szip_available, validate_filters, BitLayout, Filter, FilterInfo,
+        FilterProvider, SZip, ScaleOffset,
     };
+    use super::{register_user_filter, unregister_user_filter, UserFilter};
+    use crate::class::ObjectClass;
     use crate::hl::filters::zfp_available;
+    use crate::sys::h5t::{H5Tcopy, H5Tset_precision};
     use crate::test::with_tmp_file;
-    use crate::{plist::DatasetCreate, Result};
+    use crate::types::{CompoundField, CompoundType, TypeDescriptor};
+    use crate::{plist::DatasetCreate, Datatype, Result};
 
     #[test]
     fn test_filter_pipeline() -> Result<()> {
@@ -902,7 +1321,7 @@ mod tests {
                 Filter::fletcher32(),
                 Filter::scale_offset(ScaleOffset::Integer(3)),
             ];
-            validate_filters(&pipeline, H5T_class_t::H5T_INTEGER)?;
+            validate_filters(&pipeline, &Datatype::from_type::<i32>()?)?;
 
             let plist = DatasetCreate::try_new()?;
             for flt in &pipeline {
@@ -910,10 +1329,7 @@ mod tests {
             }
             assert_eq!(Filter::extract_pipeline(plist.id())?, pipeline);
 
-            let mut b = DatasetCreate::build();
-            b.set_filters(&pipeline);
-            b.chunk(10);
-            let plist = b.finish()?;
+            let plist = DatasetCreate::build().set_filters(&pipeline).chunk(10).finish()?;
             assert_eq!(Filter::extract_pipeline(plist.id())?, pipeline);
 
             let res = with_tmp_file(|file| {
@@ -935,13 +1351,80 @@ mod tests {
         assert!(!bad_filter.encode_enabled());
         assert!(!bad_filter.decode_enabled());
         assert_err!(
-            validate_filters(&[bad_filter], H5T_class_t::H5T_INTEGER),
+            validate_filters(&[bad_filter], &Datatype::from_type::<i32>()?),
             "Filter not available"
         );
 
         Ok(())
     }
 
+    fn compound_desc() -> TypeDescriptor {
+        TypeDescriptor::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<i32>("a", 0, 0),
+                CompoundField::typed::<f64>("b", 8, 1),
+            ],
+            size: 16,
+        })
+    }
+
+    #[test]
+    fn test_validate_filters_dtype() -> Result<()> {
+        let int = Datatype::from_type::<i32>()?;
+        let float = Datatype::from_type::<f32>()?;
+        let compound = Datatype::from_descriptor(&compound_desc())?;
+        let so_int = |bits| [Filter::scale_offset(ScaleOffset::Integer(bits))];
+        let so_float = |factor| [Filter::scale_offset(ScaleOffset::FloatDScale(factor))];
+
+        validate_filters(&[Filter::nbit()], &int)?;
+        validate_filters(&[Filter::nbit()], &float)?;
+        assert_err!(
+            validate_filters(&[Filter::nbit()], &compound),
+            "nbit filter requires an integer or float type, got"
+        );
+        let string = Datatype::from_type::<crate::types::VarLenUnicode>()?;
+        assert_err!(validate_filters(&[Filter::nbit()], &string), "nbit filter requires");
+
+        validate_filters(&so_int(32), &int)?;
+        assert_err!(validate_filters(&so_int(33), &int), "minimum bits 33 exceed the precision");
+        assert_err!(validate_filters(&so_float(2), &int), "Invalid scale-offset mode");
+        assert_err!(validate_filters(&so_int(2), &float), "Invalid scale-offset mode");
+        validate_filters(&so_float(9), &float)?;
+        assert_err!(validate_filters(&so_float(10), &float), "factor 10 is too large");
+        validate_filters(&so_float(10), &Datatype::from_type::<f64>()?)?;
+        assert_err!(validate_filters(&so_int(2), &compound), "Can only use scale-offset");
+
+        // integers with a reduced precision
+        let narrow = Datatype::from_id(h5lock!(H5Tcopy(int.id())))?;
+        h5call!(H5Tset_precision(narrow.id(), 12))?;
+        assert_eq!(BitLayout::of(&narrow)?, BitLayout { size: 4, precision: 12, offset: 0 });
+        validate_filters(&[Filter::nbit()], &narrow)?;
+        assert_err!(validate_filters(&so_int(13), &narrow), "precision of the type (12 bits)");
+        Ok(())
+    }
+
+    #[test]
+    fn test_dataset_filters_dtype() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset_builder().nbit().empty_as(&compound_desc()).shape(10);
+            assert_err!(ds.create("nbit"), "nbit filter requires an integer or float type");
+            let ds = file.new_dataset::<i32>().scale_offset(ScaleOffset::FloatDScale(2));
+            assert_err!(ds.shape(10).create("so"), "Invalid scale-offset mode for integer type");
+            assert!(!file.link_exists("nbit") && !file.link_exists("so"));
+
+            let ints = Array1::from_shape_fn(1000, |i| (i as i32 % 100) - 50);
+            let ds = file.new_dataset_builder().nbit().with_data(&ints).create("ints").unwrap();
+            assert_eq!(ds.read_1d::<i32>().unwrap(), ints);
+            let so = ScaleOffset::Integer(0);
+            let ds = file.new_dataset_builder().scale_offset(so).with_data(&ints);
+            assert_eq!(ds.create("so_ints").unwrap().read_1d::<i32>().unwrap(), ints);
+            let floats = Array1::from_shape_fn(1000, |i| i as f64 * 0.5);
+            let so = ScaleOffset::FloatDScale(1);
+            let ds = file.new_dataset_builder().scale_offset(so).with_data(&floats);
+            assert_eq!(ds.create("so_floats").unwrap().read_1d::<f64>().unwrap(), floats);
+        })
+    }
+
     #[test]
     #[cfg(feature = "zfp")]
     fn test_zfp_accuracy() -> Result<()> {
@@ -1136,20 +1619,20 @@ mod tests {
             let ds = file.dataset("zfp_reversible").unwrap();
 
             let read_data: Vec<f32> = ds.read_raw().unwrap();
-            let n_bytes = file.size();
+            let n_bytes = ds.storage_size();
 
             // ZFP is lossy, so we check approximate equality
             assert_eq!(read_data.len(), data.len());
             dbg!(&data.clone().into_raw_vec_and_offset().0[0..15]);
             dbg!(&read_data[0..15]);
-            let target_bytes = (data.len() * 4) as u64;
+            let target_bytes = ds.logical_size().unwrap();
+            assert_eq!(target_bytes, (data.len() * 4) as u64);
             assert!(
-                n_bytes <= target_bytes,
+                n_bytes < target_bytes,
                 "Dataset size {} exceeds target {}",
                 n_bytes,
                 target_bytes
             );
-            assert_eq!(n_bytes, 29432);
             //
             for (i, (original, compressed)) in data.iter().zip(read_data.iter()).enumerate() {
                 let diff = (original - compressed).abs();
@@ -1236,4 +1719,27 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_register_unless_available() {
+        const ID: super::H5Z_filter_t = 40125;
+        let register = || -> std::result::Result<(), &'static str> { Err("not registered") };
+        assert_eq!(register_unless_available(ID, "test", register), FilterProvider::Unavailable);
+        assert_eq!(register_unless_available(ID, "test", || Ok(())), FilterProvider::Crate);
+
+        let copy = |data: &[u8], _: &[u32]| -> Result<Vec<u8>> { Ok(data.to_vec()) };
+        register_user_filter(UserFilter::new(ID, "test", copy, copy)).unwrap();
+        let register = || panic!("an available filter must not be registered again");
+        assert_eq!(register_unless_available(ID, "test", register), FilterProvider::External);
+        unregister_user_filter(ID).unwrap();
+    }
+
+    #[test]
+    fn test_filters_disabled() {
+        assert!(!filters_disabled(None));
+        assert!(!filters_disabled(Some("".into())));
+        assert!(!filters_disabled(Some("0".into())));
+        assert!(filters_disabled(Some("1".into())));
+        assert!(filters_disabled(Some("yes".into())));
+    }
 }
@@ -0,0 +1,391 @@
+//! Registration of user-defined filters implemented with Rust closures.
+//!
+//! The filter callbacks of `H5Z_class2_t` are not passed the filter id, so each registered
+//! filter is assigned one of [`MAX_USER_FILTERS`] slots with its own pair of static
+//! trampolines, which look up the closures of the slot in a global registry.
+
+use std::ffi::CString;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::sync::Arc;
+
+use parking_lot::{const_rwlock, RwLock};
+
+use crate::sys::h5::htri_t;
+use crate::sys::h5z::{
+    H5Z_class2_t, H5Z_filter_t, H5Zregister, H5Zunregister, H5Z_CLASS_T_VERS, H5Z_FLAG_REVERSE,
+};
+
+use crate::internal_prelude::*;
+
+/// Maximum number of user filters registered with [`register_user_filter`] at the same time.
+pub const MAX_USER_FILTERS: usize = 16;
+
+/// Range of filter ids reserved by HDF5 for third-party filters.
+const USER_FILTER_IDS: std::ops::RangeInclusive<H5Z_filter_t> = 256..=65535;
+
+type CodecFn = dyn Fn(&[u8], &[c_uint]) -> Result<Vec<u8>> + Send + Sync;
+type CanApplyFn = dyn Fn(&Datatype) -> bool + Send + Sync;
+
+/// A user-defined filter implemented with Rust closures.
+///
+/// The encoder and decoder are passed the filter input and the filter parameters (the client
+/// data set with [`Filter::User`](super::Filter::User)) and return the filter output, which
+/// must not be empty. Returning an error or panicking makes the filter fail.
+pub struct UserFilter {
+    id: H5Z_filter_t,
+    name: String,
+    encode: Box<CodecFn>,
+    decode: Box<CodecFn>,
+    can_apply: Option<Box<CanApplyFn>>,
+}
+
+impl UserFilter {
+    /// Creates a user filter with the given id (in the range 256 to 65535), name, encoder and
+    /// decoder.
+    pub fn new<E, D>(id: H5Z_filter_t, name: &str, encode: E, decode: D) -> Self
+    where
+        E: Fn(&[u8], &[c_uint]) -> Result<Vec<u8>> + Send + Sync + 'static,
+        D: Fn(&[u8], &[c_uint]) -> Result<Vec<u8>> + Send + Sync + 'static,
+    {
+        Self {
+            id,
+            name: name.to_owned(),
+            encode: Box::new(encode),
+            decode: Box::new(decode),
+            can_apply: None,
+        }
+    }
+
+    /// Sets a check run when a dataset with the given datatype is created with the filter.
+    ///
+    /// If it returns `false`, the library skips the filter if it is optional (as filters set
+    /// with [`Filter::User`](super::Filter::User) are) and fails the dataset creation otherwise.
+    #[must_use]
+    pub fn can_apply<F>(mut self, can_apply: F) -> Self
+    where
+        F: Fn(&Datatype) -> bool + Send + Sync + 'static,
+    {
+        self.can_apply = Some(Box::new(can_apply));
+        self
+    }
+
+    /// Returns the filter id.
+    pub fn id(&self) -> H5Z_filter_t {
+        self.id
+    }
+
+    /// Returns the filter name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Debug for UserFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UserFilter")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("can_apply", &self.can_apply.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+struct Registered {
+    filter: UserFilter,
+    /// The name passed to the library, which keeps a pointer to it while registered.
+    name: CString,
+}
+
+static REGISTRY: RwLock<[Option<Arc<Registered>>; MAX_USER_FILTERS]> =
+    const_rwlock([const { None }; MAX_USER_FILTERS]);
+
+fn registered(slot: usize) -> Option<Arc<Registered>> {
+    REGISTRY.read().get(slot).cloned().flatten()
+}
+
+type FilterCallback = unsafe extern "C" fn(
+    c_uint,
+    size_t,
+    *const c_uint,
+    size_t,
+    *mut size_t,
+    *mut *mut c_void,
+) -> size_t;
+type CanApplyCallback = unsafe extern "C" fn(hid_t, hid_t, hid_t) -> htri_t;
+
+unsafe extern "C" fn filter_slot<const SLOT: usize>(
+    flags: c_uint,
+    cd_nelmts: size_t,
+    cd_values: *const c_uint,
+    nbytes: size_t,
+    buf_size: *mut size_t,
+    buf: *mut *mut c_void,
+) -> size_t {
+    unsafe { run_filter(SLOT, flags, cd_nelmts, cd_values, nbytes, buf_size, buf) }
+}
+
+unsafe extern "C" fn can_apply_slot<const SLOT: usize>(
+    _dcpl_id: hid_t,
+    type_id: hid_t,
+    _space_id: hid_t,
+) -> htri_t {
+    run_can_apply(SLOT, type_id)
+}
+
+const fn trampolines<const SLOT: usize>() -> (FilterCallback, CanApplyCallback) {
+    (filter_slot::<SLOT>, can_apply_slot::<SLOT>)
+}
+
+const TRAMPOLINES: [(FilterCallback, CanApplyCallback); MAX_USER_FILTERS] = [
+    trampolines::<0>(),
+    trampolines::<1>(),
+    trampolines::<2>(),
+    trampolines::<3>(),
+    trampolines::<4>(),
+    trampolines::<5>(),
+    trampolines::<6>(),
+    trampolines::<7>(),
+    trampolines::<8>(),
+    trampolines::<9>(),
+    trampolines::<10>(),
+    trampolines::<11>(),
+    trampolines::<12>(),
+    trampolines::<13>(),
+    trampolines::<14>(),
+    trampolines::<15>(),
+];
+
+/// Runs the closures of the filter in `slot` following the buffer contract of HDF5: the
+/// output is copied into a `malloc`'d buffer replacing (and freeing) the input buffer, and
+/// 0 is returned on failure, leaving the input buffer untouched.
+unsafe fn run_filter(
+    slot: usize,
+    flags: c_uint,
+    cd_nelmts: size_t,
+    cd_values: *const c_uint,
+    nbytes: size_t,
+    buf_size: *mut size_t,
+    buf: *mut *mut c_void,
+) -> size_t {
+    let Some(registered) = registered(slot) else {
+        return 0;
+    };
+    if buf.is_null() || unsafe { *buf }.is_null() || buf_size.is_null() {
+        return 0;
+    }
+    let cdata = if cd_nelmts == 0 || cd_values.is_null() {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(cd_values, cd_nelmts) }
+    };
+    let input = unsafe { slice::from_raw_parts((*buf).cast::<u8>(), nbytes) };
+    let filter = &registered.filter;
+    let output = panic::catch_unwind(AssertUnwindSafe(|| {
+        if flags & H5Z_FLAG_REVERSE == 0 {
+            (filter.encode)(input, cdata)
+        } else {
+            (filter.decode)(input, cdata)
+        }
+    }));
+    let Ok(Ok(output)) = output else {
+        return 0;
+    };
+    if output.is_empty() {
+        return 0;
+    }
+    unsafe {
+        let outbuf = libc::malloc(output.len());
+        if outbuf.is_null() {
+            return 0;
+        }
+        ptr::copy_nonoverlapping(output.as_ptr(), outbuf.cast::<u8>(), output.len());
+        libc::free(*buf);
+        *buf = outbuf;
+        *buf_size = output.len();
+    }
+    output.len()
+}
+
+fn run_can_apply(slot: usize, type_id: hid_t) -> htri_t {
+    let Some(registered) = registered(slot) else {
+        return -1;
+    };
+    let Some(can_apply) = &registered.filter.can_apply else {
+        return 1;
+    };
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let dtype = Datatype::from_handle(Handle::try_borrow(type_id).ok()?);
+        Some(can_apply(&dtype))
+    }))
+    .ok()
+    .flatten()
+    .map_or(-1, htri_t::from)
+}
+
+/// Registers a user-defined filter with the library, replacing any filter with the same id.
+///
+/// Datasets can then use the filter with [`Filter::User`](super::Filter::User) (or
+/// `add_filter()` on the dataset builder); the filter is found under the same id when reading
+/// them back.
+///
+/// # Errors
+///
+/// Returns an error if the id is not in the range 256 to 65535 reserved for third-party
+/// filters, if [`MAX_USER_FILTERS`] other user filters are already registered, or if the
+/// library fails to register the filter.
+pub fn register_user_filter(filter: UserFilter) -> Result<()> {
+    let id = filter.id;
+    ensure!(USER_FILTER_IDS.contains(&id), "user filter id {} is not in the range 256..=65535", id);
+    let name = to_cstring(filter.name.as_str())?;
+    // the library lock is taken before the registry, like in the filter callbacks
+    h5lock!({
+        let mut registry = REGISTRY.write();
+        let slot = registry
+            .iter()
+            .position(|entry| entry.as_ref().is_some_and(|entry| entry.filter.id == id))
+            .or_else(|| registry.iter().position(Option::is_none))
+            .ok_or_else(|| {
+                Error::from(format!("at most {MAX_USER_FILTERS} user filters can be registered"))
+            })?;
+        let (filter_fn, can_apply_fn) = TRAMPOLINES[slot];
+        let registered = Arc::new(Registered { filter, name });
+        let class = H5Z_class2_t {
+            version: H5Z_CLASS_T_VERS,
+            id,
+            encoder_present: 1,
+            decoder_present: 1,
+            name: registered.name.as_ptr(),
+            can_apply: registered.filter.can_apply.as_ref().map(|_| can_apply_fn),
+            set_local: None,
+            filter: Some(filter_fn),
+        };
+        let previous = registry[slot].replace(registered);
+        if let Err(err) = h5call!(H5Zregister(ptr::addr_of!(class).cast())) {
+            registry[slot] = previous;
+            return Err(err).with_context(|| format!("registering user filter {id}"));
+        }
+        Ok(())
+    })
+}
+
+/// Unregisters a filter registered with [`register_user_filter`].
+///
+/// # Errors
+///
+/// Returns an error if no user filter with this id is registered, or if the library refuses
+/// to unregister it (e.g. while objects using the filter are open).
+pub fn unregister_user_filter(id: H5Z_filter_t) -> Result<()> {
+    h5lock!({
+        let mut registry = REGISTRY.write();
+        let slot = registry
+            .iter()
+            .position(|entry| entry.as_ref().is_some_and(|entry| entry.filter.id == id))
+            .ok_or_else(|| Error::from(format!("user filter {id} is not registered")))?;
+        h5call!(H5Zunregister(id)).with_context(|| format!("unregistering user filter {id}"))?;
+        registry[slot] = None;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::hl::filters::Filter;
+
+    fn xor(data: &[u8], cdata: &[c_uint]) -> Result<Vec<u8>> {
+        let key = *cdata.first().ok_or("missing xor key")? as u8;
+        Ok(data.iter().map(|b| b ^ key).collect())
+    }
+
+    #[test]
+    pub fn test_user_filter_round_trip() {
+        const ID: H5Z_filter_t = 40123;
+        let encoded = Arc::new(AtomicUsize::new(0));
+        let checked = Arc::new(AtomicUsize::new(0));
+        let (encode_count, checked_size) = (Arc::clone(&encoded), Arc::clone(&checked));
+        let filter = UserFilter::new(
+            ID,
+            "xor",
+            move |data, cdata| {
+                encode_count.fetch_add(1, Ordering::SeqCst);
+                xor(data, cdata)
+            },
+            xor,
+        )
+        .can_apply(move |dtype| {
+            checked_size.store(dtype.size(), Ordering::SeqCst);
+            true
+        });
+        assert_eq!((filter.id(), filter.name()), (ID, "xor"));
+        register_user_filter(filter).unwrap();
+        assert!(Filter::user(ID, &[]).is_available());
+
+        with_tmp_file(|file| {
+            let data = (0..1000).collect::<Vec<i32>>();
+            let ds = file
+                .new_dataset_builder()
+                .chunk(100)
+                .set_filters(&[Filter::User(ID, vec![0x5a])])
+                .with_data(&data)
+                .create("xor")
+                .unwrap();
+            assert_eq!(ds.filters(), vec![Filter::User(ID, vec![0x5a])]);
+            assert_eq!(ds.read_raw::<i32>().unwrap(), data);
+            assert_eq!(checked.load(Ordering::SeqCst), 4);
+            assert!(encoded.load(Ordering::SeqCst) >= 10);
+            drop(ds);
+
+            let ds = file.dataset("xor").unwrap();
+            assert_eq!(ds.read_slice_1d::<i32, _>(150..250).unwrap().to_vec(), &data[150..250]);
+
+            assert_err!(unregister_user_filter(ID + 1), "user filter 40124 is not registered");
+        });
+        unregister_user_filter(ID).unwrap();
+        assert!(!Filter::user(ID, &[]).is_available());
+
+        assert_err!(
+            register_user_filter(UserFilter::new(7, "bad", xor, xor)),
+            "user filter id 7 is not in the range 256..=65535"
+        );
+    }
+
+    #[test]
+    pub fn test_user_filter_failures() {
+        const ID: H5Z_filter_t = 40124;
+        register_user_filter(UserFilter::new(
+            ID,
+            "broken",
+            |data, _| Ok(data.to_vec()),
+            |_, _| panic!("cannot decode"),
+        ))
+        .unwrap();
+
+        with_tmp_file(|file| {
+            let ds = file
+                .new_dataset_builder()
+                .chunk(10)
+                .set_filters(&[Filter::User(ID, vec![])])
+                .with_data(&[1_u8; 10])
+                .create("broken")
+                .unwrap();
+            // the panic is caught and turned into a filter failure, whose error stack can be
+            // expanded and formatted
+            let err = ds.read_raw::<u8>().unwrap_err();
+            let mut source = &err;
+            while let Error::Context { source: inner, .. } = source {
+                source = inner;
+            }
+            let Error::HDF5(stack) = source else { panic!("expected an HDF5 error, got {err}") };
+            let frames = stack.clone().expand().unwrap();
+            assert!(!frames.is_empty());
+            assert!(frames.iter().all(|frame| frame.detail().is_some()));
+            assert!(!err.to_string().is_empty());
+        });
+        unregister_user_filter(ID).unwrap();
+    }
+}
@@ -1,21 +1,31 @@
-use std::fmt::{self, Debug};
+use std::ffi::CStr;
+use std::fmt::{self, Debug, Display};
+use std::mem;
 use std::ops::Deref;
 use std::panic;
-use std::ptr::addr_of_mut;
+use std::path::Path;
+use std::ptr::{self, addr_of_mut};
 
 use crate::sys::{
     h5::{hsize_t, H5_index_t, H5_iter_order_t},
     h5d::H5Dopen2,
-    h5g::{H5G_info_t, H5Gcreate2, H5Gget_info, H5Gopen2},
+    h5g::{
+        H5G_info_t, H5Gcreate2, H5Gcreate_anon, H5Gget_create_plist, H5Gget_info, H5Gopen2,
+        H5G_STORAGE_TYPE_COMPACT, H5G_STORAGE_TYPE_DENSE, H5G_STORAGE_TYPE_SYMBOL_TABLE,
+    },
     h5l::{
         H5L_info_t, H5L_iterate_t, H5L_type_t, H5Lcreate_external, H5Lcreate_hard, H5Lcreate_soft,
-        H5Ldelete, H5Lexists, H5Literate, H5Lmove, H5L_SAME_LOC,
+        H5Ldelete, H5Lexists, H5Lget_info2, H5Lget_name_by_idx, H5Lget_val, H5Literate, H5Lmove,
+        H5Lunpack_elink_val, H5L_SAME_LOC,
     },
+    h5o::{H5Oopen, H5Oopen_by_idx},
     h5p::{H5Pcreate, H5Pset_create_intermediate_group},
     h5t::H5T_cset_t,
 };
 
 use crate::globals::H5P_LINK_CREATE;
+use crate::hl::plist::group_create::{GroupCreate, GroupCreateBuilder, LinkCreationOrder};
+use crate::hl::plist::link_access::LinkAccess;
 use crate::internal_prelude::*;
 use crate::{Location, LocationType};
 
@@ -52,6 +62,12 @@ impl Debug for Group {
     }
 }
 
+impl Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display_fmt(f)
+    }
+}
+
 impl Deref for Group {
     type Target = Location;
 
@@ -67,11 +83,213 @@ fn group_info(id: hid_t) -> Result<H5G_info_t> {
 
 fn make_lcpl() -> Result<PropertyList> {
     h5lock!({
-        let lcpl = PropertyList::from_id(h5try!(H5Pcreate(*H5P_LINK_CREATE)))?;
+        let lcpl = PropertyList::from_id(h5try!(H5Pcreate(H5P_LINK_CREATE.get()?)))?;
         h5call!(H5Pset_create_intermediate_group(lcpl.id(), 1)).and(Ok(lcpl))
     })
 }
 
+/// Checks the name of a group or dataset about to be created in `parent`.
+///
+/// Unlike attribute names, which are used verbatim, '/' separates the components of these
+/// names, so the last component cannot be empty, and the groups leading to it have to exist
+/// unless intermediate groups are created along the way.
+pub(crate) fn check_link_name(parent: hid_t, name: &str, create_intermediate: bool) -> Result<()> {
+    ensure!(!name.is_empty(), "object name is empty");
+    ensure!(
+        !name.ends_with('/'),
+        "invalid object name '{}': '/' separates the components of group and dataset paths, \
+         so the last component cannot be empty",
+        name
+    );
+    if create_intermediate {
+        return Ok(());
+    }
+    let mut end = 0;
+    while let Some(pos) = name[end..].find('/') {
+        end += pos;
+        let path = &name[..end];
+        end += 1;
+        if path.is_empty() || path.ends_with('/') {
+            continue;
+        }
+        let c_path = to_cstring(path)?;
+        let exists = h5call!(H5Lexists(parent, c_path.as_ptr(), H5P_DEFAULT)).is_ok_and(|e| e > 0);
+        ensure!(
+            exists,
+            "cannot create '{}': group '{}' does not exist ('/' separates the components of \
+             group and dataset paths; enable `create_intermediate_group` on the link creation \
+             property list to create missing groups)",
+            name,
+            path
+        );
+    }
+    Ok(())
+}
+
+/// Returns `true` if the link `path` exists in `parent`.
+///
+/// The groups leading to the link are checked first, since the library fails rather than
+/// returning `false` when one of them is missing.
+pub(crate) fn link_path_exists(parent: hid_t, path: &str) -> Result<bool> {
+    let ends = path.match_indices('/').map(|(pos, _)| pos).chain([path.len()]);
+    for end in ends {
+        let prefix = &path[..end];
+        if prefix.is_empty() || prefix.ends_with('/') {
+            continue;
+        }
+        let c_prefix = to_cstring(prefix)?;
+        if h5call!(H5Lexists(parent, c_prefix.as_ptr(), H5P_DEFAULT))? <= 0 {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+pub(crate) fn move_link(src_loc: &Group, src: &str, dst_loc: &Group, dst: &str) -> Result<()> {
+    let src = to_cstring(src)?;
+    let dst = to_cstring(dst)?;
+    h5call!(H5Lmove(
+        src_loc.id(),
+        src.as_ptr(),
+        dst_loc.id(),
+        dst.as_ptr(),
+        H5P_DEFAULT,
+        H5P_DEFAULT
+    ))
+    .and(Ok(()))
+}
+
+/// Builder used to create groups with custom group creation properties.
+///
+/// See [`GroupCreateBuilder`] and the [`group_create`](crate::plist::group_create) module
+/// for guidance on tuning groups that will hold many links.
+#[derive(Clone, Debug)]
+pub struct GroupBuilder {
+    parent: Result<Handle>,
+    gcpl: GroupCreateBuilder,
+}
+
+impl GroupBuilder {
+    /// Creates a new group builder for creating groups in `parent`.
+    pub fn new(parent: &Group) -> Self {
+        Self { parent: parent.try_borrow(), gcpl: GroupCreateBuilder::default() }
+    }
+
+    /// Replaces the group creation properties with those of an existing property list.
+    pub fn set_gcpl(mut self, gcpl: &GroupCreate) -> Result<Self> {
+        self.gcpl = GroupCreateBuilder::from_plist(gcpl)?;
+        Ok(self)
+    }
+
+    /// Allows accessing the builder object for the group creation property list.
+    pub fn with_gcpl<F>(mut self, func: F) -> Self
+    where
+        F: FnOnce(GroupCreateBuilder) -> GroupCreateBuilder,
+    {
+        self.gcpl = func(mem::take(&mut self.gcpl));
+        self
+    }
+
+    /// Sets the expected number of links in the group and the expected length of their names.
+    pub fn est_link_info(self, est_num_entries: u32, est_name_len: u32) -> Self {
+        self.with_gcpl(|p| p.est_link_info(est_num_entries, est_name_len))
+    }
+
+    /// Sets link storage phase change thresholds.
+    pub fn link_phase_change(self, max_compact: u32, min_dense: u32) -> Self {
+        self.with_gcpl(|p| p.link_phase_change(max_compact, min_dense))
+    }
+
+    /// Sets flags for tracking and indexing link creation order in the group.
+    pub fn link_creation_order(self, link_creation_order: LinkCreationOrder) -> Self {
+        self.with_gcpl(|p| p.link_creation_order(link_creation_order))
+    }
+
+    /// Creates the group `name`, creating intermediate groups as needed.
+    ///
+    /// As '/' separates path components, `name` cannot end with '/'.
+    pub fn create(self, name: &str) -> Result<Group> {
+        let parent = self.parent?;
+        parent.ensure_writable()?;
+        check_link_name(parent.id(), name, true)
+            .with_context(|| format!("creating group '{name}'"))?;
+        h5lock!({
+            let lcpl = make_lcpl()?;
+            let gcpl = self.gcpl.finish()?;
+            let c_name = to_cstring(name)?;
+            h5call!(H5Gcreate2(parent.id(), c_name.as_ptr(), lcpl.id(), gcpl.id(), H5P_DEFAULT))
+                .and_then(Group::from_id)
+                .with_context(|| format!("creating group '{name}'"))
+        })
+    }
+
+    /// Opens the group `name` if it exists, and creates it like [`create()`](Self::create)
+    /// otherwise; the creation properties only apply to a newly created group.
+    ///
+    /// Fails if `name` exists but is not a group. The check and the creation are done while
+    /// holding the library lock, so other threads of this process cannot race with them.
+    pub fn get_or_create(self, name: &str) -> Result<Group> {
+        h5lock!({
+            let parent = match &self.parent {
+                Ok(parent) => parent,
+                Err(_) => return self.create(name),
+            };
+            if !link_path_exists(parent.id(), name)? {
+                return self.create(name);
+            }
+            let c_name = to_cstring(name)?;
+            let obj = h5call!(H5Oopen(parent.id(), c_name.as_ptr(), H5P_DEFAULT))
+                .and_then(Object::from_id)
+                .with_context(|| format!("opening group '{name}'"))?;
+            ensure!(obj.id_type() == H5I_GROUP, "'{}' exists but is not a group", name);
+            obj.cast()
+        })
+    }
+}
+
+/// The way links are stored in a group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupStorageType {
+    /// Links are stored in a symbol table (the storage used before HDF5 1.8).
+    SymbolTable,
+    /// Links are stored compactly in the object header of the group.
+    Compact,
+    /// Links are stored in a fractal heap indexed by a B-tree.
+    Dense,
+    /// The storage type is unknown.
+    Unknown,
+}
+
+/// Information about the link storage of a group, as returned by [`Group::storage_info`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupStorageInfo {
+    /// The way links are stored in the group.
+    pub storage_type: GroupStorageType,
+    /// The number of links in the group.
+    pub nlinks: u64,
+    /// The current maximum creation order value of the links in the group.
+    pub max_corder: i64,
+    /// Whether a file is mounted on the group.
+    pub mounted: bool,
+}
+
+impl From<H5G_info_t> for GroupStorageInfo {
+    fn from(info: H5G_info_t) -> Self {
+        let storage_type = match info.storage_type {
+            H5G_STORAGE_TYPE_SYMBOL_TABLE => GroupStorageType::SymbolTable,
+            H5G_STORAGE_TYPE_COMPACT => GroupStorageType::Compact,
+            H5G_STORAGE_TYPE_DENSE => GroupStorageType::Dense,
+            _ => GroupStorageType::Unknown,
+        };
+        Self {
+            storage_type,
+            nlinks: info.nlinks as _,
+            max_corder: info.max_corder,
+            mounted: info.mounted > 0,
+        }
+    }
+}
+
 impl Group {
     /// Returns the number of objects in the container (or 0 if the container is invalid).
     pub fn len(&self) -> u64 {
@@ -86,23 +304,50 @@ impl Group {
     /// Create a new group in a file or group.
     pub fn create_group(&self, name: &str) -> Result<Self> {
         // TODO: &mut self?
-        h5lock!({
-            let lcpl = make_lcpl()?;
-            let name = to_cstring(name)?;
-            Self::from_id(h5try!(H5Gcreate2(
-                self.id(),
-                name.as_ptr(),
-                lcpl.id(),
-                H5P_DEFAULT,
-                H5P_DEFAULT
-            )))
-        })
+        self.new_group_builder().create(name)
+    }
+
+    /// Opens the group `name` if it exists, and creates it along with any missing intermediate
+    /// groups otherwise. See [`GroupBuilder::get_or_create()`].
+    pub fn get_or_create_group(&self, name: &str) -> Result<Self> {
+        self.new_group_builder().get_or_create(name)
+    }
+
+    /// Instantiates a new group builder, for creating groups with custom creation properties.
+    pub fn new_group_builder(&self) -> GroupBuilder {
+        GroupBuilder::new(self)
+    }
+
+    /// Returns a copy of the group creation property list.
+    pub fn create_plist(&self) -> Result<GroupCreate> {
+        h5lock!(GroupCreate::from_id(h5try!(H5Gget_create_plist(self.id()))))
+    }
+
+    /// A short alias for `create_plist()`.
+    pub fn gcpl(&self) -> Result<GroupCreate> {
+        self.create_plist()
+    }
+
+    /// Returns information about the link storage of the group.
+    pub fn storage_info(&self) -> Result<GroupStorageInfo> {
+        group_info(self.id()).map(Into::into)
     }
 
     /// Opens an existing group in a file or group.
     pub fn group(&self, name: &str) -> Result<Self> {
-        let name = to_cstring(name)?;
-        Self::from_id(h5try!(H5Gopen2(self.id(), name.as_ptr(), H5P_DEFAULT)))
+        let c_name = to_cstring(name)?;
+        h5call!(H5Gopen2(self.id(), c_name.as_ptr(), H5P_DEFAULT))
+            .and_then(Self::from_id)
+            .with_context(|| format!("opening group '{name}'"))
+    }
+
+    /// Opens an existing group, traversing links with the given link access properties (e.g.
+    /// an [external link prefix](crate::plist::LinkAccessBuilder::elink_prefix)).
+    pub fn group_with(&self, name: &str, lapl: &LinkAccess) -> Result<Self> {
+        let c_name = to_cstring(name)?;
+        h5call!(H5Oopen(self.id(), c_name.as_ptr(), lapl.id()))
+            .and_then(Self::from_id)
+            .with_context(|| format!("opening group '{name}'"))
     }
 
     /// Creates a soft link.
@@ -111,6 +356,7 @@ impl Group {
     /// Note: `target` and `link_name` are relative to the current object.
     pub fn link_soft(&self, target: &str, link_name: &str) -> Result<()> {
         // TODO: &mut self?
+        self.handle().ensure_writable()?;
         h5lock!({
             let lcpl = make_lcpl()?;
             let target = to_cstring(target)?;
@@ -129,6 +375,7 @@ impl Group {
     /// Creates a hard link. Note: `target` and `link_name` are relative to the current object.
     pub fn link_hard(&self, target: &str, link_name: &str) -> Result<()> {
         // TODO: &mut self?
+        self.handle().ensure_writable()?;
         let target = to_cstring(target)?;
         let link_name = to_cstring(link_name)?;
         h5call!(H5Lcreate_hard(
@@ -150,15 +397,16 @@ impl Group {
     ///
     /// For a detailed explanation on how `target_file_name` is resolved, see
     /// [https://portal.hdfgroup.org/display/HDF5/H5L_CREATE_EXTERNAL](https://portal.hdfgroup.org/display/HDF5/H5L_CREATE_EXTERNAL)
-    pub fn link_external(
+    pub fn link_external<P: AsRef<Path>>(
         &self,
-        target_file_name: &str,
+        target_file_name: P,
         target: &str,
         link_name: &str,
     ) -> Result<()> {
         // TODO: &mut self?
+        self.handle().ensure_writable()?;
         let target = to_cstring(target)?;
-        let target_file_name = to_cstring(target_file_name)?;
+        let target_file_name = path_to_cstring(target_file_name)?;
         let link_name = to_cstring(link_name)?;
         h5call!(H5Lcreate_external(
             target_file_name.as_ptr(),
@@ -174,6 +422,7 @@ impl Group {
     /// Relinks an object. Note: `name` and `path` are relative to the current object.
     pub fn relink(&self, name: &str, path: &str) -> Result<()> {
         // TODO: &mut self?
+        self.handle().ensure_writable()?;
         let name = to_cstring(name)?;
         let path = to_cstring(path)?;
         h5call!(H5Lmove(
@@ -187,9 +436,81 @@ impl Group {
         .and(Ok(()))
     }
 
+    /// Renames the link `src` of this group to `dst`, keeping its position in creation order.
+    ///
+    /// [`relink`](Self::relink) gives the renamed link a new creation order, which moves it to
+    /// the end of creation-order iteration. If link creation order is tracked in the group,
+    /// this instead moves `src` and all links created after it into a temporary anonymous
+    /// group and back in their original order, with `src` renamed to `dst`; this costs two
+    /// link moves per link created after `src`. The creation order values of the moved links
+    /// change (see [`creation_order_of`](Self::creation_order_of)), but their relative order
+    /// does not.
+    ///
+    /// If a link cannot be moved, the links moved so far are moved back under their original
+    /// names, possibly out of order, and the error is returned.
+    ///
+    /// If creation order is not tracked, this is a plain [`relink`](Self::relink).
+    pub fn rename_preserving_order(&self, src: &str, dst: &str) -> Result<()> {
+        for name in [src, dst] {
+            ensure!(!name.is_empty() && !name.contains('/'), "invalid link name '{}'", name);
+        }
+        self.handle().ensure_writable()?;
+        h5lock!({
+            let Some(order) = self.link_info(src)?.creation_order else {
+                return self.relink(src, dst);
+            };
+            ensure!(!self.link_exists(dst), "link '{}' already exists", dst);
+            let mut tail = self.iter_visit_default(vec![], |_, name, info, tail| {
+                match info.creation_order {
+                    Some(corder) if corder > order => tail.push((corder, name.to_owned())),
+                    _ => (),
+                }
+                true
+            })?;
+            tail.sort();
+            let mut links = vec![(src.to_owned(), dst.to_owned())];
+            links.extend(tail.into_iter().map(|(_, name)| (name.clone(), name)));
+            let tmp = Self::from_id(h5try!(H5Gcreate_anon(self.id(), H5P_DEFAULT, H5P_DEFAULT)))?;
+            for (i, (old, new)) in links.iter().enumerate() {
+                if let Err(err) = move_link(self, old, &tmp, new) {
+                    // restore the links moved so far, giving up on their order
+                    for (old, new) in &links[..i] {
+                        let _ = move_link(&tmp, new, self, old);
+                    }
+                    return Err(err);
+                }
+            }
+            for (i, (_, new)) in links.iter().enumerate() {
+                if let Err(err) = move_link(&tmp, new, self, new) {
+                    // move back the remaining links under their old names and undo the rename,
+                    // so that no link is left behind or renamed, giving up on their order
+                    for (old, new) in &links[i..] {
+                        let _ = move_link(&tmp, new, self, old);
+                    }
+                    if i > 0 {
+                        let _ = move_link(self, dst, self, src);
+                    }
+                    return Err(err);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns the creation order of the link `name`.
+    ///
+    /// Fails if link creation order is not tracked in the group.
+    pub fn creation_order_of(&self, name: &str) -> Result<u64> {
+        match self.link_info(name)?.creation_order {
+            Some(order) => Ok(order as _),
+            None => fail!("link creation order is not tracked for '{}'", name),
+        }
+    }
+
     /// Removes a link to an object from this file or group.
     pub fn unlink(&self, name: &str) -> Result<()> {
         // TODO: &mut self?
+        self.handle().ensure_writable()?;
         let name = to_cstring(name)?;
         h5call!(H5Ldelete(self.id(), name.as_ptr(), H5P_DEFAULT)).and(Ok(()))
     }
@@ -203,6 +524,82 @@ impl Group {
         .unwrap_or(false)
     }
 
+    /// Returns information about the link `name` without following it.
+    pub fn link_info(&self, name: &str) -> Result<LinkInfo> {
+        let name = to_cstring(name)?;
+        let mut info = mem::MaybeUninit::<H5L_info_t>::uninit();
+        h5try!(H5Lget_info2(self.id(), name.as_ptr(), info.as_mut_ptr(), H5P_DEFAULT));
+        Ok((&unsafe { info.assume_init() }).into())
+    }
+
+    /// Returns the target of a soft or external link (`None` for hard links).
+    pub(crate) fn link_target(&self, name: &str) -> Result<Option<LinkTarget>> {
+        let c_name = to_cstring(name)?;
+        h5lock!({
+            let mut info = mem::MaybeUninit::<H5L_info_t>::uninit();
+            h5try!(H5Lget_info2(self.id(), c_name.as_ptr(), info.as_mut_ptr(), H5P_DEFAULT));
+            let info = unsafe { info.assume_init() };
+            if info.type_ == H5L_type_t::H5L_TYPE_HARD {
+                return Ok(None);
+            }
+            let size = unsafe { info.u.val_size };
+            let mut buf = vec![0_u8; size];
+            h5try!(H5Lget_val(
+                self.id(),
+                c_name.as_ptr(),
+                buf.as_mut_ptr().cast(),
+                size,
+                H5P_DEFAULT
+            ));
+            if info.type_ == H5L_type_t::H5L_TYPE_SOFT {
+                let path = CStr::from_bytes_until_nul(&buf).map_err(|err| err.to_string())?;
+                return Ok(Some(LinkTarget::Soft(path.to_string_lossy().into_owned())));
+            }
+            ensure!(
+                info.type_ == H5L_type_t::H5L_TYPE_EXTERNAL,
+                "unsupported user-defined link '{}'",
+                name
+            );
+            let (mut file, mut path) = (ptr::null(), ptr::null());
+            h5try!(H5Lunpack_elink_val(
+                buf.as_ptr().cast(),
+                size,
+                ptr::null_mut(),
+                &mut file,
+                &mut path
+            ));
+            ensure!(!file.is_null() && !path.is_null(), "invalid external link '{}'", name);
+            let (file, path) = unsafe { (string_from_cstr(file), string_from_cstr(path)) };
+            Ok(Some(LinkTarget::External { file, path }))
+        })
+    }
+
+    /// Deletes the link `name` along with everything that is only reachable through it.
+    ///
+    /// Unlike [`unlink`](Self::unlink), which only removes the link itself, this visits the
+    /// members of a group depth-first and unlinks them too, so that nested groups, datasets and
+    /// named datatypes don't keep their object headers alive. Groups that are also reachable
+    /// through other hard links are unlinked without touching their members, and soft or
+    /// external links are never followed.
+    ///
+    /// Note that HDF5 does not shrink files when objects are deleted; the space is reused for
+    /// new objects, or can be reclaimed with [`File::repack_into`](crate::File::repack_into).
+    pub fn delete_recursive(&self, name: &str) -> Result<()> {
+        self.handle().ensure_writable()?;
+        h5lock!({
+            if self.link_info(name)?.link_type == LinkType::Hard {
+                let info = self.loc_info_by_name(name)?;
+                if info.loc_type == LocationType::Group && info.num_links == 1 {
+                    let group = self.group(name)?;
+                    for member in group.member_names()? {
+                        group.delete_recursive(&member)?;
+                    }
+                }
+            }
+            self.unlink(name)
+        })
+    }
+
     /// Instantiates a new typed dataset builder.
     pub fn new_dataset<T: H5Type>(&self) -> DatasetBuilderEmpty {
         self.new_dataset_builder().empty::<T>()
@@ -215,8 +612,96 @@ impl Group {
 
     /// Opens an existing dataset in the file or group.
     pub fn dataset(&self, name: &str) -> Result<Dataset> {
-        let name = to_cstring(name)?;
-        Dataset::from_id(h5try!(H5Dopen2(self.id(), name.as_ptr(), H5P_DEFAULT)))
+        let c_name = to_cstring(name)?;
+        h5call!(H5Dopen2(self.id(), c_name.as_ptr(), H5P_DEFAULT))
+            .and_then(Dataset::from_id)
+            .with_context(|| format!("opening dataset '{name}'"))
+    }
+
+    /// Opens an existing dataset, traversing links with the given link access properties
+    /// (e.g. an [external link prefix](crate::plist::LinkAccessBuilder::elink_prefix)).
+    pub fn dataset_with(&self, name: &str, lapl: &LinkAccess) -> Result<Dataset> {
+        let c_name = to_cstring(name)?;
+        h5call!(H5Oopen(self.id(), c_name.as_ptr(), lapl.id()))
+            .and_then(Dataset::from_id)
+            .with_context(|| format!("opening dataset '{name}'"))
+    }
+
+    /// Opens the `idx`-th member of the group and returns its link name along with the object.
+    ///
+    /// Using [`TraversalOrder::Creation`] requires link creation order to be tracked in the
+    /// group, otherwise the library will return an error.
+    pub fn member_by_index(
+        &self,
+        idx: usize,
+        index_type: TraversalOrder,
+        order: IterationOrder,
+    ) -> Result<(String, ObjectHandleKind)> {
+        h5lock!({
+            let count = self.len();
+            ensure!(
+                (idx as u64) < count,
+                "member index {} is out of range: group has {} members",
+                idx,
+                count
+            );
+            let dot = to_cstring(".")?;
+            let (index_type, order, n) = (index_type.into(), order.into(), idx as hsize_t);
+            let name = get_h5_str(|m, s| {
+                H5Lget_name_by_idx(self.id(), dot.as_ptr(), index_type, order, n, m, s, H5P_DEFAULT)
+            })?;
+            let id =
+                h5try!(H5Oopen_by_idx(self.id(), dot.as_ptr(), index_type, order, n, H5P_DEFAULT));
+            let obj = ObjectHandleKind::from_location(Location::from_id(id)?)?;
+            Ok((name, obj))
+        })
+    }
+}
+
+/// The objects in a group, as returned by [`Group::list_members`].
+#[derive(Clone, Debug, Default)]
+pub struct GroupMembers {
+    /// Link names and handles of the objects, in iteration order.
+    pub members: Vec<(String, ObjectHandleKind)>,
+    /// Descriptions of the symbolic links that were skipped because their targets could not
+    /// be opened.
+    pub warnings: Vec<String>,
+}
+
+impl IntoIterator for GroupMembers {
+    type Item = (String, ObjectHandleKind);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.members.into_iter()
+    }
+}
+
+/// Target of a symbolic link.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum LinkTarget {
+    /// Path of the target object within the same file.
+    Soft(String),
+    /// File name and object path of the target of an external link.
+    External { file: String, path: String },
+}
+
+/// An object opened through a group, resolved to its concrete type.
+#[derive(Clone, Debug)]
+pub enum ObjectHandleKind {
+    Group(Group),
+    Dataset(Dataset),
+    Datatype(Datatype),
+}
+
+impl ObjectHandleKind {
+    pub(crate) fn from_location(loc: Location) -> Result<Self> {
+        Ok(match loc.id_type() {
+            H5I_GROUP => Self::Group(unsafe { loc.cast_unchecked() }),
+            H5I_DATASET => Self::Dataset(unsafe { loc.cast_unchecked() }),
+            H5I_DATATYPE => Self::Datatype(unsafe { loc.cast_unchecked() }),
+            id_type => fail!("unexpected object type: {:?}", id_type),
+        })
     }
 }
 
@@ -241,6 +726,26 @@ impl From<TraversalOrder> for H5_index_t {
     }
 }
 
+/// Order in which operations that process many objects (such as repacking a file or copying
+/// attributes) visit them, so that their output does not depend on incidental iteration order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DeterministicOrder {
+    /// Creation order where it is tracked, name order otherwise.
+    #[default]
+    Creation,
+    /// Name order, even where creation order is tracked.
+    Name,
+}
+
+impl DeterministicOrder {
+    pub(crate) fn traversal(self, creation_tracked: bool) -> TraversalOrder {
+        match self {
+            Self::Creation if creation_tracked => TraversalOrder::Creation,
+            _ => TraversalOrder::Name,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum IterationOrder {
     Increasing,
@@ -319,6 +824,7 @@ impl Group {
         struct Vtable<'a, F, D> {
             f: &'a mut F,
             d: &'a mut D,
+            error: Option<Error>,
         }
         // Maps a closure to a C callback
         //
@@ -335,13 +841,17 @@ impl Group {
             panic::catch_unwind(|| {
                 let vtable = op_data.cast::<Vtable<F, G>>();
                 let vtable = unsafe { vtable.as_mut().expect("iter_visit: null op_data ptr") };
-                unsafe { name.as_ref().expect("iter_visit: null name ptr") };
-                let name = unsafe { std::ffi::CStr::from_ptr(name) };
+                let name = match unsafe { name_from_cstr(name, "link") } {
+                    Ok(name) => name,
+                    Err(err) => {
+                        vtable.error = Some(err);
+                        return -1;
+                    }
+                };
                 let info = unsafe { info.as_ref().expect("iter_visit: null info ptr") };
                 let handle = Handle::try_borrow(id).expect("iter_visit: unable to create a handle");
                 let group = Group::from_handle(handle);
-                let ret =
-                    (vtable.f)(&group, name.to_string_lossy().as_ref(), info.into(), vtable.d);
+                let ret = (vtable.f)(&group, &name, info.into(), vtable.d);
                 i32::from(!ret)
             })
             .unwrap_or(-1)
@@ -351,18 +861,22 @@ impl Group {
         let iter_pos: *mut hsize_t = &mut 0_u64;
 
         // Store our references on the heap
-        let mut vtable = Vtable { f: &mut op, d: &mut val };
+        let mut vtable = Vtable { f: &mut op, d: &mut val, error: None };
         let other_data = addr_of_mut!(vtable).cast::<c_void>();
 
-        h5call!(H5Literate(
+        // A single call visits all links; the callback never stops and restarts the iteration.
+        let result = h5call!(H5Literate(
             self.id(),
             traversal_order.into(),
             iteration_order.into(),
             iter_pos,
             callback_fn,
             other_data
-        ))
-        .map(|_| val)
+        ));
+        if let Some(err) = vtable.error {
+            return Err(err).with_context(|| format!("listing links of group '{}'", self.name()));
+        }
+        result.map(|_| val)
     }
 
     /// Visits all objects in the group using default iteration/traversal order.
@@ -373,43 +887,84 @@ impl Group {
         self.iter_visit(IterationOrder::default(), TraversalOrder::default(), val, op)
     }
 
-    fn get_all_of_type(&self, loc_type: LocationType) -> Result<Vec<Location>> {
-        self.iter_visit_default(vec![], |group, name, _info, objects| {
-            if let Ok(info) = group.loc_info_by_name(name) {
-                if info.loc_type == loc_type {
-                    if let Ok(loc) = group.open_by_token(info.token) {
-                        objects.push(loc);
-                        return true; // ok, object extracted and pushed
-                    }
-                } else {
-                    return true; // ok, object is of another type, skipped
+    /// Opens all objects in the group, non-recursively, in a single pass over its links.
+    ///
+    /// Symbolic links whose targets cannot be opened (such as dangling soft links) are
+    /// skipped and reported in [`GroupMembers::warnings`]; failing to open the target of a
+    /// hard link is an error.
+    pub fn list_members(&self) -> Result<GroupMembers> {
+        let capacity = listing_capacity(self.len());
+        let visited = h5lock!(self.iter_visit_default(
+            Vec::with_capacity(capacity),
+            |group, name, info, visited| {
+                let obj = group.loc_info_by_name(name).and_then(|info| {
+                    ObjectHandleKind::from_location(group.open_by_token(info.token)?)
+                });
+                visited.push((name.to_owned(), info.link_type, obj));
+                true
+            }
+        ))?;
+        let mut listing = GroupMembers::default();
+        for (name, link_type, obj) in visited {
+            match obj {
+                Ok(obj) => listing.members.push((name, obj)),
+                Err(err) if link_type != LinkType::Hard => {
+                    listing.warnings.push(format!("skipped {link_type:?} link '{name}': {err}"));
                 }
+                Err(err) => return Err(err).with_context(|| format!("opening member '{name}'")),
             }
-            false // an error occurred somewhere along the way
-        })
+        }
+        Ok(listing)
+    }
+
+    /// Returns the names and handles of all objects in the group, non-recursively.
+    ///
+    /// See [`list_members`](Self::list_members); symbolic links that cannot be resolved are
+    /// skipped.
+    pub fn members(&self) -> Result<Vec<(String, ObjectHandleKind)>> {
+        self.list_members().map(|listing| listing.members)
     }
 
-    /// Returns all groups in the group, non-recursively
-    pub fn groups(&self) -> Result<Vec<Self>> {
-        self.get_all_of_type(LocationType::Group)
-            .map(|vec| vec.into_iter().map(|obj| unsafe { obj.cast_unchecked() }).collect())
+    /// Returns the names and handles of all groups in the group, non-recursively.
+    pub fn groups(&self) -> Result<Vec<(String, Self)>> {
+        Ok(self
+            .list_members()?
+            .into_iter()
+            .filter_map(|(name, obj)| match obj {
+                ObjectHandleKind::Group(group) => Some((name, group)),
+                _ => None,
+            })
+            .collect())
     }
 
-    /// Returns all datasets in the group, non-recursively
-    pub fn datasets(&self) -> Result<Vec<Dataset>> {
-        self.get_all_of_type(LocationType::Dataset)
-            .map(|vec| vec.into_iter().map(|obj| unsafe { obj.cast_unchecked() }).collect())
+    /// Returns the names and handles of all datasets in the group, non-recursively.
+    pub fn datasets(&self) -> Result<Vec<(String, Dataset)>> {
+        Ok(self
+            .list_members()?
+            .into_iter()
+            .filter_map(|(name, obj)| match obj {
+                ObjectHandleKind::Dataset(dataset) => Some((name, dataset)),
+                _ => None,
+            })
+            .collect())
     }
 
     /// Returns all named types in the group, non-recursively
     pub fn named_datatypes(&self) -> Result<Vec<Datatype>> {
-        self.get_all_of_type(LocationType::NamedDatatype)
-            .map(|vec| vec.into_iter().map(|obj| unsafe { obj.cast_unchecked() }).collect())
+        Ok(self
+            .list_members()?
+            .into_iter()
+            .filter_map(|(_, obj)| match obj {
+                ObjectHandleKind::Datatype(datatype) => Some(datatype),
+                _ => None,
+            })
+            .collect())
     }
 
     /// Returns the names of all objects in the group, non-recursively.
     pub fn member_names(&self) -> Result<Vec<String>> {
-        self.iter_visit_default(vec![], |_, name, _, names| {
+        let capacity = listing_capacity(self.len());
+        self.iter_visit_default(Vec::with_capacity(capacity), |_, name, _, names| {
             names.push(name.to_owned());
             true
         })
@@ -418,6 +973,7 @@ impl Group {
 
 #[cfg(test)]
 pub mod tests {
+    use crate::hl::group::LinkTarget;
     use crate::internal_prelude::*;
 
     #[test]
@@ -468,6 +1024,40 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_member_by_index() {
+        use crate::file::LinkCreationOrder;
+        with_tmp_path(|path| {
+            let file = File::with_options()
+                .with_fcpl(|p| {
+                    p.link_creation_order(LinkCreationOrder::TRACKED | LinkCreationOrder::INDEXED)
+                })
+                .create(&path)
+                .unwrap();
+            file.create_group("zeta").unwrap();
+            file.new_dataset::<u8>().create("alpha").unwrap();
+            file.create_group("mu").unwrap();
+            let names = |index, order| {
+                (0..3).map(|i| file.member_by_index(i, index, order).unwrap().0).collect::<Vec<_>>()
+            };
+            let (crt, name) = (TraversalOrder::Creation, TraversalOrder::Name);
+            assert_eq!(names(crt, IterationOrder::Increasing), ["zeta", "alpha", "mu"]);
+            assert_eq!(names(crt, IterationOrder::Decreasing), ["mu", "alpha", "zeta"]);
+            assert_eq!(names(name, IterationOrder::Increasing), ["alpha", "mu", "zeta"]);
+            assert_eq!(names(name, IterationOrder::Decreasing), ["zeta", "mu", "alpha"]);
+
+            let (name, obj) = file.member_by_index(1, crt, IterationOrder::Increasing).unwrap();
+            assert_eq!(name, "alpha");
+            assert!(matches!(obj, ObjectHandleKind::Dataset(ds) if ds.name() == "/alpha"));
+            let (_, obj) = file.member_by_index(0, crt, IterationOrder::Increasing).unwrap();
+            assert!(matches!(obj, ObjectHandleKind::Group(g) if g.name() == "/zeta"));
+            assert_err!(
+                file.member_by_index(3, crt, IterationOrder::Increasing),
+                "member index 3 is out of range: group has 3 members"
+            );
+        })
+    }
+
     #[test]
     pub fn test_clone() {
         with_tmp_file(|file| {
@@ -504,6 +1094,51 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_storage_info() {
+        use crate::hl::group::GroupStorageType;
+        with_tmp_path(|path| {
+            let file = File::with_options().with_fapl(|p| p.libver_v18()).create(&path).unwrap();
+            let info = file.create_group("small").unwrap().storage_info().unwrap();
+            assert_eq!(info.storage_type, GroupStorageType::Compact);
+            assert_eq!(info.nlinks, 0);
+            assert!(!info.mounted);
+
+            let group = file
+                .new_group_builder()
+                .est_link_info(100_000, 12)
+                .link_phase_change(16, 8)
+                .create("large")
+                .unwrap();
+            let gcpl = group.gcpl().unwrap();
+            assert_eq!(gcpl.est_link_info().est_num_entries, 100_000);
+            assert_eq!(gcpl.link_phase_change().max_compact, 16);
+            // links are kept in the object header up to max_compact, and indexed by name in a
+            // B-tree once there are more, so insertions and lookups do not scan all links
+            for i in 0..10_000 {
+                if i == 16 {
+                    assert_eq!(
+                        group.storage_info().unwrap().storage_type,
+                        GroupStorageType::Compact
+                    );
+                }
+                group.new_dataset::<u8>().shape(1).create(format!("ds_{i:06}").as_str()).unwrap();
+                if i == 16 {
+                    assert_eq!(group.storage_info().unwrap().storage_type, GroupStorageType::Dense);
+                }
+            }
+            let info = group.storage_info().unwrap();
+            assert_eq!(info.storage_type, GroupStorageType::Dense);
+            assert_eq!(info.nlinks, 10_000);
+            assert!(group.link_exists("ds_009999"));
+
+            // without the new-style format, groups use a symbol table
+            let file = File::create(path.with_extension("old.h5")).unwrap();
+            let group = file.new_group_builder().link_phase_change(0, 0).create("g").unwrap();
+            assert_eq!(group.storage_info().unwrap().storage_type, GroupStorageType::SymbolTable);
+        })
+    }
+
     #[test]
     pub fn test_link_hard() {
         with_tmp_file(|file| {
@@ -581,18 +1216,53 @@ pub mod tests {
         with_tmp_file(|file| {
             file.create_group("test").unwrap();
             file.group("test").unwrap();
-            assert_err!(
-                file.relink("test", "foo/test"),
-                "unable to move link: component not found"
-            );
+            assert_eq!(file.relink("test", "foo/test").unwrap_err().kind(), ErrorKind::NotFound);
             file.create_group("foo").unwrap();
-            assert_err!(file.relink("bar", "/baz"), "unable to move link: name doesn't exist");
+            assert_eq!(file.relink("bar", "/baz").unwrap_err().kind(), ErrorKind::NotFound);
             file.relink("test", "/foo/test").unwrap();
             file.group("/foo/test").unwrap();
-            assert_err_re!(
-                file.group("test"),
-                "unable to (?:synchronously )?open group: object.+doesn't exist"
-            );
+            assert_eq!(file.group("test").unwrap_err().kind(), ErrorKind::NotFound);
+        })
+    }
+
+    #[test]
+    pub fn test_rename_preserving_order() {
+        use crate::file::LinkCreationOrder;
+        with_tmp_path(|path| {
+            let file = File::with_options()
+                .with_fcpl(|p| p.link_creation_order(LinkCreationOrder::TRACKED))
+                .create(&path)
+                .unwrap();
+            file.create_group("a").unwrap();
+            file.new_dataset_builder().with_data(&[1, 2, 3]).create("b").unwrap();
+            file.create_group("c").unwrap();
+            file.rename_preserving_order("b", "z").unwrap();
+            let names = (0..3)
+                .map(|i| {
+                    file.member_by_index(i, TraversalOrder::Creation, IterationOrder::Increasing)
+                        .unwrap()
+                        .0
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["a", "z", "c"]);
+            let orders =
+                names.iter().map(|n| file.creation_order_of(n).unwrap()).collect::<Vec<_>>();
+            assert!(orders.windows(2).all(|w| w[0] < w[1]));
+            assert!(!file.link_exists("b"));
+            assert_eq!(file.dataset("z").unwrap().read_raw::<i32>().unwrap(), [1, 2, 3]);
+            assert_err!(file.rename_preserving_order("a", "c"), "link 'c' already exists");
+            assert_err!(file.rename_preserving_order("a", "x/y"), "invalid link name 'x/y'");
+            assert!(file.rename_preserving_order("missing", "x").is_err());
+            assert_eq!(file.len(), 3);
+
+            // without tracked creation order, this is a plain relink
+            let group = file.create_group("untracked").unwrap();
+            for name in ["a", "b", "c"] {
+                group.create_group(name).unwrap();
+            }
+            assert_err!(group.creation_order_of("a"), "link creation order is not tracked for 'a'");
+            group.rename_preserving_order("b", "z").unwrap();
+            assert_eq!(group.member_names().unwrap(), ["a", "c", "z"]);
         })
     }
 
@@ -606,6 +1276,34 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_delete_recursive() {
+        with_tmp_file(|file| {
+            file.create_group("a/b/c").unwrap();
+            file.new_dataset::<u8>().shape(4).create("a/b/c/ds").unwrap();
+            file.new_dataset::<u8>().shape(4).create("a/ds").unwrap();
+            file.link_hard("a/b", "shared").unwrap();
+            file.link_soft("/keep", "a/soft").unwrap();
+            file.create_group("keep").unwrap();
+            assert_eq!(file.link_info("a/soft").unwrap().link_type, LinkType::Soft);
+            assert_eq!(file.link_target("a/soft").unwrap(), Some(LinkTarget::Soft("/keep".into())));
+            assert_eq!(file.link_target("keep").unwrap(), None);
+
+            file.delete_recursive("a").unwrap();
+            assert!(!file.link_exists("a"));
+            // "a/b" is also linked as "shared", so its members must survive
+            file.dataset("shared/c/ds").unwrap();
+            file.group("keep").unwrap();
+
+            file.delete_recursive("shared").unwrap();
+            assert_eq!(file.member_names().unwrap(), vec!["keep"]);
+            assert_err_re!(
+                file.delete_recursive("missing"),
+                "unable to (?:synchronously )?get link info"
+            );
+        })
+    }
+
     #[test]
     pub fn test_dataset() {
         with_tmp_file(|file| {
@@ -618,6 +1316,32 @@ pub mod tests {
         });
     }
 
+    #[test]
+    pub fn test_slash_in_link_names() {
+        with_tmp_file(|file| {
+            // intermediate groups are created by default
+            file.new_dataset::<u8>().create("a/b/c").unwrap();
+            assert!(file.group("a/b").is_ok());
+            file.create_group("x/y").unwrap();
+            assert!(file.group("x").is_ok());
+
+            // without them, a missing parent is reported as such
+            let err = file
+                .new_dataset::<u8>()
+                .create_intermediate_group(false)
+                .create("missing/d")
+                .unwrap_err();
+            assert!(err.to_string().contains("group 'missing' does not exist"), "{err}");
+            assert!(!file.link_exists("missing"));
+            file.new_dataset::<u8>().create_intermediate_group(false).create("a/d").unwrap();
+
+            // a trailing '/' never names an object
+            assert_err!(file.create_group("g/"), "last component cannot be empty");
+            assert_err!(file.new_dataset::<u8>().create("d/"), "last component cannot be empty");
+            assert_err!(file.create_group(""), "empty");
+        })
+    }
+
     #[test]
     pub fn test_get_member_names() {
         with_tmp_file(|file| {
@@ -663,6 +1387,60 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_external_link_cache_and_prefix() {
+        use crate::plist::LinkAccess;
+        use std::fs;
+
+        with_tmp_dir(|dir| {
+            for i in 0..5 {
+                let file = File::create(dir.join(format!("f{i}.h5"))).unwrap();
+                file.new_dataset::<i32>().create("x").unwrap().write_scalar(&i).unwrap();
+            }
+            let file = File::create(dir.join("main.h5")).unwrap();
+            for i in 0..5 {
+                file.link_external(&format!("f{i}.h5"), "x", &format!("e{i}")).unwrap();
+            }
+            drop(file);
+
+            let copy_dir = dir.join("copy");
+            fs::create_dir(&copy_dir).unwrap();
+            for i in 0..5 {
+                let name = format!("f{i}.h5");
+                fs::copy(dir.join(&name), copy_dir.join(&name)).unwrap();
+                let file = File::open_rw(copy_dir.join(&name)).unwrap();
+                file.dataset("x").unwrap().write_scalar(&(i + 100)).unwrap();
+            }
+
+            let file = File::with_options()
+                .with_fapl(|p| p.elink_file_cache_size(2))
+                .open(dir.join("main.h5"))
+                .unwrap();
+            assert_eq!(file.fapl().unwrap().elink_file_cache_size(), 2);
+            for _ in 0..2 {
+                for i in 0..5 {
+                    let ds = file.dataset(&format!("e{i}")).unwrap();
+                    assert_eq!(ds.read_scalar::<i32>().unwrap(), i);
+                }
+                file.clear_external_link_cache().unwrap();
+            }
+
+            // the prefix takes precedence over the directory of the main file
+            let lapl =
+                LinkAccess::build().elink_prefix(copy_dir.to_str().unwrap()).finish().unwrap();
+            assert_eq!(lapl.elink_prefix(), copy_dir.to_str().unwrap());
+            for i in 0..5 {
+                let ds = file.dataset_with(&format!("e{i}"), &lapl).unwrap();
+                assert_eq!(ds.read_scalar::<i32>().unwrap(), i + 100);
+            }
+            assert_eq!(file.dataset("e0").unwrap().read_scalar::<i32>().unwrap(), 0);
+            assert_err!(file.group_with("e0", &lapl), "opening group 'e0'");
+            file.create_group("g").unwrap();
+            assert!(file.group_with("g", &lapl).is_ok());
+            assert_err!(file.dataset_with("missing", &lapl), "opening dataset 'missing'");
+        })
+    }
+
     #[test]
     pub fn test_iterators() {
         with_tmp_file(|file| {
@@ -676,7 +1454,8 @@ pub mod tests {
 
             let groups = file.groups().unwrap();
             assert_eq!(groups.len(), 2);
-            for group in groups {
+            for (name, group) in groups {
+                assert_eq!(group.name(), format!("/{name}"));
                 assert!(matches!(group.name().as_ref(), "/a" | "/b"));
             }
 
@@ -685,9 +1464,96 @@ pub mod tests {
 
             let datasets = group_a.datasets().unwrap();
             assert_eq!(datasets.len(), 3);
-            for dataset in datasets {
+            for (name, dataset) in datasets {
+                assert_eq!(dataset.name(), format!("/a/{name}"));
                 assert!(matches!(dataset.name().as_ref(), "/a/foo" | "/a/123" | "/a/bar"));
             }
         })
     }
+
+    #[test]
+    pub fn test_list_members() {
+        with_tmp_file(|file| {
+            let group = file.create_group("g").unwrap();
+            for name in ["x", "y", "z"] {
+                group.new_dataset::<i32>().shape(4).create(name).unwrap();
+            }
+            group.create_group("sub1").unwrap();
+            group.create_group("sub2").unwrap();
+            let dtype = Datatype::from_type::<f64>().unwrap();
+            let name = to_cstring("dtype").unwrap();
+            h5call!(crate::sys::h5t::H5Tcommit2(
+                group.id(),
+                name.as_ptr(),
+                dtype.id(),
+                H5P_DEFAULT,
+                H5P_DEFAULT,
+                H5P_DEFAULT
+            ))
+            .unwrap();
+            group.link_soft("/g/missing", "dangling").unwrap();
+
+            let listing = group.list_members().unwrap();
+            assert_eq!(listing.members.len(), 6);
+            assert_eq!(listing.warnings.len(), 1);
+            assert!(listing.warnings[0].contains("'dangling'"));
+            assert_eq!(group.members().unwrap().len(), 6);
+            assert_eq!(group.named_datatypes().unwrap().len(), 1);
+
+            let datasets = group.datasets().unwrap();
+            let names: Vec<_> = datasets.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(names, ["x", "y", "z"]);
+            for (_, dataset) in &datasets {
+                dataset.write(&[1, 2, 3, 4]).unwrap();
+                assert_eq!(dataset.read_raw::<i32>().unwrap(), [1, 2, 3, 4]);
+            }
+            let groups = group.groups().unwrap();
+            let names: Vec<_> = groups.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(names, ["sub1", "sub2"]);
+            for (name, sub) in &groups {
+                sub.create_group("inner").unwrap();
+                assert_eq!(sub.member_names().unwrap(), ["inner"]);
+                assert_eq!(sub.name(), format!("/g/{name}"));
+            }
+            assert!(
+                listing
+                    .into_iter()
+                    .any(|(name, obj)| name == "dtype"
+                        && matches!(obj, ObjectHandleKind::Datatype(_)))
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_listing_is_linear() {
+        use crate::sys::link_iterate_calls;
+
+        with_tmp_file(|file| {
+            // each listing is collected in a single H5Literate pass over the links
+            let check_listing = |group: &Group, n: usize| {
+                for i in 0..n {
+                    group.link_soft("/target", &format!("link{i:05}")).unwrap();
+                }
+                let calls = link_iterate_calls();
+                let names = group.member_names().unwrap();
+                assert_eq!(link_iterate_calls() - calls, 1);
+                assert_eq!(names.len(), n);
+                assert_eq!(names[n - 1], format!("link{:05}", n - 1));
+            };
+            check_listing(&file.create_group("small").unwrap(), 10_000);
+            check_listing(&file.create_group("large").unwrap(), 20_000);
+
+            let group = file.group("large").unwrap();
+            let calls = link_iterate_calls();
+            assert_eq!(group.list_members().unwrap().warnings.len(), 20_000);
+            assert_eq!(link_iterate_calls() - calls, 1);
+
+            for i in 0..1000 {
+                group.new_attr::<u8>().create(format!("attr{i:04}").as_str()).unwrap();
+            }
+            let names = group.attr_names().unwrap();
+            assert_eq!(names.len(), 1000);
+            assert_eq!(names[999], "attr0999");
+        })
+    }
 }
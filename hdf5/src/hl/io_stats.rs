@@ -0,0 +1,439 @@
+//! Opt-in per-dataset I/O statistics and a chunk cache advisor.
+//!
+//! HDF5 does not report chunk cache hit rates, so the statistics are collected in this crate:
+//! once [`Dataset::enable_io_stats`] is called, every read and write going through [`Reader`]
+//! and [`Writer`] on that dataset handle records the bounding box of its file selection,
+//! whether the selection is aligned with chunk boundaries and which chunks it touches.
+//! [`Dataset::io_stats`] summarizes the recorded requests and [`IoStats::suggest_chunk_cache`]
+//! derives chunk cache parameters from them.
+//!
+//! The statistics are attached to the dataset handle and shared by its clones; a dataset opened
+//! again by name starts without statistics.
+//!
+//! [`Reader`]: crate::Reader
+//! [`Writer`]: crate::Writer
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt::{self, Display};
+
+use crate::hl::plist::dataset_access::ChunkCache;
+use crate::hl::selection::RawSelection;
+use crate::internal_prelude::*;
+
+/// Maximum number of distinct chunks remembered; further chunks are no longer counted.
+const MAX_TRACKED_CHUNKS: usize = 1 << 20;
+
+/// Number of most recent requests kept in [`IoStats::recent`].
+const MAX_RECENT_REQUESTS: usize = 64;
+
+/// Upper bound for the suggested number of hash table slots.
+const MAX_SUGGESTED_NSLOTS: usize = 1_000_003;
+
+/// Direction of a recorded I/O request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IoKind {
+    Read,
+    Write,
+}
+
+/// A single recorded read or write.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IoRequest {
+    /// Whether the request was a read or a write.
+    pub kind: IoKind,
+    /// Half-open `(start, end)` bounds of the file selection along each axis.
+    pub bounds: Vec<(Ix, Ix)>,
+    /// Number of selected elements.
+    pub elements: usize,
+    /// Number of bytes transferred, in the memory datatype.
+    pub bytes: usize,
+    /// Whether the selection bounds coincide with chunk boundaries along every axis (always
+    /// `false` for datasets that are not chunked).
+    pub chunk_aligned: bool,
+    /// Number of chunks touched by the request (chunks intersecting the bounding box for
+    /// hyperslab selections).
+    pub chunks: usize,
+}
+
+/// Estimated risk of the chunk cache evicting chunks still needed by a single request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ThrashRisk {
+    /// The chunks touched by a request fit into the cache with plenty of hash slots.
+    Low,
+    /// The chunks fit into the cache bytes, but there are too few hash slots for them, so
+    /// colliding chunks evict each other.
+    Moderate,
+    /// The chunks touched by a request do not fit into the cache (or a single chunk is larger
+    /// than the cache and bypasses it).
+    High,
+}
+
+impl ThrashRisk {
+    /// Assesses the risk for requests touching `chunks` chunks of `chunk_bytes` bytes each.
+    pub fn assess(chunks: usize, chunk_bytes: usize, cache: &ChunkCache) -> Self {
+        if chunks == 0 || chunk_bytes == 0 {
+            return Self::Low;
+        }
+        let capacity = cache.nbytes / chunk_bytes;
+        if chunks > capacity {
+            Self::High
+        } else if chunks.saturating_mul(10) > cache.nslots {
+            Self::Moderate
+        } else {
+            Self::Low
+        }
+    }
+}
+
+impl Display for ThrashRisk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Low => "low",
+            Self::Moderate => "moderate",
+            Self::High => "high",
+        })
+    }
+}
+
+/// Summary of the I/O requests recorded on a dataset handle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IoStats {
+    /// Total number of recorded requests.
+    pub requests: u64,
+    /// Number of recorded reads.
+    pub reads: u64,
+    /// Number of recorded writes.
+    pub writes: u64,
+    /// Total number of bytes transferred.
+    pub bytes: u64,
+    /// Number of requests whose selection was aligned with chunk boundaries.
+    pub aligned_requests: u64,
+    /// Number of distinct chunks touched by all requests.
+    pub distinct_chunks: usize,
+    /// Whether more than the tracked maximum of distinct chunks were touched, in which case
+    /// `distinct_chunks` is a lower bound.
+    pub distinct_chunks_saturated: bool,
+    /// Sum over all requests of the chunks touched by each request.
+    pub chunk_visits: u64,
+    /// Largest number of chunks touched by a single request.
+    pub max_chunks_per_request: usize,
+    /// Chunk shape, if the dataset is chunked.
+    pub chunk: Option<Vec<Ix>>,
+    /// Size of a chunk in bytes, in the file datatype (0 if not chunked).
+    pub chunk_bytes: usize,
+    /// Chunk cache parameters of the dataset access property list.
+    pub cache: ChunkCache,
+    /// The most recent requests, oldest first.
+    pub recent: Vec<IoRequest>,
+}
+
+impl IoStats {
+    /// Average number of chunks touched per request.
+    pub fn mean_chunks_per_request(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.chunk_visits as f64 / self.requests as f64
+        }
+    }
+
+    /// Estimates the cache thrash risk of the largest request against the configured cache.
+    pub fn thrash_risk(&self) -> ThrashRisk {
+        ThrashRisk::assess(self.max_chunks_per_request, self.chunk_bytes, &self.cache)
+    }
+
+    /// Suggests chunk cache parameters for the observed access pattern.
+    ///
+    /// The cache is sized to hold the chunks touched by the largest request (but never smaller
+    /// than the library default), with a prime number of hash slots of about 100 times the
+    /// number of chunks fitting into it. If every request was chunk-aligned and a write,
+    /// fully written chunks are preempted first (`w0 = 1`). Returns `None` if the dataset is
+    /// not chunked or nothing was recorded.
+    pub fn suggest_chunk_cache(&self) -> Option<ChunkCache> {
+        if self.chunk_bytes == 0 || self.requests == 0 {
+            return None;
+        }
+        let default = ChunkCache::default();
+        let nbytes =
+            self.max_chunks_per_request.saturating_mul(self.chunk_bytes).max(default.nbytes);
+        let fitting = nbytes / self.chunk_bytes;
+        let nslots =
+            next_prime(fitting.saturating_mul(100).clamp(default.nslots, MAX_SUGGESTED_NSLOTS));
+        let w0 = if self.aligned_requests == self.requests && self.reads == 0 {
+            1.0
+        } else {
+            self.cache.w0
+        };
+        Some(ChunkCache { nslots, nbytes, w0 })
+    }
+}
+
+impl Display for IoStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} requests ({} reads, {} writes, {} aligned), {} bytes, {}{} distinct chunks, \
+             {:.1} chunks per request (max {}), thrash risk {}",
+            self.requests,
+            self.reads,
+            self.writes,
+            self.aligned_requests,
+            self.bytes,
+            if self.distinct_chunks_saturated { ">=" } else { "" },
+            self.distinct_chunks,
+            self.mean_chunks_per_request(),
+            self.max_chunks_per_request,
+            self.thrash_risk(),
+        )
+    }
+}
+
+/// Recorder shared by a dataset handle and its clones.
+#[derive(Debug)]
+pub(crate) struct IoRecorder {
+    chunk: Option<Vec<Ix>>,
+    chunk_bytes: usize,
+    requests: u64,
+    reads: u64,
+    writes: u64,
+    bytes: u64,
+    aligned_requests: u64,
+    chunk_visits: u64,
+    max_chunks_per_request: usize,
+    chunks: HashSet<Vec<Ix>>,
+    saturated: bool,
+    recent: VecDeque<IoRequest>,
+}
+
+impl IoRecorder {
+    pub(crate) fn new(chunk: Option<Vec<Ix>>, element_size: usize) -> Self {
+        let chunk_bytes = chunk.as_ref().map_or(0, |c| c.iter().product::<Ix>() * element_size);
+        Self {
+            chunk,
+            chunk_bytes,
+            requests: 0,
+            reads: 0,
+            writes: 0,
+            bytes: 0,
+            aligned_requests: 0,
+            chunk_visits: 0,
+            max_chunks_per_request: 0,
+            chunks: HashSet::new(),
+            saturated: false,
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Records a request on the file selection `fspace` (the whole dataspace `space` if `None`).
+    pub(crate) fn record(
+        &mut self,
+        kind: IoKind,
+        space: &Dataspace,
+        fspace: Option<&Dataspace>,
+        element_size: usize,
+    ) -> Result<()> {
+        let shape = space.shape()?;
+        let (selection, elements) = match fspace {
+            Some(fspace) => (fspace.get_raw_selection()?, fspace.selection_size()),
+            None => (RawSelection::All, space.size()?),
+        };
+        let mut touched = HashSet::new();
+        let bounds = match &selection {
+            RawSelection::None => vec![],
+            RawSelection::Points(points) => {
+                let mut bounds = vec![(Ix::MAX, 0); shape.len()];
+                for point in points.rows() {
+                    for (b, &x) in bounds.iter_mut().zip(point) {
+                        *b = (b.0.min(x), b.1.max(x + 1));
+                    }
+                    if let Some(chunk) = &self.chunk {
+                        touched.insert(point.iter().zip(chunk).map(|(x, c)| x / c).collect());
+                    }
+                }
+                bounds
+            }
+            RawSelection::RegularHyperslab(hyper) => hyper
+                .iter()
+                .zip(&shape)
+                .map(|(s, &n)| {
+                    let end = s.count.map_or(n, |count| {
+                        if count == 0 {
+                            s.start
+                        } else {
+                            s.start + (count - 1) * s.step + s.block
+                        }
+                    });
+                    (s.start, end)
+                })
+                .collect(),
+            RawSelection::All | RawSelection::ComplexHyperslab => {
+                shape.iter().map(|&n| (0, n)).collect()
+            }
+        };
+        let empty = elements == 0 || bounds.iter().any(|(start, end)| start >= end);
+
+        let (chunk_aligned, chunks) = match &self.chunk {
+            Some(chunk) if !empty => {
+                let aligned =
+                    bounds.iter().zip(chunk).zip(&shape).all(|(((start, end), c), &n)| {
+                        start % c == 0 && (end % c == 0 || *end == n)
+                    });
+                if !matches!(selection, RawSelection::Points(_)) {
+                    let limit = MAX_TRACKED_CHUNKS.saturating_sub(self.chunks.len());
+                    self.saturated |= !chunks_in_box(&bounds, chunk, limit, &mut touched);
+                }
+                let count = if touched.is_empty() {
+                    bounds
+                        .iter()
+                        .zip(chunk)
+                        .map(|((start, end), c)| (end - 1) / c - start / c + 1)
+                        .product()
+                } else {
+                    touched.len()
+                };
+                (aligned, count)
+            }
+            _ => (false, 0),
+        };
+        for chunk in touched {
+            if self.chunks.len() >= MAX_TRACKED_CHUNKS {
+                self.saturated = true;
+                break;
+            }
+            self.chunks.insert(chunk);
+        }
+
+        let bytes = elements * element_size;
+        self.requests += 1;
+        match kind {
+            IoKind::Read => self.reads += 1,
+            IoKind::Write => self.writes += 1,
+        }
+        self.bytes += bytes as u64;
+        self.aligned_requests += u64::from(chunk_aligned);
+        self.chunk_visits += chunks as u64;
+        self.max_chunks_per_request = self.max_chunks_per_request.max(chunks);
+        if self.recent.len() == MAX_RECENT_REQUESTS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(IoRequest { kind, bounds, elements, bytes, chunk_aligned, chunks });
+        Ok(())
+    }
+
+    pub(crate) fn snapshot(&self, cache: ChunkCache) -> IoStats {
+        IoStats {
+            requests: self.requests,
+            reads: self.reads,
+            writes: self.writes,
+            bytes: self.bytes,
+            aligned_requests: self.aligned_requests,
+            distinct_chunks: self.chunks.len(),
+            distinct_chunks_saturated: self.saturated,
+            chunk_visits: self.chunk_visits,
+            max_chunks_per_request: self.max_chunks_per_request,
+            chunk: self.chunk.clone(),
+            chunk_bytes: self.chunk_bytes,
+            cache,
+            recent: self.recent.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Collects the chunk indices intersecting the bounding box, unless there are more than `limit`
+/// of them; returns `false` in that case (the chunk count is then computed from the box).
+fn chunks_in_box(
+    bounds: &[(Ix, Ix)],
+    chunk: &[Ix],
+    limit: usize,
+    out: &mut HashSet<Vec<Ix>>,
+) -> bool {
+    let lo: Vec<Ix> = bounds.iter().zip(chunk).map(|((start, _), c)| start / c).collect();
+    let hi: Vec<Ix> = bounds.iter().zip(chunk).map(|((_, end), c)| (end - 1) / c).collect();
+    let count = lo.iter().zip(&hi).fold(1usize, |n, (l, h)| n.saturating_mul(h - l + 1));
+    if count > limit {
+        return false;
+    }
+    let mut index = lo.clone();
+    loop {
+        out.insert(index.clone());
+        let mut axis = index.len();
+        loop {
+            if axis == 0 {
+                return true;
+            }
+            axis -= 1;
+            if index[axis] < hi[axis] {
+                index[axis] += 1;
+                break;
+            }
+            index[axis] = lo[axis];
+        }
+    }
+}
+
+fn next_prime(n: usize) -> usize {
+    let is_prime = |n: usize| n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0);
+    (n..).find(|&n| is_prime(n)).unwrap_or(n)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ndarray::{s, Array2};
+
+    use super::{next_prime, ChunkCache, ThrashRisk, MAX_SUGGESTED_NSLOTS};
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_thrash_risk() {
+        let cache = ChunkCache { nslots: 521, nbytes: 1024 * 1024, w0: 0.75 };
+        assert_eq!(ThrashRisk::assess(0, 800, &cache), ThrashRisk::Low);
+        assert_eq!(ThrashRisk::assess(10, 800, &cache), ThrashRisk::Low);
+        assert_eq!(ThrashRisk::assess(100, 800, &cache), ThrashRisk::Moderate);
+        assert_eq!(ThrashRisk::assess(2000, 800, &cache), ThrashRisk::High);
+        assert_eq!(ThrashRisk::assess(1, 2 * 1024 * 1024, &cache), ThrashRisk::High);
+        assert_eq!(next_prime(521), 521);
+        assert_eq!(next_prime(1000), 1009);
+    }
+
+    #[test]
+    pub fn test_io_stats() {
+        with_tmp_file(|file| {
+            let ds =
+                file.new_dataset::<f64>().shape((100, 100)).chunk((10, 10)).create("data").unwrap();
+            assert!(ds.io_stats().is_none());
+            ds.enable_io_stats();
+            ds.write(&Array2::<f64>::zeros((100, 100))).unwrap();
+            let _: Array2<f64> = ds.read_slice(s![0..10, 0..10]).unwrap();
+            let _: Array2<f64> = ds.read_slice(s![5..25, 0..10]).unwrap();
+            let _: Vec<f64> = ds.read_raw().unwrap();
+
+            let stats = ds.clone().io_stats().unwrap();
+            assert_eq!((stats.requests, stats.reads, stats.writes), (4, 3, 1));
+            assert_eq!(stats.bytes, 8 * (10_000 + 100 + 200 + 10_000));
+            assert_eq!(stats.aligned_requests, 3);
+            assert_eq!(stats.distinct_chunks, 100);
+            assert_eq!(stats.max_chunks_per_request, 100);
+            assert_eq!(stats.chunk_visits, 100 + 1 + 3 + 100);
+            assert_eq!(stats.chunk_bytes, 800);
+            assert_eq!(stats.recent.len(), 4);
+            assert_eq!(stats.recent[2].bounds, vec![(5, 25), (0, 10)]);
+            assert!(!stats.recent[2].chunk_aligned);
+            assert_eq!(stats.recent[2].chunks, 3);
+            assert_eq!(stats.thrash_risk(), ThrashRisk::Moderate);
+
+            let suggestion = stats.suggest_chunk_cache().unwrap();
+            assert!(suggestion.nbytes >= 100 * 800);
+            assert!(suggestion.nslots >= 10 * 100 && suggestion.nslots <= MAX_SUGGESTED_NSLOTS);
+            assert_eq!(suggestion.w0, stats.cache.w0);
+            assert_eq!(ThrashRisk::assess(100, 800, &suggestion), ThrashRisk::Low);
+
+            let contiguous = file.new_dataset::<i32>().shape(10).create("flat").unwrap();
+            contiguous.enable_io_stats();
+            contiguous.write(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]).unwrap();
+            let stats = contiguous.io_stats().unwrap();
+            assert_eq!((stats.requests, stats.bytes, stats.distinct_chunks), (1, 40, 0));
+            assert_eq!(stats.thrash_risk(), ThrashRisk::Low);
+            assert!(stats.suggest_chunk_cache().is_none());
+        })
+    }
+}
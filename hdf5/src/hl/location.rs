@@ -11,17 +11,20 @@ use crate::sys::h5o::{
     H5Oget_info_by_name3, H5Oopen_by_addr, H5Oopen_by_token, H5O_INFO_BASIC, H5O_INFO_NUM_ATTRS,
     H5O_INFO_TIME,
 };
+use crate::sys::{api_table, haddr_t, ObjectInfoApi};
 use crate::sys::{
-    h5a::{H5Adelete, H5Aopen},
+    h5::{H5_index_t, H5_iter_order_t},
+    h5a::{H5Adelete, H5Adelete_by_idx, H5Aopen, H5Aopen_by_idx},
     h5f::H5Fget_name,
     h5i::{H5Iget_file_id, H5Iget_name},
     h5o::{H5O_type_t, H5Oget_comment},
 };
-use crate::sys::{haddr_t, hdf5_version_at_least};
+use hdf5_types::OwnedDynValue;
 
 use crate::internal_prelude::*;
 
-use super::attribute::AttributeBuilderEmpty;
+use super::attribute::{write_scalar_attrs, AttributeBuilderEmpty};
+use super::group::IterationOrder;
 
 /// Named location (file, group, dataset, named datatype).
 #[repr(transparent)]
@@ -68,6 +71,16 @@ impl Location {
         h5lock!(get_h5_str(|m, s| H5Iget_name(self.id(), m, s)).unwrap_or_else(|_| String::new()))
     }
 
+    /// Returns the absolute path of the object within the file (`"/"` for the root group).
+    ///
+    /// Unlike [`name()`](Self::name), this fails for objects that have no path, i.e.
+    /// anonymous objects and objects whose last link has been removed.
+    pub fn path(&self) -> Result<String> {
+        let path = h5lock!(get_h5_str(|m, s| H5Iget_name(self.id(), m, s)))?;
+        ensure!(!path.is_empty(), "object has no path in the file (anonymous or unlinked)");
+        Ok(path)
+    }
+
     /// Returns the name of the file containing the named object (or the file itself).
     pub fn filename(&self) -> String {
         // TODO: should this return Result<String> or an empty string if it fails?
@@ -90,6 +103,7 @@ impl Location {
     #[deprecated(note = "attributes are preferred to comments")]
     pub fn set_comment(&self, comment: &str) -> Result<()> {
         // TODO: &mut self?
+        self.handle().ensure_writable()?;
         let comment = to_cstring(comment)?;
         #[allow(deprecated)]
         h5call!(H5Oset_comment(self.id(), comment.as_ptr())).and(Ok(()))
@@ -99,6 +113,7 @@ impl Location {
     #[deprecated(note = "attributes are preferred to comments")]
     pub fn clear_comment(&self) -> Result<()> {
         // TODO: &mut self?
+        self.handle().ensure_writable()?;
         #[allow(deprecated)]
         h5call!(H5Oset_comment(self.id(), ptr::null_mut())).and(Ok(()))
     }
@@ -115,8 +130,36 @@ impl Location {
 
     /// Create a new named attribute on the object.
     pub fn attr(&self, name: &str) -> Result<Attribute> {
-        let name = to_cstring(name)?;
-        Attribute::from_id(h5try!(H5Aopen(self.id(), name.as_ptr(), H5P_DEFAULT)))
+        let c_name = to_cstring(name)?;
+        h5call!(H5Aopen(self.id(), c_name.as_ptr(), H5P_DEFAULT))
+            .and_then(Attribute::from_id)
+            .with_context(|| format!("opening attribute '{name}'"))
+    }
+
+    /// Opens the `idx`-th attribute of the object by creation order.
+    ///
+    /// Attribute creation order has to be tracked on the object for this to succeed
+    /// (see `attr_creation_order()` on the creation property list builders).
+    pub fn attr_by_index(&self, idx: usize, order: IterationOrder) -> Result<Attribute> {
+        h5lock!({
+            let count = self.loc_info()?.num_attrs;
+            ensure!(
+                idx < count,
+                "attribute index {} is out of range: object has {} attributes",
+                idx,
+                count
+            );
+            let dot = to_cstring(".")?;
+            Attribute::from_id(h5try!(H5Aopen_by_idx(
+                self.id(),
+                dot.as_ptr(),
+                H5_index_t::H5_INDEX_CRT_ORDER,
+                order.into(),
+                idx as _,
+                H5P_DEFAULT,
+                H5P_DEFAULT
+            )))
+        })
     }
 
     /// Return the names of all attributes on the object.
@@ -128,12 +171,121 @@ impl Location {
         Attribute::attr_names(self)
     }
 
+    /// Writes many scalar attributes at once, creating those that do not exist yet.
+    ///
+    /// The attributes are written in the order of their names with the library lock
+    /// acquired once, which is much faster than separate calls when writing many small
+    /// attributes onto the same object. An existing attribute of the same type is
+    /// overwritten in place; one of another type or shape is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a name occurs twice or a library call fails, in which case the
+    /// attributes before the failing one (in name order) have been written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> hdf5_rt::Result<()> {
+    /// use hdf5_rt::types::OwnedDynValue;
+    ///
+    /// let file = hdf5_rt::File::create("attrs.h5")?;
+    /// file.write_attrs(&[("step", OwnedDynValue::from(10_u64)), ("dt", 0.1.into())])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_attrs(&self, attrs: &[(&str, OwnedDynValue)]) -> Result<()> {
+        write_scalar_attrs(self, attrs, false).map(drop)
+    }
+
+    /// Like [`write_attrs`](Self::write_attrs), but skips the attributes that already hold
+    /// the given value (compared after reading them), leaving the object header untouched.
+    ///
+    /// Returns the number of attributes that were written.
+    pub fn write_attrs_if_changed(&self, attrs: &[(&str, OwnedDynValue)]) -> Result<usize> {
+        write_scalar_attrs(self, attrs, true)
+    }
+
+    /// Writes scalar attributes of a single type from a map or another sequence of pairs,
+    /// see [`write_attrs`](Self::write_attrs).
+    pub fn write_attrs_typed<I, K, T>(&self, attrs: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (K, T)>,
+        K: AsRef<str>,
+        T: H5Type,
+    {
+        let (names, values): (Vec<K>, Vec<OwnedDynValue>) =
+            attrs.into_iter().map(|(name, value)| (name, value.into())).unzip();
+        let attrs: Vec<_> = names.iter().map(AsRef::as_ref).zip(values).collect();
+        self.write_attrs(&attrs)
+    }
+
     pub fn delete_attr(&self, name: &str) -> Result<()> {
+        self.handle().ensure_writable()?;
         let name = to_cstring(name)?;
         h5call!(H5Adelete(self.id(), name.as_ptr()))?;
         Ok(())
     }
 
+    /// Returns the number of attributes on the object.
+    pub fn attr_count(&self) -> Result<usize> {
+        self.loc_info().map(|info| info.num_attrs)
+    }
+
+    /// Deletes the `idx`-th attribute of the object by creation order.
+    ///
+    /// Attribute creation order has to be tracked on the object for this to succeed.
+    /// Deleting an attribute shifts the indices of all attributes after it, so indices
+    /// obtained before the deletion (e.g. while iterating) no longer refer to the same
+    /// attributes.
+    pub fn delete_attr_by_index(&self, idx: usize, order: IterationOrder) -> Result<()> {
+        self.handle().ensure_writable()?;
+        h5lock!({
+            let count = self.attr_count()?;
+            ensure!(
+                idx < count,
+                "attribute index {} is out of range: object has {} attributes",
+                idx,
+                count
+            );
+            let dot = to_cstring(".")?;
+            h5call!(H5Adelete_by_idx(
+                self.id(),
+                dot.as_ptr(),
+                H5_index_t::H5_INDEX_CRT_ORDER,
+                order.into(),
+                idx as _,
+                H5P_DEFAULT
+            ))
+            .and(Ok(()))
+        })
+    }
+
+    /// Deletes all attributes of the object.
+    ///
+    /// Attributes are removed one at a time from the front of the name index (which, unlike
+    /// the creation order index, always exists), so none are skipped as the remaining ones
+    /// shift down. The library lock is held throughout.
+    pub fn clear_attrs(&self) -> Result<()> {
+        self.handle().ensure_writable()?;
+        h5lock!({
+            let dot = to_cstring(".")?;
+            for _ in 0..self.attr_count()? {
+                h5try!(H5Adelete_by_idx(
+                    self.id(),
+                    dot.as_ptr(),
+                    H5_index_t::H5_INDEX_NAME,
+                    H5_iter_order_t::H5_ITER_INC,
+                    0,
+                    H5P_DEFAULT
+                ));
+            }
+            let count = self.attr_count()?;
+            ensure!(count == 0, "{} attributes left after clearing", count);
+            Ok(())
+        })
+    }
+
     /// Returns the object's metadata.
     pub fn loc_info(&self) -> Result<LocationInfo> {
         H5O_get_info(self.id(), true)
@@ -169,6 +321,11 @@ impl Location {
         H5O_open_by_token(self.id(), token)
     }
 
+    /// Returns the identifier of the object, equal for all handles to it.
+    pub fn object_id(&self) -> Result<ObjectId> {
+        Ok(ObjectId::from(&H5O_get_info(self.id(), false)?))
+    }
+
     /// Generate a [object reference](ObjectReference) to the object for a reference storage.
     ///
     /// This can be a group, dataset or datatype. Other objects are not supported.
@@ -202,6 +359,7 @@ impl Location {
         ocpypl: Option<&PropertyList>,
         lcpl: Option<&PropertyList>,
     ) -> Result<()> {
+        dst_loc.handle().ensure_writable()?;
         // Validate property list classes if provided
         if let Some(pl) = ocpypl {
             if !pl.is_class(PropertyListClass::ObjectCopy) {
@@ -234,7 +392,8 @@ impl Location {
 ///
 /// In HDF5 < 1.12, this is an address (`haddr_t`).
 /// In HDF5 >= 1.12, this is a token (`H5O_token_t`).
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LocationToken {
     /// Address-based identifier (HDF5 < 1.12)
     Address(haddr_t),
@@ -242,6 +401,42 @@ pub enum LocationToken {
     Token(H5O_token_t),
 }
 
+/// An identifier of an HDF5 object, usable as a key for "the same object" regardless of the
+/// link it was opened through.
+///
+/// It pairs the number of the file the object lives in with the object's address or token
+/// (see [`LocationToken`]). Two handles to the same object have equal identifiers, and
+/// [`File::open_by_id`] opens the object again.
+///
+/// The file number is only stable while the library keeps the file open; comparing
+/// identifiers taken in different sessions, or persisting them, is only meaningful for the
+/// [`token`](Self::token) alone, which stays valid for the lifetime of the object in the file
+/// (on HDF5 >= 1.12, tokens are opaque and may be reused once the object is deleted).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectId {
+    fileno: u64,
+    token: LocationToken,
+}
+
+impl ObjectId {
+    /// Returns the number the library assigned to the file containing the object.
+    pub fn fileno(&self) -> u64 {
+        self.fileno
+    }
+
+    /// Returns the address or token of the object within its file.
+    pub fn token(&self) -> LocationToken {
+        self.token
+    }
+}
+
+impl From<&LocationInfo> for ObjectId {
+    fn from(info: &LocationInfo) -> Self {
+        Self { fileno: info.fileno, token: info.token }
+    }
+}
+
 /// The type of an object in a [`Location`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LocationType {
@@ -343,51 +538,42 @@ fn info_fields(full: bool) -> c_uint {
 
 #[allow(non_snake_case)]
 fn H5O_get_info(loc_id: hid_t, full: bool) -> Result<LocationInfo> {
-    if hdf5_version_at_least(1, 12, 0) {
-        // HDF5 >= 1.12: Use H5Oget_info3 with H5O_info2_t
-        let mut info_buf: MaybeUninit<H5O_info2_t> = MaybeUninit::uninit();
-        let info_ptr = info_buf.as_mut_ptr();
-        h5call!(H5Oget_info3(loc_id, info_ptr, info_fields(full)))?;
-        let info = unsafe { info_buf.assume_init() };
-        Ok(LocationInfo::from_info2(info))
-    } else {
-        // HDF5 < 1.12: Use H5Oget_info1 with H5O_info1_t
-        // Note: H5Oget_info1 does NOT have a fields parameter (only 2 params)
-        let mut info_buf: MaybeUninit<H5O_info1_t> = MaybeUninit::uninit();
-        let info_ptr = info_buf.as_mut_ptr();
-        let result = unsafe { H5Oget_info1(loc_id, info_ptr) };
-        match result {
-            Some(ret) if ret >= 0 => {
-                let info = unsafe { info_buf.assume_init() };
-                Ok(LocationInfo::from_info1(info))
-            }
-            Some(_) => Err(Error::query()?),
-            None => fail!("H5Oget_info1 not available"),
+    match api_table().object_info {
+        ObjectInfoApi::V3 => {
+            let mut info_buf: MaybeUninit<H5O_info2_t> = MaybeUninit::uninit();
+            let info_ptr = info_buf.as_mut_ptr();
+            h5call!(H5Oget_info3(loc_id, info_ptr, info_fields(full)))?;
+            let info = unsafe { info_buf.assume_init() };
+            Ok(LocationInfo::from_info2(info))
+        }
+        ObjectInfoApi::V1 => {
+            // Note: H5Oget_info1 does NOT have a fields parameter (only 2 params)
+            let mut info_buf: MaybeUninit<H5O_info1_t> = MaybeUninit::uninit();
+            let info_ptr = info_buf.as_mut_ptr();
+            h5call!(H5Oget_info1(loc_id, info_ptr)?)?;
+            let info = unsafe { info_buf.assume_init() };
+            Ok(LocationInfo::from_info1(info))
         }
     }
 }
 
 #[allow(non_snake_case)]
 fn H5O_get_info_by_name(loc_id: hid_t, name: *const c_char, _full: bool) -> Result<LocationInfo> {
-    if hdf5_version_at_least(1, 12, 0) {
-        let mut info_buf: MaybeUninit<H5O_info2_t> = MaybeUninit::uninit();
-        let info_ptr = info_buf.as_mut_ptr();
-        h5call!(H5Oget_info_by_name3(loc_id, name, info_ptr, info_fields(_full), H5P_DEFAULT))?;
-        let info = unsafe { info_buf.assume_init() };
-        Ok(LocationInfo::from_info2(info))
-    } else {
-        // HDF5 < 1.12: Use H5Oget_info_by_name1 with H5O_info1_t
-        // Note: H5Oget_info_by_name1 does NOT have a fields parameter (only 4 params)
-        let mut info_buf: MaybeUninit<H5O_info1_t> = MaybeUninit::uninit();
-        let info_ptr = info_buf.as_mut_ptr();
-        let result = unsafe { H5Oget_info_by_name1(loc_id, name, info_ptr, H5P_DEFAULT) };
-        match result {
-            Some(ret) if ret >= 0 => {
-                let info = unsafe { info_buf.assume_init() };
-                Ok(LocationInfo::from_info1(info))
-            }
-            Some(_) => Err(Error::query()?),
-            None => fail!("H5Oget_info_by_name1 not available"),
+    match api_table().object_info {
+        ObjectInfoApi::V3 => {
+            let mut info_buf: MaybeUninit<H5O_info2_t> = MaybeUninit::uninit();
+            let info_ptr = info_buf.as_mut_ptr();
+            h5call!(H5Oget_info_by_name3(loc_id, name, info_ptr, info_fields(_full), H5P_DEFAULT))?;
+            let info = unsafe { info_buf.assume_init() };
+            Ok(LocationInfo::from_info2(info))
+        }
+        ObjectInfoApi::V1 => {
+            // Note: H5Oget_info_by_name1 does NOT have a fields parameter (only 4 params)
+            let mut info_buf: MaybeUninit<H5O_info1_t> = MaybeUninit::uninit();
+            let info_ptr = info_buf.as_mut_ptr();
+            h5call!(H5Oget_info_by_name1(loc_id, name, info_ptr, H5P_DEFAULT)?)?;
+            let info = unsafe { info_buf.assume_init() };
+            Ok(LocationInfo::from_info1(info))
         }
     }
 }
@@ -409,6 +595,8 @@ fn H5O_open_by_token(loc_id: hid_t, token: LocationToken) -> Result<Location> {
 
 #[cfg(test)]
 pub mod tests {
+    use std::collections::HashSet;
+
     use crate::{hl::plist::object_copy::ObjectCopy, internal_prelude::*, plist::LinkCreate};
 
     #[test]
@@ -425,6 +613,134 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_path() {
+        with_tmp_file(|file| {
+            assert_eq!(file.path().unwrap(), "/");
+            let ds = file.new_dataset::<i32>().create("a/b/ds").unwrap();
+            assert_eq!(ds.path().unwrap(), "/a/b/ds");
+            assert_eq!(file.group("a/b").unwrap().path().unwrap(), "/a/b");
+
+            let anon = file.new_dataset::<i32>().create(None).unwrap();
+            assert_err!(anon.path(), "object has no path in the file (anonymous or unlinked)");
+            assert_eq!(anon.name(), "");
+
+            file.new_dataset::<u8>().create("a/sibling").unwrap();
+            let parent = ds.file().unwrap();
+            assert_eq!(parent.filename(), file.filename());
+            assert_eq!(parent.dataset("/a/sibling").unwrap().path().unwrap(), "/a/sibling");
+            drop(file);
+            assert_eq!(parent.dataset("/a/b/ds").unwrap().path().unwrap(), "/a/b/ds");
+        })
+    }
+
+    #[test]
+    pub fn test_attr_by_index() {
+        use crate::file::AttrCreationOrder;
+        with_tmp_path(|path| {
+            let file = File::with_options()
+                .with_fcpl(|p| {
+                    p.attr_creation_order(AttrCreationOrder::TRACKED | AttrCreationOrder::INDEXED)
+                })
+                .create(&path)
+                .unwrap();
+            for name in ["c", "a", "b"] {
+                file.new_attr::<u32>().create(name).unwrap();
+            }
+            let names = |order| {
+                (0..3).map(|i| file.attr_by_index(i, order).unwrap().name()).collect::<Vec<_>>()
+            };
+            assert_eq!(names(IterationOrder::Increasing), ["c", "a", "b"]);
+            assert_eq!(names(IterationOrder::Decreasing), ["b", "a", "c"]);
+            assert_err!(
+                file.attr_by_index(3, IterationOrder::Increasing),
+                "attribute index 3 is out of range: object has 3 attributes"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_attr_count_delete_clear() {
+        use crate::file::AttrCreationOrder;
+        with_tmp_path(|path| {
+            let file = File::with_options()
+                .with_fcpl(|p| {
+                    p.attr_creation_order(AttrCreationOrder::TRACKED | AttrCreationOrder::INDEXED)
+                })
+                .create(&path)
+                .unwrap();
+            assert_eq!(file.attr_count().unwrap(), 0);
+            for i in 0..10 {
+                file.new_attr::<u32>().create(format!("attr{i}").as_str()).unwrap();
+            }
+            assert_eq!(file.attr_count().unwrap(), 10);
+            file.delete_attr_by_index(2, IterationOrder::Increasing).unwrap();
+            assert_eq!(file.attr_count().unwrap(), 9);
+            let mut names = file.attr_names().unwrap();
+            names.sort();
+            let expected: Vec<_> = [0, 1, 3, 4, 5, 6, 7, 8, 9].map(|i| format!("attr{i}")).into();
+            assert_eq!(names, expected);
+            assert_err!(
+                file.delete_attr_by_index(9, IterationOrder::Increasing),
+                "attribute index 9 is out of range: object has 9 attributes"
+            );
+            file.clear_attrs().unwrap();
+            assert_eq!(file.attr_count().unwrap(), 0);
+            assert!(file.attr_names().unwrap().is_empty());
+            file.clear_attrs().unwrap();
+
+            // clearing doesn't rely on creation order being tracked
+            let group = file.create_group("g").unwrap();
+            for name in ["z", "y", "x"] {
+                group.new_attr::<u8>().create(name).unwrap();
+            }
+            group.clear_attrs().unwrap();
+            assert_eq!(group.attr_count().unwrap(), 0);
+        })
+    }
+
+    #[test]
+    pub fn test_write_attrs() {
+        use std::collections::BTreeMap;
+        use types::{OwnedDynValue, VarLenUnicode};
+        with_tmp_file(|file| {
+            let label = "x".parse::<VarLenUnicode>().unwrap();
+            file.new_attr::<u8>().shape(3).create("replaced").unwrap();
+            file.write_attrs(&[
+                ("step", OwnedDynValue::from(7_u64)),
+                ("replaced", 1.5_f64.into()),
+                ("label", label.clone().into()),
+            ])
+            .unwrap();
+            assert_eq!(file.attr_names().unwrap(), ["label", "replaced", "step"]);
+            assert_eq!(file.attr("step").unwrap().read_scalar::<u64>().unwrap(), 7);
+            assert_eq!(file.attr("replaced").unwrap().read_scalar::<f64>().unwrap(), 1.5);
+            assert_eq!(file.attr("label").unwrap().read_scalar::<VarLenUnicode>().unwrap(), label);
+
+            // unchanged values are skipped, changed ones overwritten in place
+            let attrs = [
+                ("step", OwnedDynValue::from(8_u64)),
+                ("replaced", 1.5_f64.into()),
+                ("label", label.clone().into()),
+            ];
+            assert_eq!(file.write_attrs_if_changed(&attrs).unwrap(), 1);
+            assert_eq!(file.attr("step").unwrap().read_scalar::<u64>().unwrap(), 8);
+            assert_eq!(file.write_attrs_if_changed(&attrs).unwrap(), 0);
+            assert_eq!(file.write_attrs_if_changed(&[("step", 8_i32.into())]).unwrap(), 1);
+            assert_eq!(file.attr("step").unwrap().read_scalar::<i32>().unwrap(), 8);
+
+            let map: BTreeMap<String, u16> = (0..5).map(|i| (format!("n{i}"), i)).collect();
+            file.write_attrs_typed(map).unwrap();
+            assert_eq!(file.attr("n3").unwrap().read_scalar::<u16>().unwrap(), 3);
+
+            assert_err!(
+                file.write_attrs(&[("a", 1_u8.into()), ("a", 2_u8.into())]),
+                "duplicate attribute name 'a'"
+            );
+            assert!(!file.attr_names().unwrap().contains(&"a".to_owned()));
+        })
+    }
+
     #[test]
     pub fn test_file() {
         with_tmp_file(|file| {
@@ -507,6 +823,32 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_object_id() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u8>().create("a/ds").unwrap();
+            file.link_hard("a/ds", "link").unwrap();
+            let id = ds.object_id().unwrap();
+            assert_eq!(file.dataset("a/ds").unwrap().object_id().unwrap(), id);
+            assert_eq!(file.dataset("link").unwrap().object_id().unwrap(), id);
+            assert_eq!(id, ObjectId::from(&file.loc_info_by_name("link").unwrap()));
+
+            let other = file.new_dataset::<u8>().create("other").unwrap().object_id().unwrap();
+            assert_ne!(other, id);
+            assert_ne!(file.group("a").unwrap().object_id().unwrap(), id);
+            assert_eq!(other.fileno(), id.fileno());
+            assert_eq!(HashSet::from([id, other, ds.object_id().unwrap()]).len(), 2);
+
+            let opened = file.open_by_id(&id).unwrap();
+            assert!(["/a/ds", "/link"].contains(&opened.name().as_str()));
+            assert_eq!(opened.object_id().unwrap(), id);
+
+            with_tmp_file(|other_file| {
+                assert_err!(other_file.open_by_id(&id), "object id belongs to file number");
+            });
+        })
+    }
+
     #[test]
     pub fn test_copy_dataset_between_files() {
         with_tmp_path(|src_path| {
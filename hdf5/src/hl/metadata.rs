@@ -0,0 +1,142 @@
+//! Conventional metadata attributes understood by h5py, xarray and NeXus tools.
+
+use hdf5_types::VarLenUnicode;
+
+use crate::conventions::{DIMENSION_LABELS_ATTR, NX_CLASS_ATTR, UNITS_ATTR};
+use crate::hl::strings::varlen_unicode_strings;
+use crate::internal_prelude::*;
+
+/// Returns `true` if `loc` has an attribute named `name`.
+pub(crate) fn has_attr(loc: &Location, name: &str) -> Result<bool> {
+    Ok(loc.attr_names()?.iter().any(|attr| attr == name))
+}
+
+/// Writes a scalar variable-length UTF-8 string attribute, replacing any existing one.
+fn set_string_attr(loc: &Location, name: &str, value: &str) -> Result<()> {
+    if has_attr(loc, name)? {
+        loc.delete_attr(name)?;
+    }
+    loc.new_attr::<VarLenUnicode>().create(name)?.write_str(value)
+}
+
+/// Reads a single-element string attribute, or returns `None` if it is missing or not a string.
+pub(crate) fn string_attr(loc: &Location, name: &str) -> Option<String> {
+    h5lock!({
+        if !has_attr(loc, name).ok()? {
+            return None;
+        }
+        loc.attr(name).ok()?.read_string().ok()
+    })
+}
+
+impl Dataset {
+    /// Sets the physical units of the dataset (the [`UNITS_ATTR`] attribute).
+    pub fn set_units(&self, units: &str) -> Result<()> {
+        set_string_attr(self, UNITS_ATTR, units)
+    }
+
+    /// Returns the physical units of the dataset, if set.
+    pub fn units(&self) -> Option<String> {
+        string_attr(self, UNITS_ATTR)
+    }
+
+    /// Sets the label of the axis `dim` (in the [`DIMENSION_LABELS_ATTR`] attribute), keeping
+    /// the labels of the other axes.
+    pub fn set_axis_label(&self, dim: usize, label: &str) -> Result<()> {
+        let ndim = self.ndim();
+        ensure!(dim < ndim, "axis {} is out of range for a {}-dimensional dataset", dim, ndim);
+        h5lock!({
+            let mut labels = self.axis_labels();
+            labels[dim] = Some(label.to_owned());
+            let labels = varlen_unicode_strings(labels.iter().map(|l| l.as_deref().unwrap_or("")))?;
+            if has_attr(self, DIMENSION_LABELS_ATTR)? {
+                self.delete_attr(DIMENSION_LABELS_ATTR)?;
+            }
+            self.new_attr_builder().with_data(&labels).create(DIMENSION_LABELS_ATTR)?;
+            Ok(())
+        })
+    }
+
+    /// Returns the label of each axis, or `None` for unlabeled axes.
+    pub fn axis_labels(&self) -> Vec<Option<String>> {
+        let labels = h5lock!({
+            if !has_attr(self, DIMENSION_LABELS_ATTR).ok()? {
+                return None;
+            }
+            self.attr(DIMENSION_LABELS_ATTR).ok()?.read_strings().ok()
+        });
+        let mut labels = labels
+            .unwrap_or_default()
+            .into_iter()
+            .map(|label| (!label.is_empty()).then_some(label))
+            .collect::<Vec<_>>();
+        labels.resize(self.ndim(), None);
+        labels
+    }
+}
+
+impl Group {
+    /// Sets the NeXus base class of the group (the [`NX_CLASS_ATTR`] attribute), e.g.
+    /// `NXentry`.
+    pub fn set_nx_class(&self, nx_class: &str) -> Result<()> {
+        set_string_attr(self, NX_CLASS_ATTR, nx_class)
+    }
+
+    /// Returns the NeXus base class of the group, if set.
+    pub fn nx_class(&self) -> Option<String> {
+        string_attr(self, NX_CLASS_ATTR)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use hdf5_types::TypeDescriptor;
+
+    use crate::conventions::{DIMENSION_LABELS_ATTR, NX_CLASS_ATTR, UNITS_ATTR};
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_metadata() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            let entry = file.create_group("entry").unwrap();
+            entry.set_nx_class("NXentry").unwrap();
+            let ds = entry.new_dataset::<f64>().shape((4, 3, 2)).create("data").unwrap();
+            assert_eq!(ds.units(), None);
+            assert_eq!(ds.axis_labels(), [None, None, None]);
+            ds.set_units("m").unwrap();
+            ds.set_units("m/s").unwrap();
+            ds.set_axis_label(2, "time").unwrap();
+            ds.set_axis_label(0, "x").unwrap();
+            assert!(ds.set_axis_label(3, "z").is_err());
+            let scalar = file.new_dataset::<i32>().create("scalar").unwrap();
+            assert!(scalar.set_axis_label(0, "x").is_err());
+            assert!(scalar.axis_labels().is_empty());
+            file.close().unwrap();
+
+            let file = File::open(&path).unwrap();
+            let entry = file.group("entry").unwrap();
+            assert_eq!(entry.nx_class().as_deref(), Some("NXentry"));
+            assert_eq!(file.nx_class(), None);
+            let ds = entry.dataset("data").unwrap();
+            assert_eq!(ds.units().as_deref(), Some("m/s"));
+            let expected = [Some("x".to_owned()), None, Some("time".to_owned())];
+            assert_eq!(ds.axis_labels(), expected);
+
+            // the attributes have the types and shapes written by h5py:
+            // `ds.attrs["units"] = "m/s"`, `ds.dims[i].label = ...` and
+            // `entry.attrs["NX_class"] = "NXentry"`
+            let vlen = TypeDescriptor::VarLenUnicode;
+            for (attr, shape) in [
+                (ds.attr(UNITS_ATTR).unwrap(), vec![]),
+                (ds.attr(DIMENSION_LABELS_ATTR).unwrap(), vec![3]),
+                (entry.attr(NX_CLASS_ATTR).unwrap(), vec![]),
+            ] {
+                assert_eq!(attr.dtype().unwrap().to_descriptor().unwrap(), vlen);
+                assert_eq!(attr.shape().unwrap(), shape);
+            }
+            let labels = ds.attr(DIMENSION_LABELS_ATTR).unwrap().read_strings().unwrap();
+            assert_eq!(labels, ["x", "", "time"]);
+        })
+    }
+}
@@ -6,16 +6,60 @@ use std::str::FromStr;
 
 use crate::sys::h5p::{
     H5Pcopy, H5Pequal, H5Pexist, H5Pget_class, H5Pget_class_name, H5Pget_nprops, H5Pisa_class,
-    H5Piterate, H5Pset_vlen_mem_manager,
+    H5Piterate,
 };
 
 use crate::internal_prelude::*;
 
+/// Declares a deprecated trait with the `&mut self` setters that property list builders had
+/// before their setters started consuming the builder, implemented for `&mut` references to
+/// the builder, so that code chaining setters on such references keeps compiling.
+macro_rules! impl_builder_mut {
+    (
+        $trait:ident for $builder:ident {
+            $(
+                $(#[$attr:meta])*
+                fn $name:ident $([$($gen:tt)*])? ($($arg:ident: $ty:ty),* $(,)?);
+            )*
+        }
+    ) => {
+        #[doc = concat!("The `&mut self` setters of [`", stringify!($builder), "`].")]
+        ///
+        /// The setters of the builder consume it and return it; this trait provides them
+        /// for `&mut` references to the builder (such as the ones returned by the deprecated
+        /// accessors of the file and dataset builders), modifying the builder in place.
+        #[deprecated(note = "builders are consumed by their setters; use `builder = builder.setter(..)`")]
+        pub trait $trait: Sized {
+            $(
+                $(#[$attr])*
+                #[doc = concat!("Calls [`", stringify!($builder), "::", stringify!($name), "`] in place.")]
+                #[deprecated(note = "builders are consumed by their setters; use `builder = builder.setter(..)`")]
+                fn $name $(<$($gen)*>)? (self, $($arg: $ty),*) -> Self;
+            )*
+        }
+
+        #[allow(deprecated)]
+        impl $trait for &mut $builder {
+            $(
+                $(#[$attr])*
+                #[inline]
+                fn $name $(<$($gen)*>)? (self, $($arg: $ty),*) -> Self {
+                    *self = std::mem::take(self).$name($($arg),*);
+                    self
+                }
+            )*
+        }
+    };
+}
+
 pub mod common;
 pub mod dataset_access;
 pub mod dataset_create;
+pub mod dataset_transfer;
 pub mod file_access;
 pub mod file_create;
+pub mod group_create;
+pub mod link_access;
 pub mod link_create;
 pub mod object_copy;
 
@@ -175,7 +219,7 @@ impl PropertyList {
         unsafe extern "C" fn callback(_: hid_t, name: *const c_char, data: *mut c_void) -> herr_t {
             panic::catch_unwind(|| {
                 let data = unsafe { &mut *(data.cast::<Vec<String>>()) };
-                // SAFETY: caller guarantees name is a valid CStr and UTF-8
+                // SAFETY: the library passes a valid C string or, defensively, null (skipped)
                 let name = unsafe { string_from_cstr(name) };
                 if !name.is_empty() {
                     data.push(name);
@@ -201,12 +245,7 @@ impl PropertyList {
     pub fn class(&self) -> Result<PropertyListClass> {
         h5lock!({
             let class_id = h5check(H5Pget_class(self.id()))?;
-            let buf = H5Pget_class_name(class_id);
-            if buf.is_null() {
-                return Err(Error::query().unwrap_or_else(|_| "invalid property class".into()));
-            }
-            let name = string_from_cstr(buf);
-            h5_free_memory(buf.cast());
+            let name = h5_string(H5Pget_class_name(class_id))?;
             PropertyListClass::from_str(&name)
         })
     }
@@ -240,28 +279,6 @@ impl PropertyList {
     }
 }
 
-/// Set the memory manager for variable length items to
-/// the same allocator as is in use by hdf5-types
-// TODO: move this to dataset_transfer module when DatasetTransfer plist is implemented
-pub fn set_vlen_manager_libc(plist: hid_t) -> Result<()> {
-    extern "C" fn alloc(size: size_t, _info: *mut c_void) -> *mut c_void {
-        panic::catch_unwind(|| unsafe { libc::malloc(size) }).unwrap_or(ptr::null_mut())
-    }
-    unsafe extern "C" fn free(ptr: *mut c_void, _info: *mut libc::c_void) {
-        let _p = panic::catch_unwind(|| unsafe {
-            libc::free(ptr);
-        });
-    }
-    h5try!(H5Pset_vlen_mem_manager(
-        plist,
-        Some(alloc),
-        ptr::null_mut(),
-        Some(free),
-        ptr::null_mut()
-    ));
-    Ok(())
-}
-
 #[cfg(test)]
 pub mod tests {
     use crate::sys::h5p::H5Pcreate;
@@ -28,6 +28,29 @@ impl Default for AttrPhaseChange {
     }
 }
 
+/// Link storage phase change thresholds of a group.
+///
+/// Links of a new-style group are initially kept in compact storage (i.e., storage in
+/// the group's object header). When the number of links exceeds `max_compact`, link
+/// storage switches to dense storage (i.e., storage in a fractal heap indexed with a
+/// B-tree). If the number of links subsequently falls below `min_dense`, the links are
+/// returned to compact storage.
+///
+/// If `max_compact` is set to 0 (zero), dense storage is always used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinkPhaseChange {
+    /// Maximum number of links to be stored in compact storage (default: 8).
+    pub max_compact: u32,
+    /// Minimum number of links to be stored in dense storage (default: 6).
+    pub min_dense: u32,
+}
+
+impl Default for LinkPhaseChange {
+    fn default() -> Self {
+        Self { max_compact: 8, min_dense: 6 }
+    }
+}
+
 bitflags! {
     /// Flags for tracking and indexing attribute creation order of an object.
     ///
@@ -45,3 +68,16 @@ bitflags! {
         const INDEXED = H5P_CRT_ORDER_INDEXED as _;
     }
 }
+
+bitflags! {
+    /// Flags for tracking and indexing link creation order in a group.
+    ///
+    /// Default behavior is that link creation order is neither tracked nor indexed.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+    pub struct LinkCreationOrder: u32 {
+        /// Link creation order is tracked but not necessarily indexed.
+        const TRACKED = H5P_CRT_ORDER_TRACKED as _;
+        /// Link creation order is indexed (requires to be tracked).
+        const INDEXED = H5P_CRT_ORDER_INDEXED as _;
+    }
+}
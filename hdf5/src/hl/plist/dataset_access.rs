@@ -7,6 +7,7 @@ Not implemented:
 
 use std::fmt::{self, Debug};
 use std::ops::Deref;
+use std::path::{Path, PathBuf};
 
 use crate::sys::h5p::{H5Pcreate, H5Pget_chunk_cache, H5Pset_chunk_cache};
 #[cfg(all(feature = "1.10.0", feature = "have-parallel"))]
@@ -130,7 +131,7 @@ impl From<VirtualView> for H5D_vds_view_t {
 #[derive(Clone, Debug, Default)]
 pub struct DatasetAccessBuilder {
     chunk_cache: Option<ChunkCache>,
-    efile_prefix: Option<String>,
+    efile_prefix: Option<PathBuf>,
     #[cfg(all(feature = "1.10.0", feature = "link"))]
     virtual_view: Option<VirtualView>,
     #[cfg(all(feature = "1.10.0", feature = "link"))]
@@ -149,36 +150,38 @@ impl DatasetAccessBuilder {
     pub fn from_plist(plist: &DatasetAccess) -> Result<Self> {
         let mut builder = Self::default();
         let v = plist.get_chunk_cache()?;
-        builder.chunk_cache(v.nslots, v.nbytes, v.w0);
+        builder = builder.chunk_cache(v.nslots, v.nbytes, v.w0);
         {
             let v = plist.get_efile_prefix()?;
-            builder.efile_prefix(&v);
+            builder = builder.efile_prefix(&v);
         }
         #[cfg(all(feature = "1.10.0", feature = "link"))]
         {
-            builder.virtual_view(plist.get_virtual_view()?);
-            builder.virtual_printf_gap(plist.get_virtual_printf_gap()?);
+            builder = builder.virtual_view(plist.get_virtual_view()?);
+            builder = builder.virtual_printf_gap(plist.get_virtual_printf_gap()?);
         }
         #[cfg(all(feature = "1.10.0", feature = "have-parallel"))]
-        builder.all_coll_metadata_ops(plist.get_all_coll_metadata_ops()?);
+        {
+            builder = builder.all_coll_metadata_ops(plist.get_all_coll_metadata_ops()?);
+        }
         Ok(builder)
     }
 
     /// Sets the [`ChunkCache`] options.
-    pub fn chunk_cache(&mut self, nslots: usize, nbytes: usize, w0: f64) -> &mut Self {
+    pub fn chunk_cache(mut self, nslots: usize, nbytes: usize, w0: f64) -> Self {
         self.chunk_cache = Some(ChunkCache { nslots, nbytes, w0 });
         self
     }
 
     /// Sets the external dataset storage file prefix.
-    pub fn efile_prefix(&mut self, prefix: &str) -> &mut Self {
-        self.efile_prefix = Some(prefix.into());
+    pub fn efile_prefix<P: AsRef<Path>>(mut self, prefix: P) -> Self {
+        self.efile_prefix = Some(prefix.as_ref().into());
         self
     }
 
     /// Sets the [`VirtualView`] options.
     #[cfg(all(feature = "1.10.0", feature = "link"))]
-    pub fn virtual_view(&mut self, view: VirtualView) -> &mut Self {
+    pub fn virtual_view(mut self, view: VirtualView) -> Self {
         self.virtual_view = Some(view);
         self
     }
@@ -186,14 +189,14 @@ impl DatasetAccessBuilder {
     /// Sets the maximum number of files/datasets allowed to be missing when determining the extent
     /// of an unlimited virtual dataset with printf-style mappings.
     #[cfg(all(feature = "1.10.0", feature = "link"))]
-    pub fn virtual_printf_gap(&mut self, gap_size: usize) -> &mut Self {
+    pub fn virtual_printf_gap(mut self, gap_size: usize) -> Self {
         self.virtual_printf_gap = Some(gap_size);
         self
     }
 
     /// Sets metadata I/O mode for read options to collective or independent.
     #[cfg(all(feature = "1.10.0", feature = "have-parallel"))]
-    pub fn all_coll_metadata_ops(&mut self, is_collective: bool) -> &mut Self {
+    pub fn all_coll_metadata_ops(mut self, is_collective: bool) -> Self {
         self.all_coll_metadata_ops = Some(is_collective);
         self
     }
@@ -203,7 +206,7 @@ impl DatasetAccessBuilder {
             h5try!(H5Pset_chunk_cache(id, v.nslots as _, v.nbytes as _, v.w0 as _));
         }
         if let Some(ref v) = self.efile_prefix {
-            let v = to_cstring(v.as_ref())?;
+            let v = path_to_cstring(v)?;
             h5try!(H5Pset_efile_prefix(id, v.as_ptr()));
         }
         #[cfg(all(feature = "1.10.0", feature = "link"))]
@@ -238,11 +241,24 @@ impl DatasetAccessBuilder {
     }
 }
 
+impl_builder_mut! {
+    DatasetAccessBuilderMut for DatasetAccessBuilder {
+        fn chunk_cache(nslots: usize, nbytes: usize, w0: f64);
+        fn efile_prefix[P: AsRef<Path>](prefix: P);
+        #[cfg(all(feature = "1.10.0", feature = "link"))]
+        fn virtual_view(view: VirtualView);
+        #[cfg(all(feature = "1.10.0", feature = "link"))]
+        fn virtual_printf_gap(gap_size: usize);
+        #[cfg(all(feature = "1.10.0", feature = "have-parallel"))]
+        fn all_coll_metadata_ops(is_collective: bool);
+    }
+}
+
 /// Dataset access property list.
 impl DatasetAccess {
     /// Creates a new dataset access property list.
     pub fn try_new() -> Result<Self> {
-        Self::from_id(h5try!(H5Pcreate(*H5P_DATASET_ACCESS)))
+        Self::from_id(h5try!(H5Pcreate(H5P_DATASET_ACCESS.get()?)))
     }
 
     /// Creates a copy of the property list.
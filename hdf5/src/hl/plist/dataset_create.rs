@@ -2,6 +2,7 @@
 
 use std::fmt::{self, Debug};
 use std::ops::Deref;
+use std::path::{Path, PathBuf};
 use std::ptr::{self, addr_of_mut};
 
 #[cfg(all(feature = "1.10.0", feature = "link"))]
@@ -17,15 +18,15 @@ use crate::sys::h5p::{
     H5Pset_attr_phase_change, H5Pset_chunk, H5Pset_external, H5Pset_fill_time, H5Pset_fill_value,
     H5Pset_layout, H5Pset_obj_track_times,
 };
-use crate::sys::h5t::H5Tget_class;
+use crate::sys::h5p::{
+    H5Pget_virtual_count, H5Pget_virtual_dsetname, H5Pget_virtual_filename,
+    H5Pget_virtual_srcspace, H5Pget_virtual_vspace, H5Pset_virtual,
+};
 use crate::sys::h5z::H5Z_filter_t;
 #[cfg(all(feature = "1.10.0", feature = "link"))]
 use crate::sys::{
     h5d::H5D_CHUNK_DONT_FILTER_PARTIAL_CHUNKS,
-    h5p::{
-        H5Pget_chunk_opts, H5Pget_virtual_count, H5Pget_virtual_dsetname, H5Pget_virtual_filename,
-        H5Pget_virtual_srcspace, H5Pget_virtual_vspace, H5Pset_chunk_opts, H5Pset_virtual,
-    },
+    h5p::{H5Pget_chunk_opts, H5Pset_chunk_opts},
 };
 use hdf5_types::{OwnedDynValue, TypeDescriptor};
 
@@ -77,7 +78,6 @@ impl Debug for DatasetCreate {
         #[cfg(all(feature = "1.10.0", feature = "link"))]
         formatter.field("chunk_opts", &self.chunk_opts());
         formatter.field("external", &self.external());
-        #[cfg(all(feature = "1.10.0", feature = "link"))]
         formatter.field("virtual_map", &self.virtual_map());
         formatter.field("obj_track_times", &self.obj_track_times());
         formatter.field("attr_phase_change", &self.attr_phase_change());
@@ -110,6 +110,7 @@ impl Clone for DatasetCreate {
 
 /// Options for how to store raw data for a dataset.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Layout {
     /// Raw data is stored in the file's object header.
     Compact,
@@ -118,7 +119,6 @@ pub enum Layout {
     /// Raw data is stored in separate chunks in the file.
     Chunked,
     /// Raw data is drawn from multiple datasets in different files.
-    #[cfg(all(feature = "1.10.0", feature = "link"))]
     Virtual,
 }
 
@@ -133,7 +133,6 @@ impl From<H5D_layout_t> for Layout {
         match layout {
             H5D_layout_t::H5D_COMPACT => Self::Compact,
             H5D_layout_t::H5D_CHUNKED => Self::Chunked,
-            #[cfg(all(feature = "1.10.0", feature = "link"))]
             H5D_layout_t::H5D_VIRTUAL => Self::Virtual,
             _ => Self::Contiguous,
         }
@@ -145,7 +144,6 @@ impl From<Layout> for H5D_layout_t {
         match layout {
             Layout::Compact => Self::H5D_COMPACT,
             Layout::Chunked => Self::H5D_CHUNKED,
-            #[cfg(all(feature = "1.10.0", feature = "link"))]
             Layout::Virtual => Self::H5D_VIRTUAL,
             Layout::Contiguous => Self::H5D_CONTIGUOUS,
         }
@@ -279,7 +277,7 @@ impl From<FillValue> for H5D_fill_value_t {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ExternalFile {
     /// The name of the file.
-    pub name: String,
+    pub name: PathBuf,
     /// The offset in bytes from the start of the file to the location where the data starts.
     pub offset: usize,
     /// The number of bytes reserved in the file for data.
@@ -287,7 +285,6 @@ pub struct ExternalFile {
 }
 
 /// Properties of a mapping between virtual and source datasets.
-#[cfg(all(feature = "1.10.0", feature = "link"))]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct VirtualMapping {
     /// The name of the HDF5 file containing the source dataset.
@@ -304,7 +301,6 @@ pub struct VirtualMapping {
     pub vds_selection: Selection,
 }
 
-#[cfg(all(feature = "1.10.0", feature = "link"))]
 impl VirtualMapping {
     /// Constructs a `VirtualMapping` with the given parameters.
     pub fn new<F, D, E1, S1, E2, S2>(
@@ -347,7 +343,6 @@ pub struct DatasetCreateBuilder {
     #[cfg(all(feature = "1.10.0", feature = "link"))]
     chunk_opts: Option<ChunkOpts>,
     external: Vec<ExternalFile>,
-    #[cfg(all(feature = "1.10.0", feature = "link"))]
     virtual_map: Vec<VirtualMapping>,
     obj_track_times: Option<bool>,
     attr_phase_change: Option<AttrPhaseChange>,
@@ -366,87 +361,85 @@ impl DatasetCreateBuilder {
     /// exposed in the property list API).
     pub fn from_plist(plist: &DatasetCreate) -> Result<Self> {
         let mut builder = Self::default();
-        builder.set_filters(&plist.get_filters()?);
-        builder.alloc_time(Some(plist.get_alloc_time()?));
-        builder.fill_time(plist.get_fill_time()?);
+        builder = builder.set_filters(&plist.get_filters()?);
+        builder = builder.alloc_time(Some(plist.get_alloc_time()?));
+        builder = builder.fill_time(plist.get_fill_time()?);
         if let Some(v) = plist.get_chunk()? {
-            builder.chunk(&v);
+            builder = builder.chunk(&v);
         }
         let layout = plist.get_layout()?;
-        builder.layout(layout);
+        builder = builder.layout(layout);
         #[cfg(all(feature = "1.10.0", feature = "link"))]
-        {
-            if let Some(v) = plist.get_chunk_opts()? {
-                builder.chunk_opts(v);
-            }
-            if layout == Layout::Virtual {
-                for mapping in &plist.get_virtual_map()? {
-                    builder.virtual_map(
-                        &mapping.src_filename,
-                        &mapping.src_dataset,
-                        &mapping.src_extents,
-                        &mapping.src_selection,
-                        &mapping.vds_extents,
-                        &mapping.vds_selection,
-                    );
-                }
+        if let Some(v) = plist.get_chunk_opts()? {
+            builder = builder.chunk_opts(v);
+        }
+        if layout == Layout::Virtual {
+            for mapping in &plist.get_virtual_map()? {
+                builder = builder.virtual_map(
+                    &mapping.src_filename,
+                    &mapping.src_dataset,
+                    &mapping.src_extents,
+                    &mapping.src_selection,
+                    &mapping.vds_extents,
+                    &mapping.vds_selection,
+                );
             }
         }
         for external in &plist.get_external()? {
-            builder.external(&external.name, external.offset, external.size);
+            builder = builder.external(&external.name, external.offset, external.size);
         }
-        builder.obj_track_times(plist.get_obj_track_times()?);
+        builder = builder.obj_track_times(plist.get_obj_track_times()?);
         let apc = plist.get_attr_phase_change()?;
-        builder.attr_phase_change(apc.max_compact, apc.min_dense);
-        builder.attr_creation_order(plist.get_attr_creation_order()?);
+        builder = builder.attr_phase_change(apc.max_compact, apc.min_dense);
+        builder = builder.attr_creation_order(plist.get_attr_creation_order()?);
         Ok(builder)
     }
 
     /// Sets the dataset filters from a slice of filter specifiers.
-    pub fn set_filters(&mut self, filters: &[Filter]) -> &mut Self {
+    pub fn set_filters(mut self, filters: &[Filter]) -> Self {
         self.filters = filters.to_owned();
         self
     }
 
     /// Adds a deflation filter with some compression level to the dataset.
-    pub fn deflate(&mut self, level: u8) -> &mut Self {
+    pub fn deflate(mut self, level: u8) -> Self {
         self.filters.push(Filter::deflate(level));
         self
     }
 
     /// Adds a shuffle filter to the dataset.
-    pub fn shuffle(&mut self) -> &mut Self {
+    pub fn shuffle(mut self) -> Self {
         self.filters.push(Filter::shuffle());
         self
     }
 
     /// Adds a Fletcher32 checksum filter to the dataset.
-    pub fn fletcher32(&mut self) -> &mut Self {
+    pub fn fletcher32(mut self) -> Self {
         self.filters.push(Filter::fletcher32());
         self
     }
 
     /// Adds an Szip compression filter with some coding method and pixels per block to the dataset.
-    pub fn szip(&mut self, coding: SZip, px_per_block: u8) -> &mut Self {
+    pub fn szip(mut self, coding: SZip, px_per_block: u8) -> Self {
         self.filters.push(Filter::szip(coding, px_per_block));
         self
     }
 
     /// Adds an N-bit compression filter to the dataset.
-    pub fn nbit(&mut self) -> &mut Self {
+    pub fn nbit(mut self) -> Self {
         self.filters.push(Filter::nbit());
         self
     }
 
     /// Adds a scale-offset compression filter with some scaling mode to the dataset.
-    pub fn scale_offset(&mut self, mode: ScaleOffset) -> &mut Self {
+    pub fn scale_offset(mut self, mode: ScaleOffset) -> Self {
         self.filters.push(Filter::scale_offset(mode));
         self
     }
 
     /// Adds an LZF compression filter to the dataset.
     #[cfg(feature = "lzf")]
-    pub fn lzf(&mut self) -> &mut Self {
+    pub fn lzf(mut self) -> Self {
         self.filters.push(Filter::lzf());
         self
     }
@@ -458,7 +451,7 @@ impl DatasetCreateBuilder {
     /// of minimum 1MB per core should be selected.
     /// For e.g. 16 cores a minimum chunksize of 16MB should allow efficient
     /// compression and decompression, although larger chunks might be more efficient.
-    pub fn blosc<T>(&mut self, complib: Blosc, clevel: u8, shuffle: T) -> &mut Self
+    pub fn blosc<T>(mut self, complib: Blosc, clevel: u8, shuffle: T) -> Self
     where
         T: Into<BloscShuffle>,
     {
@@ -468,7 +461,7 @@ impl DatasetCreateBuilder {
 
     /// Adds a Blosc filter with LZ compression to the dataset.
     #[cfg(feature = "blosc")]
-    pub fn blosc_blosclz<T>(&mut self, clevel: u8, shuffle: T) -> &mut Self
+    pub fn blosc_blosclz<T>(mut self, clevel: u8, shuffle: T) -> Self
     where
         T: Into<BloscShuffle>,
     {
@@ -478,7 +471,7 @@ impl DatasetCreateBuilder {
 
     /// Adds a Blosc filter with LZ4 compression to the dataset.
     #[cfg(feature = "blosc-lz4")]
-    pub fn blosc_lz4<T>(&mut self, clevel: u8, shuffle: T) -> &mut Self
+    pub fn blosc_lz4<T>(mut self, clevel: u8, shuffle: T) -> Self
     where
         T: Into<BloscShuffle>,
     {
@@ -488,7 +481,7 @@ impl DatasetCreateBuilder {
 
     /// Adds a Blosc filter with LZ4HC compression to the dataset.
     #[cfg(feature = "blosc-lz4")]
-    pub fn blosc_lz4hc<T>(&mut self, clevel: u8, shuffle: T) -> &mut Self
+    pub fn blosc_lz4hc<T>(mut self, clevel: u8, shuffle: T) -> Self
     where
         T: Into<BloscShuffle>,
     {
@@ -498,7 +491,7 @@ impl DatasetCreateBuilder {
 
     /// Adds a Blosc filter with Snappy compression to the dataset.
     #[cfg(feature = "blosc-snappy")]
-    pub fn blosc_snappy<T>(&mut self, clevel: u8, shuffle: T) -> &mut Self
+    pub fn blosc_snappy<T>(mut self, clevel: u8, shuffle: T) -> Self
     where
         T: Into<BloscShuffle>,
     {
@@ -508,7 +501,7 @@ impl DatasetCreateBuilder {
 
     /// Adds a Blosc filter with Zlib compression to the dataset.
     #[cfg(feature = "blosc-zlib")]
-    pub fn blosc_zlib<T>(&mut self, clevel: u8, shuffle: T) -> &mut Self
+    pub fn blosc_zlib<T>(mut self, clevel: u8, shuffle: T) -> Self
     where
         T: Into<BloscShuffle>,
     {
@@ -518,7 +511,7 @@ impl DatasetCreateBuilder {
 
     /// Adds a Blosc filter with Zstd compression to the dataset.
     #[cfg(feature = "blosc-zstd")]
-    pub fn blosc_zstd<T>(&mut self, clevel: u8, shuffle: T) -> &mut Self
+    pub fn blosc_zstd<T>(mut self, clevel: u8, shuffle: T) -> Self
     where
         T: Into<BloscShuffle>,
     {
@@ -527,59 +520,49 @@ impl DatasetCreateBuilder {
     }
 
     #[cfg(feature = "zfp")]
-    pub fn zfp_accuracy(
-        &mut self,
-        accuracy: f64,
-        chunk_dims: Vec<usize>,
-        n_bytes: u8,
-    ) -> &mut Self {
+    pub fn zfp_accuracy(mut self, accuracy: f64, chunk_dims: Vec<usize>, n_bytes: u8) -> Self {
         self.filters.push(Filter::zfp_accuracy(accuracy, chunk_dims, n_bytes));
         self
     }
 
     #[cfg(feature = "zfp")]
-    pub fn zfp_rate(&mut self, rate: f64, chunk_dims: Vec<usize>, n_bytes: u8) -> &mut Self {
+    pub fn zfp_rate(mut self, rate: f64, chunk_dims: Vec<usize>, n_bytes: u8) -> Self {
         self.filters.push(Filter::zfp_rate(rate, chunk_dims, n_bytes));
         self
     }
 
     #[cfg(feature = "zfp")]
-    pub fn zfp_precision(
-        &mut self,
-        precision: u8,
-        chunk_dims: Vec<usize>,
-        n_bytes: u8,
-    ) -> &mut Self {
+    pub fn zfp_precision(mut self, precision: u8, chunk_dims: Vec<usize>, n_bytes: u8) -> Self {
         self.filters.push(Filter::zfp_precision(precision, chunk_dims, n_bytes));
         self
     }
 
     #[cfg(feature = "zfp")]
-    pub fn zfp_reversible(&mut self, chunk_dims: Vec<usize>, n_bytes: u8) -> &mut Self {
+    pub fn zfp_reversible(mut self, chunk_dims: Vec<usize>, n_bytes: u8) -> Self {
         self.filters.push(Filter::zfp_reversible(chunk_dims, n_bytes));
         self
     }
 
     /// Adds a user-defined filter with the given identifier and parameters to the dataset.
-    pub fn add_filter(&mut self, id: H5Z_filter_t, cdata: &[c_uint]) -> &mut Self {
+    pub fn add_filter(mut self, id: H5Z_filter_t, cdata: &[c_uint]) -> Self {
         self.filters.push(Filter::user(id, cdata));
         self
     }
 
     /// Removes all filters from the dataset.
-    pub fn clear_filters(&mut self) -> &mut Self {
+    pub fn clear_filters(mut self) -> Self {
         self.filters.clear();
         self
     }
 
     /// Sets the dataset's storage space allocation timing.
-    pub fn alloc_time(&mut self, alloc_time: Option<AllocTime>) -> &mut Self {
+    pub fn alloc_time(mut self, alloc_time: Option<AllocTime>) -> Self {
         self.alloc_time = Some(alloc_time);
         self
     }
 
     /// Sets the time when fill values should be written to the dataset.
-    pub fn fill_time(&mut self, fill_time: FillTime) -> &mut Self {
+    pub fn fill_time(mut self, fill_time: FillTime) -> Self {
         self.fill_time = Some(fill_time);
         self
     }
@@ -589,13 +572,13 @@ impl DatasetCreateBuilder {
     }
 
     /// Sets the dataset's fill value.
-    pub fn fill_value<T: Into<OwnedDynValue>>(&mut self, fill_value: T) -> &mut Self {
+    pub fn fill_value<T: Into<OwnedDynValue>>(mut self, fill_value: T) -> Self {
         self.fill_value = Some(fill_value.into());
         self
     }
 
     /// Clears the dataset's fill value.
-    pub fn no_fill_value(&mut self) -> &mut Self {
+    pub fn no_fill_value(mut self) -> Self {
         self.fill_value = None;
         self
     }
@@ -607,47 +590,51 @@ impl DatasetCreateBuilder {
     /// If compression is enabled, it is a good idea to have chunks of sufficient
     /// size to allow efficient compression. Chunk sizes of less than 4MB will in
     /// most cases be inefficient, and will yield limited space- and time-savings.
-    pub fn chunk<D: Dimension>(&mut self, chunk: D) -> &mut Self {
+    pub fn chunk<D: Dimension>(mut self, chunk: D) -> Self {
         self.chunk = Some(chunk.dims());
         self
     }
 
     /// Clears the dataset's chunking settings.
-    pub fn no_chunk(&mut self) -> &mut Self {
+    pub fn no_chunk(mut self) -> Self {
         self.chunk = None;
         self
     }
 
     /// Sets the dataset's raw data layout.
-    pub fn layout(&mut self, layout: Layout) -> &mut Self {
+    pub fn layout(mut self, layout: Layout) -> Self {
         self.layout = Some(layout);
         self
     }
 
     /// Sets the dataset's edge chunk options.
     #[cfg(all(feature = "1.10.0", feature = "link"))]
-    pub fn chunk_opts(&mut self, opts: ChunkOpts) -> &mut Self {
+    pub fn chunk_opts(mut self, opts: ChunkOpts) -> Self {
         self.chunk_opts = Some(opts);
         self
     }
 
     /// Adds an external file to the dataset.
-    pub fn external(&mut self, name: &str, offset: usize, size: usize) -> &mut Self {
-        self.external.push(ExternalFile { name: name.to_owned(), offset, size });
+    ///
+    /// Relative names are resolved against the external file prefix of the dataset access
+    /// property list (see
+    /// [`DatasetAccessBuilder::efile_prefix`](crate::plist::DatasetAccessBuilder::efile_prefix)),
+    /// or else against the current directory.
+    pub fn external<P: AsRef<Path>>(mut self, name: P, offset: usize, size: usize) -> Self {
+        self.external.push(ExternalFile { name: name.as_ref().into(), offset, size });
         self
     }
 
     /// Adds a mapping between virtual and source datasets.
-    #[cfg(all(feature = "1.10.0", feature = "link"))]
     pub fn virtual_map<F, D, E1, S1, E2, S2>(
-        &mut self,
+        mut self,
         src_filename: F,
         src_dataset: D,
         src_extents: E1,
         src_selection: S1,
         vds_extents: E2,
         vds_selection: S2,
-    ) -> &mut Self
+    ) -> Self
     where
         F: AsRef<str>,
         D: AsRef<str>,
@@ -668,19 +655,19 @@ impl DatasetCreateBuilder {
     }
 
     /// Sets whether to record time data for the dataset.
-    pub fn obj_track_times(&mut self, track_times: bool) -> &mut Self {
+    pub fn obj_track_times(mut self, track_times: bool) -> Self {
         self.obj_track_times = Some(track_times);
         self
     }
 
     /// Sets the dataset's attribute storage phase change thresholds.
-    pub fn attr_phase_change(&mut self, max_compact: u32, min_dense: u32) -> &mut Self {
+    pub fn attr_phase_change(mut self, max_compact: u32, min_dense: u32) -> Self {
         self.attr_phase_change = Some(AttrPhaseChange { max_compact, min_dense });
         self
     }
 
     /// Sets whether to track and/or index the dataset's attribute creation order.
-    pub fn attr_creation_order(&mut self, attr_creation_order: AttrCreationOrder) -> &mut Self {
+    pub fn attr_creation_order(mut self, attr_creation_order: AttrCreationOrder) -> Self {
         self.attr_creation_order = Some(attr_creation_order);
         self
     }
@@ -690,7 +677,7 @@ impl DatasetCreateBuilder {
             ensure!(self.chunk.is_some(), "Filter requires dataset to be chunked");
         }
         for filter in &self.filters {
-            filter.apply_to_plist(id)?;
+            filter.apply_to_plist(id).with_context(|| format!("applying filter {filter:?}"))?;
         }
         if let Some(v) = self.alloc_time {
             let v = v.map_or(H5D_alloc_time_t::H5D_ALLOC_TIME_DEFAULT, Into::into);
@@ -701,36 +688,37 @@ impl DatasetCreateBuilder {
         }
         if let Some(ref v) = self.fill_value {
             let dtype = Datatype::from_descriptor(v.type_descriptor())?;
-            h5try!(H5Pset_fill_value(id, dtype.id(), v.get_buf().as_ptr().cast()));
+            h5call!(H5Pset_fill_value(id, dtype.id(), v.get_buf().as_ptr().cast()))
+                .with_context(|| format!("setting fill value {v:?}"))?;
         }
         if let Some(v) = self.layout {
-            h5try!(H5Pset_layout(id, v.into()));
+            h5call!(H5Pset_layout(id, v.into()))
+                .with_context(|| format!("setting layout {v:?}"))?;
         }
-        if let Some(ref v) = self.chunk {
-            let v = v.iter().map(|&x| x as _).collect::<Vec<_>>();
-            h5try!(H5Pset_chunk(id, v.len() as _, v.as_ptr()));
+        if let Some(ref chunk) = self.chunk {
+            let v = crate::dim::to_hsize(chunk);
+            h5call!(H5Pset_chunk(id, v.len() as _, v.as_ptr()))
+                .with_context(|| format!("setting chunk shape {chunk:?}"))?;
         }
         #[cfg(all(feature = "1.10.0", feature = "link"))]
-        {
-            if let Some(v) = self.chunk_opts {
-                h5try!(H5Pset_chunk_opts(id, v.bits() as _));
-            }
-            for v in &self.virtual_map {
-                let src_filename = to_cstring(v.src_filename.as_str())?;
-                let src_dataset = to_cstring(v.src_dataset.as_str())?;
-                let src_space = Dataspace::try_new(&v.src_extents)?.select(&v.src_selection)?;
-                let vds_space = Dataspace::try_new(&v.vds_extents)?.select(&v.vds_selection)?;
-                h5try!(H5Pset_virtual(
-                    id,
-                    vds_space.id(),
-                    src_filename.as_ptr(),
-                    src_dataset.as_ptr(),
-                    src_space.id()
-                ));
-            }
+        if let Some(v) = self.chunk_opts {
+            h5try!(H5Pset_chunk_opts(id, v.bits() as _));
+        }
+        for v in &self.virtual_map {
+            let src_filename = to_cstring(v.src_filename.as_str())?;
+            let src_dataset = to_cstring(v.src_dataset.as_str())?;
+            let src_space = Dataspace::try_new(&v.src_extents)?.select(&v.src_selection)?;
+            let vds_space = Dataspace::try_new(&v.vds_extents)?.select(&v.vds_selection)?;
+            h5try!(H5Pset_virtual(
+                id,
+                vds_space.id(),
+                src_filename.as_ptr(),
+                src_dataset.as_ptr(),
+                src_space.id()
+            ));
         }
         for external in &self.external {
-            let name = to_cstring(external.name.as_str())?;
+            let name = path_to_cstring(&external.name)?;
             let size = if external.size == 0 { H5F_UNLIMITED as _ } else { external.size as _ };
             h5try!(H5Pset_external(id, name.as_ptr(), external.offset as _, size));
         }
@@ -746,14 +734,26 @@ impl DatasetCreateBuilder {
         Ok(())
     }
 
-    pub(crate) fn validate_filters(&self, datatype_id: hid_t) -> Result<()> {
-        validate_filters(&self.filters, h5lock!(H5Tget_class(datatype_id)))
+    pub(crate) fn validate_filters(&self, dtype: &Datatype) -> Result<()> {
+        validate_filters(&self.filters, dtype)
     }
 
     pub(crate) fn has_filters(&self) -> bool {
         !self.filters.is_empty()
     }
 
+    pub(crate) fn get_filters_ref(&self) -> &[Filter] {
+        &self.filters
+    }
+
+    pub(crate) fn get_layout_opt(&self) -> Option<Layout> {
+        self.layout
+    }
+
+    pub(crate) fn get_fill_value_opt(&self) -> Option<&OwnedDynValue> {
+        self.fill_value.as_ref()
+    }
+
     /// Copies the builder settings into a dataset creation property list.
     pub fn apply(&self, plist: &mut DatasetCreate) -> Result<()> {
         h5lock!(self.populate_plist(plist.id()))
@@ -768,11 +768,77 @@ impl DatasetCreateBuilder {
     }
 }
 
+impl_builder_mut! {
+    DatasetCreateBuilderMut for DatasetCreateBuilder {
+        fn set_filters(filters: &[Filter]);
+        fn deflate(level: u8);
+        fn shuffle();
+        fn fletcher32();
+        fn szip(coding: SZip, px_per_block: u8);
+        fn nbit();
+        fn scale_offset(mode: ScaleOffset);
+        #[cfg(feature = "lzf")]
+        fn lzf();
+        #[cfg(feature = "blosc")]
+        fn blosc[T: Into<BloscShuffle>](complib: Blosc, clevel: u8, shuffle: T);
+        #[cfg(feature = "blosc")]
+        fn blosc_blosclz[T: Into<BloscShuffle>](clevel: u8, shuffle: T);
+        #[cfg(feature = "blosc-lz4")]
+        fn blosc_lz4[T: Into<BloscShuffle>](clevel: u8, shuffle: T);
+        #[cfg(feature = "blosc-lz4")]
+        fn blosc_lz4hc[T: Into<BloscShuffle>](clevel: u8, shuffle: T);
+        #[cfg(feature = "blosc-snappy")]
+        fn blosc_snappy[T: Into<BloscShuffle>](clevel: u8, shuffle: T);
+        #[cfg(feature = "blosc-zlib")]
+        fn blosc_zlib[T: Into<BloscShuffle>](clevel: u8, shuffle: T);
+        #[cfg(feature = "blosc-zstd")]
+        fn blosc_zstd[T: Into<BloscShuffle>](clevel: u8, shuffle: T);
+        #[cfg(feature = "zfp")]
+        fn zfp_accuracy(accuracy: f64, chunk_dims: Vec<usize>, n_bytes: u8);
+        #[cfg(feature = "zfp")]
+        fn zfp_rate(rate: f64, chunk_dims: Vec<usize>, n_bytes: u8);
+        #[cfg(feature = "zfp")]
+        fn zfp_precision(precision: u8, chunk_dims: Vec<usize>, n_bytes: u8);
+        #[cfg(feature = "zfp")]
+        fn zfp_reversible(chunk_dims: Vec<usize>, n_bytes: u8);
+        fn add_filter(id: H5Z_filter_t, cdata: &[c_uint]);
+        fn clear_filters();
+        fn alloc_time(alloc_time: Option<AllocTime>);
+        fn fill_time(fill_time: FillTime);
+        fn fill_value[T: Into<OwnedDynValue>](fill_value: T);
+        fn no_fill_value();
+        fn chunk[D: Dimension](chunk: D);
+        fn no_chunk();
+        fn layout(layout: Layout);
+        #[cfg(all(feature = "1.10.0", feature = "link"))]
+        fn chunk_opts(opts: ChunkOpts);
+        fn external[P: AsRef<Path>](name: P, offset: usize, size: usize);
+        fn virtual_map[
+            F: AsRef<str>,
+            D: AsRef<str>,
+            E1: Into<Extents>,
+            S1: Into<Selection>,
+            E2: Into<Extents>,
+            S2: Into<Selection>,
+        ](
+            src_filename: F,
+            src_dataset: D,
+            src_extents: E1,
+            src_selection: S1,
+            vds_extents: E2,
+            vds_selection: S2,
+        );
+        fn obj_track_times(track_times: bool);
+        fn attr_phase_change(max_compact: u32, min_dense: u32);
+        fn attr_creation_order(attr_creation_order: AttrCreationOrder);
+    }
+}
+
 /// Dataset creation property list.
 impl DatasetCreate {
     /// Constructs a new dataset creation property list.
     pub fn try_new() -> Result<Self> {
-        Self::from_id(h5try!(H5Pcreate(*H5P_DATASET_CREATE)))
+        Self::from_id(h5try!(H5Pcreate(H5P_DATASET_CREATE.get()?)))
     }
 
     /// Returns a copy of the dataset creation property list.
@@ -879,7 +945,7 @@ impl DatasetCreate {
             let ndims = h5try!(H5Pget_chunk(self.id(), 0, ptr::null_mut()));
             let mut buf: Vec<hsize_t> = vec![0; ndims as usize];
             h5try!(H5Pget_chunk(self.id(), ndims, buf.as_mut_ptr()));
-            Ok(Some(buf.into_iter().map(|x| x as _).collect()))
+            Ok(Some(crate::dim::to_ix(&buf)?))
         } else {
             Ok(None)
         }
@@ -939,7 +1005,7 @@ impl DatasetCreate {
                 ));
                 #[allow(clippy::absurd_extreme_comparisons)]
                 external.push(ExternalFile {
-                    name: string_from_cstr(name.as_ptr()),
+                    name: path_from_cstr(name.as_ptr()),
                     offset: offset as _,
                     size: if size >= H5F_UNLIMITED { 0 } else { size as _ },
                 });
@@ -953,7 +1019,6 @@ impl DatasetCreate {
         self.get_external().unwrap_or_default()
     }
 
-    #[cfg(all(feature = "1.10.0", feature = "link"))]
     #[doc(hidden)]
     pub fn get_virtual_map(&self) -> Result<Vec<VirtualMapping>> {
         sync(|| unsafe {
@@ -990,7 +1055,6 @@ impl DatasetCreate {
     }
 
     /// Returns a vector of virtual mapping specifiers for the dataset.
-    #[cfg(all(feature = "1.10.0", feature = "link"))]
     pub fn virtual_map(&self) -> Vec<VirtualMapping> {
         self.get_virtual_map().unwrap_or_default()
     }
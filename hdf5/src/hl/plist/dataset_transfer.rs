@@ -0,0 +1,392 @@
+//! Dataset transfer properties.
+
+/*
+Not implemented:
+- H5P{set,get}_vlen_mem_manager (due to having to deal with raw C extern callbacks; the vlen
+  memory manager is always set to the libc allocator on reads)
+- H5P{set,get}_btree_ratios, H5P{set,get}_preserve
+*/
+
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+use std::panic;
+use std::ptr;
+
+use crate::sys::h5p::{
+    H5Pcreate, H5Pget_buffer, H5Pget_edc_check, H5Pget_hyper_vector_size, H5Pget_type_conv_cb,
+    H5Pset_buffer, H5Pset_edc_check, H5Pset_hyper_vector_size, H5Pset_type_conv_cb,
+    H5Pset_vlen_mem_manager,
+};
+use crate::sys::h5t::{H5T_conv_except_func_t, H5T_conv_except_t, H5T_conv_ret_t};
+use crate::sys::h5z::H5Z_EDC_t;
+
+use crate::globals::H5P_DATASET_XFER;
+use crate::internal_prelude::*;
+
+/// Dataset transfer properties.
+#[repr(transparent)]
+pub struct DatasetTransfer(Handle);
+
+impl ObjectClass for DatasetTransfer {
+    const NAME: &'static str = "dataset transfer property list";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_GENPROP_LST];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.is_class(PropertyListClass::DataTransfer),
+            "expected dataset transfer property list, got {:?}",
+            self.class()
+        );
+        Ok(())
+    }
+}
+
+impl Debug for DatasetTransfer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut formatter = f.debug_struct("DatasetTransfer");
+        formatter.field("buffer_size", &self.buffer_size());
+        formatter.field("edc_check", &self.edc_check());
+        formatter.field("hyper_vector_size", &self.hyper_vector_size());
+        formatter.field("on_conversion_exception", &self.on_conversion_exception());
+        formatter.finish()
+    }
+}
+
+impl Deref for DatasetTransfer {
+    type Target = PropertyList;
+
+    fn deref(&self) -> &PropertyList {
+        unsafe { self.transmute() }
+    }
+}
+
+impl PartialEq for DatasetTransfer {
+    fn eq(&self, other: &Self) -> bool {
+        <PropertyList as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for DatasetTransfer {}
+
+impl Clone for DatasetTransfer {
+    fn clone(&self) -> Self {
+        unsafe { self.deref().clone().cast_unchecked() }
+    }
+}
+
+/// Default size of the type conversion and background buffers (1 MiB).
+pub const DEFAULT_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Default number of hyperslab offset and length pairs processed at a time (1024).
+pub const DEFAULT_HYPER_VECTOR_SIZE: usize = 1024;
+
+/// What happens when a value cannot be represented exactly in the destination type of a
+/// conversion, e.g. when reading `f64` data outside the range of `f32` as `f32`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConversionExceptionPolicy {
+    /// Silently handle the value the library's way: values out of range are clamped to the
+    /// limits of the destination type (or become infinities for floating-point types).
+    #[default]
+    Default,
+    /// Fail the transfer at the first exception.
+    Abort,
+    /// Handle the value the library's way, but count the exceptions by kind; see
+    /// [`Reader::read_with_report`](crate::Reader::read_with_report).
+    Count,
+}
+
+/// Numbers of conversion exceptions by kind, collected with
+/// [`ConversionExceptionPolicy::Count`].
+///
+/// Not every conversion path detects every kind of exception: the library's hard-coded
+/// conversions between native types report overflows and infinities, but no precision loss.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConversionReport {
+    /// Values above the range of the destination type.
+    pub overflow: u64,
+    /// Values below the range of the destination type.
+    pub underflow: u64,
+    /// Values that lost precision.
+    pub precision: u64,
+    /// Floating-point values whose fractional part was truncated to an integer.
+    pub truncated: u64,
+    /// Positive infinities converted to a type without them.
+    pub positive_infinity: u64,
+    /// Negative infinities converted to a type without them.
+    pub negative_infinity: u64,
+    /// NaNs converted to a type without them.
+    pub nan: u64,
+}
+
+impl ConversionReport {
+    /// Returns the total number of exceptions.
+    pub fn total(&self) -> u64 {
+        self.overflow
+            + self.underflow
+            + self.precision
+            + self.truncated
+            + self.positive_infinity
+            + self.negative_infinity
+            + self.nan
+    }
+
+    /// Returns `true` if every value was converted exactly.
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    fn record(&mut self, except: H5T_conv_except_t) {
+        let count = match except {
+            H5T_conv_except_t::H5T_CONV_EXCEPT_RANGE_HI => &mut self.overflow,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_RANGE_LOW => &mut self.underflow,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_PRECISION => &mut self.precision,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_TRUNCATE => &mut self.truncated,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_PINF => &mut self.positive_infinity,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_NINF => &mut self.negative_infinity,
+            H5T_conv_except_t::H5T_CONV_EXCEPT_NAN => &mut self.nan,
+        };
+        *count += 1;
+    }
+}
+
+/// The address passed to the exception callback to make it abort the conversion.
+static ABORT_CONVERSION: u8 = 0;
+
+fn abort_marker() -> *mut c_void {
+    ptr::addr_of!(ABORT_CONVERSION).cast_mut().cast()
+}
+
+/// The exception callback of all policies but the default one: aborts if `data` is the abort
+/// marker, otherwise leaves the exception to the library and counts it into the
+/// [`ConversionReport`] `data` points to, if any.
+unsafe extern "C" fn conversion_exception(
+    except: H5T_conv_except_t,
+    _src_id: hid_t,
+    _dst_id: hid_t,
+    _src_buf: *mut c_void,
+    _dst_buf: *mut c_void,
+    data: *mut c_void,
+) -> H5T_conv_ret_t {
+    if data == abort_marker() {
+        return H5T_conv_ret_t::H5T_CONV_ABORT;
+    }
+    if let Some(report) = unsafe { data.cast::<ConversionReport>().as_mut() } {
+        report.record(except);
+    }
+    H5T_conv_ret_t::H5T_CONV_UNHANDLED
+}
+
+fn set_conversion_callback(plist: hid_t, policy: ConversionExceptionPolicy) -> Result<()> {
+    let (op, data): (H5T_conv_except_func_t, _) = match policy {
+        ConversionExceptionPolicy::Default => (None, ptr::null_mut()),
+        ConversionExceptionPolicy::Abort => (Some(conversion_exception), abort_marker()),
+        ConversionExceptionPolicy::Count => (Some(conversion_exception), ptr::null_mut()),
+    };
+    h5try!(H5Pset_type_conv_cb(plist, op, data));
+    Ok(())
+}
+
+/// Makes transfers with the property list count conversion exceptions into `report`, which
+/// must stay valid for as long as the property list is used.
+pub(crate) fn set_conversion_report(plist: hid_t, report: *mut ConversionReport) -> Result<()> {
+    h5try!(H5Pset_type_conv_cb(plist, Some(conversion_exception), report.cast()));
+    Ok(())
+}
+
+/// Builder used to create dataset transfer property list.
+#[derive(Clone, Debug, Default)]
+pub struct DatasetTransferBuilder {
+    buffer_size: Option<usize>,
+    edc_check: Option<bool>,
+    hyper_vector_size: Option<usize>,
+    on_conversion_exception: Option<ConversionExceptionPolicy>,
+}
+
+impl DatasetTransferBuilder {
+    /// Creates a new dataset transfer property list builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new builder from an existing property list.
+    pub fn from_plist(plist: &DatasetTransfer) -> Result<Self> {
+        let mut builder = Self::default();
+        builder = builder.buffer_size(plist.get_buffer_size()?);
+        builder = builder.edc_check(plist.get_edc_check()?);
+        builder = builder.hyper_vector_size(plist.get_hyper_vector_size()?);
+        builder = builder.on_conversion_exception(plist.get_on_conversion_exception()?);
+        Ok(builder)
+    }
+
+    /// Sets the maximum size in bytes of the type conversion and background buffers.
+    ///
+    /// Reads and writes that require a datatype conversion are processed in pieces of at most
+    /// this many bytes, so raising it (from the default of 1 MiB) can considerably speed up
+    /// converted transfers of large datasets. The buffers are allocated by the library.
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = Some(size);
+        self
+    }
+
+    /// Sets whether error detection (e.g. the Fletcher32 checksum filter) is checked on reads.
+    pub fn edc_check(mut self, enable: bool) -> Self {
+        self.edc_check = Some(enable);
+        self
+    }
+
+    /// Sets the number of hyperslab offset and length pairs the library processes at a time
+    /// (1024 by default).
+    ///
+    /// Raising it can speed up transfers of selections made of many small hyperslabs.
+    pub fn hyper_vector_size(mut self, size: usize) -> Self {
+        self.hyper_vector_size = Some(size);
+        self
+    }
+
+    /// Sets what happens when a value cannot be converted exactly to the destination type.
+    pub fn on_conversion_exception(mut self, policy: ConversionExceptionPolicy) -> Self {
+        self.on_conversion_exception = Some(policy);
+        self
+    }
+
+    fn populate_plist(&self, id: hid_t) -> Result<()> {
+        if let Some(size) = self.buffer_size {
+            h5try!(H5Pset_buffer(id, size as _, ptr::null_mut(), ptr::null_mut()));
+        }
+        if let Some(enable) = self.edc_check {
+            let check = if enable { H5Z_EDC_t::H5Z_ENABLE_EDC } else { H5Z_EDC_t::H5Z_DISABLE_EDC };
+            h5try!(H5Pset_edc_check(id, check));
+        }
+        if let Some(size) = self.hyper_vector_size {
+            h5try!(H5Pset_hyper_vector_size(id, size as _));
+        }
+        if let Some(policy) = self.on_conversion_exception {
+            set_conversion_callback(id, policy)?;
+        }
+        Ok(())
+    }
+
+    /// Copies the builder settings into a dataset transfer property list.
+    pub fn apply(&self, plist: &mut DatasetTransfer) -> Result<()> {
+        h5lock!(self.populate_plist(plist.id()))
+    }
+
+    /// Constructs a new dataset transfer property list.
+    pub fn finish(&self) -> Result<DatasetTransfer> {
+        h5lock!({
+            let mut plist = DatasetTransfer::try_new()?;
+            self.apply(&mut plist).map(|()| plist)
+        })
+    }
+}
+
+impl_builder_mut! {
+    DatasetTransferBuilderMut for DatasetTransferBuilder {
+        fn buffer_size(size: usize);
+        fn edc_check(enable: bool);
+        fn hyper_vector_size(size: usize);
+        fn on_conversion_exception(policy: ConversionExceptionPolicy);
+    }
+}
+
+/// Dataset transfer property list.
+impl DatasetTransfer {
+    /// Creates a new dataset transfer property list.
+    pub fn try_new() -> Result<Self> {
+        Self::from_id(h5try!(H5Pcreate(H5P_DATASET_XFER.get()?)))
+    }
+
+    /// Creates a copy of the property list.
+    pub fn copy(&self) -> Result<Self> {
+        Ok(unsafe { self.deref().copy()?.cast_unchecked() })
+    }
+
+    /// Creates a new dataset transfer property list builder.
+    pub fn build() -> DatasetTransferBuilder {
+        DatasetTransferBuilder::new()
+    }
+
+    #[doc(hidden)]
+    pub fn get_buffer_size(&self) -> Result<usize> {
+        let size = h5lock!(H5Pget_buffer(self.id(), ptr::null_mut(), ptr::null_mut()));
+        ensure!(size > 0, "failed to retrieve the transfer buffer size");
+        Ok(size as _)
+    }
+
+    /// Returns the maximum size in bytes of the type conversion and background buffers.
+    pub fn buffer_size(&self) -> usize {
+        self.get_buffer_size().unwrap_or(DEFAULT_BUFFER_SIZE)
+    }
+
+    #[doc(hidden)]
+    pub fn get_edc_check(&self) -> Result<bool> {
+        match h5lock!(H5Pget_edc_check(self.id())) {
+            H5Z_EDC_t::H5Z_ENABLE_EDC => Ok(true),
+            H5Z_EDC_t::H5Z_DISABLE_EDC => Ok(false),
+            check => fail!("Invalid error detection setting: {:?}", check),
+        }
+    }
+
+    /// Returns `true` if error detection is checked on reads.
+    pub fn edc_check(&self) -> bool {
+        self.get_edc_check().unwrap_or(true)
+    }
+
+    #[doc(hidden)]
+    pub fn get_hyper_vector_size(&self) -> Result<usize> {
+        let mut size: size_t = 0;
+        h5call!(H5Pget_hyper_vector_size(self.id(), &mut size))?;
+        Ok(size as _)
+    }
+
+    /// Returns the number of hyperslab offset and length pairs processed at a time.
+    pub fn hyper_vector_size(&self) -> usize {
+        self.get_hyper_vector_size().unwrap_or(DEFAULT_HYPER_VECTOR_SIZE)
+    }
+
+    #[doc(hidden)]
+    pub fn get_on_conversion_exception(&self) -> Result<ConversionExceptionPolicy> {
+        let mut op: H5T_conv_except_func_t = None;
+        let mut data: *mut c_void = ptr::null_mut();
+        h5call!(H5Pget_type_conv_cb(self.id(), &mut op, &mut data))?;
+        Ok(match op {
+            None => ConversionExceptionPolicy::Default,
+            Some(_) if data == abort_marker() => ConversionExceptionPolicy::Abort,
+            Some(_) => ConversionExceptionPolicy::Count,
+        })
+    }
+
+    /// Returns what happens when a value cannot be converted exactly to the destination type.
+    pub fn on_conversion_exception(&self) -> ConversionExceptionPolicy {
+        self.get_on_conversion_exception().unwrap_or_default()
+    }
+}
+
+/// Set the memory manager for variable length items to
+/// the same allocator as is in use by hdf5-types
+pub(crate) fn set_vlen_manager_libc(plist: hid_t) -> Result<()> {
+    extern "C" fn alloc(size: size_t, _info: *mut c_void) -> *mut c_void {
+        panic::catch_unwind(|| unsafe { libc::malloc(size) }).unwrap_or(ptr::null_mut())
+    }
+    unsafe extern "C" fn free(ptr: *mut c_void, _info: *mut libc::c_void) {
+        let _p = panic::catch_unwind(|| unsafe {
+            libc::free(ptr);
+        });
+    }
+    h5try!(H5Pset_vlen_mem_manager(
+        plist,
+        Some(alloc),
+        ptr::null_mut(),
+        Some(free),
+        ptr::null_mut()
+    ));
+    Ok(())
+}
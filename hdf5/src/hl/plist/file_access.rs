@@ -4,7 +4,6 @@
 Not supported due to complexity combined with low likelihood of ever being used:
 
 - Low level direct VFD access: H5P{set,get}_driver, H5Pget_driver_info
-- Direct file image API: H5P{set,get}_file_image, H5P{set,get}_file_image_callbacks
 - Custom file access property lists in multi/family drivers
 - Interfacing directly with multi/family parts via types/offsets
 */
@@ -13,6 +12,7 @@ use std::fmt::{self, Debug};
 use std::iter;
 use std::mem;
 use std::ops::Deref;
+use std::path::{Path, PathBuf};
 use std::ptr::{self, addr_of, addr_of_mut};
 
 use bitflags::bitflags;
@@ -34,13 +34,13 @@ use crate::sys::h5fd::{
     H5FD_LOG_TIME_WRITE, H5FD_LOG_TRUNCATE,
 };
 use crate::sys::h5p::{
-    H5Pcreate, H5Pget_alignment, H5Pget_cache, H5Pget_driver, H5Pget_fapl_core, H5Pget_fapl_family,
-    H5Pget_fapl_multi, H5Pget_fclose_degree, H5Pget_gc_references, H5Pget_mdc_config,
-    H5Pget_meta_block_size, H5Pget_sieve_buf_size, H5Pget_small_data_block_size, H5Pset_alignment,
-    H5Pset_cache, H5Pset_fapl_core, H5Pset_fapl_family, H5Pset_fapl_log, H5Pset_fapl_multi,
-    H5Pset_fapl_sec2, H5Pset_fapl_split, H5Pset_fapl_stdio, H5Pset_fclose_degree,
-    H5Pset_gc_references, H5Pset_mdc_config, H5Pset_meta_block_size, H5Pset_sieve_buf_size,
-    H5Pset_small_data_block_size,
+    H5Pcreate, H5Pget_alignment, H5Pget_cache, H5Pget_driver, H5Pget_driver_info, H5Pget_fapl_core,
+    H5Pget_fapl_family, H5Pget_fapl_multi, H5Pget_fclose_degree, H5Pget_gc_references,
+    H5Pget_mdc_config, H5Pget_meta_block_size, H5Pget_sieve_buf_size, H5Pget_small_data_block_size,
+    H5Pset_alignment, H5Pset_cache, H5Pset_fapl_core, H5Pset_fapl_family, H5Pset_fapl_log,
+    H5Pset_fapl_multi, H5Pset_fapl_sec2, H5Pset_fapl_split, H5Pset_fapl_stdio,
+    H5Pset_fclose_degree, H5Pset_gc_references, H5Pset_mdc_config, H5Pset_meta_block_size,
+    H5Pset_sieve_buf_size, H5Pset_small_data_block_size,
 };
 #[cfg(feature = "have-direct")]
 use crate::sys::h5p::{H5Pget_fapl_direct, H5Pset_fapl_direct};
@@ -49,7 +49,6 @@ use crate::sys::h5p::{H5Pget_fapl_mpio, H5Pset_fapl_mpio};
 
 #[cfg(all(feature = "1.10.1", feature = "link"))]
 use crate::sys::h5ac::{H5AC_cache_image_config_t, H5AC__CACHE_IMAGE__ENTRY_AGEOUT__NONE};
-#[cfg(all(feature = "1.10.2", feature = "link"))]
 use crate::sys::h5f::H5F_libver_t;
 #[cfg(all(feature = "1.10.0", feature = "have-parallel"))]
 use crate::sys::h5p::{
@@ -63,7 +62,7 @@ use crate::sys::h5p::{
     H5Pget_evict_on_close, H5Pget_mdc_image_config, H5Pget_page_buffer_size, H5Pset_evict_on_close,
     H5Pset_mdc_image_config, H5Pset_page_buffer_size,
 };
-#[cfg(all(feature = "1.10.2", feature = "link"))]
+use crate::sys::h5p::{H5Pget_file_image, H5Pset_file_image};
 use crate::sys::h5p::{H5Pget_libver_bounds, H5Pset_libver_bounds};
 #[cfg(all(feature = "1.10.0", feature = "link"))]
 use crate::sys::h5p::{
@@ -114,7 +113,6 @@ impl Debug for FileAccess {
         formatter.field("fclose_degree", &self.fclose_degree());
         formatter.field("gc_references", &self.gc_references());
         formatter.field("small_data_block_size", &self.small_data_block_size());
-        #[cfg(all(feature = "1.10.2", feature = "link"))]
         formatter.field("libver_bounds", &self.libver_bounds());
         formatter.field("elink_file_cache_size", &self.elink_file_cache_size());
         formatter.field("meta_block_size", &self.meta_block_size());
@@ -162,19 +160,32 @@ impl Clone for FileAccess {
 }
 
 /// Core file driver properties.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct CoreDriver {
     /// Size, in bytes, of memory increments.
     pub increment: usize,
-    /// Whether to write the file contents to disk when the file is closed.
+    /// Whether to write the file contents to disk when the file is closed (the backing store).
     pub filebacked: bool,
     /// Size, in bytes, of write aggregation pages. Setting to 1 enables tracking with no paging.
     pub write_tracking: usize,
+    /// Initial file image, see [`FileAccessBuilder::file_image`].
+    pub initial_image: Option<Vec<u8>>,
 }
 
 impl Default for CoreDriver {
     fn default() -> Self {
-        Self { increment: 1024 * 1024, filebacked: false, write_tracking: 0 }
+        Self { increment: 1024 * 1024, filebacked: false, write_tracking: 0, initial_image: None }
+    }
+}
+
+impl Debug for CoreDriver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CoreDriver")
+            .field("increment", &self.increment)
+            .field("filebacked", &self.filebacked)
+            .field("write_tracking", &self.write_tracking)
+            .field("initial_image_size", &self.initial_image.as_ref().map(Vec::len))
+            .finish()
     }
 }
 
@@ -261,10 +272,22 @@ impl Default for LogFlags {
 
 /// Logging virtual file driver properties.
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
-pub struct LogOptions {
-    logfile: Option<String>,
-    flags: LogFlags,
-    buf_size: usize,
+pub struct LogDriver {
+    /// File the log is written to, or `None` for the standard error.
+    pub path: Option<PathBuf>,
+    /// Types of logged activity.
+    pub flags: LogFlags,
+    /// Size in bytes of the buffers tracking the flavor and access counts of file bytes.
+    pub buf_size: usize,
+}
+
+/// Properties of the Log driver as returned by `H5Pget_driver_info` (`H5FD_log_fapl_t`, which
+/// the library does not export).
+#[repr(C)]
+struct LogFapl {
+    logfile: *mut c_char,
+    flags: u64,
+    buf_size: size_t,
 }
 
 static FD_MEM_TYPES: &[H5F_mem_t] = &[
@@ -393,9 +416,9 @@ impl MultiDriver {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SplitDriver {
     /// Metadata filename extension.
-    pub meta_ext: String,
+    pub meta_ext: PathBuf,
     /// Raw data filename extension.
-    pub raw_ext: String,
+    pub raw_ext: PathBuf,
 }
 
 impl Default for SplitDriver {
@@ -530,7 +553,7 @@ pub enum FileDriver {
     /// Uses functions from the standard C `stdio.h` to perform buffered access to a single file.
     Stdio,
     /// SEC2 with logging capabilities.
-    Log,
+    Log(LogDriver),
     /// Keeps file contents in memory until the file is closed, enabling faster access.
     Core(CoreDriver),
     /// Partitions file address space into pieces and sends them to separate storage files.
@@ -547,6 +570,25 @@ pub enum FileDriver {
     Direct(DirectDriver),
 }
 
+impl FileDriver {
+    /// Returns the name HDF5 uses for the driver (e.g. `"sec2"`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sec2 => "sec2",
+            Self::Stdio => "stdio",
+            Self::Log(_) => "log",
+            Self::Core(_) => "core",
+            Self::Family(_) => "family",
+            Self::Multi(_) => "multi",
+            Self::Split(_) => "split",
+            #[cfg(feature = "mpio")]
+            Self::Mpio(_) => "mpio",
+            #[cfg(feature = "have-direct")]
+            Self::Direct(_) => "direct",
+        }
+    }
+}
+
 /// Options for what to do when trying to close a file while there are open objects inside it.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FileCloseDegree {
@@ -557,6 +599,10 @@ pub enum FileCloseDegree {
     /// Terminate file identifier access, but delay closing until all objects are closed.
     Weak,
     /// Return an error if the file has open objects.
+    ///
+    /// Errors cannot be reported when the last handle to a file is dropped, so the file then
+    /// stays open until the process exits; use [`File::try_close`](crate::File::try_close)
+    /// to detect this.
     Semi,
     /// Close all open objects, then close the file.
     Strong,
@@ -611,6 +657,7 @@ impl Default for Alignment {
 
 /// Raw data chunk cache parameters.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChunkCache {
     /// The number of objects in the cache.
     pub nslots: usize,
@@ -1018,12 +1065,13 @@ pub struct CacheLogOptions {
     pub start_on_access: bool,
 }
 
-#[cfg(all(feature = "1.10.2", feature = "link"))]
 mod libver {
     use super::*;
+    use crate::raw::hdf5_version_at_least;
 
     /// Options for which library format version to use when storing objects.
     #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum LibraryVersion {
         /// Use the earliest possible format.
         Earliest = 0,
@@ -1031,6 +1079,10 @@ mod libver {
         V18 = 1,
         /// Use the latest v110 format.
         V110 = 2,
+        /// Use the latest v112 format (requires HDF5 1.12 or later).
+        V112 = 3,
+        /// Use the latest v114 format (requires HDF5 1.14 or later).
+        V114 = 4,
     }
 
     impl LibraryVersion {
@@ -1039,9 +1091,15 @@ mod libver {
             self == Self::Earliest
         }
 
-        /// Returns the latest library version.
-        pub const fn latest() -> Self {
-            Self::V110
+        /// Returns the latest format version supported by the loaded library.
+        pub fn latest() -> Self {
+            if hdf5_version_at_least(1, 14, 0) {
+                Self::V114
+            } else if hdf5_version_at_least(1, 12, 0) {
+                Self::V112
+            } else {
+                Self::V110
+            }
         }
     }
 
@@ -1050,6 +1108,8 @@ mod libver {
             match v {
                 LibraryVersion::V18 => Self::H5F_LIBVER_V18,
                 LibraryVersion::V110 => Self::H5F_LIBVER_V110,
+                LibraryVersion::V112 => Self::H5F_LIBVER_V112,
+                LibraryVersion::V114 => Self::H5F_LIBVER_V114,
                 LibraryVersion::Earliest => Self::H5F_LIBVER_EARLIEST,
             }
         }
@@ -1060,6 +1120,8 @@ mod libver {
             match libver {
                 H5F_libver_t::H5F_LIBVER_V18 => Self::V18,
                 H5F_libver_t::H5F_LIBVER_V110 => Self::V110,
+                H5F_libver_t::H5F_LIBVER_V112 => Self::V112,
+                H5F_libver_t::H5F_LIBVER_V114 => Self::V114,
                 _ => Self::Earliest,
             }
         }
@@ -1067,6 +1129,7 @@ mod libver {
 
     /// Library format version bounds for writing objects to a file.
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct LibVerBounds {
         /// The earliest version to use for writing objects.
         pub low: LibraryVersion,
@@ -1093,15 +1156,14 @@ mod libver {
     }
 }
 
-#[cfg(all(feature = "1.10.2", feature = "link"))]
 pub use self::libver::*;
 
 /// Builder used to create file access property list.
 #[derive(Clone, Debug, Default)]
 pub struct FileAccessBuilder {
     file_driver: Option<FileDriver>,
-    log_options: LogOptions,
     write_tracking: Option<usize>,
+    file_image: Option<Vec<u8>>,
     fclose_degree: Option<FileCloseDegree>,
     alignment: Option<Alignment>,
     chunk_cache: Option<ChunkCache>,
@@ -1125,7 +1187,6 @@ pub struct FileAccessBuilder {
     coll_metadata_write: Option<bool>,
     gc_references: Option<bool>,
     small_data_block_size: Option<u64>,
-    #[cfg(all(feature = "1.10.2", feature = "link"))]
     libver_bounds: Option<LibVerBounds>,
 }
 
@@ -1138,45 +1199,39 @@ impl FileAccessBuilder {
     /// Creates a new builder from an existing property list.
     pub fn from_plist(plist: &FileAccess) -> Result<Self> {
         let mut builder = Self::default();
-        builder.fclose_degree(plist.get_fclose_degree()?);
+        builder = builder.fclose_degree(plist.get_fclose_degree()?);
         let v = plist.get_alignment()?;
-        builder.alignment(v.threshold, v.alignment);
+        builder = builder.alignment(v.threshold, v.alignment);
         let v = plist.get_chunk_cache()?;
-        builder.chunk_cache(v.nslots, v.nbytes, v.w0);
+        builder = builder.chunk_cache(v.nslots, v.nbytes, v.w0);
         let drv = plist.get_driver()?;
-        builder.driver(&drv);
-        builder.gc_references(plist.get_gc_references()?);
-        builder.small_data_block_size(plist.get_small_data_block_size()?);
-        #[cfg(all(feature = "1.10.2", feature = "link"))]
-        {
-            let v = plist.get_libver_bounds()?;
-            builder.libver_bounds(v.low, v.high);
-        }
-        builder.elink_file_cache_size(plist.get_elink_file_cache_size()?);
-        builder.meta_block_size(plist.get_meta_block_size()?);
+        builder = builder.driver(&drv);
+        builder = builder.gc_references(plist.get_gc_references()?);
+        builder = builder.small_data_block_size(plist.get_small_data_block_size()?);
+        let v = plist.get_libver_bounds()?;
+        builder = builder.libver_bounds(v.low, v.high);
+        builder = builder.elink_file_cache_size(plist.get_elink_file_cache_size()?);
+        builder = builder.meta_block_size(plist.get_meta_block_size()?);
         #[cfg(all(feature = "1.10.1", feature = "link"))]
         {
             let v = plist.get_page_buffer_size()?;
-            builder.page_buffer_size(v.buf_size, v.min_meta_perc, v.min_raw_perc);
-            builder.evict_on_close(plist.get_evict_on_close()?);
-            builder.mdc_image_config(plist.get_mdc_image_config()?.generate_image);
+            builder = builder.page_buffer_size(v.buf_size, v.min_meta_perc, v.min_raw_perc);
+            builder = builder.evict_on_close(plist.get_evict_on_close()?);
+            builder = builder.mdc_image_config(plist.get_mdc_image_config()?.generate_image);
         }
-        builder.sieve_buf_size(plist.get_sieve_buf_size()?);
+        builder = builder.sieve_buf_size(plist.get_sieve_buf_size()?);
         #[cfg(all(feature = "1.10.0", feature = "link"))]
         {
-            builder.metadata_read_attempts(plist.get_metadata_read_attempts()?);
+            builder = builder.metadata_read_attempts(plist.get_metadata_read_attempts()?);
             let v = plist.get_mdc_log_options()?;
-            builder.mdc_log_options(v.is_enabled, &v.location, v.start_on_access);
+            builder = builder.mdc_log_options(v.is_enabled, &v.location, v.start_on_access);
         }
         #[cfg(all(feature = "1.10.0", feature = "have-parallel"))]
         {
-            builder.all_coll_metadata_ops(plist.get_all_coll_metadata_ops()?);
-            builder.coll_metadata_write(plist.get_coll_metadata_write()?);
-        }
-        builder.mdc_config(&plist.get_mdc_config()?);
-        if let FileDriver::Core(ref drv) = drv {
-            builder.write_tracking(drv.write_tracking);
+            builder = builder.all_coll_metadata_ops(plist.get_all_coll_metadata_ops()?);
+            builder = builder.coll_metadata_write(plist.get_coll_metadata_write()?);
         }
+        builder = builder.mdc_config(&plist.get_mdc_config()?);
         Ok(builder)
     }
 
@@ -1185,31 +1240,35 @@ impl FileAccessBuilder {
     /// If called with `FileCloseDegree::Strong`, the programmer is responsible
     /// for closing all items before closing the file. Failure to do so might
     /// invalidate newly created objects.
-    pub fn fclose_degree(&mut self, fc_degree: FileCloseDegree) -> &mut Self {
+    pub fn fclose_degree(mut self, fc_degree: FileCloseDegree) -> Self {
         self.fclose_degree = Some(fc_degree);
         self
     }
 
     /// Sets the file alignment parameters.
-    pub fn alignment(&mut self, threshold: u64, alignment: u64) -> &mut Self {
+    pub fn alignment(mut self, threshold: u64, alignment: u64) -> Self {
         self.alignment = Some(Alignment { threshold, alignment });
         self
     }
 
     /// Sets the raw data chunk cache parameters.
-    pub fn chunk_cache(&mut self, nslots: usize, nbytes: usize, w0: f64) -> &mut Self {
+    pub fn chunk_cache(mut self, nslots: usize, nbytes: usize, w0: f64) -> Self {
         self.chunk_cache = Some(ChunkCache { nslots, nbytes, w0 });
         self
     }
 
     /// Sets the number of files that can be held open in an external link open file cache.
-    pub fn elink_file_cache_size(&mut self, efc_size: u32) -> &mut Self {
+    ///
+    /// Cached files stay open until the cache is full or cleared with
+    /// [`File::clear_external_link_cache`](crate::File::clear_external_link_cache). Zero (the
+    /// default) disables the cache.
+    pub fn elink_file_cache_size(mut self, efc_size: u32) -> Self {
         self.elink_file_cache_size = Some(efc_size);
         self
     }
 
     /// Sets the minimum metadata block size in bytes.
-    pub fn meta_block_size(&mut self, size: u64) -> &mut Self {
+    pub fn meta_block_size(mut self, size: u64) -> Self {
         self.meta_block_size = Some(size);
         self
     }
@@ -1217,24 +1276,24 @@ impl FileAccessBuilder {
     /// Sets the page buffer size properties.
     #[cfg(all(feature = "1.10.1", feature = "link"))]
     pub fn page_buffer_size(
-        &mut self,
+        mut self,
         buf_size: usize,
         min_meta_perc: u32,
         min_raw_perc: u32,
-    ) -> &mut Self {
+    ) -> Self {
         self.page_buffer_size = Some(PageBufferSize { buf_size, min_meta_perc, min_raw_perc });
         self
     }
 
     /// Sets the maximum size of the data sieve buffer.
-    pub fn sieve_buf_size(&mut self, size: usize) -> &mut Self {
+    pub fn sieve_buf_size(mut self, size: usize) -> Self {
         self.sieve_buf_size = Some(size);
         self
     }
 
     /// Sets whether object metadata should be evicted from cache when an object is closed.
     #[cfg(all(feature = "1.10.1", feature = "link"))]
-    pub fn evict_on_close(&mut self, evict_on_close: bool) -> &mut Self {
+    pub fn evict_on_close(mut self, evict_on_close: bool) -> Self {
         self.evict_on_close = Some(evict_on_close);
         self
     }
@@ -1242,20 +1301,20 @@ impl FileAccessBuilder {
     /// Sets the number of reads that the library will try when reading checksummed metadata in a
     /// file opened with SWMR access.
     #[cfg(all(feature = "1.10.0", feature = "link"))]
-    pub fn metadata_read_attempts(&mut self, attempts: u32) -> &mut Self {
+    pub fn metadata_read_attempts(mut self, attempts: u32) -> Self {
         self.metadata_read_attempts = Some(attempts);
         self
     }
 
     /// Sets the metadata cache configuration.
-    pub fn mdc_config(&mut self, config: &MetadataCacheConfig) -> &mut Self {
+    pub fn mdc_config(mut self, config: &MetadataCacheConfig) -> Self {
         self.mdc_config = Some(config.clone());
         self
     }
 
     /// Sets whether a cache image should be created on file close.
     #[cfg(all(feature = "1.10.1", feature = "link"))]
-    pub fn mdc_image_config(&mut self, generate_image: bool) -> &mut Self {
+    pub fn mdc_image_config(mut self, generate_image: bool) -> Self {
         self.mdc_image_config = Some(CacheImageConfig {
             generate_image,
             save_resize_status: false,
@@ -1267,11 +1326,11 @@ impl FileAccessBuilder {
     /// Sets metadata cache logging options.
     #[cfg(all(feature = "1.10.0", feature = "link"))]
     pub fn mdc_log_options(
-        &mut self,
+        mut self,
         is_enabled: bool,
         location: &str,
         start_on_access: bool,
-    ) -> &mut Self {
+    ) -> Self {
         self.mdc_log_options =
             Some(CacheLogOptions { is_enabled, location: location.into(), start_on_access });
         self
@@ -1279,135 +1338,157 @@ impl FileAccessBuilder {
 
     /// Sets whether metadata reads are collective.
     #[cfg(all(feature = "1.10.0", feature = "have-parallel"))]
-    pub fn all_coll_metadata_ops(&mut self, is_collective: bool) -> &mut Self {
+    pub fn all_coll_metadata_ops(mut self, is_collective: bool) -> Self {
         self.all_coll_metadata_ops = Some(is_collective);
         self
     }
 
     /// Sets whether metadata writes are collective.
     #[cfg(all(feature = "1.10.0", feature = "have-parallel"))]
-    pub fn coll_metadata_write(&mut self, is_collective: bool) -> &mut Self {
+    pub fn coll_metadata_write(mut self, is_collective: bool) -> Self {
         self.coll_metadata_write = Some(is_collective);
         self
     }
 
     /// Sets whether reference garbage collection is enabled.
-    pub fn gc_references(&mut self, gc_ref: bool) -> &mut Self {
+    pub fn gc_references(mut self, gc_ref: bool) -> Self {
         self.gc_references = Some(gc_ref);
         self
     }
 
     /// Sets the maximum size in bytes of a contiguous block reserved for small data.
-    pub fn small_data_block_size(&mut self, size: u64) -> &mut Self {
+    pub fn small_data_block_size(mut self, size: u64) -> Self {
         self.small_data_block_size = Some(size);
         self
     }
 
     /// Sets the range of library versions to use when writing objects.
-    #[cfg(all(feature = "1.10.2", feature = "link"))]
-    pub fn libver_bounds(&mut self, low: LibraryVersion, high: LibraryVersion) -> &mut Self {
+    pub fn libver_bounds(mut self, low: LibraryVersion, high: LibraryVersion) -> Self {
         self.libver_bounds = Some(LibVerBounds { low, high });
         self
     }
 
     /// Allows use of the earliest library version when writing objects.
-    #[cfg(all(feature = "1.10.2", feature = "link"))]
-    pub fn libver_earliest(&mut self) -> &mut Self {
+    pub fn libver_earliest(self) -> Self {
         self.libver_bounds(LibraryVersion::Earliest, LibraryVersion::latest())
     }
 
     /// Sets the earliest library version for writing objects to v18.
-    #[cfg(all(feature = "1.10.2", feature = "link"))]
-    pub fn libver_v18(&mut self) -> &mut Self {
+    pub fn libver_v18(self) -> Self {
         self.libver_bounds(LibraryVersion::V18, LibraryVersion::latest())
     }
 
     /// Sets the earliest library version for writing objects to v110.
-    #[cfg(all(feature = "1.10.2", feature = "link"))]
-    pub fn libver_v110(&mut self) -> &mut Self {
+    pub fn libver_v110(self) -> Self {
         self.libver_bounds(LibraryVersion::V110, LibraryVersion::latest())
     }
 
     /// Allows only the latest library version when writing objects.
-    #[cfg(all(feature = "1.10.2", feature = "link"))]
-    pub fn libver_latest(&mut self) -> &mut Self {
+    pub fn libver_latest(self) -> Self {
         self.libver_bounds(LibraryVersion::latest(), LibraryVersion::latest())
     }
 
     /// Sets which file driver to use.
-    pub fn driver(&mut self, file_driver: &FileDriver) -> &mut Self {
+    pub fn driver(mut self, file_driver: &FileDriver) -> Self {
         self.file_driver = Some(file_driver.clone());
         self
     }
 
     /// Sets the file driver to SEC2 (POSIX).
-    pub fn sec2(&mut self) -> &mut Self {
+    pub fn sec2(self) -> Self {
         self.driver(&FileDriver::Sec2)
     }
 
     /// Sets the file driver to STDIO.
-    pub fn stdio(&mut self) -> &mut Self {
+    pub fn stdio(self) -> Self {
         self.driver(&FileDriver::Stdio)
     }
 
     /// Sets the file driver to SEC2 with logging and configures it.
-    pub fn log_options(
-        &mut self,
-        logfile: Option<&str>,
+    ///
+    /// Without a `logfile`, the log is written to the standard error.
+    pub fn log_options<P: AsRef<Path>>(
+        self,
+        logfile: Option<P>,
         flags: LogFlags,
         buf_size: usize,
-    ) -> &mut Self {
-        self.log_options.logfile = logfile.map(Into::into);
-        self.log_options.flags = flags;
-        self.log_options.buf_size = buf_size;
-        self.driver(&FileDriver::Log)
+    ) -> Self {
+        let path = logfile.map(|path| path.as_ref().into());
+        self.driver(&FileDriver::Log(LogDriver { path, flags, buf_size }))
     }
 
     /// Sets the file driver to SEC2 with logging.
-    pub fn log(&mut self) -> &mut Self {
-        self.log_options(None, LogFlags::LOC_IO, 0)
+    pub fn log(self) -> Self {
+        self.driver(&FileDriver::Log(LogDriver::default()))
     }
 
-    /// Sets the file driver to Core and configures it.
-    pub fn core_options(&mut self, increment: usize, filebacked: bool) -> &mut Self {
+    /// Sets the file driver to Core with the given memory increment and backing store.
+    ///
+    /// The file image starts at `increment` bytes (or at the size of the existing file or
+    /// [file image](Self::file_image)) and grows by multiples of `increment` when full; each
+    /// growth may reallocate and copy the whole image. For in-memory files of many gigabytes,
+    /// an increment of tens of megabytes or more avoids most of the copies, at the cost of
+    /// up to one increment of unused memory. The increment must be positive.
+    ///
+    /// If `filebacked` is set, the image is written to the file named when creating or
+    /// opening it on flush and close; with [`write_tracking`](Self::write_tracking) enabled,
+    /// only the pages modified since the last write are written.
+    pub fn core_options(self, increment: usize, filebacked: bool) -> Self {
         let drv = CoreDriver { increment, filebacked, ..CoreDriver::default() };
         self.driver(&FileDriver::Core(drv))
     }
 
-    /// Sets the file driver to Core and sets whether to write file contents to disk upon closing.
-    pub fn core_filebacked(&mut self, filebacked: bool) -> &mut Self {
-        let drv = CoreDriver { filebacked, ..CoreDriver::default() };
-        self.driver(&FileDriver::Core(drv))
+    /// Sets the file driver to Core with the default increment and sets whether to write file
+    /// contents to disk upon closing, see [`core_options`](Self::core_options).
+    pub fn core_filebacked(self, filebacked: bool) -> Self {
+        self.core_options(CoreDriver::default().increment, filebacked)
     }
 
     /// Sets the file driver to Core.
-    pub fn core(&mut self) -> &mut Self {
+    pub fn core(self) -> Self {
         self.driver(&FileDriver::Core(CoreDriver::default()))
     }
 
     /// Sets the write tracking page size for the Core file driver.
-    pub fn write_tracking(&mut self, page_size: usize) -> &mut Self {
+    ///
+    /// With a backing store, write tracking makes flushing and closing write only the pages
+    /// modified since the last write instead of the whole image; a page size of 0 disables it.
+    pub fn write_tracking(mut self, page_size: usize) -> Self {
         self.write_tracking = Some(page_size);
         self
     }
 
+    /// Sets the initial file image used by the Core file driver.
+    ///
+    /// When a file is opened with the Core driver and a file image, its contents are read
+    /// from a copy of the image instead of from disk, so the file name only identifies the
+    /// file within the library. See also [`core_image`](Self::core_image).
+    pub fn file_image(mut self, image: &[u8]) -> Self {
+        self.file_image = Some(image.to_vec());
+        self
+    }
+
+    /// Sets the file driver to Core without a backing store and sets its initial file image.
+    ///
+    /// This allows opening a file from an in-memory copy of its bytes, as returned by
+    /// [`File::to_image`](crate::File::to_image); see also
+    /// [`File::from_image`](crate::File::from_image).
+    pub fn core_image(self, image: &[u8]) -> Self {
+        self.file_image(image).core_filebacked(false)
+    }
+
     /// Sets the file driver to Family.
-    pub fn family(&mut self) -> &mut Self {
+    pub fn family(self) -> Self {
         self.driver(&FileDriver::Family(FamilyDriver::default()))
     }
 
     /// Sets the file driver to Family and configures the file member size.
-    pub fn family_options(&mut self, member_size: usize) -> &mut Self {
+    pub fn family_options(self, member_size: usize) -> Self {
         self.driver(&FileDriver::Family(FamilyDriver { member_size }))
     }
 
     /// Sets the file driver to Multi and configures it.
-    pub fn multi_options(
-        &mut self,
-        files: &[MultiFile],
-        layout: &MultiLayout,
-        relax: bool,
-    ) -> &mut Self {
+    pub fn multi_options(self, files: &[MultiFile], layout: &MultiLayout, relax: bool) -> Self {
         self.driver(&FileDriver::Multi(MultiDriver {
             files: files.to_vec(),
             layout: layout.clone(),
@@ -1416,53 +1497,47 @@ impl FileAccessBuilder {
     }
 
     /// Sets the file driver to Multi.
-    pub fn multi(&mut self) -> &mut Self {
+    pub fn multi(self) -> Self {
         self.driver(&FileDriver::Multi(MultiDriver::default()))
     }
 
     /// Sets the file driver to Split and configures it.
-    pub fn split_options(&mut self, meta_ext: &str, raw_ext: &str) -> &mut Self {
+    pub fn split_options<M: AsRef<Path>, R: AsRef<Path>>(self, meta_ext: M, raw_ext: R) -> Self {
         self.driver(&FileDriver::Split(SplitDriver {
-            meta_ext: meta_ext.into(),
-            raw_ext: raw_ext.into(),
+            meta_ext: meta_ext.as_ref().into(),
+            raw_ext: raw_ext.as_ref().into(),
         }))
     }
 
     /// Sets the file driver to Split.
-    pub fn split(&mut self) -> &mut Self {
+    pub fn split(self) -> Self {
         self.driver(&FileDriver::Split(SplitDriver::default()))
     }
 
     /// Sets the file driver to MPI-I/O and configures it.
     #[cfg(feature = "mpio")]
-    pub fn mpio(&mut self, comm: mpi_sys::MPI_Comm, info: Option<mpi_sys::MPI_Info>) -> &mut Self {
+    pub fn mpio(mut self, comm: mpi_sys::MPI_Comm, info: Option<mpi_sys::MPI_Info>) -> Self {
         // We use .unwrap() here since MPI will almost surely terminate the process anyway.
         self.driver(&FileDriver::Mpio(MpioDriver::try_new(comm, info).unwrap()))
     }
 
     /// Sets the file driver to Direct and configures it.
     #[cfg(feature = "have-direct")]
-    pub fn direct_options(
-        &mut self,
-        alignment: usize,
-        block_size: usize,
-        cbuf_size: usize,
-    ) -> &mut Self {
+    pub fn direct_options(mut self, alignment: usize, block_size: usize, cbuf_size: usize) -> Self {
         self.driver(&FileDriver::Direct(DirectDriver { alignment, block_size, cbuf_size }))
     }
 
     /// Sets the file driver to Direct.
     #[cfg(feature = "have-direct")]
-    pub fn direct(&mut self) -> &mut Self {
+    pub fn direct(mut self) -> Self {
         self.driver(&FileDriver::Direct(DirectDriver::default()))
     }
 
-    fn set_log(&self, id: hid_t) -> Result<()> {
-        let opt = &self.log_options;
-        let flags = opt.flags.bits() as _;
-        let buf_size = opt.buf_size as _;
-        if let Some(ref logfile) = opt.logfile {
-            let logfile = to_cstring(logfile.as_ref())?;
+    fn set_log(id: hid_t, drv: &LogDriver) -> Result<()> {
+        let flags = drv.flags.bits() as _;
+        let buf_size = drv.buf_size as _;
+        if let Some(ref logfile) = drv.path {
+            let logfile = path_to_cstring(logfile)?;
             h5try!(H5Pset_fapl_log(id, logfile.as_ptr(), flags, buf_size));
         } else {
             h5try!(H5Pset_fapl_log(id, ptr::null(), flags, buf_size));
@@ -1471,14 +1546,20 @@ impl FileAccessBuilder {
     }
 
     fn set_core(&self, id: hid_t, drv: &CoreDriver) -> Result<()> {
+        ensure!(drv.increment > 0, "core driver increment must be positive");
         h5try!(H5Pset_fapl_core(id, drv.increment as _, hbool_t::from(drv.filebacked)));
-        if let Some(page_size) = self.write_tracking {
+        let write_tracking = (drv.write_tracking > 0).then_some(drv.write_tracking);
+        if let Some(page_size) = self.write_tracking.or(write_tracking) {
             h5try!(H5Pset_core_write_tracking(
                 id,
                 hbool_t::from(page_size > 0),
                 page_size.max(1) as _
             ));
         }
+        if let Some(image) = self.file_image.as_ref().or(drv.initial_image.as_ref()) {
+            // the library copies the image, so the buffer is not modified
+            h5try!(H5Pset_file_image(id, image.as_ptr().cast_mut().cast(), image.len()));
+        }
         Ok(())
     }
 
@@ -1537,8 +1618,8 @@ impl FileAccessBuilder {
     }
 
     fn set_split(id: hid_t, drv: &SplitDriver) -> Result<()> {
-        let meta_ext = to_cstring(drv.meta_ext.as_ref())?;
-        let raw_ext = to_cstring(drv.raw_ext.as_ref())?;
+        let meta_ext = path_to_cstring(&drv.meta_ext)?;
+        let raw_ext = path_to_cstring(&drv.raw_ext)?;
         h5try!(H5Pset_fapl_split(
             id,
             meta_ext.as_ptr(),
@@ -1569,8 +1650,8 @@ impl FileAccessBuilder {
             FileDriver::Stdio => {
                 h5try!(H5Pset_fapl_stdio(id));
             }
-            FileDriver::Log => {
-                self.set_log(id)?;
+            FileDriver::Log(drv) => {
+                Self::set_log(id, drv)?;
             }
             FileDriver::Core(drv) => {
                 self.set_core(id, drv)?;
@@ -1617,11 +1698,8 @@ impl FileAccessBuilder {
         if let Some(v) = self.small_data_block_size {
             h5try!(H5Pset_small_data_block_size(id, v as _));
         }
-        #[cfg(all(feature = "1.10.2", feature = "link"))]
-        {
-            if let Some(v) = self.libver_bounds {
-                h5try!(H5Pset_libver_bounds(id, v.low.into(), v.high.into()));
-            }
+        if let Some(v) = self.libver_bounds {
+            h5try!(H5Pset_libver_bounds(id, v.low.into(), v.high.into()));
         }
         if let Some(v) = self.elink_file_cache_size {
             h5try!(H5Pset_elink_file_cache_size(id, v as _));
@@ -1700,11 +1778,67 @@ impl FileAccessBuilder {
     }
 }
 
+impl_builder_mut! {
+    FileAccessBuilderMut for FileAccessBuilder {
+        fn fclose_degree(fc_degree: FileCloseDegree);
+        fn alignment(threshold: u64, alignment: u64);
+        fn chunk_cache(nslots: usize, nbytes: usize, w0: f64);
+        fn elink_file_cache_size(efc_size: u32);
+        fn meta_block_size(size: u64);
+        #[cfg(all(feature = "1.10.1", feature = "link"))]
+        fn page_buffer_size(buf_size: usize, min_meta_perc: u32, min_raw_perc: u32);
+        fn sieve_buf_size(size: usize);
+        #[cfg(all(feature = "1.10.1", feature = "link"))]
+        fn evict_on_close(evict_on_close: bool);
+        #[cfg(all(feature = "1.10.0", feature = "link"))]
+        fn metadata_read_attempts(attempts: u32);
+        fn mdc_config(config: &MetadataCacheConfig);
+        #[cfg(all(feature = "1.10.1", feature = "link"))]
+        fn mdc_image_config(generate_image: bool);
+        #[cfg(all(feature = "1.10.0", feature = "link"))]
+        fn mdc_log_options(is_enabled: bool, location: &str, start_on_access: bool);
+        #[cfg(all(feature = "1.10.0", feature = "have-parallel"))]
+        fn all_coll_metadata_ops(is_collective: bool);
+        #[cfg(all(feature = "1.10.0", feature = "have-parallel"))]
+        fn coll_metadata_write(is_collective: bool);
+        fn gc_references(gc_ref: bool);
+        fn small_data_block_size(size: u64);
+        fn libver_bounds(low: LibraryVersion, high: LibraryVersion);
+        fn libver_earliest();
+        fn libver_v18();
+        fn libver_v110();
+        fn libver_latest();
+        fn driver(file_driver: &FileDriver);
+        fn sec2();
+        fn stdio();
+        fn log_options[P: AsRef<Path>](logfile: Option<P>, flags: LogFlags, buf_size: usize);
+        fn log();
+        fn core_options(increment: usize, filebacked: bool);
+        fn core_filebacked(filebacked: bool);
+        fn core();
+        fn write_tracking(page_size: usize);
+        fn file_image(image: &[u8]);
+        fn core_image(image: &[u8]);
+        fn family();
+        fn family_options(member_size: usize);
+        fn multi_options(files: &[MultiFile], layout: &MultiLayout, relax: bool);
+        fn multi();
+        fn split_options[M: AsRef<Path>, R: AsRef<Path>](meta_ext: M, raw_ext: R);
+        fn split();
+        #[cfg(feature = "mpio")]
+        fn mpio(comm: mpi_sys::MPI_Comm, info: Option<mpi_sys::MPI_Info>);
+        #[cfg(feature = "have-direct")]
+        fn direct_options(alignment: usize, block_size: usize, cbuf_size: usize);
+        #[cfg(feature = "have-direct")]
+        fn direct();
+    }
+}
+
 /// File access property list.
 impl FileAccess {
     /// Creates a new file access property list.
     pub fn try_new() -> Result<Self> {
-        Self::from_id(h5try!(H5Pcreate(*H5P_FILE_ACCESS)))
+        Self::from_id(h5try!(H5Pcreate(H5P_FILE_ACCESS.get()?)))
     }
 
     /// Creates a copy of the property list.
@@ -1737,9 +1871,43 @@ impl FileAccess {
         } else {
             drv.write_tracking = 0;
         }
+        drv.initial_image = self.get_file_image()?;
         Ok(drv)
     }
 
+    fn get_log(&self) -> Result<LogDriver> {
+        h5lock!({
+            let info = H5Pget_driver_info(self.id()).cast::<LogFapl>();
+            ensure!(!info.is_null(), "unable to get the log driver properties");
+            let info = unsafe { &*info };
+            Ok(LogDriver {
+                path: (!info.logfile.is_null()).then(|| unsafe { path_from_cstr(info.logfile) }),
+                flags: LogFlags::from_bits_truncate(info.flags),
+                buf_size: info.buf_size as _,
+            })
+        })
+    }
+
+    #[doc(hidden)]
+    pub fn get_file_image(&self) -> Result<Option<Vec<u8>>> {
+        h5lock!({
+            let mut buf: *mut c_void = ptr::null_mut();
+            let mut len: size_t = 0;
+            h5try!(H5Pget_file_image(self.id(), addr_of_mut!(buf), addr_of_mut!(len)));
+            if buf.is_null() {
+                return Ok(None);
+            }
+            let image = unsafe { std::slice::from_raw_parts(buf.cast::<u8>(), len) }.to_vec();
+            unsafe { crate::util::h5_free_memory(buf) };
+            Ok(Some(image))
+        })
+    }
+
+    /// Returns a copy of the initial file image used by the Core file driver, if any.
+    pub fn file_image(&self) -> Option<Vec<u8>> {
+        self.get_file_image().unwrap_or(None)
+    }
+
     #[doc(hidden)]
     fn get_family(&self) -> Result<FamilyDriver> {
         let member_size = h5get!(H5Pget_fapl_family(self.id()): hsize_t, hid_t)?.0;
@@ -1763,29 +1931,26 @@ impl FileAccess {
             memb_addr.as_mut_ptr(),
             addr_of_mut!(relax),
         ));
+        // SAFETY: the names are null or strings allocated by the previous H5P call, which are
+        // copied and freed here before anything can return early
+        let memb_name = memb_name.map(|name| unsafe {
+            let copied = string_from_cstr(name);
+            h5_free_memory(name.cast());
+            copied
+        });
         let mut mapping: [u8; N] = unsafe { mem::zeroed() };
         let mut layout = MultiLayout::default();
         let mut files = Vec::new();
         for i in 1..N {
-            let (map, name, addr) = (memb_map[i], memb_name[i], memb_addr[i]);
+            let (map, name, addr) = (memb_map[i], &memb_name[i], memb_addr[i]);
             let j = map as usize;
             ensure!(j < N, "member map index out of bounds: {} (expected 0-{})", j, N - 1);
             if mapping[j] == 0 {
                 mapping[j] = 0xff - (files.len() as u8);
-                files.push(MultiFile::new(
-                    // SAFETY: name produced by HDF5 is nul-terminated and valid UTF-8
-                    unsafe { &string_from_cstr(name) },
-                    addr as _,
-                ));
+                files.push(MultiFile::new(name, addr as _));
             }
             *layout.get_mut(i - 1) = 0xff - mapping[j];
         }
-        for &memb_name in &memb_name {
-            // SAFETY: the array contains pointers to strings allocated by the previous H5P call
-            unsafe {
-                crate::util::h5_free_memory(memb_name as *mut _);
-            }
-        }
         let relax = relax > 0;
         let drv = MultiDriver { files, layout, relax };
         drv.validate().map(|()| drv)
@@ -1827,7 +1992,7 @@ impl FileAccess {
         } else if drv_id == *H5FD_STDIO {
             Ok(FileDriver::Stdio)
         } else if drv_id == *H5FD_LOG {
-            Ok(FileDriver::Log)
+            self.get_log().map(FileDriver::Log)
         } else if drv_id == *H5FD_CORE {
             self.get_core().map(FileDriver::Core)
         } else if drv_id == *H5FD_FAMILY {
@@ -1889,6 +2054,7 @@ impl FileAccess {
         h5get!(H5Pget_elink_file_cache_size(self.id()): c_uint).map(|x| x as _)
     }
 
+    /// Returns the number of files that can be held open in an external link open file cache.
     pub fn elink_file_cache_size(&self) -> u32 {
         self.get_elink_file_cache_size().unwrap_or(0)
     }
@@ -2062,7 +2228,6 @@ impl FileAccess {
         self.get_small_data_block_size().unwrap_or(2048)
     }
 
-    #[cfg(all(feature = "1.10.2", feature = "link"))]
     #[doc(hidden)]
     pub fn get_libver_bounds(&self) -> Result<LibVerBounds> {
         h5get!(H5Pget_libver_bounds(self.id()): H5F_libver_t, H5F_libver_t)
@@ -2070,13 +2235,11 @@ impl FileAccess {
     }
 
     /// Returns the library format version bounds for writing objects to a file.
-    #[cfg(all(feature = "1.10.2", feature = "link"))]
     pub fn libver_bounds(&self) -> LibVerBounds {
         self.get_libver_bounds().ok().unwrap_or_default()
     }
 
     /// Returns the lower library format version bound for writing objects to a file.
-    #[cfg(all(feature = "1.10.2", feature = "link"))]
     pub fn libver(&self) -> LibraryVersion {
         self.get_libver_bounds().ok().unwrap_or_default().low
     }
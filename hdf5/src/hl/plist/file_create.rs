@@ -13,11 +13,11 @@ use crate::sys::h5o::{
 };
 use crate::sys::h5p::{
     H5Pcreate, H5Pget_attr_creation_order, H5Pget_attr_phase_change, H5Pget_istore_k,
-    H5Pget_obj_track_times, H5Pget_shared_mesg_index, H5Pget_shared_mesg_nindexes,
-    H5Pget_shared_mesg_phase_change, H5Pget_sizes, H5Pget_sym_k, H5Pget_userblock,
-    H5Pset_attr_creation_order, H5Pset_attr_phase_change, H5Pset_istore_k, H5Pset_obj_track_times,
-    H5Pset_shared_mesg_index, H5Pset_shared_mesg_nindexes, H5Pset_shared_mesg_phase_change,
-    H5Pset_sym_k, H5Pset_userblock,
+    H5Pget_link_creation_order, H5Pget_obj_track_times, H5Pget_shared_mesg_index,
+    H5Pget_shared_mesg_nindexes, H5Pget_shared_mesg_phase_change, H5Pget_sizes, H5Pget_sym_k,
+    H5Pget_userblock, H5Pset_attr_creation_order, H5Pset_attr_phase_change, H5Pset_istore_k,
+    H5Pset_link_creation_order, H5Pset_obj_track_times, H5Pset_shared_mesg_index,
+    H5Pset_shared_mesg_nindexes, H5Pset_shared_mesg_phase_change, H5Pset_sym_k, H5Pset_userblock,
 };
 #[cfg(all(feature = "1.10.1", feature = "link"))]
 use crate::sys::h5p::{
@@ -26,7 +26,7 @@ use crate::sys::h5p::{
 };
 
 use crate::globals::H5P_FILE_CREATE;
-pub use crate::hl::plist::common::{AttrCreationOrder, AttrPhaseChange};
+pub use crate::hl::plist::common::{AttrCreationOrder, AttrPhaseChange, LinkCreationOrder};
 use crate::internal_prelude::*;
 
 /// File creation properties.
@@ -68,6 +68,7 @@ impl Debug for FileCreate {
         formatter.field("obj_track_times", &self.obj_track_times());
         formatter.field("attr_phase_change", &self.attr_phase_change());
         formatter.field("attr_creation_order", &self.attr_creation_order());
+        formatter.field("link_creation_order", &self.link_creation_order());
         #[cfg(all(feature = "1.10.1", feature = "link"))]
         {
             formatter.field("file_space_page_size", &self.file_space_page_size());
@@ -227,6 +228,7 @@ pub struct FileCreateBuilder {
     obj_track_times: Option<bool>,
     attr_phase_change: Option<AttrPhaseChange>,
     attr_creation_order: Option<AttrCreationOrder>,
+    link_creation_order: Option<LinkCreationOrder>,
     #[cfg(all(feature = "1.10.1", feature = "link"))]
     file_space_page_size: Option<u64>,
     #[cfg(all(feature = "1.10.1", feature = "link"))]
@@ -242,21 +244,22 @@ impl FileCreateBuilder {
     /// Creates a new builder from an existing property list.
     pub fn from_plist(plist: &FileCreate) -> Result<Self> {
         let mut builder = Self::default();
-        builder.userblock(plist.get_userblock()?);
+        builder = builder.userblock(plist.get_userblock()?);
         let v = plist.get_sym_k()?;
-        builder.sym_k(v.tree_rank, v.node_size);
-        builder.istore_k(plist.get_istore_k()?);
+        builder = builder.sym_k(v.tree_rank, v.node_size);
+        builder = builder.istore_k(plist.get_istore_k()?);
         let v = plist.get_shared_mesg_phase_change()?;
-        builder.shared_mesg_phase_change(v.max_list, v.min_btree);
-        builder.shared_mesg_indexes(&plist.get_shared_mesg_indexes()?);
-        builder.obj_track_times(plist.get_obj_track_times()?);
+        builder = builder.shared_mesg_phase_change(v.max_list, v.min_btree);
+        builder = builder.shared_mesg_indexes(&plist.get_shared_mesg_indexes()?);
+        builder = builder.obj_track_times(plist.get_obj_track_times()?);
         let apc = plist.get_attr_phase_change()?;
-        builder.attr_phase_change(apc.max_compact, apc.min_dense);
-        builder.attr_creation_order(plist.get_attr_creation_order()?);
+        builder = builder.attr_phase_change(apc.max_compact, apc.min_dense);
+        builder = builder.attr_creation_order(plist.get_attr_creation_order()?);
+        builder = builder.link_creation_order(plist.get_link_creation_order()?);
         #[cfg(all(feature = "1.10.1", feature = "link"))]
         {
-            builder.file_space_page_size(plist.get_file_space_page_size()?);
-            builder.file_space_strategy(plist.get_file_space_strategy()?);
+            builder = builder.file_space_page_size(plist.get_file_space_page_size()?);
+            builder = builder.file_space_strategy(plist.get_file_space_strategy()?);
         }
         Ok(builder)
     }
@@ -266,7 +269,7 @@ impl FileCreateBuilder {
     /// Sets the user block size of a file creation property list. The default
     /// user block size is 0; it may be set to any power of 2 equal to 512 or
     /// greater (512, 1024, 2048, etc.).
-    pub fn userblock(&mut self, size: u64) -> &mut Self {
+    pub fn userblock(mut self, size: u64) -> Self {
         self.userblock = Some(size);
         self
     }
@@ -277,7 +280,7 @@ impl FileCreateBuilder {
     /// `node_size`) retains the current value.
     ///
     /// For further details, see [`SymbolTableInfo`](struct.SymbolTableInfo.html).
-    pub fn sym_k(&mut self, tree_rank: u32, node_size: u32) -> &mut Self {
+    pub fn sym_k(mut self, tree_rank: u32, node_size: u32) -> Self {
         self.sym_k = Some(SymbolTableInfo { tree_rank, node_size });
         self
     }
@@ -295,7 +298,7 @@ impl FileCreateBuilder {
     /// cannot exceed 65536.
     ///
     /// The default value for `istore_k` is 32.
-    pub fn istore_k(&mut self, ik: u32) -> &mut Self {
+    pub fn istore_k(mut self, ik: u32) -> Self {
         self.istore_k = Some(ik);
         self
     }
@@ -303,7 +306,7 @@ impl FileCreateBuilder {
     /// Sets shared object header message storage phase change thresholds.
     ///
     /// For further details, see [`PhaseChangeInfo`](struct.PhaseChangeInfo.html).
-    pub fn shared_mesg_phase_change(&mut self, max_list: u32, min_btree: u32) -> &mut Self {
+    pub fn shared_mesg_phase_change(mut self, max_list: u32, min_btree: u32) -> Self {
         self.shared_mesg_phase_change = Some(PhaseChangeInfo { max_list, min_btree });
         self
     }
@@ -312,7 +315,7 @@ impl FileCreateBuilder {
     ///
     /// For each specified index, sets the types of messages that may be stored
     /// and the minimum size of each message
-    pub fn shared_mesg_indexes(&mut self, indexes: &[SharedMessageIndex]) -> &mut Self {
+    pub fn shared_mesg_indexes(mut self, indexes: &[SharedMessageIndex]) -> Self {
         self.shared_mesg_indexes = Some(indexes.into());
         self
     }
@@ -320,7 +323,7 @@ impl FileCreateBuilder {
     /// Sets a property that governs the recording of times associated with an object.
     ///
     /// If true, time data will be recorded; if false, time data will not be recorded.
-    pub fn obj_track_times(&mut self, track_times: bool) -> &mut Self {
+    pub fn obj_track_times(mut self, track_times: bool) -> Self {
         self.obj_track_times = Some(track_times);
         self
     }
@@ -328,7 +331,7 @@ impl FileCreateBuilder {
     /// Sets attribute storage phase change thresholds.
     ///
     /// For further details, see [`AttrPhaseChange`](enum.AttrPhaseChange.html).
-    pub fn attr_phase_change(&mut self, max_compact: u32, min_dense: u32) -> &mut Self {
+    pub fn attr_phase_change(mut self, max_compact: u32, min_dense: u32) -> Self {
         self.attr_phase_change = Some(AttrPhaseChange { max_compact, min_dense });
         self
     }
@@ -336,18 +339,26 @@ impl FileCreateBuilder {
     /// Sets flags for tracking and indexing attribute creation order.
     ///
     /// For further details, see [`AttrCreationOrder`](struct.AttrCreationOrder.html).
-    pub fn attr_creation_order(&mut self, attr_creation_order: AttrCreationOrder) -> &mut Self {
+    pub fn attr_creation_order(mut self, attr_creation_order: AttrCreationOrder) -> Self {
         self.attr_creation_order = Some(attr_creation_order);
         self
     }
 
+    /// Sets flags for tracking and indexing link creation order in the root group.
+    ///
+    /// For further details, see [`LinkCreationOrder`](struct.LinkCreationOrder.html).
+    pub fn link_creation_order(mut self, link_creation_order: LinkCreationOrder) -> Self {
+        self.link_creation_order = Some(link_creation_order);
+        self
+    }
+
     #[cfg(all(feature = "1.10.1", feature = "link"))]
     /// Sets the file space page size.
     ///
     /// The minimum size is 512. Setting a value less than 512 will result in
     /// an error. The library default size for the file space page size when
     /// not set is 4096.
-    pub fn file_space_page_size(&mut self, fsp_size: u64) -> &mut Self {
+    pub fn file_space_page_size(mut self, fsp_size: u64) -> Self {
         self.file_space_page_size = Some(fsp_size);
         self
     }
@@ -358,7 +369,7 @@ impl FileCreateBuilder {
     /// This setting cannot be changed for the life of the file.
     ///
     /// For further details, see [`FileSpaceStrategy`](enum.FileSpaceStrategy.html).
-    pub fn file_space_strategy(&mut self, strategy: FileSpaceStrategy) -> &mut Self {
+    pub fn file_space_strategy(mut self, strategy: FileSpaceStrategy) -> Self {
         self.file_space_strategy = Some(strategy);
         self
     }
@@ -396,6 +407,9 @@ impl FileCreateBuilder {
         if let Some(v) = self.attr_creation_order {
             h5try!(H5Pset_attr_creation_order(id, v.bits() as _));
         }
+        if let Some(v) = self.link_creation_order {
+            h5try!(H5Pset_link_creation_order(id, v.bits() as _));
+        }
         #[cfg(all(feature = "1.10.1", feature = "link"))]
         {
             if let Some(v) = self.file_space_page_size {
@@ -438,11 +452,29 @@ impl FileCreateBuilder {
     }
 }
 
+impl_builder_mut! {
+    FileCreateBuilderMut for FileCreateBuilder {
+        fn userblock(size: u64);
+        fn sym_k(tree_rank: u32, node_size: u32);
+        fn istore_k(ik: u32);
+        fn shared_mesg_phase_change(max_list: u32, min_btree: u32);
+        fn shared_mesg_indexes(indexes: &[SharedMessageIndex]);
+        fn obj_track_times(track_times: bool);
+        fn attr_phase_change(max_compact: u32, min_dense: u32);
+        fn attr_creation_order(attr_creation_order: AttrCreationOrder);
+        fn link_creation_order(link_creation_order: LinkCreationOrder);
+        #[cfg(all(feature = "1.10.1", feature = "link"))]
+        fn file_space_page_size(fsp_size: u64);
+        #[cfg(all(feature = "1.10.1", feature = "link"))]
+        fn file_space_strategy(strategy: FileSpaceStrategy);
+    }
+}
+
 /// File creation property list.
 impl FileCreate {
     /// Creates a new file creation property list.
     pub fn try_new() -> Result<Self> {
-        Self::from_id(h5try!(H5Pcreate(*H5P_FILE_CREATE)))
+        Self::from_id(h5try!(H5Pcreate(H5P_FILE_CREATE.get()?)))
     }
 
     /// Creates a copy of the file creation property list.
@@ -598,6 +630,17 @@ impl FileCreate {
         self.get_attr_creation_order().unwrap_or_default()
     }
 
+    #[doc(hidden)]
+    pub fn get_link_creation_order(&self) -> Result<LinkCreationOrder> {
+        h5get!(H5Pget_link_creation_order(self.id()): c_uint)
+            .map(LinkCreationOrder::from_bits_truncate)
+    }
+
+    /// Returns flags for tracking and indexing link creation order in the root group.
+    pub fn link_creation_order(&self) -> LinkCreationOrder {
+        self.get_link_creation_order().unwrap_or_default()
+    }
+
     /// Retrieves the file space page size.
     #[cfg(all(feature = "1.10.1", feature = "link"))]
     pub fn file_space_page_size(&self) -> u64 {
@@ -0,0 +1,242 @@
+//! Group creation properties.
+//!
+//! Groups created with the default properties are sized for a handful of links. Groups
+//! that are expected to hold many links (say, hundreds of thousands of datasets) should
+//! be created with tuned properties:
+//!
+//! - [`est_link_info`](GroupCreateBuilder::est_link_info) tells the library how many links
+//!   to expect and how long their names are, so that it can size the initial link storage
+//!   and avoid repeatedly growing it.
+//! - [`link_phase_change`](GroupCreateBuilder::link_phase_change) controls when the links are
+//!   moved from compact storage in the object header to dense storage indexed by a B-tree.
+//!   Compact storage is searched linearly and becomes slow with many links, so groups that
+//!   will be large should switch to dense storage early (or use it from the start by setting
+//!   `max_compact` to 0).
+//!
+//! These properties only apply to groups using the new-style (HDF5 1.8+) link storage, which
+//! is used in files whose lower library version bound is 1.8 or later (see
+//! [`libver_v18`](crate::plist::FileAccessBuilder::libver_v18)) and in groups tracking link
+//! creation order. Other groups use a symbol table and ignore the phase change thresholds.
+//! The storage in use can be checked with [`Group::storage_info`](crate::Group::storage_info).
+
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+
+use crate::sys::h5p::{
+    H5Pcreate, H5Pget_est_link_info, H5Pget_link_creation_order, H5Pget_link_phase_change,
+    H5Pset_est_link_info, H5Pset_link_creation_order, H5Pset_link_phase_change,
+};
+
+use crate::globals::H5P_GROUP_CREATE;
+pub use crate::hl::plist::common::{LinkCreationOrder, LinkPhaseChange};
+use crate::internal_prelude::*;
+
+/// Group creation properties.
+#[repr(transparent)]
+pub struct GroupCreate(Handle);
+
+impl ObjectClass for GroupCreate {
+    const NAME: &'static str = "group create property list";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_GENPROP_LST];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.is_class(PropertyListClass::GroupCreate),
+            "expected group create property list, got {:?}",
+            self.class()
+        );
+        Ok(())
+    }
+}
+
+impl Debug for GroupCreate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut formatter = f.debug_struct("GroupCreate");
+        formatter.field("est_link_info", &self.est_link_info());
+        formatter.field("link_phase_change", &self.link_phase_change());
+        formatter.field("link_creation_order", &self.link_creation_order());
+        formatter.finish()
+    }
+}
+
+impl Deref for GroupCreate {
+    type Target = PropertyList;
+
+    fn deref(&self) -> &PropertyList {
+        unsafe { self.transmute() }
+    }
+}
+
+impl PartialEq for GroupCreate {
+    fn eq(&self, other: &Self) -> bool {
+        <PropertyList as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for GroupCreate {}
+
+impl Clone for GroupCreate {
+    fn clone(&self) -> Self {
+        unsafe { self.deref().clone().cast_unchecked() }
+    }
+}
+
+/// The expected number of links in a group and the expected length of their names.
+///
+/// The estimates are used to size the initial link storage of a group in compact storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EstLinkInfo {
+    /// Expected number of links in the group (default: 4).
+    pub est_num_entries: u32,
+    /// Expected length of the link names, in bytes (default: 8).
+    pub est_name_len: u32,
+}
+
+impl Default for EstLinkInfo {
+    fn default() -> Self {
+        Self { est_num_entries: 4, est_name_len: 8 }
+    }
+}
+
+/// Builder used to create group creation property list.
+#[derive(Clone, Debug, Default)]
+pub struct GroupCreateBuilder {
+    est_link_info: Option<EstLinkInfo>,
+    link_phase_change: Option<LinkPhaseChange>,
+    link_creation_order: Option<LinkCreationOrder>,
+}
+
+impl GroupCreateBuilder {
+    /// Creates a new group creation property list builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new builder from an existing property list.
+    pub fn from_plist(plist: &GroupCreate) -> Result<Self> {
+        let mut builder = Self::default();
+        let eli = plist.get_est_link_info()?;
+        builder = builder.est_link_info(eli.est_num_entries, eli.est_name_len);
+        let lpc = plist.get_link_phase_change()?;
+        builder = builder.link_phase_change(lpc.max_compact, lpc.min_dense);
+        builder = builder.link_creation_order(plist.get_link_creation_order()?);
+        Ok(builder)
+    }
+
+    /// Sets the expected number of links in the group and the expected length of their names.
+    ///
+    /// For further details, see [`EstLinkInfo`].
+    pub fn est_link_info(mut self, est_num_entries: u32, est_name_len: u32) -> Self {
+        self.est_link_info = Some(EstLinkInfo { est_num_entries, est_name_len });
+        self
+    }
+
+    /// Sets link storage phase change thresholds.
+    ///
+    /// For further details, see [`LinkPhaseChange`].
+    pub fn link_phase_change(mut self, max_compact: u32, min_dense: u32) -> Self {
+        self.link_phase_change = Some(LinkPhaseChange { max_compact, min_dense });
+        self
+    }
+
+    /// Sets flags for tracking and indexing link creation order in the group.
+    ///
+    /// For further details, see [`LinkCreationOrder`].
+    pub fn link_creation_order(mut self, link_creation_order: LinkCreationOrder) -> Self {
+        self.link_creation_order = Some(link_creation_order);
+        self
+    }
+
+    fn populate_plist(&self, id: hid_t) -> Result<()> {
+        if let Some(v) = self.est_link_info {
+            h5try!(H5Pset_est_link_info(id, v.est_num_entries as _, v.est_name_len as _));
+        }
+        if let Some(v) = self.link_phase_change {
+            h5try!(H5Pset_link_phase_change(id, v.max_compact as _, v.min_dense as _));
+        }
+        if let Some(v) = self.link_creation_order {
+            h5try!(H5Pset_link_creation_order(id, v.bits() as _));
+        }
+        Ok(())
+    }
+
+    /// Copies the builder settings into a group creation property list.
+    pub fn apply(&self, plist: &mut GroupCreate) -> Result<()> {
+        h5lock!(self.populate_plist(plist.id()))
+    }
+
+    /// Constructs a new group creation property list.
+    pub fn finish(&self) -> Result<GroupCreate> {
+        h5lock!({
+            let mut plist = GroupCreate::try_new()?;
+            self.apply(&mut plist).map(|()| plist)
+        })
+    }
+}
+
+impl_builder_mut! {
+    GroupCreateBuilderMut for GroupCreateBuilder {
+        fn est_link_info(est_num_entries: u32, est_name_len: u32);
+        fn link_phase_change(max_compact: u32, min_dense: u32);
+        fn link_creation_order(link_creation_order: LinkCreationOrder);
+    }
+}
+
+/// Group creation property list.
+impl GroupCreate {
+    /// Creates a new group creation property list.
+    pub fn try_new() -> Result<Self> {
+        Self::from_id(h5try!(H5Pcreate(H5P_GROUP_CREATE.get()?)))
+    }
+
+    /// Creates a copy of the group creation property list.
+    pub fn copy(&self) -> Result<Self> {
+        Ok(unsafe { self.deref().copy()?.cast_unchecked() })
+    }
+
+    /// Returns a builder for configuring a group creation property list.
+    pub fn build() -> GroupCreateBuilder {
+        GroupCreateBuilder::new()
+    }
+
+    #[doc(hidden)]
+    pub fn get_est_link_info(&self) -> Result<EstLinkInfo> {
+        h5get!(H5Pget_est_link_info(self.id()): c_uint, c_uint)
+            .map(|(n, len)| EstLinkInfo { est_num_entries: n as _, est_name_len: len as _ })
+    }
+
+    /// Returns the expected number of links in the group and the expected length of their names.
+    pub fn est_link_info(&self) -> EstLinkInfo {
+        self.get_est_link_info().unwrap_or_default()
+    }
+
+    #[doc(hidden)]
+    pub fn get_link_phase_change(&self) -> Result<LinkPhaseChange> {
+        h5get!(H5Pget_link_phase_change(self.id()): c_uint, c_uint)
+            .map(|(mc, md)| LinkPhaseChange { max_compact: mc as _, min_dense: md as _ })
+    }
+
+    /// Returns link storage phase change thresholds.
+    pub fn link_phase_change(&self) -> LinkPhaseChange {
+        self.get_link_phase_change().unwrap_or_default()
+    }
+
+    #[doc(hidden)]
+    pub fn get_link_creation_order(&self) -> Result<LinkCreationOrder> {
+        h5get!(H5Pget_link_creation_order(self.id()): c_uint)
+            .map(LinkCreationOrder::from_bits_truncate)
+    }
+
+    /// Returns flags for tracking and indexing link creation order in the group.
+    pub fn link_creation_order(&self) -> LinkCreationOrder {
+        self.get_link_creation_order().unwrap_or_default()
+    }
+}
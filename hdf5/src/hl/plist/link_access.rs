@@ -0,0 +1,158 @@
+//! Link access properties.
+
+/*
+Not implemented:
+- H5P{set,get}_elink_cb (due to having to deal with raw C extern callbacks)
+- H5P{set,get}_elink_fapl, H5P{set,get}_elink_acc_flags, H5P{set,get}_nlinks
+*/
+
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use crate::sys::h5p::{H5Pcreate, H5Pget_elink_prefix, H5Pset_elink_prefix};
+
+use crate::globals::H5P_LINK_ACCESS;
+use crate::internal_prelude::*;
+
+/// Link access properties.
+#[repr(transparent)]
+pub struct LinkAccess(Handle);
+
+impl ObjectClass for LinkAccess {
+    const NAME: &'static str = "link access property list";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_GENPROP_LST];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.is_class(PropertyListClass::LinkAccess),
+            "expected link access property list, got {:?}",
+            self.class()
+        );
+        Ok(())
+    }
+}
+
+impl Debug for LinkAccess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut formatter = f.debug_struct("LinkAccess");
+        formatter.field("elink_prefix", &self.elink_prefix());
+        formatter.finish()
+    }
+}
+
+impl Deref for LinkAccess {
+    type Target = PropertyList;
+
+    fn deref(&self) -> &PropertyList {
+        unsafe { self.transmute() }
+    }
+}
+
+impl PartialEq for LinkAccess {
+    fn eq(&self, other: &Self) -> bool {
+        <PropertyList as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for LinkAccess {}
+
+impl Clone for LinkAccess {
+    fn clone(&self) -> Self {
+        unsafe { self.deref().clone().cast_unchecked() }
+    }
+}
+
+/// Builder used to create link access property list.
+#[derive(Clone, Debug, Default)]
+pub struct LinkAccessBuilder {
+    elink_prefix: Option<PathBuf>,
+}
+
+impl LinkAccessBuilder {
+    /// Creates a new link access property list builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new builder from an existing property list.
+    pub fn from_plist(plist: &LinkAccess) -> Result<Self> {
+        let mut builder = Self::default();
+        builder = builder.elink_prefix(&plist.get_elink_prefix()?);
+        Ok(builder)
+    }
+
+    /// Sets the directory in which the files targeted by external links with relative paths
+    /// are looked up first.
+    ///
+    /// The `HDF5_EXT_PREFIX` environment variable takes precedence; if the file is not found
+    /// with either prefix, the library falls back to the directory of the file holding the
+    /// link and then to the current working directory.
+    pub fn elink_prefix<P: AsRef<Path>>(mut self, prefix: P) -> Self {
+        self.elink_prefix = Some(prefix.as_ref().into());
+        self
+    }
+
+    fn populate_plist(&self, id: hid_t) -> Result<()> {
+        if let Some(ref prefix) = self.elink_prefix {
+            let prefix = path_to_cstring(prefix)?;
+            h5try!(H5Pset_elink_prefix(id, prefix.as_ptr()));
+        }
+        Ok(())
+    }
+
+    /// Copies the builder settings into a link access property list.
+    pub fn apply(&self, plist: &mut LinkAccess) -> Result<()> {
+        h5lock!(self.populate_plist(plist.id()))
+    }
+
+    /// Constructs a new link access property list.
+    pub fn finish(&self) -> Result<LinkAccess> {
+        h5lock!({
+            let mut plist = LinkAccess::try_new()?;
+            self.apply(&mut plist).map(|()| plist)
+        })
+    }
+}
+
+impl_builder_mut! {
+    LinkAccessBuilderMut for LinkAccessBuilder {
+        fn elink_prefix[P: AsRef<Path>](prefix: P);
+    }
+}
+
+/// Link access property list.
+impl LinkAccess {
+    /// Creates a new link access property list.
+    pub fn try_new() -> Result<Self> {
+        Self::from_id(h5try!(H5Pcreate(H5P_LINK_ACCESS.get()?)))
+    }
+
+    /// Creates a copy of the link access property list.
+    pub fn copy(&self) -> Result<Self> {
+        Ok(unsafe { self.deref().copy()?.cast_unchecked() })
+    }
+
+    /// Returns a builder for configuring a link access property list.
+    pub fn build() -> LinkAccessBuilder {
+        LinkAccessBuilder::new()
+    }
+
+    #[doc(hidden)]
+    pub fn get_elink_prefix(&self) -> Result<String> {
+        h5lock!(get_h5_str(|m, s| H5Pget_elink_prefix(self.id(), m, s)))
+    }
+
+    /// Returns the prefix applied to relative paths of external link targets.
+    pub fn elink_prefix(&self) -> String {
+        self.get_elink_prefix().ok().unwrap_or_default()
+    }
+}
@@ -94,19 +94,19 @@ impl LinkCreateBuilder {
     /// Creates a new builder from an existing property list.
     pub fn from_plist(plist: &LinkCreate) -> Result<Self> {
         let mut builder = Self::default();
-        builder.create_intermediate_group(plist.get_create_intermediate_group()?);
-        builder.char_encoding(plist.get_char_encoding()?);
+        builder = builder.create_intermediate_group(plist.get_create_intermediate_group()?);
+        builder = builder.char_encoding(plist.get_char_encoding()?);
         Ok(builder)
     }
 
     /// Sets whether to create intermediate groups upon creation of an object.
-    pub fn create_intermediate_group(&mut self, create: bool) -> &mut Self {
+    pub fn create_intermediate_group(mut self, create: bool) -> Self {
         self.create_intermediate_group = Some(create);
         self
     }
 
     /// Sets the character encoding to use when creating links.
-    pub fn char_encoding(&mut self, encoding: CharEncoding) -> &mut Self {
+    pub fn char_encoding(mut self, encoding: CharEncoding) -> Self {
         self.char_encoding = Some(encoding);
         self
     }
@@ -139,11 +139,18 @@ impl LinkCreateBuilder {
     }
 }
 
+impl_builder_mut! {
+    LinkCreateBuilderMut for LinkCreateBuilder {
+        fn create_intermediate_group(create: bool);
+        fn char_encoding(encoding: CharEncoding);
+    }
+}
+
 /// Link create property list.
 impl LinkCreate {
     /// Creates a new link creation property list.
     pub fn try_new() -> Result<Self> {
-        Self::from_id(h5try!(H5Pcreate(*H5P_LINK_CREATE)))
+        Self::from_id(h5try!(H5Pcreate(H5P_LINK_CREATE.get()?)))
     }
 
     /// Creates a copy of the link creation property list.
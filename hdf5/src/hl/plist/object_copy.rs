@@ -3,7 +3,8 @@ use std::fmt::{self, Debug};
 use std::ops::Deref;
 
 use crate::sys::h5o::{
-    H5O_COPY_EXPAND_EXT_LINK_FLAG, H5O_COPY_EXPAND_SOFT_LINK_FLAG, H5O_COPY_SHALLOW_HIERARCHY_FLAG,
+    H5O_COPY_EXPAND_EXT_LINK_FLAG, H5O_COPY_EXPAND_SOFT_LINK_FLAG,
+    H5O_COPY_MERGE_COMMITTED_DTYPE_FLAG, H5O_COPY_SHALLOW_HIERARCHY_FLAG,
     H5O_COPY_WITHOUT_ATTR_FLAG,
 };
 use crate::sys::h5p::{H5Pcreate, H5Pget_copy_object, H5Pset_copy_object};
@@ -44,6 +45,7 @@ impl Debug for ObjectCopy {
         formatter.field("shallow_hierarchy", &self.shallow_hierarchy());
         formatter.field("expand_soft_links", &self.expand_soft_links());
         formatter.field("expand_ext_links", &self.expand_ext_links());
+        formatter.field("merge_committed_dtype", &self.merge_committed_dtype());
         formatter.finish()
     }
 }
@@ -77,6 +79,7 @@ pub struct ObjectCopyBuilder {
     shallow_hierarchy: Option<bool>,
     expand_soft_links: Option<bool>,
     expand_ext_links: Option<bool>,
+    merge_committed_dtype: Option<bool>,
 }
 
 impl ObjectCopyBuilder {
@@ -93,33 +96,41 @@ impl ObjectCopyBuilder {
         builder.shallow_hierarchy = Some(flags & H5O_COPY_SHALLOW_HIERARCHY_FLAG != 0);
         builder.expand_soft_links = Some(flags & H5O_COPY_EXPAND_SOFT_LINK_FLAG != 0);
         builder.expand_ext_links = Some(flags & H5O_COPY_EXPAND_EXT_LINK_FLAG != 0);
+        builder.merge_committed_dtype = Some(flags & H5O_COPY_MERGE_COMMITTED_DTYPE_FLAG != 0);
         Ok(builder)
     }
 
     /// Copy object without copying attributes.
-    pub fn copy_without_attr(&mut self, enable: bool) -> &mut Self {
+    pub fn copy_without_attr(mut self, enable: bool) -> Self {
         self.copy_without_attr = Some(enable);
         self
     }
 
     /// Copy only immediate members of a group (shallow copy).
-    pub fn shallow_hierarchy(&mut self, enable: bool) -> &mut Self {
+    pub fn shallow_hierarchy(mut self, enable: bool) -> Self {
         self.shallow_hierarchy = Some(enable);
         self
     }
 
     /// Expand soft links into new objects.
-    pub fn expand_soft_links(&mut self, enable: bool) -> &mut Self {
+    pub fn expand_soft_links(mut self, enable: bool) -> Self {
         self.expand_soft_links = Some(enable);
         self
     }
 
     /// Expand external links into new objects.
-    pub fn expand_ext_links(&mut self, enable: bool) -> &mut Self {
+    pub fn expand_ext_links(mut self, enable: bool) -> Self {
         self.expand_ext_links = Some(enable);
         self
     }
 
+    /// Use a matching committed datatype linked in the destination file, if any, for the
+    /// committed datatypes of copied objects instead of copying them.
+    pub fn merge_committed_dtype(mut self, enable: bool) -> Self {
+        self.merge_committed_dtype = Some(enable);
+        self
+    }
+
     fn populate_plist(&self, id: hid_t) -> Result<()> {
         let mut flags = 0u32;
 
@@ -135,6 +146,9 @@ impl ObjectCopyBuilder {
         if self.expand_ext_links.unwrap_or(false) {
             flags |= H5O_COPY_EXPAND_EXT_LINK_FLAG;
         }
+        if self.merge_committed_dtype.unwrap_or(false) {
+            flags |= H5O_COPY_MERGE_COMMITTED_DTYPE_FLAG;
+        }
 
         if flags != 0 {
             h5try!(H5Pset_copy_object(id, flags));
@@ -155,10 +169,20 @@ impl ObjectCopyBuilder {
     }
 }
 
+impl_builder_mut! {
+    ObjectCopyBuilderMut for ObjectCopyBuilder {
+        fn copy_without_attr(enable: bool);
+        fn shallow_hierarchy(enable: bool);
+        fn expand_soft_links(enable: bool);
+        fn expand_ext_links(enable: bool);
+        fn merge_committed_dtype(enable: bool);
+    }
+}
+
 /// Object copy property list.
 impl ObjectCopy {
     pub fn try_new() -> Result<Self> {
-        Self::from_id(h5try!(H5Pcreate(*H5P_OBJECT_COPY)))
+        Self::from_id(h5try!(H5Pcreate(H5P_OBJECT_COPY.get()?)))
     }
 
     pub fn copy(&self) -> Result<Self> {
@@ -189,6 +213,10 @@ impl ObjectCopy {
     pub fn expand_ext_links(&self) -> bool {
         self.get_flags().map(|f| f & H5O_COPY_EXPAND_EXT_LINK_FLAG != 0).unwrap_or(false)
     }
+
+    pub fn merge_committed_dtype(&self) -> bool {
+        self.get_flags().map(|f| f & H5O_COPY_MERGE_COMMITTED_DTYPE_FLAG != 0).unwrap_or(false)
+    }
 }
 
 #[cfg(test)]
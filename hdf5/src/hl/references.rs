@@ -9,7 +9,8 @@ use crate::sys::h5o::H5O_type_t;
 use crate::sys::h5r::H5R_type_t;
 
 pub use legacy::ObjectReference1;
-pub use standard::ObjectReference2;
+pub(crate) use standard::ensure_std_references;
+pub use standard::{AttributeReference, ObjectReference2, RegionReference};
 
 mod private {
     pub trait ObjectReferencePrivate {}
@@ -30,7 +31,14 @@ pub trait ObjectReference: Sized + H5Type + private::ObjectReferencePrivate {
     fn create(location: &Location, name: &str) -> Result<Self>;
 
     /// Dereference the object reference in the space provided.
+    ///
+    /// Fails for [null](Self::is_null) references.
     fn dereference(&self, location: &Location) -> Result<ReferencedObject>;
+
+    /// Returns `true` if this is a null reference, i.e. all its bytes are zero.
+    ///
+    /// Elements of reference datasets or attributes that were never written read back as null.
+    fn is_null(&self) -> bool;
 }
 /// The result of dereferencing an [object reference](ObjectReference).
 ///
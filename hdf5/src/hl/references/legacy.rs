@@ -7,6 +7,7 @@ use crate::sys::{
     h5p::H5P_DEFAULT,
     h5r::{hobj_ref_t, H5Rcreate, H5Rdereference, H5Rget_obj_type2, H5R_OBJECT1},
 };
+use hdf5_types::references::HOBJ_REF_SIZE;
 use hdf5_types::H5Type;
 
 use super::{private::ObjectReferencePrivate, ObjectReference};
@@ -24,6 +25,23 @@ unsafe impl H5Type for ObjectReference1 {
     }
 }
 
+impl ObjectReference1 {
+    /// Returns a null reference.
+    pub fn null() -> Self {
+        Self { inner: 0 }
+    }
+
+    /// Creates a reference from its raw bytes, the native-endian file address of the object.
+    pub fn from_raw_bytes(bytes: [u8; HOBJ_REF_SIZE]) -> Self {
+        Self { inner: hobj_ref_t::from_ne_bytes(bytes) }
+    }
+
+    /// Returns the raw bytes of this reference, the native-endian file address of the object.
+    pub fn to_raw_bytes(&self) -> [u8; HOBJ_REF_SIZE] {
+        self.inner.to_ne_bytes()
+    }
+}
+
 impl ObjectReferencePrivate for ObjectReference1 {}
 
 impl ObjectReference for ObjectReference1 {
@@ -43,14 +61,15 @@ impl ObjectReference for ObjectReference1 {
             name.as_ptr(),
             Self::REF_TYPE,
             -1
-        ))?;
+        )?)?;
         let reference = unsafe { ref_out.assume_init() };
         Ok(Self { inner: reference })
     }
 
     fn get_object_type(&self, location: &Location) -> Result<crate::sys::h5o::H5O_type_t> {
+        ensure!(!self.is_null(), "cannot dereference a null object reference");
         let mut objtype = std::mem::MaybeUninit::<H5O_type_t>::uninit();
-        h5call!(H5Rget_obj_type2(location.id(), H5R_OBJECT1, self.ptr(), objtype.as_mut_ptr()))?;
+        h5call!(H5Rget_obj_type2(location.id(), H5R_OBJECT1, self.ptr(), objtype.as_mut_ptr())?)?;
         let objtype = unsafe { objtype.assume_init() };
         Ok(objtype)
     }
@@ -59,7 +78,11 @@ impl ObjectReference for ObjectReference1 {
         let object_type = self.get_object_type(location)?;
         // HDF5 1.10.0+ signature includes H5P_DEFAULT
         let object_id =
-            h5call!(H5Rdereference(location.id(), H5P_DEFAULT, H5R_OBJECT1, self.ptr()))?;
+            h5call!(H5Rdereference(location.id(), H5P_DEFAULT, H5R_OBJECT1, self.ptr())?)?;
         ReferencedObject::from_type_and_id(object_type, object_id)
     }
+
+    fn is_null(&self) -> bool {
+        self.inner == 0
+    }
 }
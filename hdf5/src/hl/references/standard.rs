@@ -1,23 +1,64 @@
 //! New standard reference types introduced in v1.12.0.
 //!
-//! These are gated on v1.12.1 since there appear to be multiple bugs in v1.12.0.
+//! These require v1.12.1 since there appear to be multiple bugs in v1.12.0. The types are
+//! always compiled, and their operations check the loaded library version at runtime.
 //!
+use crate::raw::{hdf5_version, hdf5_version_at_least, Version};
 use crate::sys::h5o::H5O_type_t;
-use crate::sys::h5r::H5R_type_t::H5R_OBJECT2;
-use crate::sys::h5r::{H5R_ref_t, H5Rcreate_object, H5Rdestroy, H5Rget_obj_type3, H5Ropen_object};
+use crate::sys::h5r::H5R_type_t::{self, H5R_ATTR, H5R_DATASET_REGION2, H5R_OBJECT2};
+use crate::sys::h5r::{
+    H5R_ref_t, H5R_ref_t_u, H5Rcreate_attr, H5Rcreate_object, H5Rcreate_region, H5Rdestroy,
+    H5Rget_attr_name, H5Rget_obj_type3, H5Rget_type, H5Ropen_attr, H5Ropen_object, H5Ropen_region,
+};
+use hdf5_types::references::{is_null_reference, H5R_REF_SIZE};
 
 use super::{private::ObjectReferencePrivate, ObjectReference};
 use crate::internal_prelude::*;
-use crate::Location;
+use crate::{Attribute, Location};
+
+/// The oldest library version whose standard references are supported.
+pub(crate) const STD_REFERENCE_VERSION: Version = Version { major: 1, minor: 12, micro: 1 };
+
+/// Returns a [`VersionUnsupported`](Error::VersionUnsupported) error for `feature` if the
+/// loaded library is older than [`STD_REFERENCE_VERSION`].
+pub(crate) fn ensure_std_references(feature: &str) -> Result<()> {
+    let Version { major, minor, micro } = STD_REFERENCE_VERSION;
+    if hdf5_version_at_least(major, minor, micro) {
+        Ok(())
+    } else {
+        Err(Error::VersionUnsupported {
+            feature: feature.into(),
+            required: STD_REFERENCE_VERSION,
+            detected: hdf5_version(),
+        })
+    }
+}
 
 /// A reference to a HDF5 item that can be stored in attributes or datasets.
 #[repr(transparent)]
 pub struct StdReference(H5R_ref_t);
 
 impl StdReference {
+    fn null() -> Self {
+        Self(H5R_ref_t { u: H5R_ref_t_u { __data: [0; H5R_REF_SIZE] } })
+    }
+
     fn ptr(&self) -> *const H5R_ref_t {
         std::ptr::addr_of!(self.0)
     }
+
+    fn as_bytes(&self) -> &[u8; H5R_REF_SIZE] {
+        // SAFETY: every bit pattern is a valid byte array
+        unsafe { &self.0.u.__data }
+    }
+
+    fn is_null(&self) -> bool {
+        is_null_reference(self.as_bytes())
+    }
+
+    fn ref_type(&self) -> H5R_type_t {
+        h5lock!(H5Rget_type(self.ptr()))
+    }
 }
 
 //todo: could we query some actual object parameters to make this more useful?
@@ -35,7 +76,10 @@ unsafe impl H5Type for StdReference {
 
 impl Drop for StdReference {
     fn drop(&mut self) {
-        let _e = h5call!(H5Rdestroy(&mut self.0));
+        // null references own no resources
+        if !self.is_null() {
+            let _e = h5call!(H5Rdestroy(&mut self.0));
+        }
     }
 }
 
@@ -43,6 +87,23 @@ impl Drop for StdReference {
 #[derive(Debug)]
 pub struct ObjectReference2(StdReference);
 
+impl ObjectReference2 {
+    /// Returns a null reference.
+    pub fn null() -> Self {
+        Self(StdReference::null())
+    }
+
+    /// Returns the raw bytes of this reference.
+    ///
+    /// Unlike [`ObjectReference1`](super::ObjectReference1), the bytes of a new-style reference
+    /// are an opaque in-memory representation that may hold pointers into the library, so they
+    /// are only meaningful within the library session that created them. References are only
+    /// portable once HDF5 itself serializes them, by writing them to a dataset or attribute.
+    pub fn as_bytes(&self) -> &[u8; H5R_REF_SIZE] {
+        self.0.as_bytes()
+    }
+}
+
 impl ObjectReferencePrivate for ObjectReference2 {}
 
 impl ObjectReference for ObjectReference2 {
@@ -58,6 +119,8 @@ impl ObjectReference for ObjectReference2 {
     }
 
     fn get_object_type(&self, _location: &Location) -> Result<crate::sys::h5o::H5O_type_t> {
+        ensure_std_references("ObjectReference2")?;
+        ensure!(!self.is_null(), "cannot dereference a null object reference");
         let mut objtype = std::mem::MaybeUninit::<H5O_type_t>::uninit();
         // Cast to *mut as HDF5 API signature requires, though it doesn't mutate the reference
         h5call!(H5Rget_obj_type3(self.0.ptr() as *mut _, H5P_DEFAULT, objtype.as_mut_ptr()))?;
@@ -71,6 +134,10 @@ impl ObjectReference for ObjectReference2 {
         let object_id = h5call!(H5Ropen_object(self.0.ptr() as *mut _, H5P_DEFAULT, H5P_DEFAULT))?;
         ReferencedObject::from_type_and_id(object_type, object_id)
     }
+
+    fn is_null(&self) -> bool {
+        self.0.is_null()
+    }
 }
 
 unsafe impl H5Type for ObjectReference2 {
@@ -79,7 +146,117 @@ unsafe impl H5Type for ObjectReference2 {
     }
 }
 
+/// A reference to an attribute of an object, stored with the standard (v1.12+) reference type.
+///
+/// Attribute references share the file datatype of [`ObjectReference2`], so both kinds can be
+/// stored in the same dataset.
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct AttributeReference(StdReference);
+
+impl AttributeReference {
+    /// Creates a reference to the attribute `attr_name` of the object `name` relative to `location`.
+    pub fn create(location: &Location, name: &str, attr_name: &str) -> Result<Self> {
+        ensure_std_references("AttributeReference")?;
+        let name = to_cstring(name)?;
+        let attr_name = to_cstring(attr_name)?;
+        let mut out = std::mem::MaybeUninit::<H5R_ref_t>::uninit();
+        h5call!(H5Rcreate_attr(
+            location.id(),
+            name.as_ptr(),
+            attr_name.as_ptr(),
+            H5P_DEFAULT,
+            out.as_mut_ptr()
+        ))?;
+        Ok(Self(StdReference(unsafe { out.assume_init() })))
+    }
+
+    /// Opens the referenced attribute.
+    pub fn dereference(&self) -> Result<Attribute> {
+        self.ensure_attr()?;
+        // Cast to *mut as HDF5 API signature requires, though it doesn't mutate the reference
+        let attr_id = h5call!(H5Ropen_attr(self.0.ptr() as *mut _, H5P_DEFAULT, H5P_DEFAULT))?;
+        Attribute::from_id(attr_id)
+    }
+
+    /// Returns the name of the referenced attribute.
+    pub fn attr_name(&self) -> Result<String> {
+        self.ensure_attr()?;
+        h5lock!(get_h5_str(|m, s| H5Rget_attr_name(self.0.ptr(), m, s)))
+    }
+
+    fn ensure_attr(&self) -> Result<()> {
+        ensure_std_references("AttributeReference")?;
+        ensure!(!self.0.is_null(), "cannot dereference a null attribute reference");
+        let ref_type = self.0.ref_type();
+        ensure!(ref_type == H5R_ATTR, "expected an attribute reference, got {:?}", ref_type);
+        Ok(())
+    }
+}
+
+unsafe impl H5Type for AttributeReference {
+    fn type_descriptor() -> hdf5_types::TypeDescriptor {
+        hdf5_types::TypeDescriptor::Reference(hdf5_types::Reference::Std)
+    }
+}
+
+/// A reference to a selection of a dataset, stored with the standard (v1.12+) reference type.
+///
+/// Region references share the file datatype of [`ObjectReference2`], so both kinds can be
+/// stored in the same dataset.
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct RegionReference(StdReference);
+
+impl RegionReference {
+    /// Creates a reference to the elements of `dataset` selected by `selection`.
+    pub fn create<S: Into<Selection>>(dataset: &Dataset, selection: S) -> Result<Self> {
+        ensure_std_references("RegionReference")?;
+        let space = dataset.space()?.select(selection)?;
+        let name = to_cstring(".")?;
+        let mut out = std::mem::MaybeUninit::<H5R_ref_t>::uninit();
+        h5call!(H5Rcreate_region(
+            dataset.id(),
+            name.as_ptr(),
+            space.id(),
+            H5P_DEFAULT,
+            out.as_mut_ptr()
+        ))?;
+        Ok(Self(StdReference(unsafe { out.assume_init() })))
+    }
+
+    /// Opens the referenced dataset.
+    pub fn dataset(&self) -> Result<Dataset> {
+        self.ensure_region()?;
+        // Cast to *mut as HDF5 API signature requires, though it doesn't mutate the reference
+        let id = h5call!(H5Ropen_object(self.0.ptr() as *mut _, H5P_DEFAULT, H5P_DEFAULT))?;
+        Dataset::from_id(id)
+    }
+
+    /// Returns the referenced selection.
+    pub fn selection(&self) -> Result<Selection> {
+        self.ensure_region()?;
+        let id = h5call!(H5Ropen_region(self.0.ptr() as *mut _, H5P_DEFAULT, H5P_DEFAULT))?;
+        Dataspace::from_id(id)?.get_selection()
+    }
+
+    fn ensure_region(&self) -> Result<()> {
+        ensure_std_references("RegionReference")?;
+        ensure!(!self.0.is_null(), "cannot dereference a null region reference");
+        let ref_type = self.0.ref_type();
+        ensure!(ref_type == H5R_DATASET_REGION2, "expected a region reference, got {:?}", ref_type);
+        Ok(())
+    }
+}
+
+unsafe impl H5Type for RegionReference {
+    fn type_descriptor() -> hdf5_types::TypeDescriptor {
+        hdf5_types::TypeDescriptor::Reference(hdf5_types::Reference::Std)
+    }
+}
+
 fn create_object_reference(dataset: &Location, name: &str) -> Result<H5R_ref_t> {
+    ensure_std_references("ObjectReference2")?;
     let mut out: std::mem::MaybeUninit<H5R_ref_t> = std::mem::MaybeUninit::uninit();
     let name = to_cstring(name)?;
     h5call!(H5Rcreate_object(dataset.id(), name.as_ptr(), H5P_DEFAULT, out.as_mut_ptr().cast(),))?;
@@ -92,9 +269,9 @@ mod tests {
 
     #[test]
     pub fn test_references() {
-        // ObjectReference2 (H5R_ref_t) requires HDF5 1.12.0+
-        if !crate::sys::hdf5_version_at_least(1, 12, 0) {
-            eprintln!("Skipping test_references: requires HDF5 >= 1.12.0");
+        // ObjectReference2 (H5R_ref_t) requires HDF5 1.12.1+
+        if !crate::raw::hdf5_version_at_least(1, 12, 1) {
+            eprintln!("Skipping test_references: requires HDF5 >= 1.12.1");
             return;
         }
 
@@ -111,4 +288,40 @@ mod tests {
             assert!(matches!(ds, ReferencedObject::Dataset(_)));
         })
     }
+
+    #[test]
+    pub fn test_attribute_references() {
+        if !crate::raw::hdf5_version_at_least(1, 12, 1) {
+            eprintln!("Skipping test_attribute_references: requires HDF5 >= 1.12.1");
+            return;
+        }
+
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            let ds = file.new_dataset::<f64>().create("data/ds").unwrap();
+            ds.new_attr::<i32>().create("units").unwrap().write_scalar(&42).unwrap();
+            ds.new_attr::<u8>().create("other").unwrap().write_scalar(&7).unwrap();
+
+            let refs = [
+                AttributeReference::create(&file, "data/ds", "units").unwrap(),
+                AttributeReference::create(&file.group("data").unwrap(), "ds", "other").unwrap(),
+            ];
+            file.new_dataset_builder().with_data(&refs).create("attr_refs").unwrap();
+            let objs = [file.reference::<ObjectReference2>("data/ds").unwrap()];
+            file.new_dataset_builder().with_data(&objs).create("obj_refs").unwrap();
+            assert!(AttributeReference::create(&file, "data/ds", "missing").is_err());
+            drop((refs, objs, ds, file));
+
+            let file = File::open(&path).unwrap();
+            let refs = file.dataset("attr_refs").unwrap().read_raw::<AttributeReference>().unwrap();
+            assert_eq!(refs[0].attr_name().unwrap(), "units");
+            assert_eq!(refs[0].dereference().unwrap().read_scalar::<i32>().unwrap(), 42);
+            assert_eq!(refs[1].attr_name().unwrap(), "other");
+            assert_eq!(refs[1].dereference().unwrap().read_scalar::<u8>().unwrap(), 7);
+
+            // object references can't be reinterpreted as attribute references
+            let objs = file.dataset("obj_refs").unwrap().read_raw::<AttributeReference>().unwrap();
+            assert_err!(objs[0].dereference(), "expected an attribute reference");
+        })
+    }
 }
@@ -0,0 +1,308 @@
+//! Reading 1-D compound datasets in row groups, with per-field column extraction.
+
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
+use std::ops::Range;
+use std::sync::Arc;
+
+use hdf5_types::{CompoundType, TypeDescriptor};
+
+use crate::internal_prelude::*;
+
+/// A block of consecutive rows of a 1-D compound dataset, as yielded by [`RowGroupIter`].
+///
+/// The rows are stored as read (array-of-structs); [`column`](Self::column) transposes a single
+/// field into its own vector for consumers that expect struct-of-arrays input.
+pub struct RowGroup<T> {
+    start: usize,
+    rows: Vec<T>,
+    ty: Arc<CompoundType>,
+}
+
+impl<T> Debug for RowGroup<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RowGroup").field("rows", &self.range()).finish_non_exhaustive()
+    }
+}
+
+impl<T> RowGroup<T> {
+    /// Returns the index of the first row of the group within the dataset.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the range of dataset rows covered by the group.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.start + self.rows.len()
+    }
+
+    /// Returns the number of rows in the group.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if the group contains no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the rows of the group.
+    pub fn rows(&self) -> &[T] {
+        &self.rows
+    }
+
+    /// Consumes the group and returns its rows.
+    pub fn into_rows(self) -> Vec<T> {
+        self.rows
+    }
+
+    /// Returns the names of the fields of `T` in declaration order.
+    pub fn field_names(&self) -> Vec<&str> {
+        let mut fields: Vec<_> = self.ty.fields.iter().collect();
+        fields.sort_by_key(|field| field.index);
+        fields.into_iter().map(|field| field.name.as_str()).collect()
+    }
+
+    /// Extracts the field `name` of every row into a column.
+    ///
+    /// The type `F` must have exactly the type descriptor of the field in `T`; no conversion
+    /// is performed since the data is already in memory.
+    pub fn column<F: H5Type + Clone>(&self, name: &str) -> Result<Vec<F>> {
+        let field = self
+            .ty
+            .fields
+            .iter()
+            .find(|field| field.name == name)
+            .ok_or_else(|| format!("no field '{name}' in the row type"))?;
+        let expected = F::type_descriptor();
+        ensure!(
+            field.ty == expected,
+            "field '{}' has type {}, cannot extract it as {}",
+            name,
+            field.ty,
+            expected
+        );
+        ensure!(
+            field.offset + mem::size_of::<F>() <= mem::size_of::<T>(),
+            "field '{}' at offset {} does not fit into the row type",
+            name,
+            field.offset
+        );
+        let offset = field.offset;
+        let column = self
+            .rows
+            .iter()
+            .map(|row| unsafe {
+                let ptr = (row as *const T).cast::<u8>().add(offset).cast::<F>();
+                // the row keeps owning the field, so the bitwise copy must not be dropped
+                let value = ManuallyDrop::new(ptr.read_unaligned());
+                F::clone(&value)
+            })
+            .collect();
+        Ok(column)
+    }
+}
+
+/// Iterator over consecutive row groups of a 1-D compound dataset, see
+/// [`Reader::read_row_groups`].
+pub struct RowGroupIter<'a, T> {
+    reader: Reader<'a>,
+    ty: Arc<CompoundType>,
+    rows_per_group: usize,
+    pos: usize,
+    len: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Debug for RowGroupIter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RowGroupIter")
+            .field("rows_per_group", &self.rows_per_group)
+            .field("pos", &self.pos)
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: H5Type> Iterator for RowGroupIter<'_, T> {
+    type Item = Result<RowGroup<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.len {
+            return None;
+        }
+        let start = self.pos;
+        let count = self.rows_per_group.min(self.len - start);
+        self.pos += count;
+        let rows = self
+            .reader
+            .read_rows(start, count)
+            .with_context(|| format!("reading rows {}..{}", start, start + count));
+        Some(rows.map(|rows| RowGroup { start, rows, ty: Arc::clone(&self.ty) }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.len - self.pos).div_ceil(self.rows_per_group);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: H5Type> ExactSizeIterator for RowGroupIter<'_, T> {}
+
+impl<'a> Reader<'a> {
+    /// Returns an iterator reading a 1-D compound dataset in groups of at most
+    /// `rows_per_group` rows, the last group holding the remainder.
+    ///
+    /// Each group is fetched with a single read; fields can then be extracted as columns
+    /// with [`RowGroup::column`].
+    pub fn read_row_groups<T: H5Type>(&self, rows_per_group: usize) -> Result<RowGroupIter<'a, T>> {
+        ensure!(rows_per_group > 0, "rows per group must be positive");
+        let desc = T::type_descriptor();
+        let TypeDescriptor::Compound(ty) = desc else {
+            fail!("row groups require a compound row type, got {}", desc);
+        };
+        let obj = self.container();
+        ensure!(!obj.is_attr(), "row groups cannot be read from attributes");
+        let shape = obj.shape()?;
+        ensure!(shape.len() == 1, "expected a 1-D dataset, got shape {:?}", shape);
+        Ok(RowGroupIter {
+            reader: self.clone(),
+            ty: Arc::new(ty),
+            rows_per_group,
+            pos: 0,
+            len: shape[0],
+            marker: PhantomData,
+        })
+    }
+}
+
+impl Container {
+    /// Returns an iterator reading a 1-D compound dataset in row groups, see
+    /// [`Reader::read_row_groups`].
+    pub fn read_row_groups<T: H5Type>(&self, rows_per_group: usize) -> Result<RowGroupIter<'_, T>> {
+        self.as_reader().read_row_groups(rows_per_group)
+    }
+}
+
+impl Dataset {
+    /// Returns the number of rows of the dataset, that is, its extent along the first
+    /// dimension.
+    pub fn n_rows(&self) -> Result<usize> {
+        let shape = self.shape()?;
+        shape.first().copied().ok_or_else(|| "a scalar dataset has no rows".into())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::mem;
+
+    use hdf5_types::{CompoundField, CompoundType, TypeDescriptor, VarLenUnicode};
+
+    use crate::internal_prelude::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    #[repr(C)]
+    struct Trade {
+        id: u64,
+        price: f64,
+        qty: i32,
+    }
+
+    unsafe impl H5Type for Trade {
+        fn type_descriptor() -> TypeDescriptor {
+            TypeDescriptor::Compound(CompoundType {
+                fields: vec![
+                    CompoundField::typed::<u64>("id", mem::offset_of!(Trade, id), 0),
+                    CompoundField::typed::<f64>("price", mem::offset_of!(Trade, price), 1),
+                    CompoundField::typed::<i32>("qty", mem::offset_of!(Trade, qty), 2),
+                ],
+                size: mem::size_of::<Trade>(),
+            })
+        }
+    }
+
+    #[test]
+    pub fn test_row_groups() {
+        with_tmp_file(|file| {
+            let trades: Vec<_> = (0..10_000)
+                .map(|i| Trade { id: i, price: i as f64 * 0.25, qty: (i % 97) as i32 - 48 })
+                .collect();
+            let ds = file.new_dataset_builder().with_data(&trades).create("trades").unwrap();
+            assert_eq!(ds.n_rows().unwrap(), 10_000);
+
+            let groups = ds.read_row_groups::<Trade>(3_000).unwrap();
+            assert_eq!(groups.len(), 4);
+            let groups = groups.collect::<Result<Vec<_>>>().unwrap();
+            let lens: Vec<_> = groups.iter().map(|g| g.len()).collect();
+            assert_eq!(lens, [3_000, 3_000, 3_000, 1_000]);
+            assert_eq!(groups[3].range(), 9_000..10_000);
+            assert_eq!(groups[0].field_names(), ["id", "price", "qty"]);
+
+            let full = ds.read_raw::<Trade>().unwrap();
+            let mut reassembled = Vec::new();
+            for group in &groups {
+                let ids = group.column::<u64>("id").unwrap();
+                let prices = group.column::<f64>("price").unwrap();
+                let qtys = group.column::<i32>("qty").unwrap();
+                for (i, row) in group.rows().iter().enumerate() {
+                    assert_eq!((ids[i], prices[i], qtys[i]), (row.id, row.price, row.qty));
+                }
+                reassembled.extend(group.rows().iter().cloned());
+            }
+            assert_eq!(reassembled, full);
+
+            assert_err!(groups[0].column::<u64>("missing"), "no field 'missing'");
+            assert_err!(groups[0].column::<i64>("id"), "cannot extract it as");
+            assert_err!(ds.read_row_groups::<Trade>(0), "must be positive");
+            assert_err!(ds.read_row_groups::<u64>(10), "compound row type");
+
+            let empty = file.new_dataset::<Trade>().shape(0).create("empty").unwrap();
+            assert_eq!(empty.read_row_groups::<Trade>(10).unwrap().count(), 0);
+            let matrix = file.new_dataset::<Trade>().shape((2, 2)).create("matrix").unwrap();
+            assert_eq!(matrix.n_rows().unwrap(), 2);
+            assert_err!(matrix.read_row_groups::<Trade>(1), "expected a 1-D dataset");
+        })
+    }
+
+    #[test]
+    pub fn test_row_group_string_column() {
+        #[derive(Clone)]
+        #[repr(C)]
+        struct Tagged {
+            tag: VarLenUnicode,
+            value: f32,
+        }
+
+        unsafe impl H5Type for Tagged {
+            fn type_descriptor() -> TypeDescriptor {
+                TypeDescriptor::Compound(CompoundType {
+                    fields: vec![
+                        CompoundField::typed::<VarLenUnicode>(
+                            "tag",
+                            mem::offset_of!(Tagged, tag),
+                            0,
+                        ),
+                        CompoundField::typed::<f32>("value", mem::offset_of!(Tagged, value), 1),
+                    ],
+                    size: mem::size_of::<Tagged>(),
+                })
+            }
+        }
+
+        with_tmp_file(|file| {
+            let rows: Vec<_> = (0..5)
+                .map(|i| Tagged { tag: format!("t{i}").parse().unwrap(), value: i as f32 })
+                .collect();
+            let ds = file.new_dataset_builder().with_data(&rows).create("tagged").unwrap();
+            let tags: Vec<String> = ds
+                .read_row_groups::<Tagged>(2)
+                .unwrap()
+                .map(|group| group.unwrap().column::<VarLenUnicode>("tag").unwrap())
+                .flat_map(|column| column.into_iter().map(|tag| tag.as_str().to_owned()))
+                .collect();
+            assert_eq!(tags, ["t0", "t1", "t2", "t3", "t4"]);
+        })
+    }
+}
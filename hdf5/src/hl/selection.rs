@@ -3,30 +3,35 @@ use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Display};
 use std::mem;
 use std::ops::{Deref, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+use std::ptr;
 use std::slice;
 
 use ndarray::{self, s, Array1, Array2, ArrayView1, ArrayView2};
 
 use crate::sys::h5s::{
-    H5S_sel_type, H5Sget_select_elem_npoints, H5Sget_select_elem_pointlist, H5Sget_select_type,
-    H5Sget_simple_extent_ndims, H5Sselect_all, H5Sselect_elements, H5Sselect_hyperslab,
-    H5Sselect_none, H5S_SELECT_SET, H5S_UNLIMITED,
+    H5S_sel_type, H5S_seloper_t, H5Scombine_hyperslab, H5Scopy, H5Sget_select_elem_npoints,
+    H5Sget_select_elem_pointlist, H5Sget_select_npoints, H5Sget_select_type,
+    H5Sget_simple_extent_ndims, H5Smodify_select, H5Sselect_all, H5Sselect_elements,
+    H5Sselect_hyperslab, H5Sselect_none, H5S_SELECT_SET, H5S_UNLIMITED,
 };
 use crate::sys::h5s::{H5Sget_regular_hyperslab, H5Sis_regular_hyperslab};
 
+use crate::dim::{alloc_buf, checked_size, hsize_to_ix, to_ix, DimVec};
 use crate::hl::extents::Ix;
 use crate::internal_prelude::*;
 
 unsafe fn get_points_selection(space_id: hid_t) -> Result<Array2<Ix>> {
-    let npoints = h5check(H5Sget_select_elem_npoints(space_id))? as usize;
+    let npoints = hsize_to_ix(h5check(H5Sget_select_elem_npoints(space_id))? as hsize_t)?;
     let ndim = h5check(H5Sget_simple_extent_ndims(space_id))? as usize;
-    let mut coords = vec![0; npoints * ndim];
+    let len = checked_size(&[npoints, ndim])?;
+    let mut coords = alloc_buf::<hsize_t>(len)?;
+    coords.resize(len, 0);
     h5check(H5Sget_select_elem_pointlist(space_id, 0, npoints as _, coords.as_mut_ptr()))?;
     let coords = if mem::size_of::<hsize_t>() == mem::size_of::<Ix>() {
         #[allow(clippy::transmute_undefined_repr)]
-        mem::transmute(coords)
+        mem::transmute::<Vec<hsize_t>, Vec<Ix>>(coords)
     } else {
-        coords.iter().map(|&x| x as _).collect()
+        to_ix(&coords)?
     };
     Ok(Array2::from_shape_vec_unchecked((npoints, ndim), coords))
 }
@@ -38,7 +43,7 @@ unsafe fn set_points_selection(space_id: hid_t, coords: ArrayView2<Ix>) -> Resul
         (Some(coords), true) => {
             Cow::Borrowed(slice::from_raw_parts(coords.as_ptr().cast(), coords.len()))
         }
-        _ => Cow::Owned(coords.iter().map(|&x| x as _).collect()),
+        _ => Cow::Owned(coords.iter().map(|&x| x as hsize_t).collect()),
     };
     h5check(H5Sselect_elements(space_id, H5S_SELECT_SET, nelem, coords.as_ptr()))?;
     Ok(())
@@ -49,8 +54,8 @@ unsafe fn get_regular_hyperslab(space_id: hid_t) -> Result<Option<RawHyperslab>>
         return Ok(None);
     }
     let ndim = h5check(H5Sget_simple_extent_ndims(space_id))? as usize;
-    let (mut start, mut stride, mut count, mut block) =
-        (vec![0; ndim], vec![0; ndim], vec![0; ndim], vec![0; ndim]);
+    let zeros = || DimVec::from_elem(0, ndim);
+    let (mut start, mut stride, mut count, mut block) = (zeros(), zeros(), zeros(), zeros());
     h5check(H5Sget_regular_hyperslab(
         space_id,
         start.as_mut_ptr(),
@@ -61,26 +66,37 @@ unsafe fn get_regular_hyperslab(space_id: hid_t) -> Result<Option<RawHyperslab>>
     let mut hyper = vec![];
     for i in 0..ndim {
         hyper.push(RawSlice {
-            start: start[i] as _,
-            step: stride[i] as _,
-            count: if count[i] == H5S_UNLIMITED { None } else { Some(count[i] as _) },
-            block: block[i] as _,
+            start: hsize_to_ix(start[i])?,
+            step: hsize_to_ix(stride[i])?,
+            count: if count[i] == H5S_UNLIMITED { None } else { Some(hsize_to_ix(count[i])?) },
+            block: hsize_to_ix(block[i])?,
         });
     }
     Ok(Some(hyper.into()))
 }
 
-unsafe fn set_regular_hyperslab(space_id: hid_t, hyper: &RawHyperslab) -> Result<()> {
-    let (mut start, mut stride, mut count, mut block) = (vec![], vec![], vec![], vec![]);
+/// Returns the start, stride, count and block arrays describing a regular hyperslab.
+fn hyperslab_arrays(hyper: &RawHyperslab) -> [DimVec; 4] {
+    let (mut start, mut stride, mut count, mut block) =
+        (DimVec::new(), DimVec::new(), DimVec::new(), DimVec::new());
     for slice_info in hyper.iter() {
-        start.push(slice_info.start as _);
-        stride.push(slice_info.step as _);
-        count.push(slice_info.count.map_or(H5S_UNLIMITED, |x| x as _));
-        block.push(slice_info.block as _);
+        start.push(slice_info.start as hsize_t);
+        stride.push(slice_info.step as hsize_t);
+        count.push(slice_info.count.map_or(H5S_UNLIMITED, |x| x as hsize_t));
+        block.push(slice_info.block as hsize_t);
     }
+    [start, stride, count, block]
+}
+
+unsafe fn select_regular_hyperslab(
+    space_id: hid_t,
+    op: H5S_seloper_t,
+    hyper: &RawHyperslab,
+) -> Result<()> {
+    let [start, stride, count, block] = hyperslab_arrays(hyper);
     h5check(H5Sselect_hyperslab(
         space_id,
-        H5S_SELECT_SET,
+        op,
         start.as_ptr(),
         stride.as_ptr(),
         count.as_ptr(),
@@ -89,6 +105,10 @@ unsafe fn set_regular_hyperslab(space_id: hid_t, hyper: &RawHyperslab) -> Result
     Ok(())
 }
 
+unsafe fn set_regular_hyperslab(space_id: hid_t, hyper: &RawHyperslab) -> Result<()> {
+    select_regular_hyperslab(space_id, H5S_SELECT_SET, hyper)
+}
+
 fn check_coords(coords: &Array2<Ix>, shape: &[Ix]) -> Result<()> {
     if coords.shape() == [0, 0] {
         return Ok(());
@@ -252,9 +272,14 @@ impl RawSelection {
 /// s s _ _ s s _ _ s s _
 /// ```
 ///
+/// A `SliceOrIndex` always selects its elements in increasing order. Slices with a negative
+/// step (like `s![..;-1]`) can only be used as part of a [`Hyperslab`], which keeps track of
+/// the reversed axes.
+///
 /// See also [`this hdf5 tutorial`](https://support.hdfgroup.org/HDF5/Tutor/select.html)
 /// for more information on hyperslab selections.
 #[derive(Clone, Copy, Debug, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SliceOrIndex {
     /// A single index
     Index(Ix),
@@ -379,7 +404,7 @@ impl TryFrom<ndarray::SliceInfoElem> for SliceOrIndex {
 impl TryFrom<ndarray::SliceInfoElem> for Hyperslab {
     type Error = Error;
     fn try_from(slice: ndarray::SliceInfoElem) -> Result<Self, Self::Error> {
-        Ok(vec![slice.try_into()?].into())
+        Self::from_slice_info_elems([slice])
     }
 }
 
@@ -568,13 +593,23 @@ impl Display for SliceOrIndex {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
 /// A descriptor of a selection of an N-dimensional array.
 ///
 /// The Hyperslab consists of [`slices`](SliceOrIndex) in N dimensions,
 /// spanning an N-dimensional hypercube. This type is used as a [`selector`](Selection)
 /// for retrieving and putting data to a [`Container`](Container).
+///
+/// Hyperslabs converted from `ndarray` slices may contain axes with a negative step. HDF5 can
+/// only select elements in increasing order, so these axes are selected with the equivalent
+/// positive step, and [`read_slice`](crate::Reader::read_slice) and
+/// [`write_slice`](crate::Writer::write_slice) reverse them in memory. Other uses of the
+/// hyperslab, like [`Dataspace::select`](crate::Dataspace::select), select the same elements
+/// in increasing order. The reversed axes are not serialized.
 pub struct Hyperslab {
     dims: Vec<SliceOrIndex>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    reversed: Vec<usize>,
 }
 
 impl Hyperslab {
@@ -586,16 +621,62 @@ impl Hyperslab {
         hyper.try_into()
     }
 
+    fn from_slice_info_elems<I>(elems: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = ndarray::SliceInfoElem>,
+    {
+        let (mut dims, mut reversed) = (vec![], vec![]);
+        for (axis, elem) in elems.into_iter().enumerate() {
+            let elem = match elem {
+                ndarray::SliceInfoElem::Slice { start, end, step } if step < 0 => {
+                    reversed.push(axis);
+                    let step =
+                        step.checked_neg().ok_or_else(|| Error::from("Step out of range"))?;
+                    ndarray::SliceInfoElem::Slice { start, end, step }
+                }
+                elem => elem,
+            };
+            dims.push(elem.try_into()?);
+        }
+        Ok(Self { dims, reversed })
+    }
+
     pub fn is_unlimited(&self) -> bool {
         self.iter().any(|&s| s.is_unlimited())
     }
 
+    /// Returns the axes sliced with a negative step, in increasing order.
+    pub fn reversed_axes(&self) -> &[usize] {
+        &self.reversed
+    }
+
+    /// Returns the axes of the selected array that are reversed, skipping the axes that are
+    /// dropped by an index.
+    fn reversed_out_axes(&self) -> Vec<usize> {
+        let mut out_axis = 0;
+        let mut axes = vec![];
+        for (axis, slice) in self.iter().enumerate() {
+            if slice.is_slice() {
+                if self.reversed.contains(&axis) {
+                    axes.push(out_axis);
+                }
+                out_axis += 1;
+            }
+        }
+        axes
+    }
+
     pub fn unlimited_axis(&self) -> Option<usize> {
         self.iter().enumerate().find_map(|(i, s)| if s.is_unlimited() { Some(i) } else { None })
     }
 
     pub fn set_unlimited(&self, axis: usize) -> Result<Self> {
         if axis < self.len() {
+            ensure!(
+                !self.reversed.contains(&axis),
+                "Cannot make the reversed axis {} unlimited",
+                axis
+            );
             let mut hyper = self.clone();
             hyper.dims[axis] = hyper.dims[axis].to_unlimited()?;
             Ok(hyper)
@@ -606,6 +687,11 @@ impl Hyperslab {
 
     pub fn set_block(&self, axis: usize, blocksize: Ix) -> Result<Self> {
         ensure!(axis < self.len(), "Invalid axis for changing the slice to block-like: {}", axis);
+        ensure!(
+            !self.reversed.contains(&axis),
+            "Cannot change the block size of the reversed axis {}",
+            axis
+        );
         let mut hyper = self.clone();
         hyper.dims[axis] = hyper.dims[axis].set_blocksize(blocksize)?;
         Ok(hyper)
@@ -622,7 +708,11 @@ impl Hyperslab {
             self.iter()
                 .zip(shape)
                 .enumerate()
-                .map(|(i, (slice, &dim))| slice_info_to_raw(i, slice, dim))
+                .map(|(i, (&slice, &dim))| {
+                    let slice =
+                        if self.reversed.contains(&i) { forward_slice(slice, dim) } else { slice };
+                    slice_info_to_raw(i, &slice, dim)
+                })
                 .collect::<Result<Vec<_>>>()?,
         );
         Ok(hyper)
@@ -648,7 +738,7 @@ impl Hyperslab {
                 },
             });
         }
-        Ok(Self { dims })
+        Ok(Self { dims, reversed: vec![] })
     }
 }
 
@@ -662,7 +752,7 @@ impl Deref for Hyperslab {
 
 impl From<Vec<SliceOrIndex>> for Hyperslab {
     fn from(dims: Vec<SliceOrIndex>) -> Self {
-        Self { dims }
+        Self { dims, reversed: vec![] }
     }
 }
 
@@ -687,7 +777,7 @@ impl From<SliceOrIndex> for Hyperslab {
 impl TryFrom<ndarray::Slice> for Hyperslab {
     type Error = Error;
     fn try_from(slice: ndarray::Slice) -> Result<Self, Self::Error> {
-        Ok(vec![SliceOrIndex::try_from(slice).map_err(|_| Error::from("Invalid slice"))?].into())
+        Self::from_slice_info_elems([slice.into()]).map_err(|_| Error::from("Invalid slice"))
     }
 }
 
@@ -699,17 +789,24 @@ where
 {
     type Error = Error;
     fn try_from(slice: ndarray::SliceInfo<T, Din, Dout>) -> Result<Self, Self::Error> {
-        slice
-            .deref()
-            .as_ref()
-            .iter()
-            .copied()
-            .map(TryInto::try_into)
-            .collect::<Result<Vec<_>>>()
-            .map(Into::into)
+        Self::from_slice_info_elems(slice.deref().as_ref().iter().copied())
     }
 }
 
+/// Turns a slice of a reversed axis into the positive-step slice selecting the same elements.
+///
+/// Like in `ndarray`, a negative step counts down from the last element of `start..end`, so the
+/// smallest selected element is `start + (end - 1 - start) % step`.
+fn forward_slice(slice: SliceOrIndex, dim: Ix) -> SliceOrIndex {
+    let (start, step, end, block) = match slice {
+        SliceOrIndex::SliceTo { start, step, end, block } => (start, step, end, block),
+        SliceOrIndex::Unlimited { start, step, block } => (start, step, dim, block),
+        _ => return slice,
+    };
+    let start = if end > start && step > 0 { start + (end - 1 - start) % step } else { start };
+    SliceOrIndex::SliceTo { start, step, end, block }
+}
+
 /// Turns `SliceOrIndex` into real dimensions given `dim` as the maximum dimension
 fn slice_info_to_raw(axis: usize, slice: &SliceOrIndex, dim: Ix) -> Result<RawSlice> {
     let err_msg = || format!("out of bounds for axis {axis} with size {dim}");
@@ -753,7 +850,24 @@ impl Display for Hyperslab {
             if i != 0 {
                 write!(f, ", ")?;
             }
-            write!(f, "{s}")?;
+            let reversed = match *s {
+                SliceOrIndex::SliceTo { start, step, end, .. } => Some((start, Some(end), step)),
+                SliceOrIndex::Unlimited { start, step, .. } => Some((start, None, step)),
+                _ => None,
+            };
+            match reversed.filter(|_| self.reversed.contains(&i)) {
+                Some((start, end, step)) => {
+                    if start != 0 {
+                        write!(f, "{start}")?;
+                    }
+                    match end {
+                        Some(end) => write!(f, "..{end}")?,
+                        None => write!(f, "..\u{221e}")?,
+                    }
+                    write!(f, ";-{step}")?;
+                }
+                None => write!(f, "{s}")?,
+            }
         }
         if slice.len() == 1 {
             write!(f, ",")?;
@@ -763,6 +877,11 @@ impl Display for Hyperslab {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SelectionRepr", into = "SelectionRepr")
+)]
 /// A selection used for reading and writing to a [`Container`](Container).
 pub enum Selection {
     /// The entire dataset.
@@ -773,6 +892,50 @@ pub enum Selection {
     Hyperslab(Hyperslab),
 }
 
+/// Serialized form of a [`Selection`], with points stored as nested lists.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+enum SelectionRepr {
+    All,
+    Points(Vec<Vec<Ix>>),
+    Hyperslab(Hyperslab),
+}
+
+#[cfg(feature = "serde")]
+impl From<Selection> for SelectionRepr {
+    fn from(selection: Selection) -> Self {
+        match selection {
+            Selection::All => Self::All,
+            Selection::Points(points) => {
+                Self::Points(points.outer_iter().map(|p| p.to_vec()).collect())
+            }
+            Selection::Hyperslab(hyper) => Self::Hyperslab(hyper),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SelectionRepr> for Selection {
+    type Error = Error;
+
+    fn try_from(repr: SelectionRepr) -> Result<Self> {
+        Ok(match repr {
+            SelectionRepr::All => Self::All,
+            SelectionRepr::Points(points) => {
+                let ndim = points.first().map_or(0, Vec::len);
+                ensure!(
+                    points.iter().all(|p| p.len() == ndim),
+                    "all points of a selection must have the same number of coordinates"
+                );
+                let shape = (points.len(), ndim);
+                let coords = points.into_iter().flatten().collect();
+                Self::Points(Array2::from_shape_vec(shape, coords).map_err(|err| err.to_string())?)
+            }
+            SelectionRepr::Hyperslab(hyper) => Self::Hyperslab(hyper),
+        })
+    }
+}
+
 impl Default for Selection {
     fn default() -> Self {
         Self::All
@@ -912,6 +1075,14 @@ impl Selection {
     pub fn is_hyperslab(&self) -> bool {
         matches!(self, Self::Hyperslab(_))
     }
+
+    /// Returns the axes of the selected array that have to be reversed in memory.
+    pub(crate) fn reversed_out_axes(&self) -> Vec<usize> {
+        match self {
+            Self::Hyperslab(hyper) => hyper.reversed_out_axes(),
+            _ => vec![],
+        }
+    }
 }
 
 impl Display for Selection {
@@ -1073,9 +1244,378 @@ macro_rules! impl_tuple {
 
 impl_tuple! { T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, }
 
+/// A set operation combining two selections, see [`SelectionExpr`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionOp {
+    /// Elements selected by either operand.
+    Union,
+    /// Elements selected by both operands.
+    Intersection,
+    /// Elements selected by the left operand but not by the right one.
+    Difference,
+    /// Elements selected by exactly one of the operands.
+    SymmetricDifference,
+}
+
+impl SelectionOp {
+    fn to_h5(self) -> H5S_seloper_t {
+        match self {
+            Self::Union => H5S_seloper_t::H5S_SELECT_OR,
+            Self::Intersection => H5S_seloper_t::H5S_SELECT_AND,
+            Self::Difference => H5S_seloper_t::H5S_SELECT_NOTB,
+            Self::SymmetricDifference => H5S_seloper_t::H5S_SELECT_XOR,
+        }
+    }
+
+    /// Returns the result of the operation when at least one operand selects nothing, in
+    /// which case it is `None`.
+    fn with_empty(self, lhs: Option<Dataspace>, rhs: Option<Dataspace>) -> Option<Dataspace> {
+        match self {
+            Self::Intersection => None,
+            Self::Difference => lhs,
+            Self::Union | Self::SymmetricDifference => lhs.or(rhs),
+        }
+    }
+}
+
+impl Display for SelectionOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Union => "|",
+            Self::Intersection => "&",
+            Self::Difference => "-",
+            Self::SymmetricDifference => "^",
+        })
+    }
+}
+
+/// A set expression over selections, evaluated by HDF5 into a single dataspace selection.
+///
+/// Expressions are built with [`Selection::union`], [`Selection::intersection`],
+/// [`Selection::difference`] and [`Selection::symmetric_difference`] (and the same methods on
+/// expressions), and evaluated with [`apply_to`](Self::apply_to). Point selections take part as
+/// sets of single-element blocks, so the result of an operation is always a hyperslab.
+///
+/// With HDF5 1.10.6 or later, arbitrary expressions are evaluated with `H5Scombine_hyperslab`
+/// and `H5Smodify_select`. Older libraries only provide in-place operations on one dataspace,
+/// so every right operand must then be a single selection, e.g. `(a | b) & c` works but
+/// `a & (b | c)` does not; additionally, point selections may only be the leftmost operand or
+/// the right operand of a union. Expressions outside these rules fail to evaluate there.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SelectionExpr {
+    /// A single selection.
+    Leaf(Selection),
+    /// A set operation applied to two expressions.
+    Binary(SelectionOp, Box<SelectionExpr>, Box<SelectionExpr>),
+}
+
+impl<T: Into<Selection>> From<T> for SelectionExpr {
+    fn from(selection: T) -> Self {
+        Self::Leaf(selection.into())
+    }
+}
+
+impl Display for SelectionExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Leaf(selection) => write!(f, "{selection}"),
+            Self::Binary(op, lhs, rhs) => write!(f, "({lhs} {op} {rhs})"),
+        }
+    }
+}
+
+impl SelectionExpr {
+    fn binary(self, op: SelectionOp, rhs: impl Into<Self>) -> Self {
+        Self::Binary(op, Box::new(self), Box::new(rhs.into()))
+    }
+
+    /// Returns the union of the two selections.
+    #[must_use]
+    pub fn union(self, rhs: impl Into<Self>) -> Self {
+        self.binary(SelectionOp::Union, rhs)
+    }
+
+    /// Returns the intersection of the two selections.
+    #[must_use]
+    pub fn intersection(self, rhs: impl Into<Self>) -> Self {
+        self.binary(SelectionOp::Intersection, rhs)
+    }
+
+    /// Returns the elements of this selection that are not selected by `rhs`.
+    #[must_use]
+    pub fn difference(self, rhs: impl Into<Self>) -> Self {
+        self.binary(SelectionOp::Difference, rhs)
+    }
+
+    /// Returns the elements selected by exactly one of the two selections.
+    #[must_use]
+    pub fn symmetric_difference(self, rhs: impl Into<Self>) -> Self {
+        self.binary(SelectionOp::SymmetricDifference, rhs)
+    }
+
+    /// Evaluates the expression on a copy of `space` and returns it with the resulting
+    /// selection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a selection does not fit the dataspace, or if the expression cannot
+    /// be evaluated with the loaded HDF5 version (see the type-level documentation).
+    pub fn apply_to(&self, space: &Dataspace) -> Result<Dataspace> {
+        if let Self::Leaf(selection) = self {
+            return space.select(selection);
+        }
+        let combined = if crate::raw::hdf5_version_at_least(1, 10, 6) {
+            sync(|| self.eval_combined(space))
+        } else {
+            sync(|| self.eval_sequential(space))
+        }
+        .with_context(|| format!("evaluating selection {self}"))?;
+        match combined {
+            Some(combined) => {
+                ensure!(combined.is_valid(), "Invalid selection, out of extents");
+                Ok(combined)
+            }
+            None => space.select_raw(RawSelection::None),
+        }
+    }
+
+    /// Evaluates the expression bottom-up with the functional selection API; `None` stands for
+    /// an empty selection, which the combinators do not accept.
+    fn eval_combined(&self, space: &Dataspace) -> Result<Option<Dataspace>> {
+        match self {
+            Self::Leaf(selection) => leaf_as_hyperslab(space, selection),
+            Self::Binary(op, lhs, rhs) => {
+                let Some(lhs) = lhs.eval_combined(space)? else {
+                    return Ok(op.with_empty(None, rhs.eval_combined(space)?));
+                };
+                if let Self::Leaf(selection) = &**rhs {
+                    if let RawSelection::RegularHyperslab(hyper) =
+                        selection.clone().into_raw(space.shape()?)?
+                    {
+                        let [start, stride, count, block] = hyperslab_arrays(&hyper);
+                        let id = h5check(unsafe {
+                            H5Scombine_hyperslab(
+                                lhs.id(),
+                                op.to_h5(),
+                                start.as_ptr(),
+                                stride.as_ptr(),
+                                count.as_ptr(),
+                                block.as_ptr(),
+                            )
+                        })?;
+                        return non_empty(Dataspace::from_id(id)?);
+                    }
+                }
+                let Some(rhs) = rhs.eval_combined(space)? else {
+                    return Ok(op.with_empty(Some(lhs), None));
+                };
+                h5check(unsafe { H5Smodify_select(lhs.id(), op.to_h5(), rhs.id()) })?;
+                non_empty(lhs)
+            }
+        }
+    }
+
+    /// Evaluates a left-deep expression by applying each right operand to one dataspace in
+    /// place, as HDF5 versions before 1.10.6 allow.
+    fn eval_sequential(&self, space: &Dataspace) -> Result<Option<Dataspace>> {
+        match self {
+            Self::Leaf(selection) => leaf_as_hyperslab(space, selection),
+            Self::Binary(op, lhs, rhs) => {
+                let Self::Leaf(selection) = &**rhs else {
+                    fail!(
+                        "the right operand of '{}' must be a single selection, combining \
+                         arbitrary expressions requires HDF5 1.10.6 or later",
+                        op
+                    );
+                };
+                let lhs = lhs.eval_sequential(space)?;
+                let raw = selection.clone().into_raw(space.shape()?)?;
+                let Some(lhs) = lhs else {
+                    return Ok(op.with_empty(None, leaf_as_hyperslab(space, selection)?));
+                };
+                match raw {
+                    RawSelection::None => return Ok(op.with_empty(Some(lhs), None)),
+                    RawSelection::Points(ref coords) => {
+                        ensure!(
+                            *op == SelectionOp::Union,
+                            "points can only be the right operand of a union before HDF5 1.10.6"
+                        );
+                        unsafe { select_points_as_blocks(&lhs, coords.view(), true)? };
+                    }
+                    _ => unsafe { select_as_hyperslab(lhs.id(), op.to_h5(), &raw, space)? },
+                }
+                non_empty(lhs)
+            }
+        }
+    }
+}
+
+/// Returns `None` if nothing is selected in `space`.
+fn non_empty(space: Dataspace) -> Result<Option<Dataspace>> {
+    let npoints = h5check(unsafe { H5Sget_select_npoints(space.id()) })?;
+    Ok((npoints > 0).then_some(space))
+}
+
+/// Selects `selection` in a copy of `space` as a hyperslab, so that it can be combined with
+/// other selections; returns `None` if it selects nothing.
+fn leaf_as_hyperslab(space: &Dataspace, selection: &Selection) -> Result<Option<Dataspace>> {
+    let raw = selection.clone().into_raw(space.shape()?)?;
+    if raw == RawSelection::None {
+        return Ok(None);
+    }
+    let leaf = space.copy();
+    unsafe {
+        match raw {
+            RawSelection::Points(ref coords) => {
+                select_points_as_blocks(&leaf, coords.view(), false)?;
+            }
+            _ => select_as_hyperslab(leaf.id(), H5S_SELECT_SET, &raw, space)?,
+        }
+    }
+    non_empty(leaf)
+}
+
+/// Applies a non-point selection with the given operation using hyperslab calls only.
+unsafe fn select_as_hyperslab(
+    space_id: hid_t,
+    op: H5S_seloper_t,
+    raw: &RawSelection,
+    space: &Dataspace,
+) -> Result<()> {
+    match raw {
+        RawSelection::All => {
+            let all: Vec<_> =
+                space.shape()?.iter().map(|&dim| RawSlice::new(0, 1, Some(1), dim)).collect();
+            select_regular_hyperslab(space_id, op, &RawHyperslab::from(all))
+        }
+        RawSelection::RegularHyperslab(hyper) => select_regular_hyperslab(space_id, op, hyper),
+        _ => fail!("Unexpected selection in a selection expression: {:?}", raw),
+    }
+}
+
+/// Number of blocks selected into one dataspace before the partial selections are merged.
+const POINT_BATCH: usize = 64;
+
+/// Selects the given points as blocks, added to the current selection if `add` is set and
+/// replacing it otherwise.
+///
+/// Points that are adjacent along the last axis are merged into one block. Every
+/// `H5Sselect_hyperslab(OR)` call is linear in the size of the selection it extends, so with
+/// HDF5 1.10.6 or later the blocks are selected into copies of the dataspace in batches of
+/// [`POINT_BATCH`], which are then merged pairwise with `H5Smodify_select`.
+unsafe fn select_points_as_blocks(
+    space: &Dataspace,
+    coords: ArrayView2<Ix>,
+    add: bool,
+) -> Result<()> {
+    let blocks = point_blocks(coords);
+    let op = if add { H5S_seloper_t::H5S_SELECT_OR } else { H5S_SELECT_SET };
+    if blocks.len() <= POINT_BATCH || !crate::raw::hdf5_version_at_least(1, 10, 6) {
+        return select_blocks(space.id(), &blocks, op);
+    }
+    let (head, tail) = if add { blocks.split_at(0) } else { blocks.split_at(POINT_BATCH) };
+    let mut parts = tail
+        .chunks(POINT_BATCH)
+        .map(|batch| {
+            let part = Dataspace::from_id(h5check(H5Scopy(space.id()))?)?;
+            select_blocks(part.id(), batch, H5S_SELECT_SET)?;
+            Ok(part)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    while parts.len() > 1 {
+        let mut merged = Vec::with_capacity(parts.len().div_ceil(2));
+        let mut iter = parts.into_iter();
+        while let Some(lhs) = iter.next() {
+            if let Some(rhs) = iter.next() {
+                h5check(H5Smodify_select(lhs.id(), H5S_seloper_t::H5S_SELECT_OR, rhs.id()))?;
+            }
+            merged.push(lhs);
+        }
+        parts = merged;
+    }
+    select_blocks(space.id(), head, H5S_SELECT_SET)?;
+    if let Some(rest) = parts.first() {
+        h5check(H5Smodify_select(space.id(), H5S_seloper_t::H5S_SELECT_OR, rest.id()))?;
+    }
+    Ok(())
+}
+
+/// Sorts and deduplicates the points and merges runs along the last axis into blocks, given
+/// as their start and their length along the last axis.
+fn point_blocks(coords: ArrayView2<Ix>) -> Vec<(DimVec, hsize_t)> {
+    let mut points: Vec<DimVec> =
+        coords.outer_iter().map(|point| point.iter().map(|&x| x as hsize_t).collect()).collect();
+    points.sort_unstable();
+    points.dedup();
+    let mut blocks: Vec<(DimVec, hsize_t)> = Vec::with_capacity(points.len());
+    for point in points {
+        if let (Some((start, len)), Some((&last, prefix))) = (blocks.last_mut(), point.split_last())
+        {
+            if start[..prefix.len()] == *prefix && start[prefix.len()] + *len == last {
+                *len += 1;
+                continue;
+            }
+        }
+        blocks.push((point, 1));
+    }
+    blocks
+}
+
+/// Selects the blocks one by one, the first one with `op` and the others with `OR`.
+unsafe fn select_blocks(
+    space_id: hid_t,
+    blocks: &[(DimVec, hsize_t)],
+    op: H5S_seloper_t,
+) -> Result<()> {
+    for (i, (start, len)) in blocks.iter().enumerate() {
+        let mut count = DimVec::from_elem(1, start.len());
+        if let Some(last) = count.last_mut() {
+            *last = *len;
+        }
+        let op = if i == 0 { op } else { H5S_seloper_t::H5S_SELECT_OR };
+        h5check(H5Sselect_hyperslab(
+            space_id,
+            op,
+            start.as_ptr(),
+            ptr::null(),
+            count.as_ptr(),
+            ptr::null(),
+        ))?;
+    }
+    Ok(())
+}
+
+impl Selection {
+    /// Returns the union of the two selections, see [`SelectionExpr`].
+    #[must_use]
+    pub fn union(self, rhs: impl Into<SelectionExpr>) -> SelectionExpr {
+        SelectionExpr::from(self).union(rhs)
+    }
+
+    /// Returns the intersection of the two selections, see [`SelectionExpr`].
+    #[must_use]
+    pub fn intersection(self, rhs: impl Into<SelectionExpr>) -> SelectionExpr {
+        SelectionExpr::from(self).intersection(rhs)
+    }
+
+    /// Returns the elements of this selection that are not selected by `rhs`, see
+    /// [`SelectionExpr`].
+    #[must_use]
+    pub fn difference(self, rhs: impl Into<SelectionExpr>) -> SelectionExpr {
+        SelectionExpr::from(self).difference(rhs)
+    }
+
+    /// Returns the elements selected by exactly one of the two selections, see
+    /// [`SelectionExpr`].
+    #[must_use]
+    pub fn symmetric_difference(self, rhs: impl Into<SelectionExpr>) -> SelectionExpr {
+        SelectionExpr::from(self).symmetric_difference(rhs)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use ndarray::{arr1, arr2, s, Array2};
+    use ndarray::{arr1, arr2, s, Array1, Array2};
     use pretty_assertions::assert_eq;
 
     use super::{
@@ -1255,6 +1795,45 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_hyperslab_reversed() -> Result<()> {
+        let h = Hyperslab::try_new(s![1..9;-3, 2, ..;-1])?;
+        assert_eq!(h.reversed_axes(), [0, 2]);
+        assert_eq!(h.reversed_out_axes(), [0, 1]);
+        assert_eq!(h.to_string(), "(1..9;-3, 2, ..\u{221e};-1)");
+        assert_ne!(h, Hyperslab::try_new(s![1..9;3, 2, ..])?);
+        assert_eq!(Selection::from(h.clone()).out_shape([10, 4, 3])?, [3, 3]);
+        // 8, 5, 2 are selected in increasing order
+        let raw = h.clone().into_raw([10, 4, 3])?;
+        assert_eq!(raw[0], RawSlice::new(2, 3, Some(3), 1));
+        assert_eq!(raw[2], RawSlice::new(0, 1, Some(3), 1));
+
+        assert_err!(h.set_block(0, 2), "Cannot change the block size of the reversed axis 0");
+        assert_err!(h.set_unlimited(2), "Cannot make the reversed axis 2 unlimited");
+        assert_err!(
+            SliceOrIndex::try_from(ndarray::SliceInfoElem::Slice { start: 0, end: None, step: -1 }),
+            "Step must be non-negative"
+        );
+
+        // the forward slice selects the elements of the ndarray slice
+        let arr = Array1::from_iter(0..7);
+        for start in 0..7 {
+            for end in start..=7 {
+                for step in 1..4 {
+                    let h = Hyperslab::try_new(s![start..end;-step])?;
+                    let raw = h.into_raw([7])?[0];
+                    let mut selected: Vec<_> = (raw.start..)
+                        .step_by(raw.step)
+                        .take(raw.count.unwrap_or_default())
+                        .collect();
+                    selected.reverse();
+                    assert_eq!(arr.slice(s![start..end;-step]).to_vec(), selected);
+                }
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_selection_default() {
         assert!(Selection::default().is_all());
@@ -1329,7 +1908,7 @@ mod test {
         }
 
         check(s![1, 2], &[1, 2, 3], "Slice ndim (2) != shape ndim (3)");
-        assert!(Hyperslab::try_new(s![0, ..;-1]).is_err());
+        check(s![0, ..5;-1], &[1, 2], "Slice end 5 out of bounds for axis 1 with size 2");
         check(s![0, 0], &[0, 1], "Index 0 out of bounds for axis 0 with size 0");
         check(s![.., 1], &[0, 1], "Index 1 out of bounds for axis 1 with size 1");
         assert!(Hyperslab::try_new(s![-3]).is_err());
@@ -1643,19 +2222,140 @@ mod test {
     fn use_selection_on_dataset() {
         with_tmp_file(|file| {
             let ds = file.new_dataset::<u8>().shape((5, 5)).create("ds_fixed").unwrap();
-            assert_eq!(&ds.shape(), &[5, 5]);
+            assert_eq!(&ds.shape().unwrap(), &[5, 5]);
             let ds = file.new_dataset::<u8>().shape((0.., 0..)).create("ds_twounlim").unwrap();
-            assert_eq!(&ds.shape(), &[0, 0]);
+            assert_eq!(&ds.shape().unwrap(), &[0, 0]);
             ds.resize((5, 5)).unwrap();
-            assert_eq!(&ds.shape(), &[5, 5]);
+            assert_eq!(&ds.shape().unwrap(), &[5, 5]);
             let ds = file.new_dataset::<u8>().shape((5, 0..)).create("ds_oneunlim0").unwrap();
-            assert_eq!(&ds.shape(), &[5, 0]);
+            assert_eq!(&ds.shape().unwrap(), &[5, 0]);
             ds.resize((5, 5)).unwrap();
-            assert_eq!(&ds.shape(), &[5, 5]);
+            assert_eq!(&ds.shape().unwrap(), &[5, 5]);
             let ds = file.new_dataset::<u8>().shape((0.., 5)).create("ds_oneunlim1").unwrap();
-            assert_eq!(&ds.shape(), &[0, 5]);
+            assert_eq!(&ds.shape().unwrap(), &[0, 5]);
             ds.resize((5, 5)).unwrap();
-            assert_eq!(&ds.shape(), &[5, 5]);
+            assert_eq!(&ds.shape().unwrap(), &[5, 5]);
+        })
+    }
+
+    #[test]
+    fn test_selection_expr() {
+        use super::SelectionExpr;
+        use crate::raw::hdf5_version_at_least;
+
+        with_tmp_file(|file| {
+            let data = Array2::from_shape_fn((10, 12), |(i, j)| (i * 12 + j) as i32);
+            let ds = file.new_dataset_builder().with_data(&data).create("x").unwrap();
+            let space = ds.space().unwrap();
+
+            let a = Selection::try_new(s![1..6, 2..9]).unwrap();
+            let points = arr2(&[[0_usize, 0], [3, 3], [7, 7], [8, 10], [9, 1]]);
+            let c = Selection::try_new(s![0..9;2, 1..11]).unwrap();
+            // brute-force masks computed in ndarray
+            let mut mask_a = Array2::from_elem((10, 12), false);
+            mask_a.slice_mut(s![1..6, 2..9]).fill(true);
+            let mut mask_p = Array2::from_elem((10, 12), false);
+            for p in points.outer_iter() {
+                mask_p[[p[0], p[1]]] = true;
+            }
+            let mut mask_c = Array2::from_elem((10, 12), false);
+            mask_c.slice_mut(s![0..9;2, 1..11]).fill(true);
+            let values = |mask: Array2<bool>| -> Vec<i32> {
+                data.iter().zip(mask.iter()).filter(|(_, &m)| m).map(|(&v, _)| v).collect()
+            };
+
+            // (A | P) & C, evaluable by both strategies
+            let expr = a.clone().union(&points).intersection(c.clone());
+            let expected = values((&mask_a | &mask_p) & &mask_c);
+            assert!(!expected.is_empty());
+            let mut strategies: Vec<fn(&SelectionExpr, &Dataspace) -> _> =
+                vec![SelectionExpr::eval_sequential];
+            if hdf5_version_at_least(1, 10, 6) {
+                strategies.push(SelectionExpr::eval_combined);
+            }
+            for eval in strategies {
+                let selected = sync(|| eval(&expr, &space)).unwrap().unwrap();
+                assert_eq!(selected.selection_size(), expected.len());
+            }
+            assert_eq!(expr.apply_to(&space).unwrap().selection_size(), expected.len());
+            assert_eq!(ds.read_expr::<i32>(&expr).unwrap(), expected);
+
+            // A & (C | P) needs the functional API
+            let nested = a.clone().intersection(c.clone().union(&points));
+            assert_err!(sync(|| nested.eval_sequential(&space)), "must be a single selection");
+            assert_err!(
+                sync(|| c.clone().intersection(&points).eval_sequential(&space)),
+                "points can only be the right operand of a union"
+            );
+            if hdf5_version_at_least(1, 10, 6) {
+                let expected = values(&mask_a & &(&mask_c | &mask_p));
+                assert_eq!(ds.read_expr::<i32>(&nested).unwrap(), expected);
+            }
+
+            // other operations and empty operands
+            let all = Selection::All;
+            let diff = all.clone().difference(a.clone()).symmetric_difference(c.clone());
+            assert_eq!(ds.read_expr::<i32>(&diff).unwrap(), values(!&mask_a ^ &mask_c));
+            let none = Selection::try_new(s![0..0, ..]).unwrap();
+            assert!(ds.read_expr::<i32>(&a.clone().intersection(none.clone())).unwrap().is_empty());
+            assert_eq!(
+                ds.read_expr::<i32>(&none.clone().union(a.clone())).unwrap(),
+                values(mask_a)
+            );
+            assert!(ds.read_expr::<i32>(&none.difference(c)).unwrap().is_empty());
+            assert_eq!(
+                Selection::try_new(s![1..6, 2]).unwrap().union(Selection::All).to_string(),
+                "((1..6, 2) | ..)"
+            );
+        })
+    }
+
+    #[test]
+    fn test_point_blocks() {
+        use super::point_blocks;
+
+        let points = arr2(&[[2_usize, 3], [0, 1], [2, 4], [0, 2], [2, 3], [0, 4], [1, 5], [2, 5]]);
+        let blocks: Vec<(Vec<hsize_t>, hsize_t)> =
+            point_blocks(points.view()).into_iter().map(|(s, n)| (s.to_vec(), n)).collect();
+        assert_eq!(
+            blocks,
+            vec![(vec![0, 1], 2), (vec![0, 4], 1), (vec![1, 5], 1), (vec![2, 3], 3)]
+        );
+        assert!(point_blocks(Array2::zeros((0, 2)).view()).is_empty());
+    }
+
+    #[test]
+    fn test_selection_expr_many_points() {
+        with_tmp_file(|file| {
+            let data = Array2::from_shape_fn((40, 40), |(i, j)| (i * 40 + j) as i32);
+            let ds = file.new_dataset_builder().with_data(&data).create("x").unwrap();
+
+            // scattered points and runs along the last axis, with duplicates, enough to be
+            // selected in several batches
+            let mut coords = Vec::new();
+            for i in 0..40 {
+                for j in 0..40 {
+                    if (i * 7 + j * 3) % 5 == 0 || (i % 3 == 0 && j < 10) {
+                        coords.extend([i, j]);
+                    }
+                }
+            }
+            coords.extend([0, 0, 39, 39, 0, 0]);
+            let points = Array2::from_shape_vec((coords.len() / 2, 2), coords).unwrap();
+            let mut mask_p = Array2::from_elem((40, 40), false);
+            for p in points.outer_iter() {
+                mask_p[[p[0], p[1]]] = true;
+            }
+            let mut mask_a = Array2::from_elem((40, 40), false);
+            mask_a.slice_mut(s![5..20, 10..30]).fill(true);
+            let values = |mask: Array2<bool>| -> Vec<i32> {
+                data.iter().zip(mask.iter()).filter(|(_, &m)| m).map(|(&v, _)| v).collect()
+            };
+
+            let none = Selection::try_new(s![0..0, ..]).unwrap();
+            assert_eq!(ds.read_expr::<i32>(&none.union(&points)).unwrap(), values(mask_p.clone()));
+            let a = Selection::try_new(s![5..20, 10..30]).unwrap();
+            assert_eq!(ds.read_expr::<i32>(&a.union(&points)).unwrap(), values(&mask_a | &mask_p));
         })
     }
 }
@@ -0,0 +1,226 @@
+//! Declarative descriptions of datasets that can be stored in configuration files.
+
+use hdf5_types::{FloatSize, IntSize, OwnedDynValue, TypeDescriptor};
+
+use crate::hl::extents::{Extent, SimpleExtents};
+use crate::hl::filters::Filter;
+use crate::hl::plist::dataset_create::FillValue;
+use crate::internal_prelude::*;
+
+/// A scalar fill value of a [`DatasetSpec`], converted to the dataset type when applied.
+///
+/// With the `serde` feature, this is serialized as a plain boolean or number.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(untagged))]
+pub enum FillSpec {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+}
+
+impl FillSpec {
+    fn to_i64(self) -> Option<i64> {
+        match self {
+            Self::Bool(b) => Some(b.into()),
+            Self::Int(i) => Some(i),
+            Self::UInt(u) => u.try_into().ok(),
+            Self::Float(f) if f.fract() == 0.0 && f.abs() < 2f64.powi(63) => Some(f as _),
+            Self::Float(_) => None,
+        }
+    }
+
+    fn to_u64(self) -> Option<u64> {
+        match self {
+            Self::UInt(u) => Some(u),
+            Self::Float(f) if f.fract() == 0.0 && (0.0..2f64.powi(64)).contains(&f) => Some(f as _),
+            _ => self.to_i64().and_then(|i| i.try_into().ok()),
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        match self {
+            Self::Bool(b) => f64::from(u8::from(b)),
+            Self::Int(i) => i as _,
+            Self::UInt(u) => u as _,
+            Self::Float(f) => f,
+        }
+    }
+
+    /// Converts the fill value to a value of type `dtype`.
+    pub(crate) fn to_value(self, dtype: &TypeDescriptor) -> Result<OwnedDynValue> {
+        macro_rules! int {
+            ($conv:ident, $ty:ty) => {
+                self.$conv().and_then(|v| <$ty>::try_from(v).ok()).map(OwnedDynValue::new)
+            };
+        }
+        let value = match dtype {
+            TypeDescriptor::Integer(IntSize::U1) => int!(to_i64, i8),
+            TypeDescriptor::Integer(IntSize::U2) => int!(to_i64, i16),
+            TypeDescriptor::Integer(IntSize::U4) => int!(to_i64, i32),
+            TypeDescriptor::Integer(IntSize::U8) => int!(to_i64, i64),
+            TypeDescriptor::Unsigned(IntSize::U1) => int!(to_u64, u8),
+            TypeDescriptor::Unsigned(IntSize::U2) => int!(to_u64, u16),
+            TypeDescriptor::Unsigned(IntSize::U4) => int!(to_u64, u32),
+            TypeDescriptor::Unsigned(IntSize::U8) => int!(to_u64, u64),
+            TypeDescriptor::Float(FloatSize::U4) => Some((self.to_f64() as f32).into()),
+            TypeDescriptor::Float(FloatSize::U8) => Some(self.to_f64().into()),
+            TypeDescriptor::Boolean => match self.to_u64() {
+                Some(v @ (0 | 1)) => Some((v == 1).into()),
+                _ => None,
+            },
+            _ => fail!("fill values of type {} are not supported", dtype),
+        };
+        value.ok_or_else(|| format!("fill value {self:?} does not fit into type {dtype}").into())
+    }
+
+    /// Converts a scalar value to a fill value, or returns `None` for other types.
+    fn from_value(value: OwnedDynValue) -> Option<Self> {
+        Some(match value.type_descriptor().clone() {
+            TypeDescriptor::Integer(IntSize::U1) => Self::Int(value.cast::<i8>().ok()?.into()),
+            TypeDescriptor::Integer(IntSize::U2) => Self::Int(value.cast::<i16>().ok()?.into()),
+            TypeDescriptor::Integer(IntSize::U4) => Self::Int(value.cast::<i32>().ok()?.into()),
+            TypeDescriptor::Integer(IntSize::U8) => Self::Int(value.cast::<i64>().ok()?),
+            TypeDescriptor::Unsigned(IntSize::U1) => Self::UInt(value.cast::<u8>().ok()?.into()),
+            TypeDescriptor::Unsigned(IntSize::U2) => Self::UInt(value.cast::<u16>().ok()?.into()),
+            TypeDescriptor::Unsigned(IntSize::U4) => Self::UInt(value.cast::<u32>().ok()?.into()),
+            TypeDescriptor::Unsigned(IntSize::U8) => Self::UInt(value.cast::<u64>().ok()?),
+            TypeDescriptor::Float(FloatSize::U4) => Self::Float(value.cast::<f32>().ok()?.into()),
+            TypeDescriptor::Float(FloatSize::U8) => Self::Float(value.cast::<f64>().ok()?),
+            TypeDescriptor::Boolean => Self::Bool(value.cast::<bool>().ok()?),
+            _ => return None,
+        })
+    }
+}
+
+/// A description of a dataset: its type, shape, storage and fill value.
+///
+/// A spec can be applied with [`create`](Self::create) and extracted from an existing
+/// dataset with [`from_dataset`](Self::from_dataset). With the `serde` feature it can be
+/// read from and written to configuration files, e.g. in JSON:
+///
+/// ```json
+/// {
+///   "dtype": {"Float": 8},
+///   "shape": [100, 3],
+///   "maxshape": [null, 3],
+///   "chunks": [10, 3],
+///   "filters": [{"filter": "shuffle"}, {"filter": "deflate", "level": 4}],
+///   "fill": -1.0
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DatasetSpec {
+    /// The element type.
+    pub dtype: TypeDescriptor,
+    /// The current shape; an empty shape denotes a scalar dataset.
+    pub shape: Vec<Ix>,
+    /// The maximum shape, with `None` for unlimited dimensions. Defaults to `shape`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub maxshape: Option<Vec<Option<Ix>>>,
+    /// The chunk shape, if the dataset is chunked.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub chunks: Option<Vec<Ix>>,
+    /// The filter pipeline.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub filters: Vec<Filter>,
+    /// The fill value, if different from the library default.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fill: Option<FillSpec>,
+}
+
+impl DatasetSpec {
+    /// Creates a spec for a dataset of type `dtype` and fixed shape `shape`.
+    pub fn new(dtype: TypeDescriptor, shape: Vec<Ix>) -> Self {
+        Self { dtype, shape, maxshape: None, chunks: None, filters: vec![], fill: None }
+    }
+
+    fn extents(&self) -> Result<Extents> {
+        let Some(maxshape) = &self.maxshape else {
+            return Ok(if self.shape.is_empty() {
+                Extents::Scalar
+            } else {
+                SimpleExtents::fixed(&self.shape).into()
+            });
+        };
+        ensure!(
+            maxshape.len() == self.shape.len(),
+            "maxshape {:?} does not match shape {:?}",
+            maxshape,
+            self.shape
+        );
+        let extents = self.shape.iter().zip(maxshape).map(|(&dim, &max)| Extent::new(dim, max));
+        Ok(SimpleExtents::from_vec(extents.collect()).into())
+    }
+
+    /// Creates the dataset `name` in `parent` according to the spec.
+    pub fn create(&self, parent: &Group, name: &str) -> Result<Dataset> {
+        let mut builder = parent.new_dataset_builder().empty_as(&self.dtype);
+        if let Some(chunks) = &self.chunks {
+            builder = builder.chunk(chunks.as_slice());
+        }
+        builder = builder.set_filters(&self.filters);
+        if let Some(fill) = self.fill {
+            builder = builder.fill_value(fill.to_value(&self.dtype)?);
+        }
+        builder.shape(self.extents()?).create(name)
+    }
+
+    /// Describes an existing dataset.
+    ///
+    /// Fails if the dataset has a null dataspace, or a user-defined fill value of a type
+    /// other than integers, floats and booleans.
+    pub fn from_dataset(dataset: &Dataset) -> Result<Self> {
+        h5lock!({
+            let extents = dataset.space()?.extents()?;
+            ensure!(!extents.is_null(), "datasets with null dataspaces cannot be described");
+            let shape = extents.dims();
+            let maxdims = extents.maxdims();
+            let is_fixed = maxdims.iter().zip(&shape).all(|(max, &dim)| *max == Some(dim));
+            let dcpl = dataset.dcpl()?;
+            let fill = if dcpl.get_fill_value_defined()? == FillValue::UserDefined {
+                match dataset.fill_value()? {
+                    Some(value) => Some(FillSpec::from_value(value).ok_or_else(|| {
+                        format!("unsupported fill value type for dataset '{}'", dataset.name())
+                    })?),
+                    None => None,
+                }
+            } else {
+                None
+            };
+            Ok(Self {
+                dtype: dataset.dtype()?.to_descriptor()?,
+                shape,
+                maxshape: if is_fixed { None } else { Some(maxdims) },
+                chunks: dcpl.get_chunk()?,
+                filters: dcpl.get_filters()?,
+                fill,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use hdf5_types::{H5Type, TypeDescriptor};
+
+    use super::FillSpec;
+
+    #[test]
+    pub fn test_fill_spec() {
+        let value = FillSpec::Int(-3).to_value(&i16::type_descriptor()).unwrap();
+        assert_eq!(value.cast::<i16>().unwrap(), -3);
+        let value = FillSpec::Float(7.0).to_value(&u8::type_descriptor()).unwrap();
+        assert_eq!(FillSpec::from_value(value), Some(FillSpec::UInt(7)));
+        let value = FillSpec::Int(2).to_value(&f32::type_descriptor()).unwrap();
+        assert_eq!(FillSpec::from_value(value), Some(FillSpec::Float(2.0)));
+        let value = FillSpec::Int(1).to_value(&bool::type_descriptor()).unwrap();
+        assert_eq!(FillSpec::from_value(value), Some(FillSpec::Bool(true)));
+        assert!(FillSpec::Int(-1).to_value(&u32::type_descriptor()).is_err());
+        assert!(FillSpec::Int(300).to_value(&i8::type_descriptor()).is_err());
+        assert!(FillSpec::Float(0.5).to_value(&i64::type_descriptor()).is_err());
+        assert!(FillSpec::Int(2).to_value(&bool::type_descriptor()).is_err());
+        assert!(FillSpec::Int(0).to_value(&TypeDescriptor::VarLenUnicode).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! Conversions between Rust strings and string datasets/attributes.
+
+use hdf5_types::{FixedUnicode, StringError, TypeDescriptor, VarLenAscii, VarLenUnicode};
+
+use crate::sys::h5a::H5Awrite;
+
+use crate::internal_prelude::*;
+
+/// What to do with strings that do not fit into a fixed-length string type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StringTruncation {
+    /// Fail, reporting the offending element.
+    Error,
+    /// Truncate to the longest prefix that fits, at a character boundary.
+    Truncate,
+}
+
+/// Returns the longest prefix of `s` of at most `len` bytes ending at a character boundary.
+fn truncate_str(s: &str, len: usize) -> &str {
+    let mut end = len.min(s.len());
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+pub(crate) fn varlen_unicode_strings<I, S>(data: I) -> Result<Vec<VarLenUnicode>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    data.into_iter()
+        .enumerate()
+        .map(|(i, s)| {
+            s.as_ref().parse::<VarLenUnicode>().map_err(|err| format!("element {}: {}", i, err))
+        })
+        .collect::<Result<_, _>>()
+        .map_err(Into::into)
+}
+
+pub(crate) fn fixed_unicode_strings<const N: usize, I, S>(
+    data: I,
+    truncation: StringTruncation,
+) -> Result<Vec<FixedUnicode<N>>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut strings = vec![];
+    for (i, s) in data.into_iter().enumerate() {
+        let s = s.as_ref();
+        ensure!(!s.contains('\0'), "element {}: {}", i, StringError::InternalNull);
+        let s = match truncation {
+            StringTruncation::Error => {
+                ensure!(
+                    s.len() <= N,
+                    "element {}: string of {} bytes does not fit into {} bytes",
+                    i,
+                    s.len(),
+                    N
+                );
+                s
+            }
+            StringTruncation::Truncate => truncate_str(s, N),
+        };
+        strings.push(s.parse().map_err(|err| format!("element {}: {}", i, err))?);
+    }
+    Ok(strings)
+}
+
+impl Attribute {
+    /// Writes a string into a single-element string attribute of any string type.
+    ///
+    /// Fails if the string does not fit into a fixed-length string type, or if it contains
+    /// non-ASCII characters and the attribute has the ASCII character set.
+    pub fn write_str(&self, value: &str) -> Result<()> {
+        ensure!(
+            self.size()? == 1,
+            "expected a single-element attribute, got shape {:?}",
+            self.shape()
+        );
+        let desc = self.dtype()?.to_descriptor()?;
+        match desc {
+            TypeDescriptor::VarLenUnicode => {
+                self.write_raw(&varlen_unicode_strings([value])?)?;
+            }
+            TypeDescriptor::VarLenAscii => {
+                let value = VarLenAscii::from_ascii(value).map_err(|err| err.to_string())?;
+                self.write_raw(&[value])?;
+            }
+            TypeDescriptor::FixedAscii(len) | TypeDescriptor::FixedUnicode(len) => {
+                ensure!(
+                    matches!(desc, TypeDescriptor::FixedUnicode(_)) || value.is_ascii(),
+                    "cannot write non-ASCII string to an attribute of type {}",
+                    desc
+                );
+                ensure!(!value.contains('\0'), "{}", StringError::InternalNull);
+                ensure!(
+                    value.len() <= len,
+                    "string of {} bytes does not fit into {} bytes",
+                    value.len(),
+                    len
+                );
+                let mut buf = value.as_bytes().to_vec();
+                buf.resize(len, 0);
+                let mem_dtype = Datatype::from_descriptor(&desc)?;
+                h5try!(H5Awrite(self.id(), mem_dtype.id(), buf.as_ptr().cast()));
+            }
+            _ => fail!("expected a string type, got {}", desc),
+        }
+        Ok(())
+    }
+
+    /// Reads a single-element string attribute of any string type.
+    ///
+    /// See [`Reader::read_strings`].
+    pub fn read_string(&self) -> Result<String> {
+        match <[String; 1]>::try_from(self.read_strings()?) {
+            Ok([value]) => Ok(value),
+            Err(strings) => {
+                fail!("expected a single-element attribute, got {} elements", strings.len())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::truncate_str;
+
+    #[test]
+    pub fn test_truncate_str() {
+        assert_eq!(truncate_str("abc", 5), "abc");
+        assert_eq!(truncate_str("abc", 2), "ab");
+        assert_eq!(truncate_str("aé", 2), "a");
+        assert_eq!(truncate_str("aé", 3), "aé");
+        assert_eq!(truncate_str("é", 0), "");
+    }
+}
@@ -0,0 +1,114 @@
+//! Reading and writing timestamps (`time` feature).
+//!
+//! See [`types::time`](crate::types::time) for the supported encodings.
+
+use std::str::FromStr;
+
+use hdf5_types::time::{parse_iso8601, Iso8601, TimeEncoding, Timestamp, ISO8601_LEN};
+use hdf5_types::{
+    FixedAscii, FixedUnicode, StringError, TypeDescriptor, VarLenAscii, VarLenUnicode,
+};
+
+use crate::internal_prelude::*;
+
+fn parse_all<S: AsRef<str>>(strings: Vec<S>) -> Result<Vec<i64>> {
+    strings
+        .iter()
+        .map(|s| parse_iso8601(s.as_ref()).map_err(|err| err.to_string().into()))
+        .collect()
+}
+
+fn iso_strings<T: Timestamp>(values: &[T]) -> Result<Vec<Iso8601>> {
+    values.iter().map(|v| v.to_iso8601().map_err(|err| err.to_string().into())).collect()
+}
+
+fn convert_strings<S>(
+    strings: &[Iso8601],
+    convert: impl Fn(&str) -> Result<S, StringError>,
+) -> Result<Vec<S>> {
+    strings.iter().map(|s| convert(s.as_str()).map_err(|err| err.to_string().into())).collect()
+}
+
+impl Container {
+    /// Reads timestamps stored either as nanoseconds since the Unix epoch or as ISO-8601
+    /// strings, whichever is found in the file.
+    pub fn read_times<T: Timestamp>(&self) -> Result<Vec<T>> {
+        let nanos = match self.dtype()?.to_descriptor()? {
+            TypeDescriptor::Integer(_) | TypeDescriptor::Unsigned(_) => self.read_raw::<i64>()?,
+            TypeDescriptor::FixedAscii(_) => {
+                parse_all(self.read_raw::<FixedAscii<ISO8601_LEN>>()?)?
+            }
+            TypeDescriptor::FixedUnicode(_) => {
+                parse_all(self.read_raw::<FixedUnicode<ISO8601_LEN>>()?)?
+            }
+            TypeDescriptor::VarLenAscii => parse_all(self.read_raw::<VarLenAscii>()?)?,
+            TypeDescriptor::VarLenUnicode => parse_all(self.read_raw::<VarLenUnicode>()?)?,
+            desc => fail!("expected timestamps stored as integers or strings, got {}", desc),
+        };
+        Ok(nanos.into_iter().map(T::from_unix_nanos).collect())
+    }
+
+    /// Reads a single timestamp, see [`read_times`](Self::read_times).
+    pub fn read_time<T: Timestamp>(&self) -> Result<T> {
+        let mut values = self.read_times::<T>()?;
+        ensure!(values.len() == 1, "expected a single timestamp, got {}", values.len());
+        values.pop().ok_or_else(|| "expected a single timestamp".into())
+    }
+
+    /// Writes timestamps using the encoding of the existing attribute or dataset.
+    pub fn write_times<T: Timestamp>(&self, values: &[T]) -> Result<()> {
+        match self.dtype()?.to_descriptor()? {
+            TypeDescriptor::Integer(_) | TypeDescriptor::Unsigned(_) => {
+                let nanos = values
+                    .iter()
+                    .map(|v| v.to_unix_nanos().map_err(|err| err.to_string().into()))
+                    .collect::<Result<Vec<i64>>>()?;
+                self.write_raw(&nanos)
+            }
+            TypeDescriptor::FixedAscii(_) => self.write_raw(&iso_strings(values)?),
+            TypeDescriptor::FixedUnicode(_) => {
+                let strings =
+                    convert_strings(&iso_strings(values)?, FixedUnicode::<ISO8601_LEN>::from_str)?;
+                self.write_raw(&strings)
+            }
+            TypeDescriptor::VarLenAscii => {
+                let strings = convert_strings(&iso_strings(values)?, VarLenAscii::from_ascii)?;
+                self.write_raw(&strings)
+            }
+            TypeDescriptor::VarLenUnicode => {
+                let strings = convert_strings(&iso_strings(values)?, VarLenUnicode::from_str)?;
+                self.write_raw(&strings)
+            }
+            desc => fail!("expected timestamps stored as integers or strings, got {}", desc),
+        }
+    }
+
+    /// Writes a single timestamp, see [`write_times`](Self::write_times).
+    pub fn write_time<T: Timestamp>(&self, value: &T) -> Result<()> {
+        self.write_times(std::slice::from_ref(value))
+    }
+}
+
+impl Location {
+    /// Creates a one-dimensional attribute holding timestamps.
+    ///
+    /// The timestamps are stored using the process-wide [`TimeEncoding::current()`].
+    pub fn create_time_attr<T: Timestamp>(&self, name: &str, values: &[T]) -> Result<Attribute> {
+        let desc = TimeEncoding::current().type_descriptor();
+        let attr = self.new_attr_builder().empty_as(&desc).shape(values.len()).create(name)?;
+        attr.write_times(values)?;
+        Ok(attr)
+    }
+}
+
+impl Group {
+    /// Creates a one-dimensional dataset holding timestamps.
+    ///
+    /// The timestamps are stored using the process-wide [`TimeEncoding::current()`].
+    pub fn create_time_dataset<T: Timestamp>(&self, name: &str, values: &[T]) -> Result<Dataset> {
+        let desc = TimeEncoding::current().type_descriptor();
+        let ds = self.new_dataset_builder().empty_as(&desc).shape(values.len()).create(name)?;
+        ds.write_times(values)?;
+        Ok(ds)
+    }
+}
@@ -0,0 +1,312 @@
+//! Writing trees of values (maps, lists, arrays and scalars) as groups and reading them back.
+
+use hdf5_types::{OwnedDynValue, TypeDescriptor, VarLenAscii, VarLenUnicode};
+use indexmap::IndexMap;
+use ndarray::{Array, ArrayD, ArrayView, IxDyn};
+
+use crate::conventions::LIST_LEN_ATTR;
+use crate::dim::checked_buf_size;
+use crate::internal_prelude::*;
+
+/// A tree of values that maps onto groups, datasets and attributes.
+///
+/// Maps and lists are stored as groups, arrays as datasets and scalars as attributes of the
+/// parent group (or as 0-dimensional datasets, see [`TreeOptions`]). List members are named
+/// with zero-padded indices (`"00"`, `"01"`, ...) and the group holds the list length in the
+/// [`LIST_LEN_ATTR`] attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub enum H5Value {
+    /// A single value.
+    Scalar(OwnedDynValue),
+    /// An n-dimensional array whose elements all have the type `dtype`.
+    Array { dtype: TypeDescriptor, data: ArrayD<OwnedDynValue> },
+    /// Named values, in insertion order.
+    Map(IndexMap<String, H5Value>),
+    /// A sequence of values.
+    List(Vec<H5Value>),
+}
+
+impl H5Value {
+    /// Creates a scalar value.
+    pub fn scalar<T: H5Type>(value: T) -> Self {
+        Self::Scalar(OwnedDynValue::new(value))
+    }
+
+    /// Creates an array value by copying the elements of an array view.
+    pub fn array<'a, A, T, D>(data: A) -> Self
+    where
+        A: Into<ArrayView<'a, T, D>>,
+        T: H5Type + Clone,
+        D: ndarray::Dimension,
+    {
+        let data = data.into().into_dyn().map(|x| OwnedDynValue::new(x.clone()));
+        Self::Array { dtype: T::type_descriptor(), data }
+    }
+
+    /// Creates a string scalar, failing if the string contains null characters.
+    pub fn string(value: &str) -> Result<Self> {
+        Ok(Self::scalar(value.parse::<VarLenUnicode>().map_err(|e| e.to_string())?))
+    }
+
+    /// Returns the value of a map entry, or `None` if this is not a map or has no such key.
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        self.as_map().and_then(|map| map.get(key))
+    }
+
+    /// Returns the entries if this is a map.
+    pub fn as_map(&self) -> Option<&IndexMap<String, Self>> {
+        match self {
+            Self::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if this is a list.
+    pub fn as_list(&self) -> Option<&[Self]> {
+        match self {
+            Self::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    /// Returns a copy of the value if this is a scalar of type `T`.
+    pub fn to_scalar<T: H5Type>(&self) -> Option<T> {
+        match self {
+            Self::Scalar(value) => value.clone().cast().ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if this is a variable-length string scalar.
+    pub fn to_str(&self) -> Option<String> {
+        self.to_scalar::<VarLenUnicode>()
+            .map(|s| s.as_str().to_owned())
+            .or_else(|| self.to_scalar::<VarLenAscii>().map(|s| s.as_str().to_owned()))
+    }
+
+    /// Returns a copy of the elements if this is an array of type `T`.
+    pub fn to_array<T: H5Type>(&self) -> Option<ArrayD<T>> {
+        match self {
+            Self::Array { dtype, data } if *dtype == T::type_descriptor() => {
+                let values = data.iter().map(|x| x.clone().cast().ok()).collect::<Option<_>>()?;
+                ArrayD::from_shape_vec(data.raw_dim(), values).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_from_scalar {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for H5Value {
+                fn from(value: $ty) -> Self {
+                    Self::scalar(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_scalar!(bool, i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, VarLenUnicode, VarLenAscii);
+
+impl From<OwnedDynValue> for H5Value {
+    fn from(value: OwnedDynValue) -> Self {
+        Self::Scalar(value)
+    }
+}
+
+impl<T: H5Type + Clone, D: ndarray::Dimension> From<Array<T, D>> for H5Value {
+    fn from(data: Array<T, D>) -> Self {
+        Self::array(&data)
+    }
+}
+
+impl From<IndexMap<String, H5Value>> for H5Value {
+    fn from(map: IndexMap<String, H5Value>) -> Self {
+        Self::Map(map)
+    }
+}
+
+impl From<Vec<H5Value>> for H5Value {
+    fn from(list: Vec<H5Value>) -> Self {
+        Self::List(list)
+    }
+}
+
+/// Where [`to_group_with`] stores scalar values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScalarStorage {
+    /// As attributes of the parent group.
+    #[default]
+    Attribute,
+    /// As 0-dimensional datasets.
+    Dataset,
+}
+
+/// Options for [`to_group_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TreeOptions {
+    /// Where scalar values are stored.
+    pub scalars: ScalarStorage,
+}
+
+/// Writes a value tree as `name` in `parent` with the default options.
+///
+/// See [`to_group_with`].
+pub fn to_group(parent: &Group, name: &str, value: &H5Value) -> Result<()> {
+    to_group_with(parent, name, value, &TreeOptions::default())
+}
+
+/// Writes a value tree as `name` in `parent`.
+///
+/// Maps and lists become groups, arrays become datasets and scalars become attributes of
+/// `parent` or 0-dimensional datasets, depending on [`TreeOptions::scalars`].
+pub fn to_group_with(
+    parent: &Group,
+    name: &str,
+    value: &H5Value,
+    options: &TreeOptions,
+) -> Result<()> {
+    ensure!(!name.is_empty() && !name.contains('/'), "invalid member name '{}'", name);
+    match value {
+        H5Value::Map(map) => {
+            let group = parent.create_group(name)?;
+            for (key, value) in map {
+                to_group_with(&group, key, value, options)
+                    .with_context(|| format!("writing '{key}'"))?;
+            }
+        }
+        H5Value::List(list) => {
+            let group = parent.create_group(name)?;
+            group.new_attr::<u64>().create(LIST_LEN_ATTR)?.write_scalar(&(list.len() as u64))?;
+            for (i, value) in list.iter().enumerate() {
+                let key = list_key(i, list.len());
+                to_group_with(&group, &key, value, options)
+                    .with_context(|| format!("writing '{key}'"))?;
+            }
+        }
+        H5Value::Array { dtype, data } => {
+            let ds =
+                parent.new_dataset_builder().empty_as(dtype).shape(data.shape()).create(name)?;
+            let values: Vec<&OwnedDynValue> = data.iter().collect();
+            write_values(&ds, dtype, &values)?;
+        }
+        H5Value::Scalar(value) => {
+            let dtype = value.type_descriptor();
+            let obj: Container = match options.scalars {
+                ScalarStorage::Attribute => {
+                    (*parent.new_attr_builder().empty_as(dtype).create(name)?).clone()
+                }
+                ScalarStorage::Dataset => {
+                    (*parent.new_dataset_builder().empty_as(dtype).create(name)?).clone()
+                }
+            };
+            write_values(&obj, dtype, &[value])?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the value tree stored in a group.
+///
+/// Groups with a [`LIST_LEN_ATTR`] attribute become lists (whose elements are members or,
+/// for scalars, attributes of the group), other groups become maps holding their members
+/// (in link iteration order) followed by their attributes. Datasets and
+/// attributes become arrays, or scalars if their dataspace is scalar.
+pub fn from_group(group: &Group) -> Result<H5Value> {
+    let attr_names = group.attr_names()?;
+    if attr_names.iter().any(|name| name == LIST_LEN_ATTR) {
+        let len = group.attr(LIST_LEN_ATTR)?.read_scalar::<u64>()? as usize;
+        let list = (0..len)
+            .map(|i| {
+                let key = list_key(i, len);
+                read_member(group, &key).with_context(|| format!("reading '{key}'"))
+            })
+            .collect::<Result<_>>()?;
+        return Ok(H5Value::List(list));
+    }
+    let mut map = IndexMap::new();
+    for name in group.member_names()? {
+        let value = read_member(group, &name).with_context(|| format!("reading '{name}'"))?;
+        map.insert(name, value);
+    }
+    for name in attr_names {
+        let value = group
+            .attr(&name)
+            .and_then(|attr| read_values(&attr))
+            .with_context(|| format!("reading '{name}'"))?;
+        map.insert(name, value);
+    }
+    Ok(H5Value::Map(map))
+}
+
+fn list_key(index: usize, len: usize) -> String {
+    let width = len.saturating_sub(1).to_string().len();
+    format!("{index:0width$}")
+}
+
+fn read_member(group: &Group, name: &str) -> Result<H5Value> {
+    // list elements that are scalars may be attributes of the list group
+    if !group.link_exists(name) && group.attr_names()?.iter().any(|attr| attr == name) {
+        return group.attr(name).and_then(|attr| read_values(&attr));
+    }
+    match group.loc_type_by_name(name)? {
+        LocationType::Group => from_group(&group.group(name)?),
+        LocationType::Dataset => group.dataset(name).and_then(|ds| read_values(&ds)),
+        loc_type => fail!("unsupported object type {:?}", loc_type),
+    }
+}
+
+fn write_values(obj: &Container, dtype: &TypeDescriptor, values: &[&OwnedDynValue]) -> Result<()> {
+    if let Some(value) = values.iter().find(|value| value.type_descriptor() != dtype) {
+        fail!("element of type {} in an array of type {dtype}", value.type_descriptor());
+    }
+    let mem_type = Datatype::from_descriptor(dtype)?;
+    // variable-length data is only referenced by the buffer, which does not own it
+    let buf: Vec<u8> =
+        values.iter().flat_map(|value| unsafe { value.get_buf() }).copied().collect();
+    if buf.is_empty() {
+        return Ok(());
+    }
+    obj.as_writer().write_from_buf_as(buf.as_ptr().cast(), &mem_type, None, None)
+}
+
+fn read_values(obj: &Container) -> Result<H5Value> {
+    let dtype = obj.dtype()?.to_descriptor()?;
+    let size = dtype.size();
+    ensure!(size > 0, "cannot read values of zero-sized type {}", dtype);
+    let mem_type = Datatype::from_descriptor(&dtype)?;
+    let mut buf = vec![0_u8; checked_buf_size(obj.size()?, size)?];
+    if !buf.is_empty() {
+        obj.as_reader().read_into_buf_as(buf.as_mut_ptr().cast(), &mem_type, None, None)?;
+    }
+    // each value takes ownership of the variable-length data allocated by the read
+    let mut values: Vec<OwnedDynValue> = buf
+        .chunks_exact(size)
+        .map(|elem| unsafe { OwnedDynValue::from_raw(dtype.clone(), elem.into()) })
+        .collect();
+    if obj.is_scalar() {
+        match values.pop() {
+            Some(value) => Ok(H5Value::Scalar(value)),
+            None => fail!("scalar without a value"),
+        }
+    } else {
+        let data = ArrayD::from_shape_vec(IxDyn(&obj.shape()?), values)?;
+        Ok(H5Value::Array { dtype, data })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::list_key;
+
+    #[test]
+    pub fn test_list_key() {
+        assert_eq!(list_key(0, 0), "0");
+        assert_eq!(list_key(0, 1), "0");
+        assert_eq!(list_key(3, 10), "3");
+        assert_eq!(list_key(3, 11), "03");
+        assert_eq!(list_key(42, 1000), "042");
+    }
+}
@@ -0,0 +1,226 @@
+//! Dataset handles with a statically known element type and number of dimensions.
+
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use ndarray::{Array, ArrayView, Axis, Dim, Dimension};
+
+use crate::internal_prelude::*;
+
+/// A dataset whose element type `T` and number of dimensions `NDIM` have been checked once,
+/// as returned by [`Dataset::typed`].
+///
+/// Reads and writes use arrays of the fixed dimensionality `NDIM`, so rank mismatches are
+/// caught when the handle is created instead of deep inside the conversion code. The
+/// underlying [`Dataset`] is available through `Deref`.
+pub struct TypedDataset<T, const NDIM: usize> {
+    dataset: Dataset,
+    marker: PhantomData<fn() -> T>,
+}
+
+/// A one-dimensional dataset with elements of type `T`.
+pub type Dataset1D<T> = TypedDataset<T, 1>;
+
+/// A two-dimensional dataset with elements of type `T`.
+pub type Dataset2D<T> = TypedDataset<T, 2>;
+
+impl<T, const NDIM: usize> Clone for TypedDataset<T, NDIM> {
+    fn clone(&self) -> Self {
+        Self { dataset: self.dataset.clone(), marker: PhantomData }
+    }
+}
+
+impl<T, const NDIM: usize> Debug for TypedDataset<T, NDIM> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.dataset, f)
+    }
+}
+
+impl<T, const NDIM: usize> Deref for TypedDataset<T, NDIM> {
+    type Target = Dataset;
+
+    fn deref(&self) -> &Dataset {
+        &self.dataset
+    }
+}
+
+impl<T, const NDIM: usize> From<TypedDataset<T, NDIM>> for Dataset {
+    fn from(typed: TypedDataset<T, NDIM>) -> Self {
+        typed.dataset
+    }
+}
+
+impl<T, const NDIM: usize> TypedDataset<T, NDIM>
+where
+    T: H5Type,
+    Dim<[Ix; NDIM]>: Dimension,
+{
+    fn new(dataset: &Dataset) -> Result<Self> {
+        ensure!(
+            dataset.ndim() == NDIM,
+            "expected a {}-dimensional dataset, got shape {:?}",
+            NDIM,
+            dataset.shape()
+        );
+        // the same check the reader performs with its default conversion level
+        dataset.dtype()?.ensure_convertible(&Datatype::from_type::<T>()?, Conversion::Soft)?;
+        Ok(Self { dataset: dataset.clone(), marker: PhantomData })
+    }
+
+    /// Returns the untyped dataset.
+    pub fn dataset(&self) -> &Dataset {
+        &self.dataset
+    }
+
+    /// Returns the current shape of the dataset.
+    pub fn shape(&self) -> Result<[Ix; NDIM]> {
+        let mut shape = [0; NDIM];
+        for (dim, len) in shape.iter_mut().zip(self.dataset.shape()?) {
+            *dim = len;
+        }
+        Ok(shape)
+    }
+
+    /// Reads the dataset into an array.
+    pub fn read(&self) -> Result<Array<T, Dim<[Ix; NDIM]>>> {
+        self.dataset.read()
+    }
+
+    /// Reads a selection of the dataset into an array, see [`Reader::read_slice`].
+    pub fn read_slice<S, D>(&self, selection: S) -> Result<Array<T, D>>
+    where
+        S: TryInto<Selection>,
+        Error: From<S::Error>,
+        D: Dimension,
+    {
+        self.dataset.read_slice(selection)
+    }
+
+    /// Writes an array with the shape of the dataset.
+    pub fn write<'a, A>(&self, arr: A) -> Result<()>
+    where
+        A: Into<ArrayView<'a, T, Dim<[Ix; NDIM]>>>,
+        T: 'a,
+    {
+        self.dataset.write(arr)
+    }
+
+    /// Appends a block of elements along the first axis, resizing the dataset.
+    ///
+    /// The block either has `NDIM` dimensions, appending `block.shape()[0]` rows, or `NDIM - 1`
+    /// dimensions, appending a single row. The other dimensions must match the dataset, which
+    /// must be chunked and resizable along the first axis. Returns the new length of the first
+    /// axis.
+    pub fn append<'a, A, D>(&self, block: A) -> Result<Ix>
+    where
+        A: Into<ArrayView<'a, T, D>>,
+        T: 'a,
+        D: Dimension,
+    {
+        ensure!(NDIM > 0, "cannot append to a scalar dataset");
+        let mut block = block.into().into_dyn();
+        if block.ndim() + 1 == NDIM {
+            block.insert_axis_inplace(Axis(0));
+        }
+        let shape = self.shape()?;
+        ensure!(
+            block.ndim() == NDIM && block.shape()[1..] == shape[1..],
+            "cannot append a block of shape {:?} to a dataset of shape {:?}",
+            block.shape(),
+            shape
+        );
+        let (start, count) = (shape[0], block.shape()[0]);
+        if count == 0 {
+            return Ok(start);
+        }
+        let mut new_shape = shape;
+        new_shape[0] += count;
+        self.dataset
+            .resize(&new_shape[..])
+            .with_context(|| format!("resizing dataset '{}'", self.dataset.name()))?;
+        let slices = new_shape
+            .iter()
+            .enumerate()
+            .map(|(i, &len)| SliceOrIndex::SliceCount {
+                start: if i == 0 { start } else { 0 },
+                step: 1,
+                count: if i == 0 { count } else { len },
+                block: 1,
+            })
+            .collect::<Vec<_>>();
+        self.dataset.write_slice(&block, Hyperslab::from(slices))?;
+        Ok(new_shape[0])
+    }
+}
+
+impl Dataset {
+    /// Checks the element type and number of dimensions of the dataset and returns a typed
+    /// handle to it.
+    ///
+    /// Fails if the dataset does not have `NDIM` dimensions, or if its elements cannot be
+    /// converted to `T` under the default conversion rules of [`Reader`].
+    pub fn typed<T, const NDIM: usize>(&self) -> Result<TypedDataset<T, NDIM>>
+    where
+        T: H5Type,
+        Dim<[Ix; NDIM]>: Dimension,
+    {
+        TypedDataset::new(self).with_context(|| format!("opening dataset '{}'", self.name()))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ndarray::{arr1, arr2, s, Array2};
+
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_typed_dataset() {
+        with_tmp_file(|file| {
+            let data = Array2::from_shape_fn((3, 4), |(i, j)| (i * 10 + j) as i32);
+            let ds = file.new_dataset_builder().with_data(&data).create("x").unwrap();
+            let typed = ds.typed::<i64, 2>().unwrap();
+            assert_eq!(typed.shape().unwrap(), [3, 4]);
+            assert_eq!(typed.read().unwrap(), data.mapv(i64::from));
+            assert_eq!(typed.read().unwrap(), ds.read_2d::<i64>().unwrap());
+            assert_eq!(
+                typed.read_slice::<_, ndarray::Ix1>(s![1, ..]).unwrap(),
+                arr1(&[10, 11, 12, 13])
+            );
+            typed.write(&(data.mapv(i64::from) * 2)).unwrap();
+            assert_eq!(ds.read_raw::<i32>().unwrap()[5], 22);
+            assert_eq!(typed.name(), "/x");
+
+            assert_err!(ds.typed::<i32, 1>(), "expected a 1-dimensional dataset, got shape [3, 4]");
+            assert_err!(ds.typed::<i32, 3>(), "opening dataset '/x'");
+            let strings =
+                file.new_dataset::<hdf5_types::VarLenUnicode>().shape(2).create("s").unwrap();
+            assert!(strings.typed::<f64, 1>().is_err());
+            assert!(strings.typed::<hdf5_types::VarLenUnicode, 1>().is_ok());
+        })
+    }
+
+    #[test]
+    pub fn test_typed_append() {
+        with_tmp_file(|file| {
+            let ds =
+                file.new_dataset::<f32>().chunk((4, 2)).shape((0.., 2)).create("rows").unwrap();
+            let rows = ds.typed::<f32, 2>().unwrap();
+            assert_eq!(rows.append(&arr2(&[[1., 2.], [3., 4.]])).unwrap(), 2);
+            assert_eq!(rows.append(&arr1(&[5., 6.])).unwrap(), 3);
+            assert_eq!(rows.append(&Array2::<f32>::zeros((0, 2))).unwrap(), 3);
+            assert_eq!(rows.read().unwrap(), arr2(&[[1., 2.], [3., 4.], [5., 6.]]));
+            assert_err!(rows.append(&arr1(&[1., 2., 3.])), "cannot append a block of shape [1, 3]");
+
+            let values = file.new_dataset::<u8>().chunk(8).shape(0..).create("values").unwrap();
+            let values = values.typed::<u8, 1>().unwrap();
+            values.append(&arr1(&[1, 2, 3])).unwrap();
+            values.append(ndarray::arr0(4).view()).unwrap();
+            assert_eq!(values.read().unwrap(), arr1(&[1, 2, 3, 4]));
+
+            let fixed = file.new_dataset::<u8>().shape(2).create("fixed").unwrap();
+            assert_err!(fixed.typed::<u8, 1>().unwrap().append(&arr1(&[1])), "resizing dataset");
+        })
+    }
+}
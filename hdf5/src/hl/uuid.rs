@@ -0,0 +1,128 @@
+//! Reading and writing UUIDs (`uuid` feature).
+//!
+//! See [`types::uuid`](crate::types::uuid) for the supported encodings.
+
+use std::str::FromStr;
+
+use hdf5_types::uuid::{Uuid, UuidEncoding, UuidString, UUID_STRING_LEN};
+use hdf5_types::{
+    FixedAscii, FixedUnicode, IntSize, OpaqueType, StringError, TypeDescriptor, VarLenAscii,
+    VarLenUnicode,
+};
+
+use crate::internal_prelude::*;
+
+/// Length of the longest UUID string form, `urn:uuid:` followed by the hyphenated form.
+const MAX_UUID_STRING_LEN: usize = 45;
+
+fn parse_all<S: AsRef<str>>(strings: Vec<S>) -> Result<Vec<Uuid>> {
+    strings
+        .iter()
+        .map(|s| {
+            Uuid::parse_str(s.as_ref().trim())
+                .map_err(|err| format!("invalid UUID {:?}: {err}", s.as_ref()).into())
+        })
+        .collect()
+}
+
+fn convert_strings<S>(
+    values: &[Uuid],
+    convert: impl Fn(&str) -> Result<S, StringError>,
+) -> Result<Vec<S>> {
+    values
+        .iter()
+        .map(|&v| convert(UuidString::new(v).as_str()).map_err(|err| err.to_string().into()))
+        .collect()
+}
+
+impl Container {
+    /// Reads UUIDs stored either as 16-byte opaque values (or arrays of 16 bytes) or as
+    /// strings, whichever is found in the file.
+    ///
+    /// Strings may be in any of the forms accepted by [`Uuid::parse_str`].
+    pub fn read_uuids(&self) -> Result<Vec<Uuid>> {
+        match self.dtype()?.to_descriptor()? {
+            TypeDescriptor::Opaque(OpaqueType { size: 16, .. }) => self.read_raw::<Uuid>(),
+            TypeDescriptor::FixedArray(ref ty, 16)
+                if matches!(**ty, TypeDescriptor::Unsigned(IntSize::U1)) =>
+            {
+                Ok(self.read_raw::<[u8; 16]>()?.into_iter().map(Uuid::from_bytes).collect())
+            }
+            TypeDescriptor::FixedAscii(_) => {
+                parse_all(self.read_raw::<FixedAscii<MAX_UUID_STRING_LEN>>()?)
+            }
+            TypeDescriptor::FixedUnicode(_) => {
+                parse_all(self.read_raw::<FixedUnicode<MAX_UUID_STRING_LEN>>()?)
+            }
+            TypeDescriptor::VarLenAscii => parse_all(self.read_raw::<VarLenAscii>()?),
+            TypeDescriptor::VarLenUnicode => parse_all(self.read_raw::<VarLenUnicode>()?),
+            desc => {
+                fail!("expected UUIDs stored as 16-byte opaque values or strings, got {}", desc)
+            }
+        }
+    }
+
+    /// Reads a single UUID, see [`read_uuids`](Self::read_uuids).
+    pub fn read_uuid(&self) -> Result<Uuid> {
+        let mut values = self.read_uuids()?;
+        ensure!(values.len() == 1, "expected a single UUID, got {}", values.len());
+        values.pop().ok_or_else(|| "expected a single UUID".into())
+    }
+
+    /// Writes UUIDs using the encoding of the existing attribute or dataset.
+    pub fn write_uuids(&self, values: &[Uuid]) -> Result<()> {
+        match self.dtype()?.to_descriptor()? {
+            TypeDescriptor::Opaque(OpaqueType { size: 16, .. }) => self.write_raw(values),
+            TypeDescriptor::FixedArray(ref ty, 16)
+                if matches!(**ty, TypeDescriptor::Unsigned(IntSize::U1)) =>
+            {
+                self.write_raw(&values.iter().map(|v| *v.as_bytes()).collect::<Vec<_>>())
+            }
+            TypeDescriptor::FixedAscii(_) => {
+                self.write_raw(&values.iter().copied().map(UuidString::new).collect::<Vec<_>>())
+            }
+            TypeDescriptor::FixedUnicode(_) => {
+                let strings = convert_strings(values, FixedUnicode::<UUID_STRING_LEN>::from_str)?;
+                self.write_raw(&strings)
+            }
+            TypeDescriptor::VarLenAscii => {
+                self.write_raw(&convert_strings(values, VarLenAscii::from_ascii)?)
+            }
+            TypeDescriptor::VarLenUnicode => {
+                self.write_raw(&convert_strings(values, VarLenUnicode::from_str)?)
+            }
+            desc => {
+                fail!("expected UUIDs stored as 16-byte opaque values or strings, got {}", desc)
+            }
+        }
+    }
+
+    /// Writes a single UUID, see [`write_uuids`](Self::write_uuids).
+    pub fn write_uuid(&self, value: &Uuid) -> Result<()> {
+        self.write_uuids(std::slice::from_ref(value))
+    }
+}
+
+impl Location {
+    /// Creates a one-dimensional attribute holding UUIDs.
+    ///
+    /// The UUIDs are stored using the process-wide [`UuidEncoding::current()`].
+    pub fn create_uuid_attr(&self, name: &str, values: &[Uuid]) -> Result<Attribute> {
+        let desc = UuidEncoding::current().type_descriptor();
+        let attr = self.new_attr_builder().empty_as(&desc).shape(values.len()).create(name)?;
+        attr.write_uuids(values)?;
+        Ok(attr)
+    }
+}
+
+impl Group {
+    /// Creates a one-dimensional dataset holding UUIDs.
+    ///
+    /// The UUIDs are stored using the process-wide [`UuidEncoding::current()`].
+    pub fn create_uuid_dataset(&self, name: &str, values: &[Uuid]) -> Result<Dataset> {
+        let desc = UuidEncoding::current().type_descriptor();
+        let ds = self.new_dataset_builder().empty_as(&desc).shape(values.len()).create(name)?;
+        ds.write_uuids(values)?;
+        Ok(ds)
+    }
+}
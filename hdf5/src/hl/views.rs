@@ -0,0 +1,197 @@
+//! Named views (slices) of datasets, stored with the [`VIEWS_GROUP`] convention.
+
+use hdf5_types::VarLenUnicode;
+use ndarray::{Array, ArrayD};
+
+use crate::conventions::{
+    VIEWS_GROUP, VIEW_COUNT_ATTR, VIEW_REGION_ATTR, VIEW_START_ATTR, VIEW_STRIDE_ATTR,
+    VIEW_TARGET_ATTR,
+};
+use crate::hl::selection::{RawSelection, RawSlice};
+use crate::internal_prelude::*;
+use crate::sys::{api_table, ReferenceApi};
+use crate::RegionReference;
+
+/// A named selection of a dataset, as returned by [`File::open_view`].
+#[derive(Clone, Debug)]
+pub struct DatasetView {
+    /// The dataset the view refers to.
+    pub dataset: Dataset,
+    /// The selected elements of the dataset.
+    pub selection: Selection,
+}
+
+impl DatasetView {
+    /// Reads the selected elements into an n-dimensional array.
+    ///
+    /// The array has the dimensionality of the dataset.
+    pub fn read<T: H5Type, D: ndarray::Dimension>(&self) -> Result<Array<T, D>> {
+        self.dataset.read_slice(self.selection.clone())
+    }
+
+    /// Reads the selected elements into an array with dynamic number of dimensions.
+    pub fn read_dyn<T: H5Type>(&self) -> Result<ArrayD<T>> {
+        self.read()
+    }
+
+    /// Returns the shape of the selected elements.
+    pub fn shape(&self) -> Result<Vec<Ix>> {
+        self.selection.out_shape(self.dataset.shape()?)
+    }
+}
+
+impl From<DatasetView> for (Dataset, Selection) {
+    fn from(view: DatasetView) -> Self {
+        (view.dataset, view.selection)
+    }
+}
+
+/// Returns the per-axis slices of a selection that can be stored as a view.
+fn view_slices(dataset: &Dataset, selection: &Selection) -> Result<Vec<RawSlice>> {
+    let shape = dataset.shape()?;
+    match selection.clone().into_raw(&shape)? {
+        RawSelection::All => Ok(shape.iter().map(|&n| RawSlice::new(0, 1, Some(n), 1)).collect()),
+        RawSelection::RegularHyperslab(hyper) => hyper
+            .iter()
+            .map(|slice| {
+                ensure!(slice.block == 1, "cannot save a view with blocks of size {}", slice.block);
+                let count = slice.count.ok_or("cannot save a view with an unlimited selection")?;
+                Ok(RawSlice::new(slice.start, slice.step, Some(count), 1))
+            })
+            .collect(),
+        RawSelection::None => fail!("cannot save an empty selection as a view"),
+        _ => fail!("only hyperslab selections can be saved as views, got {}", selection),
+    }
+}
+
+impl Dataset {
+    /// Saves a selection of the dataset as the view `name` in the [`VIEWS_GROUP`] group of
+    /// the file.
+    ///
+    /// See [`save_view_in`](Self::save_view_in).
+    pub fn save_view(&self, name: &str, selection: &Selection) -> Result<()> {
+        self.save_view_in(VIEWS_GROUP, name, selection)
+    }
+
+    /// Saves a selection of the dataset as the view `name` in the group `group` of the file,
+    /// which is created if needed.
+    ///
+    /// Only selections of evenly spaced single elements along each axis can be stored (see
+    /// [`VIEWS_GROUP`]); indexed axes are stored with a count of one. If the library supports
+    /// region references, a region reference to the selection is stored as well.
+    pub fn save_view_in(&self, group: &str, name: &str, selection: &Selection) -> Result<()> {
+        ensure!(!name.is_empty() && !name.contains('/'), "invalid view name '{}'", name);
+        let slices = view_slices(self, selection)?;
+        let file = self.file()?;
+        let views =
+            if file.link_exists(group) { file.group(group)? } else { file.create_group(group)? };
+        let view = views.create_group(name)?;
+        view.new_attr::<VarLenUnicode>().create(VIEW_TARGET_ATTR)?.write_str(&self.name())?;
+        let start: Vec<u64> = slices.iter().map(|s| s.start as u64).collect();
+        let count: Vec<u64> = slices.iter().map(|s| s.count.unwrap_or(0) as u64).collect();
+        let stride: Vec<u64> = slices.iter().map(|s| s.step as u64).collect();
+        for (attr, values) in
+            [(VIEW_START_ATTR, start), (VIEW_COUNT_ATTR, count), (VIEW_STRIDE_ATTR, stride)]
+        {
+            view.new_attr_builder().with_data(&values).create(attr)?;
+        }
+        if api_table().references == ReferenceApi::Standard {
+            let region = RegionReference::create(self, selection.clone())?;
+            view.new_attr::<RegionReference>().create(VIEW_REGION_ATTR)?.write_scalar(&region)?;
+        }
+        Ok(())
+    }
+}
+
+impl File {
+    /// Opens the view `name` saved in the [`VIEWS_GROUP`] group of the file.
+    ///
+    /// See [`open_view_in`](Self::open_view_in).
+    pub fn open_view(&self, name: &str) -> Result<DatasetView> {
+        self.open_view_in(VIEWS_GROUP, name)
+    }
+
+    /// Opens the view `name` saved in the group `group` of the file.
+    ///
+    /// The region reference of the view is used if present and supported by the library;
+    /// otherwise the dataset and selection are read from the other view attributes.
+    pub fn open_view_in(&self, group: &str, name: &str) -> Result<DatasetView> {
+        let view =
+            self.group(group)?.group(name).with_context(|| format!("opening view '{name}'"))?;
+        if api_table().references == ReferenceApi::Standard
+            && view.attr_names()?.iter().any(|attr| attr == VIEW_REGION_ATTR)
+        {
+            let region = view.attr(VIEW_REGION_ATTR)?.read_scalar::<RegionReference>()?;
+            return Ok(DatasetView { dataset: region.dataset()?, selection: region.selection()? });
+        }
+        let dataset = self.dataset(&view.attr(VIEW_TARGET_ATTR)?.read_string()?)?;
+        let start = view.attr(VIEW_START_ATTR)?.read_raw::<u64>()?;
+        let count = view.attr(VIEW_COUNT_ATTR)?.read_raw::<u64>()?;
+        let stride = view.attr(VIEW_STRIDE_ATTR)?.read_raw::<u64>()?;
+        let ndim = dataset.ndim();
+        ensure!(
+            start.len() == ndim && count.len() == ndim && stride.len() == ndim,
+            "view '{}' does not match the {}-dimensional dataset '{}'",
+            name,
+            ndim,
+            dataset.name()
+        );
+        let slices = (0..ndim)
+            .map(|i| SliceOrIndex::SliceCount {
+                start: start[i] as _,
+                step: stride[i] as _,
+                count: count[i] as _,
+                block: 1,
+            })
+            .collect::<Vec<_>>();
+        Ok(DatasetView { dataset, selection: Hyperslab::from(slices).into() })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ndarray::{s, Array3};
+
+    use crate::conventions::{VIEW_REGION_ATTR, VIEW_STRIDE_ATTR, VIEW_TARGET_ATTR};
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_views() {
+        with_tmp_path(|path| {
+            let data = Array3::from_shape_fn((6, 8, 10), |(i, j, k)| (i * 100 + j * 10 + k) as i32);
+            let file = File::create(&path).unwrap();
+            let ds = file.new_dataset_builder().with_data(&data).create("tensors/t").unwrap();
+            let selection = Selection::try_new(s![1..5;2, ..;3, 2..9;3]).unwrap();
+            ds.save_view("strided", &selection).unwrap();
+            ds.save_view_in("custom/views", "all", &Selection::All).unwrap();
+            assert!(ds.save_view("strided", &selection).is_err());
+            let points = Selection::Points(ndarray::arr2(&[[0, 0, 0]]));
+            assert!(ds.save_view("points", &points).is_err());
+            let view = file.group("views/strided").unwrap();
+            assert_eq!(view.attr(VIEW_TARGET_ATTR).unwrap().read_string().unwrap(), "/tensors/t");
+            assert_eq!(view.attr(VIEW_STRIDE_ATTR).unwrap().read_raw::<u64>().unwrap(), [2, 3, 3]);
+            file.close().unwrap();
+
+            let file = File::open(&path).unwrap();
+            let full = file.dataset("tensors/t").unwrap().read::<i32, ndarray::Ix3>().unwrap();
+            let expected = full.slice(s![1..5;2, ..;3, 2..9;3]).to_owned();
+            let view = file.open_view("strided").unwrap();
+            assert_eq!(view.dataset.name(), "/tensors/t");
+            assert_eq!(view.shape().unwrap(), [2, 3, 3]);
+            assert_eq!(view.read_dyn::<i32>().unwrap(), expected.clone().into_dyn());
+            let view = file.open_view_in("custom/views", "all").unwrap();
+            assert_eq!(view.read::<i32, ndarray::Ix3>().unwrap(), data);
+            assert!(file.open_view("missing").is_err());
+            file.close().unwrap();
+
+            // views without the optional region reference are read from the other attributes
+            let file = File::open_rw(&path).unwrap();
+            let view = file.group("views/strided").unwrap();
+            if view.attr_names().unwrap().iter().any(|attr| attr == VIEW_REGION_ATTR) {
+                view.delete_attr(VIEW_REGION_ATTR).unwrap();
+            }
+            let (dataset, selection) = file.open_view("strided").unwrap().into();
+            assert_eq!(dataset.read_slice::<i32, _, _>(selection).unwrap(), expected);
+        })
+    }
+}
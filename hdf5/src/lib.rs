@@ -49,29 +49,82 @@
 
 mod export {
     pub use crate::{
-        class::from_id,
         dim::{Dimension, Ix},
-        error::{silence_errors, Error, ErrorFrame, ErrorStack, ExpandedErrorStack, Result},
+        error::{
+            silence_errors, Error, ErrorFrame, ErrorKind, ErrorStack, ExpandedErrorStack, Result,
+        },
+        flush::{FlushDaemon, FlushHandle},
+        hl::container::{max_read_bytes, set_max_read_bytes, DEFAULT_MAX_REQUEST_SIZE},
         hl::extents::{Extent, Extents, SimpleExtents},
-        hl::selection::{Hyperslab, Selection, SliceOrIndex},
+        hl::selection::{Hyperslab, Selection, SelectionExpr, SelectionOp, SliceOrIndex},
+        hl::spec::{DatasetSpec, FillSpec},
+        hl::strings::StringTruncation,
+        hl::tree::{from_group, to_group, to_group_with, H5Value, ScalarStorage, TreeOptions},
+        hl::typed::{Dataset1D, Dataset2D, TypedDataset},
+        hl::views::DatasetView,
         hl::{
             references::{ObjectReference, ObjectReference1, ReferencedObject},
             Attribute, AttributeBuilder, AttributeBuilderData, AttributeBuilderEmpty,
-            AttributeBuilderEmptyShape, ByteReader, Container, Conversion, Dataset, DatasetBuilder,
-            DatasetBuilderData, DatasetBuilderEmpty, DatasetBuilderEmptyShape, Dataspace, Datatype,
-            File, FileBuilder, Group, LinkInfo, LinkType, Location, LocationInfo, LocationToken,
-            LocationType, Object, OpenMode, PropertyList, Reader, Writer,
+            AttributeBuilderEmptyShape, AttributeBuilderIter, BorrowedImageFile, ByteReader,
+            Container, Conversion, CopyOptions, Dataset, DatasetBuilder, DatasetBuilderData,
+            DatasetBuilderEmpty, DatasetBuilderEmptyShape, DatasetBuilderIter,
+            DatasetBuilderStrings, Dataspace, Datatype, DeterministicOrder, File, FileBuilder,
+            FlushScope, FormatInfo, Group, GroupBuilder, GroupMembers, GroupStorageInfo,
+            GroupStorageType, IterationOrder, LinkInfo, LinkType, Location, LocationInfo,
+            LocationToken, LocationType, Object, ObjectHandleKind, ObjectId, OpenMode, OpenObject,
+            OpenObjectKind, PropertyList, Reader, RepackOptions, TraversalOrder, Writer,
         },
+        sync::batch,
     };
 
-    // ObjectReference2 requires HDF5 1.12.1+ which is satisfied by our minimum requirement
-    pub use crate::hl::references::ObjectReference2;
+    // the standard references check for HDF5 1.12.1+ at runtime
+    pub use crate::hl::references::{AttributeReference, ObjectReference2, RegionReference};
 
     #[doc(hidden)]
     pub use crate::error::h5check;
 
     pub use hdf5_types::H5Type;
 
+    /// The types and traits most programs need, for importing them all at once.
+    ///
+    /// The items re-exported here are the stable surface of the crate; unlike the paths of
+    /// other items, they are kept across releases.
+    ///
+    /// ```no_run
+    /// use hdf5_rt::prelude::*;
+    ///
+    /// fn first_row(file: &File) -> Result<Vec<f64>> {
+    ///     let ds: Dataset = file.dataset("data")?;
+    ///     Ok(ds.read_slice_1d::<f64, _>(Selection::from((0, ..)))?.to_vec())
+    /// }
+    /// ```
+    pub mod prelude {
+        pub use crate::error::{Error, ErrorKind, Result};
+        pub use crate::hl::filters::Filter;
+        pub use crate::hl::references::{ObjectReference, ObjectReference1, ObjectReference2};
+        pub use crate::hl::selection::Selection;
+        pub use crate::hl::{
+            Attribute, Container, Dataset, Dataspace, Datatype, File, Group, Location,
+        };
+        pub use hdf5_types::{H5Type, TypeDescriptor};
+    }
+
+    /// Low-level items for bindings that call into the C library themselves.
+    ///
+    /// This is the supported way to load the library, query its version and convert between
+    /// objects and their raw identifiers; the `sys` module it is based on may change at any time.
+    /// Calls into the C library have to be made while holding the library lock, see [`sync`].
+    pub mod raw {
+        pub use crate::class::{from_id, into_id};
+        pub use crate::sync::sync;
+        pub use crate::sys::h5::{haddr_t, herr_t, hsize_t, hssize_t, htri_t};
+        pub use crate::sys::h5i::{hid_t, H5I_INVALID_HID};
+        pub use crate::sys::runtime::{
+            hdf5_version, hdf5_version_at_least, init, is_initialized, library_path, shutdown,
+        };
+        pub use crate::sys::{Version, LIBRARY_PATH_ENV};
+    }
+
     /// Base types and interfaces for creating compound data types.
     pub mod types {
         pub use hdf5_types::*;
@@ -79,11 +132,24 @@ mod export {
 
     /// Multi-dimensional datasets.
     pub mod dataset {
-        pub use crate::hl::chunks::ChunkInfo;
+        #[cfg(feature = "arrow")]
+        pub use crate::hl::arrow::ArrowBatches;
+        #[cfg(feature = "checksum")]
+        pub use crate::hl::checksum::ChecksumStatus;
+        pub use crate::hl::chunks::{
+            ChunkInfo, ChunkReport, CHUNK_WARN_AMPLIFICATION, WARN_CHUNKING_ENV,
+        };
+        pub use crate::hl::columns::{ErasedColumn, FromColumns};
         // NOTE: ChunkInfoRef is not available in runtime-loading mode (requires H5Dchunk_iter)
-        pub use crate::hl::dataset::{Chunk, Dataset, DatasetBuilder};
+        pub use crate::hl::dataset::{
+            Chunk, Dataset, DatasetBuilder, LayoutInfo, SpaceStatus, UsageSummary, CHUNK_MAX_SIZE,
+            DEFAULT_ITER_BATCH_SIZE,
+        };
+        pub use crate::hl::io_stats::{IoKind, IoRequest, IoStats, ThrashRisk};
         pub use crate::hl::plist::dataset_access::*;
         pub use crate::hl::plist::dataset_create::*;
+        pub use crate::hl::plist::dataset_transfer::*;
+        pub use crate::hl::row_groups::{RowGroup, RowGroupIter};
     }
 
     /// Datatype objects for defining the layout of a data element.
@@ -93,19 +159,68 @@ mod export {
 
     /// HDF5 file objects.
     pub mod file {
-        pub use crate::hl::file::{File, FileBuilder, OpenMode};
+        #[allow(deprecated)]
+        pub use crate::hl::file::FileBuilderMut;
+        pub use crate::hl::file::{File, FileBuilder, OpenMode, RepackOptions};
+        pub use crate::hl::file_image::BorrowedImageFile;
+        pub use crate::hl::file_info::{
+            FormatInfo, ObjectCounts, StorageBreakdown, StorageSummary,
+        };
         pub use crate::hl::plist::file_access::*;
         pub use crate::hl::plist::file_create::*;
     }
 
     /// Property list objects.
+    ///
+    /// Each property list has a builder whose setters consume and return it, so a property
+    /// list can be created in one expression:
+    ///
+    /// ```no_run
+    /// # fn main() -> hdf5_rt::Result<()> {
+    /// use hdf5_rt::plist::DatasetAccess;
+    ///
+    /// let dapl = DatasetAccess::build().chunk_cache(521, 1 << 20, 0.75).efile_prefix("ext").finish()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Builders are `Clone` and [`finish`](DatasetAccessBuilder::finish) only borrows them, so
+    /// a common configuration can be kept around and cloned before adding to it:
+    ///
+    /// ```no_run
+    /// # fn main() -> hdf5_rt::Result<()> {
+    /// use hdf5_rt::plist::DatasetCreate;
+    ///
+    /// let base = DatasetCreate::build().chunk((64, 64)).shuffle();
+    /// let fast = base.clone().deflate(1).finish()?;
+    /// let small = base.deflate(9).finish()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The setters of earlier releases took `&mut self`; they are still available, deprecated,
+    /// through traits implemented for `&mut` references to the builders (such as
+    /// [`FileAccessBuilderMut`]), so code chaining setters on such references keeps compiling.
+    /// A setter called as a statement on an owned builder moves it now, so `b.sec2();` has to
+    /// be replaced with `b = b.sec2();`.
     pub mod plist {
         pub use crate::hl::plist::dataset_access::{DatasetAccess, DatasetAccessBuilder};
         pub use crate::hl::plist::dataset_create::{DatasetCreate, DatasetCreateBuilder};
+        pub use crate::hl::plist::dataset_transfer::{DatasetTransfer, DatasetTransferBuilder};
         pub use crate::hl::plist::file_access::{FileAccess, FileAccessBuilder};
         pub use crate::hl::plist::file_create::{FileCreate, FileCreateBuilder};
+        pub use crate::hl::plist::group_create::{GroupCreate, GroupCreateBuilder};
+        pub use crate::hl::plist::link_access::{LinkAccess, LinkAccessBuilder};
         pub use crate::hl::plist::link_create::{LinkCreate, LinkCreateBuilder};
         pub use crate::hl::plist::object_copy::{ObjectCopy, ObjectCopyBuilder};
+        #[allow(deprecated)]
+        pub use crate::hl::plist::{
+            dataset_access::DatasetAccessBuilderMut, dataset_create::DatasetCreateBuilderMut,
+            dataset_transfer::DatasetTransferBuilderMut, file_access::FileAccessBuilderMut,
+            file_create::FileCreateBuilderMut, group_create::GroupCreateBuilderMut,
+            link_access::LinkAccessBuilderMut, link_create::LinkCreateBuilderMut,
+            object_copy::ObjectCopyBuilderMut,
+        };
         pub use crate::hl::plist::{PropertyList, PropertyListClass};
 
         /// Dataset access property lists.
@@ -116,6 +231,10 @@ mod export {
         pub mod dataset_create {
             pub use crate::hl::plist::dataset_create::*;
         }
+        /// Dataset transfer property lists.
+        pub mod dataset_transfer {
+            pub use crate::hl::plist::dataset_transfer::*;
+        }
         /// File access property lists.
         pub mod file_access {
             pub use crate::hl::plist::file_access::*;
@@ -124,6 +243,14 @@ mod export {
         pub mod file_create {
             pub use crate::hl::plist::file_create::*;
         }
+        /// Group creation property lists.
+        pub mod group_create {
+            pub use crate::hl::plist::group_create::*;
+        }
+        /// Link access property lists.
+        pub mod link_access {
+            pub use crate::hl::plist::link_access::*;
+        }
         /// Link creation property lists.
         pub mod link_create {
             pub use crate::hl::plist::link_create::*;
@@ -138,7 +265,17 @@ mod export {
 
 pub use crate::export::*;
 
-// Low-level FFI abstraction layer (for future runtime-loading support)
+/// Takes ownership of an object via its identifier, see [`raw::from_id`].
+///
+/// # Safety
+///
+/// See [`raw::from_id`].
+#[deprecated(note = "use `raw::from_id` instead")]
+pub unsafe fn from_id<T: class::ObjectClass>(id: raw::hid_t) -> Result<T> {
+    raw::from_id(id)
+}
+
+// Low-level FFI abstraction layer; its layout is not stable, downstream crates should use `raw`
 #[doc(hidden)]
 pub mod sys;
 
@@ -149,8 +286,7 @@ mod class;
 
 mod dim;
 mod error;
-#[doc(hidden)]
-pub mod globals;
+pub(crate) mod globals;
 mod handle;
 #[doc(hidden)]
 pub mod sync;
@@ -158,6 +294,34 @@ mod util;
 
 mod hl;
 
+#[cfg(feature = "conformance")]
+pub mod conformance;
+
+pub mod conventions;
+
+pub mod custom_id;
+
+pub mod diagnostics;
+
+#[cfg(feature = "test-fixtures")]
+pub mod fixtures;
+
+pub mod flush;
+pub mod netcdf_compat;
+pub mod ops;
+#[cfg(feature = "parallel-write")]
+pub mod parallel_write;
+pub mod sharding;
+pub mod staging;
+pub mod swmr;
+pub mod versioning;
+
+#[cfg(feature = "native-reader")]
+pub mod native;
+
+#[cfg(feature = "tokio")]
+pub mod task;
+
 mod internal_prelude {
     pub use libc::size_t;
     #[allow(unused_imports)]
@@ -180,11 +344,14 @@ mod internal_prelude {
         hl::plist::PropertyListClass,
         sync::sync,
         util::{
-            get_h5_str, h5_free_memory, string_from_cstr, string_from_fixed_bytes,
+            get_h5_str, h5_free_memory, h5_string, listing_capacity, name_from_cstr,
+            path_from_cstr, path_to_cstring, string_from_cstr, string_from_fixed_bytes,
             string_to_fixed_bytes, to_cstring,
         },
     };
 
+    pub(crate) use crate::error::ErrorContext;
+
     #[cfg(test)]
     pub use crate::test::{with_tmp_dir, with_tmp_file, with_tmp_path};
 }
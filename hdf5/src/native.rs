@@ -0,0 +1,572 @@
+//! Pure-Rust, read-only access to simple HDF5 files without loading the HDF5 library.
+//!
+//! This is a deliberately limited fallback for environments where no `libhdf5` can be
+//! installed (e.g. sandboxed CI smoke tests). It parses the file format directly and supports:
+//!
+//! - superblock versions 0 to 3;
+//! - groups stored as symbol tables or compact link messages (hard links only);
+//! - datasets with contiguous, compact or chunked storage without any filters, where chunks
+//!   are indexed by a version 1 B-tree, a single chunk, an implicit index or a non-paged
+//!   fixed array;
+//! - little- and big-endian integers (1, 2, 4, 8 bytes) and IEEE floats (4, 8 bytes).
+//!
+//! Anything else (compression and other filters, strings, compounds, dense link storage,
+//! soft/external links, virtual datasets, ...) is refused with an error naming the
+//! unsupported feature rather than returning wrong data. Metadata checksums are verified
+//! where the format provides them.
+//!
+//! Requires the `native-reader` crate feature.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::native::SimpleFile;
+//!
+//! let file = SimpleFile::open("data.h5")?;
+//! let ds = file.dataset("group/values")?;
+//! println!("{:?} {:?}", ds.shape(), ds.dtype());
+//! let values = ds.read::<f64>()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Debug};
+use std::mem;
+use std::path::Path;
+use std::ptr;
+use std::sync::Arc;
+
+use hdf5_types::TypeDescriptor;
+use ndarray::{ArrayD, IxDyn};
+
+use crate::dim::{alloc_buf, checked_size};
+use crate::internal_prelude::*;
+
+mod format;
+mod io;
+
+use self::format::{ChunkIndex, DatasetInfo, LinkTarget, Message, Storage};
+use self::io::Source;
+
+/// A read-only HDF5 file opened by the native reader.
+pub struct SimpleFile {
+    src: Arc<Source>,
+    root: u64,
+}
+
+impl Debug for SimpleFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SimpleFile").field("root", &self.root).finish_non_exhaustive()
+    }
+}
+
+impl SimpleFile {
+    /// Opens a file for reading, parsing its superblock.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut src = Source::open(path.as_ref())?;
+        let root = format::read_superblock(&mut src)?;
+        Ok(Self { src: Arc::new(src), root })
+    }
+
+    /// Returns the names of all links in the root group.
+    pub fn member_names(&self) -> Result<Vec<String>> {
+        let messages = format::read_object_header(&self.src, self.root)?;
+        Ok(format::group_links(&self.src, &messages)?.into_iter().map(|(name, _)| name).collect())
+    }
+
+    /// Opens a dataset by its path relative to the root group.
+    pub fn dataset(&self, name: &str) -> Result<SimpleDataset> {
+        let messages = self.resolve(name)?;
+        ensure!(format::is_dataset(&messages), "'{}' is not a dataset", name);
+        let info = format::parse_dataset(&self.src, &messages)
+            .map_err(|err| format!("unable to read dataset '{name}': {err}"))?;
+        Ok(SimpleDataset { src: Arc::clone(&self.src), name: name.to_owned(), info })
+    }
+
+    fn resolve(&self, path: &str) -> Result<Vec<Message>> {
+        let mut messages = format::read_object_header(&self.src, self.root)?;
+        for part in path.split('/').filter(|p| !p.is_empty() && *p != ".") {
+            ensure!(format::is_group(&messages), "'{}': parent of '{}' is not a group", path, part);
+            let links = format::group_links(&self.src, &messages)?;
+            let target = links.into_iter().find(|(name, _)| name == part).map(|(_, t)| t);
+            let addr = match target {
+                Some(LinkTarget::Hard(addr)) => addr,
+                Some(LinkTarget::Soft(dest)) => {
+                    fail!("'{}': soft link '{}' -> '{}' is not supported", path, part, dest)
+                }
+                Some(LinkTarget::External) => {
+                    fail!("'{}': external link '{}' is not supported", path, part)
+                }
+                Some(LinkTarget::Other(tp)) => {
+                    fail!("'{}': link '{}' of type {} is not supported", path, part, tp)
+                }
+                None => fail!("'{}': object '{}' doesn't exist", path, part),
+            };
+            messages = format::read_object_header(&self.src, addr)?;
+        }
+        Ok(messages)
+    }
+}
+
+/// A dataset of a [`SimpleFile`].
+pub struct SimpleDataset {
+    src: Arc<Source>,
+    name: String,
+    info: DatasetInfo,
+}
+
+impl Debug for SimpleDataset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SimpleDataset")
+            .field("name", &self.name)
+            .field("shape", &self.info.shape)
+            .field("dtype", &self.info.dtype)
+            .finish()
+    }
+}
+
+impl SimpleDataset {
+    /// Returns the path the dataset was opened with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the shape of the dataset (empty for scalars).
+    pub fn shape(&self) -> &[Ix] {
+        &self.info.shape
+    }
+
+    /// Returns the maximum shape of the dataset.
+    pub fn maxshape(&self) -> &[Ix] {
+        &self.info.maxdims
+    }
+
+    /// Returns the number of dimensions.
+    pub fn ndim(&self) -> usize {
+        self.info.shape.len()
+    }
+
+    /// Returns the total number of elements.
+    pub fn size(&self) -> usize {
+        self.info.shape.iter().product()
+    }
+
+    /// Returns the element type as stored in the file (in native byte order).
+    pub fn dtype(&self) -> &TypeDescriptor {
+        &self.info.dtype
+    }
+
+    /// Returns `true` if the elements are stored as big-endian.
+    pub fn is_big_endian(&self) -> bool {
+        self.info.big_endian
+    }
+
+    /// Reads the whole dataset into a flat vector in C order.
+    ///
+    /// The requested type must match the stored type exactly; no conversions are performed.
+    pub fn read_raw<T: H5Type>(&self) -> Result<Vec<T>> {
+        let desc = T::type_descriptor();
+        ensure!(
+            desc == self.info.dtype && mem::size_of::<T>() == self.info.elem_size,
+            "'{}': requested type {} does not match stored type {}",
+            self.name,
+            desc,
+            self.info.dtype
+        );
+        let mut bytes = self.read_bytes().map_err(|err| format!("'{}': {}", self.name, err))?;
+        if self.info.big_endian != cfg!(target_endian = "big") {
+            bytes.chunks_exact_mut(self.info.elem_size).for_each(<[u8]>::reverse);
+        }
+        let len = bytes.len() / self.info.elem_size;
+        let mut out = Vec::<T>::with_capacity(len);
+        // SAFETY: T is a plain numeric type of `elem_size` bytes (checked above)
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), out.as_mut_ptr().cast::<u8>(), bytes.len());
+            out.set_len(len);
+        }
+        Ok(out)
+    }
+
+    /// Reads the whole dataset into an n-dimensional array.
+    pub fn read<T: H5Type>(&self) -> Result<ArrayD<T>> {
+        let data = self.read_raw()?;
+        Ok(ArrayD::from_shape_vec(IxDyn(&self.info.shape), data)?)
+    }
+
+    fn read_bytes(&self) -> Result<Vec<u8>> {
+        let info = &self.info;
+        let total = info
+            .shape
+            .iter()
+            .try_fold(info.elem_size, |acc, &d| acc.checked_mul(d))
+            .ok_or("dataset size overflows usize")?;
+        let mut out = match &info.fill {
+            Some(fill) => fill.repeat(total / info.elem_size),
+            None => vec![0; total],
+        };
+        match &info.storage {
+            Storage::Compact(data) => {
+                ensure!(data.len() == total, "compact data size {} != {}", data.len(), total);
+                out.copy_from_slice(data);
+            }
+            Storage::Contiguous { addr: None, .. } => {}
+            Storage::Contiguous { addr: Some(addr), size } => {
+                ensure!(*size as usize >= total, "contiguous storage size {} < {}", size, total);
+                out = self.src.read(*addr, total)?;
+            }
+            Storage::Chunked { chunk, index } => self.read_chunks(chunk, index, &mut out)?,
+        }
+        Ok(out)
+    }
+
+    fn read_chunks(&self, chunk: &[usize], index: &ChunkIndex, out: &mut [u8]) -> Result<()> {
+        let info = &self.info;
+        ensure!(chunk.iter().all(|&c| c > 0), "invalid chunk shape {:?}", chunk);
+        let chunk_bytes = chunk
+            .iter()
+            .try_fold(info.elem_size, |acc, &c| acc.checked_mul(c))
+            .ok_or("chunk size overflows usize")?;
+        let mut copy = |offset: &[usize], addr: u64| -> Result<()> {
+            let data = self.src.read(addr, chunk_bytes)?;
+            copy_chunk(out, &info.shape, &data, chunk, offset, info.elem_size);
+            Ok(())
+        };
+        match *index {
+            ChunkIndex::BTreeV1(None)
+            | ChunkIndex::Single(None)
+            | ChunkIndex::Implicit(None)
+            | ChunkIndex::FixedArray(None) => {}
+            ChunkIndex::BTreeV1(Some(root)) => {
+                let rank = chunk.len();
+                let key_size = 8 + 8 * (rank + 1);
+                format::visit_btree_v1(&self.src, root, 1, key_size, 0, &mut |key, addr| {
+                    let mut c = self.src.cursor(key, "chunk key");
+                    let size = c.u32()? as usize;
+                    let mask = c.u32()?;
+                    ensure!(mask == 0, "chunk with filter mask {:#x} is not supported", mask);
+                    ensure!(size == chunk_bytes, "chunk size {} != {}", size, chunk_bytes);
+                    let offset = (0..rank)
+                        .map(|_| c.u64().map(|o| o as usize))
+                        .collect::<Result<Vec<_>>>()?;
+                    copy(&offset, addr)
+                })?;
+            }
+            ChunkIndex::Single(Some(addr)) => copy(&vec![0; chunk.len()], addr)?,
+            ChunkIndex::Implicit(Some(base)) => {
+                for (offset, linear) in chunk_grid(&info.shape, &info.maxdims, chunk)? {
+                    let addr = (linear as u64)
+                        .checked_mul(chunk_bytes as u64)
+                        .and_then(|pos| base.checked_add(pos))
+                        .ok_or_else(|| format!("address of chunk {linear} overflows"))?;
+                    copy(&offset, addr)?;
+                }
+            }
+            ChunkIndex::FixedArray(Some(header)) => {
+                let addrs = format::fixed_array_addresses(&self.src, header)?;
+                for (offset, linear) in chunk_grid(&info.shape, &info.maxdims, chunk)? {
+                    match addrs.get(linear) {
+                        Some(Some(addr)) => copy(&offset, *addr)?,
+                        Some(None) => {}
+                        None => fail!("chunk {} missing from fixed array index", linear),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Enumerates the element offsets of all chunks covering `shape`, together with each chunk's
+/// linear index in the chunk grid spanned by `maxdims`, which must be at least `shape`.
+fn chunk_grid(
+    shape: &[usize],
+    maxdims: &[usize],
+    chunk: &[usize],
+) -> Result<Vec<(Vec<usize>, usize)>> {
+    ensure!(
+        maxdims.iter().zip(shape).all(|(m, s)| m >= s),
+        "maximum dimensions {:?} are smaller than the shape {:?}",
+        maxdims,
+        shape
+    );
+    let counts: Vec<usize> = shape.iter().zip(chunk).map(|(s, c)| s.div_ceil(*c)).collect();
+    let max_counts: Vec<usize> = maxdims.iter().zip(chunk).map(|(s, c)| s.div_ceil(*c)).collect();
+    let total = checked_size(&counts)?;
+    let mut out = alloc_buf(total)?;
+    let mut scaled = vec![0; shape.len()];
+    for _ in 0..total {
+        let offset = scaled.iter().zip(chunk).map(|(s, c)| s * c).collect();
+        let linear = scaled
+            .iter()
+            .zip(&max_counts)
+            .try_fold(0_usize, |acc, (&s, &n)| acc.checked_mul(n)?.checked_add(s))
+            .ok_or("chunk index overflows usize")?;
+        out.push((offset, linear));
+        for d in (0..scaled.len()).rev() {
+            scaled[d] += 1;
+            if scaled[d] < counts[d] {
+                break;
+            }
+            scaled[d] = 0;
+        }
+    }
+    Ok(out)
+}
+
+/// Copies the part of a chunk located at `offset` that lies within `shape` into `out`.
+fn copy_chunk(
+    out: &mut [u8],
+    shape: &[usize],
+    data: &[u8],
+    chunk: &[usize],
+    offset: &[usize],
+    elem: usize,
+) {
+    let rank = shape.len();
+    let extent: Vec<usize> =
+        (0..rank).map(|i| chunk[i].min(shape[i].saturating_sub(offset[i]))).collect();
+    if rank == 0 || extent.contains(&0) {
+        return;
+    }
+    let run = extent[rank - 1] * elem;
+    let mut idx = vec![0; rank - 1];
+    loop {
+        let (mut src, mut dst) = (0, 0);
+        for i in 0..rank {
+            let ci = idx.get(i).copied().unwrap_or(0);
+            src = src * chunk[i] + ci;
+            dst = dst * shape[i] + offset[i] + ci;
+        }
+        let (src, dst) = (src * elem, dst * elem);
+        out[dst..dst + run].copy_from_slice(&data[src..src + run]);
+        let mut d = rank - 1;
+        loop {
+            if d == 0 {
+                return;
+            }
+            d -= 1;
+            idx[d] += 1;
+            if idx[d] < extent[d] {
+                break;
+            }
+            idx[d] = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::io::lookup3;
+    use super::{chunk_grid, SimpleFile};
+    use crate::internal_prelude::*;
+
+    const UNDEF: [u8; 8] = [0xff; 8];
+
+    fn le(v: u64, n: usize) -> Vec<u8> {
+        v.to_le_bytes()[..n].to_vec()
+    }
+
+    fn ohdr(messages: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        let body: Vec<u8> = messages
+            .iter()
+            .flat_map(|(kind, data)| {
+                let mut msg = vec![*kind];
+                msg.extend(le(data.len() as _, 2));
+                msg.push(0);
+                msg.extend(data);
+                msg
+            })
+            .collect();
+        let mut out = b"OHDR\x02\x02".to_vec();
+        out.extend(le(body.len() as _, 4));
+        out.extend(body);
+        out.extend(lookup3(&out).to_le_bytes());
+        out
+    }
+
+    fn dataspace(dims: &[u64]) -> (u8, Vec<u8>) {
+        let mut data = vec![2, dims.len() as u8, 0, if dims.is_empty() { 0 } else { 1 }];
+        dims.iter().for_each(|&d| data.extend(le(d, 8)));
+        (0x01, data)
+    }
+
+    fn int_type(size: u32, signed: bool) -> (u8, Vec<u8>) {
+        let mut data = vec![0x10, if signed { 0x08 } else { 0 }, 0, 0];
+        data.extend(le(size as _, 4));
+        data.extend(le(0, 2));
+        data.extend(le(8 * size as u64, 2));
+        (0x03, data)
+    }
+
+    fn f64_be_type() -> (u8, Vec<u8>) {
+        let mut data = vec![0x11, 0x21, 63, 0];
+        data.extend(le(8, 4));
+        data.extend(le(0, 2));
+        data.extend(le(64, 2));
+        data.extend([52, 11, 0, 52]);
+        data.extend(le(1023, 4));
+        (0x03, data)
+    }
+
+    fn link(name: &str, addr: u64) -> (u8, Vec<u8>) {
+        let mut data = vec![1, 0, name.len() as u8];
+        data.extend(name.as_bytes());
+        data.extend(le(addr, 8));
+        (0x06, data)
+    }
+
+    fn link_info() -> (u8, Vec<u8>) {
+        let mut data = vec![0, 0];
+        data.extend(UNDEF);
+        data.extend(UNDEF);
+        (0x02, data)
+    }
+
+    /// Builds a file with superblock v2 and version 2 object headers.
+    fn build_file() -> Vec<u8> {
+        let mut file = vec![0; 48];
+        let push = |file: &mut Vec<u8>, bytes: Vec<u8>| {
+            let addr = file.len() as u64;
+            file.extend(bytes);
+            addr
+        };
+
+        let ints: Vec<u8> = (0..6_i32).flat_map(|v| (v * 10).to_le_bytes()).collect();
+        let ints_addr = push(&mut file, ints);
+        let mut layout = vec![3, 1];
+        layout.extend(le(ints_addr, 8));
+        layout.extend(le(24, 8));
+        let ints = ohdr(&[dataspace(&[2, 3]), int_type(4, true), (0x08, layout)]);
+        let ints = push(&mut file, ints);
+
+        let mut layout = vec![3, 0];
+        layout.extend(le(8, 2));
+        layout.extend(1.5_f64.to_be_bytes());
+        let scalar = ohdr(&[dataspace(&[]), f64_be_type(), (0x08, layout)]);
+        let scalar = push(&mut file, scalar);
+
+        // 5x3 u16 array in 2x2 chunks, implicit index (chunks stored in linear order)
+        let mut chunks = vec![];
+        for (ci, cj) in [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)] {
+            for i in 0..2 {
+                for j in 0..2 {
+                    let v = ((ci * 2 + i) * 3 + cj * 2 + j) as u16;
+                    chunks.extend(v.to_le_bytes());
+                }
+            }
+        }
+        let chunks_addr = push(&mut file, chunks);
+        let mut layout = vec![4, 2, 0, 3, 8];
+        [2, 2, 2].iter().for_each(|&d| layout.extend(le(d, 8)));
+        layout.push(2);
+        layout.extend(le(chunks_addr, 8));
+        let chunked = ohdr(&[dataspace(&[5, 3]), int_type(2, false), (0x08, layout)]);
+        let chunked = push(&mut file, chunked);
+
+        let mut layout = vec![3, 1];
+        layout.extend(UNDEF);
+        layout.extend(le(0, 8));
+        let pipeline = (0x0b, vec![2, 1, 1, 0, 0, 0, 0, 0]);
+        let filtered =
+            ohdr(&[dataspace(&[4]), int_type(4, true), pipeline, (0x08, layout.clone())]);
+        let filtered = push(&mut file, filtered);
+
+        let string_type = (0x03, vec![0x13, 0, 0, 0, 8, 0, 0, 0]);
+        let strings = ohdr(&[dataspace(&[4]), string_type, (0x08, layout)]);
+        let strings = push(&mut file, strings);
+
+        let group = ohdr(&[link_info(), link("ints", ints)]);
+        let group = push(&mut file, group);
+
+        let root = ohdr(&[
+            link_info(),
+            link("ints", ints),
+            link("scalar", scalar),
+            link("chunked", chunked),
+            link("filtered", filtered),
+            link("strings", strings),
+            link("g", group),
+        ]);
+        let root = push(&mut file, root);
+
+        let mut sb = b"\x89HDF\r\n\x1a\n\x02\x08\x08\x00".to_vec();
+        sb.extend(le(0, 8));
+        sb.extend(UNDEF);
+        sb.extend(le(file.len() as _, 8));
+        sb.extend(le(root, 8));
+        sb.extend(lookup3(&sb).to_le_bytes());
+        file[..48].copy_from_slice(&sb);
+        file
+    }
+
+    #[test]
+    pub fn test_lookup3() {
+        assert_eq!(lookup3(b""), 0xdeadbeef);
+        assert_eq!(lookup3(b"Four score and seven years ago"), 0x17770551);
+    }
+
+    #[test]
+    pub fn test_chunk_grid_overflow() {
+        let grid = chunk_grid(&[3, 2], &[4, 2], &[2, 1]).unwrap();
+        let linear: Vec<_> = grid.iter().map(|(_, linear)| *linear).collect();
+        assert_eq!(linear, [0, 1, 2, 3]);
+        assert_eq!(grid[3].0, [2, 1]);
+
+        let max = usize::MAX;
+        assert_err!(chunk_grid(&[2, 2], &[max, max], &[1, 1]), "chunk index overflows usize");
+        assert_err!(chunk_grid(&[max / 2, 4], &[max / 2, 4], &[1, 1]), "overflows");
+        assert_err!(chunk_grid(&[4], &[2], &[1]), "smaller than the shape");
+    }
+
+    #[test]
+    pub fn test_read_v2_file() {
+        with_tmp_path(|path| {
+            std::fs::write(&path, build_file()).unwrap();
+            let file = SimpleFile::open(&path).unwrap();
+            assert_eq!(
+                file.member_names().unwrap(),
+                ["ints", "scalar", "chunked", "filtered", "strings", "g"]
+            );
+
+            let ints = file.dataset("ints").unwrap();
+            assert_eq!(ints.shape(), [2, 3]);
+            assert_eq!(ints.dtype(), &i32::type_descriptor());
+            assert_eq!(ints.read_raw::<i32>().unwrap(), [0, 10, 20, 30, 40, 50]);
+            assert_eq!(file.dataset("/g/ints").unwrap().read::<i32>().unwrap()[[1, 2]], 50);
+            assert_err!(
+                ints.read_raw::<u32>(),
+                "requested type uint32 does not match stored type int32"
+            );
+
+            let scalar = file.dataset("scalar").unwrap();
+            assert!(scalar.shape().is_empty());
+            assert!(scalar.is_big_endian());
+            assert_eq!(scalar.read::<f64>().unwrap().iter().copied().collect::<Vec<_>>(), [1.5]);
+
+            let chunked = file.dataset("chunked").unwrap().read::<u16>().unwrap();
+            let expected = ndarray::Array::from_shape_fn((5, 3), |(i, j)| (i * 3 + j) as u16);
+            assert_eq!(chunked, expected.into_dyn());
+
+            assert_err!(file.dataset("filtered"), "datasets with filters are not supported");
+            assert_err!(file.dataset("strings"), "string datatypes are not supported");
+            assert_err!(file.dataset("g"), "'g' is not a dataset");
+            assert_err!(file.dataset("missing"), "object 'missing' doesn't exist");
+        })
+    }
+
+    #[test]
+    pub fn test_corrupt_file() {
+        with_tmp_path(|path| {
+            let mut bytes = build_file();
+            let len = bytes.len();
+            bytes[len - 10] ^= 0xff; // inside the root object header
+            std::fs::write(&path, &bytes).unwrap();
+            let file = SimpleFile::open(&path).unwrap();
+            assert_err!(file.member_names(), "checksum mismatch in object header");
+
+            std::fs::write(&path, b"not an hdf5 file").unwrap();
+            assert_err!(SimpleFile::open(&path), "no HDF5 superblock found");
+        })
+    }
+}
@@ -0,0 +1,652 @@
+use std::collections::VecDeque;
+
+use hdf5_types::{FloatSize, IntSize, TypeDescriptor};
+
+use super::io::{verify_checksum, Source};
+use crate::internal_prelude::*;
+
+const SUPERBLOCK_SIGNATURE: &[u8; 8] = b"\x89HDF\r\n\x1a\n";
+
+/// Upper bound on continuation blocks / B-tree depth to guard against corrupt files.
+const MAX_BLOCKS: usize = 4096;
+const MAX_BTREE_DEPTH: usize = 64;
+
+const MSG_NIL: u16 = 0x00;
+const MSG_DATASPACE: u16 = 0x01;
+const MSG_LINK_INFO: u16 = 0x02;
+const MSG_DATATYPE: u16 = 0x03;
+const MSG_FILL_VALUE_OLD: u16 = 0x04;
+const MSG_FILL_VALUE: u16 = 0x05;
+const MSG_LINK: u16 = 0x06;
+const MSG_EXTERNAL_FILES: u16 = 0x07;
+const MSG_LAYOUT: u16 = 0x08;
+const MSG_FILTER_PIPELINE: u16 = 0x0b;
+const MSG_CONTINUATION: u16 = 0x10;
+const MSG_SYMBOL_TABLE: u16 = 0x11;
+const MSG_LAST_KNOWN: u16 = 0x18;
+
+const MSG_FLAG_SHARED: u8 = 0x02;
+const MSG_FLAG_FAIL_IF_UNKNOWN: u8 = 0x80;
+
+/// Locates and parses the superblock, configuring the source; returns the root object address.
+pub(super) fn read_superblock(src: &mut Source) -> Result<u64> {
+    let mut pos = 0;
+    let start = loop {
+        ensure!(pos + 8 <= src.len(), "no HDF5 superblock found (not an HDF5 file?)");
+        if src.read_abs(pos, 8)? == SUPERBLOCK_SIGNATURE {
+            break pos;
+        }
+        pos = if pos == 0 { 512 } else { pos * 2 };
+    };
+    let buf = src.read_abs(start, (src.len() - start).min(128) as usize)?;
+    let mut c = src.cursor(&buf, "superblock");
+    c.skip(8)?;
+    let version = c.u8()?;
+    let (offset_size, length_size) = match version {
+        0 | 1 => {
+            c.skip(4)?; // free-space, root group and shared header versions, reserved
+            let sizes = (c.u8()?, c.u8()?);
+            c.skip(1 + 4 + 4)?; // reserved, group K values, consistency flags
+            if version == 1 {
+                c.skip(4)?; // indexed storage K, reserved
+            }
+            sizes
+        }
+        2 | 3 => {
+            let sizes = (c.u8()?, c.u8()?);
+            c.skip(1)?; // consistency flags
+            sizes
+        }
+        _ => fail!("superblock version {} is not supported by the native reader", version),
+    };
+    for size in [offset_size, length_size] {
+        ensure!(matches!(size, 2 | 4 | 8), "invalid offset/length size {} in superblock", size);
+    }
+    src.offset_size = offset_size;
+    src.length_size = length_size;
+    let pos0 = c.pos();
+    let mut c = src.cursor(&buf[pos0..], "superblock");
+    let base = c.addr()?.unwrap_or(0);
+    let root = if version < 2 {
+        c.addr()?; // free-space info
+        c.addr()?; // end of file
+        ensure!(
+            c.addr()?.is_none(),
+            "files with a driver info block (family/multi drivers) are not supported"
+        );
+        c.offset()?; // root link name offset
+        c.addr()?
+    } else {
+        c.addr()?; // superblock extension
+        c.addr()?; // end of file
+        let root = c.addr()?;
+        let end = pos0 + c.pos() + 4;
+        verify_checksum(&buf[..end], "superblock")?;
+        root
+    };
+    src.base = base;
+    root.ok_or_else(|| "superblock has no root group address".into())
+}
+
+/// A raw object header message.
+pub(super) struct Message {
+    pub kind: u16,
+    pub flags: u8,
+    pub data: Vec<u8>,
+}
+
+impl Message {
+    fn ensure_unshared(&self, what: &str) -> Result<()> {
+        ensure!(
+            self.flags & MSG_FLAG_SHARED == 0,
+            "shared {} messages are not supported by the native reader",
+            what
+        );
+        Ok(())
+    }
+}
+
+/// Reads all messages of an object header, following continuation blocks.
+pub(super) fn read_object_header(src: &Source, addr: u64) -> Result<Vec<Message>> {
+    let prefix = src.read_upto(addr, 40)?;
+    ensure!(!prefix.is_empty(), "object header address {} is beyond the end of file", addr);
+    if prefix.starts_with(b"OHDR") {
+        read_object_header_v2(src, addr, &prefix)
+    } else {
+        read_object_header_v1(src, addr, &prefix)
+    }
+}
+
+fn read_object_header_v1(src: &Source, addr: u64, prefix: &[u8]) -> Result<Vec<Message>> {
+    let mut c = src.cursor(prefix, "object header");
+    let version = c.u8()?;
+    ensure!(version == 1, "object header version {} at address {} is not supported", version, addr);
+    c.skip(1 + 2 + 4)?; // reserved, number of messages, reference count
+    let size = c.u32()?;
+    let mut blocks = VecDeque::from([(addr + 16, u64::from(size))]);
+    let mut messages = vec![];
+    let mut nblocks = 0;
+    while let Some((block_addr, block_len)) = blocks.pop_front() {
+        nblocks += 1;
+        ensure!(nblocks <= MAX_BLOCKS, "too many object header continuation blocks");
+        let block = src.read(block_addr, block_len as usize)?;
+        let mut c = src.cursor(&block, "object header message");
+        while c.remaining() >= 8 {
+            let kind = c.u16()?;
+            let size = c.u16()? as usize;
+            let flags = c.u8()?;
+            c.skip(3)?;
+            let data = c.bytes(size)?;
+            push_message(src, &mut messages, &mut blocks, kind, flags, data)?;
+        }
+    }
+    Ok(messages)
+}
+
+fn read_object_header_v2(src: &Source, addr: u64, prefix: &[u8]) -> Result<Vec<Message>> {
+    let mut c = src.cursor(prefix, "object header");
+    c.skip(4)?;
+    let version = c.u8()?;
+    ensure!(version == 2, "object header version {} at address {} is not supported", version, addr);
+    let flags = c.u8()?;
+    if flags & 0x20 != 0 {
+        c.skip(16)?; // access/modification/change/birth times
+    }
+    if flags & 0x10 != 0 {
+        c.skip(4)?; // attribute phase change values
+    }
+    let size = c.uint(1 << (flags & 0x03))? as usize;
+    let start = c.pos();
+    let crt_order = flags & 0x04 != 0;
+
+    let chunk = src.read(addr, start + size + 4)?;
+    verify_checksum(&chunk, "object header")?;
+    let mut blocks = VecDeque::new();
+    let mut messages = vec![];
+    parse_messages_v2(src, &chunk[start..start + size], crt_order, &mut messages, &mut blocks)?;
+    let mut nblocks = 0;
+    while let Some((block_addr, block_len)) = blocks.pop_front() {
+        nblocks += 1;
+        ensure!(nblocks <= MAX_BLOCKS, "too many object header continuation blocks");
+        let block = src.read(block_addr, block_len as usize)?;
+        verify_checksum(&block, "object header continuation block")?;
+        src.cursor(&block, "object header continuation block").expect_signature(b"OCHK")?;
+        let body = &block[4..block.len() - 4];
+        parse_messages_v2(src, body, crt_order, &mut messages, &mut blocks)?;
+    }
+    Ok(messages)
+}
+
+fn parse_messages_v2(
+    src: &Source,
+    body: &[u8],
+    crt_order: bool,
+    messages: &mut Vec<Message>,
+    blocks: &mut VecDeque<(u64, u64)>,
+) -> Result<()> {
+    let header_len = if crt_order { 6 } else { 4 };
+    let mut c = src.cursor(body, "object header message");
+    while c.remaining() >= header_len {
+        let kind = u16::from(c.u8()?);
+        let size = c.u16()? as usize;
+        let flags = c.u8()?;
+        if crt_order {
+            c.skip(2)?;
+        }
+        let data = c.bytes(size)?;
+        push_message(src, messages, blocks, kind, flags, data)?;
+    }
+    Ok(())
+}
+
+fn push_message(
+    src: &Source,
+    messages: &mut Vec<Message>,
+    blocks: &mut VecDeque<(u64, u64)>,
+    kind: u16,
+    flags: u8,
+    data: &[u8],
+) -> Result<()> {
+    match kind {
+        MSG_NIL => {}
+        MSG_CONTINUATION => {
+            let mut c = src.cursor(data, "continuation message");
+            let addr = c.addr()?.ok_or("continuation message with undefined address")?;
+            blocks.push_back((addr, c.length()?));
+        }
+        _ => {
+            ensure!(
+                kind <= MSG_LAST_KNOWN || flags & MSG_FLAG_FAIL_IF_UNKNOWN == 0,
+                "object header contains unknown message type {:#06x} marked as required",
+                kind
+            );
+            messages.push(Message { kind, flags, data: data.to_vec() });
+        }
+    }
+    Ok(())
+}
+
+fn find_message(messages: &[Message], kind: u16) -> Option<&Message> {
+    messages.iter().find(|msg| msg.kind == kind)
+}
+
+/// Target of a link inside a group.
+pub(super) enum LinkTarget {
+    Hard(u64),
+    Soft(String),
+    External,
+    Other(u8),
+}
+
+/// Returns `true` if the object header describes a group.
+pub(super) fn is_group(messages: &[Message]) -> bool {
+    messages.iter().any(|m| matches!(m.kind, MSG_SYMBOL_TABLE | MSG_LINK_INFO | MSG_LINK))
+}
+
+/// Returns `true` if the object header describes a dataset.
+pub(super) fn is_dataset(messages: &[Message]) -> bool {
+    find_message(messages, MSG_LAYOUT).is_some()
+}
+
+/// Lists all links of a group, either from a symbol table or from compact link messages.
+pub(super) fn group_links(src: &Source, messages: &[Message]) -> Result<Vec<(String, LinkTarget)>> {
+    if let Some(msg) = find_message(messages, MSG_SYMBOL_TABLE) {
+        let mut c = src.cursor(&msg.data, "symbol table message");
+        let btree = c.addr()?.ok_or("symbol table message without B-tree address")?;
+        let heap = c.addr()?.ok_or("symbol table message without local heap address")?;
+        return symbol_table_links(src, btree, heap);
+    }
+    if let Some(msg) = find_message(messages, MSG_LINK_INFO) {
+        let mut c = src.cursor(&msg.data, "link info message");
+        c.skip(1)?; // version
+        let flags = c.u8()?;
+        if flags & 0x01 != 0 {
+            c.skip(8)?; // maximum creation index
+        }
+        ensure!(
+            c.addr()?.is_none(),
+            "groups with dense link storage (fractal heap) are not supported by the native reader"
+        );
+    }
+    messages.iter().filter(|msg| msg.kind == MSG_LINK).map(|msg| parse_link(src, msg)).collect()
+}
+
+fn parse_link(src: &Source, msg: &Message) -> Result<(String, LinkTarget)> {
+    let mut c = src.cursor(&msg.data, "link message");
+    let version = c.u8()?;
+    ensure!(version == 1, "link message version {} is not supported", version);
+    let flags = c.u8()?;
+    let link_type = if flags & 0x08 != 0 { c.u8()? } else { 0 };
+    if flags & 0x04 != 0 {
+        c.skip(8)?; // creation order
+    }
+    if flags & 0x10 != 0 {
+        c.skip(1)?; // character set
+    }
+    let name_len = c.uint(1 << (flags & 0x03))? as usize;
+    let name = String::from_utf8_lossy(c.bytes(name_len)?).into_owned();
+    let target = match link_type {
+        0 => LinkTarget::Hard(c.addr()?.ok_or("hard link with undefined address")?),
+        1 => {
+            let len = c.u16()? as usize;
+            LinkTarget::Soft(String::from_utf8_lossy(c.bytes(len)?).into_owned())
+        }
+        64 => LinkTarget::External,
+        other => LinkTarget::Other(other),
+    };
+    Ok((name, target))
+}
+
+fn symbol_table_links(src: &Source, btree: u64, heap: u64) -> Result<Vec<(String, LinkTarget)>> {
+    let heap = read_local_heap(src, heap)?;
+    let mut links = vec![];
+    let key_size = src.length_size as usize;
+    visit_btree_v1(src, btree, 0, key_size, 0, &mut |_, snod| {
+        let entry_size = 2 * src.offset_size as usize + 24;
+        let header = src.read(snod, 8)?;
+        let mut c = src.cursor(&header, "symbol table node");
+        c.expect_signature(b"SNOD")?;
+        c.skip(2)?; // version, reserved
+        let count = c.u16()? as usize;
+        let body = src.read(snod + 8, count * entry_size)?;
+        let mut c = src.cursor(&body, "symbol table entry");
+        for _ in 0..count {
+            let name_offset = c.offset()? as usize;
+            let addr = c.addr()?.ok_or("symbol table entry with undefined address")?;
+            c.skip(24)?; // cache type, reserved, scratch-pad
+            ensure!(
+                name_offset < heap.len(),
+                "link name offset {} outside local heap",
+                name_offset
+            );
+            let name = &heap[name_offset..];
+            let end = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+            links
+                .push((String::from_utf8_lossy(&name[..end]).into_owned(), LinkTarget::Hard(addr)));
+        }
+        Ok(())
+    })?;
+    Ok(links)
+}
+
+fn read_local_heap(src: &Source, addr: u64) -> Result<Vec<u8>> {
+    let header = src.read(addr, 8 + 2 * src.length_size as usize + src.offset_size as usize)?;
+    let mut c = src.cursor(&header, "local heap");
+    c.expect_signature(b"HEAP")?;
+    c.skip(4)?; // version, reserved
+    let size = c.length()?;
+    c.length()?; // free list offset
+    let data = c.addr()?.ok_or("local heap without data segment")?;
+    src.read(data, size as usize)
+}
+
+/// Visits all leaf entries (left key, child address) of a version 1 B-tree.
+pub(super) fn visit_btree_v1(
+    src: &Source,
+    addr: u64,
+    node_type: u8,
+    key_size: usize,
+    depth: usize,
+    visit: &mut dyn FnMut(&[u8], u64) -> Result<()>,
+) -> Result<()> {
+    ensure!(depth <= MAX_BTREE_DEPTH, "B-tree is too deep (corrupt file?)");
+    let offset_size = src.offset_size as usize;
+    let header = src.read(addr, 8 + 2 * offset_size)?;
+    let mut c = src.cursor(&header, "B-tree node");
+    c.expect_signature(b"TREE")?;
+    let found_type = c.u8()?;
+    ensure!(found_type == node_type, "unexpected B-tree node type {}", found_type);
+    let level = c.u8()?;
+    let entries = c.u16()? as usize;
+    let body_len = (entries + 1) * key_size + entries * offset_size;
+    let body = src.read(addr + header.len() as u64, body_len)?;
+    let mut c = src.cursor(&body, "B-tree node");
+    for _ in 0..entries {
+        let key = c.bytes(key_size)?;
+        let child = c.addr()?.ok_or("B-tree node with undefined child address")?;
+        if level == 0 {
+            visit(key, child)?;
+        } else {
+            visit_btree_v1(src, child, node_type, key_size, depth + 1, visit)?;
+        }
+    }
+    Ok(())
+}
+
+/// How the raw data of a dataset is stored.
+pub(super) enum Storage {
+    Compact(Vec<u8>),
+    Contiguous { addr: Option<u64>, size: u64 },
+    Chunked { chunk: Vec<usize>, index: ChunkIndex },
+}
+
+/// Chunk index variants supported by the native reader.
+pub(super) enum ChunkIndex {
+    BTreeV1(Option<u64>),
+    Single(Option<u64>),
+    Implicit(Option<u64>),
+    FixedArray(Option<u64>),
+}
+
+/// Everything needed to read a dataset's raw data.
+pub(super) struct DatasetInfo {
+    pub shape: Vec<usize>,
+    pub maxdims: Vec<usize>,
+    pub dtype: TypeDescriptor,
+    pub elem_size: usize,
+    pub big_endian: bool,
+    pub storage: Storage,
+    pub fill: Option<Vec<u8>>,
+}
+
+pub(super) fn parse_dataset(src: &Source, messages: &[Message]) -> Result<DatasetInfo> {
+    if let Some(msg) = find_message(messages, MSG_FILTER_PIPELINE) {
+        let nfilters = msg.data.get(1).copied().unwrap_or(0);
+        ensure!(
+            nfilters == 0,
+            "datasets with filters are not supported by the native reader (found {} filter(s))",
+            nfilters
+        );
+    }
+    ensure!(
+        find_message(messages, MSG_EXTERNAL_FILES).is_none(),
+        "datasets with external storage are not supported by the native reader"
+    );
+    let space = find_message(messages, MSG_DATASPACE).ok_or("dataset has no dataspace message")?;
+    space.ensure_unshared("dataspace")?;
+    let (shape, maxdims) = parse_dataspace(src, &space.data)?;
+    let dtype = find_message(messages, MSG_DATATYPE).ok_or("dataset has no datatype message")?;
+    dtype.ensure_unshared("datatype (committed datatypes)")?;
+    let (dtype, elem_size, big_endian) = parse_datatype(src, &dtype.data)?;
+    let layout = find_message(messages, MSG_LAYOUT).ok_or("dataset has no layout message")?;
+    layout.ensure_unshared("layout")?;
+    let storage = parse_layout(src, &layout.data, shape.len(), elem_size)?;
+    let fill = parse_fill_value(src, messages, elem_size)?;
+    Ok(DatasetInfo { shape, maxdims, dtype, elem_size, big_endian, storage, fill })
+}
+
+fn parse_dataspace(src: &Source, data: &[u8]) -> Result<(Vec<usize>, Vec<usize>)> {
+    let mut c = src.cursor(data, "dataspace message");
+    let version = c.u8()?;
+    let ndims = c.u8()? as usize;
+    let flags = c.u8()?;
+    match version {
+        1 => c.skip(5)?,
+        2 => match c.u8()? {
+            0 | 1 => {}
+            2 => fail!("null dataspaces are not supported by the native reader"),
+            other => fail!("unknown dataspace type {}", other),
+        },
+        _ => fail!("dataspace message version {} is not supported", version),
+    }
+    let mut read_dims = || -> Result<Vec<usize>> {
+        (0..ndims)
+            .map(|_| {
+                let dim = c.length()?;
+                usize::try_from(dim)
+                    .map_err(|_| format!("dimension {dim} does not fit in usize").into())
+            })
+            .collect()
+    };
+    let shape = read_dims()?;
+    let maxdims = if flags & 0x01 != 0 { read_dims()? } else { shape.clone() };
+    Ok((shape, maxdims))
+}
+
+const DATATYPE_CLASSES: &[&str] = &[
+    "fixed-point",
+    "floating-point",
+    "time",
+    "string",
+    "bitfield",
+    "opaque",
+    "compound",
+    "reference",
+    "enum",
+    "variable-length",
+    "array",
+    "complex",
+];
+
+fn parse_datatype(src: &Source, data: &[u8]) -> Result<(TypeDescriptor, usize, bool)> {
+    let mut c = src.cursor(data, "datatype message");
+    let class = c.u8()? & 0x0f;
+    if class > 1 {
+        let name = DATATYPE_CLASSES.get(class as usize).copied().unwrap_or("unknown");
+        fail!("{} datatypes are not supported by the native reader", name);
+    }
+    let bits = c.u8()?;
+    c.skip(2)?;
+    let size = c.u32()? as usize;
+    let offset = c.u16()?;
+    let precision = c.u16()? as usize;
+    ensure!(
+        offset == 0 && precision == 8 * size,
+        "{}-byte numeric type with bit offset {} and precision {} is not supported",
+        size,
+        offset,
+        precision
+    );
+    let big_endian = bits & 0x01 != 0;
+    let desc = match class {
+        0 => {
+            let int_size = IntSize::from_int(size)
+                .ok_or_else(|| format!("{size}-byte integers are not supported"))?;
+            if bits & 0x08 != 0 {
+                TypeDescriptor::Integer(int_size)
+            } else {
+                TypeDescriptor::Unsigned(int_size)
+            }
+        }
+        _ => {
+            ensure!(bits & 0x40 == 0, "VAX floating-point byte order is not supported");
+            let (exp_loc, exp_size, mant_loc, mant_size) = (c.u8()?, c.u8()?, c.u8()?, c.u8()?);
+            let bias = c.u32()?;
+            let ieee = match size {
+                4 => (exp_loc, exp_size, mant_loc, mant_size, bias) == (23, 8, 0, 23, 127),
+                8 => (exp_loc, exp_size, mant_loc, mant_size, bias) == (52, 11, 0, 52, 1023),
+                _ => false,
+            };
+            ensure!(ieee, "{}-byte non-IEEE floating-point types are not supported", size);
+            match size {
+                4 => TypeDescriptor::Float(FloatSize::U4),
+                _ => TypeDescriptor::Float(FloatSize::U8),
+            }
+        }
+    };
+    Ok((desc, size, big_endian))
+}
+
+fn parse_layout(src: &Source, data: &[u8], rank: usize, elem_size: usize) -> Result<Storage> {
+    let mut c = src.cursor(data, "layout message");
+    let version = c.u8()?;
+    ensure!(
+        version == 3 || version == 4,
+        "layout message version {} is not supported by the native reader",
+        version
+    );
+    let class = c.u8()?;
+    let storage = match class {
+        0 => {
+            let size = c.u16()? as usize;
+            Storage::Compact(c.bytes(size)?.to_vec())
+        }
+        1 => Storage::Contiguous { addr: c.addr()?, size: c.length()? },
+        2 if version == 3 => {
+            let ndims = c.u8()? as usize;
+            let addr = c.addr()?;
+            let dims = (0..ndims).map(|_| c.u32().map(|d| d as usize)).collect::<Result<_>>()?;
+            let chunk = chunk_dims(dims, rank, elem_size)?;
+            Storage::Chunked { chunk, index: ChunkIndex::BTreeV1(addr) }
+        }
+        2 => {
+            c.skip(1)?; // flags
+            let ndims = c.u8()? as usize;
+            let width = c.u8()? as usize;
+            let dims =
+                (0..ndims).map(|_| c.uint(width).map(|d| d as usize)).collect::<Result<_>>()?;
+            let chunk = chunk_dims(dims, rank, elem_size)?;
+            let index_type = c.u8()?;
+            match index_type {
+                1 | 2 => {}
+                3 => c.skip(1)?, // page bits
+                4 => fail!("extensible array chunk indexes are not supported by the native reader"),
+                5 => fail!("version 2 B-tree chunk indexes are not supported by the native reader"),
+                other => fail!("unknown chunk index type {}", other),
+            }
+            let addr = c.addr()?;
+            let index = match index_type {
+                1 => ChunkIndex::Single(addr),
+                2 => ChunkIndex::Implicit(addr),
+                _ => ChunkIndex::FixedArray(addr),
+            };
+            Storage::Chunked { chunk, index }
+        }
+        3 => fail!("virtual datasets are not supported by the native reader"),
+        other => fail!("unknown layout class {}", other),
+    };
+    Ok(storage)
+}
+
+fn chunk_dims(mut dims: Vec<usize>, rank: usize, elem_size: usize) -> Result<Vec<usize>> {
+    ensure!(
+        dims.len() == rank + 1,
+        "chunk rank {} does not match dataset rank {}",
+        dims.len().saturating_sub(1),
+        rank
+    );
+    let elem = dims.pop().unwrap_or(0);
+    ensure!(elem == elem_size, "chunk element size {} != datatype size {}", elem, elem_size);
+    ensure!(dims.iter().all(|&d| d > 0), "invalid chunk dimensions {:?}", dims);
+    Ok(dims)
+}
+
+fn parse_fill_value(
+    src: &Source,
+    messages: &[Message],
+    elem_size: usize,
+) -> Result<Option<Vec<u8>>> {
+    let value = if let Some(msg) = find_message(messages, MSG_FILL_VALUE) {
+        let mut c = src.cursor(&msg.data, "fill value message");
+        let version = c.u8()?;
+        let defined = if version >= 3 {
+            c.u8()? & 0x20 != 0
+        } else {
+            c.skip(2)?; // allocation and write times
+            c.u8()? != 0 || version == 1
+        };
+        if defined && c.remaining() >= 4 {
+            let size = c.u32()? as usize;
+            Some(c.bytes(size)?.to_vec())
+        } else {
+            None
+        }
+    } else if let Some(msg) = find_message(messages, MSG_FILL_VALUE_OLD) {
+        let mut c = src.cursor(&msg.data, "fill value message");
+        let size = c.u32()? as usize;
+        Some(c.bytes(size)?.to_vec())
+    } else {
+        None
+    };
+    match value {
+        Some(v) if v.iter().all(|&b| b == 0) => Ok(None),
+        Some(v) => {
+            ensure!(
+                v.len() == elem_size,
+                "fill value size {} != datatype size {}",
+                v.len(),
+                elem_size
+            );
+            Ok(Some(v))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Reads the chunk addresses of a non-paged fixed array index, in linear chunk order.
+pub(super) fn fixed_array_addresses(src: &Source, addr: u64) -> Result<Vec<Option<u64>>> {
+    let offset_size = src.offset_size as usize;
+    let header_len = 4 + 4 + src.length_size as usize + offset_size + 4;
+    let header = src.read(addr, header_len)?;
+    verify_checksum(&header, "fixed array header")?;
+    let mut c = src.cursor(&header, "fixed array header");
+    c.expect_signature(b"FAHD")?;
+    c.skip(1)?; // version
+    let client = c.u8()?;
+    ensure!(client == 0, "filtered fixed array chunk indexes are not supported");
+    let entry_size = c.u8()? as usize;
+    let page_bits = c.u8()?;
+    let count = c.length()? as usize;
+    let block = c.addr()?.ok_or("fixed array without data block")?;
+    ensure!(
+        page_bits >= 64 || count <= 1 << page_bits,
+        "paged fixed array chunk indexes are not supported by the native reader"
+    );
+    ensure!(entry_size == offset_size, "unexpected fixed array entry size {}", entry_size);
+    let block = src.read(block, 4 + 2 + offset_size + count * entry_size + 4)?;
+    verify_checksum(&block, "fixed array data block")?;
+    let mut c = src.cursor(&block, "fixed array data block");
+    c.expect_signature(b"FADB")?;
+    c.skip(2 + offset_size)?; // version, client id, header address
+    (0..count).map(|_| c.addr()).collect()
+}
@@ -0,0 +1,233 @@
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use parking_lot::Mutex;
+
+use crate::internal_prelude::*;
+
+/// Positioned access to the raw bytes of a file.
+pub(super) struct Source {
+    file: Mutex<fs::File>,
+    len: u64,
+    /// Base address that all file addresses are relative to.
+    pub base: u64,
+    pub offset_size: u8,
+    pub length_size: u8,
+}
+
+impl Source {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = fs::File::open(path)
+            .map_err(|err| format!("unable to open '{}': {}", path.display(), err))?;
+        let len = file.metadata().map_err(|err| err.to_string())?.len();
+        Ok(Self { file: Mutex::new(file), len, base: 0, offset_size: 8, length_size: 8 })
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Reads `len` bytes at an absolute position in the file.
+    pub fn read_abs(&self, pos: u64, len: usize) -> Result<Vec<u8>> {
+        let end = pos.checked_add(len as u64);
+        ensure!(
+            end.map_or(false, |end| end <= self.len),
+            "attempt to read {} bytes at position {} beyond the end of file ({} bytes)",
+            len,
+            pos,
+            self.len
+        );
+        let mut buf = vec![0; len];
+        let mut file = self.file.lock();
+        file.seek(SeekFrom::Start(pos))
+            .and_then(|_| file.read_exact(&mut buf))
+            .map_err(|err| format!("read error at position {pos}: {err}"))?;
+        Ok(buf)
+    }
+
+    /// Reads `len` bytes at a file address (relative to the base address).
+    pub fn read(&self, addr: u64, len: usize) -> Result<Vec<u8>> {
+        let pos = self.base.checked_add(addr).ok_or("file address overflow")?;
+        self.read_abs(pos, len)
+    }
+
+    /// Reads up to `len` bytes at a file address, stopping at the end of file.
+    pub fn read_upto(&self, addr: u64, len: usize) -> Result<Vec<u8>> {
+        let pos = self.base.checked_add(addr).ok_or("file address overflow")?;
+        let avail = self.len.saturating_sub(pos).min(len as u64);
+        self.read_abs(pos, avail as usize)
+    }
+
+    pub fn cursor<'a>(&self, buf: &'a [u8], what: &'static str) -> Cursor<'a> {
+        Cursor::new(buf, what, self.offset_size, self.length_size)
+    }
+}
+
+/// Little-endian decoder for on-disk structures.
+pub(super) struct Cursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    what: &'static str,
+    offset_size: u8,
+    length_size: u8,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(buf: &'a [u8], what: &'static str, offset_size: u8, length_size: u8) -> Self {
+        Self { buf, pos: 0, what, offset_size, length_size }
+    }
+
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    pub fn bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        ensure!(n <= self.remaining(), "truncated {} (expected {} more bytes)", self.what, n);
+        let out = &self.buf[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(out)
+    }
+
+    pub fn skip(&mut self, n: usize) -> Result<()> {
+        self.bytes(n).map(|_| ())
+    }
+
+    pub fn uint(&mut self, n: usize) -> Result<u64> {
+        ensure!(n <= 8, "invalid integer width {} in {}", n, self.what);
+        let bytes = self.bytes(n)?;
+        Ok(bytes.iter().rev().fold(0, |acc, &b| (acc << 8) | u64::from(b)))
+    }
+
+    pub fn u8(&mut self) -> Result<u8> {
+        self.uint(1).map(|v| v as _)
+    }
+
+    pub fn u16(&mut self) -> Result<u16> {
+        self.uint(2).map(|v| v as _)
+    }
+
+    pub fn u32(&mut self) -> Result<u32> {
+        self.uint(4).map(|v| v as _)
+    }
+
+    pub fn u64(&mut self) -> Result<u64> {
+        self.uint(8)
+    }
+
+    /// Reads a "size of lengths" wide value.
+    pub fn length(&mut self) -> Result<u64> {
+        self.uint(self.length_size as _)
+    }
+
+    /// Reads a "size of offsets" wide value.
+    pub fn offset(&mut self) -> Result<u64> {
+        self.uint(self.offset_size as _)
+    }
+
+    /// Reads a "size of offsets" wide value, returning `None` for the undefined address.
+    pub fn addr(&mut self) -> Result<Option<u64>> {
+        let undef = u64::MAX >> (64 - 8 * u32::from(self.offset_size));
+        self.uint(self.offset_size as _).map(|v| if v == undef { None } else { Some(v) })
+    }
+
+    pub fn expect_signature(&mut self, sig: &[u8; 4]) -> Result<()> {
+        let found = self.bytes(4)?;
+        ensure!(
+            found == sig,
+            "invalid {} signature: expected {:?}, found {:?}",
+            self.what,
+            String::from_utf8_lossy(sig),
+            String::from_utf8_lossy(found)
+        );
+        Ok(())
+    }
+}
+
+/// Verifies the trailing Jenkins lookup3 checksum of a metadata block.
+pub(super) fn verify_checksum(block: &[u8], what: &str) -> Result<()> {
+    ensure!(block.len() >= 4, "truncated {}", what);
+    let (data, stored) = block.split_at(block.len() - 4);
+    let stored = u32::from_le_bytes([stored[0], stored[1], stored[2], stored[3]]);
+    let computed = lookup3(data);
+    ensure!(
+        stored == computed,
+        "checksum mismatch in {}: stored {:#010x}, computed {:#010x}",
+        what,
+        stored,
+        computed
+    );
+    Ok(())
+}
+
+/// Bob Jenkins' lookup3 `hashlittle()` with a zero seed, as used for HDF5 metadata checksums.
+pub(super) fn lookup3(data: &[u8]) -> u32 {
+    fn mix(a: &mut u32, b: &mut u32, c: &mut u32) {
+        *a = a.wrapping_sub(*c);
+        *a ^= c.rotate_left(4);
+        *c = c.wrapping_add(*b);
+        *b = b.wrapping_sub(*a);
+        *b ^= a.rotate_left(6);
+        *a = a.wrapping_add(*c);
+        *c = c.wrapping_sub(*b);
+        *c ^= b.rotate_left(8);
+        *b = b.wrapping_add(*a);
+        *a = a.wrapping_sub(*c);
+        *a ^= c.rotate_left(16);
+        *c = c.wrapping_add(*b);
+        *b = b.wrapping_sub(*a);
+        *b ^= a.rotate_left(19);
+        *a = a.wrapping_add(*c);
+        *c = c.wrapping_sub(*b);
+        *c ^= b.rotate_left(4);
+        *b = b.wrapping_add(*a);
+    }
+
+    fn finalize(a: &mut u32, b: &mut u32, c: &mut u32) {
+        *c ^= *b;
+        *c = c.wrapping_sub(b.rotate_left(14));
+        *a ^= *c;
+        *a = a.wrapping_sub(c.rotate_left(11));
+        *b ^= *a;
+        *b = b.wrapping_sub(a.rotate_left(25));
+        *c ^= *b;
+        *c = c.wrapping_sub(b.rotate_left(16));
+        *a ^= *c;
+        *a = a.wrapping_sub(c.rotate_left(4));
+        *b ^= *a;
+        *b = b.wrapping_sub(a.rotate_left(14));
+        *c ^= *b;
+        *c = c.wrapping_sub(b.rotate_left(24));
+    }
+
+    fn word(bytes: &[u8]) -> u32 {
+        bytes.iter().enumerate().fold(0, |acc, (i, &b)| acc | (u32::from(b) << (8 * i)))
+    }
+
+    let init = 0xdead_beef_u32.wrapping_add(data.len() as u32);
+    let (mut a, mut b, mut c) = (init, init, init);
+    let mut rest = data;
+    while rest.len() > 12 {
+        a = a.wrapping_add(word(&rest[0..4]));
+        b = b.wrapping_add(word(&rest[4..8]));
+        c = c.wrapping_add(word(&rest[8..12]));
+        mix(&mut a, &mut b, &mut c);
+        rest = &rest[12..];
+    }
+    if rest.is_empty() {
+        return c;
+    }
+    a = a.wrapping_add(word(&rest[..rest.len().min(4)]));
+    if rest.len() > 4 {
+        b = b.wrapping_add(word(&rest[4..rest.len().min(8)]));
+    }
+    if rest.len() > 8 {
+        c = c.wrapping_add(word(&rest[8..]));
+    }
+    finalize(&mut a, &mut b, &mut c);
+    c
+}
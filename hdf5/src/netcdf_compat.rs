@@ -0,0 +1,261 @@
+//! Reading NetCDF-4 files, which are HDF5 files following the conventions of the netCDF-C
+//! library.
+//!
+//! NetCDF-4 dimensions are stored as HDF5 dimension scales: every variable (dataset) lists the
+//! scale of each of its axes in its [`DIMENSION_LIST_ATTR`] attribute, and every scale lists
+//! the variables using it in its [`REFERENCE_LIST_ATTR`] attribute. A dimension with a
+//! coordinate variable is that variable itself; other dimensions are datasets without data
+//! whose [`DIMENSION_SCALE_NAME_ATTR`] starts with [`NETCDF_DIMENSION_NAME_PREFIX`], which
+//! [`Group::netcdf_variables`] leaves out.
+//!
+//! [`REFERENCE_LIST_ATTR`]: crate::conventions::REFERENCE_LIST_ATTR
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! let file = hdf5_rt::File::open("air.nc")?;
+//! if file.is_netcdf4()? {
+//!     for (name, var) in file.netcdf_variables()? {
+//!         let dims: Vec<_> = var.netcdf_dimensions()?.iter().map(|dim| dim.name()).collect();
+//!         println!("{name}({})", dims.join(", "));
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use hdf5_types::VarLenArray;
+
+use crate::conventions::{
+    CLASS_ATTR, DIMENSION_LIST_ATTR, DIMENSION_SCALE_CLASS, DIMENSION_SCALE_NAME_ATTR,
+    NC_PROPERTIES_ATTR, NETCDF_DIMENSION_NAME_PREFIX,
+};
+use crate::hl::metadata::{has_attr, string_attr};
+use crate::internal_prelude::*;
+
+impl File {
+    /// Returns `true` if the file was written by the netCDF-C library, i.e. if its root group
+    /// has the [`NC_PROPERTIES_ATTR`] attribute.
+    ///
+    /// Files written by netCDF-C versions before 4.4.1 lack the attribute.
+    pub fn is_netcdf4(&self) -> Result<bool> {
+        has_attr(self, NC_PROPERTIES_ATTR)
+    }
+}
+
+impl Dataset {
+    /// Returns `true` if the dataset is a dimension scale, i.e. if its [`CLASS_ATTR`]
+    /// attribute is [`DIMENSION_SCALE_CLASS`].
+    pub fn is_dimension_scale(&self) -> bool {
+        string_attr(self, CLASS_ATTR).is_some_and(|class| class == DIMENSION_SCALE_CLASS)
+    }
+
+    /// Returns `true` if the dataset only stands for a NetCDF-4 dimension without a
+    /// coordinate variable and holds no data.
+    pub fn is_netcdf_dimension_only(&self) -> bool {
+        self.is_dimension_scale()
+            && string_attr(self, DIMENSION_SCALE_NAME_ATTR)
+                .is_some_and(|name| name.starts_with(NETCDF_DIMENSION_NAME_PREFIX))
+    }
+
+    /// Returns the dimension scale of each axis of a NetCDF-4 variable, resolved from its
+    /// [`DIMENSION_LIST_ATTR`] attribute.
+    ///
+    /// A coordinate variable, a 1-D dimension scale without that attribute, is its own
+    /// dimension, and scalar variables have no dimensions. Fails if an axis does not have
+    /// exactly one scale attached.
+    pub fn netcdf_dimensions(&self) -> Result<Vec<Self>> {
+        let name = self.name();
+        let ndim = self.ndim();
+        if !has_attr(self, DIMENSION_LIST_ATTR)? {
+            if ndim == 1 && self.is_dimension_scale() {
+                return Ok(vec![self.clone()]);
+            }
+            ensure!(
+                ndim == 0,
+                "dataset '{}' has no {} attribute and is not a coordinate variable",
+                name,
+                DIMENSION_LIST_ATTR
+            );
+            return Ok(vec![]);
+        }
+        let lists = self
+            .attr(DIMENSION_LIST_ATTR)?
+            .read_raw::<VarLenArray<ObjectReference1>>()
+            .with_context(|| format!("reading {DIMENSION_LIST_ATTR} of dataset '{name}'"))?;
+        ensure!(
+            lists.len() == ndim,
+            "{} of dataset '{}' has {} entries for {} axes",
+            DIMENSION_LIST_ATTR,
+            name,
+            lists.len(),
+            ndim
+        );
+        lists
+            .iter()
+            .enumerate()
+            .map(|(axis, scales)| {
+                let [scale] = scales.as_slice() else {
+                    fail!(
+                        "axis {} of dataset '{}' has {} dimension scales attached, expected one",
+                        axis,
+                        name,
+                        scales.len()
+                    );
+                };
+                match scale.dereference(self) {
+                    Ok(ReferencedObject::Dataset(dim)) => Ok(dim),
+                    Ok(_) => {
+                        fail!("the scale of axis {} of dataset '{}' is not a dataset", axis, name)
+                    }
+                    Err(err) => Err(err).with_context(|| {
+                        format!("resolving the scale of axis {axis} of dataset '{name}'")
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Group {
+    /// Returns the names and handles of the NetCDF-4 variables in the group, non-recursively:
+    /// all datasets except those that only stand for a dimension.
+    ///
+    /// See [`Dataset::is_netcdf_dimension_only`]; coordinate variables are included.
+    pub fn netcdf_variables(&self) -> Result<Vec<(String, Dataset)>> {
+        let mut datasets = self.datasets()?;
+        datasets.retain(|(_, ds)| !ds.is_netcdf_dimension_only());
+        Ok(datasets)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::mem;
+    use std::ops::ControlFlow;
+
+    use hdf5_types::{
+        CompoundField, CompoundType, FixedAscii, TypeDescriptor, VarLenArray, VarLenAscii,
+    };
+
+    use crate::conventions::{
+        CLASS_ATTR, DIMENSION_LIST_ATTR, DIMENSION_SCALE_CLASS, DIMENSION_SCALE_NAME_ATTR,
+        NC_PROPERTIES_ATTR, NETCDF_DIMENSION_NAME_PREFIX, REFERENCE_LIST_ATTR,
+    };
+    use crate::internal_prelude::*;
+
+    /// An element of the `REFERENCE_LIST` attribute of a dimension scale.
+    #[derive(Clone, Copy)]
+    #[repr(C)]
+    struct DimensionUse {
+        dataset: ObjectReference1,
+        dimension: i32,
+    }
+
+    unsafe impl H5Type for DimensionUse {
+        fn type_descriptor() -> TypeDescriptor {
+            TypeDescriptor::Compound(CompoundType {
+                fields: vec![
+                    CompoundField::typed::<ObjectReference1>(
+                        "dataset",
+                        mem::offset_of!(DimensionUse, dataset),
+                        0,
+                    ),
+                    CompoundField::typed::<i32>(
+                        "dimension",
+                        mem::offset_of!(DimensionUse, dimension),
+                        1,
+                    ),
+                ],
+                size: mem::size_of::<DimensionUse>(),
+            })
+        }
+    }
+
+    fn write_fixed_str<const N: usize>(loc: &Location, name: &str, value: &str) {
+        loc.new_attr::<FixedAscii<N>>().create(name).unwrap().write_str(value).unwrap();
+    }
+
+    fn make_scale(ds: &Dataset, name: &str) {
+        write_fixed_str::<16>(ds, CLASS_ATTR, DIMENSION_SCALE_CLASS);
+        write_fixed_str::<64>(ds, DIMENSION_SCALE_NAME_ATTR, name);
+    }
+
+    /// Builds the layout netCDF-C writes for `temp(time, x)` with a coordinate variable for
+    /// `time` but not for `x`, and a scalar variable.
+    fn write_netcdf_file(file: &File) {
+        write_fixed_str::<48>(file, NC_PROPERTIES_ATTR, "version=2,netcdf=4.9.2,hdf5=1.14.3");
+        let time =
+            file.new_dataset_builder().with_data(&[0.0, 6.0, 12.0, 18.0]).create("time").unwrap();
+        make_scale(&time, "time");
+        let x = file.new_dataset::<f32>().shape(3).create("x").unwrap();
+        make_scale(&x, &format!("{NETCDF_DIMENSION_NAME_PREFIX}{:>10}", 3));
+        let temp = file.new_dataset::<f32>().shape((4, 3)).create("temp").unwrap();
+        file.new_dataset::<i32>().create("scalar").unwrap();
+
+        let scale_refs =
+            |name| VarLenArray::from_slice(&[file.reference::<ObjectReference1>(name).unwrap()]);
+        let dims = [scale_refs("time"), scale_refs("x")];
+        temp.new_attr_builder().with_data(&dims).create(DIMENSION_LIST_ATTR).unwrap();
+        let temp_ref = file.reference::<ObjectReference1>("temp").unwrap();
+        for (scale, dimension) in [(&time, 0), (&x, 1)] {
+            let uses = [DimensionUse { dataset: temp_ref, dimension }];
+            scale.new_attr_builder().with_data(&uses).create(REFERENCE_LIST_ATTR).unwrap();
+        }
+    }
+
+    #[test]
+    pub fn test_netcdf_compat() {
+        with_tmp_file(|file| {
+            assert!(!file.is_netcdf4().unwrap());
+            write_netcdf_file(&file);
+            assert!(file.is_netcdf4().unwrap());
+
+            let names = |dims: Vec<Dataset>| dims.iter().map(|d| d.name()).collect::<Vec<_>>();
+            let temp = file.dataset("temp").unwrap();
+            assert_eq!(names(temp.netcdf_dimensions().unwrap()), ["/time", "/x"]);
+            let time = file.dataset("time").unwrap();
+            assert!(time.is_dimension_scale() && !time.is_netcdf_dimension_only());
+            assert_eq!(names(time.netcdf_dimensions().unwrap()), ["/time"]);
+            let x = file.dataset("x").unwrap();
+            assert!(x.is_dimension_scale() && x.is_netcdf_dimension_only());
+            assert!(file.dataset("scalar").unwrap().netcdf_dimensions().unwrap().is_empty());
+
+            let vars: Vec<_> = file.netcdf_variables().unwrap().into_iter().map(|v| v.0).collect();
+            assert_eq!(vars, ["scalar", "temp", "time"]);
+            assert_eq!(file.datasets().unwrap().len(), 4);
+
+            let plain = file.new_dataset::<u8>().shape(2).create("plain").unwrap();
+            assert_err!(plain.netcdf_dimensions(), "has no DIMENSION_LIST attribute");
+        })
+    }
+
+    #[test]
+    pub fn test_netcdf_special_attributes() {
+        with_tmp_file(|file| {
+            write_netcdf_file(&file);
+
+            // the reference attributes are copied through their dynamically-typed values
+            let options = CopyOptions { progress_threshold: 0, ..CopyOptions::default() };
+            for name in ["temp", "x"] {
+                let copy = format!("{name}_copy");
+                let copied =
+                    file.copy_object_with_progress(name, &file, &copy, &options, |_, _| {
+                        ControlFlow::Continue(())
+                    });
+                assert_eq!(copied.unwrap(), ControlFlow::Continue(()));
+            }
+            let dims = file.dataset("temp_copy").unwrap().netcdf_dimensions().unwrap();
+            assert_eq!(dims.iter().map(|d| d.name()).collect::<Vec<_>>(), ["/time", "/x"]);
+            let uses = file.dataset("x_copy").unwrap().attr(REFERENCE_LIST_ATTR).unwrap();
+            assert_eq!(uses.read_raw::<DimensionUse>().unwrap()[0].dimension, 1);
+
+            let tree = from_group(&file).unwrap();
+            assert_eq!(tree.get("scalar").and_then(|v| v.to_scalar::<i32>()), Some(0));
+            let props = tree.get(NC_PROPERTIES_ATTR).unwrap();
+            assert!(props.to_scalar::<FixedAscii<48>>().is_some());
+            assert!(props.to_scalar::<VarLenAscii>().is_none());
+        })
+    }
+}
@@ -0,0 +1,390 @@
+//! Operations on objects that may span files.
+//!
+//! HDF5 can only move links within a file. [`move_object`] moves an object to any location:
+//! within a file it moves the link, and to another file it copies the object, verifies the
+//! copy and only then deletes the link to the source.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::ops::{move_object, MoveOptions};
+//! use hdf5_rt::File;
+//!
+//! let (src, dst) = (File::open_rw("old.h5")?, File::open_rw("archive.h5")?);
+//! let report = move_object(&src, "/a/ds", &dst, "/b/ds", MoveOptions::default())?;
+//! for warning in &report.warnings {
+//!     eprintln!("{warning}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(test)]
+use std::cell::Cell;
+use std::collections::HashSet;
+
+use hdf5_types::{Reference, TypeDescriptor};
+
+#[cfg(feature = "checksum")]
+use crate::hl::checksum::compute_checksum;
+#[cfg(feature = "checksum")]
+use crate::hl::copy::has_pointers;
+use crate::hl::group::move_link;
+use crate::internal_prelude::*;
+
+#[cfg(test)]
+thread_local! {
+    /// Makes the verification of moved datasets fail on the current thread.
+    static FAIL_VERIFICATION: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Options for [`move_object`].
+#[derive(Clone, Debug)]
+pub struct MoveOptions {
+    /// Whether moved datasets are compared with their source by a checksum of their logical
+    /// data, in addition to their datatype, shape, filters and attribute names. Requires the
+    /// `checksum` feature, with which it is enabled by default.
+    pub verify_checksum: bool,
+    /// Whether the source file is searched for object references to the moved object, which
+    /// dangle once it is deleted; they are reported in [`MoveReport::warnings`].
+    pub find_dangling_references: bool,
+}
+
+impl Default for MoveOptions {
+    fn default() -> Self {
+        Self { verify_checksum: cfg!(feature = "checksum"), find_dangling_references: false }
+    }
+}
+
+/// Outcome of a successful [`move_object`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MoveReport {
+    /// Datasets that could not be compared by checksum, because their datatype holds
+    /// variable-length data or references, and datasets or attributes holding references to
+    /// the moved object.
+    pub warnings: Vec<String>,
+}
+
+/// Moves the object `src_name` of `src` (recursively, for groups) to `dst_name` in `dst`.
+///
+/// `src` and `dst` are files or groups, possibly of different files, and the parent group of
+/// `dst_name` must exist. Within a file, the link is moved with `H5Lmove`, so the object
+/// itself stays in place and references to it remain valid; `options` do not apply.
+///
+/// Across files, the object is copied with [`Group::copy_object`], preserving its attributes
+/// and filters, then the copy is compared with the source, see
+/// [`MoveOptions::verify_checksum`]. If they differ, the copy is deleted and the source is
+/// kept; otherwise the link `src_name` is deleted, which deletes the source object unless
+/// other hard links point to it.
+pub fn move_object(
+    src: &Location,
+    src_name: &str,
+    dst: &Location,
+    dst_name: &str,
+    options: MoveOptions,
+) -> Result<MoveReport> {
+    #[cfg(not(feature = "checksum"))]
+    ensure!(!options.verify_checksum, "checksum verification requires the `checksum` feature");
+    let src = as_group(src).context("moving from a location that is not a file or group")?;
+    let dst = as_group(dst).context("moving to a location that is not a file or group")?;
+    src.handle().ensure_writable()?;
+    h5lock!({
+        let src_info = src.loc_info_by_name(src_name)?;
+        ensure!(!dst.link_exists(dst_name), "the destination '{}' already exists", dst_name);
+        if dst.loc_info()?.fileno == src_info.fileno {
+            move_link(&src, src_name, &dst, dst_name)
+                .with_context(|| format!("moving '{src_name}' to '{dst_name}'"))?;
+            return Ok(MoveReport::default());
+        }
+        src.copy_object(src_name, &dst, dst_name, None)
+            .with_context(|| format!("copying '{src_name}' to '{dst_name}'"))?;
+
+        let mut verifier =
+            Verifier { options: &options, visited: HashSet::new(), warnings: vec![] };
+        if let Err(err) = verifier.verify(&src, src_name, &dst, dst_name) {
+            let _ = dst.unlink(dst_name);
+            return Err(err).with_context(|| {
+                format!("verifying the copy of '{src_name}', the source is kept")
+            });
+        }
+        let mut report = MoveReport { warnings: verifier.warnings };
+        if options.find_dangling_references && src_info.num_links == 1 {
+            let mut visited = HashSet::from([(src_info.fileno, src_info.token)]);
+            let root = src.file()?;
+            find_references(&root, &src_info, &mut visited, &mut report.warnings)?;
+        }
+        src.unlink(src_name)?;
+        Ok(report)
+    })
+}
+
+/// Returns the group (or the root group of the file) at a location.
+fn as_group(loc: &Location) -> Result<Group> {
+    match ObjectHandleKind::from_location(loc.open_by_token(loc.loc_info()?.token)?)? {
+        ObjectHandleKind::Group(group) => Ok(group),
+        _ => fail!("'{}' is not a group", loc.name()),
+    }
+}
+
+fn open_object(group: &Group, name: &str) -> Result<ObjectHandleKind> {
+    ObjectHandleKind::from_location(group.open_by_token(group.loc_info_by_name(name)?.token)?)
+}
+
+/// Compares a copied object with its source.
+struct Verifier<'a> {
+    options: &'a MoveOptions,
+    visited: HashSet<(u64, LocationToken)>,
+    warnings: Vec<String>,
+}
+
+impl Verifier<'_> {
+    fn verify(&mut self, src: &Group, src_name: &str, dst: &Group, dst_name: &str) -> Result<()> {
+        let info = src.loc_info_by_name(src_name)?;
+        // objects linked several times within a copied group are only compared once
+        if !self.visited.insert((info.fileno, info.token)) {
+            return Ok(());
+        }
+        match (open_object(src, src_name)?, open_object(dst, dst_name)?) {
+            (ObjectHandleKind::Group(src), ObjectHandleKind::Group(dst)) => {
+                self.verify_group(&src, &dst)
+            }
+            (ObjectHandleKind::Dataset(src), ObjectHandleKind::Dataset(dst)) => {
+                self.verify_dataset(&src, &dst)
+            }
+            (ObjectHandleKind::Datatype(src), ObjectHandleKind::Datatype(dst)) => {
+                ensure!(src == dst, "the named datatype '{}' differs from the source", dst_name);
+                Ok(())
+            }
+            (_, dst) => fail!("'{:?}' is not the same kind of object as the source", dst),
+        }
+    }
+
+    fn verify_group(&mut self, src: &Group, dst: &Group) -> Result<()> {
+        let (names, dst_names) = (src.member_names()?, dst.member_names()?);
+        ensure!(
+            names == dst_names,
+            "group '{}' has the members {:?}, expected {:?}",
+            dst.name(),
+            dst_names,
+            names
+        );
+        verify_attr_names(src, dst)?;
+        for name in &names {
+            match src.link_target(name)? {
+                None => self.verify(src, name, dst, name)?,
+                target => ensure!(
+                    dst.link_target(name)? == target,
+                    "the link '{}' in '{}' has a different target",
+                    name,
+                    dst.name()
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_dataset(&mut self, src: &Dataset, dst: &Dataset) -> Result<()> {
+        let name = dst.name();
+        ensure!(src.dtype()? == dst.dtype()?, "dataset '{}' has a different datatype", name);
+        ensure!(
+            src.shape()? == dst.shape()?,
+            "dataset '{}' has shape {:?}, expected {:?}",
+            name,
+            dst.shape(),
+            src.shape()
+        );
+        ensure!(src.filters() == dst.filters(), "dataset '{}' has different filters", name);
+        verify_attr_names(src, dst)?;
+        #[cfg(test)]
+        ensure!(!FAIL_VERIFICATION.get(), "dataset '{}' differs from the source", name);
+        #[cfg(feature = "checksum")]
+        if self.options.verify_checksum {
+            if src.dtype()?.to_descriptor().map_or(true, |desc| has_pointers(&desc)) {
+                self.warnings.push(format!(
+                    "dataset '{name}' was not compared by checksum, its datatype holds \
+                     variable-length data or references"
+                ));
+            } else {
+                ensure!(
+                    compute_checksum(src)? == compute_checksum(dst)?,
+                    "dataset '{}' differs from the source",
+                    name
+                );
+            }
+        }
+        #[cfg(not(feature = "checksum"))]
+        let _ = self.options;
+        Ok(())
+    }
+}
+
+fn verify_attr_names(src: &Location, dst: &Location) -> Result<()> {
+    let (mut names, mut dst_names) = (src.attr_names()?, dst.attr_names()?);
+    names.sort();
+    dst_names.sort();
+    ensure!(
+        names == dst_names,
+        "'{}' has the attributes {:?}, expected {:?}",
+        dst.name(),
+        dst_names,
+        names
+    );
+    Ok(())
+}
+
+/// Adds a warning for each dataset or attribute reachable from `loc` that holds object
+/// references to `target`. Only datasets and attributes of object reference types are
+/// searched, not references nested in other types.
+fn find_references(
+    loc: &Location,
+    target: &LocationInfo,
+    visited: &mut HashSet<(u64, LocationToken)>,
+    warnings: &mut Vec<String>,
+) -> Result<()> {
+    for attr_name in loc.attr_names()? {
+        let what = || format!("attribute '{attr_name}' of '{}'", loc.name());
+        let attr = loc.attr(&attr_name)?;
+        count_references(&attr, target, what, warnings)?;
+    }
+    let ObjectHandleKind::Group(group) = ObjectHandleKind::from_location(loc.clone())? else {
+        return Ok(());
+    };
+    for (_, obj) in group.members()? {
+        let member: Location = match obj {
+            ObjectHandleKind::Group(group) => group.cast()?,
+            ObjectHandleKind::Dataset(ds) => {
+                count_references(&ds, target, || format!("dataset '{}'", ds.name()), warnings)?;
+                ds.cast()?
+            }
+            ObjectHandleKind::Datatype(dtype) => dtype.cast()?,
+        };
+        let info = member.loc_info()?;
+        if visited.insert((info.fileno, info.token)) {
+            find_references(&member, target, visited, warnings)?;
+        }
+    }
+    Ok(())
+}
+
+fn count_references<F>(
+    obj: &Container,
+    target: &LocationInfo,
+    what: F,
+    warnings: &mut Vec<String>,
+) -> Result<()>
+where
+    F: FnOnce() -> String,
+{
+    let is_target = |referenced: Result<ReferencedObject>| {
+        let info = match referenced {
+            Ok(ReferencedObject::Group(group)) => group.loc_info(),
+            Ok(ReferencedObject::Dataset(ds)) => ds.loc_info(),
+            Ok(ReferencedObject::Datatype(dtype)) => {
+                dtype.cast::<Location>().and_then(|loc| loc.loc_info())
+            }
+            // references that cannot be resolved do not point to the moved object
+            Err(_) => return false,
+        };
+        info.is_ok_and(|info| info.fileno == target.fileno && info.token == target.token)
+    };
+    let count = match obj.dtype()?.to_descriptor() {
+        Ok(TypeDescriptor::Reference(Reference::Object)) => obj
+            .read_raw::<ObjectReference1>()?
+            .iter()
+            .filter(|r| !r.is_null() && is_target(r.dereference(obj)))
+            .count(),
+        Ok(TypeDescriptor::Reference(Reference::Std)) => obj
+            .read_raw::<ObjectReference2>()?
+            .iter()
+            .filter(|r| !r.is_null() && is_target(r.dereference(obj)))
+            .count(),
+        _ => 0,
+    };
+    if count > 0 {
+        warnings.push(format!("{} holds {} references to the moved object", what(), count));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ndarray::Array2;
+
+    use super::{move_object, MoveOptions, MoveReport, FAIL_VERIFICATION};
+    use crate::hl::filters::Filter;
+    use crate::internal_prelude::*;
+
+    fn create_files(dir: &std::path::Path) -> (File, File, Array2<i32>) {
+        let src = File::create(dir.join("src.h5")).unwrap();
+        let dst = File::create(dir.join("dst.h5")).unwrap();
+        let data = Array2::from_shape_fn((50, 20), |(i, j)| (i * 20 + j) as i32);
+        let ds = src.create_group("a").unwrap().new_dataset_builder();
+        let ds = ds.chunk((10, 20)).deflate(4).with_data(&data).create("ds").unwrap();
+        ds.new_attr::<u32>().create("version").unwrap().write_scalar(&3).unwrap();
+        dst.create_group("b").unwrap();
+        (src, dst, data)
+    }
+
+    #[test]
+    pub fn test_move_object() {
+        with_tmp_dir(|dir| {
+            let (src, dst, data) = create_files(&dir);
+            let report = move_object(&src, "/a/ds", &dst, "/b/ds", MoveOptions::default());
+            assert_eq!(report.unwrap(), MoveReport::default());
+            assert!(!src.link_exists("/a/ds"));
+            let ds = dst.dataset("/b/ds").unwrap();
+            assert_eq!(ds.read_2d::<i32>().unwrap(), data);
+            assert_eq!(ds.chunk(), Some(vec![10, 20]));
+            assert!(ds.filters().contains(&Filter::Deflate(4)));
+            assert_eq!(ds.attr("version").unwrap().read_scalar::<u32>().unwrap(), 3);
+
+            // groups are moved recursively, and within a file too
+            let group = dst.group("b").unwrap();
+            group.new_dataset::<u8>().shape(3).create("empty").unwrap();
+            group.link_soft("ds", "alias").unwrap();
+            let token = group.loc_info().unwrap().token;
+            move_object(&dst, "b", &dst, "c", MoveOptions::default()).unwrap();
+            assert!(!dst.link_exists("b"));
+            // the link is moved, not the object
+            assert_eq!(dst.group("c").unwrap().loc_info().unwrap().token, token);
+            assert_eq!(dst.group("c").unwrap().member_names().unwrap(), ["alias", "ds", "empty"]);
+
+            assert_err!(
+                move_object(&src, "a", &dst, "c", MoveOptions::default()),
+                "the destination 'c' already exists"
+            );
+            let ds = dst.dataset("c/ds").unwrap();
+            assert_err!(
+                move_object(&ds, "x", &dst, "x", MoveOptions::default()),
+                "not a file or group"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_move_object_verification_failure() {
+        with_tmp_dir(|dir| {
+            let (src, dst, data) = create_files(&dir);
+            FAIL_VERIFICATION.set(true);
+            let result = move_object(&src, "/a/ds", &dst, "/b/ds", MoveOptions::default());
+            FAIL_VERIFICATION.set(false);
+            assert_err!(result, "the source is kept");
+            assert_eq!(src.dataset("/a/ds").unwrap().read_2d::<i32>().unwrap(), data);
+            assert!(!dst.link_exists("/b/ds"));
+        })
+    }
+
+    #[test]
+    pub fn test_move_object_dangling_references() {
+        with_tmp_dir(|dir| {
+            let (src, dst, _) = create_files(&dir);
+            let refs =
+                [src.reference::<ObjectReference1>("/a/ds").unwrap(), ObjectReference1::null()];
+            src.new_dataset_builder().with_data(&refs).create("refs").unwrap();
+            let options = MoveOptions { find_dangling_references: true, ..MoveOptions::default() };
+            let report = move_object(&src, "/a/ds", &dst, "/b/ds", options).unwrap();
+            assert_eq!(report.warnings, ["dataset '/refs' holds 1 references to the moved object"]);
+        })
+    }
+}
@@ -0,0 +1,400 @@
+//! Compressing the chunks of a dataset on several threads.
+//!
+//! The library runs the filter pipeline of a dataset on the calling thread, so writing
+//! large compressed datasets is bound by the speed of a single core. A
+//! [`ParallelChunkWriter`] splits the data into chunks, runs the pipeline on each of them in
+//! a thread pool, and hands the filtered chunks to the library with
+//! [`Dataset::write_chunk_raw`], which only stores them.
+//!
+//! The pipeline is run by this module's own implementations of the filters, which support:
+//!
+//! - [shuffle](crate::filters::Filter::Shuffle);
+//! - [deflate](crate::filters::Filter::Deflate), producing zlib streams that the library's
+//!   deflate filter decodes, though not necessarily byte for byte the same as the library's;
+//! - zstd, the registered filter [`ZSTD_FILTER_ID`] with the compression level as its only
+//!   parameter, as set by e.g. `hdf5plugin` and the HDF Group's filter plugins.
+//!
+//! Datasets with any other filter in their pipeline (including blosc, which compresses on
+//! several threads of its own, and the checksum and szip filters), whose elements are not
+//! plain bytes (variable-length strings and arrays, references, and compound types with
+//! padding), or whose datatype differs from the type being written, are written the usual
+//! way instead, without an error or warning. Whether and why a dataset is written this way is
+//! reported by [`fallback_reason`](ParallelChunkWriter::fallback_reason), except for the
+//! datatype, which is only known once [`write`](ParallelChunkWriter::write) is called.
+//!
+//! Requires the `parallel-write` crate feature.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::{parallel_write::ParallelChunkWriter, File};
+//! use ndarray::Array2;
+//!
+//! let file = File::create("out.h5")?;
+//! let data = Array2::<f64>::zeros((4096, 4096));
+//! let ds = file
+//!     .new_dataset::<f64>()
+//!     .shape(data.dim())
+//!     .chunk((256, 256))
+//!     .shuffle()
+//!     .deflate(6)
+//!     .create("data")?;
+//! ParallelChunkWriter::new(&ds, 8)?.write(&data)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::Write;
+use std::slice;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use ndarray::ArrayView;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use hdf5_types::TypeDescriptor;
+
+use crate::hl::filters::Filter;
+use crate::internal_prelude::*;
+use crate::sys::h5z::H5Z_filter_t;
+
+/// Identifier of the zstd filter registered with the HDF Group.
+pub const ZSTD_FILTER_ID: H5Z_filter_t = 32015;
+
+/// Number of chunks filtered per thread before the filtered chunks are written.
+const CHUNKS_PER_THREAD: usize = 4;
+
+/// A filter of the pipeline, as run by this module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Stage {
+    Shuffle(usize),
+    Deflate(u32),
+    Zstd(i32),
+}
+
+impl Stage {
+    fn apply(self, data: Vec<u8>) -> Result<Vec<u8>> {
+        match self {
+            Self::Shuffle(size) => Ok(shuffle(&data, size)),
+            Self::Deflate(level) => {
+                let out = Vec::with_capacity(data.len() / 2);
+                let mut encoder = ZlibEncoder::new(out, Compression::new(level));
+                encoder
+                    .write_all(&data)
+                    .and_then(|()| encoder.finish())
+                    .map_err(|err| format!("deflate failed: {err}").into())
+            }
+            Self::Zstd(level) => zstd::bulk::compress(&data, level)
+                .map_err(|err| format!("zstd compression failed: {err}").into()),
+        }
+    }
+}
+
+/// Shuffles the bytes of the elements of `size` bytes in `data` like the library's shuffle
+/// filter: the first bytes of all elements come first, then the second bytes, and so on,
+/// followed by any trailing bytes that don't make up an element.
+fn shuffle(data: &[u8], size: usize) -> Vec<u8> {
+    let count = data.len() / size.max(1);
+    if size <= 1 || count <= 1 {
+        return data.to_vec();
+    }
+    let mut out = vec![0; data.len()];
+    for (byte, dst) in out.chunks_exact_mut(count).take(size).enumerate() {
+        for (i, value) in dst.iter_mut().enumerate() {
+            *value = data[i * size + byte];
+        }
+    }
+    out[count * size..].copy_from_slice(&data[count * size..]);
+    out
+}
+
+/// Returns the pipeline as run by this module, or why it can't be.
+fn plan_pipeline(filters: &[Filter], type_size: usize) -> Result<Vec<Stage>, String> {
+    filters
+        .iter()
+        .map(|filter| match *filter {
+            Filter::Shuffle => Ok(Stage::Shuffle(type_size)),
+            Filter::Deflate(level) => Ok(Stage::Deflate(level.into())),
+            Filter::User(ZSTD_FILTER_ID, ref params) => {
+                Ok(Stage::Zstd(params.first().map_or(0, |&level| level as _)))
+            }
+            ref filter => Err(format!("filter {filter} is only run by the library")),
+        })
+        .collect()
+}
+
+/// Checks that elements of type `desc` can be copied into chunks as they are in memory, or
+/// returns why they can't: variable-length data and references have to be converted by the
+/// library, and the padding bytes of compound types are uninitialized.
+fn check_plain(desc: &TypeDescriptor) -> Result<(), String> {
+    match desc {
+        TypeDescriptor::VarLenArray(_)
+        | TypeDescriptor::VarLenAscii
+        | TypeDescriptor::VarLenUnicode
+        | TypeDescriptor::Reference(_) => {
+            Err(format!("elements of type {desc} are converted by the library"))
+        }
+        TypeDescriptor::FixedArray(ty, _) | TypeDescriptor::FixedArrayNd(ty, _) => check_plain(ty),
+        TypeDescriptor::Compound(compound) => {
+            let mut fields: Vec<_> = compound.fields.iter().collect();
+            fields.sort_by_key(|field| field.offset);
+            let mut end = 0;
+            for field in fields {
+                if field.offset != end {
+                    break;
+                }
+                check_plain(&field.ty)?;
+                end += field.ty.size();
+            }
+            if end != compound.size {
+                return Err(format!("compound type {desc} has padding"));
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the strides of an array of shape `dims` in C order, in elements.
+fn c_strides(dims: &[Ix]) -> Vec<Ix> {
+    let mut strides = vec![1; dims.len()];
+    for i in (0..dims.len().saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * dims[i + 1];
+    }
+    strides
+}
+
+/// Chunk layout of a dataset of a given shape, for copying chunks out of its data.
+struct ChunkGrid<'a> {
+    shape: &'a [Ix],
+    chunk: &'a [Ix],
+    grid: Vec<Ix>,
+    elem_size: usize,
+}
+
+impl<'a> ChunkGrid<'a> {
+    fn new(shape: &'a [Ix], chunk: &'a [Ix], elem_size: usize) -> Self {
+        let grid = shape.iter().zip(chunk).map(|(&s, &c)| s.div_ceil(c)).collect();
+        Self { shape, chunk, grid, elem_size }
+    }
+
+    fn len(&self) -> usize {
+        self.grid.iter().product()
+    }
+
+    /// Returns the offset of the chunk with index `index`, in C order over the chunk grid.
+    fn offset(&self, mut index: usize) -> Vec<Ix> {
+        let mut offset = vec![0; self.grid.len()];
+        for i in (0..self.grid.len()).rev() {
+            offset[i] = index % self.grid[i] * self.chunk[i];
+            index /= self.grid[i];
+        }
+        offset
+    }
+
+    /// Copies the chunk at `offset` out of `data` (the whole dataset in C order), padding
+    /// the parts of edge chunks beyond the dataset with zeros.
+    fn gather(&self, data: &[u8], offset: &[Ix]) -> Vec<u8> {
+        let ndim = self.shape.len();
+        let elem = self.elem_size;
+        let block: Vec<Ix> =
+            (0..ndim).map(|i| self.chunk[i].min(self.shape[i] - offset[i])).collect();
+        let (src_strides, dst_strides) = (c_strides(self.shape), c_strides(self.chunk));
+        let row = block[ndim - 1] * elem;
+        let mut out = vec![0; self.chunk.iter().product::<Ix>() * elem];
+        let rows: Ix = block[..ndim - 1].iter().product();
+        let mut pos = vec![0; ndim - 1];
+        for _ in 0..rows {
+            let src = (0..ndim - 1).map(|i| (offset[i] + pos[i]) * src_strides[i]).sum::<Ix>()
+                + offset[ndim - 1];
+            let dst = (0..ndim - 1).map(|i| pos[i] * dst_strides[i]).sum::<Ix>();
+            out[dst * elem..dst * elem + row].copy_from_slice(&data[src * elem..src * elem + row]);
+            // next row in C order
+            for i in (0..ndim - 1).rev() {
+                pos[i] += 1;
+                if pos[i] < block[i] {
+                    break;
+                }
+                pos[i] = 0;
+            }
+        }
+        out
+    }
+}
+
+/// Writes whole datasets, running their filter pipeline on a pool of threads; see the
+/// [module documentation](self).
+#[derive(Debug)]
+pub struct ParallelChunkWriter {
+    ds: Dataset,
+    pool: ThreadPool,
+    chunk: Vec<Ix>,
+    pipeline: Result<Vec<Stage>, String>,
+}
+
+impl ParallelChunkWriter {
+    /// Creates a writer for `ds` running the filters on `n_threads` threads.
+    ///
+    /// Fails if the dataset is not chunked. Datasets whose pipeline can't be run outside the
+    /// library, or whose elements can't be copied as bytes, are accepted, and written the
+    /// usual way.
+    pub fn new(ds: &Dataset, n_threads: usize) -> Result<Self> {
+        ensure!(n_threads > 0, "number of threads must be positive");
+        let chunk = ds.chunk().ok_or_else(|| format!("{} is not chunked", ds.describe()))?;
+        let dtype = ds.dtype()?;
+        let pipeline = dtype
+            .to_descriptor()
+            .map_err(|err| err.to_string())
+            .and_then(|desc| check_plain(&desc))
+            .and_then(|()| plan_pipeline(&ds.filters(), dtype.size()));
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(n_threads)
+            .thread_name(|i| format!("hdf5-chunk-{i}"))
+            .build()
+            .map_err(|err| format!("failed to build the thread pool: {err}"))?;
+        Ok(Self { ds: ds.clone(), pool, chunk, pipeline })
+    }
+
+    /// Returns the dataset written to.
+    pub fn dataset(&self) -> &Dataset {
+        &self.ds
+    }
+
+    /// Returns why the filter pipeline of the dataset is run by the library on the calling
+    /// thread instead, if it is.
+    pub fn fallback_reason(&self) -> Option<&str> {
+        self.pipeline.as_ref().err().map(String::as_str)
+    }
+
+    /// Writes the whole dataset, which must have the same shape as `arr`.
+    ///
+    /// Every chunk is written, including chunks holding only fill values. If the memory
+    /// type of `T` differs from the datatype of the dataset, or the dataset is written by the
+    /// library (see [`fallback_reason`](Self::fallback_reason)), the data is written with
+    /// [`Container::write`] instead.
+    pub fn write<'a, A, T, D>(&self, arr: A) -> Result<()>
+    where
+        A: Into<ArrayView<'a, T, D>>,
+        T: H5Type + Clone + Sync,
+        D: ndarray::Dimension,
+    {
+        let view = arr.into();
+        let shape = self.ds.shape()?;
+        ensure!(
+            view.shape() == shape.as_slice(),
+            "shape mismatch when writing {}: data {:?}, dataset {:?}",
+            self.ds.describe(),
+            view.shape(),
+            shape
+        );
+        let stages = match self.pipeline {
+            Ok(ref stages) if Datatype::from_type::<T>()? == self.ds.dtype()? => stages,
+            _ => return self.ds.write(view),
+        };
+        self.ds.handle().ensure_writable()?;
+
+        let view = view.as_standard_layout();
+        let elem_size = std::mem::size_of::<T>();
+        // the data is only copied around, never interpreted, by the threads
+        let data =
+            unsafe { slice::from_raw_parts(view.as_ptr().cast::<u8>(), view.len() * elem_size) };
+        let grid = ChunkGrid::new(&shape, &self.chunk, elem_size);
+        let batch = self.pool.current_num_threads() * CHUNKS_PER_THREAD;
+        for start in (0..grid.len()).step_by(batch.max(1)) {
+            let end = grid.len().min(start + batch);
+            let chunks = self.pool.install(|| {
+                (start..end)
+                    .into_par_iter()
+                    .map(|index| {
+                        let offset = grid.offset(index);
+                        let chunk = grid.gather(data, &offset);
+                        let chunk =
+                            stages.iter().try_fold(chunk, |data, stage| stage.apply(data))?;
+                        Ok((offset, chunk))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })?;
+            for (offset, chunk) in chunks {
+                self.ds.write_chunk_raw(&offset, 0, &chunk)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use hdf5_types::{
+        CompoundField, CompoundType, FixedAscii, H5Type, TypeDescriptor, VarLenArray, VarLenUnicode,
+    };
+
+    use super::{check_plain, plan_pipeline, shuffle, ChunkGrid, Stage, ZSTD_FILTER_ID};
+    use crate::hl::filters::{Filter, SZip};
+    use crate::hl::references::ObjectReference2;
+
+    #[test]
+    pub fn test_shuffle() {
+        let data: Vec<u8> = (0..14).collect();
+        assert_eq!(shuffle(&data, 4), [0, 4, 8, 1, 5, 9, 2, 6, 10, 3, 7, 11, 12, 13]);
+        assert_eq!(shuffle(&data, 1), data);
+        assert_eq!(shuffle(&data[..5], 4), &data[..5]);
+        assert_eq!(shuffle(&data[..8], 2), [0, 2, 4, 6, 1, 3, 5, 7]);
+    }
+
+    #[test]
+    pub fn test_plan_pipeline() {
+        let filters = [Filter::Shuffle, Filter::Deflate(4), Filter::User(ZSTD_FILTER_ID, vec![])];
+        let stages = plan_pipeline(&filters, 8).unwrap();
+        assert_eq!(stages, [Stage::Shuffle(8), Stage::Deflate(4), Stage::Zstd(0)]);
+        let filters = [Filter::Shuffle, Filter::SZip(SZip::Entropy, 8)];
+        assert!(plan_pipeline(&filters, 8).unwrap_err().contains("szip"));
+        assert!(plan_pipeline(&[Filter::Fletcher32], 8).is_err());
+    }
+
+    #[test]
+    pub fn test_check_plain() {
+        let compound = |fields, size| TypeDescriptor::Compound(CompoundType { fields, size });
+        assert!(check_plain(&f64::type_descriptor()).is_ok());
+        assert!(check_plain(&<[[u16; 3]; 2]>::type_descriptor()).is_ok());
+        assert!(check_plain(&FixedAscii::<8>::type_descriptor()).is_ok());
+        let err = check_plain(&VarLenUnicode::type_descriptor()).unwrap_err();
+        assert!(err.contains("converted by the library"), "{err}");
+        assert!(check_plain(&<[VarLenArray<u8>; 2]>::type_descriptor()).is_err());
+
+        let packed =
+            vec![CompoundField::typed::<u8>("a", 0, 0), CompoundField::typed::<u16>("b", 1, 1)];
+        assert!(check_plain(&compound(packed, 3)).is_ok());
+        let padded =
+            vec![CompoundField::typed::<u8>("a", 0, 0), CompoundField::typed::<u32>("b", 4, 1)];
+        assert!(check_plain(&compound(padded, 8)).unwrap_err().contains("padding"));
+        let trailing = vec![CompoundField::typed::<u32>("a", 0, 0)];
+        assert!(check_plain(&compound(trailing, 8)).unwrap_err().contains("padding"));
+        let size = 4 + std::mem::size_of::<ObjectReference2>();
+        let reference = vec![
+            CompoundField::typed::<i32>("a", 0, 0),
+            CompoundField::typed::<ObjectReference2>("r", 4, 1),
+        ];
+        assert!(check_plain(&compound(reference, size)).is_err());
+    }
+
+    #[test]
+    pub fn test_chunk_grid() {
+        // a 5x3 dataset of 1-byte elements in 2x2 chunks
+        let data: Vec<u8> = (0..15).collect();
+        let grid = ChunkGrid::new(&[5, 3], &[2, 2], 1);
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid.offset(0), [0, 0]);
+        assert_eq!(grid.offset(1), [0, 2]);
+        assert_eq!(grid.offset(5), [4, 2]);
+        assert_eq!(grid.gather(&data, &[0, 0]), [0, 1, 3, 4]);
+        assert_eq!(grid.gather(&data, &[0, 2]), [2, 0, 5, 0]);
+        assert_eq!(grid.gather(&data, &[4, 0]), [12, 13, 0, 0]);
+        assert_eq!(grid.gather(&data, &[4, 2]), [14, 0, 0, 0]);
+
+        let data: Vec<u8> = (0..12).collect();
+        let grid = ChunkGrid::new(&[3], &[4], 4);
+        assert_eq!(grid.gather(&data, &[0]), [(0..12).collect::<Vec<_>>(), vec![0; 4]].concat());
+    }
+}
@@ -0,0 +1,453 @@
+//! Datasets written as shard files by independent producers and read through a virtual dataset.
+//!
+//! Each producer process writes its rows of a logical dataset to its own shard file with a
+//! [`ShardWriter`]; no coordination between producers is needed beyond agreeing on the
+//! schema and on which rows each shard covers. Afterwards, [`ShardSet::discover`] finds and
+//! validates the shard files and [`ShardSet::build_vds`] assembles a virtual dataset over them,
+//! so that readers see a single dataset.
+//!
+//! The layout of shard files is stable so that other languages can produce them:
+//!
+//! - shard `i` of the logical dataset `base_name` is the file `"{base_name}.{i}.h5"`, with `i`
+//!   written in decimal without leading zeros;
+//! - the shard's rows are stored in the dataset [`SHARD_DATASET`] of the file, whose first axis
+//!   is the row axis;
+//! - the dataset holds the attributes [`SHARD_INDEX_ATTR`], [`SHARD_COUNT_ATTR`] and
+//!   [`SHARD_ROWS_ATTR`] describing the shard and the rows of the logical dataset it covers.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::sharding::{ShardInfo, ShardSet, ShardWriter};
+//! use hdf5_rt::{DatasetSpec, File, H5Type};
+//! use ndarray::Array2;
+//!
+//! // in producer `i` out of 4, each writing 100 rows
+//! # let i = 0;
+//! let spec = DatasetSpec::new(f64::type_descriptor(), vec![100, 3]);
+//! let info = ShardInfo { index: i, total: 4, rows: u64::from(i) * 100..u64::from(i + 1) * 100 };
+//! let shard = ShardWriter::create("shards", "samples", info, &spec)?;
+//! shard.dataset().write(&Array2::<f64>::zeros((100, 3)))?;
+//! shard.close()?;
+//!
+//! // once all producers are done
+//! let shards = ShardSet::discover("shards", "samples")?;
+//! assert!(shards.gaps().is_empty() && shards.missing_shards().is_empty());
+//! let file = File::create("shards/samples.h5")?;
+//! let samples = shards.build_vds(&file, "samples")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::conventions::{SHARD_COUNT_ATTR, SHARD_DATASET, SHARD_INDEX_ATTR, SHARD_ROWS_ATTR};
+use crate::hl::extents::SimpleExtents;
+use crate::internal_prelude::*;
+
+/// The position of a shard within a sharded dataset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShardInfo {
+    /// The index of the shard, from 0 to `total - 1`.
+    pub index: u32,
+    /// The total number of shards of the dataset.
+    pub total: u32,
+    /// The rows of the logical dataset stored in the shard.
+    pub rows: Range<u64>,
+}
+
+/// A shard file being written by a producer.
+#[derive(Debug)]
+pub struct ShardWriter {
+    file: File,
+    dataset: Dataset,
+    info: ShardInfo,
+}
+
+impl ShardWriter {
+    /// Creates the shard file `"{base_name}.{index}.h5"` in `dir`, holding a dataset created
+    /// from `spec` and the attributes describing the shard.
+    ///
+    /// The first dimension of `spec.shape` must match the number of rows covered by the shard.
+    /// An existing shard file is overwritten.
+    pub fn create(
+        dir: impl AsRef<Path>,
+        base_name: &str,
+        info: ShardInfo,
+        spec: &DatasetSpec,
+    ) -> Result<Self> {
+        let path = shard_path(dir.as_ref(), base_name, info.index);
+        let create = || {
+            check_base_name(base_name)?;
+            ensure!(
+                info.index < info.total,
+                "shard index {} out of range for {} shards",
+                info.index,
+                info.total
+            );
+            ensure!(info.rows.start <= info.rows.end, "invalid row range {:?}", info.rows);
+            ensure!(
+                spec.shape.first().map(|&n| n as u64) == Some(info.rows.end - info.rows.start),
+                "shape {:?} does not match the {} rows covered by the shard",
+                spec.shape,
+                info.rows.end - info.rows.start
+            );
+            let file = File::create(&path)?;
+            let dataset = spec.create(&file, SHARD_DATASET)?;
+            dataset.new_attr::<u32>().create(SHARD_INDEX_ATTR)?.write_scalar(&info.index)?;
+            dataset.new_attr::<u32>().create(SHARD_COUNT_ATTR)?.write_scalar(&info.total)?;
+            let rows = [info.rows.start, info.rows.end];
+            dataset.new_attr_builder().with_data(&rows).create(SHARD_ROWS_ATTR)?;
+            Ok(Self { file, dataset, info })
+        };
+        create().with_context(|| format!("creating shard file '{}'", path.display()))
+    }
+
+    /// Returns the dataset holding the rows of the shard.
+    pub fn dataset(&self) -> &Dataset {
+        &self.dataset
+    }
+
+    /// Returns the position of the shard.
+    pub fn info(&self) -> &ShardInfo {
+        &self.info
+    }
+
+    /// Returns the shard file.
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    /// Closes the shard file.
+    pub fn close(self) -> Result<()> {
+        drop(self.dataset);
+        self.file.close()
+    }
+}
+
+/// A shard file found by [`ShardSet::discover`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shard {
+    /// The path of the shard file.
+    pub path: PathBuf,
+    /// The position of the shard, as recorded in the file.
+    pub info: ShardInfo,
+    /// The shape of the shard dataset.
+    pub shape: Vec<Ix>,
+}
+
+/// The validated shards of a sharded dataset, see the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct ShardSet {
+    shards: Vec<Shard>,
+    spec: DatasetSpec,
+    total: u32,
+}
+
+impl ShardSet {
+    /// Finds the shard files of the dataset `base_name` in `dir` and validates them.
+    ///
+    /// Fails if no shard is found, if the shards disagree on the element type, the trailing
+    /// dimensions or the total number of shards, or if the row ranges of two shards overlap.
+    /// Missing shards are not an error; they are reported by [`gaps`](Self::gaps) and
+    /// [`missing_shards`](Self::missing_shards).
+    pub fn discover(dir: impl AsRef<Path>, base_name: &str) -> Result<Self> {
+        let dir = dir.as_ref();
+        Self::discover_in(dir, base_name).with_context(|| {
+            format!("discovering shards of '{}' in '{}'", base_name, dir.display())
+        })
+    }
+
+    fn discover_in(dir: &Path, base_name: &str) -> Result<Self> {
+        check_base_name(base_name)?;
+        let prefix = format!("{base_name}.");
+        let names = fs::read_dir(dir)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|e| e.file_name()))
+                    .collect::<std::io::Result<Vec<_>>>()
+            })
+            .map_err(|err| format!("unable to list '{}': {}", dir.display(), err))?;
+        let mut indices: Vec<u32> = names
+            .iter()
+            .filter_map(|name| {
+                let n = name.to_str()?.strip_prefix(&prefix)?.strip_suffix(".h5")?;
+                n.parse().ok().filter(|i: &u32| i.to_string() == n)
+            })
+            .collect();
+        indices.sort_unstable();
+        let read = |index| {
+            let path = shard_path(dir, base_name, index);
+            read_shard(&path, index)
+                .with_context(|| format!("reading shard file '{}'", path.display()))
+        };
+        let Some((&first_index, indices)) = indices.split_first() else {
+            fail!("no shard files found");
+        };
+        let (first, spec) = read(first_index)?;
+        let total = first.info.total;
+        let mut shards = vec![first];
+        for &index in indices {
+            let (shard, shard_spec) = read(index)?;
+            check_compatible(&shards[0], &spec, &shard, &shard_spec)?;
+            shards.push(shard);
+        }
+        shards.sort_by_key(|shard: &Shard| (shard.info.rows.start, shard.info.rows.end));
+        for pair in shards.windows(2) {
+            ensure!(
+                pair[1].info.rows.start >= pair[0].info.rows.end,
+                "rows {:?} of '{}' overlap rows {:?} of '{}'",
+                pair[1].info.rows,
+                pair[1].path.display(),
+                pair[0].info.rows,
+                pair[0].path.display()
+            );
+        }
+        Ok(Self { shards, spec, total })
+    }
+
+    /// Returns the shards, ordered by their first row.
+    pub fn shards(&self) -> &[Shard] {
+        &self.shards
+    }
+
+    /// Returns the total number of shards recorded in the shard files.
+    pub fn total_shards(&self) -> u32 {
+        self.total
+    }
+
+    /// Returns the spec of the shard datasets, with the shape of the first shard.
+    pub fn spec(&self) -> &DatasetSpec {
+        &self.spec
+    }
+
+    /// Returns the number of rows of the logical dataset, i.e. the end of the last covered
+    /// row range.
+    pub fn num_rows(&self) -> u64 {
+        self.shards.iter().map(|shard| shard.info.rows.end).max().unwrap_or(0)
+    }
+
+    /// Returns the ranges of rows below [`num_rows`](Self::num_rows) not covered by any shard.
+    pub fn gaps(&self) -> Vec<Range<u64>> {
+        let mut gaps = vec![];
+        let mut next = 0;
+        for shard in &self.shards {
+            if shard.info.rows.start > next {
+                gaps.push(next..shard.info.rows.start);
+            }
+            next = next.max(shard.info.rows.end);
+        }
+        gaps
+    }
+
+    /// Returns the indices of the shards that were not found, in increasing order.
+    pub fn missing_shards(&self) -> Vec<u32> {
+        (0..self.total)
+            .filter(|&index| self.shards.iter().all(|shard| shard.info.index != index))
+            .collect()
+    }
+
+    /// Creates the virtual dataset `name` in `parent`, mapping the rows of each shard.
+    ///
+    /// Rows in [gaps](Self::gaps) read as the fill value of the shard datasets. Shard files
+    /// in the directory of the file holding `parent` are referenced by their file names, so
+    /// that the directory can be moved as a whole; other shard files are referenced by their
+    /// absolute paths.
+    pub fn build_vds(&self, parent: &Group, name: &str) -> Result<Dataset> {
+        h5lock!({
+            let spec = &self.spec;
+            let mut shape = spec.shape.clone();
+            shape[0] = usize::try_from(self.num_rows())
+                .map_err(|_| format!("{} rows do not fit in memory indices", self.num_rows()))?;
+            let target_dir = fs::canonicalize(parent.filename())
+                .ok()
+                .and_then(|path| path.parent().map(Path::to_path_buf));
+            let mut builder = parent.new_dataset_builder().empty_as(&spec.dtype);
+            if let Some(fill) = spec.fill {
+                builder = builder.fill_value(fill.to_value(&spec.dtype)?);
+            }
+            for shard in &self.shards {
+                let path = fs::canonicalize(&shard.path).map_err(|err| {
+                    format!("unable to resolve '{}': {}", shard.path.display(), err)
+                })?;
+                let src_filename = match (&target_dir, path.parent(), path.file_name()) {
+                    (Some(target_dir), Some(dir), Some(file_name)) if target_dir == dir => {
+                        PathBuf::from(file_name)
+                    }
+                    _ => path.clone(),
+                };
+                let src_filename = src_filename
+                    .to_str()
+                    .ok_or_else(|| format!("non-UTF-8 shard path '{}'", path.display()))?
+                    .to_owned();
+                let rows = shard.info.rows.start as Ix..shard.info.rows.end as Ix;
+                let vds_selection: Vec<SliceOrIndex> = std::iter::once(rows.into())
+                    .chain(shape[1..].iter().map(|_| (..).into()))
+                    .collect();
+                builder = builder.virtual_map(
+                    src_filename,
+                    SHARD_DATASET,
+                    SimpleExtents::fixed(&shard.shape),
+                    Selection::All,
+                    SimpleExtents::fixed(&shape),
+                    Hyperslab::from(vds_selection),
+                );
+            }
+            builder.shape(SimpleExtents::fixed(&shape)).create(name)
+        })
+        .with_context(|| format!("building virtual dataset '{name}' over shards"))
+    }
+}
+
+fn check_base_name(base_name: &str) -> Result<()> {
+    ensure!(
+        !base_name.is_empty() && !base_name.contains(['/', '\\']),
+        "invalid shard base name '{}'",
+        base_name
+    );
+    Ok(())
+}
+
+fn shard_path(dir: &Path, base_name: &str, index: u32) -> PathBuf {
+    dir.join(format!("{base_name}.{index}.h5"))
+}
+
+/// Reads the shard attributes and the schema of the shard file `path`.
+fn read_shard(path: &Path, index: u32) -> Result<(Shard, DatasetSpec)> {
+    let file = File::open(path)?;
+    let dataset = file.dataset(SHARD_DATASET)?;
+    let info = ShardInfo {
+        index: dataset.attr(SHARD_INDEX_ATTR)?.read_scalar()?,
+        total: dataset.attr(SHARD_COUNT_ATTR)?.read_scalar()?,
+        rows: match dataset.attr(SHARD_ROWS_ATTR)?.read_raw::<u64>()?[..] {
+            [start, end] if start <= end => start..end,
+            ref rows => fail!("invalid row range {:?}", rows),
+        },
+    };
+    ensure!(
+        info.index == index,
+        "shard index {} does not match the file name index {}",
+        info.index,
+        index
+    );
+    ensure!(
+        info.index < info.total,
+        "shard index {} out of range for {} shards",
+        index,
+        info.total
+    );
+    let spec = DatasetSpec::from_dataset(&dataset)?;
+    ensure!(
+        spec.shape.first().map(|&n| n as u64) == Some(info.rows.end - info.rows.start),
+        "shape {:?} does not match the row range {:?}",
+        spec.shape,
+        info.rows
+    );
+    drop(dataset);
+    file.close()?;
+    Ok((Shard { path: path.to_owned(), info, shape: spec.shape.clone() }, spec))
+}
+
+fn check_compatible(
+    first: &Shard,
+    spec: &DatasetSpec,
+    shard: &Shard,
+    other: &DatasetSpec,
+) -> Result<()> {
+    let describe = |shard: &Shard| shard.path.display().to_string();
+    ensure!(
+        spec.dtype == other.dtype,
+        "element type {} of '{}' differs from {} of '{}'",
+        other.dtype,
+        describe(shard),
+        spec.dtype,
+        describe(first)
+    );
+    ensure!(
+        spec.shape[1..] == other.shape[1..],
+        "shape {:?} of '{}' is incompatible with {:?} of '{}'",
+        other.shape,
+        describe(shard),
+        spec.shape,
+        describe(first)
+    );
+    ensure!(
+        shard.info.total == first.info.total,
+        "'{}' records {} shards while '{}' records {}",
+        describe(shard),
+        shard.info.total,
+        describe(first),
+        first.info.total
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+
+    use ndarray::{s, Array2};
+
+    use super::{ShardInfo, ShardSet, ShardWriter};
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_shards() {
+        with_tmp_dir(|dir| {
+            let spec = DatasetSpec::new(i64::type_descriptor(), vec![100, 3]);
+            for i in 0..4u32 {
+                let rows = u64::from(i) * 100..u64::from(i + 1) * 100;
+                let info = ShardInfo { index: i, total: 4, rows: rows.clone() };
+                let shard = ShardWriter::create(&dir, "x", info, &spec).unwrap();
+                let data = Array2::from_shape_fn((100, 3), |(r, c)| {
+                    (rows.start as i64 + r as i64) * 10 + c as i64
+                });
+                shard.dataset().write(&data).unwrap();
+                shard.close().unwrap();
+            }
+            let info = ShardInfo { index: 4, total: 4, rows: 0..100 };
+            assert_err!(ShardWriter::create(&dir, "x", info, &spec), "out of range");
+            let info = ShardInfo { index: 0, total: 4, rows: 0..50 };
+            assert_err!(ShardWriter::create(&dir, "y", info, &spec), "does not match");
+
+            let shards = ShardSet::discover(&dir, "x").unwrap();
+            assert_eq!(shards.shards().len(), 4);
+            assert_eq!(shards.total_shards(), 4);
+            assert_eq!(shards.num_rows(), 400);
+            assert!(shards.gaps().is_empty());
+            assert!(shards.missing_shards().is_empty());
+
+            let file = File::create(dir.join("x.h5")).unwrap();
+            let vds = shards.build_vds(&file, "x").unwrap();
+            assert_eq!(vds.shape().unwrap(), [400, 3]);
+            let rows = vds.read_slice_2d::<i64, _>(s![95..205, ..]).unwrap();
+            let expected =
+                Array2::from_shape_fn((110, 3), |(r, c)| (95 + r as i64) * 10 + c as i64);
+            assert_eq!(rows, expected);
+            drop(vds);
+            file.close().unwrap();
+
+            // an overlapping shard is rejected
+            let info = ShardInfo { index: 0, total: 4, rows: 50..150 };
+            ShardWriter::create(&dir, "z", info, &spec).unwrap().close().unwrap();
+            let info = ShardInfo { index: 1, total: 4, rows: 100..200 };
+            ShardWriter::create(&dir, "z", info, &spec).unwrap().close().unwrap();
+            assert_err!(ShardSet::discover(&dir, "z"), "overlap");
+            assert_err!(ShardSet::discover(&dir, "w"), "no shard files found");
+
+            // a deleted shard shows up as a gap
+            fs::remove_file(dir.join("x.1.h5")).unwrap();
+            let shards = ShardSet::discover(&dir, "x").unwrap();
+            assert_eq!(shards.gaps(), [100..200]);
+            assert_eq!(shards.missing_shards(), [1]);
+            fs::remove_file(dir.join("x.3.h5")).unwrap();
+            let shards = ShardSet::discover(&dir, "x").unwrap();
+            assert_eq!(shards.num_rows(), 300);
+            assert_eq!(shards.gaps(), [100..200]);
+            assert_eq!(shards.missing_shards(), [1, 3]);
+        })
+    }
+}
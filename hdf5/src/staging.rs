@@ -0,0 +1,270 @@
+//! Staging groups of objects that should appear in a file all at once.
+//!
+//! HDF5 has no transactions, so a writer that must guarantee that either all or none of a set
+//! of datasets and attributes appear in a file (e.g. the parts of a checkpoint) writes them
+//! into a hidden staging group and links it into place when done:
+//!
+//! - [`Checkpoint::begin`] creates the staging group as a member of the root group named
+//!   [`STAGING_PREFIX`] followed by a unique identifier;
+//! - [`Checkpoint::commit`] flushes the file and then moves the staging group to the final
+//!   path with a single link operation. A group already at the final path is first moved
+//!   aside (to the staging name followed by [`STAGING_REPLACED_SUFFIX`]) and deleted after
+//!   the new group is in place;
+//! - [`Checkpoint::abort`], or dropping the checkpoint without committing, deletes the
+//!   staging group.
+//!
+//! # Guarantees
+//!
+//! Within the HDF5 metadata the final path switches atomically: it either does not exist,
+//! or holds the previous group, or holds the complete new group, and since the file is
+//! flushed before the move, the staged data reaches the file before the link does.
+//!
+//! This is not a guarantee against the process or the OS crashing while the library writes
+//! to the file. HDF5 writes modified metadata in no particular order when flushing, so a
+//! crash during a flush can leave the file inconsistent or unreadable. If the writer stops
+//! between the steps of a commit, the staging group (or the previous group moved aside) is
+//! left in the file, and [`Checkpoint::find_stale`] finds it for cleanup. When a group is
+//! replaced, there is a short window between moving the previous group aside and moving the
+//! new one in during which the final path does not exist.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::{staging::Checkpoint, File};
+//! use ndarray::arr1;
+//!
+//! let file = File::open_rw("run.h5")?;
+//! let checkpoint = Checkpoint::begin(&file, "checkpoint")?;
+//! checkpoint.new_dataset_builder().with_data(&arr1(&[1.0, 2.0])).create("state")?;
+//! checkpoint.new_attr::<u64>().create("step")?.write_scalar(&1000)?;
+//! let group = checkpoint.commit()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ops::Deref;
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::conventions::{STAGING_PREFIX, STAGING_REPLACED_SUFFIX};
+use crate::internal_prelude::*;
+
+#[cfg(test)]
+thread_local! {
+    /// Makes the next commit on this thread fail after flushing, before moving any link.
+    static FAIL_BEFORE_MOVE: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// A staging group that is moved to its final path on [`commit`](Self::commit), see the
+/// [module documentation](self).
+///
+/// The checkpoint dereferences to the staging group, in which the objects are created.
+#[derive(Debug)]
+pub struct Checkpoint {
+    file: File,
+    group: Group,
+    staging_path: String,
+    final_path: String,
+    done: bool,
+}
+
+impl Checkpoint {
+    /// Creates a staging group in `file` for a group to be committed to `final_path`.
+    ///
+    /// The parent group of `final_path` must exist when committing.
+    pub fn begin(file: &File, final_path: &str) -> Result<Self> {
+        let name = final_path.trim_matches('/');
+        ensure!(!name.is_empty(), "invalid checkpoint path '{}'", final_path);
+        let staging_path = format!("/{STAGING_PREFIX}{}", unique_id());
+        let group = file
+            .create_group(&staging_path)
+            .with_context(|| format!("creating the staging group for '{final_path}'"))?;
+        Ok(Self {
+            file: file.clone(),
+            group,
+            staging_path,
+            final_path: format!("/{name}"),
+            done: false,
+        })
+    }
+
+    /// Returns the staging group.
+    pub fn group(&self) -> &Group {
+        &self.group
+    }
+
+    /// Returns the absolute path of the staging group.
+    pub fn staging_path(&self) -> &str {
+        &self.staging_path
+    }
+
+    /// Returns the absolute path the group is committed to.
+    pub fn final_path(&self) -> &str {
+        &self.final_path
+    }
+
+    /// Flushes the file and moves the staging group to the final path, replacing any object
+    /// there, and returns the committed group.
+    ///
+    /// If the commit fails before the staging group is moved, any previous object at the
+    /// final path is restored and the staging group is left in the file, see
+    /// [`find_stale`](Self::find_stale).
+    pub fn commit(mut self) -> Result<Group> {
+        // whatever happens, the staging group is not deleted from here on
+        self.done = true;
+        let (file, staging, target) = (&self.file, &self.staging_path, &self.final_path);
+        h5lock!({
+            file.flush()?;
+            #[cfg(test)]
+            ensure!(!FAIL_BEFORE_MOVE.with(|fail| fail.replace(false)), "simulated failure");
+            let replaced = format!("{staging}{STAGING_REPLACED_SUFFIX}");
+            let has_previous = file.link_exists(target);
+            if has_previous {
+                file.relink(target, &replaced)
+                    .with_context(|| format!("moving '{target}' aside"))?;
+            }
+            if let Err(err) = file.relink(staging, target) {
+                if has_previous {
+                    let _ = file.relink(&replaced, target);
+                }
+                return Err(err);
+            }
+            if has_previous {
+                file.delete_recursive(&replaced)
+                    .with_context(|| format!("deleting the replaced group '{replaced}'"))?;
+            }
+            file.flush()?;
+            file.group(target)
+        })
+        .with_context(|| format!("committing '{staging}' to '{target}'"))
+    }
+
+    /// Deletes the staging group and everything in it.
+    pub fn abort(mut self) -> Result<()> {
+        self.done = true;
+        self.file
+            .delete_recursive(&self.staging_path)
+            .with_context(|| format!("deleting the staging group '{}'", self.staging_path))
+    }
+
+    /// Returns the absolute paths of the staging groups left in `file` by checkpoints that
+    /// were neither committed nor aborted, including previous groups moved aside by an
+    /// interrupted commit. They can be deleted with
+    /// [`Group::delete_recursive`](crate::Group::delete_recursive).
+    pub fn find_stale(file: &File) -> Result<Vec<String>> {
+        Ok(file
+            .member_names()?
+            .into_iter()
+            .filter(|name| name.starts_with(STAGING_PREFIX))
+            .map(|name| format!("/{name}"))
+            .collect())
+    }
+}
+
+impl Deref for Checkpoint {
+    type Target = Group;
+
+    fn deref(&self) -> &Group {
+        &self.group
+    }
+}
+
+impl Drop for Checkpoint {
+    fn drop(&mut self) {
+        if !self.done {
+            let _ = self.file.delete_recursive(&self.staging_path);
+        }
+    }
+}
+
+/// Returns an identifier that is unique among the checkpoints of all processes in practice.
+fn unique_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{:08x}{:016x}{count:04x}", process::id(), nanos as u64)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ndarray::arr1;
+
+    use super::{Checkpoint, FAIL_BEFORE_MOVE};
+    use crate::internal_prelude::*;
+
+    fn read_step(group: &Group) -> u64 {
+        group.attr("step").unwrap().read_scalar().unwrap()
+    }
+
+    fn write_checkpoint(checkpoint: &Checkpoint, step: u64) {
+        let data = arr1(&[step as f64; 4]);
+        checkpoint.new_dataset_builder().with_data(&data).create("state").unwrap();
+        checkpoint.create_group("nested").unwrap().new_dataset::<u8>().create("x").unwrap();
+        checkpoint.new_attr::<u64>().create("step").unwrap().write_scalar(&step).unwrap();
+    }
+
+    #[test]
+    pub fn test_checkpoint_commit_abort() {
+        with_tmp_file(|file| {
+            let checkpoint = Checkpoint::begin(&file, "ckpt").unwrap();
+            assert!(checkpoint.staging_path().starts_with("/.staging-"));
+            assert_eq!(checkpoint.final_path(), "/ckpt");
+            write_checkpoint(&checkpoint, 1);
+            assert!(!file.link_exists("ckpt"));
+            let group = checkpoint.commit().unwrap();
+            assert_eq!(group.name(), "/ckpt");
+            assert_eq!(read_step(&group), 1);
+            assert_eq!(group.member_names().unwrap(), ["nested", "state"]);
+            assert_eq!(file.dataset("ckpt/state").unwrap().read_raw::<f64>().unwrap(), [1.0; 4]);
+
+            // replacing a previous checkpoint leaves only the new one
+            let checkpoint = Checkpoint::begin(&file, "/ckpt").unwrap();
+            write_checkpoint(&checkpoint, 2);
+            checkpoint.commit().unwrap();
+            assert_eq!(read_step(&file.group("ckpt").unwrap()), 2);
+            assert_eq!(file.member_names().unwrap(), ["ckpt"]);
+
+            // aborting or dropping leaves no trace
+            let checkpoint = Checkpoint::begin(&file, "ckpt").unwrap();
+            write_checkpoint(&checkpoint, 3);
+            checkpoint.abort().unwrap();
+            drop(Checkpoint::begin(&file, "other").unwrap());
+            assert_eq!(file.member_names().unwrap(), ["ckpt"]);
+            assert!(Checkpoint::find_stale(&file).unwrap().is_empty());
+            assert_eq!(read_step(&file.group("ckpt").unwrap()), 2);
+
+            assert_err!(Checkpoint::begin(&file, "/"), "invalid checkpoint path");
+        })
+    }
+
+    #[test]
+    pub fn test_checkpoint_interrupted() {
+        with_tmp_file(|file| {
+            let checkpoint = Checkpoint::begin(&file, "ckpt").unwrap();
+            write_checkpoint(&checkpoint, 1);
+            checkpoint.commit().unwrap();
+
+            // a failure between the flush and the move keeps the previous checkpoint
+            let checkpoint = Checkpoint::begin(&file, "ckpt").unwrap();
+            write_checkpoint(&checkpoint, 2);
+            let staging = checkpoint.staging_path().to_owned();
+            FAIL_BEFORE_MOVE.with(|fail| fail.set(true));
+            assert_err!(checkpoint.commit(), "simulated failure");
+            assert_eq!(read_step(&file.group("ckpt").unwrap()), 1);
+            assert_eq!(Checkpoint::find_stale(&file).unwrap(), [staging.clone()]);
+            assert_eq!(read_step(&file.group(&staging).unwrap()), 2);
+
+            // so does a failed move
+            let checkpoint = Checkpoint::begin(&file, "missing/ckpt").unwrap();
+            assert!(checkpoint.commit().is_err());
+            assert_eq!(Checkpoint::find_stale(&file).unwrap().len(), 2);
+
+            for path in Checkpoint::find_stale(&file).unwrap() {
+                file.delete_recursive(&path).unwrap();
+            }
+            assert_eq!(file.member_names().unwrap(), ["ckpt"]);
+        })
+    }
+}
@@ -0,0 +1,188 @@
+//! Polling datasets that another process appends to under SWMR.
+//!
+//! A [`PollingReader`] remembers how many rows of a dataset it has returned and, on each
+//! [`poll`](PollingReader::poll), refreshes the dataset metadata and reads only the rows
+//! appended since, along the first axis. Datasets have to be unlimited along that axis.
+//!
+//! A writer that restarts by recreating the file should store an increasing number in the
+//! [`SWMR_GENERATION_ATTR`] attribute of the dataset or of the root group, so that readers can
+//! tell the new file from the old one with
+//! [`generation_changed`](PollingReader::generation_changed). Without the attribute, the
+//! dataset's address and creation times identify its generation, which may not change when a
+//! file is recreated with the same layout.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::{swmr::PollingReader, File, OpenMode};
+//!
+//! let file = File::open_as("log.h5", OpenMode::ReadSWMR)?;
+//! let mut reader = PollingReader::new(&file.dataset("events")?)?;
+//! loop {
+//!     if reader.generation_changed()? {
+//!         println!("writer restarted");
+//!     }
+//!     if let Some(rows) = reader.poll::<f64>()? {
+//!         println!("{} new rows", rows.shape()[0]);
+//!     }
+//!     std::thread::sleep(std::time::Duration::from_secs(1));
+//! }
+//! # }
+//! ```
+
+use ndarray::ArrayD;
+
+use crate::conventions::SWMR_GENERATION_ATTR;
+use crate::internal_prelude::*;
+use crate::sys::h5a::H5Aexists;
+use crate::sys::h5f::{H5Fget_intent, H5F_ACC_SWMR_READ};
+
+/// Identifies one incarnation of a polled dataset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Generation {
+    /// The value of the [`SWMR_GENERATION_ATTR`] attribute.
+    Attribute(u64),
+    /// The location and creation times of the dataset, if there is no such attribute.
+    Object { token: LocationToken, ctime: i64, btime: i64 },
+}
+
+impl Generation {
+    fn of(ds: &Dataset) -> Result<Self> {
+        let root = ds.file()?;
+        let locations: [&Location; 2] = [ds, &root];
+        for loc in locations {
+            let name = to_cstring(SWMR_GENERATION_ATTR)?;
+            if h5call!(H5Aexists(loc.id(), name.as_ptr()))? > 0 {
+                let generation = loc.attr(SWMR_GENERATION_ATTR)?.read_scalar::<u64>()?;
+                return Ok(Self::Attribute(generation));
+            }
+        }
+        let info = ds.loc_info()?;
+        Ok(Self::Object { token: info.token, ctime: info.ctime, btime: info.btime })
+    }
+}
+
+/// Reads the rows appended to a dataset since the previous poll, see the
+/// [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct PollingReader {
+    dataset: Dataset,
+    rows_seen: usize,
+    generation: Generation,
+}
+
+impl PollingReader {
+    /// Creates a reader for a dataset that is unlimited along its first axis.
+    ///
+    /// The rows present when the reader is created count as new, so that the first poll
+    /// returns them.
+    pub fn new(dataset: &Dataset) -> Result<Self> {
+        Self::check_extendable(dataset)
+            .with_context(|| format!("polling dataset '{}'", dataset.name()))?;
+        Ok(Self { dataset: dataset.clone(), rows_seen: 0, generation: Generation::of(dataset)? })
+    }
+
+    fn check_extendable(dataset: &Dataset) -> Result<()> {
+        let maxdims = dataset.space()?.maxdims();
+        ensure!(!maxdims.is_empty(), "a scalar dataset has no rows");
+        ensure!(
+            maxdims[0].is_none(),
+            "the dataset must be unlimited along the first axis, got maximum shape {:?}",
+            maxdims
+        );
+        Ok(())
+    }
+
+    /// Returns the polled dataset.
+    pub fn dataset(&self) -> &Dataset {
+        &self.dataset
+    }
+
+    /// Returns the number of rows returned so far.
+    pub fn rows_seen(&self) -> usize {
+        self.rows_seen
+    }
+
+    /// Refreshes the dataset and reads the rows appended since the previous poll, or returns
+    /// `None` if there are none.
+    ///
+    /// Fails if the dataset has fewer rows than were already returned, which happens when the
+    /// writer has restarted; [`generation_changed`](Self::generation_changed) then switches to
+    /// the new file.
+    pub fn poll<T: H5Type>(&mut self) -> Result<Option<ArrayD<T>>> {
+        self.dataset.refresh()?;
+        let shape = self.dataset.shape()?;
+        let rows = shape[0];
+        ensure!(
+            rows >= self.rows_seen,
+            "dataset '{}' shrank from {} to {} rows, the writer may have recreated the file",
+            self.dataset.name(),
+            self.rows_seen,
+            rows
+        );
+        if rows == self.rows_seen {
+            return Ok(None);
+        }
+        let mut slices: Vec<SliceOrIndex> = vec![(self.rows_seen..rows).into()];
+        slices.extend(shape[1..].iter().map(|_| SliceOrIndex::from(..)));
+        let block = self.dataset.read_slice(Hyperslab::from(slices))?;
+        self.rows_seen = rows;
+        Ok(Some(block))
+    }
+
+    /// Reopens the file by name and checks whether it is a new generation of the file the
+    /// dataset was read from.
+    ///
+    /// If it is, the reader switches to the dataset of the same name in the new file and
+    /// starts over from its first row.
+    pub fn generation_changed(&mut self) -> Result<bool> {
+        let file = self.dataset.file()?;
+        let intent = h5get!(H5Fget_intent(file.id()): c_uint)?;
+        let mode =
+            if intent & H5F_ACC_SWMR_READ == 0 { OpenMode::Read } else { OpenMode::ReadSWMR };
+        let name = self.dataset.name();
+        let reopened = File::open_as(file.filename(), mode)
+            .and_then(|file| file.dataset(&name))
+            .with_context(|| format!("reopening dataset '{name}'"))?;
+        let generation = Generation::of(&reopened)?;
+        if generation == self.generation {
+            return Ok(false);
+        }
+        Self::check_extendable(&reopened)
+            .with_context(|| format!("polling dataset '{name}' in the new file"))?;
+        self.dataset = reopened;
+        self.generation = generation;
+        self.rows_seen = 0;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::PollingReader;
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_polling_reader_validation() {
+        with_tmp_file(|file| {
+            let fixed = file.new_dataset::<u8>().shape((4, 2)).create("fixed").unwrap();
+            assert_err!(PollingReader::new(&fixed), "must be unlimited along the first axis");
+            let scalar = file.new_dataset::<u8>().create("scalar").unwrap();
+            assert_err!(PollingReader::new(&scalar), "a scalar dataset has no rows");
+            let ds = file.new_dataset::<u8>().chunk((4, 2)).shape((2.., 2)).create("x").unwrap();
+            ds.write(&ndarray::arr2(&[[1, 2], [3, 4]])).unwrap();
+
+            let mut reader = PollingReader::new(&ds).unwrap();
+            let rows = reader.poll::<u8>().unwrap().unwrap();
+            assert_eq!(rows, ndarray::arr2(&[[1, 2], [3, 4]]).into_dyn());
+            assert!(reader.poll::<u8>().unwrap().is_none());
+            ds.resize((3, 2)).unwrap();
+            ds.write_slice(&ndarray::arr2(&[[5, 6]]), ndarray::s![2.., ..]).unwrap();
+            let rows = reader.poll::<u8>().unwrap().unwrap();
+            assert_eq!(rows, ndarray::arr2(&[[5, 6]]).into_dyn());
+            assert_eq!(reader.rows_seen(), 3);
+            assert!(!reader.generation_changed().unwrap());
+        })
+    }
+}
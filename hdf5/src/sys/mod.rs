@@ -5,8 +5,10 @@
 //!
 //! For build-time linking, use the upstream hdf5-metno crate directly.
 
-mod runtime;
+mod preflight;
+pub(crate) mod runtime;
 
+pub use preflight::{init_checked, preflight, MissingSymbol, PreflightReport, SymbolKind};
 pub use runtime::*;
 
 // Re-export submodules for API compatibility
@@ -21,9 +23,9 @@ pub mod h5 {
 
 pub mod h5a {
     pub use super::runtime::{
-        H5A_info_t, H5A_operator2_t, H5Aclose, H5Acreate2, H5Adelete, H5Aexists, H5Aget_name,
-        H5Aget_num_attrs, H5Aget_space, H5Aget_storage_size, H5Aget_type, H5Aiterate2, H5Aopen,
-        H5Aopen_by_idx, H5Aread, H5Awrite,
+        H5A_info_t, H5A_operator2_t, H5Aclose, H5Acreate2, H5Adelete, H5Adelete_by_idx, H5Aexists,
+        H5Aget_name, H5Aget_num_attrs, H5Aget_space, H5Aget_storage_size, H5Aget_type, H5Aiterate2,
+        H5Aopen, H5Aopen_by_idx, H5Aread, H5Awrite,
     };
 }
 
@@ -41,10 +43,11 @@ pub mod h5c {
 
 pub mod h5d {
     pub use super::runtime::{
-        H5D_alloc_time_t, H5D_fill_time_t, H5D_fill_value_t, H5D_layout_t, H5Dclose, H5Dcreate2,
-        H5Dcreate_anon, H5Dflush, H5Dget_access_plist, H5Dget_chunk_info, H5Dget_create_plist,
-        H5Dget_num_chunks, H5Dget_offset, H5Dget_space, H5Dget_storage_size, H5Dget_type, H5Dopen2,
-        H5Dread, H5Drefresh, H5Dset_extent, H5Dwrite,
+        H5D_alloc_time_t, H5D_fill_time_t, H5D_fill_value_t, H5D_layout_t, H5D_space_status_t,
+        H5Dclose, H5Dcreate2, H5Dcreate_anon, H5Dflush, H5Dget_access_plist, H5Dget_chunk_info,
+        H5Dget_create_plist, H5Dget_num_chunks, H5Dget_offset, H5Dget_space, H5Dget_space_status,
+        H5Dget_storage_size, H5Dget_type, H5Dopen2, H5Dread, H5Drefresh, H5Dset_extent,
+        H5Dvlen_reclaim, H5Dwrite, H5Dwrite_chunk,
     };
 }
 
@@ -111,6 +114,7 @@ pub mod h5e {
         H5E_CANTLIST,
         H5E_CANTLOAD,
         H5E_CANTLOCK,
+        H5E_CANTLOCKFILE,
         H5E_CANTMARKDIRTY,
         H5E_CANTMERGE,
         H5E_CANTMODIFY,
@@ -220,11 +224,13 @@ pub mod h5e {
 
 pub mod h5f {
     pub use super::runtime::{
-        H5F_close_degree_t, H5F_fspace_strategy_t, H5F_libver_t, H5F_mem_t, H5Fclose, H5Fcreate,
-        H5Fflush, H5Fget_access_plist, H5Fget_create_plist, H5Fget_filesize, H5Fget_freespace,
-        H5Fget_intent, H5Fget_name, H5Fget_obj_count, H5Fget_obj_ids, H5Fopen, H5Fstart_swmr_write,
-        H5F_ACC_CREAT, H5F_ACC_DEFAULT, H5F_ACC_EXCL, H5F_ACC_RDONLY, H5F_ACC_RDWR,
-        H5F_ACC_SWMR_READ, H5F_ACC_SWMR_WRITE, H5F_ACC_TRUNC, H5F_FAMILY_DEFAULT,
+        H5F_close_degree_t, H5F_fspace_strategy_t, H5F_info2_free_t, H5F_info2_sohm_t,
+        H5F_info2_super_t, H5F_info2_t, H5F_libver_t, H5F_mem_t, H5Fclear_elink_file_cache,
+        H5Fclose, H5Fcreate, H5Fflush, H5Fget_access_plist, H5Fget_create_plist, H5Fget_file_image,
+        H5Fget_filesize, H5Fget_freespace, H5Fget_info2, H5Fget_intent, H5Fget_name,
+        H5Fget_obj_count, H5Fget_obj_ids, H5Fget_vfd_handle, H5Fopen, H5Freopen,
+        H5Fstart_swmr_write, H5F_ACC_CREAT, H5F_ACC_DEFAULT, H5F_ACC_EXCL, H5F_ACC_RDONLY,
+        H5F_ACC_RDWR, H5F_ACC_SWMR_READ, H5F_ACC_SWMR_WRITE, H5F_ACC_TRUNC, H5F_FAMILY_DEFAULT,
         H5F_LIBVER_LATEST, H5F_OBJ_ALL, H5F_OBJ_ATTR, H5F_OBJ_DATASET, H5F_OBJ_DATATYPE,
         H5F_OBJ_FILE, H5F_OBJ_GROUP, H5F_OBJ_LOCAL, H5F_SCOPE_GLOBAL, H5F_SCOPE_LOCAL,
         H5F_UNLIMITED,
@@ -233,34 +239,37 @@ pub mod h5f {
 
 pub mod h5fd {
     pub use super::runtime::{
-        H5FD_LOG_ALL, H5FD_LOG_ALLOC, H5FD_LOG_FILE_IO, H5FD_LOG_FILE_READ, H5FD_LOG_FILE_WRITE,
-        H5FD_LOG_FLAVOR, H5FD_LOG_FREE, H5FD_LOG_LOC_IO, H5FD_LOG_LOC_READ, H5FD_LOG_LOC_SEEK,
-        H5FD_LOG_LOC_WRITE, H5FD_LOG_META_IO, H5FD_LOG_NUM_IO, H5FD_LOG_NUM_READ,
-        H5FD_LOG_NUM_SEEK, H5FD_LOG_NUM_TRUNCATE, H5FD_LOG_NUM_WRITE, H5FD_LOG_TIME_CLOSE,
-        H5FD_LOG_TIME_IO, H5FD_LOG_TIME_OPEN, H5FD_LOG_TIME_READ, H5FD_LOG_TIME_SEEK,
-        H5FD_LOG_TIME_STAT, H5FD_LOG_TIME_TRUNCATE, H5FD_LOG_TIME_WRITE, H5FD_LOG_TRUNCATE,
-        H5FD_MEM_NTYPES,
+        H5FD_file_image_callbacks_t, H5FD_file_image_op_t, H5FD_LOG_ALL, H5FD_LOG_ALLOC,
+        H5FD_LOG_FILE_IO, H5FD_LOG_FILE_READ, H5FD_LOG_FILE_WRITE, H5FD_LOG_FLAVOR, H5FD_LOG_FREE,
+        H5FD_LOG_LOC_IO, H5FD_LOG_LOC_READ, H5FD_LOG_LOC_SEEK, H5FD_LOG_LOC_WRITE,
+        H5FD_LOG_META_IO, H5FD_LOG_NUM_IO, H5FD_LOG_NUM_READ, H5FD_LOG_NUM_SEEK,
+        H5FD_LOG_NUM_TRUNCATE, H5FD_LOG_NUM_WRITE, H5FD_LOG_TIME_CLOSE, H5FD_LOG_TIME_IO,
+        H5FD_LOG_TIME_OPEN, H5FD_LOG_TIME_READ, H5FD_LOG_TIME_SEEK, H5FD_LOG_TIME_STAT,
+        H5FD_LOG_TIME_TRUNCATE, H5FD_LOG_TIME_WRITE, H5FD_LOG_TRUNCATE, H5FD_MEM_NTYPES,
     };
 }
 
 pub mod h5g {
     pub use super::runtime::{
-        H5G_info_t, H5Gclose, H5Gcreate2, H5Gget_create_plist, H5Gget_info, H5Gopen2,
+        H5G_info_t, H5Gclose, H5Gcreate2, H5Gcreate_anon, H5Gget_create_plist, H5Gget_info,
+        H5Gopen2, H5G_STORAGE_TYPE_COMPACT, H5G_STORAGE_TYPE_DENSE, H5G_STORAGE_TYPE_SYMBOL_TABLE,
+        H5G_STORAGE_TYPE_UNKNOWN,
     };
 }
 
 pub mod h5i {
     pub use super::runtime::{
-        hid_t, H5I_type_t, H5Idec_ref, H5Iget_file_id, H5Iget_name, H5Iget_ref, H5Iget_type,
-        H5Iinc_ref, H5Iis_valid, H5I_INVALID_HID,
+        hid_t, H5I_free_t, H5I_type_raw_t, H5I_type_t, H5Idec_ref, H5Idestroy_type, H5Iget_file_id,
+        H5Iget_name, H5Iget_ref, H5Iget_type, H5Iinc_ref, H5Iis_valid, H5Iobject_verify,
+        H5Iregister, H5Iregister_type, H5Iremove_verify, H5I_INVALID_HID,
     };
 }
 
 pub mod h5l {
     pub use super::runtime::{
         H5L_info2_t, H5L_info_t, H5L_iterate2_t, H5L_iterate_t, H5L_type_t, H5Lcreate_external,
-        H5Lcreate_hard, H5Lcreate_soft, H5Ldelete, H5Lexists, H5Lget_info2, H5Literate,
-        H5Literate2, H5Lmove, H5L_SAME_LOC,
+        H5Lcreate_hard, H5Lcreate_soft, H5Ldelete, H5Lexists, H5Lget_info2, H5Lget_name_by_idx,
+        H5Lget_val, H5Literate, H5Literate2, H5Lmove, H5Lunpack_elink_val, H5L_SAME_LOC,
     };
 }
 
@@ -268,7 +277,7 @@ pub mod h5o {
     pub use super::runtime::{
         H5O_info1_t, H5O_info2_t, H5O_token_t, H5O_type_t, H5Oclose, H5Ocopy, H5Oget_comment,
         H5Oget_info1, H5Oget_info3, H5Oget_info_by_name1, H5Oget_info_by_name3, H5Oopen,
-        H5Oopen_by_addr, H5Oopen_by_token, H5Oset_comment, H5O_COPY_ALL,
+        H5Oopen_by_addr, H5Oopen_by_idx, H5Oopen_by_token, H5Oset_comment, H5O_COPY_ALL,
         H5O_COPY_EXPAND_EXT_LINK_FLAG, H5O_COPY_EXPAND_REFERENCE_FLAG,
         H5O_COPY_EXPAND_SOFT_LINK_FLAG, H5O_COPY_MERGE_COMMITTED_DTYPE_FLAG,
         H5O_COPY_PRESERVE_NULL_FLAG, H5O_COPY_SHALLOW_HIERARCHY_FLAG, H5O_COPY_WITHOUT_ATTR_FLAG,
@@ -292,6 +301,7 @@ pub mod h5p {
         H5Pget_alloc_time,
         H5Pget_attr_creation_order,
         H5Pget_attr_phase_change,
+        H5Pget_buffer,
         H5Pget_cache,
         H5Pget_char_encoding,
         H5Pget_chunk,
@@ -302,23 +312,31 @@ pub mod h5p {
         H5Pget_core_write_tracking,
         H5Pget_create_intermediate_group,
         H5Pget_driver,
+        H5Pget_driver_info,
+        H5Pget_edc_check,
         H5Pget_efile_prefix,
         H5Pget_elink_file_cache_size,
+        H5Pget_elink_prefix,
+        H5Pget_est_link_info,
         H5Pget_external,
         H5Pget_external_count,
         H5Pget_fapl_core,
         H5Pget_fapl_family,
         H5Pget_fapl_multi,
         H5Pget_fclose_degree,
+        H5Pget_file_image,
+        H5Pget_file_image_callbacks,
         H5Pget_fill_time,
         H5Pget_fill_value,
         H5Pget_filter2,
         H5Pget_filter_by_id2,
         H5Pget_gc_references,
+        H5Pget_hyper_vector_size,
         H5Pget_istore_k,
         H5Pget_layout,
         H5Pget_libver_bounds,
         H5Pget_link_creation_order,
+        H5Pget_link_phase_change,
         H5Pget_mdc_config,
         H5Pget_meta_block_size,
         H5Pget_nfilters,
@@ -331,7 +349,13 @@ pub mod h5p {
         H5Pget_sizes,
         H5Pget_small_data_block_size,
         H5Pget_sym_k,
+        H5Pget_type_conv_cb,
         H5Pget_userblock,
+        H5Pget_virtual_count,
+        H5Pget_virtual_dsetname,
+        H5Pget_virtual_filename,
+        H5Pget_virtual_srcspace,
+        H5Pget_virtual_vspace,
         H5Pisa_class,
         H5Piterate,
         H5Pmodify_filter,
@@ -339,6 +363,7 @@ pub mod h5p {
         H5Pset_alloc_time,
         H5Pset_attr_creation_order,
         H5Pset_attr_phase_change,
+        H5Pset_buffer,
         H5Pset_cache,
         H5Pset_char_encoding,
         H5Pset_chunk,
@@ -347,8 +372,11 @@ pub mod h5p {
         H5Pset_core_write_tracking,
         H5Pset_create_intermediate_group,
         H5Pset_deflate,
+        H5Pset_edc_check,
         H5Pset_efile_prefix,
         H5Pset_elink_file_cache_size,
+        H5Pset_elink_prefix,
+        H5Pset_est_link_info,
         H5Pset_external,
         H5Pset_fapl_core,
         H5Pset_fapl_family,
@@ -358,15 +386,19 @@ pub mod h5p {
         H5Pset_fapl_split,
         H5Pset_fapl_stdio,
         H5Pset_fclose_degree,
+        H5Pset_file_image,
+        H5Pset_file_image_callbacks,
         H5Pset_fill_time,
         H5Pset_fill_value,
         H5Pset_filter,
         H5Pset_fletcher32,
         H5Pset_gc_references,
+        H5Pset_hyper_vector_size,
         H5Pset_istore_k,
         H5Pset_layout,
         H5Pset_libver_bounds,
         H5Pset_link_creation_order,
+        H5Pset_link_phase_change,
         H5Pset_mdc_config,
         H5Pset_meta_block_size,
         H5Pset_nbit,
@@ -380,7 +412,9 @@ pub mod h5p {
         H5Pset_small_data_block_size,
         H5Pset_sym_k,
         H5Pset_szip,
+        H5Pset_type_conv_cb,
         H5Pset_userblock,
+        H5Pset_virtual,
         H5Pset_vlen_mem_manager,
         H5P_CLS_ATTRIBUTE_CREATE,
         H5P_CLS_DATASET_ACCESS,
@@ -425,18 +459,21 @@ pub mod h5p {
 
 pub mod h5r {
     pub use super::runtime::{
-        hobj_ref_t, H5R_ref_t, H5R_type_t, H5Rcreate, H5Rcreate_object, H5Rdereference, H5Rdestroy,
-        H5Rget_obj_type2, H5Rget_obj_type3, H5Ropen_object, H5R_OBJECT, H5R_OBJECT1, H5R_OBJECT2,
+        hobj_ref_t, H5R_ref_t, H5R_ref_t_u, H5R_type_t, H5Rcreate, H5Rcreate_attr,
+        H5Rcreate_object, H5Rcreate_region, H5Rdereference, H5Rdestroy, H5Rget_attr_name,
+        H5Rget_obj_type2, H5Rget_obj_type3, H5Rget_type, H5Ropen_attr, H5Ropen_object,
+        H5Ropen_region, H5R_DATASET_REGION2, H5R_OBJECT, H5R_OBJECT1, H5R_OBJECT2,
     };
 }
 
 pub mod h5s {
     pub use super::runtime::{
-        H5S_class_t, H5S_sel_type, H5S_seloper_t, H5Sclose, H5Scopy, H5Screate, H5Screate_simple,
-        H5Sdecode, H5Sencode, H5Sget_regular_hyperslab, H5Sget_select_elem_npoints,
-        H5Sget_select_elem_pointlist, H5Sget_select_npoints, H5Sget_select_type,
-        H5Sget_simple_extent_dims, H5Sget_simple_extent_ndims, H5Sget_simple_extent_npoints,
-        H5Sget_simple_extent_type, H5Sis_regular_hyperslab, H5Sselect_all, H5Sselect_elements,
+        H5S_class_t, H5S_sel_type, H5S_seloper_t, H5Sclose, H5Scombine_hyperslab,
+        H5Scombine_select, H5Scopy, H5Screate, H5Screate_simple, H5Sdecode, H5Sencode,
+        H5Sget_regular_hyperslab, H5Sget_select_elem_npoints, H5Sget_select_elem_pointlist,
+        H5Sget_select_npoints, H5Sget_select_type, H5Sget_simple_extent_dims,
+        H5Sget_simple_extent_ndims, H5Sget_simple_extent_npoints, H5Sget_simple_extent_type,
+        H5Sis_regular_hyperslab, H5Smodify_select, H5Sselect_all, H5Sselect_elements,
         H5Sselect_hyperslab, H5Sselect_none, H5Sselect_valid, H5S_ALL, H5S_MAX_RANK,
         H5S_SELECT_SET, H5S_UNLIMITED,
     };
@@ -450,6 +487,9 @@ pub mod h5t {
         // Types and enums
         H5T_class_t,
         H5T_cmd_t,
+        H5T_conv_except_func_t,
+        H5T_conv_except_t,
+        H5T_conv_ret_t,
         H5T_conv_t,
         H5T_cset_t,
         H5T_order_t,
@@ -465,6 +505,8 @@ pub mod h5t {
         H5Tcreate,
         H5Tenum_create,
         H5Tenum_insert,
+        H5Tenum_nameof,
+        H5Tenum_valueof,
         H5Tequal,
         H5Tfind,
         H5Tget_array_dims2,
@@ -484,8 +526,10 @@ pub mod h5t {
         H5Tget_size,
         H5Tget_strpad,
         H5Tget_super,
+        H5Tget_tag,
         H5Tinsert,
         H5Tis_variable_str,
+        H5Treclaim,
         H5Tset_cset,
         H5Tset_ebias,
         H5Tset_fields,
@@ -494,6 +538,7 @@ pub mod h5t {
         H5Tset_precision,
         H5Tset_size,
         H5Tset_strpad,
+        H5Tset_tag,
         H5Tvlen_create,
         // Character set constants
         H5T_CSET_ASCII,
@@ -603,41 +648,60 @@ pub mod h5t {
 
 pub mod h5z {
     pub use super::runtime::{
-        H5Z_class2_t, H5Z_filter_t, H5Zfilter_avail, H5Zget_filter_info, H5Zregister,
-        H5Z_CLASS_T_VERS, H5Z_FILTER_CONFIG_DECODE_ENABLED, H5Z_FILTER_CONFIG_ENCODE_ENABLED,
-        H5Z_FILTER_DEFLATE, H5Z_FILTER_ERROR, H5Z_FILTER_FLETCHER32, H5Z_FILTER_NBIT,
-        H5Z_FILTER_NONE, H5Z_FILTER_SCALEOFFSET, H5Z_FILTER_SHUFFLE, H5Z_FILTER_SZIP,
-        H5Z_FLAG_MANDATORY, H5Z_FLAG_OPTIONAL, H5Z_FLAG_REVERSE, H5Z_SO_FLOAT_DSCALE, H5Z_SO_INT,
-        H5_SZIP_EC_OPTION_MASK, H5_SZIP_MAX_PIXELS_PER_BLOCK, H5_SZIP_NN_OPTION_MASK,
+        H5Z_EDC_t, H5Z_class2_t, H5Z_filter_t, H5Zfilter_avail, H5Zget_filter_info, H5Zregister,
+        H5Zunregister, H5Z_CLASS_T_VERS, H5Z_FILTER_CONFIG_DECODE_ENABLED,
+        H5Z_FILTER_CONFIG_ENCODE_ENABLED, H5Z_FILTER_DEFLATE, H5Z_FILTER_ERROR,
+        H5Z_FILTER_FLETCHER32, H5Z_FILTER_NBIT, H5Z_FILTER_NONE, H5Z_FILTER_SCALEOFFSET,
+        H5Z_FILTER_SHUFFLE, H5Z_FILTER_SZIP, H5Z_FLAG_MANDATORY, H5Z_FLAG_OPTIONAL,
+        H5Z_FLAG_REVERSE, H5Z_SO_FLOAT_DSCALE, H5Z_SO_INT, H5_SZIP_EC_OPTION_MASK,
+        H5_SZIP_MAX_PIXELS_PER_BLOCK, H5_SZIP_NN_OPTION_MASK,
     };
 }
 
-/// Initialize HDF5 library.
-///
-/// Loads the HDF5 library from the specified path using dlopen.
-/// If no path is specified, searches default system locations.
+// The functions below are exported from `raw`, they are kept here for compatibility
+
+/// Initialize HDF5 library, see [`raw::init`](crate::raw::init).
+#[deprecated(note = "use `raw::init` instead")]
 pub fn init(path: Option<&str>) -> Result<(), String> {
     runtime::init(path)
 }
 
 /// Check if the HDF5 library is initialized.
+#[deprecated(note = "use `raw::is_initialized` instead")]
 pub fn is_initialized() -> bool {
     runtime::is_initialized()
 }
 
 /// Get the library path.
+#[deprecated(note = "use `raw::library_path` instead")]
 pub fn library_path() -> Option<String> {
     runtime::library_path()
 }
 
-pub use runtime::Version;
+/// Close the library, see [`raw::shutdown`](crate::raw::shutdown).
+#[deprecated(note = "use `raw::shutdown` instead")]
+pub fn shutdown() -> Result<(), String> {
+    runtime::shutdown()
+}
+
+pub use runtime::{
+    ApiTable, LinkIterateApi, ObjectInfoApi, ReferenceApi, Version, DISABLE_FILTERS_ENV,
+    LIBRARY_PATH_ENV,
+};
 
 /// Get the detected HDF5 library version.
+#[deprecated(note = "use `raw::hdf5_version` instead")]
 pub fn hdf5_version() -> Option<Version> {
     runtime::hdf5_version()
 }
 
 /// Check if HDF5 version is at least the specified version.
+#[deprecated(note = "use `raw::hdf5_version_at_least` instead")]
 pub fn hdf5_version_at_least(major: u8, minor: u8, micro: u8) -> bool {
     runtime::hdf5_version_at_least(major, minor, micro)
 }
+
+/// Get the API generations to use with the loaded HDF5 library.
+pub fn api_table() -> ApiTable {
+    runtime::api_table()
+}
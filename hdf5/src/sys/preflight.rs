@@ -0,0 +1,388 @@
+//! Symbol preflight check of the loaded HDF5 library.
+//!
+//! Functions are loaded lazily on their first call, so a symbol missing from the library only
+//! shows up as a panic when the crate first uses it. [`preflight`] resolves every symbol
+//! declared in the runtime bindings up front (without calling any of them) and reports the
+//! missing ones.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::LazyLock;
+
+use super::runtime::{self, ApiTable, Version, FUNCTION_TABLES, GLOBALS};
+
+/// Minimum HDF5 version supported by the runtime bindings.
+const MIN_VERSION: Version = Version { major: 1, minor: 10, micro: 5 };
+
+/// Symbols that only exist in libraries newer than [`MIN_VERSION`].
+const INTRODUCED: &[(&str, Version)] = &[
+    ("H5Literate2", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Lget_info2", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Oget_info3", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Oget_info_by_name3", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Oopen_by_token", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Rcreate_attr", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Rcreate_object", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Rcreate_region", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Rdestroy", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Rget_attr_name", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Rget_obj_type3", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Rget_type", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Ropen_attr", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Ropen_object", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Ropen_region", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Sencode2", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5Treclaim", Version { major: 1, minor: 12, micro: 0 }),
+    ("H5T_STD_REF", Version { major: 1, minor: 12, micro: 0 }),
+];
+
+/// Kind of a symbol exported by the HDF5 library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    /// A function declared with `hdf5_function!`.
+    Function,
+    /// A global identifier (datatype, error class or property list class/default).
+    Global,
+}
+
+impl fmt::Display for SymbolKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Function => "function",
+            Self::Global => "global",
+        })
+    }
+}
+
+/// A symbol that could not be resolved in the loaded library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingSymbol {
+    /// The exported symbol name.
+    pub name: String,
+    /// The API family, e.g. `H5P` for `H5Pset_chunk` or `H5T` for `H5T_NATIVE_INT_g`.
+    pub family: String,
+    /// Whether the symbol is a function or a global.
+    pub kind: SymbolKind,
+    /// Whether the crate uses the symbol with the detected library version; missing optional
+    /// symbols (legacy functions, functions of newer versions) are only warnings.
+    pub required: bool,
+}
+
+/// Result of resolving all symbols used by the crate in the loaded library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreflightReport {
+    /// Path of the loaded library, `None` if it has not been initialized.
+    pub library_path: Option<String>,
+    /// Version of the loaded library, `None` if it couldn't be determined.
+    pub version: Option<Version>,
+    /// Whether the library was built thread-safe, `None` if it couldn't be determined.
+    pub threadsafe: Option<bool>,
+    /// Number of symbols that were checked.
+    pub checked: usize,
+    /// The symbols that could not be resolved, required or not, in declaration order.
+    pub missing: Vec<MissingSymbol>,
+}
+
+impl PreflightReport {
+    /// Returns true if the library is loaded and no required symbol is missing.
+    pub fn is_ok(&self) -> bool {
+        self.library_path.is_some() && self.missing_required().next().is_none()
+    }
+
+    /// Returns the missing symbols required with the detected library version.
+    pub fn missing_required(&self) -> impl Iterator<Item = &MissingSymbol> {
+        self.missing.iter().filter(|s| s.required)
+    }
+
+    /// Returns the missing optional symbols.
+    pub fn warnings(&self) -> impl Iterator<Item = &MissingSymbol> {
+        self.missing.iter().filter(|s| !s.required)
+    }
+
+    /// Returns the missing symbols grouped by API family, in family and name order.
+    pub fn by_family(&self) -> BTreeMap<&str, Vec<&MissingSymbol>> {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for symbol in &self.missing {
+            groups.entry(symbol.family.as_str()).or_default().push(symbol);
+        }
+        for symbols in groups.values_mut() {
+            symbols.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        groups
+    }
+}
+
+impl fmt::Display for PreflightReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some(path) = &self.library_path else {
+            return f.write_str("HDF5 library: not initialized");
+        };
+        writeln!(f, "HDF5 library: {path}")?;
+        match self.version {
+            Some(version) => write!(f, "version: {version}")?,
+            None => write!(f, "version: unknown")?,
+        }
+        match self.threadsafe {
+            Some(threadsafe) => {
+                writeln!(f, ", threadsafe: {}", if threadsafe { "yes" } else { "no" })?
+            }
+            None => writeln!(f, ", threadsafe: unknown")?,
+        }
+        write!(
+            f,
+            "symbols: {} checked, {} missing required, {} missing optional",
+            self.checked,
+            self.missing_required().count(),
+            self.warnings().count()
+        )?;
+        for (family, symbols) in self.by_family() {
+            write!(f, "\n[{family}]")?;
+            for symbol in symbols {
+                let status = if symbol.required { "error" } else { "warning" };
+                write!(f, "\n  {status}: {} ({}) is missing", symbol.name, symbol.kind)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A symbol declared in the runtime bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Declaration {
+    /// The Rust name of the function or global accessor.
+    name: &'static str,
+    /// The exported symbol, for HDF5 1.12+ in the case of globals with two names.
+    symbol: &'static str,
+    /// The exported symbol for HDF5 1.10, for globals with version-dependent names.
+    symbol_1_10: Option<&'static str>,
+    kind: SymbolKind,
+    /// Whether this is a deprecated function removed in HDF5 2.0.
+    legacy: bool,
+}
+
+impl Declaration {
+    fn symbol_for(&self, version: Option<Version>) -> &'static str {
+        match (self.symbol_1_10, version) {
+            (Some(old), Some(v)) if v < Version { major: 1, minor: 12, micro: 0 } => old,
+            _ => self.symbol,
+        }
+    }
+
+    fn is_required(&self, version: Option<Version>) -> bool {
+        let version = version.unwrap_or(MIN_VERSION);
+        if self.legacy && ApiTable::for_version(version).legacy_removed {
+            return false;
+        }
+        INTRODUCED.iter().all(|&(name, since)| name != self.name || version >= since)
+    }
+}
+
+/// The symbols declared with `hdf5_function!` and `define_native_type!`, from the tables the
+/// macros generate.
+static DECLARATIONS: LazyLock<Vec<Declaration>> = LazyLock::new(|| {
+    let functions = FUNCTION_TABLES.iter().flat_map(|table| table.iter()).map(|f| Declaration {
+        name: f.name,
+        symbol: f.name,
+        symbol_1_10: None,
+        kind: SymbolKind::Function,
+        legacy: f.legacy,
+    });
+    let globals = GLOBALS.iter().map(|g| Declaration {
+        name: g.name,
+        symbol: g.symbol,
+        symbol_1_10: g.symbol_1_10,
+        kind: SymbolKind::Global,
+        legacy: false,
+    });
+    functions.chain(globals).collect()
+});
+
+/// Returns the API family of a symbol: `H5` followed by the upper-case letters of its prefix.
+fn family(name: &str) -> &str {
+    let prefix = name.strip_prefix("H5").unwrap_or(name);
+    let len = prefix.find(|c: char| !c.is_ascii_uppercase()).unwrap_or(prefix.len());
+    &name[..name.len() - prefix.len() + len]
+}
+
+/// Resolves (without calling) every symbol the crate can use in the loaded library.
+///
+/// This covers all functions of the runtime bindings, as well as the predefined datatype,
+/// error class and property list globals. A symbol is required if the crate uses it with the
+/// detected library version; the deprecated functions removed in HDF5 2.0 and the functions
+/// introduced after HDF5 1.10.5 are optional otherwise.
+///
+/// If the library has not been initialized, nothing is checked and the report is not ok.
+pub fn preflight() -> PreflightReport {
+    let Some(library_path) = runtime::library_path() else {
+        return PreflightReport {
+            library_path: None,
+            version: None,
+            threadsafe: None,
+            checked: 0,
+            missing: Vec::new(),
+        };
+    };
+    let version = runtime::hdf5_version();
+    let mut missing = Vec::new();
+    for declaration in DECLARATIONS.iter() {
+        let symbol = declaration.symbol_for(version);
+        if runtime::resolve_symbol(symbol).is_err() {
+            missing.push(MissingSymbol {
+                name: symbol.to_owned(),
+                family: family(symbol).to_owned(),
+                kind: declaration.kind,
+                required: declaration.is_required(version),
+            });
+        }
+    }
+    let threadsafe = runtime::resolve_symbol("H5is_library_threadsafe").ok().and_then(|()| {
+        let mut ts: runtime::hbool_t = 0;
+        let _guard = runtime::LOCK.lock();
+        (unsafe { runtime::H5is_library_threadsafe(&mut ts) } >= 0).then_some(ts > 0)
+    });
+    PreflightReport {
+        library_path: Some(library_path),
+        version,
+        threadsafe,
+        checked: DECLARATIONS.len(),
+        missing,
+    }
+}
+
+/// Initializes the library like [`init`](super::init), then runs [`preflight`].
+///
+/// Returns the report if no required symbol is missing (it may still list missing optional
+/// symbols), or an error with the formatted report otherwise.
+pub fn init_checked(path: Option<&str>) -> Result<PreflightReport, String> {
+    runtime::init(path)?;
+    let report = preflight();
+    if report.is_ok() {
+        Ok(report)
+    } else {
+        Err(format!("HDF5 symbol preflight failed:\n{report}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declarations() {
+        let find = |name| DECLARATIONS.iter().find(|d| d.name == name).copied();
+        assert!(DECLARATIONS.len() > 500);
+
+        let open = find("H5open").unwrap();
+        assert_eq!((open.symbol, open.kind, open.legacy), ("H5open", SymbolKind::Function, false));
+        let libversion = find("H5get_libversion").unwrap();
+        assert_eq!(libversion.kind, SymbolKind::Function);
+        let info1 = find("H5Oget_info1").unwrap();
+        assert!(info1.legacy);
+        let cls = find("H5P_CLS_DATASET_ACCESS").unwrap();
+        assert_eq!((cls.symbol, cls.kind), ("H5P_CLS_DATASET_ACCESS_ID_g", SymbolKind::Global));
+        assert_eq!(find("H5T_NATIVE_INT8").unwrap().symbol, "H5T_NATIVE_INT8_g");
+        assert!(DECLARATIONS.iter().all(|d| d.name.starts_with("H5") && !d.symbol.is_empty()));
+        let names = DECLARATIONS.iter().map(|d| d.name).collect::<std::collections::HashSet<_>>();
+        assert_eq!(names.len(), DECLARATIONS.len());
+        for &(name, _) in INTRODUCED {
+            assert!(find(name).is_some(), "{name} is not declared");
+        }
+
+        let v = |major, minor, micro| Some(Version { major, minor, micro });
+        assert!(open.is_required(v(1, 10, 5)));
+        assert!(info1.is_required(v(1, 14, 3)));
+        assert!(!info1.is_required(v(2, 0, 0)));
+        let info3 = find("H5Oget_info3").unwrap();
+        assert!(!info3.is_required(v(1, 10, 11)));
+        assert!(info3.is_required(v(1, 12, 0)));
+        assert!(!find("H5T_STD_REF").unwrap().is_required(None));
+    }
+
+    #[test]
+    fn test_family() {
+        assert_eq!(family("H5open"), "H5");
+        assert_eq!(family("H5Pset_chunk"), "H5P");
+        assert_eq!(family("H5T_NATIVE_INT_g"), "H5T");
+        assert_eq!(family("H5E_ARGS_g"), "H5E");
+        assert_eq!(family("H5FDopen"), "H5FD");
+        assert_eq!(family("foo"), "");
+    }
+
+    #[test]
+    fn test_report_format() {
+        let missing = |name: &str, kind, required| MissingSymbol {
+            name: name.into(),
+            family: family(name).into(),
+            kind,
+            required,
+        };
+        let mut report = PreflightReport {
+            library_path: Some("/opt/hdf5/lib/libhdf5.so".into()),
+            version: Some(Version { major: 1, minor: 14, micro: 3 }),
+            threadsafe: Some(false),
+            checked: 561,
+            missing: vec![
+                missing("H5Rcreate", SymbolKind::Function, false),
+                missing("H5Pset_virtual", SymbolKind::Function, true),
+                missing("H5P_CLS_ROOT_ID_g", SymbolKind::Global, true),
+                missing("H5Oget_info3", SymbolKind::Function, true),
+            ],
+        };
+        assert!(!report.is_ok());
+        assert_eq!(report.missing_required().count(), 3);
+        assert_eq!(report.warnings().map(|s| &*s.name).collect::<Vec<_>>(), ["H5Rcreate"]);
+        let groups = report.by_family();
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), ["H5O", "H5P", "H5R"]);
+        assert_eq!(groups["H5P"][0].name, "H5P_CLS_ROOT_ID_g");
+        assert_eq!(
+            report.to_string(),
+            "HDF5 library: /opt/hdf5/lib/libhdf5.so\n\
+             version: 1.14.3, threadsafe: no\n\
+             symbols: 561 checked, 3 missing required, 1 missing optional\n\
+             [H5O]\n  error: H5Oget_info3 (function) is missing\n\
+             [H5P]\n  error: H5P_CLS_ROOT_ID_g (global) is missing\n\
+             \x20 error: H5Pset_virtual (function) is missing\n\
+             [H5R]\n  warning: H5Rcreate (function) is missing"
+        );
+
+        report.missing.retain(|s| !s.required);
+        assert!(report.is_ok());
+        report.missing.clear();
+        report.threadsafe = None;
+        assert_eq!(
+            report.to_string(),
+            "HDF5 library: /opt/hdf5/lib/libhdf5.so\n\
+             version: 1.14.3, threadsafe: unknown\n\
+             symbols: 561 checked, 0 missing required, 0 missing optional"
+        );
+
+        let report = PreflightReport {
+            library_path: None,
+            version: None,
+            threadsafe: None,
+            checked: 0,
+            missing: vec![],
+        };
+        assert!(!report.is_ok());
+        assert_eq!(report.to_string(), "HDF5 library: not initialized");
+    }
+
+    #[test]
+    fn test_preflight() {
+        let report = init_checked(None).expect("Failed to initialize HDF5");
+        assert_eq!(report.missing_required().count(), 0, "{report}");
+        assert_eq!(report.checked, DECLARATIONS.len());
+        assert_eq!(report.version, runtime::hdf5_version());
+        assert!(report.threadsafe.is_some());
+
+        runtime::set_failing_symbols(&["H5Pset_virtual", "H5T_NATIVE_INT8_g"]);
+        let report = preflight();
+        runtime::set_failing_symbols(&[]);
+        let names = report.missing_required().map(|s| &*s.name).collect::<Vec<_>>();
+        assert_eq!(names, ["H5Pset_virtual", "H5T_NATIVE_INT8_g"]);
+        assert!(!report.is_ok());
+        assert!(report
+            .to_string()
+            .contains("[H5P]\n  error: H5Pset_virtual (function) is missing"));
+    }
+}
@@ -9,6 +9,7 @@
 
 use libloading::{Library, Symbol};
 use parking_lot::ReentrantMutex;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::OnceLock;
 
 // =============================================================================
@@ -26,14 +27,19 @@ pub type herr_t = c_int;
 /// HDF5 boolean type (`_Bool` in C, 1 byte on all modern systems with `<stdbool.h>`)
 pub type hbool_t = u8;
 /// HDF5 size type (unsigned)
-pub type hsize_t = c_ulong;
+pub type hsize_t = u64;
 /// HDF5 signed size type
-pub type hssize_t = c_long;
+pub type hssize_t = i64;
 /// HDF5 address type
 pub type haddr_t = u64;
 /// HDF5 tri-state type
 pub type htri_t = c_int;
 
+// `hsize_t`, `hssize_t` and `haddr_t` are `uint64_t`/`int64_t` in the C library on all targets
+const _: () = assert!(std::mem::size_of::<hsize_t>() == 8);
+const _: () = assert!(std::mem::size_of::<hssize_t>() == 8);
+const _: () = assert!(std::mem::size_of::<haddr_t>() == 8);
+
 /// Invalid HDF5 ID
 pub const H5I_INVALID_HID: hid_t = -1;
 /// Undefined address
@@ -270,6 +276,39 @@ pub type H5T_conv_t = Option<
     ) -> herr_t,
 >;
 
+/// Kinds of exceptions raised while converting values between datatypes.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum H5T_conv_except_t {
+    H5T_CONV_EXCEPT_RANGE_HI = 0,
+    H5T_CONV_EXCEPT_RANGE_LOW = 1,
+    H5T_CONV_EXCEPT_PRECISION = 2,
+    H5T_CONV_EXCEPT_TRUNCATE = 3,
+    H5T_CONV_EXCEPT_PINF = 4,
+    H5T_CONV_EXCEPT_NINF = 5,
+    H5T_CONV_EXCEPT_NAN = 6,
+}
+
+/// Return values of a conversion exception callback.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum H5T_conv_ret_t {
+    H5T_CONV_ABORT = -1,
+    H5T_CONV_UNHANDLED = 0,
+    H5T_CONV_HANDLED = 1,
+}
+
+pub type H5T_conv_except_func_t = Option<
+    unsafe extern "C" fn(
+        except_type: H5T_conv_except_t,
+        src_id: hid_t,
+        dst_id: hid_t,
+        src_buf: *mut c_void,
+        dst_buf: *mut c_void,
+        user_data: *mut c_void,
+    ) -> H5T_conv_ret_t,
+>;
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum H5S_class_t {
@@ -329,6 +368,16 @@ pub enum H5D_alloc_time_t {
     H5D_ALLOC_TIME_INCR = 3,
 }
 
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum H5D_space_status_t {
+    H5D_SPACE_STATUS_ERROR = -1,
+    #[default]
+    H5D_SPACE_STATUS_NOT_ALLOCATED = 0,
+    H5D_SPACE_STATUS_PART_ALLOCATED = 1,
+    H5D_SPACE_STATUS_ALLOCATED = 2,
+}
+
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub enum H5D_fill_time_t {
@@ -390,6 +439,45 @@ pub enum H5F_mem_t {
 
 pub const H5FD_MEM_NTYPES: usize = 7;
 
+/// Operation on a file image passed to the file image callbacks.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum H5FD_file_image_op_t {
+    H5FD_FILE_IMAGE_OP_NO_OP = 0,
+    H5FD_FILE_IMAGE_OP_PROPERTY_LIST_SET = 1,
+    H5FD_FILE_IMAGE_OP_PROPERTY_LIST_COPY = 2,
+    H5FD_FILE_IMAGE_OP_PROPERTY_LIST_GET = 3,
+    H5FD_FILE_IMAGE_OP_PROPERTY_LIST_CLOSE = 4,
+    H5FD_FILE_IMAGE_OP_FILE_OPEN = 5,
+    H5FD_FILE_IMAGE_OP_FILE_RESIZE = 6,
+    H5FD_FILE_IMAGE_OP_FILE_CLOSE = 7,
+}
+
+/// Callbacks controlling how the library allocates, copies and frees file images.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct H5FD_file_image_callbacks_t {
+    pub image_malloc:
+        Option<unsafe extern "C" fn(size_t, H5FD_file_image_op_t, *mut c_void) -> *mut c_void>,
+    pub image_memcpy: Option<
+        unsafe extern "C" fn(
+            *mut c_void,
+            *const c_void,
+            size_t,
+            H5FD_file_image_op_t,
+            *mut c_void,
+        ) -> *mut c_void,
+    >,
+    pub image_realloc: Option<
+        unsafe extern "C" fn(*mut c_void, size_t, H5FD_file_image_op_t, *mut c_void) -> *mut c_void,
+    >,
+    pub image_free:
+        Option<unsafe extern "C" fn(*mut c_void, H5FD_file_image_op_t, *mut c_void) -> herr_t>,
+    pub udata_copy: Option<unsafe extern "C" fn(*mut c_void) -> *mut c_void>,
+    pub udata_free: Option<unsafe extern "C" fn(*mut c_void) -> herr_t>,
+    pub udata: *mut c_void,
+}
+
 // H5FD log flags
 pub const H5FD_LOG_LOC_READ: u64 = 0x0001;
 pub const H5FD_LOG_LOC_WRITE: u64 = 0x0002;
@@ -467,6 +555,7 @@ pub enum H5R_type_t {
 pub const H5R_OBJECT: H5R_type_t = H5R_type_t::H5R_OBJECT1;
 pub const H5R_OBJECT1: H5R_type_t = H5R_type_t::H5R_OBJECT1;
 pub const H5R_OBJECT2: H5R_type_t = H5R_type_t::H5R_OBJECT2;
+pub const H5R_DATASET_REGION2: H5R_type_t = H5R_type_t::H5R_DATASET_REGION2;
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -535,6 +624,15 @@ pub const H5Z_FILTER_SZIP: H5Z_filter_t = 4;
 pub const H5Z_FILTER_NBIT: H5Z_filter_t = 5;
 pub const H5Z_FILTER_SCALEOFFSET: H5Z_filter_t = 6;
 
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum H5Z_EDC_t {
+    H5Z_ERROR_EDC = -1,
+    H5Z_DISABLE_EDC = 0,
+    H5Z_ENABLE_EDC = 1,
+    H5Z_NO_EDC = 2,
+}
+
 pub const H5Z_FLAG_OPTIONAL: c_uint = 0x0001;
 pub const H5Z_FLAG_MANDATORY: c_uint = 0x0000;
 pub const H5Z_FLAG_REVERSE: c_uint = 0x0100;
@@ -576,6 +674,42 @@ pub struct H5_ih_info_t {
     pub heap_size: hsize_t,
 }
 
+/// Superblock part of the file info structure
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct H5F_info2_super_t {
+    pub version: c_uint,
+    pub super_size: hsize_t,
+    pub super_ext_size: hsize_t,
+}
+
+/// Free-space manager part of the file info structure
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct H5F_info2_free_t {
+    pub version: c_uint,
+    pub meta_size: hsize_t,
+    pub tot_space: hsize_t,
+}
+
+/// Shared object header message part of the file info structure
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct H5F_info2_sohm_t {
+    pub version: c_uint,
+    pub hdr_size: hsize_t,
+    pub msgs_info: H5_ih_info_t,
+}
+
+/// File info structure (HDF5 1.10+)
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct H5F_info2_t {
+    pub super_: H5F_info2_super_t,
+    pub free: H5F_info2_free_t,
+    pub sohm: H5F_info2_sohm_t,
+}
+
 /// Object header info structure for HDF5 < 1.12
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Default)]
@@ -631,7 +765,8 @@ pub struct H5O_info1_t {
 }
 
 #[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct H5O_token_t {
     pub __data: [u8; 16],
 }
@@ -797,6 +932,11 @@ impl Default for H5G_info_t {
     }
 }
 
+pub const H5G_STORAGE_TYPE_UNKNOWN: c_int = -1;
+pub const H5G_STORAGE_TYPE_SYMBOL_TABLE: c_int = 0;
+pub const H5G_STORAGE_TYPE_COMPACT: c_int = 1;
+pub const H5G_STORAGE_TYPE_DENSE: c_int = 2;
+
 // =============================================================================
 // Callback types
 // =============================================================================
@@ -815,6 +955,15 @@ pub type H5L_iterate2_t =
 /// Alias for H5L_iterate2_t (compatibility)
 pub type H5L_iterate_t = H5L_iterate2_t;
 
+/// Raw value of an identifier type as taken and returned by the functions for user-defined
+/// identifier types, whose values lie beyond `H5I_NTYPES` and cannot be held by
+/// [`H5I_type_t`].
+pub type H5I_type_raw_t = c_int;
+
+/// Free function of a user-defined identifier type; `request` is only passed since HDF5 1.12.
+pub type H5I_free_t =
+    Option<unsafe extern "C" fn(obj: *mut c_void, request: *mut *mut c_void) -> herr_t>;
+
 // =============================================================================
 // Version info
 // =============================================================================
@@ -826,6 +975,12 @@ pub struct Version {
     pub micro: u8,
 }
 
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.micro)
+    }
+}
+
 pub const HDF5_VERSION: Version = Version { major: 1, minor: 14, micro: 0 };
 
 // =============================================================================
@@ -835,6 +990,14 @@ pub const HDF5_VERSION: Version = Version { major: 1, minor: 14, micro: 0 };
 static LIBRARY: OnceLock<&'static Library> = OnceLock::new();
 static LIBRARY_PATH: OnceLock<String> = OnceLock::new();
 static HDF5_RUNTIME_VERSION: OnceLock<Version> = OnceLock::new();
+static API_TABLE: OnceLock<ApiTable> = OnceLock::new();
+
+/// Library generation, bumped by [`shutdown`]; handles record it when they are created.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Number of calls made into the library, counted with the `test-hooks` feature.
+#[cfg(any(test, feature = "test-hooks"))]
+static FFI_CALLS: AtomicU64 = AtomicU64::new(0);
 
 /// Thread-safety lock
 pub static LOCK: ReentrantMutex<()> = ReentrantMutex::new(());
@@ -844,33 +1007,53 @@ fn get_library() -> &'static Library {
     *LIBRARY.get().expect("HDF5 library not initialized. Call hdf5::sys::init() first.")
 }
 
+/// Environment variable holding the path of the library loaded by [`init`] when no path is
+/// given, which includes the implicit initialization on the first high-level call.
+pub const LIBRARY_PATH_ENV: &str = "HDF5_RUST_LIB";
+
+/// Environment variable that, set to anything but an empty string or `0`, keeps the filters
+/// provided by this crate from being registered.
+pub const DISABLE_FILTERS_ENV: &str = "HDF5_RUST_DISABLE_FILTERS";
+
+/// Returns the path of the library to load and the environment variable it was taken from, if
+/// any: an explicit path, else the value of [`LIBRARY_PATH_ENV`], else the platform default.
+fn library_path_to_load(
+    path: Option<&str>,
+    env_path: Option<String>,
+) -> (String, Option<&'static str>) {
+    if let Some(path) = path {
+        return (path.to_string(), None);
+    }
+    if let Some(path) = env_path.filter(|path| !path.is_empty()) {
+        return (path, Some(LIBRARY_PATH_ENV));
+    }
+    let default = if cfg!(target_os = "macos") {
+        "/opt/homebrew/lib/libhdf5.dylib"
+    } else if cfg!(target_os = "windows") {
+        "hdf5.dll"
+    } else {
+        "libhdf5.so"
+    };
+    (default.to_string(), None)
+}
+
 /// Initialize the HDF5 library by loading it from the specified path.
+///
+/// Without a path, the library is loaded from [`LIBRARY_PATH_ENV`] if it is set, and from the
+/// default location of the platform otherwise. Does nothing if the library is already loaded.
 pub fn init(path: Option<&str>) -> Result<(), String> {
     if LIBRARY.get().is_some() {
         return Ok(());
     }
 
-    let lib_path = path.map(|s| s.to_string()).unwrap_or_else(|| {
-        #[cfg(target_os = "macos")]
-        {
-            "/opt/homebrew/lib/libhdf5.dylib".to_string()
-        }
-        #[cfg(target_os = "linux")]
-        {
-            "libhdf5.so".to_string()
+    let env_path = std::env::var(LIBRARY_PATH_ENV).ok();
+    let (lib_path, source) = library_path_to_load(path, env_path);
+    let library = unsafe { Library::new(&lib_path) }.map_err(|e| match source {
+        Some(var) => {
+            format!("Failed to load HDF5 library from {} (set by {}): {}", lib_path, var, e)
         }
-        #[cfg(target_os = "windows")]
-        {
-            "hdf5.dll".to_string()
-        }
-        #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
-        {
-            "libhdf5.so".to_string()
-        }
-    });
-
-    let library = unsafe { Library::new(&lib_path) }
-        .map_err(|e| format!("Failed to load HDF5 library from {}: {}", lib_path, e))?;
+        None => format!("Failed to load HDF5 library from {}: {}", lib_path, e),
+    })?;
 
     // Leak the library handle to prevent dlclose() on exit.
     // HDF5 has problematic cleanup routines that can cause "infinite loop closing library"
@@ -906,6 +1089,7 @@ fn check_hdf5_version() -> Result<(), String> {
     // Store the version for later use
     let version = Version { major: major as u8, minor: minor as u8, micro: release as u8 };
     let _ = HDF5_RUNTIME_VERSION.set(version);
+    let _ = API_TABLE.set(ApiTable::for_version(version));
 
     // Check minimum version: 1.10.5
     if major < 1 || (major == 1 && minor < 10) || (major == 1 && minor == 10 && release < 5) {
@@ -927,21 +1111,167 @@ pub fn library_path() -> Option<String> {
     LIBRARY_PATH.get().cloned()
 }
 
+/// Closes the library, invalidating all open identifiers.
+///
+/// This bumps the library generation: handles created before are stale from then on and
+/// dropping them does not call into the library. Afterwards, the library functions return
+/// their failure value without calling into the library, so operations fail with
+/// [`LibraryShutDown`](crate::Error::LibraryShutDown). The library cannot be used again in this
+/// process, so this should only be called once all work with it is done. Does nothing if the
+/// library was never loaded or is already shut down.
+pub fn shutdown() -> Result<(), String> {
+    let _guard = LOCK.lock();
+    if LIBRARY.get().is_none() || is_shut_down() {
+        return Ok(());
+    }
+    if unsafe { H5close() } < 0 {
+        return Err("Failed to close the HDF5 library".into());
+    }
+    GENERATION.fetch_add(1, Ordering::AcqRel);
+    Ok(())
+}
+
+/// Returns the current library generation, `0` until [`shutdown`] is called.
+#[inline]
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::Acquire)
+}
+
+/// Returns `true` if the library has been closed by [`shutdown`].
+#[inline]
+pub fn is_shut_down() -> bool {
+    generation() != 0
+}
+
+/// Returns the number of calls made into the library so far.
+#[doc(hidden)]
+#[cfg(any(test, feature = "test-hooks"))]
+pub fn ffi_call_count() -> u64 {
+    FFI_CALLS.load(Ordering::Relaxed)
+}
+
+#[cfg(any(test, feature = "test-hooks"))]
+thread_local! {
+    /// Version reported instead of the loaded one on this thread, see `with_reported_version`.
+    static REPORTED_VERSION: std::cell::Cell<Option<Version>> =
+        const { std::cell::Cell::new(None) };
+}
+
+/// Runs `func` with [`hdf5_version`] and [`hdf5_version_at_least`] reporting `version` on the
+/// current thread, for testing the version checks against libraries that are not installed.
+///
+/// Only the version checks are affected: the loaded functions and the [`api_table`] stay
+/// those of the actual library.
+#[doc(hidden)]
+#[cfg(any(test, feature = "test-hooks"))]
+pub fn with_reported_version<T>(version: Version, func: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Version>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            REPORTED_VERSION.with(|reported| reported.set(self.0));
+        }
+    }
+
+    let _restore = Restore(REPORTED_VERSION.with(|reported| reported.replace(Some(version))));
+    func()
+}
+
 /// Get the runtime HDF5 library version.
 /// Returns None if the library has not been initialized.
 pub fn hdf5_version() -> Option<Version> {
+    #[cfg(any(test, feature = "test-hooks"))]
+    if let Some(version) = REPORTED_VERSION.with(std::cell::Cell::get) {
+        return Some(version);
+    }
     HDF5_RUNTIME_VERSION.get().copied()
 }
 
 /// Check if the HDF5 library version is at least the specified version.
 /// Returns false if the library has not been initialized.
 pub fn hdf5_version_at_least(major: u8, minor: u8, micro: u8) -> bool {
-    match HDF5_RUNTIME_VERSION.get() {
-        Some(version) => *version >= Version { major, minor, micro },
+    match hdf5_version() {
+        Some(version) => version >= Version { major, minor, micro },
         None => false,
     }
 }
 
+// =============================================================================
+// API generations
+// =============================================================================
+
+/// Generation of the object info API (`H5Oget_info*`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectInfoApi {
+    /// `H5Oget_info1` with `H5O_info1_t` (HDF5 1.10).
+    V1,
+    /// `H5Oget_info3` with `H5O_info2_t` and object tokens (HDF5 1.12+).
+    V3,
+}
+
+/// Generation of the reference API (`H5R*`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceApi {
+    /// `H5Rcreate`/`H5Rdereference2` with `hobj_ref_t` only (HDF5 1.10).
+    Legacy,
+    /// `H5Rcreate_object`/`H5Ropen_object` with `H5R_ref_t` (HDF5 1.12+).
+    Standard,
+}
+
+/// Generation of the link iteration API (`H5Literate*`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkIterateApi {
+    /// `H5Literate` (HDF5 1.10).
+    V1,
+    /// `H5Literate2` (HDF5 1.12+).
+    V2,
+}
+
+/// Generations of the versioned API families to use with the loaded library.
+///
+/// The table is recorded at [`init`] from the detected library version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiTable {
+    pub object_info: ObjectInfoApi,
+    pub references: ReferenceApi,
+    pub link_iterate: LinkIterateApi,
+    /// Whether the deprecated v1 functions (e.g. `H5Oget_info1`, `H5Rcreate`) were removed
+    /// from the library, as in HDF5 2.0.
+    pub legacy_removed: bool,
+}
+
+impl ApiTable {
+    /// Returns the API generations to use with the given library version.
+    pub fn for_version(version: Version) -> Self {
+        let v1_12 = version >= Version { major: 1, minor: 12, micro: 0 };
+        Self {
+            object_info: if v1_12 { ObjectInfoApi::V3 } else { ObjectInfoApi::V1 },
+            references: if v1_12 { ReferenceApi::Standard } else { ReferenceApi::Legacy },
+            link_iterate: if v1_12 { LinkIterateApi::V2 } else { LinkIterateApi::V1 },
+            legacy_removed: version.major >= 2,
+        }
+    }
+
+    /// Returns an error if the deprecated function `name` is not available in the library.
+    pub fn ensure_legacy(&self, name: &str) -> Result<(), String> {
+        if self.legacy_removed {
+            Err(format!("{} is not available in HDF5 2.x", name))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Get the API generations to use with the loaded library.
+/// Returns the table for the minimum supported version (1.10.5) if the library has not
+/// been initialized.
+pub fn api_table() -> ApiTable {
+    API_TABLE
+        .get()
+        .copied()
+        .unwrap_or_else(|| ApiTable::for_version(Version { major: 1, minor: 10, micro: 5 }))
+}
+
 /// Convert a raw HDF5 type value from H5Iget_type to our H5I_type_t enum.
 /// HDF5 1.12 added H5I_MAP and H5I_VOL which shifted all subsequent values.
 /// This function normalizes the raw value to match our HDF5 1.12+ enum.
@@ -980,10 +1310,93 @@ pub fn convert_h5i_type(raw: H5I_type_t) -> H5I_type_t {
 // Function loading macros
 // =============================================================================
 
+/// Failure value returned by the library functions without calling into the library once it
+/// has been closed by [`shutdown`].
+trait ShutDownValue {
+    fn shut_down() -> Self;
+}
+
+macro_rules! impl_shut_down_value {
+    ($($ty:ty => $value:expr),+ $(,)?) => {
+        $(impl ShutDownValue for $ty {
+            #[inline]
+            fn shut_down() -> Self {
+                $value
+            }
+        })+
+    };
+}
+
+impl_shut_down_value!(
+    c_int => -1,
+    i64 => -1,
+    isize => -1,
+    u64 => 0,
+    usize => 0,
+    *mut c_char => std::ptr::null_mut(),
+    *const c_void => std::ptr::null(),
+    *mut c_void => std::ptr::null_mut(),
+    H5T_conv_t => None,
+    H5D_layout_t => H5D_layout_t::H5D_LAYOUT_ERROR,
+    H5I_type_t => H5I_type_t::H5I_BADID,
+    H5R_type_t => H5R_type_t::H5R_BADTYPE,
+    H5S_class_t => H5S_class_t::H5S_NO_CLASS,
+    H5S_sel_type => H5S_sel_type::H5S_SEL_ERROR,
+    H5T_class_t => H5T_class_t::H5T_NO_CLASS,
+    H5T_cset_t => H5T_cset_t::H5T_CSET_ERROR,
+    H5T_order_t => H5T_order_t::H5T_ORDER_ERROR,
+    H5T_sign_t => H5T_sign_t::H5T_SGN_ERROR,
+    H5T_str_t => H5T_str_t::H5T_STR_ERROR,
+    H5Z_EDC_t => H5Z_EDC_t::H5Z_ERROR_EDC,
+);
+
+/// A function declared with `hdf5_function!`, as listed in the symbol tables walked by the
+/// [preflight](super::preflight).
+#[derive(Debug, Clone, Copy)]
+pub(super) struct FunctionDecl {
+    /// The exported symbol, which is also the name of the Rust function.
+    pub name: &'static str,
+    /// Whether this is a deprecated function removed in HDF5 2.0.
+    pub legacy: bool,
+}
+
+/// A global identifier declared with `define_native_type!`, as listed in the symbol tables
+/// walked by the [preflight](super::preflight).
+#[derive(Debug, Clone, Copy)]
+pub(super) struct GlobalDecl {
+    /// The name of the Rust accessor.
+    pub name: &'static str,
+    /// The exported symbol, for HDF5 1.12+ in the case of globals with two names.
+    pub symbol: &'static str,
+    /// The exported symbol for HDF5 1.10, for globals with version-dependent names.
+    pub symbol_1_10: Option<&'static str>,
+}
+
+/// Declares a block of functions with `hdf5_function!`, and the static table of their
+/// declarations, which must be listed in `FUNCTION_TABLES`.
+macro_rules! hdf5_functions {
+    (static $table:ident; $(hdf5_function!($($decl:tt)*);)*) => {
+        $(hdf5_function!($($decl)*);)*
+
+        static $table: &[FunctionDecl] = &[$(hdf5_function!(@decl $($decl)*)),*];
+    };
+}
+
 macro_rules! hdf5_function {
+    (@decl legacy $name:ident, $($sig:tt)*) => {
+        FunctionDecl { name: stringify!($name), legacy: true }
+    };
+    (@decl $name:ident, $($sig:tt)*) => {
+        FunctionDecl { name: stringify!($name), legacy: false }
+    };
     ($name:ident, fn($($arg:ident: $arg_ty:ty),* $(,)?) -> $ret:ty) => {
         #[inline]
         pub unsafe extern "C" fn $name($($arg: $arg_ty),*) -> $ret {
+            if is_shut_down() {
+                return ShutDownValue::shut_down();
+            }
+            #[cfg(any(test, feature = "test-hooks"))]
+            FFI_CALLS.fetch_add(1, Ordering::Relaxed);
             let lib = get_library();
             let func: Symbol<unsafe extern "C" fn($($arg_ty),*) -> $ret> = lib
                 .get(stringify!($name).as_bytes())
@@ -994,6 +1407,11 @@ macro_rules! hdf5_function {
     ($name:ident, fn($($arg:ident: $arg_ty:ty),* $(,)?)) => {
         #[inline]
         pub unsafe extern "C" fn $name($($arg: $arg_ty),*) {
+            if is_shut_down() {
+                return;
+            }
+            #[cfg(any(test, feature = "test-hooks"))]
+            FFI_CALLS.fetch_add(1, Ordering::Relaxed);
             let lib = get_library();
             let func: Symbol<unsafe extern "C" fn($($arg_ty),*)> = lib
                 .get(stringify!($name).as_bytes())
@@ -1001,371 +1419,507 @@ macro_rules! hdf5_function {
             func($($arg),*)
         }
     };
+    (legacy $name:ident, fn($($arg:ident: $arg_ty:ty),* $(,)?) -> $ret:ty) => {
+        #[doc = concat!(
+            "Deprecated `", stringify!($name), "`, removed in HDF5 2.0; returns an error there."
+        )]
+        #[inline]
+        pub unsafe fn $name($($arg: $arg_ty),*) -> Result<$ret, String> {
+            api_table().ensure_legacy(stringify!($name))?;
+            if is_shut_down() {
+                return Err(format!("{} called after the library was shut down", stringify!($name)));
+            }
+            #[cfg(any(test, feature = "test-hooks"))]
+            FFI_CALLS.fetch_add(1, Ordering::Relaxed);
+            let lib = get_library();
+            let func: Symbol<unsafe extern "C" fn($($arg_ty),*) -> $ret> = lib
+                .get(stringify!($name).as_bytes())
+                .map_err(|e| format!("Failed to load {}: {}", stringify!($name), e))?;
+            Ok(func($($arg),*))
+        }
+    };
 }
 
 // =============================================================================
 // HDF5 Functions
 // =============================================================================
 
-// H5 (Library)
-hdf5_function!(H5open, fn() -> herr_t);
-hdf5_function!(H5close, fn() -> herr_t);
-hdf5_function!(H5dont_atexit, fn() -> herr_t);
-hdf5_function!(
-    H5get_libversion,
-    fn(majnum: *mut c_uint, minnum: *mut c_uint, relnum: *mut c_uint) -> herr_t
-);
-hdf5_function!(H5is_library_threadsafe, fn(is_ts: *mut hbool_t) -> herr_t);
-hdf5_function!(H5free_memory, fn(mem: *mut c_void) -> herr_t);
-
-// H5I (Identifiers)
-hdf5_function!(H5Iget_type, fn(id: hid_t) -> H5I_type_t);
-hdf5_function!(H5Iis_valid, fn(id: hid_t) -> htri_t);
-hdf5_function!(H5Iinc_ref, fn(id: hid_t) -> c_int);
-hdf5_function!(H5Idec_ref, fn(id: hid_t) -> c_int);
-hdf5_function!(H5Iget_ref, fn(id: hid_t) -> c_int);
-hdf5_function!(H5Iget_file_id, fn(id: hid_t) -> hid_t);
-hdf5_function!(H5Iget_name, fn(id: hid_t, name: *mut c_char, size: size_t) -> ssize_t);
-
-// H5F (File)
-hdf5_function!(
-    H5Fcreate,
-    fn(filename: *const c_char, flags: c_uint, fcpl_id: hid_t, fapl_id: hid_t) -> hid_t
-);
-hdf5_function!(H5Fopen, fn(filename: *const c_char, flags: c_uint, fapl_id: hid_t) -> hid_t);
-hdf5_function!(H5Fclose, fn(file_id: hid_t) -> herr_t);
-hdf5_function!(H5Fflush, fn(object_id: hid_t, scope: c_int) -> herr_t);
-hdf5_function!(H5Fget_filesize, fn(file_id: hid_t, size: *mut hsize_t) -> herr_t);
-hdf5_function!(H5Fget_create_plist, fn(file_id: hid_t) -> hid_t);
-hdf5_function!(H5Fget_access_plist, fn(file_id: hid_t) -> hid_t);
-hdf5_function!(H5Fstart_swmr_write, fn(file_id: hid_t) -> herr_t);
-hdf5_function!(H5Fget_freespace, fn(file_id: hid_t) -> hssize_t);
-hdf5_function!(H5Fget_intent, fn(file_id: hid_t, intent: *mut c_uint) -> herr_t);
-hdf5_function!(H5Fget_obj_count, fn(file_id: hid_t, types: c_uint) -> ssize_t);
-hdf5_function!(
-    H5Fget_obj_ids,
-    fn(file_id: hid_t, types: c_uint, max_objs: size_t, obj_id_list: *mut hid_t) -> ssize_t
-);
-hdf5_function!(H5Fget_name, fn(obj_id: hid_t, name: *mut c_char, size: size_t) -> ssize_t);
-
-// H5G (Group)
-hdf5_function!(
-    H5Gcreate2,
-    fn(loc_id: hid_t, name: *const c_char, lcpl_id: hid_t, gcpl_id: hid_t, gapl_id: hid_t) -> hid_t
-);
-hdf5_function!(H5Gopen2, fn(loc_id: hid_t, name: *const c_char, gapl_id: hid_t) -> hid_t);
-hdf5_function!(H5Gclose, fn(group_id: hid_t) -> herr_t);
-hdf5_function!(H5Gget_create_plist, fn(group_id: hid_t) -> hid_t);
-hdf5_function!(H5Gget_info, fn(loc_id: hid_t, ginfo: *mut H5G_info_t) -> herr_t);
-
-// H5D (Dataset)
-hdf5_function!(
-    H5Dcreate2,
-    fn(
-        loc_id: hid_t,
-        name: *const c_char,
-        type_id: hid_t,
-        space_id: hid_t,
-        lcpl_id: hid_t,
-        dcpl_id: hid_t,
-        dapl_id: hid_t,
-    ) -> hid_t
-);
-hdf5_function!(H5Dopen2, fn(loc_id: hid_t, name: *const c_char, dapl_id: hid_t) -> hid_t);
-hdf5_function!(H5Dclose, fn(dset_id: hid_t) -> herr_t);
-hdf5_function!(H5Dget_space, fn(dset_id: hid_t) -> hid_t);
-hdf5_function!(H5Dget_type, fn(dset_id: hid_t) -> hid_t);
-hdf5_function!(H5Dget_create_plist, fn(dset_id: hid_t) -> hid_t);
-hdf5_function!(H5Dget_access_plist, fn(dset_id: hid_t) -> hid_t);
-hdf5_function!(H5Dget_storage_size, fn(dset_id: hid_t) -> hsize_t);
-hdf5_function!(
-    H5Dread,
-    fn(
-        dset_id: hid_t,
-        mem_type_id: hid_t,
-        mem_space_id: hid_t,
-        file_space_id: hid_t,
-        xfer_plist_id: hid_t,
-        buf: *mut c_void,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Dwrite,
-    fn(
-        dset_id: hid_t,
-        mem_type_id: hid_t,
-        mem_space_id: hid_t,
-        file_space_id: hid_t,
-        xfer_plist_id: hid_t,
-        buf: *const c_void,
-    ) -> herr_t
-);
-hdf5_function!(H5Dset_extent, fn(dset_id: hid_t, size: *const hsize_t) -> herr_t);
-hdf5_function!(H5Dflush, fn(dset_id: hid_t) -> herr_t);
-hdf5_function!(H5Drefresh, fn(dset_id: hid_t) -> herr_t);
-hdf5_function!(
-    H5Dget_num_chunks,
-    fn(dset_id: hid_t, fspace_id: hid_t, nchunks: *mut hsize_t) -> herr_t
-);
-hdf5_function!(
-    H5Dget_chunk_info,
-    fn(
-        dset_id: hid_t,
-        fspace_id: hid_t,
-        chk_idx: hsize_t,
-        offset: *mut hsize_t,
-        filter_mask: *mut c_uint,
-        addr: *mut haddr_t,
-        size: *mut hsize_t,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Dcreate_anon,
-    fn(loc_id: hid_t, type_id: hid_t, space_id: hid_t, dcpl_id: hid_t, dapl_id: hid_t) -> hid_t
-);
-hdf5_function!(H5Dget_offset, fn(dset_id: hid_t) -> haddr_t);
+hdf5_functions! {
+    static H5_FUNCTIONS;
+    // H5 (Library)
+    hdf5_function!(H5open, fn() -> herr_t);
+    hdf5_function!(H5close, fn() -> herr_t);
+    hdf5_function!(H5dont_atexit, fn() -> herr_t);
+    hdf5_function!(
+        H5get_libversion,
+        fn(majnum: *mut c_uint, minnum: *mut c_uint, relnum: *mut c_uint) -> herr_t
+    );
+    hdf5_function!(H5is_library_threadsafe, fn(is_ts: *mut hbool_t) -> herr_t);
+    hdf5_function!(H5free_memory, fn(mem: *mut c_void) -> herr_t);
+
+    // H5I (Identifiers)
+    hdf5_function!(H5Iget_type, fn(id: hid_t) -> H5I_type_t);
+    hdf5_function!(H5Iis_valid, fn(id: hid_t) -> htri_t);
+    hdf5_function!(H5Iinc_ref, fn(id: hid_t) -> c_int);
+    hdf5_function!(H5Idec_ref, fn(id: hid_t) -> c_int);
+    hdf5_function!(H5Iget_ref, fn(id: hid_t) -> c_int);
+    hdf5_function!(H5Iget_file_id, fn(id: hid_t) -> hid_t);
+    hdf5_function!(H5Iget_name, fn(id: hid_t, name: *mut c_char, size: size_t) -> ssize_t);
+
+    // H5I (user-defined identifier types)
+    hdf5_function!(
+        H5Iregister_type,
+        fn(hash_size: size_t, reserved: c_uint, free_func: H5I_free_t) -> H5I_type_raw_t
+    );
+    hdf5_function!(H5Iregister, fn(type_: H5I_type_raw_t, object: *const c_void) -> hid_t);
+    hdf5_function!(H5Iobject_verify, fn(id: hid_t, type_: H5I_type_raw_t) -> *mut c_void);
+    hdf5_function!(H5Iremove_verify, fn(id: hid_t, type_: H5I_type_raw_t) -> *mut c_void);
+    hdf5_function!(H5Idestroy_type, fn(type_: H5I_type_raw_t) -> herr_t);
+
+    // H5F (File)
+    hdf5_function!(
+        H5Fcreate,
+        fn(filename: *const c_char, flags: c_uint, fcpl_id: hid_t, fapl_id: hid_t) -> hid_t
+    );
+    hdf5_function!(H5Fopen, fn(filename: *const c_char, flags: c_uint, fapl_id: hid_t) -> hid_t);
+    hdf5_function!(H5Fclose, fn(file_id: hid_t) -> herr_t);
+    hdf5_function!(H5Fclear_elink_file_cache, fn(file_id: hid_t) -> herr_t);
+    hdf5_function!(H5Freopen, fn(file_id: hid_t) -> hid_t);
+    hdf5_function!(H5Fflush, fn(object_id: hid_t, scope: c_int) -> herr_t);
+    hdf5_function!(H5Fget_filesize, fn(file_id: hid_t, size: *mut hsize_t) -> herr_t);
+    hdf5_function!(
+        H5Fget_file_image,
+        fn(file_id: hid_t, buf_ptr: *mut c_void, buf_len: size_t) -> ssize_t
+    );
+    hdf5_function!(
+        H5Pset_file_image,
+        fn(fapl_id: hid_t, buf_ptr: *mut c_void, buf_len: size_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_file_image,
+        fn(fapl_id: hid_t, buf_ptr_ptr: *mut *mut c_void, buf_len_ptr: *mut size_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_file_image_callbacks,
+        fn(fapl_id: hid_t, callbacks_ptr: *mut H5FD_file_image_callbacks_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_file_image_callbacks,
+        fn(fapl_id: hid_t, callbacks_ptr: *mut H5FD_file_image_callbacks_t) -> herr_t
+    );
+    hdf5_function!(H5Fget_create_plist, fn(file_id: hid_t) -> hid_t);
+    hdf5_function!(H5Fget_access_plist, fn(file_id: hid_t) -> hid_t);
+    hdf5_function!(H5Fstart_swmr_write, fn(file_id: hid_t) -> herr_t);
+    hdf5_function!(H5Fget_freespace, fn(file_id: hid_t) -> hssize_t);
+    hdf5_function!(H5Fget_intent, fn(file_id: hid_t, intent: *mut c_uint) -> herr_t);
+    hdf5_function!(H5Fget_info2, fn(obj_id: hid_t, file_info: *mut H5F_info2_t) -> herr_t);
+    hdf5_function!(H5Fget_obj_count, fn(file_id: hid_t, types: c_uint) -> ssize_t);
+    hdf5_function!(
+        H5Fget_obj_ids,
+        fn(file_id: hid_t, types: c_uint, max_objs: size_t, obj_id_list: *mut hid_t) -> ssize_t
+    );
+    hdf5_function!(H5Fget_name, fn(obj_id: hid_t, name: *mut c_char, size: size_t) -> ssize_t);
+    hdf5_function!(
+        H5Fget_vfd_handle,
+        fn(file_id: hid_t, fapl_id: hid_t, file_handle: *mut *mut c_void) -> herr_t
+    );
+
+    // H5G (Group)
+    hdf5_function!(
+        H5Gcreate2,
+        fn(loc_id: hid_t, name: *const c_char, lcpl_id: hid_t, gcpl_id: hid_t, gapl_id: hid_t) -> hid_t
+    );
+    hdf5_function!(H5Gcreate_anon, fn(loc_id: hid_t, gcpl_id: hid_t, gapl_id: hid_t) -> hid_t);
+    hdf5_function!(H5Gopen2, fn(loc_id: hid_t, name: *const c_char, gapl_id: hid_t) -> hid_t);
+    hdf5_function!(H5Gclose, fn(group_id: hid_t) -> herr_t);
+    hdf5_function!(H5Gget_create_plist, fn(group_id: hid_t) -> hid_t);
+    hdf5_function!(H5Gget_info, fn(loc_id: hid_t, ginfo: *mut H5G_info_t) -> herr_t);
+
+    // H5D (Dataset)
+    hdf5_function!(
+        H5Dcreate2,
+        fn(
+            loc_id: hid_t,
+            name: *const c_char,
+            type_id: hid_t,
+            space_id: hid_t,
+            lcpl_id: hid_t,
+            dcpl_id: hid_t,
+            dapl_id: hid_t,
+        ) -> hid_t
+    );
+    hdf5_function!(H5Dopen2, fn(loc_id: hid_t, name: *const c_char, dapl_id: hid_t) -> hid_t);
+    hdf5_function!(H5Dclose, fn(dset_id: hid_t) -> herr_t);
+    hdf5_function!(H5Dget_space, fn(dset_id: hid_t) -> hid_t);
+    hdf5_function!(H5Dget_type, fn(dset_id: hid_t) -> hid_t);
+    hdf5_function!(H5Dget_create_plist, fn(dset_id: hid_t) -> hid_t);
+    hdf5_function!(H5Dget_access_plist, fn(dset_id: hid_t) -> hid_t);
+    hdf5_function!(H5Dget_storage_size, fn(dset_id: hid_t) -> hsize_t);
+    hdf5_function!(
+        H5Dget_space_status,
+        fn(dset_id: hid_t, allocation: *mut H5D_space_status_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Dread,
+        fn(
+            dset_id: hid_t,
+            mem_type_id: hid_t,
+            mem_space_id: hid_t,
+            file_space_id: hid_t,
+            xfer_plist_id: hid_t,
+            buf: *mut c_void,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Dwrite,
+        fn(
+            dset_id: hid_t,
+            mem_type_id: hid_t,
+            mem_space_id: hid_t,
+            file_space_id: hid_t,
+            xfer_plist_id: hid_t,
+            buf: *const c_void,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Dset_extent, fn(dset_id: hid_t, size: *const hsize_t) -> herr_t);
+    hdf5_function!(
+        legacy H5Dvlen_reclaim,
+        fn(type_id: hid_t, space_id: hid_t, dxpl_id: hid_t, buf: *mut c_void) -> herr_t
+    );
+    hdf5_function!(H5Dflush, fn(dset_id: hid_t) -> herr_t);
+    hdf5_function!(H5Drefresh, fn(dset_id: hid_t) -> herr_t);
+    hdf5_function!(
+        H5Dget_num_chunks,
+        fn(dset_id: hid_t, fspace_id: hid_t, nchunks: *mut hsize_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Dget_chunk_info,
+        fn(
+            dset_id: hid_t,
+            fspace_id: hid_t,
+            chk_idx: hsize_t,
+            offset: *mut hsize_t,
+            filter_mask: *mut c_uint,
+            addr: *mut haddr_t,
+            size: *mut hsize_t,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Dwrite_chunk,
+        fn(
+            dset_id: hid_t,
+            dxpl_id: hid_t,
+            filters: u32,
+            offset: *const hsize_t,
+            data_size: size_t,
+            buf: *const c_void,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Dcreate_anon,
+        fn(loc_id: hid_t, type_id: hid_t, space_id: hid_t, dcpl_id: hid_t, dapl_id: hid_t) -> hid_t
+    );
+    hdf5_function!(H5Dget_offset, fn(dset_id: hid_t) -> haddr_t);
+
+    // H5S (Dataspace)
+    hdf5_function!(H5Screate, fn(type_: H5S_class_t) -> hid_t);
+    hdf5_function!(
+        H5Screate_simple,
+        fn(rank: c_int, dims: *const hsize_t, maxdims: *const hsize_t) -> hid_t
+    );
+    hdf5_function!(H5Scopy, fn(space_id: hid_t) -> hid_t);
+    hdf5_function!(H5Sclose, fn(space_id: hid_t) -> herr_t);
+    hdf5_function!(H5Sget_simple_extent_ndims, fn(space_id: hid_t) -> c_int);
+    hdf5_function!(
+        H5Sget_simple_extent_dims,
+        fn(space_id: hid_t, dims: *mut hsize_t, maxdims: *mut hsize_t) -> c_int
+    );
+    hdf5_function!(H5Sget_simple_extent_npoints, fn(space_id: hid_t) -> hssize_t);
+    hdf5_function!(H5Sget_simple_extent_type, fn(space_id: hid_t) -> H5S_class_t);
+    hdf5_function!(
+        H5Sselect_hyperslab,
+        fn(
+            space_id: hid_t,
+            op: H5S_seloper_t,
+            start: *const hsize_t,
+            stride: *const hsize_t,
+            count: *const hsize_t,
+            block: *const hsize_t,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Sselect_elements,
+        fn(space_id: hid_t, op: H5S_seloper_t, num_elements: size_t, coord: *const hsize_t) -> herr_t
+    );
+    hdf5_function!(H5Sselect_all, fn(space_id: hid_t) -> herr_t);
+    hdf5_function!(H5Sselect_none, fn(space_id: hid_t) -> herr_t);
+    hdf5_function!(H5Sselect_valid, fn(space_id: hid_t) -> htri_t);
+    hdf5_function!(H5Sget_select_npoints, fn(space_id: hid_t) -> hssize_t);
+    hdf5_function!(H5Sget_select_type, fn(space_id: hid_t) -> H5S_sel_type);
+    hdf5_function!(H5Sget_select_elem_npoints, fn(space_id: hid_t) -> hssize_t);
+    hdf5_function!(
+        H5Sget_select_elem_pointlist,
+        fn(space_id: hid_t, startpoint: hsize_t, numpoints: hsize_t, buf: *mut hsize_t) -> herr_t
+    );
+    hdf5_function!(H5Sis_regular_hyperslab, fn(space_id: hid_t) -> htri_t);
+    hdf5_function!(
+        H5Sget_regular_hyperslab,
+        fn(
+            space_id: hid_t,
+            start: *mut hsize_t,
+            stride: *mut hsize_t,
+            count: *mut hsize_t,
+            block: *mut hsize_t,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Sencode2,
+        fn(obj_id: hid_t, buf: *mut c_void, nalloc: *mut size_t, fapl: hid_t) -> herr_t
+    );
+    hdf5_function!(H5Sdecode, fn(buf: *const c_void) -> hid_t);
+    // H5S selection combinators (HDF5 1.10.6+)
+    hdf5_function!(
+        H5Scombine_hyperslab,
+        fn(
+            space_id: hid_t,
+            op: H5S_seloper_t,
+            start: *const hsize_t,
+            stride: *const hsize_t,
+            count: *const hsize_t,
+            block: *const hsize_t,
+        ) -> hid_t
+    );
+    hdf5_function!(
+        H5Scombine_select,
+        fn(space1_id: hid_t, op: H5S_seloper_t, space2_id: hid_t) -> hid_t
+    );
+    hdf5_function!(
+        H5Smodify_select,
+        fn(space1_id: hid_t, op: H5S_seloper_t, space2_id: hid_t) -> herr_t
+    );
+
+    // H5T (Datatype)
+    hdf5_function!(H5Tcreate, fn(type_: H5T_class_t, size: size_t) -> hid_t);
+    hdf5_function!(H5Tcopy, fn(type_id: hid_t) -> hid_t);
+    hdf5_function!(H5Tclose, fn(type_id: hid_t) -> herr_t);
+    hdf5_function!(H5Tequal, fn(type1_id: hid_t, type2_id: hid_t) -> htri_t);
+    hdf5_function!(H5Tget_class, fn(type_id: hid_t) -> H5T_class_t);
+    hdf5_function!(H5Tget_size, fn(type_id: hid_t) -> size_t);
+    hdf5_function!(H5Tget_super, fn(type_id: hid_t) -> hid_t);
+    hdf5_function!(H5Tget_native_type, fn(type_id: hid_t, direction: c_int) -> hid_t);
+    hdf5_function!(H5Tget_order, fn(type_id: hid_t) -> H5T_order_t);
+    hdf5_function!(H5Tget_sign, fn(type_id: hid_t) -> H5T_sign_t);
+    hdf5_function!(H5Tget_precision, fn(type_id: hid_t) -> size_t);
+    hdf5_function!(H5Tget_offset, fn(type_id: hid_t) -> c_int);
+    hdf5_function!(H5Tget_nmembers, fn(type_id: hid_t) -> c_int);
+    hdf5_function!(H5Tget_member_name, fn(type_id: hid_t, membno: c_uint) -> *mut c_char);
+    hdf5_function!(H5Tget_member_offset, fn(type_id: hid_t, membno: c_uint) -> size_t);
+    hdf5_function!(H5Tget_member_type, fn(type_id: hid_t, membno: c_uint) -> hid_t);
+    hdf5_function!(
+        H5Tget_member_value,
+        fn(type_id: hid_t, membno: c_uint, value: *mut c_void) -> herr_t
+    );
+    hdf5_function!(H5Tget_array_ndims, fn(type_id: hid_t) -> c_int);
+    hdf5_function!(H5Tget_array_dims2, fn(type_id: hid_t, dims: *mut hsize_t) -> c_int);
+    hdf5_function!(H5Tget_cset, fn(type_id: hid_t) -> H5T_cset_t);
+    hdf5_function!(H5Tget_strpad, fn(type_id: hid_t) -> H5T_str_t);
+    hdf5_function!(H5Tis_variable_str, fn(type_id: hid_t) -> htri_t);
+    hdf5_function!(H5Tset_size, fn(type_id: hid_t, size: size_t) -> herr_t);
+    hdf5_function!(H5Tset_order, fn(type_id: hid_t, order: H5T_order_t) -> herr_t);
+    hdf5_function!(H5Tset_precision, fn(type_id: hid_t, prec: size_t) -> herr_t);
+    hdf5_function!(H5Tset_offset, fn(type_id: hid_t, offset: size_t) -> herr_t);
+    hdf5_function!(H5Tset_cset, fn(type_id: hid_t, cset: H5T_cset_t) -> herr_t);
+    hdf5_function!(H5Tset_strpad, fn(type_id: hid_t, strpad: H5T_str_t) -> herr_t);
+    hdf5_function!(H5Tset_tag, fn(type_id: hid_t, tag: *const c_char) -> herr_t);
+    hdf5_function!(H5Tget_tag, fn(type_id: hid_t) -> *mut c_char);
+    hdf5_function!(H5Tset_ebias, fn(type_id: hid_t, ebias: size_t) -> herr_t);
+    hdf5_function!(
+        H5Tset_fields,
+        fn(
+            type_id: hid_t,
+            spos: size_t,
+            epos: size_t,
+            esize: size_t,
+            mpos: size_t,
+            msize: size_t,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Tinsert,
+        fn(parent_id: hid_t, name: *const c_char, offset: size_t, member_id: hid_t) -> herr_t
+    );
+    hdf5_function!(H5Tarray_create2, fn(base_id: hid_t, ndims: c_uint, dim: *const hsize_t) -> hid_t);
+    hdf5_function!(H5Tenum_create, fn(base_id: hid_t) -> hid_t);
+    hdf5_function!(
+        H5Tenum_insert,
+        fn(type_id: hid_t, name: *const c_char, value: *const c_void) -> herr_t
+    );
+    hdf5_function!(
+        H5Tenum_nameof,
+        fn(type_id: hid_t, value: *const c_void, name: *mut c_char, size: size_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Tenum_valueof,
+        fn(type_id: hid_t, name: *const c_char, value: *mut c_void) -> herr_t
+    );
+    hdf5_function!(H5Tvlen_create, fn(base_id: hid_t) -> hid_t);
+    hdf5_function!(
+        H5Treclaim,
+        fn(type_id: hid_t, space_id: hid_t, dxpl_id: hid_t, buf: *mut c_void) -> herr_t
+    );
+    hdf5_function!(
+        H5Tcommit2,
+        fn(
+            loc_id: hid_t,
+            name: *const c_char,
+            type_id: hid_t,
+            lcpl_id: hid_t,
+            tcpl_id: hid_t,
+            tapl_id: hid_t,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Tcommitted, fn(type_id: hid_t) -> htri_t);
+    hdf5_function!(
+        H5Tfind,
+        fn(src_id: hid_t, dst_id: hid_t, pcdata: *mut *mut H5T_cdata_t) -> H5T_conv_t
+    );
+    hdf5_function!(H5Tcompiler_conv, fn(src_id: hid_t, dst_id: hid_t) -> htri_t);
+
+    // H5A (Attribute)
+    hdf5_function!(
+        H5Acreate2,
+        fn(
+            loc_id: hid_t,
+            attr_name: *const c_char,
+            type_id: hid_t,
+            space_id: hid_t,
+            acpl_id: hid_t,
+            aapl_id: hid_t,
+        ) -> hid_t
+    );
+    hdf5_function!(H5Aopen, fn(obj_id: hid_t, attr_name: *const c_char, aapl_id: hid_t) -> hid_t);
+    hdf5_function!(
+        H5Aopen_by_idx,
+        fn(
+            loc_id: hid_t,
+            obj_name: *const c_char,
+            idx_type: H5_index_t,
+            order: H5_iter_order_t,
+            n: hsize_t,
+            aapl_id: hid_t,
+            lapl_id: hid_t,
+        ) -> hid_t
+    );
+    hdf5_function!(H5Aclose, fn(attr_id: hid_t) -> herr_t);
+    hdf5_function!(H5Aread, fn(attr_id: hid_t, type_id: hid_t, buf: *mut c_void) -> herr_t);
+    hdf5_function!(H5Awrite, fn(attr_id: hid_t, type_id: hid_t, buf: *const c_void) -> herr_t);
+    hdf5_function!(H5Aget_space, fn(attr_id: hid_t) -> hid_t);
+    hdf5_function!(H5Aget_type, fn(attr_id: hid_t) -> hid_t);
+    hdf5_function!(H5Aget_name, fn(attr_id: hid_t, buf_size: size_t, buf: *mut c_char) -> ssize_t);
+    hdf5_function!(H5Aget_storage_size, fn(attr_id: hid_t) -> hsize_t);
+    hdf5_function!(H5Adelete, fn(loc_id: hid_t, name: *const c_char) -> herr_t);
+    hdf5_function!(
+        H5Adelete_by_idx,
+        fn(
+            loc_id: hid_t,
+            obj_name: *const c_char,
+            idx_type: H5_index_t,
+            order: H5_iter_order_t,
+            n: hsize_t,
+            lapl_id: hid_t,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Aexists, fn(obj_id: hid_t, attr_name: *const c_char) -> htri_t);
+    hdf5_function!(H5Aget_num_attrs, fn(loc_id: hid_t) -> c_int);
+    hdf5_function!(
+        H5Aiterate2,
+        fn(
+            loc_id: hid_t,
+            idx_type: H5_index_t,
+            order: H5_iter_order_t,
+            idx: *mut hsize_t,
+            op: Option<
+                unsafe extern "C" fn(hid_t, *const c_char, *const H5A_info_t, *mut c_void) -> herr_t,
+            >,
+            op_data: *mut c_void,
+        ) -> herr_t
+    );
+
+    // H5L (Link)
+    hdf5_function!(
+        H5Lcreate_hard,
+        fn(
+            cur_loc: hid_t,
+            cur_name: *const c_char,
+            dst_loc: hid_t,
+            dst_name: *const c_char,
+            lcpl_id: hid_t,
+            lapl_id: hid_t,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Lcreate_soft,
+        fn(
+            link_target: *const c_char,
+            link_loc_id: hid_t,
+            link_name: *const c_char,
+            lcpl_id: hid_t,
+            lapl_id: hid_t,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Lcreate_external,
+        fn(
+            file_name: *const c_char,
+            obj_name: *const c_char,
+            link_loc_id: hid_t,
+            link_name: *const c_char,
+            lcpl_id: hid_t,
+            lapl_id: hid_t,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Ldelete, fn(loc_id: hid_t, name: *const c_char, lapl_id: hid_t) -> herr_t);
+    hdf5_function!(H5Lexists, fn(loc_id: hid_t, name: *const c_char, lapl_id: hid_t) -> htri_t);
+    hdf5_function!(
+        H5Lmove,
+        fn(
+            src_loc: hid_t,
+            src_name: *const c_char,
+            dst_loc: hid_t,
+            dst_name: *const c_char,
+            lcpl_id: hid_t,
+            lapl_id: hid_t,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Literate2,
+        fn(
+            grp_id: hid_t,
+            idx_type: H5_index_t,
+            order: H5_iter_order_t,
+            idx: *mut hsize_t,
+            op: Option<
+                unsafe extern "C" fn(hid_t, *const c_char, *const H5L_info2_t, *mut c_void) -> herr_t,
+            >,
+            op_data: *mut c_void,
+        ) -> herr_t
+    );
+}
 
-// H5S (Dataspace)
-hdf5_function!(H5Screate, fn(type_: H5S_class_t) -> hid_t);
-hdf5_function!(
-    H5Screate_simple,
-    fn(rank: c_int, dims: *const hsize_t, maxdims: *const hsize_t) -> hid_t
-);
-hdf5_function!(H5Scopy, fn(space_id: hid_t) -> hid_t);
-hdf5_function!(H5Sclose, fn(space_id: hid_t) -> herr_t);
-hdf5_function!(H5Sget_simple_extent_ndims, fn(space_id: hid_t) -> c_int);
-hdf5_function!(
-    H5Sget_simple_extent_dims,
-    fn(space_id: hid_t, dims: *mut hsize_t, maxdims: *mut hsize_t) -> c_int
-);
-hdf5_function!(H5Sget_simple_extent_npoints, fn(space_id: hid_t) -> hssize_t);
-hdf5_function!(H5Sget_simple_extent_type, fn(space_id: hid_t) -> H5S_class_t);
-hdf5_function!(
-    H5Sselect_hyperslab,
-    fn(
-        space_id: hid_t,
-        op: H5S_seloper_t,
-        start: *const hsize_t,
-        stride: *const hsize_t,
-        count: *const hsize_t,
-        block: *const hsize_t,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Sselect_elements,
-    fn(space_id: hid_t, op: H5S_seloper_t, num_elements: size_t, coord: *const hsize_t) -> herr_t
-);
-hdf5_function!(H5Sselect_all, fn(space_id: hid_t) -> herr_t);
-hdf5_function!(H5Sselect_none, fn(space_id: hid_t) -> herr_t);
-hdf5_function!(H5Sselect_valid, fn(space_id: hid_t) -> htri_t);
-hdf5_function!(H5Sget_select_npoints, fn(space_id: hid_t) -> hssize_t);
-hdf5_function!(H5Sget_select_type, fn(space_id: hid_t) -> H5S_sel_type);
-hdf5_function!(H5Sget_select_elem_npoints, fn(space_id: hid_t) -> hssize_t);
-hdf5_function!(
-    H5Sget_select_elem_pointlist,
-    fn(space_id: hid_t, startpoint: hsize_t, numpoints: hsize_t, buf: *mut hsize_t) -> herr_t
-);
-hdf5_function!(H5Sis_regular_hyperslab, fn(space_id: hid_t) -> htri_t);
-hdf5_function!(
-    H5Sget_regular_hyperslab,
-    fn(
-        space_id: hid_t,
-        start: *mut hsize_t,
-        stride: *mut hsize_t,
-        count: *mut hsize_t,
-        block: *mut hsize_t,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Sencode2,
-    fn(obj_id: hid_t, buf: *mut c_void, nalloc: *mut size_t, fapl: hid_t) -> herr_t
-);
-hdf5_function!(H5Sdecode, fn(buf: *const c_void) -> hid_t);
-
-// H5T (Datatype)
-hdf5_function!(H5Tcreate, fn(type_: H5T_class_t, size: size_t) -> hid_t);
-hdf5_function!(H5Tcopy, fn(type_id: hid_t) -> hid_t);
-hdf5_function!(H5Tclose, fn(type_id: hid_t) -> herr_t);
-hdf5_function!(H5Tequal, fn(type1_id: hid_t, type2_id: hid_t) -> htri_t);
-hdf5_function!(H5Tget_class, fn(type_id: hid_t) -> H5T_class_t);
-hdf5_function!(H5Tget_size, fn(type_id: hid_t) -> size_t);
-hdf5_function!(H5Tget_super, fn(type_id: hid_t) -> hid_t);
-hdf5_function!(H5Tget_native_type, fn(type_id: hid_t, direction: c_int) -> hid_t);
-hdf5_function!(H5Tget_order, fn(type_id: hid_t) -> H5T_order_t);
-hdf5_function!(H5Tget_sign, fn(type_id: hid_t) -> H5T_sign_t);
-hdf5_function!(H5Tget_precision, fn(type_id: hid_t) -> size_t);
-hdf5_function!(H5Tget_offset, fn(type_id: hid_t) -> c_int);
-hdf5_function!(H5Tget_nmembers, fn(type_id: hid_t) -> c_int);
-hdf5_function!(H5Tget_member_name, fn(type_id: hid_t, membno: c_uint) -> *mut c_char);
-hdf5_function!(H5Tget_member_offset, fn(type_id: hid_t, membno: c_uint) -> size_t);
-hdf5_function!(H5Tget_member_type, fn(type_id: hid_t, membno: c_uint) -> hid_t);
-hdf5_function!(
-    H5Tget_member_value,
-    fn(type_id: hid_t, membno: c_uint, value: *mut c_void) -> herr_t
-);
-hdf5_function!(H5Tget_array_ndims, fn(type_id: hid_t) -> c_int);
-hdf5_function!(H5Tget_array_dims2, fn(type_id: hid_t, dims: *mut hsize_t) -> c_int);
-hdf5_function!(H5Tget_cset, fn(type_id: hid_t) -> H5T_cset_t);
-hdf5_function!(H5Tget_strpad, fn(type_id: hid_t) -> H5T_str_t);
-hdf5_function!(H5Tis_variable_str, fn(type_id: hid_t) -> htri_t);
-hdf5_function!(H5Tset_size, fn(type_id: hid_t, size: size_t) -> herr_t);
-hdf5_function!(H5Tset_order, fn(type_id: hid_t, order: H5T_order_t) -> herr_t);
-hdf5_function!(H5Tset_precision, fn(type_id: hid_t, prec: size_t) -> herr_t);
-hdf5_function!(H5Tset_offset, fn(type_id: hid_t, offset: size_t) -> herr_t);
-hdf5_function!(H5Tset_cset, fn(type_id: hid_t, cset: H5T_cset_t) -> herr_t);
-hdf5_function!(H5Tset_strpad, fn(type_id: hid_t, strpad: H5T_str_t) -> herr_t);
-hdf5_function!(H5Tset_ebias, fn(type_id: hid_t, ebias: size_t) -> herr_t);
-hdf5_function!(
-    H5Tset_fields,
-    fn(
-        type_id: hid_t,
-        spos: size_t,
-        epos: size_t,
-        esize: size_t,
-        mpos: size_t,
-        msize: size_t,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Tinsert,
-    fn(parent_id: hid_t, name: *const c_char, offset: size_t, member_id: hid_t) -> herr_t
-);
-hdf5_function!(H5Tarray_create2, fn(base_id: hid_t, ndims: c_uint, dim: *const hsize_t) -> hid_t);
-hdf5_function!(H5Tenum_create, fn(base_id: hid_t) -> hid_t);
-hdf5_function!(
-    H5Tenum_insert,
-    fn(type_id: hid_t, name: *const c_char, value: *const c_void) -> herr_t
-);
-hdf5_function!(H5Tvlen_create, fn(base_id: hid_t) -> hid_t);
-hdf5_function!(
-    H5Tcommit2,
-    fn(
-        loc_id: hid_t,
-        name: *const c_char,
-        type_id: hid_t,
-        lcpl_id: hid_t,
-        tcpl_id: hid_t,
-        tapl_id: hid_t,
-    ) -> herr_t
-);
-hdf5_function!(H5Tcommitted, fn(type_id: hid_t) -> htri_t);
-hdf5_function!(
-    H5Tfind,
-    fn(src_id: hid_t, dst_id: hid_t, pcdata: *mut *mut H5T_cdata_t) -> H5T_conv_t
-);
-hdf5_function!(H5Tcompiler_conv, fn(src_id: hid_t, dst_id: hid_t) -> htri_t);
-
-// H5A (Attribute)
-hdf5_function!(
-    H5Acreate2,
-    fn(
-        loc_id: hid_t,
-        attr_name: *const c_char,
-        type_id: hid_t,
-        space_id: hid_t,
-        acpl_id: hid_t,
-        aapl_id: hid_t,
-    ) -> hid_t
-);
-hdf5_function!(H5Aopen, fn(obj_id: hid_t, attr_name: *const c_char, aapl_id: hid_t) -> hid_t);
-hdf5_function!(
-    H5Aopen_by_idx,
-    fn(
-        loc_id: hid_t,
-        obj_name: *const c_char,
-        idx_type: H5_index_t,
-        order: H5_iter_order_t,
-        n: hsize_t,
-        aapl_id: hid_t,
-        lapl_id: hid_t,
-    ) -> hid_t
-);
-hdf5_function!(H5Aclose, fn(attr_id: hid_t) -> herr_t);
-hdf5_function!(H5Aread, fn(attr_id: hid_t, type_id: hid_t, buf: *mut c_void) -> herr_t);
-hdf5_function!(H5Awrite, fn(attr_id: hid_t, type_id: hid_t, buf: *const c_void) -> herr_t);
-hdf5_function!(H5Aget_space, fn(attr_id: hid_t) -> hid_t);
-hdf5_function!(H5Aget_type, fn(attr_id: hid_t) -> hid_t);
-hdf5_function!(H5Aget_name, fn(attr_id: hid_t, buf_size: size_t, buf: *mut c_char) -> ssize_t);
-hdf5_function!(H5Aget_storage_size, fn(attr_id: hid_t) -> hsize_t);
-hdf5_function!(H5Adelete, fn(loc_id: hid_t, name: *const c_char) -> herr_t);
-hdf5_function!(H5Aexists, fn(obj_id: hid_t, attr_name: *const c_char) -> htri_t);
-hdf5_function!(H5Aget_num_attrs, fn(loc_id: hid_t) -> c_int);
-hdf5_function!(
-    H5Aiterate2,
-    fn(
-        loc_id: hid_t,
-        idx_type: H5_index_t,
-        order: H5_iter_order_t,
-        idx: *mut hsize_t,
-        op: Option<
-            unsafe extern "C" fn(hid_t, *const c_char, *const H5A_info_t, *mut c_void) -> herr_t,
-        >,
-        op_data: *mut c_void,
-    ) -> herr_t
-);
+#[cfg(test)]
+thread_local! {
+    static LINK_ITERATE_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
 
-// H5L (Link)
-hdf5_function!(
-    H5Lcreate_hard,
-    fn(
-        cur_loc: hid_t,
-        cur_name: *const c_char,
-        dst_loc: hid_t,
-        dst_name: *const c_char,
-        lcpl_id: hid_t,
-        lapl_id: hid_t,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Lcreate_soft,
-    fn(
-        link_target: *const c_char,
-        link_loc_id: hid_t,
-        link_name: *const c_char,
-        lcpl_id: hid_t,
-        lapl_id: hid_t,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Lcreate_external,
-    fn(
-        file_name: *const c_char,
-        obj_name: *const c_char,
-        link_loc_id: hid_t,
-        link_name: *const c_char,
-        lcpl_id: hid_t,
-        lapl_id: hid_t,
-    ) -> herr_t
-);
-hdf5_function!(H5Ldelete, fn(loc_id: hid_t, name: *const c_char, lapl_id: hid_t) -> herr_t);
-hdf5_function!(H5Lexists, fn(loc_id: hid_t, name: *const c_char, lapl_id: hid_t) -> htri_t);
-hdf5_function!(
-    H5Lmove,
-    fn(
-        src_loc: hid_t,
-        src_name: *const c_char,
-        dst_loc: hid_t,
-        dst_name: *const c_char,
-        lcpl_id: hid_t,
-        lapl_id: hid_t,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Literate2,
-    fn(
-        grp_id: hid_t,
-        idx_type: H5_index_t,
-        order: H5_iter_order_t,
-        idx: *mut hsize_t,
-        op: Option<
-            unsafe extern "C" fn(hid_t, *const c_char, *const H5L_info2_t, *mut c_void) -> herr_t,
-        >,
-        op_data: *mut c_void,
-    ) -> herr_t
-);
+/// Returns the number of [`H5Literate`] calls made on the current thread, to test that link
+/// listings are collected in a single pass.
+#[cfg(test)]
+pub(crate) fn link_iterate_calls() -> usize {
+    LINK_ITERATE_CALLS.with(std::cell::Cell::get)
+}
 
 /// Version-dependent wrapper for H5Literate
-/// Uses H5Literate2 on HDF5 1.12.0+ and H5Literate on earlier versions
+/// Uses H5Literate2 on HDF5 1.12.0+ and H5Literate on earlier versions (see [`ApiTable`])
 pub unsafe fn H5Literate(
     grp_id: hid_t,
     idx_type: H5_index_t,
@@ -1374,7 +1928,9 @@ pub unsafe fn H5Literate(
     op: H5L_iterate2_t,
     op_data: *mut c_void,
 ) -> herr_t {
-    if hdf5_version_at_least(1, 12, 0) {
+    #[cfg(test)]
+    LINK_ITERATE_CALLS.with(|calls| calls.set(calls.get() + 1));
+    if api_table().link_iterate == LinkIterateApi::V2 {
         H5Literate2(grp_id, idx_type, order, idx, op, op_data)
     } else {
         // In HDF5 1.10.x, the function is called "H5Literate" (no version suffix)
@@ -1400,66 +1956,94 @@ pub unsafe fn H5Literate(
     }
 }
 
-hdf5_function!(
-    H5Lget_info2,
-    fn(loc_id: hid_t, name: *const c_char, linfo: *mut H5L_info2_t, lapl_id: hid_t) -> herr_t
-);
-
-// H5O (Object)
-hdf5_function!(H5Oopen, fn(loc_id: hid_t, name: *const c_char, lapl_id: hid_t) -> hid_t);
-hdf5_function!(H5Oclose, fn(object_id: hid_t) -> herr_t);
-hdf5_function!(
-    H5Ocopy,
-    fn(
-        src_loc_id: hid_t,
-        src_name: *const c_char,
-        dst_loc_id: hid_t,
-        dst_name: *const c_char,
-        ocpypl_id: hid_t,
-        lcpl_id: hid_t,
-    ) -> herr_t
-);
-hdf5_function!(H5Oget_info3, fn(loc_id: hid_t, oinfo: *mut H5O_info2_t, fields: c_uint) -> herr_t);
-hdf5_function!(
-    H5Oget_info_by_name3,
-    fn(
-        loc_id: hid_t,
-        name: *const c_char,
-        oinfo: *mut H5O_info2_t,
-        fields: c_uint,
-        lapl_id: hid_t,
-    ) -> herr_t
-);
-hdf5_function!(H5Oopen_by_token, fn(loc_id: hid_t, token: H5O_token_t) -> hid_t);
-hdf5_function!(H5Oset_comment, fn(obj_id: hid_t, comment: *const c_char) -> herr_t);
-hdf5_function!(H5Oget_comment, fn(obj_id: hid_t, comment: *mut c_char, bufsize: size_t) -> ssize_t);
-
-// Pre-1.12 functions (loaded conditionally)
-
-/// H5Oget_info1 - Available in HDF5 1.10.3+
-/// Note: H5Oget_info1 has only 2 parameters (no fields), unlike H5Oget_info2/3
-/// Returns None if the function is not available
-pub unsafe fn H5Oget_info1(loc_id: hid_t, oinfo: *mut H5O_info1_t) -> Option<herr_t> {
-    let lib = get_library();
-    let func: Option<Symbol<unsafe extern "C" fn(hid_t, *mut H5O_info1_t) -> herr_t>> =
-        lib.get(b"H5Oget_info1").ok();
-    func.map(|f| f(loc_id, oinfo))
-}
-
-/// H5Oget_info_by_name1 - Available in HDF5 1.10.3+
-/// Note: H5Oget_info_by_name1 has only 4 parameters (no fields), unlike version 2/3
-/// Returns None if the function is not available
-pub unsafe fn H5Oget_info_by_name1(
-    loc_id: hid_t,
-    name: *const c_char,
-    oinfo: *mut H5O_info1_t,
-    lapl_id: hid_t,
-) -> Option<herr_t> {
-    let lib = get_library();
-    let func: Option<
-        Symbol<unsafe extern "C" fn(hid_t, *const c_char, *mut H5O_info1_t, hid_t) -> herr_t>,
-    > = lib.get(b"H5Oget_info_by_name1").ok();
-    func.map(|f| f(loc_id, name, oinfo, lapl_id))
+hdf5_functions! {
+    static H5L_FUNCTIONS;
+    hdf5_function!(
+        H5Lget_info2,
+        fn(loc_id: hid_t, name: *const c_char, linfo: *mut H5L_info2_t, lapl_id: hid_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Lget_val,
+        fn(
+            loc_id: hid_t,
+            name: *const c_char,
+            buf: *mut c_void,
+            size: size_t,
+            lapl_id: hid_t,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Lunpack_elink_val,
+        fn(
+            ext_linkval: *const c_void,
+            link_size: size_t,
+            flags: *mut c_uint,
+            filename: *mut *const c_char,
+            obj_path: *mut *const c_char,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Lget_name_by_idx,
+        fn(
+            loc_id: hid_t,
+            group_name: *const c_char,
+            idx_type: H5_index_t,
+            order: H5_iter_order_t,
+            n: hsize_t,
+            name: *mut c_char,
+            size: size_t,
+            lapl_id: hid_t,
+        ) -> ssize_t
+    );
+
+    // H5O (Object)
+    hdf5_function!(H5Oopen, fn(loc_id: hid_t, name: *const c_char, lapl_id: hid_t) -> hid_t);
+    hdf5_function!(
+        H5Oopen_by_idx,
+        fn(
+            loc_id: hid_t,
+            group_name: *const c_char,
+            idx_type: H5_index_t,
+            order: H5_iter_order_t,
+            n: hsize_t,
+            lapl_id: hid_t,
+        ) -> hid_t
+    );
+    hdf5_function!(H5Oclose, fn(object_id: hid_t) -> herr_t);
+    hdf5_function!(
+        H5Ocopy,
+        fn(
+            src_loc_id: hid_t,
+            src_name: *const c_char,
+            dst_loc_id: hid_t,
+            dst_name: *const c_char,
+            ocpypl_id: hid_t,
+            lcpl_id: hid_t,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Oget_info3, fn(loc_id: hid_t, oinfo: *mut H5O_info2_t, fields: c_uint) -> herr_t);
+    hdf5_function!(
+        H5Oget_info_by_name3,
+        fn(
+            loc_id: hid_t,
+            name: *const c_char,
+            oinfo: *mut H5O_info2_t,
+            fields: c_uint,
+            lapl_id: hid_t,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Oopen_by_token, fn(loc_id: hid_t, token: H5O_token_t) -> hid_t);
+    hdf5_function!(H5Oset_comment, fn(obj_id: hid_t, comment: *const c_char) -> herr_t);
+    hdf5_function!(H5Oget_comment, fn(obj_id: hid_t, comment: *mut c_char, bufsize: size_t) -> ssize_t);
+
+    // Pre-1.12 functions (loaded conditionally)
+
+    // H5Oget_info1 and H5Oget_info_by_name1 have no fields parameter, unlike version 2/3
+    hdf5_function!(legacy H5Oget_info1, fn(loc_id: hid_t, oinfo: *mut H5O_info1_t) -> herr_t);
+    hdf5_function!(
+        legacy H5Oget_info_by_name1,
+        fn(loc_id: hid_t, name: *const c_char, oinfo: *mut H5O_info1_t, lapl_id: hid_t) -> herr_t
+    );
 }
 
 /// H5Oopen_by_addr - Available in all HDF5 versions
@@ -1490,370 +2074,475 @@ pub unsafe fn H5Sencode(
     }
 }
 
-// H5P (Property List)
-hdf5_function!(H5Pcreate, fn(cls_id: hid_t) -> hid_t);
-hdf5_function!(H5Pcopy, fn(plist_id: hid_t) -> hid_t);
-hdf5_function!(H5Pclose, fn(plist_id: hid_t) -> herr_t);
-hdf5_function!(H5Pget_class, fn(plist_id: hid_t) -> hid_t);
-hdf5_function!(H5Pequal, fn(id1: hid_t, id2: hid_t) -> htri_t);
-hdf5_function!(H5Pexist, fn(plist_id: hid_t, name: *const c_char) -> htri_t);
-hdf5_function!(H5Pset_create_intermediate_group, fn(plist_id: hid_t, crt_intmd: c_uint) -> herr_t);
-hdf5_function!(H5Pset_char_encoding, fn(plist_id: hid_t, encoding: H5T_cset_t) -> herr_t);
-hdf5_function!(H5Pset_chunk, fn(plist_id: hid_t, ndims: c_int, dim: *const hsize_t) -> herr_t);
-hdf5_function!(H5Pget_chunk, fn(plist_id: hid_t, max_ndims: c_int, dim: *mut hsize_t) -> c_int);
-hdf5_function!(H5Pset_layout, fn(plist_id: hid_t, layout: H5D_layout_t) -> herr_t);
-hdf5_function!(H5Pget_layout, fn(plist_id: hid_t) -> H5D_layout_t);
-hdf5_function!(H5Pset_deflate, fn(plist_id: hid_t, level: c_uint) -> herr_t);
-hdf5_function!(H5Pset_shuffle, fn(plist_id: hid_t) -> herr_t);
-hdf5_function!(H5Pset_fletcher32, fn(plist_id: hid_t) -> herr_t);
-hdf5_function!(H5Pset_nbit, fn(plist_id: hid_t) -> herr_t);
-hdf5_function!(
-    H5Pset_scaleoffset,
-    fn(plist_id: hid_t, scale_type: c_int, scale_factor: c_int) -> herr_t
-);
-hdf5_function!(
-    H5Pset_szip,
-    fn(plist_id: hid_t, options_mask: c_uint, pixels_per_block: c_uint) -> herr_t
-);
-hdf5_function!(
-    H5Pset_filter,
-    fn(
-        plist_id: hid_t,
-        filter: H5Z_filter_t,
-        flags: c_uint,
-        cd_nelmts: size_t,
-        cd_values: *const c_uint,
-    ) -> herr_t
-);
-hdf5_function!(H5Pget_nfilters, fn(plist_id: hid_t) -> c_int);
-hdf5_function!(
-    H5Pget_filter2,
-    fn(
-        plist_id: hid_t,
-        filter: c_uint,
-        flags: *mut c_uint,
-        cd_nelmts: *mut size_t,
-        cd_values: *mut c_uint,
-        namelen: size_t,
-        name: *mut c_char,
-        filter_config: *mut c_uint,
-    ) -> H5Z_filter_t
-);
-hdf5_function!(
-    H5Pget_filter_by_id2,
-    fn(
-        plist_id: hid_t,
-        filter_id: H5Z_filter_t,
-        flags: *mut c_uint,
-        cd_nelmts: *mut size_t,
-        cd_values: *mut c_uint,
-        namelen: size_t,
-        name: *mut c_char,
-        filter_config: *mut c_uint,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Pmodify_filter,
-    fn(
-        plist_id: hid_t,
-        filter: H5Z_filter_t,
-        flags: c_uint,
-        cd_nelmts: size_t,
-        cd_values: *const c_uint,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Pset_fill_value,
-    fn(plist_id: hid_t, type_id: hid_t, value: *const c_void) -> herr_t
-);
-hdf5_function!(
-    H5Pget_fill_value,
-    fn(plist_id: hid_t, type_id: hid_t, value: *mut c_void) -> herr_t
-);
-hdf5_function!(H5Pfill_value_defined, fn(plist_id: hid_t, status: *mut H5D_fill_value_t) -> herr_t);
-hdf5_function!(H5Pset_alloc_time, fn(plist_id: hid_t, alloc_time: H5D_alloc_time_t) -> herr_t);
-hdf5_function!(H5Pget_alloc_time, fn(plist_id: hid_t, alloc_time: *mut H5D_alloc_time_t) -> herr_t);
-hdf5_function!(H5Pset_fill_time, fn(plist_id: hid_t, fill_time: H5D_fill_time_t) -> herr_t);
-hdf5_function!(H5Pget_fill_time, fn(plist_id: hid_t, fill_time: *mut H5D_fill_time_t) -> herr_t);
-hdf5_function!(
-    H5Pset_chunk_cache,
-    fn(dapl_id: hid_t, rdcc_nslots: size_t, rdcc_nbytes: size_t, rdcc_w0: c_double) -> herr_t
-);
-hdf5_function!(
-    H5Pget_chunk_cache,
-    fn(
-        dapl_id: hid_t,
-        rdcc_nslots: *mut size_t,
-        rdcc_nbytes: *mut size_t,
-        rdcc_w0: *mut c_double,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Pset_libver_bounds,
-    fn(fapl_id: hid_t, low: H5F_libver_t, high: H5F_libver_t) -> herr_t
-);
-hdf5_function!(
-    H5Pget_libver_bounds,
-    fn(fapl_id: hid_t, low: *mut H5F_libver_t, high: *mut H5F_libver_t) -> herr_t
-);
-hdf5_function!(H5Pset_fclose_degree, fn(fapl_id: hid_t, degree: H5F_close_degree_t) -> herr_t);
-hdf5_function!(H5Pget_fclose_degree, fn(fapl_id: hid_t, degree: *mut H5F_close_degree_t) -> herr_t);
-hdf5_function!(H5Pset_userblock, fn(plist_id: hid_t, size: hsize_t) -> herr_t);
-hdf5_function!(H5Pget_userblock, fn(plist_id: hid_t, size: *mut hsize_t) -> herr_t);
-hdf5_function!(H5Pset_copy_object, fn(plist_id: hid_t, copy_options: c_uint) -> herr_t);
-hdf5_function!(H5Pget_copy_object, fn(plist_id: hid_t, copy_options: *mut c_uint) -> herr_t);
-hdf5_function!(H5Pset_link_creation_order, fn(plist_id: hid_t, crt_order_flags: c_uint) -> herr_t);
-hdf5_function!(
-    H5Pget_link_creation_order,
-    fn(plist_id: hid_t, crt_order_flags: *mut c_uint) -> herr_t
-);
-hdf5_function!(H5Pset_attr_creation_order, fn(plist_id: hid_t, crt_order_flags: c_uint) -> herr_t);
-hdf5_function!(
-    H5Pget_attr_creation_order,
-    fn(plist_id: hid_t, crt_order_flags: *mut c_uint) -> herr_t
-);
-hdf5_function!(H5Pset_efile_prefix, fn(dapl_id: hid_t, prefix: *const c_char) -> herr_t);
-hdf5_function!(
-    H5Pget_efile_prefix,
-    fn(dapl_id: hid_t, prefix: *mut c_char, size: size_t) -> ssize_t
-);
-hdf5_function!(H5Pset_elink_file_cache_size, fn(plist_id: hid_t, efc_size: c_uint) -> herr_t);
-hdf5_function!(H5Pget_elink_file_cache_size, fn(plist_id: hid_t, efc_size: *mut c_uint) -> herr_t);
-hdf5_function!(
-    H5Pset_core_write_tracking,
-    fn(fapl_id: hid_t, is_enabled: hbool_t, page_size: size_t) -> herr_t
-);
-hdf5_function!(
-    H5Pget_core_write_tracking,
-    fn(fapl_id: hid_t, is_enabled: *mut hbool_t, page_size: *mut size_t) -> herr_t
-);
-
-// Additional property list functions
-hdf5_function!(H5Pget_driver, fn(plist_id: hid_t) -> hid_t);
-hdf5_function!(
-    H5Pset_fapl_core,
-    fn(fapl_id: hid_t, increment: size_t, backing_store: hbool_t) -> herr_t
-);
-hdf5_function!(
-    H5Pset_fapl_family,
-    fn(fapl_id: hid_t, memb_size: hsize_t, memb_fapl_id: hid_t) -> herr_t
-);
-hdf5_function!(
-    H5Pset_fapl_log,
-    fn(fapl_id: hid_t, logfile: *const c_char, flags: c_uint, buf_size: size_t) -> herr_t
-);
-hdf5_function!(
-    H5Pset_fapl_multi,
-    fn(
-        fapl_id: hid_t,
-        memb_map: *const H5F_mem_t,
-        memb_fapl: *const hid_t,
-        memb_name: *const *const c_char,
-        memb_addr: *const haddr_t,
-        relax: hbool_t,
-    ) -> herr_t
-);
-hdf5_function!(H5Pset_fapl_sec2, fn(fapl_id: hid_t) -> herr_t);
-hdf5_function!(H5Pset_fapl_stdio, fn(fapl_id: hid_t) -> herr_t);
-hdf5_function!(H5Pget_class_name, fn(pclass_id: hid_t) -> *mut c_char);
-hdf5_function!(H5Pget_nprops, fn(plist_id: hid_t, nprops: *mut size_t) -> herr_t);
-hdf5_function!(H5Pisa_class, fn(plist_id: hid_t, pclass_id: hid_t) -> htri_t);
-hdf5_function!(
-    H5Piterate,
-    fn(
-        plist_id: hid_t,
-        idx: *mut c_int,
-        iter_func: Option<unsafe extern "C" fn(hid_t, *const c_char, *mut c_void) -> herr_t>,
-        iter_data: *mut c_void,
-    ) -> c_int
-);
-hdf5_function!(
-    H5Pset_vlen_mem_manager,
-    fn(
-        plist_id: hid_t,
-        alloc_func: Option<unsafe extern "C" fn(size_t, *mut c_void) -> *mut c_void>,
-        alloc_info: *mut c_void,
-        free_func: Option<unsafe extern "C" fn(*mut c_void, *mut c_void)>,
-        free_info: *mut c_void,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Pget_fapl_core,
-    fn(fapl_id: hid_t, increment: *mut size_t, backing_store: *mut hbool_t) -> herr_t
-);
-hdf5_function!(
-    H5Pget_fapl_family,
-    fn(fapl_id: hid_t, memb_size: *mut hsize_t, memb_fapl_id: *mut hid_t) -> herr_t
-);
-hdf5_function!(
-    H5Pget_fapl_multi,
-    fn(
-        fapl_id: hid_t,
-        memb_map: *mut H5F_mem_t,
-        memb_fapl: *mut hid_t,
-        memb_name: *mut *mut c_char,
-        memb_addr: *mut haddr_t,
-        relax: *mut hbool_t,
-    ) -> herr_t
-);
-
-// Additional H5P functions
-hdf5_function!(H5Pall_filters_avail, fn(plist_id: hid_t) -> htri_t);
-hdf5_function!(
-    H5Pget_alignment,
-    fn(fapl_id: hid_t, threshold: *mut hsize_t, alignment: *mut hsize_t) -> herr_t
-);
-hdf5_function!(
-    H5Pset_alignment,
-    fn(fapl_id: hid_t, threshold: hsize_t, alignment: hsize_t) -> herr_t
-);
-hdf5_function!(
-    H5Pget_attr_phase_change,
-    fn(plist_id: hid_t, max_compact: *mut c_uint, min_dense: *mut c_uint) -> herr_t
-);
-hdf5_function!(
-    H5Pset_attr_phase_change,
-    fn(plist_id: hid_t, max_compact: c_uint, min_dense: c_uint) -> herr_t
-);
-hdf5_function!(
-    H5Pget_cache,
-    fn(
-        fapl_id: hid_t,
-        mdc_nelmts: *mut c_int,
-        rdcc_nslots: *mut size_t,
-        rdcc_nbytes: *mut size_t,
-        rdcc_w0: *mut c_double,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Pset_cache,
-    fn(
-        fapl_id: hid_t,
-        mdc_nelmts: c_int,
-        rdcc_nslots: size_t,
-        rdcc_nbytes: size_t,
-        rdcc_w0: c_double,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Pget_external,
-    fn(
-        plist_id: hid_t,
-        idx: c_uint,
-        name_size: size_t,
-        name: *mut c_char,
-        offset: *mut i64,
-        size: *mut hsize_t,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Pset_external,
-    fn(plist_id: hid_t, name: *const c_char, offset: i64, size: hsize_t) -> herr_t
-);
-hdf5_function!(H5Pget_external_count, fn(plist_id: hid_t) -> c_int);
-hdf5_function!(H5Pget_gc_references, fn(fapl_id: hid_t, gc_ref: *mut c_uint) -> herr_t);
-hdf5_function!(H5Pset_gc_references, fn(fapl_id: hid_t, gc_ref: c_uint) -> herr_t);
-hdf5_function!(
-    H5Pget_mdc_config,
-    fn(fapl_id: hid_t, config_ptr: *mut H5AC_cache_config_t) -> herr_t
-);
-hdf5_function!(
-    H5Pset_mdc_config,
-    fn(fapl_id: hid_t, config_ptr: *const H5AC_cache_config_t) -> herr_t
-);
-hdf5_function!(H5Pget_meta_block_size, fn(fapl_id: hid_t, size: *mut hsize_t) -> herr_t);
-hdf5_function!(H5Pset_meta_block_size, fn(fapl_id: hid_t, size: hsize_t) -> herr_t);
-hdf5_function!(H5Pget_obj_track_times, fn(plist_id: hid_t, track_times: *mut hbool_t) -> herr_t);
-hdf5_function!(H5Pset_obj_track_times, fn(plist_id: hid_t, track_times: hbool_t) -> herr_t);
-hdf5_function!(H5Pget_sieve_buf_size, fn(fapl_id: hid_t, size: *mut size_t) -> herr_t);
-hdf5_function!(H5Pset_sieve_buf_size, fn(fapl_id: hid_t, size: size_t) -> herr_t);
-hdf5_function!(H5Pget_small_data_block_size, fn(fapl_id: hid_t, size: *mut hsize_t) -> herr_t);
-hdf5_function!(H5Pset_small_data_block_size, fn(fapl_id: hid_t, size: hsize_t) -> herr_t);
-hdf5_function!(
-    H5Pset_fapl_split,
-    fn(
-        fapl_id: hid_t,
-        meta_ext: *const c_char,
-        meta_plist_id: hid_t,
-        raw_ext: *const c_char,
-        raw_plist_id: hid_t,
-    ) -> herr_t
-);
-hdf5_function!(H5Pget_char_encoding, fn(plist_id: hid_t, encoding: *mut H5T_cset_t) -> herr_t);
-
-// Additional H5P functions for file creation
-hdf5_function!(H5Pget_istore_k, fn(plist_id: hid_t, ik: *mut c_uint) -> herr_t);
-hdf5_function!(H5Pset_istore_k, fn(plist_id: hid_t, ik: c_uint) -> herr_t);
-hdf5_function!(H5Pget_sym_k, fn(plist_id: hid_t, ik: *mut c_uint, lk: *mut c_uint) -> herr_t);
-hdf5_function!(H5Pset_sym_k, fn(plist_id: hid_t, ik: c_uint, lk: c_uint) -> herr_t);
-hdf5_function!(
-    H5Pget_sizes,
-    fn(plist_id: hid_t, sizeof_addr: *mut size_t, sizeof_size: *mut size_t) -> herr_t
-);
-hdf5_function!(H5Pget_shared_mesg_nindexes, fn(plist_id: hid_t, nindexes: *mut c_uint) -> herr_t);
-hdf5_function!(H5Pset_shared_mesg_nindexes, fn(plist_id: hid_t, nindexes: c_uint) -> herr_t);
-hdf5_function!(
-    H5Pget_shared_mesg_index,
-    fn(
-        plist_id: hid_t,
-        index_num: c_uint,
-        mesg_type_flags: *mut c_uint,
-        min_mesg_size: *mut c_uint,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Pset_shared_mesg_index,
-    fn(
-        plist_id: hid_t,
-        index_num: c_uint,
-        mesg_type_flags: c_uint,
-        min_mesg_size: c_uint,
-    ) -> herr_t
-);
-hdf5_function!(
-    H5Pget_shared_mesg_phase_change,
-    fn(plist_id: hid_t, max_list: *mut c_uint, min_btree: *mut c_uint) -> herr_t
-);
-hdf5_function!(
-    H5Pset_shared_mesg_phase_change,
-    fn(plist_id: hid_t, max_list: c_uint, min_btree: c_uint) -> herr_t
-);
-hdf5_function!(
-    H5Pget_create_intermediate_group,
-    fn(plist_id: hid_t, crt_intmd: *mut c_uint) -> herr_t
-);
-
-// H5R (Reference)
-hdf5_function!(
-    H5Rcreate_object,
-    fn(loc_id: hid_t, name: *const c_char, oapl_id: hid_t, ref_ptr: *mut H5R_ref_t) -> herr_t
-);
-hdf5_function!(
-    H5Ropen_object,
-    fn(ref_ptr: *mut H5R_ref_t, rapl_id: hid_t, oapl_id: hid_t) -> hid_t
-);
-hdf5_function!(H5Rdestroy, fn(ref_ptr: *mut H5R_ref_t) -> herr_t);
-hdf5_function!(
-    H5Rget_obj_type3,
-    fn(ref_ptr: *mut H5R_ref_t, rapl_id: hid_t, obj_type: *mut H5O_type_t) -> herr_t
-);
-
-// Legacy H5R functions (v1.8-1.10)
-hdf5_function!(
-    H5Rcreate,
-    fn(
-        ref_ptr: *mut c_void,
-        loc_id: hid_t,
-        name: *const c_char,
-        ref_type: H5R_type_t,
-        space_id: hid_t,
-    ) -> herr_t
-);
-// H5Rdereference2 - HDF5 1.10.0+ signature (4 parameters)
-hdf5_function!(
-    H5Rdereference2,
-    fn(obj_id: hid_t, oapl_id: hid_t, ref_type: H5R_type_t, ref_ptr: *const c_void) -> hid_t
-);
+hdf5_functions! {
+    static H5P_FUNCTIONS;
+    // H5P (Property List)
+    hdf5_function!(H5Pcreate, fn(cls_id: hid_t) -> hid_t);
+    hdf5_function!(H5Pcopy, fn(plist_id: hid_t) -> hid_t);
+    hdf5_function!(H5Pclose, fn(plist_id: hid_t) -> herr_t);
+    hdf5_function!(H5Pget_class, fn(plist_id: hid_t) -> hid_t);
+    hdf5_function!(H5Pequal, fn(id1: hid_t, id2: hid_t) -> htri_t);
+    hdf5_function!(H5Pexist, fn(plist_id: hid_t, name: *const c_char) -> htri_t);
+    hdf5_function!(H5Pset_create_intermediate_group, fn(plist_id: hid_t, crt_intmd: c_uint) -> herr_t);
+    hdf5_function!(H5Pset_char_encoding, fn(plist_id: hid_t, encoding: H5T_cset_t) -> herr_t);
+    hdf5_function!(H5Pset_chunk, fn(plist_id: hid_t, ndims: c_int, dim: *const hsize_t) -> herr_t);
+    hdf5_function!(H5Pget_chunk, fn(plist_id: hid_t, max_ndims: c_int, dim: *mut hsize_t) -> c_int);
+    hdf5_function!(H5Pset_layout, fn(plist_id: hid_t, layout: H5D_layout_t) -> herr_t);
+    hdf5_function!(H5Pget_layout, fn(plist_id: hid_t) -> H5D_layout_t);
+    hdf5_function!(H5Pset_deflate, fn(plist_id: hid_t, level: c_uint) -> herr_t);
+    hdf5_function!(H5Pset_shuffle, fn(plist_id: hid_t) -> herr_t);
+    hdf5_function!(H5Pset_fletcher32, fn(plist_id: hid_t) -> herr_t);
+    hdf5_function!(H5Pset_nbit, fn(plist_id: hid_t) -> herr_t);
+    hdf5_function!(
+        H5Pset_scaleoffset,
+        fn(plist_id: hid_t, scale_type: c_int, scale_factor: c_int) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_szip,
+        fn(plist_id: hid_t, options_mask: c_uint, pixels_per_block: c_uint) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_filter,
+        fn(
+            plist_id: hid_t,
+            filter: H5Z_filter_t,
+            flags: c_uint,
+            cd_nelmts: size_t,
+            cd_values: *const c_uint,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Pget_nfilters, fn(plist_id: hid_t) -> c_int);
+    hdf5_function!(
+        H5Pget_filter2,
+        fn(
+            plist_id: hid_t,
+            filter: c_uint,
+            flags: *mut c_uint,
+            cd_nelmts: *mut size_t,
+            cd_values: *mut c_uint,
+            namelen: size_t,
+            name: *mut c_char,
+            filter_config: *mut c_uint,
+        ) -> H5Z_filter_t
+    );
+    hdf5_function!(
+        H5Pget_filter_by_id2,
+        fn(
+            plist_id: hid_t,
+            filter_id: H5Z_filter_t,
+            flags: *mut c_uint,
+            cd_nelmts: *mut size_t,
+            cd_values: *mut c_uint,
+            namelen: size_t,
+            name: *mut c_char,
+            filter_config: *mut c_uint,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Pmodify_filter,
+        fn(
+            plist_id: hid_t,
+            filter: H5Z_filter_t,
+            flags: c_uint,
+            cd_nelmts: size_t,
+            cd_values: *const c_uint,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_fill_value,
+        fn(plist_id: hid_t, type_id: hid_t, value: *const c_void) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_fill_value,
+        fn(plist_id: hid_t, type_id: hid_t, value: *mut c_void) -> herr_t
+    );
+    hdf5_function!(H5Pfill_value_defined, fn(plist_id: hid_t, status: *mut H5D_fill_value_t) -> herr_t);
+    hdf5_function!(H5Pset_alloc_time, fn(plist_id: hid_t, alloc_time: H5D_alloc_time_t) -> herr_t);
+    hdf5_function!(H5Pget_alloc_time, fn(plist_id: hid_t, alloc_time: *mut H5D_alloc_time_t) -> herr_t);
+    hdf5_function!(H5Pset_fill_time, fn(plist_id: hid_t, fill_time: H5D_fill_time_t) -> herr_t);
+    hdf5_function!(H5Pget_fill_time, fn(plist_id: hid_t, fill_time: *mut H5D_fill_time_t) -> herr_t);
+    hdf5_function!(
+        H5Pset_chunk_cache,
+        fn(dapl_id: hid_t, rdcc_nslots: size_t, rdcc_nbytes: size_t, rdcc_w0: c_double) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_chunk_cache,
+        fn(
+            dapl_id: hid_t,
+            rdcc_nslots: *mut size_t,
+            rdcc_nbytes: *mut size_t,
+            rdcc_w0: *mut c_double,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_libver_bounds,
+        fn(fapl_id: hid_t, low: H5F_libver_t, high: H5F_libver_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_libver_bounds,
+        fn(fapl_id: hid_t, low: *mut H5F_libver_t, high: *mut H5F_libver_t) -> herr_t
+    );
+    hdf5_function!(H5Pset_fclose_degree, fn(fapl_id: hid_t, degree: H5F_close_degree_t) -> herr_t);
+    hdf5_function!(H5Pget_fclose_degree, fn(fapl_id: hid_t, degree: *mut H5F_close_degree_t) -> herr_t);
+    hdf5_function!(H5Pset_userblock, fn(plist_id: hid_t, size: hsize_t) -> herr_t);
+    hdf5_function!(H5Pget_userblock, fn(plist_id: hid_t, size: *mut hsize_t) -> herr_t);
+    hdf5_function!(H5Pset_copy_object, fn(plist_id: hid_t, copy_options: c_uint) -> herr_t);
+    hdf5_function!(H5Pget_copy_object, fn(plist_id: hid_t, copy_options: *mut c_uint) -> herr_t);
+    hdf5_function!(H5Pset_link_creation_order, fn(plist_id: hid_t, crt_order_flags: c_uint) -> herr_t);
+    hdf5_function!(
+        H5Pget_link_creation_order,
+        fn(plist_id: hid_t, crt_order_flags: *mut c_uint) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_est_link_info,
+        fn(plist_id: hid_t, est_num_entries: c_uint, est_name_len: c_uint) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_est_link_info,
+        fn(plist_id: hid_t, est_num_entries: *mut c_uint, est_name_len: *mut c_uint) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_link_phase_change,
+        fn(plist_id: hid_t, max_compact: c_uint, min_dense: c_uint) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_link_phase_change,
+        fn(plist_id: hid_t, max_compact: *mut c_uint, min_dense: *mut c_uint) -> herr_t
+    );
+    hdf5_function!(H5Pset_attr_creation_order, fn(plist_id: hid_t, crt_order_flags: c_uint) -> herr_t);
+    hdf5_function!(
+        H5Pget_attr_creation_order,
+        fn(plist_id: hid_t, crt_order_flags: *mut c_uint) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_buffer,
+        fn(plist_id: hid_t, size: size_t, tconv: *mut c_void, bkg: *mut c_void) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_buffer,
+        fn(plist_id: hid_t, tconv: *mut *mut c_void, bkg: *mut *mut c_void) -> size_t
+    );
+    hdf5_function!(
+        H5Pset_type_conv_cb,
+        fn(dxpl_id: hid_t, op: H5T_conv_except_func_t, operate_data: *mut c_void) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_type_conv_cb,
+        fn(dxpl_id: hid_t, op: *mut H5T_conv_except_func_t, operate_data: *mut *mut c_void) -> herr_t
+    );
+    hdf5_function!(H5Pset_hyper_vector_size, fn(dxpl_id: hid_t, size: size_t) -> herr_t);
+    hdf5_function!(H5Pget_hyper_vector_size, fn(dxpl_id: hid_t, size: *mut size_t) -> herr_t);
+    hdf5_function!(H5Pset_edc_check, fn(plist_id: hid_t, check: H5Z_EDC_t) -> herr_t);
+    hdf5_function!(H5Pget_edc_check, fn(plist_id: hid_t) -> H5Z_EDC_t);
+    hdf5_function!(H5Pset_efile_prefix, fn(dapl_id: hid_t, prefix: *const c_char) -> herr_t);
+    hdf5_function!(
+        H5Pget_efile_prefix,
+        fn(dapl_id: hid_t, prefix: *mut c_char, size: size_t) -> ssize_t
+    );
+    hdf5_function!(H5Pset_elink_file_cache_size, fn(plist_id: hid_t, efc_size: c_uint) -> herr_t);
+    hdf5_function!(H5Pget_elink_file_cache_size, fn(plist_id: hid_t, efc_size: *mut c_uint) -> herr_t);
+    hdf5_function!(H5Pset_elink_prefix, fn(plist_id: hid_t, prefix: *const c_char) -> herr_t);
+    hdf5_function!(
+        H5Pget_elink_prefix,
+        fn(plist_id: hid_t, prefix: *mut c_char, size: size_t) -> ssize_t
+    );
+    hdf5_function!(
+        H5Pset_core_write_tracking,
+        fn(fapl_id: hid_t, is_enabled: hbool_t, page_size: size_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_core_write_tracking,
+        fn(fapl_id: hid_t, is_enabled: *mut hbool_t, page_size: *mut size_t) -> herr_t
+    );
+
+    // Additional property list functions
+    hdf5_function!(H5Pget_driver, fn(plist_id: hid_t) -> hid_t);
+    hdf5_function!(H5Pget_driver_info, fn(plist_id: hid_t) -> *const c_void);
+    hdf5_function!(
+        H5Pset_fapl_core,
+        fn(fapl_id: hid_t, increment: size_t, backing_store: hbool_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_fapl_family,
+        fn(fapl_id: hid_t, memb_size: hsize_t, memb_fapl_id: hid_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_fapl_log,
+        fn(fapl_id: hid_t, logfile: *const c_char, flags: c_uint, buf_size: size_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_fapl_multi,
+        fn(
+            fapl_id: hid_t,
+            memb_map: *const H5F_mem_t,
+            memb_fapl: *const hid_t,
+            memb_name: *const *const c_char,
+            memb_addr: *const haddr_t,
+            relax: hbool_t,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Pset_fapl_sec2, fn(fapl_id: hid_t) -> herr_t);
+    hdf5_function!(H5Pset_fapl_stdio, fn(fapl_id: hid_t) -> herr_t);
+    hdf5_function!(H5Pget_class_name, fn(pclass_id: hid_t) -> *mut c_char);
+    hdf5_function!(H5Pget_nprops, fn(plist_id: hid_t, nprops: *mut size_t) -> herr_t);
+    hdf5_function!(H5Pisa_class, fn(plist_id: hid_t, pclass_id: hid_t) -> htri_t);
+    hdf5_function!(
+        H5Piterate,
+        fn(
+            plist_id: hid_t,
+            idx: *mut c_int,
+            iter_func: Option<unsafe extern "C" fn(hid_t, *const c_char, *mut c_void) -> herr_t>,
+            iter_data: *mut c_void,
+        ) -> c_int
+    );
+    hdf5_function!(
+        H5Pset_vlen_mem_manager,
+        fn(
+            plist_id: hid_t,
+            alloc_func: Option<unsafe extern "C" fn(size_t, *mut c_void) -> *mut c_void>,
+            alloc_info: *mut c_void,
+            free_func: Option<unsafe extern "C" fn(*mut c_void, *mut c_void)>,
+            free_info: *mut c_void,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_fapl_core,
+        fn(fapl_id: hid_t, increment: *mut size_t, backing_store: *mut hbool_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_fapl_family,
+        fn(fapl_id: hid_t, memb_size: *mut hsize_t, memb_fapl_id: *mut hid_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_fapl_multi,
+        fn(
+            fapl_id: hid_t,
+            memb_map: *mut H5F_mem_t,
+            memb_fapl: *mut hid_t,
+            memb_name: *mut *mut c_char,
+            memb_addr: *mut haddr_t,
+            relax: *mut hbool_t,
+        ) -> herr_t
+    );
+
+    // Additional H5P functions
+    hdf5_function!(H5Pall_filters_avail, fn(plist_id: hid_t) -> htri_t);
+    hdf5_function!(
+        H5Pget_alignment,
+        fn(fapl_id: hid_t, threshold: *mut hsize_t, alignment: *mut hsize_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_alignment,
+        fn(fapl_id: hid_t, threshold: hsize_t, alignment: hsize_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_attr_phase_change,
+        fn(plist_id: hid_t, max_compact: *mut c_uint, min_dense: *mut c_uint) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_attr_phase_change,
+        fn(plist_id: hid_t, max_compact: c_uint, min_dense: c_uint) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_cache,
+        fn(
+            fapl_id: hid_t,
+            mdc_nelmts: *mut c_int,
+            rdcc_nslots: *mut size_t,
+            rdcc_nbytes: *mut size_t,
+            rdcc_w0: *mut c_double,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_cache,
+        fn(
+            fapl_id: hid_t,
+            mdc_nelmts: c_int,
+            rdcc_nslots: size_t,
+            rdcc_nbytes: size_t,
+            rdcc_w0: c_double,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_external,
+        fn(
+            plist_id: hid_t,
+            idx: c_uint,
+            name_size: size_t,
+            name: *mut c_char,
+            offset: *mut i64,
+            size: *mut hsize_t,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_external,
+        fn(plist_id: hid_t, name: *const c_char, offset: i64, size: hsize_t) -> herr_t
+    );
+    hdf5_function!(H5Pget_external_count, fn(plist_id: hid_t) -> c_int);
+    hdf5_function!(
+        H5Pset_virtual,
+        fn(
+            dcpl_id: hid_t,
+            vspace_id: hid_t,
+            src_file_name: *const c_char,
+            src_dset_name: *const c_char,
+            src_space_id: hid_t,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Pget_virtual_count, fn(dcpl_id: hid_t, count: *mut size_t) -> herr_t);
+    hdf5_function!(H5Pget_virtual_vspace, fn(dcpl_id: hid_t, index: size_t) -> hid_t);
+    hdf5_function!(H5Pget_virtual_srcspace, fn(dcpl_id: hid_t, index: size_t) -> hid_t);
+    hdf5_function!(
+        H5Pget_virtual_filename,
+        fn(dcpl_id: hid_t, index: size_t, name: *mut c_char, size: size_t) -> ssize_t
+    );
+    hdf5_function!(
+        H5Pget_virtual_dsetname,
+        fn(dcpl_id: hid_t, index: size_t, name: *mut c_char, size: size_t) -> ssize_t
+    );
+    hdf5_function!(H5Pget_gc_references, fn(fapl_id: hid_t, gc_ref: *mut c_uint) -> herr_t);
+    hdf5_function!(H5Pset_gc_references, fn(fapl_id: hid_t, gc_ref: c_uint) -> herr_t);
+    hdf5_function!(
+        H5Pget_mdc_config,
+        fn(fapl_id: hid_t, config_ptr: *mut H5AC_cache_config_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_mdc_config,
+        fn(fapl_id: hid_t, config_ptr: *const H5AC_cache_config_t) -> herr_t
+    );
+    hdf5_function!(H5Pget_meta_block_size, fn(fapl_id: hid_t, size: *mut hsize_t) -> herr_t);
+    hdf5_function!(H5Pset_meta_block_size, fn(fapl_id: hid_t, size: hsize_t) -> herr_t);
+    hdf5_function!(H5Pget_obj_track_times, fn(plist_id: hid_t, track_times: *mut hbool_t) -> herr_t);
+    hdf5_function!(H5Pset_obj_track_times, fn(plist_id: hid_t, track_times: hbool_t) -> herr_t);
+    hdf5_function!(H5Pget_sieve_buf_size, fn(fapl_id: hid_t, size: *mut size_t) -> herr_t);
+    hdf5_function!(H5Pset_sieve_buf_size, fn(fapl_id: hid_t, size: size_t) -> herr_t);
+    hdf5_function!(H5Pget_small_data_block_size, fn(fapl_id: hid_t, size: *mut hsize_t) -> herr_t);
+    hdf5_function!(H5Pset_small_data_block_size, fn(fapl_id: hid_t, size: hsize_t) -> herr_t);
+    hdf5_function!(
+        H5Pset_fapl_split,
+        fn(
+            fapl_id: hid_t,
+            meta_ext: *const c_char,
+            meta_plist_id: hid_t,
+            raw_ext: *const c_char,
+            raw_plist_id: hid_t,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Pget_char_encoding, fn(plist_id: hid_t, encoding: *mut H5T_cset_t) -> herr_t);
+
+    // Additional H5P functions for file creation
+    hdf5_function!(H5Pget_istore_k, fn(plist_id: hid_t, ik: *mut c_uint) -> herr_t);
+    hdf5_function!(H5Pset_istore_k, fn(plist_id: hid_t, ik: c_uint) -> herr_t);
+    hdf5_function!(H5Pget_sym_k, fn(plist_id: hid_t, ik: *mut c_uint, lk: *mut c_uint) -> herr_t);
+    hdf5_function!(H5Pset_sym_k, fn(plist_id: hid_t, ik: c_uint, lk: c_uint) -> herr_t);
+    hdf5_function!(
+        H5Pget_sizes,
+        fn(plist_id: hid_t, sizeof_addr: *mut size_t, sizeof_size: *mut size_t) -> herr_t
+    );
+    hdf5_function!(H5Pget_shared_mesg_nindexes, fn(plist_id: hid_t, nindexes: *mut c_uint) -> herr_t);
+    hdf5_function!(H5Pset_shared_mesg_nindexes, fn(plist_id: hid_t, nindexes: c_uint) -> herr_t);
+    hdf5_function!(
+        H5Pget_shared_mesg_index,
+        fn(
+            plist_id: hid_t,
+            index_num: c_uint,
+            mesg_type_flags: *mut c_uint,
+            min_mesg_size: *mut c_uint,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_shared_mesg_index,
+        fn(
+            plist_id: hid_t,
+            index_num: c_uint,
+            mesg_type_flags: c_uint,
+            min_mesg_size: c_uint,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_shared_mesg_phase_change,
+        fn(plist_id: hid_t, max_list: *mut c_uint, min_btree: *mut c_uint) -> herr_t
+    );
+    hdf5_function!(
+        H5Pset_shared_mesg_phase_change,
+        fn(plist_id: hid_t, max_list: c_uint, min_btree: c_uint) -> herr_t
+    );
+    hdf5_function!(
+        H5Pget_create_intermediate_group,
+        fn(plist_id: hid_t, crt_intmd: *mut c_uint) -> herr_t
+    );
+
+    // H5R (Reference)
+    hdf5_function!(
+        H5Rcreate_object,
+        fn(loc_id: hid_t, name: *const c_char, oapl_id: hid_t, ref_ptr: *mut H5R_ref_t) -> herr_t
+    );
+    hdf5_function!(
+        H5Ropen_object,
+        fn(ref_ptr: *mut H5R_ref_t, rapl_id: hid_t, oapl_id: hid_t) -> hid_t
+    );
+    hdf5_function!(
+        H5Rcreate_region,
+        fn(
+            loc_id: hid_t,
+            name: *const c_char,
+            space_id: hid_t,
+            oapl_id: hid_t,
+            ref_ptr: *mut H5R_ref_t,
+        ) -> herr_t
+    );
+    hdf5_function!(
+        H5Ropen_region,
+        fn(ref_ptr: *mut H5R_ref_t, rapl_id: hid_t, oapl_id: hid_t) -> hid_t
+    );
+    hdf5_function!(
+        H5Rcreate_attr,
+        fn(
+            loc_id: hid_t,
+            name: *const c_char,
+            attr_name: *const c_char,
+            oapl_id: hid_t,
+            ref_ptr: *mut H5R_ref_t,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Ropen_attr, fn(ref_ptr: *mut H5R_ref_t, rapl_id: hid_t, aapl_id: hid_t) -> hid_t);
+    hdf5_function!(
+        H5Rget_attr_name,
+        fn(ref_ptr: *const H5R_ref_t, name: *mut c_char, size: size_t) -> ssize_t
+    );
+    hdf5_function!(H5Rget_type, fn(ref_ptr: *const H5R_ref_t) -> H5R_type_t);
+    hdf5_function!(H5Rdestroy, fn(ref_ptr: *mut H5R_ref_t) -> herr_t);
+    hdf5_function!(
+        H5Rget_obj_type3,
+        fn(ref_ptr: *mut H5R_ref_t, rapl_id: hid_t, obj_type: *mut H5O_type_t) -> herr_t
+    );
+
+    // Legacy H5R functions (v1.8-1.10)
+    hdf5_function!(
+        legacy H5Rcreate,
+        fn(
+            ref_ptr: *mut c_void,
+            loc_id: hid_t,
+            name: *const c_char,
+            ref_type: H5R_type_t,
+            space_id: hid_t,
+        ) -> herr_t
+    );
+    // H5Rdereference2 - HDF5 1.10.0+ signature (4 parameters)
+    hdf5_function!(
+        legacy H5Rdereference2,
+        fn(obj_id: hid_t, oapl_id: hid_t, ref_type: H5R_type_t, ref_ptr: *const c_void) -> hid_t
+    );
+    hdf5_function!(
+        legacy H5Rget_obj_type2,
+        fn(
+            id: hid_t,
+            ref_type: H5R_type_t,
+            ref_ptr: *const c_void,
+            obj_type: *mut H5O_type_t,
+        ) -> herr_t
+    );
+}
 
 /// Alias for H5Rdereference2 for backward compatibility
 #[inline]
@@ -1862,46 +2551,45 @@ pub unsafe fn H5Rdereference(
     oapl_id: hid_t,
     ref_type: H5R_type_t,
     ref_ptr: *const c_void,
-) -> hid_t {
+) -> Result<hid_t, String> {
     H5Rdereference2(obj_id, oapl_id, ref_type, ref_ptr)
 }
-hdf5_function!(
-    H5Rget_obj_type2,
-    fn(
-        id: hid_t,
-        ref_type: H5R_type_t,
-        ref_ptr: *const c_void,
-        obj_type: *mut H5O_type_t,
-    ) -> herr_t
-);
 
-// H5E (Error)
-hdf5_function!(
-    H5Eget_msg,
-    fn(msg_id: hid_t, type_: *mut c_int, msg: *mut c_char, size: size_t) -> ssize_t
-);
-// H5Epush2 is a variadic function - not supported via dlopen wrapper
-hdf5_function!(
-    H5Ewalk2,
-    fn(
-        err_stack: hid_t,
-        direction: c_int,
-        func: Option<unsafe extern "C" fn(c_uint, *const H5E_error2_t, *mut c_void) -> herr_t>,
-        client_data: *mut c_void,
-    ) -> herr_t
-);
-hdf5_function!(H5Eclear2, fn(err_stack: hid_t) -> herr_t);
-hdf5_function!(H5Eget_current_stack, fn() -> hid_t);
-hdf5_function!(H5Eprint2, fn(err_stack: hid_t, stream: *mut libc::FILE) -> herr_t);
-hdf5_function!(
-    H5Eset_auto2,
-    fn(err_stack: hid_t, func: H5E_auto2_t, client_data: *mut c_void) -> herr_t
-);
-
-// H5Z (Filter)
-hdf5_function!(H5Zfilter_avail, fn(id: H5Z_filter_t) -> htri_t);
-hdf5_function!(H5Zget_filter_info, fn(filter: H5Z_filter_t, filter_config: *mut c_uint) -> herr_t);
-hdf5_function!(H5Zregister, fn(cls: *const H5Z_class2_t) -> herr_t);
+hdf5_functions! {
+    static H5E_FUNCTIONS;
+    // H5E (Error)
+    hdf5_function!(
+        H5Eget_msg,
+        fn(msg_id: hid_t, type_: *mut c_int, msg: *mut c_char, size: size_t) -> ssize_t
+    );
+    // H5Epush2 is a variadic function - not supported via dlopen wrapper
+    hdf5_function!(
+        H5Ewalk2,
+        fn(
+            err_stack: hid_t,
+            direction: c_int,
+            func: Option<unsafe extern "C" fn(c_uint, *const H5E_error2_t, *mut c_void) -> herr_t>,
+            client_data: *mut c_void,
+        ) -> herr_t
+    );
+    hdf5_function!(H5Eclear2, fn(err_stack: hid_t) -> herr_t);
+    hdf5_function!(H5Eget_current_stack, fn() -> hid_t);
+    hdf5_function!(H5Eprint2, fn(err_stack: hid_t, stream: *mut libc::FILE) -> herr_t);
+    hdf5_function!(
+        H5Eset_auto2,
+        fn(err_stack: hid_t, func: H5E_auto2_t, client_data: *mut c_void) -> herr_t
+    );
+
+    // H5Z (Filter)
+    hdf5_function!(H5Zfilter_avail, fn(id: H5Z_filter_t) -> htri_t);
+    hdf5_function!(H5Zget_filter_info, fn(filter: H5Z_filter_t, filter_config: *mut c_uint) -> herr_t);
+    hdf5_function!(H5Zregister, fn(cls: *const H5Z_class2_t) -> herr_t);
+    hdf5_function!(H5Zunregister, fn(id: H5Z_filter_t) -> herr_t);
+}
+
+/// The tables of all the functions declared with `hdf5_function!`.
+pub(super) static FUNCTION_TABLES: &[&[FunctionDecl]] =
+    &[H5_FUNCTIONS, H5L_FUNCTIONS, H5P_FUNCTIONS, H5E_FUNCTIONS];
 
 // Note: Property list class IDs (H5P_CLS_*) are now defined below using define_native_type! macro.
 // The old manual definitions have been removed to avoid conflicts.
@@ -1912,19 +2600,82 @@ hdf5_function!(H5Zregister, fn(cls: *const H5Z_class2_t) -> herr_t);
 // Predefined datatype IDs (loaded at runtime)
 // =============================================================================
 
+#[cfg(test)]
+thread_local! {
+    static FAILING_SYMBOLS: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Makes loading the given global symbols fail on the current thread, to test error paths.
+#[cfg(test)]
+pub(crate) fn set_failing_symbols(symbols: &[&str]) {
+    FAILING_SYMBOLS.with(|s| *s.borrow_mut() = symbols.iter().map(|&s| s.to_owned()).collect());
+}
+
+/// Loads the value of the `hid_t` global exported by the library as `symbol`, caching it in
+/// `cell` once loaded.
+///
+/// Failures are not cached, so the symbol is resolved again on the next call.
+fn load_global_id(cell: &'static OnceLock<hid_t>, symbol: &str) -> Result<&'static hid_t, String> {
+    #[cfg(test)]
+    if FAILING_SYMBOLS.with(|symbols| symbols.borrow().iter().any(|s| s == symbol)) {
+        return Err(format!("Failed to load {symbol}: symbol marked as missing"));
+    }
+    if let Some(id) = cell.get() {
+        return Ok(id);
+    }
+    let lib = LIBRARY
+        .get()
+        .ok_or_else(|| format!("Failed to load {symbol}: HDF5 library not initialized"))?;
+    let id = unsafe {
+        let id_ptr: Symbol<*const hid_t> =
+            lib.get(symbol.as_bytes()).map_err(|e| format!("Failed to load {symbol}: {e}"))?;
+        **id_ptr
+    };
+    Ok(cell.get_or_init(|| id))
+}
+
+/// Checks that the library exports `symbol`, without calling or dereferencing it.
+pub(super) fn resolve_symbol(symbol: &str) -> Result<(), String> {
+    #[cfg(test)]
+    if FAILING_SYMBOLS.with(|symbols| symbols.borrow().iter().any(|s| s == symbol)) {
+        return Err(format!("Failed to load {symbol}: symbol marked as missing"));
+    }
+    let lib = LIBRARY
+        .get()
+        .ok_or_else(|| format!("Failed to load {symbol}: HDF5 library not initialized"))?;
+    unsafe { lib.get::<*const c_void>(symbol.as_bytes()) }
+        .map(|_| ())
+        .map_err(|e| format!("Failed to load {symbol}: {e}"))
+}
+
+/// Declares a block of globals with `define_native_type!`, and the static table of their
+/// declarations.
+macro_rules! native_types {
+    (static $table:ident; $(define_native_type!($name:ident, $($symbol:literal),+);)*) => {
+        $(define_native_type!($name, $($symbol),+);)*
+
+        pub(super) static $table: &[GlobalDecl] =
+            &[$(define_native_type!(@decl $name, $($symbol),+)),*];
+    };
+}
+
 macro_rules! define_native_type {
+    (@decl $name:ident, $symbol:literal) => {
+        GlobalDecl { name: stringify!($name), symbol: $symbol, symbol_1_10: None }
+    };
+    (@decl $name:ident, $symbol_new:literal, $symbol_old:literal) => {
+        GlobalDecl { name: stringify!($name), symbol: $symbol_new, symbol_1_10: Some($symbol_old) }
+    };
     ($name:ident, $symbol:literal) => {
         paste::paste! {
             static [<_ $name _STORAGE>]: OnceLock<hid_t> = OnceLock::new();
 
+            pub fn [<$name _try_get>]() -> Result<&'static hid_t, String> {
+                load_global_id(&[<_ $name _STORAGE>], $symbol)
+            }
+
             pub fn [<$name _get>]() -> hid_t {
-                *[<_ $name _STORAGE>].get_or_init(|| {
-                    let lib = get_library();
-                    unsafe {
-                        let id_ptr: Symbol<*const hid_t> = lib.get($symbol.as_bytes()).expect(concat!("Failed to load ", $symbol));
-                        **id_ptr
-                    }
-                })
+                [<$name _try_get>]().map_or(H5I_INVALID_HID, |id| *id)
             }
 
             pub fn $name() -> hid_t { [<$name _get>]() }
@@ -1938,22 +2689,14 @@ macro_rules! define_native_type {
         paste::paste! {
             static [<_ $name _STORAGE>]: OnceLock<hid_t> = OnceLock::new();
 
+            pub fn [<$name _try_get>]() -> Result<&'static hid_t, String> {
+                // HDF5 1.12+ uses _ID_g symbols, older versions use _g symbols
+                let symbol = if hdf5_version_at_least(1, 12, 0) { $symbol_new } else { $symbol_old };
+                load_global_id(&[<_ $name _STORAGE>], symbol)
+            }
+
             pub fn [<$name _get>]() -> hid_t {
-                *[<_ $name _STORAGE>].get_or_init(|| {
-                    let lib = get_library();
-                    unsafe {
-                        // Use version to determine which symbol to load
-                        // HDF5 1.12+ uses _ID_g symbols, older versions use _g symbols
-                        let symbol_name = if hdf5_version_at_least(1, 12, 0) {
-                            $symbol_new
-                        } else {
-                            $symbol_old
-                        };
-                        let id_ptr: Symbol<*const hid_t> = lib.get(symbol_name.as_bytes())
-                            .expect(concat!("Failed to load ", $symbol_new, " or ", $symbol_old));
-                        **id_ptr
-                    }
-                })
+                [<$name _try_get>]().map_or(H5I_INVALID_HID, |id| *id)
             }
 
             pub fn $name() -> hid_t { [<$name _get>]() }
@@ -1961,301 +2704,305 @@ macro_rules! define_native_type {
     };
 }
 
-define_native_type!(H5T_NATIVE_INT8, "H5T_NATIVE_INT8_g");
-define_native_type!(H5T_NATIVE_INT16, "H5T_NATIVE_INT16_g");
-define_native_type!(H5T_NATIVE_INT32, "H5T_NATIVE_INT32_g");
-define_native_type!(H5T_NATIVE_INT64, "H5T_NATIVE_INT64_g");
-define_native_type!(H5T_NATIVE_UINT8, "H5T_NATIVE_UINT8_g");
-define_native_type!(H5T_NATIVE_UINT16, "H5T_NATIVE_UINT16_g");
-define_native_type!(H5T_NATIVE_UINT32, "H5T_NATIVE_UINT32_g");
-define_native_type!(H5T_NATIVE_UINT64, "H5T_NATIVE_UINT64_g");
-define_native_type!(H5T_NATIVE_FLOAT, "H5T_NATIVE_FLOAT_g");
-define_native_type!(H5T_NATIVE_DOUBLE, "H5T_NATIVE_DOUBLE_g");
-define_native_type!(H5T_C_S1, "H5T_C_S1_g");
-define_native_type!(H5T_STD_REF_OBJ, "H5T_STD_REF_OBJ_g");
-define_native_type!(H5T_STD_REF, "H5T_STD_REF_g");
-
-// IEEE float types
-define_native_type!(H5T_IEEE_F32BE, "H5T_IEEE_F32BE_g");
-define_native_type!(H5T_IEEE_F32LE, "H5T_IEEE_F32LE_g");
-define_native_type!(H5T_IEEE_F64BE, "H5T_IEEE_F64BE_g");
-define_native_type!(H5T_IEEE_F64LE, "H5T_IEEE_F64LE_g");
-
-// Standard integer types
-define_native_type!(H5T_STD_I8BE, "H5T_STD_I8BE_g");
-define_native_type!(H5T_STD_I8LE, "H5T_STD_I8LE_g");
-define_native_type!(H5T_STD_I16BE, "H5T_STD_I16BE_g");
-define_native_type!(H5T_STD_I16LE, "H5T_STD_I16LE_g");
-define_native_type!(H5T_STD_I32BE, "H5T_STD_I32BE_g");
-define_native_type!(H5T_STD_I32LE, "H5T_STD_I32LE_g");
-define_native_type!(H5T_STD_I64BE, "H5T_STD_I64BE_g");
-define_native_type!(H5T_STD_I64LE, "H5T_STD_I64LE_g");
-define_native_type!(H5T_STD_U8BE, "H5T_STD_U8BE_g");
-define_native_type!(H5T_STD_U8LE, "H5T_STD_U8LE_g");
-define_native_type!(H5T_STD_U16BE, "H5T_STD_U16BE_g");
-define_native_type!(H5T_STD_U16LE, "H5T_STD_U16LE_g");
-define_native_type!(H5T_STD_U32BE, "H5T_STD_U32BE_g");
-define_native_type!(H5T_STD_U32LE, "H5T_STD_U32LE_g");
-define_native_type!(H5T_STD_U64BE, "H5T_STD_U64BE_g");
-define_native_type!(H5T_STD_U64LE, "H5T_STD_U64LE_g");
-
-// Bitfield types
-define_native_type!(H5T_STD_B8BE, "H5T_STD_B8BE_g");
-define_native_type!(H5T_STD_B8LE, "H5T_STD_B8LE_g");
-define_native_type!(H5T_STD_B16BE, "H5T_STD_B16BE_g");
-define_native_type!(H5T_STD_B16LE, "H5T_STD_B16LE_g");
-define_native_type!(H5T_STD_B32BE, "H5T_STD_B32BE_g");
-define_native_type!(H5T_STD_B32LE, "H5T_STD_B32LE_g");
-define_native_type!(H5T_STD_B64BE, "H5T_STD_B64BE_g");
-define_native_type!(H5T_STD_B64LE, "H5T_STD_B64LE_g");
-
-// Reference type
-define_native_type!(H5T_STD_REF_DSETREG, "H5T_STD_REF_DSETREG_g");
-
-// Time types
-define_native_type!(H5T_UNIX_D32BE, "H5T_UNIX_D32BE_g");
-define_native_type!(H5T_UNIX_D32LE, "H5T_UNIX_D32LE_g");
-define_native_type!(H5T_UNIX_D64BE, "H5T_UNIX_D64BE_g");
-define_native_type!(H5T_UNIX_D64LE, "H5T_UNIX_D64LE_g");
-
-// String/VAX types
-define_native_type!(H5T_FORTRAN_S1, "H5T_FORTRAN_S1_g");
-define_native_type!(H5T_VAX_F32, "H5T_VAX_F32_g");
-define_native_type!(H5T_VAX_F64, "H5T_VAX_F64_g");
-
-// Additional native types
-define_native_type!(H5T_NATIVE_SCHAR, "H5T_NATIVE_SCHAR_g");
-define_native_type!(H5T_NATIVE_UCHAR, "H5T_NATIVE_UCHAR_g");
-define_native_type!(H5T_NATIVE_SHORT, "H5T_NATIVE_SHORT_g");
-define_native_type!(H5T_NATIVE_USHORT, "H5T_NATIVE_USHORT_g");
-define_native_type!(H5T_NATIVE_INT, "H5T_NATIVE_INT_g");
-define_native_type!(H5T_NATIVE_UINT, "H5T_NATIVE_UINT_g");
-define_native_type!(H5T_NATIVE_LONG, "H5T_NATIVE_LONG_g");
-define_native_type!(H5T_NATIVE_ULONG, "H5T_NATIVE_ULONG_g");
-define_native_type!(H5T_NATIVE_LLONG, "H5T_NATIVE_LLONG_g");
-define_native_type!(H5T_NATIVE_ULLONG, "H5T_NATIVE_ULLONG_g");
-define_native_type!(H5T_NATIVE_LDOUBLE, "H5T_NATIVE_LDOUBLE_g");
-define_native_type!(H5T_NATIVE_B8, "H5T_NATIVE_B8_g");
-define_native_type!(H5T_NATIVE_B16, "H5T_NATIVE_B16_g");
-define_native_type!(H5T_NATIVE_B32, "H5T_NATIVE_B32_g");
-define_native_type!(H5T_NATIVE_B64, "H5T_NATIVE_B64_g");
-define_native_type!(H5T_NATIVE_OPAQUE, "H5T_NATIVE_OPAQUE_g");
-define_native_type!(H5T_NATIVE_HADDR, "H5T_NATIVE_HADDR_g");
-define_native_type!(H5T_NATIVE_HSIZE, "H5T_NATIVE_HSIZE_g");
-define_native_type!(H5T_NATIVE_HSSIZE, "H5T_NATIVE_HSSIZE_g");
-define_native_type!(H5T_NATIVE_HERR, "H5T_NATIVE_HERR_g");
-define_native_type!(H5T_NATIVE_HBOOL, "H5T_NATIVE_HBOOL_g");
-define_native_type!(H5T_NATIVE_INT_LEAST8, "H5T_NATIVE_INT_LEAST8_g");
-define_native_type!(H5T_NATIVE_UINT_LEAST8, "H5T_NATIVE_UINT_LEAST8_g");
-define_native_type!(H5T_NATIVE_INT_FAST8, "H5T_NATIVE_INT_FAST8_g");
-define_native_type!(H5T_NATIVE_UINT_FAST8, "H5T_NATIVE_UINT_FAST8_g");
-define_native_type!(H5T_NATIVE_INT_LEAST16, "H5T_NATIVE_INT_LEAST16_g");
-define_native_type!(H5T_NATIVE_UINT_LEAST16, "H5T_NATIVE_UINT_LEAST16_g");
-define_native_type!(H5T_NATIVE_INT_FAST16, "H5T_NATIVE_INT_FAST16_g");
-define_native_type!(H5T_NATIVE_UINT_FAST16, "H5T_NATIVE_UINT_FAST16_g");
-define_native_type!(H5T_NATIVE_INT_LEAST32, "H5T_NATIVE_INT_LEAST32_g");
-define_native_type!(H5T_NATIVE_UINT_LEAST32, "H5T_NATIVE_UINT_LEAST32_g");
-define_native_type!(H5T_NATIVE_INT_FAST32, "H5T_NATIVE_INT_FAST32_g");
-define_native_type!(H5T_NATIVE_UINT_FAST32, "H5T_NATIVE_UINT_FAST32_g");
-define_native_type!(H5T_NATIVE_INT_LEAST64, "H5T_NATIVE_INT_LEAST64_g");
-define_native_type!(H5T_NATIVE_UINT_LEAST64, "H5T_NATIVE_UINT_LEAST64_g");
-define_native_type!(H5T_NATIVE_INT_FAST64, "H5T_NATIVE_INT_FAST64_g");
-define_native_type!(H5T_NATIVE_UINT_FAST64, "H5T_NATIVE_UINT_FAST64_g");
-
-// =============================================================================
-// Error class and message IDs (loaded at runtime)
-// =============================================================================
-
-// Major error classes
-define_native_type!(H5E_ERR_CLS, "H5E_ERR_CLS_g");
-define_native_type!(H5E_NONE_MAJOR, "H5E_NONE_MAJOR_g");
-define_native_type!(H5E_ARGS, "H5E_ARGS_g");
-define_native_type!(H5E_RESOURCE, "H5E_RESOURCE_g");
-define_native_type!(H5E_INTERNAL, "H5E_INTERNAL_g");
-define_native_type!(H5E_FILE, "H5E_FILE_g");
-define_native_type!(H5E_IO, "H5E_IO_g");
-define_native_type!(H5E_FUNC, "H5E_FUNC_g");
-define_native_type!(H5E_ATOM, "H5E_ATOM_g");
-define_native_type!(H5E_CACHE, "H5E_CACHE_g");
-define_native_type!(H5E_LINK, "H5E_LINK_g");
-define_native_type!(H5E_BTREE, "H5E_BTREE_g");
-define_native_type!(H5E_SYM, "H5E_SYM_g");
-define_native_type!(H5E_HEAP, "H5E_HEAP_g");
-define_native_type!(H5E_OHDR, "H5E_OHDR_g");
-define_native_type!(H5E_DATATYPE, "H5E_DATATYPE_g");
-define_native_type!(H5E_DATASPACE, "H5E_DATASPACE_g");
-define_native_type!(H5E_DATASET, "H5E_DATASET_g");
-define_native_type!(H5E_STORAGE, "H5E_STORAGE_g");
-define_native_type!(H5E_PLIST, "H5E_PLIST_g");
-define_native_type!(H5E_ATTR, "H5E_ATTR_g");
-define_native_type!(H5E_PLINE, "H5E_PLINE_g");
-define_native_type!(H5E_EFL, "H5E_EFL_g");
-define_native_type!(H5E_REFERENCE, "H5E_REFERENCE_g");
-define_native_type!(H5E_VFL, "H5E_VFL_g");
-define_native_type!(H5E_TST, "H5E_TST_g");
-define_native_type!(H5E_RS, "H5E_RS_g");
-define_native_type!(H5E_PLUGIN, "H5E_PLUGIN_g");
-define_native_type!(H5E_SLIST, "H5E_SLIST_g");
-define_native_type!(H5E_FSPACE, "H5E_FSPACE_g");
-define_native_type!(H5E_SOHM, "H5E_SOHM_g");
-define_native_type!(H5E_ERROR, "H5E_ERROR_g");
-define_native_type!(H5E_PATH, "H5E_PATH_g");
-
-// Minor error codes
-define_native_type!(H5E_NONE_MINOR, "H5E_NONE_MINOR_g");
-define_native_type!(H5E_ALIGNMENT, "H5E_ALIGNMENT_g");
-define_native_type!(H5E_ALREADYEXISTS, "H5E_ALREADYEXISTS_g");
-define_native_type!(H5E_ALREADYINIT, "H5E_ALREADYINIT_g");
-define_native_type!(H5E_BADATOM, "H5E_BADATOM_g");
-define_native_type!(H5E_BADFILE, "H5E_BADFILE_g");
-define_native_type!(H5E_BADGROUP, "H5E_BADGROUP_g");
-define_native_type!(H5E_BADITER, "H5E_BADITER_g");
-define_native_type!(H5E_BADMESG, "H5E_BADMESG_g");
-define_native_type!(H5E_BADRANGE, "H5E_BADRANGE_g");
-define_native_type!(H5E_BADSELECT, "H5E_BADSELECT_g");
-define_native_type!(H5E_BADSIZE, "H5E_BADSIZE_g");
-define_native_type!(H5E_BADTYPE, "H5E_BADTYPE_g");
-define_native_type!(H5E_BADVALUE, "H5E_BADVALUE_g");
-define_native_type!(H5E_CALLBACK, "H5E_CALLBACK_g");
-define_native_type!(H5E_CANAPPLY, "H5E_CANAPPLY_g");
-define_native_type!(H5E_CANTALLOC, "H5E_CANTALLOC_g");
-define_native_type!(H5E_CANTATTACH, "H5E_CANTATTACH_g");
-define_native_type!(H5E_CANTCLIP, "H5E_CANTCLIP_g");
-define_native_type!(H5E_CANTCLOSEFILE, "H5E_CANTCLOSEFILE_g");
-define_native_type!(H5E_CANTCLOSEOBJ, "H5E_CANTCLOSEOBJ_g");
-define_native_type!(H5E_CANTCOMPARE, "H5E_CANTCOMPARE_g");
-define_native_type!(H5E_CANTCOMPUTE, "H5E_CANTCOMPUTE_g");
-define_native_type!(H5E_CANTCONVERT, "H5E_CANTCONVERT_g");
-define_native_type!(H5E_CANTCOPY, "H5E_CANTCOPY_g");
-define_native_type!(H5E_CANTCOUNT, "H5E_CANTCOUNT_g");
-define_native_type!(H5E_CANTCREATE, "H5E_CANTCREATE_g");
-define_native_type!(H5E_CANTDEC, "H5E_CANTDEC_g");
-define_native_type!(H5E_CANTDECODE, "H5E_CANTDECODE_g");
-define_native_type!(H5E_CANTDELETE, "H5E_CANTDELETE_g");
-define_native_type!(H5E_CANTDIRTY, "H5E_CANTDIRTY_g");
-define_native_type!(H5E_CANTENCODE, "H5E_CANTENCODE_g");
-define_native_type!(H5E_CANTEXPUNGE, "H5E_CANTEXPUNGE_g");
-define_native_type!(H5E_CANTEXTEND, "H5E_CANTEXTEND_g");
-define_native_type!(H5E_CANTFILTER, "H5E_CANTFILTER_g");
-define_native_type!(H5E_CANTFLUSH, "H5E_CANTFLUSH_g");
-define_native_type!(H5E_CANTFREE, "H5E_CANTFREE_g");
-define_native_type!(H5E_CANTGC, "H5E_CANTGC_g");
-define_native_type!(H5E_CANTGET, "H5E_CANTGET_g");
-define_native_type!(H5E_CANTGETSIZE, "H5E_CANTGETSIZE_g");
-define_native_type!(H5E_CANTINC, "H5E_CANTINC_g");
-define_native_type!(H5E_CANTINIT, "H5E_CANTINIT_g");
-define_native_type!(H5E_CANTINS, "H5E_CANTINS_g");
-define_native_type!(H5E_CANTINSERT, "H5E_CANTINSERT_g");
-define_native_type!(H5E_CANTLIST, "H5E_CANTLIST_g");
-define_native_type!(H5E_CANTLOAD, "H5E_CANTLOAD_g");
-define_native_type!(H5E_CANTLOCK, "H5E_CANTLOCK_g");
-define_native_type!(H5E_CANTMARKDIRTY, "H5E_CANTMARKDIRTY_g");
-define_native_type!(H5E_CANTMERGE, "H5E_CANTMERGE_g");
-define_native_type!(H5E_CANTMODIFY, "H5E_CANTMODIFY_g");
-define_native_type!(H5E_CANTMOVE, "H5E_CANTMOVE_g");
-define_native_type!(H5E_CANTNEXT, "H5E_CANTNEXT_g");
-define_native_type!(H5E_CANTOPENFILE, "H5E_CANTOPENFILE_g");
-define_native_type!(H5E_CANTOPENOBJ, "H5E_CANTOPENOBJ_g");
-define_native_type!(H5E_CANTOPERATE, "H5E_CANTOPERATE_g");
-define_native_type!(H5E_CANTPACK, "H5E_CANTPACK_g");
-define_native_type!(H5E_CANTPIN, "H5E_CANTPIN_g");
-define_native_type!(H5E_CANTPROTECT, "H5E_CANTPROTECT_g");
-define_native_type!(H5E_CANTRECV, "H5E_CANTRECV_g");
-define_native_type!(H5E_CANTREDISTRIBUTE, "H5E_CANTREDISTRIBUTE_g");
-define_native_type!(H5E_CANTREGISTER, "H5E_CANTREGISTER_g");
-define_native_type!(H5E_CANTRELEASE, "H5E_CANTRELEASE_g");
-define_native_type!(H5E_CANTREMOVE, "H5E_CANTREMOVE_g");
-define_native_type!(H5E_CANTRENAME, "H5E_CANTRENAME_g");
-define_native_type!(H5E_CANTRESET, "H5E_CANTRESET_g");
-define_native_type!(H5E_CANTRESIZE, "H5E_CANTRESIZE_g");
-define_native_type!(H5E_CANTRESTORE, "H5E_CANTRESTORE_g");
-define_native_type!(H5E_CANTREVIVE, "H5E_CANTREVIVE_g");
-define_native_type!(H5E_CANTSELECT, "H5E_CANTSELECT_g");
-define_native_type!(H5E_CANTSERIALIZE, "H5E_CANTSERIALIZE_g");
-define_native_type!(H5E_CANTSET, "H5E_CANTSET_g");
-define_native_type!(H5E_CANTSHRINK, "H5E_CANTSHRINK_g");
-define_native_type!(H5E_CANTSORT, "H5E_CANTSORT_g");
-define_native_type!(H5E_CANTSPLIT, "H5E_CANTSPLIT_g");
-define_native_type!(H5E_CANTSWAP, "H5E_CANTSWAP_g");
-define_native_type!(H5E_CANTUNLOCK, "H5E_CANTUNLOCK_g");
-define_native_type!(H5E_CANTUNPIN, "H5E_CANTUNPIN_g");
-define_native_type!(H5E_CANTUNPROTECT, "H5E_CANTUNPROTECT_g");
-define_native_type!(H5E_CANTUPDATE, "H5E_CANTUPDATE_g");
-define_native_type!(H5E_CLOSEERROR, "H5E_CLOSEERROR_g");
-define_native_type!(H5E_COMPLEN, "H5E_COMPLEN_g");
-define_native_type!(H5E_DUPCLASS, "H5E_DUPCLASS_g");
-define_native_type!(H5E_EXISTS, "H5E_EXISTS_g");
-define_native_type!(H5E_FCNTL, "H5E_FCNTL_g");
-define_native_type!(H5E_FILEEXISTS, "H5E_FILEEXISTS_g");
-define_native_type!(H5E_FILEOPEN, "H5E_FILEOPEN_g");
-define_native_type!(H5E_LINKCOUNT, "H5E_LINKCOUNT_g");
-define_native_type!(H5E_MOUNT, "H5E_MOUNT_g");
-define_native_type!(H5E_MPI, "H5E_MPI_g");
-define_native_type!(H5E_MPIERRSTR, "H5E_MPIERRSTR_g");
-define_native_type!(H5E_NLINKS, "H5E_NLINKS_g");
-define_native_type!(H5E_NOENCODER, "H5E_NOENCODER_g");
-define_native_type!(H5E_NOFILTER, "H5E_NOFILTER_g");
-define_native_type!(H5E_NOIDS, "H5E_NOIDS_g");
-define_native_type!(H5E_NOSPACE, "H5E_NOSPACE_g");
-define_native_type!(H5E_NOTCACHED, "H5E_NOTCACHED_g");
-define_native_type!(H5E_NOTFOUND, "H5E_NOTFOUND_g");
-define_native_type!(H5E_NOTHDF5, "H5E_NOTHDF5_g");
-define_native_type!(H5E_NOTREGISTERED, "H5E_NOTREGISTERED_g");
-define_native_type!(H5E_OBJOPEN, "H5E_OBJOPEN_g");
-define_native_type!(H5E_OPENERROR, "H5E_OPENERROR_g");
-define_native_type!(H5E_OVERFLOW, "H5E_OVERFLOW_g");
-define_native_type!(H5E_PROTECT, "H5E_PROTECT_g");
-define_native_type!(H5E_READERROR, "H5E_READERROR_g");
-define_native_type!(H5E_SEEKERROR, "H5E_SEEKERROR_g");
-define_native_type!(H5E_SETDISALLOWED, "H5E_SETDISALLOWED_g");
-define_native_type!(H5E_SETLOCAL, "H5E_SETLOCAL_g");
-define_native_type!(H5E_SYSERRSTR, "H5E_SYSERRSTR_g");
-define_native_type!(H5E_SYSTEM, "H5E_SYSTEM_g");
-define_native_type!(H5E_TRAVERSE, "H5E_TRAVERSE_g");
-define_native_type!(H5E_TRUNCATED, "H5E_TRUNCATED_g");
-define_native_type!(H5E_UNINITIALIZED, "H5E_UNINITIALIZED_g");
-define_native_type!(H5E_UNSUPPORTED, "H5E_UNSUPPORTED_g");
-define_native_type!(H5E_VERSION, "H5E_VERSION_g");
-define_native_type!(H5E_WRITEERROR, "H5E_WRITEERROR_g");
-
-// =============================================================================
-// Property list class and default IDs (loaded at runtime)
-// =============================================================================
-
-// Property list classes (always use _ID_g suffix - the _g symbols are internal and contain different values)
-define_native_type!(H5P_CLS_ROOT, "H5P_CLS_ROOT_ID_g");
-define_native_type!(H5P_CLS_OBJECT_CREATE, "H5P_CLS_OBJECT_CREATE_ID_g");
-define_native_type!(H5P_CLS_FILE_CREATE, "H5P_CLS_FILE_CREATE_ID_g");
-define_native_type!(H5P_CLS_FILE_ACCESS, "H5P_CLS_FILE_ACCESS_ID_g");
-define_native_type!(H5P_CLS_DATASET_CREATE, "H5P_CLS_DATASET_CREATE_ID_g");
-define_native_type!(H5P_CLS_DATASET_ACCESS, "H5P_CLS_DATASET_ACCESS_ID_g");
-define_native_type!(H5P_CLS_DATASET_XFER, "H5P_CLS_DATASET_XFER_ID_g");
-define_native_type!(H5P_CLS_FILE_MOUNT, "H5P_CLS_FILE_MOUNT_ID_g");
-define_native_type!(H5P_CLS_GROUP_CREATE, "H5P_CLS_GROUP_CREATE_ID_g");
-define_native_type!(H5P_CLS_GROUP_ACCESS, "H5P_CLS_GROUP_ACCESS_ID_g");
-define_native_type!(H5P_CLS_DATATYPE_CREATE, "H5P_CLS_DATATYPE_CREATE_ID_g");
-define_native_type!(H5P_CLS_DATATYPE_ACCESS, "H5P_CLS_DATATYPE_ACCESS_ID_g");
-define_native_type!(H5P_CLS_STRING_CREATE, "H5P_CLS_STRING_CREATE_ID_g");
-define_native_type!(H5P_CLS_ATTRIBUTE_CREATE, "H5P_CLS_ATTRIBUTE_CREATE_ID_g");
-define_native_type!(H5P_CLS_OBJECT_COPY, "H5P_CLS_OBJECT_COPY_ID_g");
-define_native_type!(H5P_CLS_LINK_CREATE, "H5P_CLS_LINK_CREATE_ID_g");
-define_native_type!(H5P_CLS_LINK_ACCESS, "H5P_CLS_LINK_ACCESS_ID_g");
-
-// Default property lists (always use _ID_g suffix - present in all HDF5 versions)
-define_native_type!(H5P_LST_FILE_CREATE, "H5P_LST_FILE_CREATE_ID_g");
-define_native_type!(H5P_LST_FILE_ACCESS, "H5P_LST_FILE_ACCESS_ID_g");
-define_native_type!(H5P_LST_DATASET_CREATE, "H5P_LST_DATASET_CREATE_ID_g");
-define_native_type!(H5P_LST_DATASET_ACCESS, "H5P_LST_DATASET_ACCESS_ID_g");
-define_native_type!(H5P_LST_DATASET_XFER, "H5P_LST_DATASET_XFER_ID_g");
-define_native_type!(H5P_LST_FILE_MOUNT, "H5P_LST_FILE_MOUNT_ID_g");
-define_native_type!(H5P_LST_GROUP_CREATE, "H5P_LST_GROUP_CREATE_ID_g");
-define_native_type!(H5P_LST_GROUP_ACCESS, "H5P_LST_GROUP_ACCESS_ID_g");
-define_native_type!(H5P_LST_DATATYPE_CREATE, "H5P_LST_DATATYPE_CREATE_ID_g");
-define_native_type!(H5P_LST_DATATYPE_ACCESS, "H5P_LST_DATATYPE_ACCESS_ID_g");
-define_native_type!(H5P_LST_ATTRIBUTE_CREATE, "H5P_LST_ATTRIBUTE_CREATE_ID_g");
-define_native_type!(H5P_LST_OBJECT_COPY, "H5P_LST_OBJECT_COPY_ID_g");
-define_native_type!(H5P_LST_LINK_CREATE, "H5P_LST_LINK_CREATE_ID_g");
-define_native_type!(H5P_LST_LINK_ACCESS, "H5P_LST_LINK_ACCESS_ID_g");
+native_types! {
+    static GLOBALS;
+    define_native_type!(H5T_NATIVE_INT8, "H5T_NATIVE_INT8_g");
+    define_native_type!(H5T_NATIVE_INT16, "H5T_NATIVE_INT16_g");
+    define_native_type!(H5T_NATIVE_INT32, "H5T_NATIVE_INT32_g");
+    define_native_type!(H5T_NATIVE_INT64, "H5T_NATIVE_INT64_g");
+    define_native_type!(H5T_NATIVE_UINT8, "H5T_NATIVE_UINT8_g");
+    define_native_type!(H5T_NATIVE_UINT16, "H5T_NATIVE_UINT16_g");
+    define_native_type!(H5T_NATIVE_UINT32, "H5T_NATIVE_UINT32_g");
+    define_native_type!(H5T_NATIVE_UINT64, "H5T_NATIVE_UINT64_g");
+    define_native_type!(H5T_NATIVE_FLOAT, "H5T_NATIVE_FLOAT_g");
+    define_native_type!(H5T_NATIVE_DOUBLE, "H5T_NATIVE_DOUBLE_g");
+    define_native_type!(H5T_C_S1, "H5T_C_S1_g");
+    define_native_type!(H5T_STD_REF_OBJ, "H5T_STD_REF_OBJ_g");
+    define_native_type!(H5T_STD_REF, "H5T_STD_REF_g");
+
+    // IEEE float types
+    define_native_type!(H5T_IEEE_F32BE, "H5T_IEEE_F32BE_g");
+    define_native_type!(H5T_IEEE_F32LE, "H5T_IEEE_F32LE_g");
+    define_native_type!(H5T_IEEE_F64BE, "H5T_IEEE_F64BE_g");
+    define_native_type!(H5T_IEEE_F64LE, "H5T_IEEE_F64LE_g");
+
+    // Standard integer types
+    define_native_type!(H5T_STD_I8BE, "H5T_STD_I8BE_g");
+    define_native_type!(H5T_STD_I8LE, "H5T_STD_I8LE_g");
+    define_native_type!(H5T_STD_I16BE, "H5T_STD_I16BE_g");
+    define_native_type!(H5T_STD_I16LE, "H5T_STD_I16LE_g");
+    define_native_type!(H5T_STD_I32BE, "H5T_STD_I32BE_g");
+    define_native_type!(H5T_STD_I32LE, "H5T_STD_I32LE_g");
+    define_native_type!(H5T_STD_I64BE, "H5T_STD_I64BE_g");
+    define_native_type!(H5T_STD_I64LE, "H5T_STD_I64LE_g");
+    define_native_type!(H5T_STD_U8BE, "H5T_STD_U8BE_g");
+    define_native_type!(H5T_STD_U8LE, "H5T_STD_U8LE_g");
+    define_native_type!(H5T_STD_U16BE, "H5T_STD_U16BE_g");
+    define_native_type!(H5T_STD_U16LE, "H5T_STD_U16LE_g");
+    define_native_type!(H5T_STD_U32BE, "H5T_STD_U32BE_g");
+    define_native_type!(H5T_STD_U32LE, "H5T_STD_U32LE_g");
+    define_native_type!(H5T_STD_U64BE, "H5T_STD_U64BE_g");
+    define_native_type!(H5T_STD_U64LE, "H5T_STD_U64LE_g");
+
+    // Bitfield types
+    define_native_type!(H5T_STD_B8BE, "H5T_STD_B8BE_g");
+    define_native_type!(H5T_STD_B8LE, "H5T_STD_B8LE_g");
+    define_native_type!(H5T_STD_B16BE, "H5T_STD_B16BE_g");
+    define_native_type!(H5T_STD_B16LE, "H5T_STD_B16LE_g");
+    define_native_type!(H5T_STD_B32BE, "H5T_STD_B32BE_g");
+    define_native_type!(H5T_STD_B32LE, "H5T_STD_B32LE_g");
+    define_native_type!(H5T_STD_B64BE, "H5T_STD_B64BE_g");
+    define_native_type!(H5T_STD_B64LE, "H5T_STD_B64LE_g");
+
+    // Reference type
+    define_native_type!(H5T_STD_REF_DSETREG, "H5T_STD_REF_DSETREG_g");
+
+    // Time types
+    define_native_type!(H5T_UNIX_D32BE, "H5T_UNIX_D32BE_g");
+    define_native_type!(H5T_UNIX_D32LE, "H5T_UNIX_D32LE_g");
+    define_native_type!(H5T_UNIX_D64BE, "H5T_UNIX_D64BE_g");
+    define_native_type!(H5T_UNIX_D64LE, "H5T_UNIX_D64LE_g");
+
+    // String/VAX types
+    define_native_type!(H5T_FORTRAN_S1, "H5T_FORTRAN_S1_g");
+    define_native_type!(H5T_VAX_F32, "H5T_VAX_F32_g");
+    define_native_type!(H5T_VAX_F64, "H5T_VAX_F64_g");
+
+    // Additional native types
+    define_native_type!(H5T_NATIVE_SCHAR, "H5T_NATIVE_SCHAR_g");
+    define_native_type!(H5T_NATIVE_UCHAR, "H5T_NATIVE_UCHAR_g");
+    define_native_type!(H5T_NATIVE_SHORT, "H5T_NATIVE_SHORT_g");
+    define_native_type!(H5T_NATIVE_USHORT, "H5T_NATIVE_USHORT_g");
+    define_native_type!(H5T_NATIVE_INT, "H5T_NATIVE_INT_g");
+    define_native_type!(H5T_NATIVE_UINT, "H5T_NATIVE_UINT_g");
+    define_native_type!(H5T_NATIVE_LONG, "H5T_NATIVE_LONG_g");
+    define_native_type!(H5T_NATIVE_ULONG, "H5T_NATIVE_ULONG_g");
+    define_native_type!(H5T_NATIVE_LLONG, "H5T_NATIVE_LLONG_g");
+    define_native_type!(H5T_NATIVE_ULLONG, "H5T_NATIVE_ULLONG_g");
+    define_native_type!(H5T_NATIVE_LDOUBLE, "H5T_NATIVE_LDOUBLE_g");
+    define_native_type!(H5T_NATIVE_B8, "H5T_NATIVE_B8_g");
+    define_native_type!(H5T_NATIVE_B16, "H5T_NATIVE_B16_g");
+    define_native_type!(H5T_NATIVE_B32, "H5T_NATIVE_B32_g");
+    define_native_type!(H5T_NATIVE_B64, "H5T_NATIVE_B64_g");
+    define_native_type!(H5T_NATIVE_OPAQUE, "H5T_NATIVE_OPAQUE_g");
+    define_native_type!(H5T_NATIVE_HADDR, "H5T_NATIVE_HADDR_g");
+    define_native_type!(H5T_NATIVE_HSIZE, "H5T_NATIVE_HSIZE_g");
+    define_native_type!(H5T_NATIVE_HSSIZE, "H5T_NATIVE_HSSIZE_g");
+    define_native_type!(H5T_NATIVE_HERR, "H5T_NATIVE_HERR_g");
+    define_native_type!(H5T_NATIVE_HBOOL, "H5T_NATIVE_HBOOL_g");
+    define_native_type!(H5T_NATIVE_INT_LEAST8, "H5T_NATIVE_INT_LEAST8_g");
+    define_native_type!(H5T_NATIVE_UINT_LEAST8, "H5T_NATIVE_UINT_LEAST8_g");
+    define_native_type!(H5T_NATIVE_INT_FAST8, "H5T_NATIVE_INT_FAST8_g");
+    define_native_type!(H5T_NATIVE_UINT_FAST8, "H5T_NATIVE_UINT_FAST8_g");
+    define_native_type!(H5T_NATIVE_INT_LEAST16, "H5T_NATIVE_INT_LEAST16_g");
+    define_native_type!(H5T_NATIVE_UINT_LEAST16, "H5T_NATIVE_UINT_LEAST16_g");
+    define_native_type!(H5T_NATIVE_INT_FAST16, "H5T_NATIVE_INT_FAST16_g");
+    define_native_type!(H5T_NATIVE_UINT_FAST16, "H5T_NATIVE_UINT_FAST16_g");
+    define_native_type!(H5T_NATIVE_INT_LEAST32, "H5T_NATIVE_INT_LEAST32_g");
+    define_native_type!(H5T_NATIVE_UINT_LEAST32, "H5T_NATIVE_UINT_LEAST32_g");
+    define_native_type!(H5T_NATIVE_INT_FAST32, "H5T_NATIVE_INT_FAST32_g");
+    define_native_type!(H5T_NATIVE_UINT_FAST32, "H5T_NATIVE_UINT_FAST32_g");
+    define_native_type!(H5T_NATIVE_INT_LEAST64, "H5T_NATIVE_INT_LEAST64_g");
+    define_native_type!(H5T_NATIVE_UINT_LEAST64, "H5T_NATIVE_UINT_LEAST64_g");
+    define_native_type!(H5T_NATIVE_INT_FAST64, "H5T_NATIVE_INT_FAST64_g");
+    define_native_type!(H5T_NATIVE_UINT_FAST64, "H5T_NATIVE_UINT_FAST64_g");
+
+    // =============================================================================
+    // Error class and message IDs (loaded at runtime)
+    // =============================================================================
+
+    // Major error classes
+    define_native_type!(H5E_ERR_CLS, "H5E_ERR_CLS_g");
+    define_native_type!(H5E_NONE_MAJOR, "H5E_NONE_MAJOR_g");
+    define_native_type!(H5E_ARGS, "H5E_ARGS_g");
+    define_native_type!(H5E_RESOURCE, "H5E_RESOURCE_g");
+    define_native_type!(H5E_INTERNAL, "H5E_INTERNAL_g");
+    define_native_type!(H5E_FILE, "H5E_FILE_g");
+    define_native_type!(H5E_IO, "H5E_IO_g");
+    define_native_type!(H5E_FUNC, "H5E_FUNC_g");
+    define_native_type!(H5E_ATOM, "H5E_ATOM_g");
+    define_native_type!(H5E_CACHE, "H5E_CACHE_g");
+    define_native_type!(H5E_LINK, "H5E_LINK_g");
+    define_native_type!(H5E_BTREE, "H5E_BTREE_g");
+    define_native_type!(H5E_SYM, "H5E_SYM_g");
+    define_native_type!(H5E_HEAP, "H5E_HEAP_g");
+    define_native_type!(H5E_OHDR, "H5E_OHDR_g");
+    define_native_type!(H5E_DATATYPE, "H5E_DATATYPE_g");
+    define_native_type!(H5E_DATASPACE, "H5E_DATASPACE_g");
+    define_native_type!(H5E_DATASET, "H5E_DATASET_g");
+    define_native_type!(H5E_STORAGE, "H5E_STORAGE_g");
+    define_native_type!(H5E_PLIST, "H5E_PLIST_g");
+    define_native_type!(H5E_ATTR, "H5E_ATTR_g");
+    define_native_type!(H5E_PLINE, "H5E_PLINE_g");
+    define_native_type!(H5E_EFL, "H5E_EFL_g");
+    define_native_type!(H5E_REFERENCE, "H5E_REFERENCE_g");
+    define_native_type!(H5E_VFL, "H5E_VFL_g");
+    define_native_type!(H5E_TST, "H5E_TST_g");
+    define_native_type!(H5E_RS, "H5E_RS_g");
+    define_native_type!(H5E_PLUGIN, "H5E_PLUGIN_g");
+    define_native_type!(H5E_SLIST, "H5E_SLIST_g");
+    define_native_type!(H5E_FSPACE, "H5E_FSPACE_g");
+    define_native_type!(H5E_SOHM, "H5E_SOHM_g");
+    define_native_type!(H5E_ERROR, "H5E_ERROR_g");
+    define_native_type!(H5E_PATH, "H5E_PATH_g");
+
+    // Minor error codes
+    define_native_type!(H5E_NONE_MINOR, "H5E_NONE_MINOR_g");
+    define_native_type!(H5E_ALIGNMENT, "H5E_ALIGNMENT_g");
+    define_native_type!(H5E_ALREADYEXISTS, "H5E_ALREADYEXISTS_g");
+    define_native_type!(H5E_ALREADYINIT, "H5E_ALREADYINIT_g");
+    define_native_type!(H5E_BADATOM, "H5E_BADATOM_g");
+    define_native_type!(H5E_BADFILE, "H5E_BADFILE_g");
+    define_native_type!(H5E_BADGROUP, "H5E_BADGROUP_g");
+    define_native_type!(H5E_BADITER, "H5E_BADITER_g");
+    define_native_type!(H5E_BADMESG, "H5E_BADMESG_g");
+    define_native_type!(H5E_BADRANGE, "H5E_BADRANGE_g");
+    define_native_type!(H5E_BADSELECT, "H5E_BADSELECT_g");
+    define_native_type!(H5E_BADSIZE, "H5E_BADSIZE_g");
+    define_native_type!(H5E_BADTYPE, "H5E_BADTYPE_g");
+    define_native_type!(H5E_BADVALUE, "H5E_BADVALUE_g");
+    define_native_type!(H5E_CALLBACK, "H5E_CALLBACK_g");
+    define_native_type!(H5E_CANAPPLY, "H5E_CANAPPLY_g");
+    define_native_type!(H5E_CANTALLOC, "H5E_CANTALLOC_g");
+    define_native_type!(H5E_CANTATTACH, "H5E_CANTATTACH_g");
+    define_native_type!(H5E_CANTCLIP, "H5E_CANTCLIP_g");
+    define_native_type!(H5E_CANTCLOSEFILE, "H5E_CANTCLOSEFILE_g");
+    define_native_type!(H5E_CANTCLOSEOBJ, "H5E_CANTCLOSEOBJ_g");
+    define_native_type!(H5E_CANTCOMPARE, "H5E_CANTCOMPARE_g");
+    define_native_type!(H5E_CANTCOMPUTE, "H5E_CANTCOMPUTE_g");
+    define_native_type!(H5E_CANTCONVERT, "H5E_CANTCONVERT_g");
+    define_native_type!(H5E_CANTCOPY, "H5E_CANTCOPY_g");
+    define_native_type!(H5E_CANTCOUNT, "H5E_CANTCOUNT_g");
+    define_native_type!(H5E_CANTCREATE, "H5E_CANTCREATE_g");
+    define_native_type!(H5E_CANTDEC, "H5E_CANTDEC_g");
+    define_native_type!(H5E_CANTDECODE, "H5E_CANTDECODE_g");
+    define_native_type!(H5E_CANTDELETE, "H5E_CANTDELETE_g");
+    define_native_type!(H5E_CANTDIRTY, "H5E_CANTDIRTY_g");
+    define_native_type!(H5E_CANTENCODE, "H5E_CANTENCODE_g");
+    define_native_type!(H5E_CANTEXPUNGE, "H5E_CANTEXPUNGE_g");
+    define_native_type!(H5E_CANTEXTEND, "H5E_CANTEXTEND_g");
+    define_native_type!(H5E_CANTFILTER, "H5E_CANTFILTER_g");
+    define_native_type!(H5E_CANTFLUSH, "H5E_CANTFLUSH_g");
+    define_native_type!(H5E_CANTFREE, "H5E_CANTFREE_g");
+    define_native_type!(H5E_CANTGC, "H5E_CANTGC_g");
+    define_native_type!(H5E_CANTGET, "H5E_CANTGET_g");
+    define_native_type!(H5E_CANTGETSIZE, "H5E_CANTGETSIZE_g");
+    define_native_type!(H5E_CANTINC, "H5E_CANTINC_g");
+    define_native_type!(H5E_CANTINIT, "H5E_CANTINIT_g");
+    define_native_type!(H5E_CANTINS, "H5E_CANTINS_g");
+    define_native_type!(H5E_CANTINSERT, "H5E_CANTINSERT_g");
+    define_native_type!(H5E_CANTLIST, "H5E_CANTLIST_g");
+    define_native_type!(H5E_CANTLOAD, "H5E_CANTLOAD_g");
+    define_native_type!(H5E_CANTLOCK, "H5E_CANTLOCK_g");
+    define_native_type!(H5E_CANTLOCKFILE, "H5E_CANTLOCKFILE_g");
+    define_native_type!(H5E_CANTMARKDIRTY, "H5E_CANTMARKDIRTY_g");
+    define_native_type!(H5E_CANTMERGE, "H5E_CANTMERGE_g");
+    define_native_type!(H5E_CANTMODIFY, "H5E_CANTMODIFY_g");
+    define_native_type!(H5E_CANTMOVE, "H5E_CANTMOVE_g");
+    define_native_type!(H5E_CANTNEXT, "H5E_CANTNEXT_g");
+    define_native_type!(H5E_CANTOPENFILE, "H5E_CANTOPENFILE_g");
+    define_native_type!(H5E_CANTOPENOBJ, "H5E_CANTOPENOBJ_g");
+    define_native_type!(H5E_CANTOPERATE, "H5E_CANTOPERATE_g");
+    define_native_type!(H5E_CANTPACK, "H5E_CANTPACK_g");
+    define_native_type!(H5E_CANTPIN, "H5E_CANTPIN_g");
+    define_native_type!(H5E_CANTPROTECT, "H5E_CANTPROTECT_g");
+    define_native_type!(H5E_CANTRECV, "H5E_CANTRECV_g");
+    define_native_type!(H5E_CANTREDISTRIBUTE, "H5E_CANTREDISTRIBUTE_g");
+    define_native_type!(H5E_CANTREGISTER, "H5E_CANTREGISTER_g");
+    define_native_type!(H5E_CANTRELEASE, "H5E_CANTRELEASE_g");
+    define_native_type!(H5E_CANTREMOVE, "H5E_CANTREMOVE_g");
+    define_native_type!(H5E_CANTRENAME, "H5E_CANTRENAME_g");
+    define_native_type!(H5E_CANTRESET, "H5E_CANTRESET_g");
+    define_native_type!(H5E_CANTRESIZE, "H5E_CANTRESIZE_g");
+    define_native_type!(H5E_CANTRESTORE, "H5E_CANTRESTORE_g");
+    define_native_type!(H5E_CANTREVIVE, "H5E_CANTREVIVE_g");
+    define_native_type!(H5E_CANTSELECT, "H5E_CANTSELECT_g");
+    define_native_type!(H5E_CANTSERIALIZE, "H5E_CANTSERIALIZE_g");
+    define_native_type!(H5E_CANTSET, "H5E_CANTSET_g");
+    define_native_type!(H5E_CANTSHRINK, "H5E_CANTSHRINK_g");
+    define_native_type!(H5E_CANTSORT, "H5E_CANTSORT_g");
+    define_native_type!(H5E_CANTSPLIT, "H5E_CANTSPLIT_g");
+    define_native_type!(H5E_CANTSWAP, "H5E_CANTSWAP_g");
+    define_native_type!(H5E_CANTUNLOCK, "H5E_CANTUNLOCK_g");
+    define_native_type!(H5E_CANTUNPIN, "H5E_CANTUNPIN_g");
+    define_native_type!(H5E_CANTUNPROTECT, "H5E_CANTUNPROTECT_g");
+    define_native_type!(H5E_CANTUPDATE, "H5E_CANTUPDATE_g");
+    define_native_type!(H5E_CLOSEERROR, "H5E_CLOSEERROR_g");
+    define_native_type!(H5E_COMPLEN, "H5E_COMPLEN_g");
+    define_native_type!(H5E_DUPCLASS, "H5E_DUPCLASS_g");
+    define_native_type!(H5E_EXISTS, "H5E_EXISTS_g");
+    define_native_type!(H5E_FCNTL, "H5E_FCNTL_g");
+    define_native_type!(H5E_FILEEXISTS, "H5E_FILEEXISTS_g");
+    define_native_type!(H5E_FILEOPEN, "H5E_FILEOPEN_g");
+    define_native_type!(H5E_LINKCOUNT, "H5E_LINKCOUNT_g");
+    define_native_type!(H5E_MOUNT, "H5E_MOUNT_g");
+    define_native_type!(H5E_MPI, "H5E_MPI_g");
+    define_native_type!(H5E_MPIERRSTR, "H5E_MPIERRSTR_g");
+    define_native_type!(H5E_NLINKS, "H5E_NLINKS_g");
+    define_native_type!(H5E_NOENCODER, "H5E_NOENCODER_g");
+    define_native_type!(H5E_NOFILTER, "H5E_NOFILTER_g");
+    define_native_type!(H5E_NOIDS, "H5E_NOIDS_g");
+    define_native_type!(H5E_NOSPACE, "H5E_NOSPACE_g");
+    define_native_type!(H5E_NOTCACHED, "H5E_NOTCACHED_g");
+    define_native_type!(H5E_NOTFOUND, "H5E_NOTFOUND_g");
+    define_native_type!(H5E_NOTHDF5, "H5E_NOTHDF5_g");
+    define_native_type!(H5E_NOTREGISTERED, "H5E_NOTREGISTERED_g");
+    define_native_type!(H5E_OBJOPEN, "H5E_OBJOPEN_g");
+    define_native_type!(H5E_OPENERROR, "H5E_OPENERROR_g");
+    define_native_type!(H5E_OVERFLOW, "H5E_OVERFLOW_g");
+    define_native_type!(H5E_PROTECT, "H5E_PROTECT_g");
+    define_native_type!(H5E_READERROR, "H5E_READERROR_g");
+    define_native_type!(H5E_SEEKERROR, "H5E_SEEKERROR_g");
+    define_native_type!(H5E_SETDISALLOWED, "H5E_SETDISALLOWED_g");
+    define_native_type!(H5E_SETLOCAL, "H5E_SETLOCAL_g");
+    define_native_type!(H5E_SYSERRSTR, "H5E_SYSERRSTR_g");
+    define_native_type!(H5E_SYSTEM, "H5E_SYSTEM_g");
+    define_native_type!(H5E_TRAVERSE, "H5E_TRAVERSE_g");
+    define_native_type!(H5E_TRUNCATED, "H5E_TRUNCATED_g");
+    define_native_type!(H5E_UNINITIALIZED, "H5E_UNINITIALIZED_g");
+    define_native_type!(H5E_UNSUPPORTED, "H5E_UNSUPPORTED_g");
+    define_native_type!(H5E_VERSION, "H5E_VERSION_g");
+    define_native_type!(H5E_WRITEERROR, "H5E_WRITEERROR_g");
+
+    // =============================================================================
+    // Property list class and default IDs (loaded at runtime)
+    // =============================================================================
+
+    // Property list classes (always use _ID_g suffix - the _g symbols are internal and contain different values)
+    define_native_type!(H5P_CLS_ROOT, "H5P_CLS_ROOT_ID_g");
+    define_native_type!(H5P_CLS_OBJECT_CREATE, "H5P_CLS_OBJECT_CREATE_ID_g");
+    define_native_type!(H5P_CLS_FILE_CREATE, "H5P_CLS_FILE_CREATE_ID_g");
+    define_native_type!(H5P_CLS_FILE_ACCESS, "H5P_CLS_FILE_ACCESS_ID_g");
+    define_native_type!(H5P_CLS_DATASET_CREATE, "H5P_CLS_DATASET_CREATE_ID_g");
+    define_native_type!(H5P_CLS_DATASET_ACCESS, "H5P_CLS_DATASET_ACCESS_ID_g");
+    define_native_type!(H5P_CLS_DATASET_XFER, "H5P_CLS_DATASET_XFER_ID_g");
+    define_native_type!(H5P_CLS_FILE_MOUNT, "H5P_CLS_FILE_MOUNT_ID_g");
+    define_native_type!(H5P_CLS_GROUP_CREATE, "H5P_CLS_GROUP_CREATE_ID_g");
+    define_native_type!(H5P_CLS_GROUP_ACCESS, "H5P_CLS_GROUP_ACCESS_ID_g");
+    define_native_type!(H5P_CLS_DATATYPE_CREATE, "H5P_CLS_DATATYPE_CREATE_ID_g");
+    define_native_type!(H5P_CLS_DATATYPE_ACCESS, "H5P_CLS_DATATYPE_ACCESS_ID_g");
+    define_native_type!(H5P_CLS_STRING_CREATE, "H5P_CLS_STRING_CREATE_ID_g");
+    define_native_type!(H5P_CLS_ATTRIBUTE_CREATE, "H5P_CLS_ATTRIBUTE_CREATE_ID_g");
+    define_native_type!(H5P_CLS_OBJECT_COPY, "H5P_CLS_OBJECT_COPY_ID_g");
+    define_native_type!(H5P_CLS_LINK_CREATE, "H5P_CLS_LINK_CREATE_ID_g");
+    define_native_type!(H5P_CLS_LINK_ACCESS, "H5P_CLS_LINK_ACCESS_ID_g");
+
+    // Default property lists (always use _ID_g suffix - present in all HDF5 versions)
+    define_native_type!(H5P_LST_FILE_CREATE, "H5P_LST_FILE_CREATE_ID_g");
+    define_native_type!(H5P_LST_FILE_ACCESS, "H5P_LST_FILE_ACCESS_ID_g");
+    define_native_type!(H5P_LST_DATASET_CREATE, "H5P_LST_DATASET_CREATE_ID_g");
+    define_native_type!(H5P_LST_DATASET_ACCESS, "H5P_LST_DATASET_ACCESS_ID_g");
+    define_native_type!(H5P_LST_DATASET_XFER, "H5P_LST_DATASET_XFER_ID_g");
+    define_native_type!(H5P_LST_FILE_MOUNT, "H5P_LST_FILE_MOUNT_ID_g");
+    define_native_type!(H5P_LST_GROUP_CREATE, "H5P_LST_GROUP_CREATE_ID_g");
+    define_native_type!(H5P_LST_GROUP_ACCESS, "H5P_LST_GROUP_ACCESS_ID_g");
+    define_native_type!(H5P_LST_DATATYPE_CREATE, "H5P_LST_DATATYPE_CREATE_ID_g");
+    define_native_type!(H5P_LST_DATATYPE_ACCESS, "H5P_LST_DATATYPE_ACCESS_ID_g");
+    define_native_type!(H5P_LST_ATTRIBUTE_CREATE, "H5P_LST_ATTRIBUTE_CREATE_ID_g");
+    define_native_type!(H5P_LST_OBJECT_COPY, "H5P_LST_OBJECT_COPY_ID_g");
+    define_native_type!(H5P_LST_LINK_CREATE, "H5P_LST_LINK_CREATE_ID_g");
+    define_native_type!(H5P_LST_LINK_ACCESS, "H5P_LST_LINK_ACCESS_ID_g");
+}
 
 #[cfg(test)]
 mod tests {
@@ -2296,6 +3043,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_library_path_to_load() {
+        let env = Some("/env/libhdf5.so".to_string());
+        let path = library_path_to_load(Some("/explicit/libhdf5.so"), env.clone());
+        assert_eq!(path, ("/explicit/libhdf5.so".to_string(), None));
+        let path = library_path_to_load(None, env);
+        assert_eq!(path, ("/env/libhdf5.so".to_string(), Some(LIBRARY_PATH_ENV)));
+        let default = library_path_to_load(None, None);
+        assert_eq!(library_path_to_load(None, Some(String::new())), default);
+        assert_eq!(default.1, None);
+    }
+
+    #[test]
+    fn test_api_table_for_version() {
+        let v = |major, minor, micro| Version { major, minor, micro };
+        let legacy = ApiTable::for_version(v(1, 10, 5));
+        assert_eq!(legacy.object_info, ObjectInfoApi::V1);
+        assert_eq!(legacy.references, ReferenceApi::Legacy);
+        assert_eq!(legacy.link_iterate, LinkIterateApi::V1);
+        assert!(!legacy.legacy_removed);
+        assert_eq!(legacy.ensure_legacy("H5Rcreate"), Ok(()));
+
+        let table = ApiTable::for_version(v(1, 14, 3));
+        assert_eq!(table.object_info, ObjectInfoApi::V3);
+        assert_eq!(table.references, ReferenceApi::Standard);
+        assert_eq!(table.link_iterate, LinkIterateApi::V2);
+        assert!(!table.legacy_removed);
+
+        let table = ApiTable::for_version(v(2, 0, 0));
+        assert_eq!(table.object_info, ObjectInfoApi::V3);
+        assert_eq!(table.references, ReferenceApi::Standard);
+        assert_eq!(table.link_iterate, LinkIterateApi::V2);
+        assert!(table.legacy_removed);
+        assert_eq!(
+            table.ensure_legacy("H5Oget_info1"),
+            Err("H5Oget_info1 is not available in HDF5 2.x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_api_table_initialized() {
+        init(None).expect("Failed to initialize HDF5");
+        let version = hdf5_version().expect("Version should be stored after init");
+        assert_eq!(api_table(), ApiTable::for_version(version));
+    }
+
     #[test]
     fn test_h5o_info1_t_type() {
         // H5O_info1_t should be a valid type with reasonable size
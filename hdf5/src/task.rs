@@ -0,0 +1,228 @@
+//! Running reads and writes from async code without blocking the runtime.
+//!
+//! HDF5 calls are blocking and serialized by the global library lock, so async services
+//! should not call them on the runtime's worker threads. The helpers in this module run each
+//! operation with [`tokio::task::spawn_blocking`]: the file is opened inside the blocking
+//! task, the operation is performed, and the file is closed before the task returns.
+//!
+//! Handles are deliberately never shared across await points: a [`File`] or [`Dataset`]
+//! kept alive while a future is suspended would keep the file open for an unbounded time
+//! and could be used concurrently from another task, which the library lock would serialize
+//! anyway. Opening a file per operation is cheap compared to large reads.
+//!
+//! Since the library lock lets only one task make progress at a time, starting many blocking
+//! tasks only ties up threads of the blocking pool. The number of tasks running at once is
+//! therefore bounded by a [`TaskPool`]; the functions of this module use the
+//! [global pool](TaskPool::global), whose size can be set with [`set_max_blocking_tasks`].
+//!
+//! Requires the `tokio` crate feature and must be called from within a tokio runtime.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::{task, Selection};
+//!
+//! let values = task::read_dataset_async::<f64, _>("data.h5", "group/values", Selection::All)
+//!     .await?;
+//! task::write_dataset_async("out.h5", "values", values * 2.0, Selection::All).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use ndarray::{Array, ArrayD};
+use tokio::sync::Semaphore;
+
+use crate::internal_prelude::*;
+
+/// The number of blocking tasks the global pool runs at once, unless configured otherwise.
+pub const DEFAULT_MAX_BLOCKING_TASKS: usize = 4;
+
+static GLOBAL_POOL: OnceLock<TaskPool> = OnceLock::new();
+
+/// Sets the number of blocking tasks the [global pool](TaskPool::global) runs at once.
+///
+/// Fails if `max_tasks` is zero or if the global pool is already in use.
+pub fn set_max_blocking_tasks(max_tasks: usize) -> Result<()> {
+    GLOBAL_POOL
+        .set(TaskPool::new(max_tasks)?)
+        .map_err(|_| "the global task pool is already initialized".into())
+}
+
+/// Runs blocking HDF5 operations on the tokio blocking thread pool, at most `max_tasks`
+/// at a time.
+///
+/// Tasks waiting for a slot do not occupy a thread. Cloning a pool shares its slots.
+#[derive(Clone, Debug)]
+pub struct TaskPool {
+    semaphore: Arc<Semaphore>,
+    max_tasks: usize,
+}
+
+impl TaskPool {
+    /// Creates a pool running at most `max_tasks` tasks at a time.
+    ///
+    /// Fails if `max_tasks` is zero or larger than [`Semaphore::MAX_PERMITS`].
+    pub fn new(max_tasks: usize) -> Result<Self> {
+        ensure!(max_tasks > 0, "the number of blocking tasks must be positive");
+        ensure!(
+            max_tasks <= Semaphore::MAX_PERMITS,
+            "the number of blocking tasks must be at most {}",
+            Semaphore::MAX_PERMITS
+        );
+        Ok(Self::with_permits(max_tasks))
+    }
+
+    fn with_permits(max_tasks: usize) -> Self {
+        Self { semaphore: Arc::new(Semaphore::new(max_tasks)), max_tasks }
+    }
+
+    /// Returns the pool used by the functions of this module.
+    pub fn global() -> &'static Self {
+        GLOBAL_POOL.get_or_init(|| Self::with_permits(DEFAULT_MAX_BLOCKING_TASKS))
+    }
+
+    /// Returns the maximum number of tasks running at once.
+    pub fn max_tasks(&self) -> usize {
+        self.max_tasks
+    }
+
+    /// Runs `func` on the blocking thread pool once a slot is available.
+    ///
+    /// The slot is released when `func` returns, even if the returned future is dropped
+    /// before that. Panics in `func` are propagated to the caller.
+    pub async fn spawn<F, R>(&self, func: F) -> Result<R>
+    where
+        F: FnOnce() -> Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let permit = Arc::clone(&self.semaphore)
+            .acquire_owned()
+            .await
+            .map_err(|_| "the task pool is closed")?;
+        let task = tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            func()
+        });
+        match task.await {
+            Ok(result) => result,
+            Err(err) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+            Err(err) => fail!("blocking task failed: {}", err),
+        }
+    }
+
+    /// Reads a selection of a dataset, see [`read_dataset_async`].
+    pub async fn read_dataset<T, S>(
+        &self,
+        file_path: impl AsRef<Path>,
+        dataset_path: &str,
+        selection: S,
+    ) -> Result<ArrayD<T>>
+    where
+        T: H5Type + Send + 'static,
+        S: TryInto<Selection>,
+        Error: From<S::Error>,
+    {
+        let selection = selection.try_into()?;
+        let target = Target::new(file_path.as_ref(), dataset_path);
+        let context = target.describe("reading");
+        self.spawn(move || target.read(selection)).await.context(context)
+    }
+
+    /// Writes an array into a selection of a dataset, see [`write_dataset_async`].
+    pub async fn write_dataset<T, D, S>(
+        &self,
+        file_path: impl AsRef<Path>,
+        dataset_path: &str,
+        data: Array<T, D>,
+        selection: S,
+    ) -> Result<()>
+    where
+        T: H5Type + Send + 'static,
+        D: ndarray::Dimension + Send + 'static,
+        S: TryInto<Selection>,
+        Error: From<S::Error>,
+    {
+        let selection = selection.try_into()?;
+        let target = Target::new(file_path.as_ref(), dataset_path);
+        let context = target.describe("writing");
+        self.spawn(move || target.write(&data, selection)).await.context(context)
+    }
+}
+
+/// A dataset in a file, moved into a blocking task.
+struct Target {
+    file_path: PathBuf,
+    dataset_path: String,
+}
+
+impl Target {
+    fn new(file_path: &Path, dataset_path: &str) -> Self {
+        Self { file_path: file_path.to_owned(), dataset_path: dataset_path.to_owned() }
+    }
+
+    fn describe(&self, action: &str) -> String {
+        format!("{} dataset '{}' in '{}'", action, self.dataset_path, self.file_path.display())
+    }
+
+    fn read<T: H5Type>(&self, selection: Selection) -> Result<ArrayD<T>> {
+        let file = File::open(&self.file_path)?;
+        let data = file.dataset(&self.dataset_path)?.read_slice(selection)?;
+        file.close()?;
+        Ok(data)
+    }
+
+    fn write<T: H5Type, D: ndarray::Dimension>(
+        &self,
+        data: &Array<T, D>,
+        selection: Selection,
+    ) -> Result<()> {
+        let file = File::open_rw(&self.file_path)?;
+        file.dataset(&self.dataset_path)?.write_slice(data, selection)?;
+        file.close()
+    }
+}
+
+/// Reads a selection of the dataset `dataset_path` in the file `file_path` on the blocking
+/// thread pool.
+///
+/// The file is opened read-only for the duration of the read and closed afterwards. The
+/// result has the dimensionality of the selection, as with [`Reader::read_slice`]; use
+/// [`Selection::All`] to read the whole dataset.
+pub async fn read_dataset_async<T, S>(
+    file_path: impl AsRef<Path>,
+    dataset_path: &str,
+    selection: S,
+) -> Result<ArrayD<T>>
+where
+    T: H5Type + Send + 'static,
+    S: TryInto<Selection>,
+    Error: From<S::Error>,
+{
+    TaskPool::global().read_dataset(file_path, dataset_path, selection).await
+}
+
+/// Writes an array into a selection of the dataset `dataset_path` in the file `file_path` on
+/// the blocking thread pool.
+///
+/// The file is opened for writing for the duration of the write and closed afterwards. The
+/// dataset must already exist, and the shape of `data` must match the selection, as with
+/// [`Writer::write_slice`].
+pub async fn write_dataset_async<T, D, S>(
+    file_path: impl AsRef<Path>,
+    dataset_path: &str,
+    data: Array<T, D>,
+    selection: S,
+) -> Result<()>
+where
+    T: H5Type + Send + 'static,
+    D: ndarray::Dimension + Send + 'static,
+    S: TryInto<Selection>,
+    Error: From<S::Error>,
+{
+    TaskPool::global().write_dataset(file_path, dataset_path, data, selection).await
+}
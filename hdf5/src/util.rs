@@ -1,17 +1,74 @@
 use std::borrow::Borrow;
 use std::convert::TryInto;
 use std::ffi::{CStr, CString};
+use std::path::{Path, PathBuf};
 use std::ptr;
 use std::str;
 
 use crate::internal_prelude::*;
 
 /// Convert a zero-terminated string (`const char *`) into a `String`.
+///
+/// A null pointer, which HDF5 leaves in some optional string fields such as those of error
+/// stack frames, becomes an empty string, and invalid UTF-8 is replaced.
 /// # Safety
-/// The memory pointed to by `string` must be valid for constructing a `CStr`
-/// containing valid UTF-8.
+/// `string` must be null or point to memory valid for constructing a `CStr`.
 pub unsafe fn string_from_cstr(string: *const c_char) -> String {
-    unsafe { String::from_utf8_unchecked(CStr::from_ptr(string).to_bytes().to_vec()) }
+    if string.is_null() {
+        return String::new();
+    }
+    unsafe { CStr::from_ptr(string) }.to_string_lossy().into_owned()
+}
+
+/// Takes ownership of a zero-terminated string allocated by HDF5, such as the result of
+/// `H5Tget_member_name`, copying it into a `String` and freeing it with `H5free_memory`.
+///
+/// HDF5 returns a null pointer on failure, which becomes the error on the HDF5 error stack.
+/// Invalid UTF-8 is replaced.
+/// # Safety
+/// `string` must be null or point to a zero-terminated string allocated by HDF5 that is not
+/// used afterwards.
+pub unsafe fn h5_string(string: *mut c_char) -> Result<String> {
+    if string.is_null() {
+        return Err(Error::query().unwrap_or_else(|_| "null string returned by HDF5".into()));
+    }
+    let value = unsafe { string_from_cstr(string) };
+    unsafe { h5_free_memory(string.cast()) };
+    Ok(value)
+}
+
+/// Maximum length in bytes of a link or attribute name passed to iteration callbacks; longer
+/// names are treated as corrupt metadata rather than copied.
+pub const MAX_NAME_LEN: usize = 64 * 1024;
+
+/// Maximum number of entries reserved up front when collecting a listing, so that a corrupt
+/// member count cannot cause a huge allocation.
+pub const MAX_LISTING_CAPACITY: usize = 64 * 1024;
+
+/// Returns the capacity to reserve for a listing of `count` entries.
+pub fn listing_capacity(count: u64) -> usize {
+    usize::try_from(count).unwrap_or(usize::MAX).min(MAX_LISTING_CAPACITY)
+}
+
+/// Convert a zero-terminated `what` name passed by the library into a `String`, scanning at
+/// most [`MAX_NAME_LEN`] bytes. Invalid UTF-8 is replaced.
+/// # Safety
+/// `name` must be null or point to memory readable up to its terminator or past
+/// `MAX_NAME_LEN` bytes.
+pub unsafe fn name_from_cstr(name: *const c_char, what: &str) -> Result<String> {
+    ensure!(!name.is_null(), "null {} name", what);
+    let mut len = 0;
+    while len <= MAX_NAME_LEN && unsafe { *name.add(len) } != 0 {
+        len += 1;
+    }
+    ensure!(
+        len <= MAX_NAME_LEN,
+        "{} name exceeds {} bytes, the file may be corrupt",
+        what,
+        MAX_NAME_LEN
+    );
+    let bytes = unsafe { std::slice::from_raw_parts(name.cast::<u8>(), len) };
+    Ok(String::from_utf8_lossy(bytes).into_owned())
 }
 
 /// Convert a `String` or a `&str` into a zero-terminated string (`const char *`).
@@ -21,6 +78,37 @@ pub fn to_cstring<S: Borrow<str>>(string: S) -> Result<CString> {
     CString::new(string).map_err(|_| format!("null byte in string: {string:?}").into())
 }
 
+/// Convert a path into the zero-terminated file name passed to the library.
+///
+/// On Unix, the bytes of the path are passed unchanged, so paths that are not valid UTF-8 work
+/// as well. Elsewhere (i.e. on Windows, where the library expects UTF-8 file names), the path
+/// is encoded as UTF-8 and paths that are not valid Unicode are rejected.
+pub fn path_to_cstring<P: AsRef<Path>>(path: P) -> Result<CString> {
+    let path = path.as_ref();
+    #[cfg(unix)]
+    let bytes = std::os::unix::ffi::OsStrExt::as_bytes(path.as_os_str());
+    #[cfg(not(unix))]
+    let bytes = path
+        .to_str()
+        .ok_or_else(|| format!("path is not valid Unicode: {}", path.display()))?
+        .as_bytes();
+    #[allow(clippy::map_err_ignore)]
+    CString::new(bytes).map_err(|_| format!("null byte in path: {}", path.display()).into())
+}
+
+/// Convert a zero-terminated file name returned by the library into a path, the inverse of
+/// [`path_to_cstring`]. Elsewhere than on Unix, invalid UTF-8 is replaced.
+/// # Safety
+/// `name` must point to memory valid for constructing a `CStr`.
+pub unsafe fn path_from_cstr(name: *const c_char) -> PathBuf {
+    let bytes = unsafe { CStr::from_ptr(name) }.to_bytes();
+    #[cfg(unix)]
+    let path = PathBuf::from(<std::ffi::OsStr as std::os::unix::ffi::OsStrExt>::from_bytes(bytes));
+    #[cfg(not(unix))]
+    let path = PathBuf::from(String::from_utf8_lossy(bytes).into_owned());
+    path
+}
+
 /// Convert a fixed-length (possibly zero-terminated) char buffer to a string.
 /// # Panics
 /// Panics if the bytes are not valid UTF-8.
@@ -79,13 +167,14 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::os::raw::c_char;
     use std::ptr;
 
     use crate::sys::h5e::H5Eget_msg;
 
     use crate::globals::H5E_CANTOPENOBJ;
 
-    use super::{get_h5_str, string_from_cstr, to_cstring};
+    use super::{get_h5_str, name_from_cstr, string_from_cstr, to_cstring, MAX_NAME_LEN};
 
     #[test]
     pub fn test_string_cstr() {
@@ -97,6 +186,24 @@ mod tests {
         let c_s2 = to_cstring(s2).unwrap();
         // SAFETY: c_s2 is a valid C string created from a String
         assert_eq!(s2, unsafe { string_from_cstr(c_s2.as_ptr()) });
+        assert_eq!(unsafe { string_from_cstr(ptr::null()) }, "");
+        let invalid = [b'a', 0xff, 0].map(|b| b as c_char);
+        assert_eq!(unsafe { string_from_cstr(invalid.as_ptr()) }, "a\u{fffd}");
+    }
+
+    #[test]
+    pub fn test_name_from_cstr() {
+        let name = to_cstring("foo").unwrap();
+        assert_eq!(unsafe { name_from_cstr(name.as_ptr(), "link") }.unwrap(), "foo");
+        assert_err!(unsafe { name_from_cstr(ptr::null(), "link") }, "null link name");
+
+        let name = to_cstring("x".repeat(MAX_NAME_LEN)).unwrap();
+        assert_eq!(unsafe { name_from_cstr(name.as_ptr(), "link") }.unwrap().len(), MAX_NAME_LEN);
+        let name = to_cstring("x".repeat(MAX_NAME_LEN + 1)).unwrap();
+        assert_err!(
+            unsafe { name_from_cstr(name.as_ptr(), "attribute") },
+            "attribute name exceeds 65536 bytes, the file may be corrupt"
+        );
     }
 
     #[test]
@@ -0,0 +1,229 @@
+//! Lightweight versioning of datasets within a group.
+//!
+//! Writing a new version of a dataset keeps the previous versions reachable without copying
+//! them. The convention is stable so that other languages can read and write versioned
+//! datasets:
+//!
+//! - version `n` of the dataset `name` is stored as the member `"name@n"` of the group (see
+//!   [`VERSION_SEPARATOR`]), with versions numbered from 1;
+//! - the member `name` is a soft link to the latest version, so readers unaware of the
+//!   convention see the current data;
+//! - the group attribute `"name@count"` (see [`VERSION_COUNT_ATTR_SUFFIX`]) holds the latest
+//!   version number, so that version numbers are never reused after pruning.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> hdf5_rt::Result<()> {
+//! use hdf5_rt::{versioning::VersionedGroup, File};
+//! use ndarray::arr1;
+//!
+//! let file = File::create("versions.h5")?;
+//! let group = VersionedGroup::wrap(file.create_group("data")?);
+//! group.write_new_version("temperature", &arr1(&[20.5, 21.0]))?;
+//! group.write_new_version("temperature", &arr1(&[20.7, 21.3]))?;
+//! assert_eq!(group.versions("temperature")?, [1, 2]);
+//! let first = group.read_version::<f64>("temperature", 1)?;
+//! group.prune("temperature", 1)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use ndarray::{ArrayD, ArrayView};
+
+use crate::conventions::{VERSION_COUNT_ATTR_SUFFIX, VERSION_SEPARATOR};
+use crate::internal_prelude::*;
+
+/// A group holding versioned datasets, see the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct VersionedGroup {
+    group: Group,
+}
+
+impl VersionedGroup {
+    /// Wraps a group to manage the versioned datasets in it.
+    pub fn wrap(group: Group) -> Self {
+        Self { group }
+    }
+
+    /// Returns the wrapped group.
+    pub fn group(&self) -> &Group {
+        &self.group
+    }
+
+    /// Writes `data` as a new version of the dataset `name` and points `name` at it.
+    ///
+    /// Returns the number of the new version. Fails if `name` exists and is not a soft link,
+    /// i.e. if it is a dataset that is not versioned.
+    pub fn write_new_version<'d, A, T, D>(&self, name: &str, data: A) -> Result<u32>
+    where
+        A: Into<ArrayView<'d, T, D>>,
+        T: H5Type,
+        D: ndarray::Dimension,
+    {
+        check_name(name)?;
+        h5lock!({
+            let has_link = self.group.link_exists(name);
+            ensure!(
+                !has_link || self.group.link_info(name)?.link_type == LinkType::Soft,
+                "'{}' exists and is not a versioned dataset",
+                name
+            );
+            let version = self.latest_version(name)?.unwrap_or(0) + 1;
+            let version_name = version_name(name, version);
+            self.group.new_dataset_builder().with_data(data).create(version_name.as_str())?;
+            if has_link {
+                self.group.unlink(name)?;
+            }
+            self.group.link_soft(&version_name, name)?;
+            let count_attr = count_attr_name(name);
+            let attr = if self.group.attr_names()?.contains(&count_attr) {
+                self.group.attr(&count_attr)?
+            } else {
+                self.group.new_attr::<u32>().create(count_attr.as_str())?
+            };
+            attr.write_scalar(&version)?;
+            Ok(version)
+        })
+        .with_context(|| format!("writing a new version of '{name}'"))
+    }
+
+    /// Returns the numbers of the stored versions of the dataset `name`, in increasing order.
+    pub fn versions(&self, name: &str) -> Result<Vec<u32>> {
+        check_name(name)?;
+        let prefix = format!("{name}{VERSION_SEPARATOR}");
+        let mut versions: Vec<u32> = self
+            .group
+            .member_names()?
+            .iter()
+            .filter_map(|member| member.strip_prefix(&prefix))
+            .filter_map(|n| n.parse().ok().filter(|v: &u32| *v > 0 && v.to_string() == n))
+            .collect();
+        versions.sort_unstable();
+        Ok(versions)
+    }
+
+    /// Opens version `version` of the dataset `name`.
+    pub fn version(&self, name: &str, version: u32) -> Result<Dataset> {
+        check_name(name)?;
+        self.group.dataset(&version_name(name, version))
+    }
+
+    /// Reads version `version` of the dataset `name` into an array with dynamic number of
+    /// dimensions.
+    pub fn read_version<T: H5Type>(&self, name: &str, version: u32) -> Result<ArrayD<T>> {
+        self.version(name, version)?.read_dyn()
+    }
+
+    /// Deletes all but the `keep_last` latest versions of the dataset `name`.
+    ///
+    /// If no version is kept, the link `name` is removed as well. The version count is kept,
+    /// so versions written afterwards continue the numbering. Like other deletions, this does
+    /// not shrink the file; the space can be reclaimed with
+    /// [`File::repack_into`](crate::File::repack_into).
+    pub fn prune(&self, name: &str, keep_last: usize) -> Result<()> {
+        h5lock!({
+            let versions = self.versions(name)?;
+            let num_pruned = versions.len().saturating_sub(keep_last);
+            if keep_last == 0 && self.group.link_exists(name) {
+                self.group.unlink(name)?;
+            }
+            for &version in &versions[..num_pruned] {
+                self.group.delete_recursive(&version_name(name, version))?;
+            }
+            Ok(())
+        })
+        .with_context(|| format!("pruning versions of '{name}'"))
+    }
+
+    /// Returns the latest version number recorded for the dataset `name`, if any.
+    fn latest_version(&self, name: &str) -> Result<Option<u32>> {
+        let count_attr = count_attr_name(name);
+        if self.group.attr_names()?.contains(&count_attr) {
+            Ok(Some(self.group.attr(&count_attr)?.read_scalar()?))
+        } else {
+            Ok(self.versions(name)?.last().copied())
+        }
+    }
+}
+
+fn check_name(name: &str) -> Result<()> {
+    ensure!(
+        !name.is_empty() && !name.contains('/') && !name.contains(VERSION_SEPARATOR),
+        "invalid versioned dataset name '{}'",
+        name
+    );
+    Ok(())
+}
+
+fn version_name(name: &str, version: u32) -> String {
+    format!("{name}{VERSION_SEPARATOR}{version}")
+}
+
+fn count_attr_name(name: &str) -> String {
+    format!("{name}{VERSION_COUNT_ATTR_SUFFIX}")
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+
+    use ndarray::{arr1, Array1};
+
+    use super::VersionedGroup;
+    use crate::conventions::VERSION_COUNT_ATTR_SUFFIX;
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_versioned_group() {
+        with_tmp_dir(|dir| {
+            let (src_path, dst_path) = (dir.join("src.h5"), dir.join("dst.h5"));
+            let file = File::create(&src_path).unwrap();
+            let group = VersionedGroup::wrap(file.create_group("data").unwrap());
+            assert_eq!(group.versions("x").unwrap(), Vec::<u32>::new());
+
+            // 8 MB per version
+            let data: Vec<_> =
+                (0..3).map(|v| Array1::from_shape_fn(1_000_000, |i| (v * i) as f64)).collect();
+            for (i, arr) in data.iter().enumerate() {
+                assert_eq!(group.write_new_version("x", arr).unwrap(), i as u32 + 1);
+            }
+            assert_eq!(group.versions("x").unwrap(), [1, 2, 3]);
+            for (i, arr) in data.iter().enumerate() {
+                assert_eq!(
+                    group.read_version::<f64>("x", i as u32 + 1).unwrap(),
+                    arr.clone().into_dyn()
+                );
+            }
+            assert_eq!(file.dataset("data/x").unwrap().read_1d::<f64>().unwrap(), data[2]);
+            assert_eq!(
+                file.group("data").unwrap().link_info("x").unwrap().link_type,
+                LinkType::Soft
+            );
+            let count = file.group("data").unwrap().attr(&format!("x{VERSION_COUNT_ATTR_SUFFIX}"));
+            assert_eq!(count.unwrap().read_scalar::<u32>().unwrap(), 3);
+
+            assert_err!(group.write_new_version("a@1", &arr1(&[1])), "invalid versioned dataset");
+            group.group().new_dataset::<i32>().create("plain").unwrap();
+            assert_err!(group.write_new_version("plain", &arr1(&[1])), "not a versioned dataset");
+            assert_err!(group.read_version::<f64>("x", 4), "unable to");
+
+            group.prune("x", 1).unwrap();
+            assert_eq!(group.versions("x").unwrap(), [3]);
+            assert_eq!(file.dataset("data/x").unwrap().read_1d::<f64>().unwrap(), data[2]);
+            file.flush().unwrap();
+            let src_size = fs::metadata(&src_path).unwrap().len();
+            file.repack_into(&dst_path).unwrap().close().unwrap();
+            let dst_size = fs::metadata(&dst_path).unwrap().len();
+            assert!(src_size > 24_000_000, "{src_size}");
+            assert!(dst_size > 8_000_000 && dst_size < 9_000_000, "{dst_size}");
+
+            // numbering continues after pruning, even if no version is left
+            group.prune("x", 0).unwrap();
+            assert!(!group.group().link_exists("x"));
+            assert_eq!(group.versions("x").unwrap(), Vec::<u32>::new());
+            assert_eq!(group.write_new_version("x", &arr1(&[1.0])).unwrap(), 4);
+            assert_eq!(group.versions("x").unwrap(), [4]);
+        })
+    }
+}
@@ -1,26 +1,8 @@
 use std::convert::TryFrom;
-use std::fmt::{self, Debug};
-use std::iter;
 
-use hdf5_rt::types::{FixedAscii, FixedUnicode, VarLenArray, VarLenAscii, VarLenUnicode};
-use hdf5_rt::H5Type;
-
-use half::f16;
-use ndarray::{ArrayD, SliceInfo, SliceInfoElem};
-use num_complex::Complex;
-use rand::distr::StandardUniform;
-use rand::distr::{Alphanumeric, Uniform};
-use rand::prelude::Distribution;
+use ndarray::{SliceInfo, SliceInfoElem};
 use rand::prelude::Rng;
 
-pub fn gen_shape<R: Rng + ?Sized>(rng: &mut R, ndim: usize) -> Vec<usize> {
-    iter::repeat(()).map(|_| rng.random_range(0..11)).take(ndim).collect()
-}
-
-pub fn gen_ascii<R: Rng + ?Sized>(rng: &mut R, len: usize) -> String {
-    iter::repeat(()).map(|_| rng.sample(Alphanumeric)).map(char::from).take(len).collect()
-}
-
 /// Generate a random slice of elements inside the given `shape` dimension.
 pub fn gen_slice<R: Rng + ?Sized>(
     rng: &mut R,
@@ -53,144 +35,9 @@ fn gen_slice_one_dim<R: Rng + ?Sized>(rng: &mut R, shape: usize) -> ndarray::Sli
 
         let step =
             if rng.random_bool(0.9) { 1isize } else { rng.random_range(1..shape * 2) as isize };
+        // Occasionally reverse the slice.
+        let step = if rng.random_bool(0.2) { -step } else { step };
 
         ndarray::SliceInfoElem::Slice { start, end: end.map(|x| x as isize), step }
     }
 }
-
-pub trait Gen: Sized + fmt::Debug {
-    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
-}
-
-macro_rules! impl_gen_primitive {
-    ($ty:ty) => {
-        impl Gen for $ty {
-            fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
-                rng.random()
-            }
-        }
-    };
-    ($ty:ty, $($tys:ty),+) => {
-        impl_gen_primitive!($ty);
-        impl_gen_primitive!($($tys),*);
-    };
-}
-
-impl_gen_primitive!(u8, u16, u32, u64, i8, i16, i32, i64, bool, f32, f64);
-
-macro_rules! impl_gen_tuple {
-    ($t:ident) => (
-        impl<$t> Gen for ($t,) where $t: Gen {
-            fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
-                (<$t as Gen>::random(rng),)
-            }
-        }
-    );
-
-    ($t:ident, $($tt:ident),*) => (
-        impl<$t, $($tt),*> Gen for ($t, $($tt),*) where $t: Gen, $($tt: Gen),* {
-            fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
-                (<$t as Gen>::random(rng), $(<$tt as Gen>::random(rng)),*)
-            }
-        }
-        impl_gen_tuple!($($tt),*);
-    );
-}
-
-impl_gen_tuple! { A, B, C, D, E, F, G, H, I, J, K, L }
-
-impl Gen for f16 {
-    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
-        Self::from_f32(rng.random())
-    }
-}
-
-impl<T: Debug> Gen for Complex<T>
-where
-    StandardUniform: Distribution<T>,
-{
-    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
-        Self::new(rng.random(), rng.random())
-    }
-}
-
-pub fn gen_vec<R: Rng + ?Sized, T: Gen>(rng: &mut R, size: usize) -> Vec<T> {
-    iter::repeat(()).map(|_| T::random(rng)).take(size).collect()
-}
-
-pub fn gen_arr<T, R>(rng: &mut R, ndim: usize) -> ArrayD<T>
-where
-    T: H5Type + Gen,
-    R: Rng + ?Sized,
-{
-    let shape = gen_shape(rng, ndim);
-    let size = shape.iter().product();
-    let vec = gen_vec(rng, size);
-    ArrayD::from_shape_vec(shape, vec).unwrap()
-}
-
-impl<const N: usize> Gen for FixedAscii<N> {
-    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
-        let len = rng.sample(Uniform::new_inclusive(0, N).unwrap());
-        let dist = Uniform::new_inclusive(0, 127).unwrap();
-        let mut v = Vec::with_capacity(len);
-        for _ in 0..len {
-            v.push(rng.sample(dist));
-        }
-        unsafe { FixedAscii::from_ascii_unchecked(&v) }
-    }
-}
-
-impl<const N: usize> Gen for FixedUnicode<N> {
-    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
-        let len = rng.sample(Uniform::new_inclusive(0, N).unwrap());
-        let mut s = String::new();
-        for _ in 0..len {
-            let c = rng.random::<char>();
-            if c != '\0' {
-                if s.as_bytes().len() + c.len_utf8() >= len {
-                    break;
-                }
-                s.push(c);
-            }
-        }
-        unsafe { FixedUnicode::from_str_unchecked(s) }
-    }
-}
-
-impl Gen for VarLenAscii {
-    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
-        let len = rng.sample(Uniform::new_inclusive(0, 8).unwrap());
-        let dist = Uniform::new_inclusive(0, 127).unwrap();
-        let mut v = Vec::with_capacity(len);
-        for _ in 0..len {
-            v.push(rng.sample(dist));
-        }
-        unsafe { VarLenAscii::from_ascii_unchecked(&v) }
-    }
-}
-
-impl Gen for VarLenUnicode {
-    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
-        let len = rng.sample(Uniform::new_inclusive(0, 8).unwrap());
-        let mut s = String::new();
-        while s.len() < len {
-            let c = rng.random::<char>();
-            if c != '\0' {
-                s.push(c);
-            }
-        }
-        unsafe { VarLenUnicode::from_str_unchecked(s) }
-    }
-}
-
-impl<T: Gen + Copy> Gen for VarLenArray<T> {
-    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
-        let len = rng.sample(Uniform::new_inclusive(0, 8).unwrap());
-        let mut v = Vec::with_capacity(len);
-        for _ in 0..len {
-            v.push(Gen::random(rng));
-        }
-        VarLenArray::from_slice(&v)
-    }
-}
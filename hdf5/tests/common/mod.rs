@@ -1,6 +1,5 @@
 #![allow(dead_code)]
 
 pub mod gen;
-pub mod util;
 #[macro_use]
 pub mod macros;
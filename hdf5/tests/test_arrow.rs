@@ -0,0 +1,136 @@
+//! Tests for conversion to and from Arrow arrays.
+#![cfg(feature = "arrow")]
+
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float64Type, Int32Type, Int64Type};
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, Float64Array, Int64Array, LargeStringArray, RecordBatch,
+};
+use arrow_schema::DataType;
+use hdf5::fixtures::memory_file;
+use hdf5::types::{FixedAscii, VarLenUnicode};
+use hdf5_rt as hdf5;
+
+#[test]
+fn test_arrow_table_roundtrip() {
+    let file = memory_file().unwrap();
+    let batch = RecordBatch::try_from_iter([
+        ("i", Arc::new(Int64Array::from(vec![1, -2, i64::MAX])) as ArrayRef),
+        ("f", Arc::new(Float64Array::from(vec![0.5, f64::NAN, -1e300])) as ArrayRef),
+        ("b", Arc::new(BooleanArray::from(vec![true, false, true])) as ArrayRef),
+        ("s", Arc::new(LargeStringArray::from(vec!["", "héllo", "wörld"])) as ArrayRef),
+    ])
+    .unwrap();
+    file.write_table("table/", &batch).unwrap();
+
+    let table = file.group("table").unwrap();
+    assert_eq!(
+        table.dataset("s").unwrap().read_raw::<VarLenUnicode>().unwrap()[1].as_str(),
+        "héllo"
+    );
+    let read = table.read_table(&["i", "f", "b", "s"]).unwrap();
+    assert_eq!(read.schema(), batch.schema());
+    assert_eq!(read.num_rows(), 3);
+    assert_eq!(read.column(0).as_primitive::<Int64Type>(), batch.column(0).as_primitive());
+    let floats = read.column(1).as_primitive::<Float64Type>();
+    assert_eq!(floats.value(0), 0.5);
+    assert!(floats.value(1).is_nan());
+    assert_eq!(read.column(2).as_boolean(), batch.column(2).as_boolean());
+    assert_eq!(read.column(3).as_string::<i64>(), batch.column(3).as_string::<i64>());
+
+    // a subset of columns, in any order
+    let read = table.read_table(&["s", "i"]).unwrap();
+    assert_eq!(read.schema().field(0).name(), "s");
+    assert_eq!(read.num_columns(), 2);
+
+    // slices of arrays are written from their offset
+    let sliced = batch.slice(1, 2);
+    file.write_table("sliced_", &sliced).unwrap();
+    let read = file.read_table(&["sliced_i", "sliced_s"]).unwrap();
+    assert_eq!(read.column(0).as_primitive::<Int64Type>().values(), &[-2, i64::MAX]);
+    assert_eq!(read.column(1).as_string::<i64>().value(1), "wörld");
+}
+
+#[test]
+fn test_arrow_fixed_types() {
+    let file = memory_file().unwrap();
+    let arrays = [[1_i32, 2, 3], [4, 5, 6]];
+    let ds =
+        file.new_dataset_builder().with_data(&ndarray::arr1(&arrays)).create("arrays").unwrap();
+    let read = ds.read_arrow().unwrap();
+    let DataType::FixedSizeList(field, 3) = read.data_type() else {
+        panic!("unexpected type {}", read.data_type());
+    };
+    assert_eq!(field.data_type(), &DataType::Int32);
+    let list = read.as_fixed_size_list();
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.value(1).as_primitive::<Int32Type>().values(), &[4, 5, 6]);
+
+    let strings: Vec<FixedAscii<8>> =
+        ["a", "bc", "12345678"].iter().map(|s| FixedAscii::from_ascii(s).unwrap()).collect();
+    let ds = file.new_dataset_builder().with_data(&strings).create("strings").unwrap();
+    let read = ds.read_arrow().unwrap();
+    assert_eq!(read.data_type(), &DataType::Utf8);
+    let read = read.as_string::<i32>();
+    assert_eq!(read.iter().flatten().collect::<Vec<_>>(), ["a", "bc", "12345678"]);
+
+    // fixed size lists are written as fixed arrays
+    let batch = RecordBatch::try_from_iter([("arrays", ds_array(&file, "arrays"))]).unwrap();
+    let group = file.create_group("copy").unwrap();
+    group.write_table("", &batch).unwrap();
+    assert_eq!(group.dataset("arrays").unwrap().read_raw::<[i32; 3]>().unwrap(), arrays);
+}
+
+fn ds_array(file: &hdf5::File, name: &str) -> ArrayRef {
+    file.dataset(name).unwrap().read_arrow().unwrap()
+}
+
+#[test]
+fn test_arrow_errors() {
+    let file = memory_file().unwrap();
+    file.new_dataset_builder().with_data(&[1_i64, 2, 3]).create("long").unwrap();
+    file.new_dataset_builder().with_data(&[1_i64, 2]).create("short").unwrap();
+    file.new_dataset::<f32>().shape((2, 2)).create("matrix").unwrap();
+    file.new_dataset::<[[u8; 2]; 2]>().shape(3).create("nested").unwrap();
+
+    let err = file.read_table(&["long", "short"]).unwrap_err().to_string();
+    assert!(err.contains("column 'short'") && err.contains("expected 3 rows, got 2"), "{err}");
+    let err = file.read_table(&["matrix"]).unwrap_err().to_string();
+    assert!(err.contains("column 'matrix'") && err.contains("expected a 1-D dataset"), "{err}");
+    let err = file.read_table(&["long", "nested"]).unwrap_err().to_string();
+    assert!(err.contains("column 'nested'") && err.contains("unsupported type"), "{err}");
+    let err = file.read_table(&["missing"]).unwrap_err().to_string();
+    assert!(err.contains("column 'missing'"), "{err}");
+
+    let nulls = Arc::new(Int64Array::from(vec![Some(1), None])) as ArrayRef;
+    let batch = RecordBatch::try_from_iter([("nullable", nulls)]).unwrap();
+    let err = file.write_table("", &batch).unwrap_err().to_string();
+    assert!(err.contains("column 'nullable'") && err.contains("nulls"), "{err}");
+    let dates = Arc::new(arrow_array::Date32Array::from(vec![1])) as ArrayRef;
+    let batch = RecordBatch::try_from_iter([("date", dates)]).unwrap();
+    let err = file.write_table("", &batch).unwrap_err().to_string();
+    assert!(err.contains("column 'date'") && err.contains("unsupported Arrow type"), "{err}");
+}
+
+#[test]
+fn test_arrow_batches() {
+    const LEN: usize = 1_000_000;
+    let file = memory_file().unwrap();
+    let values: Vec<f64> = (0..LEN).map(|i| i as f64 * 0.5).collect();
+    let ds = file.new_dataset_builder().chunk(10_000).with_data(&values).create("big").unwrap();
+
+    let mut read = Vec::with_capacity(LEN);
+    let mut batches = 0;
+    for batch in ds.read_arrow_batches(65_536).unwrap() {
+        let batch = batch.unwrap();
+        assert!(batch.len() <= 65_536);
+        read.extend_from_slice(batch.as_primitive::<Float64Type>().values());
+        batches += 1;
+    }
+    assert_eq!(batches, LEN.div_ceil(65_536));
+    assert_eq!(read, values);
+    assert_eq!(ds.read_arrow().unwrap().as_primitive::<Float64Type>().values(), &values[..]);
+    assert!(ds.read_arrow_batches(0).is_err());
+}
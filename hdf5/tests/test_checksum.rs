@@ -0,0 +1,86 @@
+//! Tests for dataset content checksums.
+#![cfg(feature = "checksum")]
+
+use hdf5::conventions::CHECKSUM_ATTR;
+use hdf5::dataset::ChecksumStatus;
+use hdf5::fixtures::memory_file;
+use hdf5::types::{CompoundField, CompoundType, TypeDescriptor};
+use hdf5::H5Type;
+use hdf5_rt as hdf5;
+use ndarray::{s, Array2};
+
+#[test]
+fn test_checksum_roundtrip() {
+    let file = memory_file().unwrap();
+    let data = Array2::from_shape_fn((300, 7), |(i, j)| (i * 7 + j) as i64);
+    let ds = file.new_dataset::<i64>().shape((300, 7)).create("data").unwrap();
+    let checksum = ds.write_with_checksum(&data).unwrap();
+    assert_eq!(ds.attr(CHECKSUM_ATTR).unwrap().read_scalar::<u64>().unwrap(), checksum);
+    assert_eq!(ds.as_reader().verify_checksum().unwrap(), ChecksumStatus::Valid);
+    assert_eq!(ds.as_reader().verified().read_2d::<i64>().unwrap(), data);
+
+    // converted writes hash the stored bytes, so the checksum does not depend on the memory type
+    let narrow = file.new_dataset::<i64>().shape((300, 7)).create("narrow").unwrap();
+    assert_eq!(narrow.write_with_checksum(&data.mapv(|x| x as i32)).unwrap(), checksum);
+    assert_eq!(narrow.update_checksum().unwrap(), checksum);
+
+    // writing without updating the checksum is detected
+    ds.write_slice(&[-1_i64; 7], s![150, ..]).unwrap();
+    let err = ds.as_reader().verify_checksum().unwrap_err().to_string();
+    assert!(
+        err.contains("checksum mismatch") && err.contains(&format!("{checksum:#018x}")),
+        "{err}"
+    );
+    assert!(ds.as_reader().verified().read_slice_1d::<i64, _>(s![0, ..]).is_err());
+    assert!(ds.as_reader().read_slice_1d::<i64, _>(s![0, ..]).is_ok());
+    let updated = ds.update_checksum().unwrap();
+    assert_ne!(updated, checksum);
+    assert_eq!(ds.as_reader().verify_checksum().unwrap(), ChecksumStatus::Valid);
+}
+
+#[test]
+fn test_checksum_missing_and_unsupported() {
+    let file = memory_file().unwrap();
+    let ds = file.new_dataset_builder().with_data(&[1.5_f32, 2.5]).create("plain").unwrap();
+    assert_eq!(ds.as_reader().verify_checksum().unwrap(), ChecksumStatus::NotChecksummed);
+    assert_eq!(ds.as_reader().verified().read_raw::<f32>().unwrap(), [1.5, 2.5]);
+
+    let scalar = file.new_dataset::<u8>().create("scalar").unwrap();
+    scalar.write_with_checksum(&ndarray::arr0(7_u8)).unwrap();
+    assert_eq!(scalar.as_reader().verify_checksum().unwrap(), ChecksumStatus::Valid);
+
+    let strings = file.new_dataset::<hdf5::types::VarLenUnicode>().shape(2).create("s").unwrap();
+    let err = strings.update_checksum().unwrap_err().to_string();
+    assert!(err.contains("cannot checksum"), "{err}");
+}
+
+/// A compound type with 3 padding bytes between its fields.
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct Padded {
+    a: u8,
+    b: u32,
+}
+
+unsafe impl H5Type for Padded {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<u8>("a", 0, 0),
+                CompoundField::typed::<u32>("b", 4, 1),
+            ],
+            size: 8,
+        })
+    }
+}
+
+#[test]
+fn test_checksum_padded_compound() {
+    // the padding bytes are not hashed from memory, so the checksum is that of the stored data
+    let file = memory_file().unwrap();
+    let data: Vec<_> = (0..100).map(|i| Padded { a: i as u8, b: i * 1000 }).collect();
+    let ds = file.new_dataset::<Padded>().shape(100).create("padded").unwrap();
+    let checksum = ds.write_with_checksum(&data).unwrap();
+    assert_eq!(ds.update_checksum().unwrap(), checksum);
+    assert_eq!(ds.as_reader().verify_checksum().unwrap(), ChecksumStatus::Valid);
+}
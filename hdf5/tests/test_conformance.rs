@@ -0,0 +1,58 @@
+//! Tests for the interoperability cases of the `conformance` feature.
+
+use hdf5::conformance::{self, CaseStatus, CONFORMANCE_VERSION, VERSION_ATTR};
+use hdf5::fixtures::memory_file;
+use hdf5_rt as hdf5;
+
+#[test]
+fn test_write_verify_all() {
+    let file = memory_file().unwrap();
+    conformance::write_all(&file).unwrap();
+    assert_eq!(conformance::file_version(&file).unwrap(), CONFORMANCE_VERSION);
+
+    let results = conformance::verify_all(&file);
+    assert_eq!(results.len(), conformance::cases().len());
+    for result in &results {
+        assert_eq!(result.status, CaseStatus::Pass, "{}: {}", result.name, result.detail);
+        assert!(result.detail.is_empty());
+    }
+}
+
+#[test]
+fn test_corrupted_case() {
+    let file = memory_file().unwrap();
+    conformance::write_all(&file).unwrap();
+    file.dataset("layouts/chunked").unwrap().write_raw(&[7.0; 24]).unwrap();
+
+    let results = conformance::verify_all(&file);
+    let failed: Vec<_> = results.iter().filter(|r| r.status != CaseStatus::Pass).collect();
+    assert_eq!(failed.len(), 1, "{failed:?}");
+    assert_eq!(failed[0].name, "layouts");
+    assert_eq!(failed[0].detail, "dataset '/layouts/chunked' element 0: expected 0.0, found 7.0");
+}
+
+#[test]
+fn test_selected_cases() {
+    let cases = conformance::select(&["compound", "unicode_names"]).unwrap();
+    assert_eq!(cases.iter().map(|c| c.name()).collect::<Vec<_>>(), ["compound", "unicode_names"]);
+    let err = conformance::select(&["compound", "nope"]).unwrap_err();
+    assert!(err.to_string().contains("unknown conformance case 'nope'"), "{err}");
+
+    let file = memory_file().unwrap();
+    conformance::write_cases(&file, &cases).unwrap();
+    assert!(!file.link_exists("layouts"));
+    let results = conformance::verify_cases(&file, &cases);
+    assert!(results.iter().all(|r| r.status == CaseStatus::Pass), "{results:?}");
+    // cases missing from the file fail
+    let results = conformance::verify_cases(&file, &conformance::select(&["layouts"]).unwrap());
+    assert_eq!(results[0].status, CaseStatus::Fail);
+
+    // cases newer than the file are skipped, and files without a version fail
+    file.attr(VERSION_ATTR).unwrap().write_scalar(&0_u32).unwrap();
+    let results = conformance::verify_cases(&file, &cases);
+    assert!(results.iter().all(|r| r.status == CaseStatus::Skip), "{results:?}");
+    assert_eq!(results[0].detail, "added in version 1, file has version 0");
+    file.delete_attr(VERSION_ATTR).unwrap();
+    let results = conformance::verify_cases(&file, &cases);
+    assert!(results.iter().all(|r| r.status == CaseStatus::Fail), "{results:?}");
+}
@@ -3,15 +3,15 @@ use std::fmt;
 use std::io::{Read, Seek, SeekFrom};
 
 use ndarray::{s, Array1, Array2, ArrayD, IxDyn, SliceInfo};
-use rand::prelude::{Rng, SeedableRng, SmallRng};
+use rand::prelude::Rng;
 
 use hdf5_rt;
 use hdf5_rt::types::TypeDescriptor;
 
 mod common;
 
-use self::common::gen::{gen_arr, gen_slice, Gen};
-use self::common::util::new_in_memory_file;
+use self::common::gen::gen_slice;
+use hdf5_rt::fixtures::{self, gen_arr, memory_file, Gen};
 
 fn test_write_slice<T, R>(
     rng: &mut R,
@@ -127,9 +127,12 @@ where
     let out_dyn = ds.read_dyn::<T>();
     assert_eq!(arr, &out_dyn?.into_dimensionality().unwrap());
 
-    // read_scalar()
+    // read_scalar(), which accepts any shape with a single element unless strict
     let out_scalar = ds.read_scalar::<T>();
-    if ndim == 0 {
+    if ndim != 0 && arr.len() == 1 {
+        assert!(ds.as_reader().strict_scalar(true).read_scalar::<T>().is_err());
+    }
+    if arr.len() == 1 {
         assert_eq!(arr.as_slice().unwrap()[0], out_scalar?);
     } else {
         assert!(out_scalar.is_err());
@@ -154,7 +157,7 @@ where
     Ok(())
 }
 
-fn test_write<T>(ds: &hdf5_rt::Dataset, arr: &ArrayD<T>, ndim: usize) -> hdf5_rt::Result<()>
+fn test_write<T>(ds: &hdf5_rt::Dataset, arr: &ArrayD<T>) -> hdf5_rt::Result<()>
 where
     T: hdf5_rt::H5Type + fmt::Debug + PartialEq + Gen,
 {
@@ -163,7 +166,7 @@ where
     assert_eq!(&ds.read_dyn::<T>()?, arr);
 
     // .write_scalar()
-    if ndim == 0 {
+    if arr.len() == 1 {
         ds.write_scalar(&arr.as_slice().unwrap()[0])?;
         assert_eq!(&ds.read_dyn::<T>()?, arr);
     } else if arr.len() > 0 {
@@ -182,7 +185,7 @@ fn test_byte_read_seek_impl(
     arr: &ArrayD<u8>,
     ndim: usize,
 ) -> hdf5_rt::Result<()> {
-    let mut rng = SmallRng::seed_from_u64(42);
+    let mut rng = fixtures::rng(42);
     ds.write(arr)?;
 
     // Read whole
@@ -270,8 +273,8 @@ where
         packed.push(true);
     }
 
-    let mut rng = SmallRng::seed_from_u64(42);
-    let file = new_in_memory_file()?;
+    let mut rng = fixtures::rng(42);
+    let file = memory_file()?;
 
     for packed in &packed {
         for ndim in 0..=4 {
@@ -289,7 +292,7 @@ where
                     if mode == 0 {
                         test_read(&ds, &arr, ndim)?;
                     } else if mode == 1 {
-                        test_write(&ds, &arr, ndim)?;
+                        test_write(&ds, &arr)?;
                     } else if mode == 2 {
                         test_read_slice(&mut rng, &ds, &arr, ndim)?;
                     } else if mode == 3 {
@@ -337,7 +340,7 @@ fn test_read_write_complex() -> hdf5_rt::Result<()> {
 
 #[test]
 fn test_create_on_databuilder() {
-    let file = new_in_memory_file().unwrap();
+    let file = memory_file().unwrap();
 
     let _ds = file.new_dataset_builder().empty::<i32>().create("ds1").unwrap();
     let _ds = file.new_dataset_builder().with_data(&[1_i32, 2, 3]).create("ds2").unwrap();
@@ -347,8 +350,8 @@ fn test_create_on_databuilder() {
 
 #[test]
 fn test_byte_read_seek() -> hdf5_rt::Result<()> {
-    let mut rng = SmallRng::seed_from_u64(42);
-    let file = new_in_memory_file()?;
+    let mut rng = fixtures::rng(42);
+    let file = memory_file()?;
 
     for ndim in 0..=2 {
         for _ in 0..=20 {
@@ -368,7 +371,7 @@ fn test_byte_read_seek() -> hdf5_rt::Result<()> {
 
 #[test]
 fn remove_attr() {
-    let file = new_in_memory_file().unwrap();
+    let file = memory_file().unwrap();
 
     file.new_attr::<i32>().create("foo").unwrap();
     assert!(file.attr("foo").is_ok());
@@ -381,3 +384,301 @@ fn remove_attr() {
     ds.delete_attr("bar").unwrap();
     assert!(ds.attr("bar").is_err());
 }
+
+#[test]
+fn test_read_with_transfer_plist() -> hdf5_rt::Result<()> {
+    use hdf5_rt::dataset::{DatasetTransfer, DEFAULT_BUFFER_SIZE};
+
+    let file = memory_file()?;
+    // 100 MB of i32, read back as f64 so that every element goes through the conversion buffer
+    let n: i32 = 25 * 1024 * 1024;
+    let arr = Array1::from_iter((0..n).map(|i| i.wrapping_mul(7919)));
+    let ds = file.new_dataset_builder().with_data(&arr).create("x")?;
+
+    let expected = arr.mapv(f64::from);
+    let dxpl = DatasetTransfer::build().buffer_size(16 * 1024 * 1024).finish()?;
+    assert_eq!(dxpl.buffer_size(), 16 * 1024 * 1024);
+    assert_eq!(DatasetTransfer::try_new()?.buffer_size(), DEFAULT_BUFFER_SIZE);
+    assert_eq!(ds.as_reader().read_1d::<f64>()?, expected);
+    assert_eq!(ds.as_reader().transfer_plist(&dxpl).read_1d::<f64>()?, expected);
+
+    // the transfer plist also applies to writes; the caller's plist is left untouched
+    let small = DatasetTransfer::build().buffer_size(1024).finish()?;
+    ds.as_writer().dxpl(&small).write(&expected)?;
+    assert_eq!(ds.as_reader().dxpl(&small).read_1d::<i32>()?, arr);
+    assert_eq!(small.buffer_size(), 1024);
+    Ok(())
+}
+
+#[test]
+fn test_read_write_zero_size() -> hdf5_rt::Result<()> {
+    let file = memory_file()?;
+
+    let ds = file.new_dataset::<f64>().shape(0).create("empty")?;
+    assert_eq!(ds.read_raw::<f64>()?, Vec::<f64>::new());
+    assert_eq!(ds.read_1d::<f64>()?, Array1::<f64>::zeros(0));
+    ds.write_raw(&Vec::<f64>::new())?;
+    ds.write(&Array1::<f64>::zeros(0))?;
+    assert!(ds.write_raw(&[1.0_f64]).is_err());
+    assert!(ds.write_raw(&Vec::<hdf5_rt::types::VarLenUnicode>::new()).is_err());
+
+    let ds = file.new_dataset::<i32>().shape((3, 0, 2)).create("empty3")?;
+    let arr = ds.read_dyn::<i32>()?;
+    assert_eq!(arr.shape(), [3, 0, 2]);
+    assert_eq!(ds.read::<i32, ndarray::Ix3>()?.shape(), [3, 0, 2]);
+    assert_eq!(ds.read_slice::<i32, _, ndarray::Ix3>(s![1.., .., ..1])?.shape(), [2, 0, 1]);
+
+    let ds = file.new_dataset_builder().with_data(&Vec::<f64>::new()).create("from_data")?;
+    assert_eq!(ds.shape().unwrap(), [0]);
+
+    let attr = ds.new_attr::<u16>().shape(0).create("attr")?;
+    assert!(attr.read_raw::<u16>()?.is_empty());
+    attr.write_raw(&Vec::<u16>::new())?;
+    Ok(())
+}
+
+#[test]
+fn test_read_write_reversed_slice() -> hdf5_rt::Result<()> {
+    let file = memory_file()?;
+    let arr = Array2::from_shape_fn((4, 5), |(i, j)| (i * 5 + j) as i32);
+    let ds = file.new_dataset_builder().with_data(&arr).create("data")?;
+
+    let reversed = s![..;-1, ..;-1];
+    assert_eq!(ds.read_slice_2d::<i32, _>(reversed)?, arr.slice(reversed));
+    // mixed steps, combined with an index
+    let mixed = s![1..;-2, 1..5;2];
+    assert_eq!(ds.read_slice_2d::<i32, _>(mixed)?, arr.slice(mixed));
+    let row = s![2, 1..4;-1];
+    assert_eq!(ds.read_slice_1d::<i32, _>(row)?, arr.slice(row));
+
+    ds.write_slice(&arr, reversed)?;
+    assert_eq!(ds.read_2d::<i32>()?, arr.slice(reversed));
+    ds.write_slice(&arr.slice(row).to_owned(), row)?;
+    let mut expected = arr.slice(reversed).to_owned();
+    expected.slice_mut(row).assign(&arr.slice(row));
+    assert_eq!(ds.read_2d::<i32>()?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_read_with_conversion_report() -> hdf5_rt::Result<()> {
+    use hdf5_rt::dataset::{ConversionExceptionPolicy, DatasetTransfer};
+
+    let file = memory_file()?;
+    let data = [1.5, 1e300, -2.0, -1e300, 3e39, 0.25];
+    let ds = file.new_dataset_builder().with_data(&data).create("x")?;
+
+    // values outside the range of f32 are clamped to infinities, and counted
+    let (read, report) = ds.read_with_report::<f32>()?;
+    assert_eq!(
+        read.as_slice().unwrap(),
+        [1.5, f32::INFINITY, -2.0, f32::NEG_INFINITY, f32::INFINITY, 0.25]
+    );
+    assert_eq!((report.overflow, report.underflow, report.total()), (2, 1, 3));
+    let (read, report) = ds.read_with_report::<f64>()?;
+    assert_eq!(read.as_slice().unwrap(), data);
+    assert!(report.is_empty());
+
+    let count = DatasetTransfer::build()
+        .on_conversion_exception(ConversionExceptionPolicy::Count)
+        .finish()?;
+    let (_, report) = ds.as_reader().dxpl(&count).read_with_report::<f32>()?;
+    assert_eq!(report.overflow, 2);
+    // without a report to count into, the conversion proceeds as by default
+    assert_eq!(ds.as_reader().dxpl(&count).read_raw::<f32>()?[1], f32::INFINITY);
+
+    let abort = DatasetTransfer::build()
+        .on_conversion_exception(ConversionExceptionPolicy::Abort)
+        .finish()?;
+    assert!(ds.as_reader().dxpl(&abort).read_raw::<f32>().is_err());
+    assert!(ds.as_reader().dxpl(&abort).read_with_report::<f32>().is_err());
+    assert_eq!(ds.as_reader().dxpl(&abort).read_raw::<f64>()?, data);
+
+    let attr = ds.new_attr_builder().with_data(&data).create("a")?;
+    let err = attr.read_with_report::<f32>().unwrap_err();
+    assert!(err.to_string().contains("can only be reported for datasets"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn test_create_from_iter() -> hdf5_rt::Result<()> {
+    let file = memory_file()?;
+    let value = |i: usize| (i as u32).wrapping_mul(2_654_435_761);
+
+    const LEN: usize = 10_000_000;
+    let ds = file
+        .new_dataset_builder()
+        .from_iter(LEN, (0..LEN).map(value))
+        .batch_size(64 * 1024)
+        .create("generated")?;
+    assert_eq!(ds.shape().unwrap(), [LEN]);
+    assert_eq!(ds.chunk(), Some(vec![64 * 1024]));
+    let read = ds.read_raw::<u32>()?;
+    assert!(read.iter().enumerate().all(|(i, &x)| x == value(i)));
+    let tail = ds.read_slice_1d::<u32, _>(LEN - 3..)?;
+    assert_eq!(tail.as_slice().unwrap(), [value(LEN - 3), value(LEN - 2), value(LEN - 1)]);
+
+    // the iterator must yield exactly the declared number of elements
+    let err = file.new_dataset_builder().from_iter(100, 0..60_u32).create("short").unwrap_err();
+    assert!(err.to_string().contains("yielded 60 elements, expected 100"), "{err}");
+    assert!(!file.link_exists("short"));
+    let err = file.new_dataset_builder().from_iter(100, 0..150_u32).create("long").unwrap_err();
+    assert!(err.to_string().contains("more than 100 elements (consumed 101)"), "{err}");
+    assert!(!file.link_exists("long"));
+    let ds = file.new_dataset_builder().from_iter(0, 0..0_u8).create("empty")?;
+    assert_eq!(ds.shape().unwrap(), [0]);
+
+    // an explicit chunk shape sets the batch size
+    let ds = file.new_dataset_builder().chunk(7).from_iter(20, 0..20_i64).create("chunked")?;
+    assert_eq!(ds.chunk(), Some(vec![7]));
+    assert_eq!(ds.read_raw::<i64>()?, (0..20).collect::<Vec<_>>());
+
+    // n-d data is written in batches of whole rows
+    let ds = file
+        .new_dataset_builder()
+        .from_iter((1000, 3, 4), 0..12_000_u32)
+        .batch_size(100)
+        .create("nd")?;
+    assert_eq!(ds.shape()?, [1000, 3, 4]);
+    assert_eq!(ds.chunk(), Some(vec![8, 3, 4]));
+    assert_eq!(ds.read_raw::<u32>()?, (0..12_000).collect::<Vec<_>>());
+    let err = file
+        .new_dataset_builder()
+        .from_iter((10, 4), 0..38_u32)
+        .batch_size(8)
+        .create("nd-short")
+        .unwrap_err();
+    assert!(err.to_string().contains("yielded 38 elements, expected 40"), "{err}");
+    assert!(!file.link_exists("nd-short"));
+    let ds = file.new_dataset_builder().from_iter((), [2.5_f32]).create("scalar")?;
+    assert_eq!(ds.read_scalar::<f32>()?, 2.5);
+
+    let ds = file
+        .new_dataset_builder()
+        .from_iter_unbounded(1000, (0..2500_u16).map(|i| i * 3))
+        .create("unbounded")?;
+    assert_eq!(ds.shape().unwrap(), [2500]);
+    assert!(ds.is_resizable());
+    assert_eq!(ds.chunk(), Some(vec![1000]));
+    assert_eq!(ds.read_raw::<u16>()?, (0..2500).map(|i| i * 3).collect::<Vec<_>>());
+    let ds = file.new_dataset_builder().from_iter_unbounded(16, 0..0_u16).create("none")?;
+    assert_eq!(ds.shape().unwrap(), [0]);
+    Ok(())
+}
+
+#[test]
+fn test_get_or_create() -> hdf5_rt::Result<()> {
+    use hdf5_rt::File;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("get_or_create.h5");
+    let run = |file: &File| {
+        let group = file.get_or_create_group("a/b/c")?;
+        let builder = group.new_dataset::<f64>().shape((0.., 3)).chunk((8, 3)).deflate(4);
+        builder.get_or_create("values")
+    };
+
+    // first run: groups nested three deep and the dataset are created
+    let file = File::create(&path)?;
+    let ds = run(&file)?;
+    assert!(file.group("a/b").is_ok());
+    ds.resize((2, 3))?;
+    ds.write_raw(&[1., 2., 3., 4., 5., 6.])?;
+    drop((ds, file));
+
+    // second run: everything is reused, along with the data written before
+    let file = File::open_rw(&path)?;
+    let ds = run(&file)?;
+    assert_eq!(ds.read_raw::<f64>()?, [1., 2., 3., 4., 5., 6.]);
+    assert_eq!(file.group("a")?.len(), 1);
+    assert_eq!(file.group("a/b")?.len(), 1);
+    assert_eq!(file.get_or_create_group("a/b")?.name(), "/a/b");
+
+    let group = file.group("a/b/c")?;
+    let err = group.new_dataset::<i32>().shape((0.., 3)).get_or_create("values").unwrap_err();
+    assert!(err.to_string().contains("datatype mismatch"), "{err}");
+    let err = group.new_dataset::<f64>().shape((4, 3)).get_or_create("values").unwrap_err();
+    assert!(err.to_string().contains("shape mismatch"), "{err}");
+    let builder = group.new_dataset::<f64>().shape((0.., 3));
+    let err = builder.clone().chunk((4, 3)).get_or_create("values").unwrap_err();
+    assert!(err.to_string().contains("chunk shape mismatch"), "{err}");
+    let err = builder.clone().shuffle().get_or_create("values").unwrap_err();
+    assert!(err.to_string().contains("filters mismatch"), "{err}");
+    // properties not set explicitly are not checked
+    assert_eq!(builder.clone().deflate(4).get_or_create("values")?.shape().unwrap(), [2, 3]);
+    assert_eq!(builder.get_or_create("values")?.shape().unwrap(), [2, 3]);
+
+    let err = file.get_or_create_group("a/b/c/values").unwrap_err();
+    assert!(err.to_string().contains("exists but is not a group"), "{err}");
+    let err = file.new_dataset::<f64>().get_or_create("a").unwrap_err();
+    assert!(err.to_string().contains("opening dataset 'a'"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn test_bad_chunking_warning() -> hdf5_rt::Result<()> {
+    let file = memory_file()?;
+    // single-row chunks decompress every chunk for each column read
+    let bad = file.new_dataset::<f64>().shape((200, 1000)).chunk((1, 1000));
+    let err = bad.clone().warn_on_bad_chunking(true).strict_chunk(true).create("strict");
+    let err = err.unwrap_err().to_string();
+    assert!(
+        err.contains("reads 1000 times the requested data when reading whole columns"),
+        "{err}"
+    );
+    assert!(err.contains("rejected by `strict_chunk`"), "{err}");
+    assert!(!file.link_exists("strict"));
+
+    // without strict checking, the warning is printed and the dataset created
+    let ds = bad.clone().warn_on_bad_chunking(true).create("warned")?;
+    let report = ds.chunk_alignment_report(&[usize::MAX, 1])?;
+    assert_eq!(report.read_amplification, 1000.0);
+    assert_eq!(report.max_chunks_per_access, 200);
+    let report = ds.chunk_alignment_report(&[1, usize::MAX])?;
+    assert_eq!((report.read_amplification, report.max_chunks_per_access), (1.0, 1));
+    bad.warn_on_bad_chunking(false).strict_chunk(true).create("unchecked")?;
+
+    let good = file.new_dataset::<f64>().shape((200, 1000)).chunk((20, 100));
+    good.warn_on_bad_chunking(true).strict_chunk(true).create("good")?;
+    let contiguous = file.new_dataset::<f64>().shape((2, 2)).create("contiguous")?;
+    let err = contiguous.chunk_alignment_report(&[1, 2]).unwrap_err();
+    assert!(err.to_string().contains("is not chunked"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn test_read_write_single_element_as_scalar() -> hdf5_rt::Result<()> {
+    let file = memory_file()?;
+    let ds = file.new_dataset_builder().with_data(&[7_i32]).create("one")?;
+    assert_eq!(ds.read_scalar::<i32>()?, 7);
+    let err = ds.as_reader().strict_scalar(true).read_scalar::<i32>().unwrap_err();
+    assert!(err.to_string().contains("shape [1] cannot be read into a scalar"), "{err}");
+    assert!(err.to_string().contains("`strict_scalar` is set"), "{err}");
+
+    let two = file.new_dataset_builder().with_data(&[1_i32, 2]).create("two")?;
+    let err = two.read_scalar::<i32>().unwrap_err();
+    assert!(err.to_string().contains("shape [2] cannot be read into a scalar"), "{err}");
+    assert!(two.write_scalar(&3_i32).is_err());
+
+    let matrix = file.new_dataset::<f64>().shape((1, 1)).create("matrix")?;
+    matrix.write_scalar(&2.5)?;
+    assert_eq!(matrix.read_raw::<f64>()?, [2.5]);
+    assert_eq!(matrix.read_scalar::<f64>()?, 2.5);
+    let scalar = file.new_dataset::<f64>().create("scalar")?;
+    scalar.write_scalar(&1.5)?;
+    assert_eq!(scalar.as_reader().strict_scalar(true).read_scalar::<f64>()?, 1.5);
+
+    // attributes follow the same rules
+    let attr = ds.new_attr::<u8>().shape(1).create("one")?;
+    attr.write_scalar(&9)?;
+    assert_eq!(attr.read_raw::<u8>()?, [9]);
+    assert_eq!(attr.read_scalar::<u8>()?, 9);
+    assert!(attr.as_reader().strict_scalar(true).read_scalar::<u8>().is_err());
+    let attr = ds.new_attr::<u8>().shape((1, 1, 1)).create("cube")?;
+    attr.write_scalar(&4)?;
+    assert_eq!(attr.read_scalar::<u8>()?, 4);
+    let attr = ds.new_attr::<u8>().shape(2).create("two")?;
+    assert!(attr.read_scalar::<u8>().is_err());
+    assert!(attr.write_scalar(&1).is_err());
+    Ok(())
+}
@@ -1,9 +1,11 @@
 #[macro_use]
 mod common;
 
-use hdf5::sys::h5::H5I_INVALID_HID;
+use std::mem;
+
+use hdf5::raw::{from_id, H5I_INVALID_HID};
 use hdf5::types::{TypeDescriptor as TD, *};
-use hdf5::{from_id, Datatype, H5Type};
+use hdf5::{Datatype, H5Type};
 use hdf5_rt as hdf5;
 use pretty_assertions::{assert_eq, assert_str_eq};
 
@@ -40,8 +42,121 @@ pub fn test_datatype_roundtrip_primitives() {
     check_roundtrip!(VarLenUnicode, TD::VarLenUnicode);
 }
 
+#[test]
+pub fn test_datatype_roundtrip_arrays() {
+    let f32_td = || Box::new(TD::Float(FloatSize::U4));
+    check_roundtrip!([[f32; 3]; 4], TD::FixedArrayNd(f32_td(), vec![4, 3]));
+    check_roundtrip!(
+        [[[u8; 2]; 3]; 4],
+        TD::FixedArrayNd(Box::new(u8::type_descriptor()), vec![4, 3, 2])
+    );
+
+    // explicitly nested arrays are kept as arrays of arrays
+    let nested = TD::FixedArray(Box::new(TD::FixedArray(f32_td(), 3)), 4);
+    let dt = Datatype::from_descriptor(&nested).unwrap();
+    assert_eq!(dt.to_descriptor().unwrap(), nested);
+    assert_eq!(dt.size(), 48);
+
+    // arrays inside compound fields
+    for array in [nested, TD::FixedArrayNd(f32_td(), vec![4, 3])] {
+        let compound = TD::Compound(CompoundType::from_fields_aligned(vec![
+            ("id", TD::Unsigned(IntSize::U2)),
+            ("m", array.clone()),
+        ]));
+        let dt = Datatype::from_descriptor(&compound).unwrap();
+        assert_eq!(dt.to_descriptor().unwrap(), compound);
+        assert_eq!(dt.size(), 52);
+    }
+
+    assert!(Datatype::from_descriptor(&TD::FixedArrayNd(f32_td(), vec![])).is_err());
+}
+
+#[test]
+pub fn test_read_write_2d_arrays() {
+    let file = hdf5::fixtures::memory_file().unwrap();
+    let data: Vec<[[f32; 3]; 4]> = (0..5)
+        .map(|k| std::array::from_fn(|i| std::array::from_fn(|j| (k * 100 + i * 10 + j) as f32)))
+        .collect();
+    let ds = file.new_dataset_builder().with_data(&data).create("m").unwrap();
+    let desc = ds.dtype().unwrap().to_descriptor().unwrap();
+    assert_eq!(desc, TD::FixedArrayNd(Box::new(TD::Float(FloatSize::U4)), vec![4, 3]));
+    assert_eq!(ds.read_raw::<[[f32; 3]; 4]>().unwrap(), data);
+}
+
 // Note: test_datatype_roundtrip for custom enums/structs removed - requires hdf5_derive
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Marker;
+
+unsafe impl H5Type for Marker {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::unit("Marker")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Tagged {
+    id: u16,
+    marker: Marker,
+    flag: bool,
+}
+
+unsafe impl H5Type for Tagged {
+    fn type_descriptor() -> TypeDescriptor {
+        TD::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<u16>("id", mem::offset_of!(Tagged, id), 0),
+                CompoundField::typed::<Marker>("marker", mem::offset_of!(Tagged, marker), 1),
+                CompoundField::typed::<bool>("flag", mem::offset_of!(Tagged, flag), 2),
+            ],
+            size: mem::size_of::<Tagged>(),
+        })
+    }
+}
+
+struct NoFields;
+
+unsafe impl H5Type for NoFields {
+    fn type_descriptor() -> TypeDescriptor {
+        TD::Compound(CompoundType { fields: vec![], size: 0 })
+    }
+}
+
+#[test]
+pub fn test_unit_types() {
+    let file = hdf5::fixtures::memory_file().unwrap();
+    let stored = TD::Opaque(OpaqueType { size: 1, tag: "Marker".into() });
+
+    let data: Vec<_> =
+        (0..4).map(|i| Tagged { id: i * 10, marker: Marker, flag: i % 2 == 0 }).collect();
+    let ds = file.new_dataset_builder().with_data(&data).create("tagged").unwrap();
+    let TD::Compound(compound) = ds.dtype().unwrap().to_descriptor().unwrap() else {
+        panic!("expected a compound type");
+    };
+    assert_eq!(compound.fields[1].name, "marker");
+    assert_eq!(compound.fields[1].ty, stored);
+    assert_eq!(ds.read_raw::<Tagged>().unwrap(), data);
+
+    let markers = file.new_dataset::<Marker>().shape(3).create("markers").unwrap();
+    markers.write(&[Marker; 3]).unwrap();
+    assert_eq!(markers.dtype().unwrap().to_descriptor().unwrap(), stored);
+    assert_eq!(markers.read_raw::<Marker>().unwrap(), [Marker; 3]);
+    assert!(markers.read_raw::<()>().is_err());
+
+    let attr = file.new_attr::<()>().create("present").unwrap();
+    assert!(attr.space().unwrap().is_null());
+    assert_eq!(attr.read_raw::<()>().unwrap(), []);
+    let attr = file.attr("present").unwrap();
+    assert_eq!(
+        attr.dtype().unwrap().to_descriptor().unwrap().to_string(),
+        "opaque (len 1, tag ())"
+    );
+
+    let err = Datatype::from_type::<NoFields>().unwrap_err().to_string();
+    assert!(err.contains("test_datatypes::NoFields is a compound type without fields"), "{err}");
+}
+
 #[test]
 pub fn test_invalid_datatype() {
     assert_err!(from_id::<Datatype>(H5I_INVALID_HID), "Invalid handle id");
@@ -97,3 +212,53 @@ fn test_print_display_debug_datatype_fixed_len_unicode() {
     assert_str_eq!(format!("{dt:?}"), "<HDF5 datatype: unicode (len 10)>");
     assert_str_eq!(format!("{dt:#?}"), "<HDF5 datatype: unicode (len 10)>");
 }
+
+#[test]
+fn test_enum_introspection() {
+    let file = hdf5::fixtures::memory_file().unwrap();
+    let small = [("LOW", -300), ("ZERO", 0), ("HIGH", 30000)];
+    let large = [("MIN", i64::MIN), ("NEG", -1), ("MAX", i64::MAX)];
+    for (size, values) in [(IntSize::U2, &small[..]), (IntSize::U8, &large[..])] {
+        let members = values
+            .iter()
+            .map(|&(name, value)| EnumMember { name: name.into(), value: value as u64 })
+            .collect();
+        let desc = TD::Enum(EnumType { size, signed: true, members });
+        // the file datatype of a dataset is queried directly
+        let name = format!("enum{}", size as usize);
+        let ds = file.new_dataset_builder().empty_as(&desc).shape(1).create(&*name).unwrap();
+        let dt = ds.dtype().unwrap();
+
+        let expected: Vec<_> =
+            values.iter().map(|&(name, value)| (name.to_owned(), value)).collect();
+        assert_eq!(dt.enum_members().unwrap(), expected);
+        for &(name, value) in values {
+            assert_eq!(dt.enum_name_of(value).unwrap(), name);
+            assert_eq!(dt.enum_value_of(name).unwrap(), value);
+        }
+
+        // the descriptor holds the same values, truncated to the width of the base type
+        let TD::Enum(enum_type) = dt.to_descriptor().unwrap() else {
+            panic!("expected an enum descriptor");
+        };
+        let mask = u64::MAX >> (64 - 8 * size as u32);
+        let reported: Vec<_> =
+            enum_type.members.iter().map(|m| (m.name.as_str(), m.value & mask)).collect();
+        let staged: Vec<_> = expected.iter().map(|(n, v)| (n.as_str(), *v as u64 & mask)).collect();
+        assert_eq!(reported, staged);
+    }
+
+    let dt = file.dataset("enum2").unwrap().dtype().unwrap();
+    assert!(dt.enum_name_of(1).is_err());
+    assert!(dt.enum_name_of(40000).is_err());
+    assert!(dt.enum_value_of("MISSING").is_err());
+
+    // unsigned values above `i64::MAX` cannot be returned
+    let members = vec![EnumMember { name: "BIG".into(), value: u64::MAX }];
+    let desc = TD::Enum(EnumType { size: IntSize::U8, signed: false, members });
+    let dt = Datatype::from_descriptor(&desc).unwrap();
+    assert!(dt.enum_members().is_err());
+    assert!(dt.enum_value_of("BIG").is_err());
+    assert!(dt.enum_name_of(-1).is_err());
+    assert!(Datatype::from_type::<i32>().unwrap().enum_members().is_err());
+}
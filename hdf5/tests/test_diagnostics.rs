@@ -0,0 +1,55 @@
+//! Tests for handle tracking; kept in their own binary since tracking is process-wide.
+
+use hdf5::diagnostics;
+use hdf5::fixtures::memory_file;
+use hdf5::plist::file_access::FileCloseDegree;
+use hdf5_rt as hdf5;
+
+#[test]
+fn test_diagnostics() {
+    assert!(!diagnostics::is_enabled());
+    let file = memory_file().unwrap();
+    file.new_dataset::<u8>().create("untracked").unwrap();
+    assert_eq!(diagnostics::snapshot().total(), 0);
+
+    diagnostics::enable();
+    let leaked: Vec<_> = (0..5)
+        .map(|i| file.new_dataset::<i32>().shape(3).create(format!("d{i}").as_str()).unwrap())
+        .collect();
+    let snapshot = diagnostics::snapshot();
+    assert_eq!(snapshot.count("dataset"), 5);
+    assert_eq!(snapshot.count("group"), 0);
+    let names: Vec<_> = snapshot
+        .oldest
+        .iter()
+        .filter(|object| object.kind == "dataset")
+        .map(|object| object.name.as_deref().unwrap())
+        .collect();
+    assert_eq!(names, ["/d0", "/d1", "/d2", "/d3", "/d4"]);
+    assert!(snapshot.oldest[0].to_string().starts_with("dataset '/d0' (id "));
+
+    let clone = leaked[0].clone();
+    assert_eq!(diagnostics::snapshot().count("dataset"), 5);
+    assert_eq!(diagnostics::snapshot().oldest[0].handles, 2);
+    drop(clone);
+    drop(leaked);
+    let snapshot = diagnostics::snapshot();
+    assert_eq!(snapshot.count("dataset"), 0);
+    assert!(snapshot.oldest.iter().all(|object| object.kind != "dataset"));
+
+    // the error of try_close lists the oldest open objects
+    let dir = tempfile::tempdir().unwrap();
+    let file = hdf5::File::with_options()
+        .close_degree(FileCloseDegree::Semi)
+        .create(dir.path().join("semi.h5"))
+        .unwrap();
+    let group = file.create_group("open").unwrap();
+    let (file, err) = file.try_close().unwrap_err();
+    assert!(err.to_string().contains("oldest: group '/open'"), "{err}");
+    drop(group);
+    file.try_close().unwrap();
+
+    diagnostics::disable();
+    let _ds = memory_file().unwrap().new_dataset::<u8>().create("x").unwrap();
+    assert_eq!(diagnostics::snapshot().total(), 0);
+}
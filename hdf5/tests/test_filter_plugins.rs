@@ -0,0 +1,37 @@
+//! Tests for the lazy registration of the filters provided by this crate; kept in their own
+//! binary since filter registration is process-wide.
+
+use hdf5::filters::{
+    register_user_filter, registration_report, unregister_user_filter, FilterProvider, UserFilter,
+};
+use hdf5_rt as hdf5;
+
+const LZF_ID: hdf5::filters::H5Z_filter_t = 32000;
+
+#[test]
+fn test_external_filter_is_not_registered() {
+    // an external filter with the LZF id is available before this crate registers its filters
+    register_user_filter(UserFilter::new(
+        LZF_ID,
+        "external lzf",
+        |data, _| Ok(data.to_vec()),
+        |data, _| Ok(data.to_vec()),
+    ))
+    .unwrap();
+
+    let report = registration_report();
+    let names = report.iter().map(|r| r.name).collect::<Vec<_>>();
+    assert_eq!(names, ["lzf", "blosc", "zfp"]);
+    let lzf = &report[0];
+    assert_eq!(lzf.id, LZF_ID);
+    assert_eq!(lzf.provider, FilterProvider::External);
+    assert_eq!(lzf.to_string(), "lzf (32000): provided externally");
+    assert!(hdf5::filters::lzf_available());
+    for entry in &report[1..] {
+        assert_ne!(entry.provider, FilterProvider::External, "{entry}");
+    }
+
+    unregister_user_filter(LZF_ID).unwrap();
+    let lzf = &registration_report()[0];
+    assert_eq!(lzf.provider, FilterProvider::Unavailable);
+}
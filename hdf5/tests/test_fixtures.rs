@@ -0,0 +1,123 @@
+//! Tests for the fixture files and data generators of the `test-fixtures` feature.
+
+use hdf5::filters::deflate_available;
+use hdf5::fixtures::{self, FixtureRecord, FixtureSpec, FIXTURE_VERSION};
+use hdf5::types::{FixedUnicode, VarLenArray, VarLenUnicode};
+use hdf5::{Group, ObjectHandleKind, ObjectReference1, ReferencedObject};
+use hdf5_rt as hdf5;
+use ndarray::ArrayD;
+
+/// Lists the groups and datasets below `group` depth-first, datasets with their type, shape
+/// and attribute names.
+fn walk(group: &Group, lines: &mut Vec<String>) {
+    for (name, member) in group.members().unwrap() {
+        let path = format!("{}/{name}", group.name().trim_end_matches('/'));
+        match member {
+            ObjectHandleKind::Group(group) => {
+                lines.push(format!("{path}/"));
+                walk(&group, lines);
+            }
+            ObjectHandleKind::Dataset(ds) => {
+                let dtype = ds.dtype().unwrap().to_descriptor().unwrap();
+                let attrs = ds.attr_names().unwrap().join(" ");
+                lines.push(format!("{path}: {dtype} {:?} {attrs}", ds.shape()));
+            }
+            ObjectHandleKind::Datatype(_) => lines.push(format!("{path}: named datatype")),
+        }
+    }
+}
+
+#[test]
+fn test_sample_file_layout() {
+    let file = fixtures::sample_file(FixtureSpec::default()).unwrap();
+    let mut lines = vec![format!("/: {}", file.attr_names().unwrap().join(" "))];
+    walk(&file, &mut lines);
+
+    let mut expected = vec![
+        "/: fixture_version",
+        "/compound/",
+        "/compound/record_0: compound (3 fields) [8] index path",
+        "/filtered/",
+        "/filtered/deflate_0: int32 [8] index path",
+        "/filtered/shuffle_fletcher32_0: int32 [8] index path",
+        "/numeric/",
+        "/numeric/bool_0: bool [8] index path",
+        "/numeric/f32_0: float32 [8] index path",
+        "/numeric/f64_0: float64 [8] index path",
+        "/numeric/i16_0: int16 [8] index path",
+        "/numeric/i32_0: int32 [8] index path",
+        "/numeric/i64_0: int64 [8] index path",
+        "/numeric/i8_0: int8 [8] index path",
+        "/numeric/u16_0: uint16 [8] index path",
+        "/numeric/u32_0: uint32 [8] index path",
+        "/numeric/u64_0: uint64 [8] index path",
+        "/numeric/u8_0: uint8 [8] index path",
+        "/references/",
+        "/references/object_0: reference (object) [8] index path",
+        "/strings/",
+        "/strings/fixed_ascii_0: string (len 16) [8] index path",
+        "/strings/fixed_unicode_0: unicode (len 16) [8] index path",
+        "/strings/varlen_ascii_0: string (var len) [8] index path",
+        "/strings/varlen_unicode_0: unicode (var len) [8] index path",
+        "/vlen/",
+        "/vlen/array_0: [int32] (var len) [8] index path",
+    ];
+    if !deflate_available() {
+        expected.retain(|line| !line.starts_with("/filtered/deflate_0"));
+    }
+    assert_eq!(lines, expected);
+    let version = file.attr("fixture_version").unwrap().read_scalar::<u32>().unwrap();
+    assert_eq!(version, FIXTURE_VERSION);
+}
+
+#[test]
+fn test_sample_file_values() {
+    let spec = FixtureSpec {
+        datasets_per_type: 2,
+        len: 5,
+        attributes: false,
+        references: true,
+        filters: false,
+    };
+    let file = fixtures::sample_file(spec).unwrap();
+    assert!(!file.link_exists("filtered"));
+    assert_eq!(file.group("numeric").unwrap().len(), 22);
+
+    let ds = file.dataset("numeric/i64_1").unwrap();
+    assert_eq!(ds.read_raw::<i64>().unwrap(), [1, 2, 3, 4, 5]);
+    assert!(ds.attr_names().unwrap().is_empty());
+    let bools = file.dataset("numeric/bool_0").unwrap().read_raw::<bool>().unwrap();
+    assert_eq!(bools, [false, true, false, true, false]);
+
+    let strings = file.dataset("strings/fixed_unicode_1").unwrap();
+    assert_eq!(strings.read_raw::<FixedUnicode<16>>().unwrap()[3].as_str(), "ü1_3");
+    let records = file.dataset("compound/record_1").unwrap().read_raw::<FixtureRecord>().unwrap();
+    let label: VarLenUnicode = "r1_2".parse().unwrap();
+    assert_eq!(records[2], FixtureRecord { index: 2, value: 2.5, label });
+    let arrays = file.dataset("vlen/array_1").unwrap().read_raw::<VarLenArray<i32>>().unwrap();
+    assert_eq!(arrays.iter().map(|a| a.len()).collect::<Vec<_>>(), [1, 2, 3, 0, 1]);
+
+    let refs = file.dataset("references/object_1").unwrap();
+    let refs = refs.read_raw::<ObjectReference1>().unwrap();
+    match file.dereference(&refs[3]).unwrap() {
+        ReferencedObject::Dataset(ds) => assert_eq!(ds.name(), "/numeric/u64_1"),
+        _ => panic!("expected a dataset"),
+    }
+}
+
+#[test]
+fn test_generators() {
+    let first: ArrayD<i32> = fixtures::gen_arr(&mut fixtures::rng(7), 3);
+    let second: ArrayD<i32> = fixtures::gen_arr(&mut fixtures::rng(7), 3);
+    assert_eq!(first, second);
+    assert_eq!(first.ndim(), 3);
+    assert!(first.shape().iter().all(|&len| len <= 10));
+
+    let name = fixtures::gen_ascii(&mut fixtures::rng(7), 12);
+    assert_eq!(name.len(), 12);
+    assert!(name.chars().all(|c| c.is_ascii_alphanumeric()));
+    assert_ne!(
+        fixtures::memory_file().unwrap().filename(),
+        fixtures::memory_file().unwrap().filename()
+    );
+}
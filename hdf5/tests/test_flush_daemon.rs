@@ -0,0 +1,101 @@
+//! Tests for flushing a file periodically with `FlushDaemon`.
+
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use hdf5::{File, FlushDaemon, FlushHandle, OpenMode};
+use hdf5_rt as hdf5;
+
+const INTERVAL: Duration = Duration::from_millis(50);
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn wait_until(mut condition: impl FnMut() -> bool) {
+    let deadline = Instant::now() + TIMEOUT;
+    while !condition() {
+        assert!(Instant::now() < deadline, "timed out");
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+fn wait_for_flush(daemon: &FlushHandle) {
+    // flushes run and are counted with the library lock held, and counted once they have
+    // returned, so a later count means a whole flush that started after everything this
+    // thread wrote so far
+    let count = daemon.flush_count();
+    wait_until(|| daemon.flush_count() > count);
+}
+
+#[test]
+fn test_flush_daemon_swmr_writer() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("writer.h5");
+    let file = File::with_options().with_fapl(|p| p.libver_v110()).create(&path).unwrap();
+    let ds = file.new_dataset::<i64>().chunk(4).shape(0..).create("rows").unwrap();
+    file.start_swmr().unwrap();
+    let daemon = FlushDaemon::start(&file, INTERVAL).unwrap();
+    assert!(daemon.is_running());
+
+    // opening the same path again in this process would share the writer's open file instead
+    // of reading what is on disk, so the reader opens a copy taken after the flush; this is
+    // what a reader in another process sees at that point
+    let copy = dir.path().join("reader.h5");
+    for len in [4, 8, 12] {
+        ds.resize(len).unwrap();
+        ds.write_slice(&(0..len as i64).collect::<Vec<_>>(), ..).unwrap();
+        wait_for_flush(&daemon);
+        fs::copy(&path, &copy).unwrap();
+        let reader = File::open_as(&copy, OpenMode::ReadSWMR).unwrap();
+        let rows = reader.dataset("rows").unwrap().read_raw::<i64>().unwrap();
+        assert_eq!(rows, (0..len as i64).collect::<Vec<_>>());
+    }
+
+    daemon.stop().unwrap();
+}
+
+#[test]
+fn test_flush_daemon_stop() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = File::create(dir.path().join("stop.h5")).unwrap();
+    let daemon = FlushDaemon::new(INTERVAL)
+        .on_error(|err| panic!("unexpected flush error: {err}"))
+        .spawn(&file)
+        .unwrap();
+    wait_for_flush(&daemon);
+    let started = Instant::now();
+    daemon.stop().unwrap();
+    // stopping doesn't wait for the next interval
+    assert!(started.elapsed() < TIMEOUT);
+
+    // dropping the handle stops the daemon as well
+    let daemon = FlushDaemon::start(&file, Duration::from_secs(3600)).unwrap();
+    let started = Instant::now();
+    drop(daemon);
+    assert!(started.elapsed() < TIMEOUT);
+    assert_eq!(file.refcount(), 1);
+
+    assert!(FlushDaemon::start(&file, Duration::ZERO).is_err());
+}
+
+#[test]
+fn test_flush_daemon_file_dropped() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = File::create(dir.path().join("dropped.h5")).unwrap();
+    let other = file.clone();
+    let ds = file.new_dataset::<u8>().shape(4).create("data").unwrap();
+    let daemon = FlushDaemon::start(&file, INTERVAL).unwrap();
+    // the daemon doesn't hold a reference to the file
+    assert_eq!(file.refcount(), 2);
+    wait_for_flush(&daemon);
+
+    // it keeps running while any handle to the file is open
+    drop(file);
+    wait_for_flush(&daemon);
+    assert!(daemon.is_running());
+
+    // open objects don't keep it running
+    drop(other);
+    wait_until(|| !daemon.is_running());
+    daemon.stop().unwrap();
+    ds.write_raw(&[1, 2, 3, 4]).unwrap();
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "native-reader")]
+
+use std::path::Path;
+use std::str::FromStr;
+
+use ndarray::{s, Array, Array1, Array2, Array3, ArrayD};
+
+use hdf5::dataset::Layout;
+use hdf5::native::SimpleFile;
+use hdf5::H5Type;
+use hdf5_rt as hdf5;
+
+/// Fixtures are written through the HDF5 library, so these tests only run where it can be loaded.
+fn with_fixture(func: impl FnOnce(&hdf5::File, &Path) -> hdf5::Result<()>) {
+    if let Err(err) = hdf5::raw::init(None) {
+        eprintln!("skipping native reader test: {err}");
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("native.h5");
+    let file = hdf5::File::create(&path).unwrap();
+    func(&file, &path).unwrap();
+}
+
+fn check<T>(native: &SimpleFile, name: &str, expected: &ArrayD<T>)
+where
+    T: H5Type + PartialEq + std::fmt::Debug,
+{
+    let ds = native.dataset(name).unwrap();
+    assert_eq!(ds.shape(), expected.shape(), "{name}");
+    assert_eq!(ds.dtype(), &T::type_descriptor(), "{name}");
+    assert_eq!(&ds.read::<T>().unwrap(), expected, "{name}");
+}
+
+#[test]
+fn test_native_contiguous_and_compact() {
+    with_fixture(|file, path| {
+        let f64s = Array2::from_shape_fn((4, 5), |(i, j)| i as f64 * 0.5 - j as f64);
+        let i64s = Array1::from_iter(-3..7_i64);
+        let u8s = Array3::from_shape_fn((2, 3, 4), |(i, j, k)| (i * 12 + j * 4 + k) as u8);
+        let small = Array1::from_iter(0..16_u16);
+        file.new_dataset_builder().with_data(&f64s).create("f64s")?;
+        file.new_dataset_builder().with_data(&i64s).create("i64s")?;
+        file.new_dataset_builder().with_data(&u8s).create("a/b/u8s")?;
+        file.new_dataset_builder().layout(Layout::Compact).with_data(&small).create("compact")?;
+        file.new_dataset::<f32>().create("scalar")?.write_scalar(&2.5_f32)?;
+        file.new_dataset::<i32>().shape(3).create("unwritten")?;
+        file.flush()?;
+
+        let native = SimpleFile::open(path)?;
+        let mut names = native.member_names()?;
+        names.sort();
+        assert_eq!(names, ["a", "compact", "f64s", "i64s", "scalar", "unwritten"]);
+        check(&native, "f64s", &f64s.into_dyn());
+        check(&native, "i64s", &i64s.into_dyn());
+        check(&native, "/a/b/u8s", &u8s.into_dyn());
+        check(&native, "compact", &small.into_dyn());
+        check(&native, "scalar", &Array::from_elem(vec![], 2.5_f32));
+        check(&native, "unwritten", &Array::<i32, _>::zeros(vec![3]));
+        Ok(())
+    })
+}
+
+#[test]
+fn test_native_chunked() {
+    with_fixture(|file, path| {
+        let arr = Array2::from_shape_fn((10, 7), |(i, j)| (i * 7 + j) as i32 - 20);
+        file.new_dataset_builder().chunk((3, 4)).with_data(&arr).create("chunked")?;
+
+        // only the first chunk is written, the rest must come back as the fill value
+        let sparse = file.new_dataset::<u32>().chunk((2, 2)).fill_value(7_u32).shape((4, 4));
+        let sparse = sparse.create("sparse")?;
+        sparse.write_slice(&Array2::from_elem((2, 2), 1_u32), s![0..2, 0..2])?;
+        let mut expected = Array2::from_elem((4, 4), 7_u32);
+        expected.slice_mut(s![0..2, 0..2]).fill(1);
+        file.flush()?;
+
+        let native = SimpleFile::open(path)?;
+        check(&native, "chunked", &arr.into_dyn());
+        check(&native, "sparse", &expected.into_dyn());
+        Ok(())
+    })
+}
+
+#[test]
+fn test_native_refuses_unsupported() {
+    with_fixture(|file, path| {
+        if hdf5::filters::deflate_available() {
+            let arr = Array1::from_iter(0..100_i32);
+            file.new_dataset_builder().deflate(4).with_data(&arr).create("deflated")?;
+        }
+        let strings = [hdf5::types::VarLenUnicode::from_str("x").unwrap()];
+        file.new_dataset_builder().with_data(&strings).create("strings")?;
+        file.flush()?;
+
+        let native = SimpleFile::open(path)?;
+        if hdf5::filters::deflate_available() {
+            let err = native.dataset("deflated").unwrap_err().to_string();
+            assert!(err.contains("datasets with filters are not supported"), "{err}");
+        }
+        let err = native.dataset("strings").unwrap_err().to_string();
+        assert!(err.contains("variable-length datatypes are not supported"), "{err}");
+        let err = native.dataset("i64s").unwrap_err().to_string();
+        assert!(err.contains("object 'i64s' doesn't exist"), "{err}");
+        Ok(())
+    })
+}
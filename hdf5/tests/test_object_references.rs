@@ -1,14 +1,12 @@
 //! Tests for the reference type storage and retrieval.
 //!
 
-mod common;
-
-use common::util::new_in_memory_file;
-use hdf5::{ObjectReference, ObjectReference1, ReferencedObject};
+use hdf5::fixtures::memory_file;
+use hdf5::{ErrorKind, ObjectReference, ObjectReference1, ReferencedObject};
 use hdf5_rt as hdf5;
 
 fn test_group_references<R: ObjectReference>() {
-    let file = new_in_memory_file().unwrap();
+    let file = memory_file().unwrap();
     let g1 = file.create_group("g1").unwrap();
     let _g1_1 = g1.create_group("g1_1").unwrap();
 
@@ -49,7 +47,7 @@ fn test_group_references<R: ObjectReference>() {
 fn test_dataset_references<R: ObjectReference>() {
     let dummy_data = [0, 1, 2, 3];
 
-    let file = new_in_memory_file().unwrap();
+    let file = memory_file().unwrap();
     let _ds1 = file.new_dataset_builder().with_data(&dummy_data).create("ds1").unwrap();
     let g = file.create_group("g").unwrap();
     let _ds2 = g.new_dataset_builder().with_data(&dummy_data).create("ds2").unwrap();
@@ -80,7 +78,7 @@ fn test_dataset_references<R: ObjectReference>() {
 }
 
 fn test_reference_in_attribute<R: ObjectReference>() {
-    let file = new_in_memory_file().unwrap();
+    let file = memory_file().unwrap();
     let _ds1 = file.new_dataset_builder().with_data(&[1, 2, 3]).create("ds1").unwrap();
     let ref1: R = file.reference("ds1").unwrap();
 
@@ -99,14 +97,61 @@ fn test_reference_in_attribute<R: ObjectReference>() {
     }
 }
 
+fn test_reference_array_in_attribute<R: ObjectReference>() {
+    let file = memory_file().unwrap();
+    let g = file.create_group("g").unwrap();
+    file.new_dataset_builder().with_data(&[1, 2, 3]).create("ds").unwrap();
+    g.new_dataset_builder().with_data(&[4.0, 5.0]).create("nested").unwrap();
+    g.create_group("sub").unwrap();
+    let names = ["/ds", "/g", "/g/nested", "/g/sub"];
+    let refs: [R; 4] = names.map(|name| file.reference(name).unwrap());
+
+    let attr = file.new_attr_builder().with_data(&refs).create("refs").unwrap();
+    assert_eq!(attr.shape().unwrap(), [4]);
+    assert_eq!(attr.dtype().unwrap().to_descriptor().unwrap(), R::type_descriptor());
+    drop(refs);
+
+    let read = file.attr("refs").unwrap().read_1d::<R>().unwrap();
+    assert_eq!(read.len(), 4);
+    for (reference, name) in read.iter().zip(names) {
+        let object_name = match file.dereference(reference).unwrap() {
+            ReferencedObject::Group(g) => g.name(),
+            ReferencedObject::Dataset(ds) => ds.name(),
+            ReferencedObject::Datatype(_) => panic!("Expected a group or dataset reference"),
+        };
+        assert_eq!(object_name, name);
+    }
+    match g.dereference(&read[2]).unwrap() {
+        ReferencedObject::Dataset(ds) => assert_eq!(ds.read_raw::<f64>().unwrap(), [4.0, 5.0]),
+        _ => panic!("Expected a dataset reference"),
+    }
+    assert_eq!(file.attr("refs").unwrap().read_raw::<R>().unwrap().len(), 4);
+}
+
 fn test_reference_errors_on_attribute<R: ObjectReference>() {
-    let file = new_in_memory_file().unwrap();
+    let file = memory_file().unwrap();
     let _attr = file.new_attr::<i32>().create("ref_attr").unwrap();
     // Attempt to create reference to attribute should fail.
     let result = file.reference::<R>("ref_attr");
     assert!(result.is_err());
 }
 
+fn test_null_references<R: ObjectReference>(null: fn() -> R) {
+    let file = memory_file().unwrap();
+    file.create_group("g").unwrap();
+    let refs: [R; 3] = [null(), file.reference("g").unwrap(), null()];
+    let ds = file.new_dataset_builder().with_data(&refs).create("refs").unwrap();
+    let read = ds.read_1d::<R>().unwrap();
+    assert_eq!(read.iter().map(R::is_null).collect::<Vec<_>>(), [true, false, true]);
+    assert!(matches!(file.dereference(&read[1]).unwrap(), ReferencedObject::Group(_)));
+    let err = file.dereference(&read[0]).unwrap_err();
+    assert!(err.to_string().contains("cannot dereference a null object reference"), "{err}");
+
+    // elements that were never written read back as null
+    let ds = file.new_dataset::<R>().shape(2).create("unwritten").unwrap();
+    assert!(ds.read_1d::<R>().unwrap().iter().all(R::is_null));
+}
+
 // Note: test_reference_in_datatype removed - requires hdf5_derive for custom struct
 
 #[test]
@@ -123,18 +168,42 @@ fn test_reference_in_attribute_object_reference1() {
     test_reference_in_attribute::<ObjectReference1>();
 }
 
+#[test]
+fn test_reference_array_in_attribute_object_reference1() {
+    test_reference_array_in_attribute::<ObjectReference1>();
+}
+
 #[test]
 fn test_reference_errors_on_attribute_object_reference1() {
     test_reference_errors_on_attribute::<ObjectReference1>();
 }
 
-// ObjectReference2 tests - requires HDF5 1.12.0+
+#[test]
+fn test_null_references_object_reference1() {
+    test_null_references(ObjectReference1::null);
+}
+
+#[test]
+fn test_raw_bytes_object_reference1() {
+    let file = memory_file().unwrap();
+    file.create_group("g").unwrap();
+    let gref = file.reference::<ObjectReference1>("g").unwrap();
+    let bytes = gref.to_raw_bytes();
+    assert_ne!(bytes, [0; 8]);
+    let copy = ObjectReference1::from_raw_bytes(bytes);
+    assert!(!copy.is_null());
+    assert!(matches!(file.dereference(&copy).unwrap(), ReferencedObject::Group(_)));
+    assert!(ObjectReference1::from_raw_bytes([0; 8]).is_null());
+    assert_eq!(ObjectReference1::null().to_raw_bytes(), [0; 8]);
+}
+
+// ObjectReference2 tests - requires HDF5 1.12.1+
 // These tests skip automatically on older HDF5 versions
 
 #[test]
 fn test_group_references_with_objectreference2() {
-    if !hdf5::sys::hdf5_version_at_least(1, 12, 0) {
-        eprintln!("Skipping test: requires HDF5 >= 1.12.0");
+    if !hdf5::raw::hdf5_version_at_least(1, 12, 1) {
+        eprintln!("Skipping test: requires HDF5 >= 1.12.1");
         return;
     }
     test_group_references::<hdf5::ObjectReference2>();
@@ -142,8 +211,8 @@ fn test_group_references_with_objectreference2() {
 
 #[test]
 fn test_dataset_references_with_object_reference2() {
-    if !hdf5::sys::hdf5_version_at_least(1, 12, 0) {
-        eprintln!("Skipping test: requires HDF5 >= 1.12.0");
+    if !hdf5::raw::hdf5_version_at_least(1, 12, 1) {
+        eprintln!("Skipping test: requires HDF5 >= 1.12.1");
         return;
     }
     test_dataset_references::<hdf5::ObjectReference2>();
@@ -151,18 +220,86 @@ fn test_dataset_references_with_object_reference2() {
 
 #[test]
 fn test_reference_in_attribute_object_reference2() {
-    if !hdf5::sys::hdf5_version_at_least(1, 12, 0) {
-        eprintln!("Skipping test: requires HDF5 >= 1.12.0");
+    if !hdf5::raw::hdf5_version_at_least(1, 12, 1) {
+        eprintln!("Skipping test: requires HDF5 >= 1.12.1");
         return;
     }
     test_reference_in_attribute::<hdf5::ObjectReference2>();
 }
 
+#[test]
+fn test_reference_array_in_attribute_object_reference2() {
+    if !hdf5::raw::hdf5_version_at_least(1, 12, 1) {
+        // the standard reference datatype can't be created at all
+        let file = memory_file().unwrap();
+        let err = file.new_attr::<hdf5::ObjectReference2>().shape(4).create("refs").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::VersionUnsupported, "{err}");
+        assert!(err.to_string().contains("requires HDF5 1.12.1 or later"), "{err}");
+        return;
+    }
+    test_reference_array_in_attribute::<hdf5::ObjectReference2>();
+}
+
 #[test]
 fn test_reference_errors_on_attribute_object_reference2() {
-    if !hdf5::sys::hdf5_version_at_least(1, 12, 0) {
-        eprintln!("Skipping test: requires HDF5 >= 1.12.0");
+    if !hdf5::raw::hdf5_version_at_least(1, 12, 1) {
+        eprintln!("Skipping test: requires HDF5 >= 1.12.1");
         return;
     }
     test_reference_errors_on_attribute::<hdf5::ObjectReference2>();
 }
+
+#[test]
+fn test_null_references_object_reference2() {
+    if !hdf5::raw::hdf5_version_at_least(1, 12, 1) {
+        eprintln!("Skipping test: requires HDF5 >= 1.12.1");
+        return;
+    }
+    test_null_references(hdf5::ObjectReference2::null);
+    let file = memory_file().unwrap();
+    file.create_group("g").unwrap();
+    let gref = file.reference::<hdf5::ObjectReference2>("g").unwrap();
+    assert!(!gref.is_null());
+    assert_ne!(gref.as_bytes(), &[0; 64]);
+    assert_eq!(hdf5::ObjectReference2::null().as_bytes(), &[0; 64]);
+}
+
+#[test]
+#[cfg(feature = "test-hooks")]
+fn test_object_reference2_on_old_library() {
+    use hdf5::sys::{with_reported_version, Version};
+    use hdf5::types::TypeDescriptor;
+    use hdf5::Datatype;
+
+    if !hdf5::raw::hdf5_version_at_least(1, 12, 1) {
+        eprintln!("Skipping test: requires HDF5 >= 1.12.1");
+        return;
+    }
+    let file = memory_file().unwrap();
+    file.create_group("g").unwrap();
+    let gref = file.reference::<hdf5::ObjectReference2>("g").unwrap();
+    let refs = [file.reference::<hdf5::ObjectReference2>("g").unwrap()];
+    let ds = file.new_dataset_builder().with_data(&refs).create("refs").unwrap();
+
+    for old in
+        [Version { major: 1, minor: 10, micro: 11 }, Version { major: 1, minor: 12, micro: 0 }]
+    {
+        with_reported_version(old, || {
+            let assert_unsupported = |err: hdf5::Error| {
+                assert_eq!(err.kind(), ErrorKind::VersionUnsupported, "{err}");
+                let msg = err.to_string();
+                assert!(msg.contains("requires HDF5 1.12.1 or later"), "{msg}");
+                assert!(msg.contains(&format!("loaded version is {old}")), "{msg}");
+            };
+            assert_unsupported(file.reference::<hdf5::ObjectReference2>("g").unwrap_err());
+            assert_unsupported(file.dereference(&gref).unwrap_err());
+            assert_unsupported(ds.dtype().unwrap().to_descriptor().unwrap_err());
+            let desc = TypeDescriptor::Reference(hdf5::types::Reference::Std);
+            assert_unsupported(Datatype::from_descriptor(&desc).unwrap_err());
+            let attr = file.new_attr::<hdf5::ObjectReference2>().create("ref");
+            assert_unsupported(attr.unwrap_err());
+        });
+    }
+    // the reported version is restored afterwards
+    assert!(matches!(file.dereference(&gref).unwrap(), ReferencedObject::Group(_)));
+}
@@ -0,0 +1,119 @@
+//! Tests for writing datasets with the filter pipeline run on a thread pool.
+#![cfg(feature = "parallel-write")]
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use hdf5::filters::{szip_available, SZip};
+use hdf5::fixtures::memory_file;
+use hdf5::parallel_write::ParallelChunkWriter;
+use hdf5::types::VarLenUnicode;
+use hdf5::{Dataset, Group};
+use hdf5_rt as hdf5;
+use ndarray::{Array1, Array3};
+
+// edge chunks are partial along every axis
+const SHAPE: (usize, usize, usize) = (37, 21, 50);
+const CHUNK: (usize, usize, usize) = (8, 8, 16);
+
+fn sample() -> Array3<f64> {
+    Array3::from_shape_fn(SHAPE, |(i, j, k)| ((i * 31 + j * 7 + k) % 97) as f64 * 0.25 - 3.0)
+}
+
+fn checksum(ds: &Dataset) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for value in ds.read_raw::<f64>().unwrap() {
+        value.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn create(group: &Group, name: &str) -> Dataset {
+    group.new_dataset::<f64>().shape(SHAPE).chunk(CHUNK).shuffle().deflate(6).create(name).unwrap()
+}
+
+#[test]
+fn test_parallel_write_deflate_shuffle() {
+    let file = memory_file().unwrap();
+    let data = sample();
+
+    let serial = create(&file, "serial");
+    serial.write(&data).unwrap();
+    let parallel = create(&file, "parallel");
+    let writer = ParallelChunkWriter::new(&parallel, 4).unwrap();
+    assert_eq!(writer.fallback_reason(), None);
+    writer.write(&data).unwrap();
+
+    // read back through the library's pipeline
+    assert_eq!(parallel.read::<f64, ndarray::Ix3>().unwrap(), data);
+    assert_eq!(checksum(&parallel), checksum(&serial));
+    assert_eq!(parallel.num_chunks(), Some(5 * 3 * 4));
+    for index in 0..parallel.num_chunks().unwrap() {
+        let info = parallel.chunk_info(index).unwrap();
+        assert_eq!(info.filter_mask, 0);
+        assert!(info.size < (8 * 8 * 16 * 8) as u64);
+    }
+
+    // writing again replaces every chunk, also from a non-standard layout
+    let transposed = data.t().to_owned();
+    let reversed = transposed.t().into_dyn();
+    assert!(!reversed.is_standard_layout());
+    writer.write(&(&reversed * 2.0)).unwrap();
+    assert_eq!(parallel.read::<f64, ndarray::Ix3>().unwrap(), &data * 2.0);
+
+    assert!(writer.write(&Array3::<f64>::zeros((1, 2, 3))).is_err());
+}
+
+#[test]
+fn test_parallel_write_fallback() {
+    let file = memory_file().unwrap();
+    let data = sample();
+
+    // a conversion is needed, so the library writes the data
+    let ds = create(&file, "converted");
+    let writer = ParallelChunkWriter::new(&ds, 2).unwrap();
+    writer.write(&data.mapv(|v| v as f32)).unwrap();
+    assert_eq!(ds.read::<f32, ndarray::Ix3>().unwrap(), data.mapv(|v| v as f32));
+
+    let contiguous = file.new_dataset::<f64>().shape(SHAPE).create("contiguous").unwrap();
+    assert!(ParallelChunkWriter::new(&contiguous, 2).is_err());
+    assert!(ParallelChunkWriter::new(&ds, 0).is_err());
+
+    if !szip_available() {
+        eprintln!("Skipping the szip fallback: szip is not available");
+        return;
+    }
+    let ds = file
+        .new_dataset::<f64>()
+        .shape(SHAPE)
+        .chunk(CHUNK)
+        .szip(SZip::NearestNeighbor, 8)
+        .create("szip")
+        .unwrap();
+    let writer = ParallelChunkWriter::new(&ds, 2).unwrap();
+    let reason = writer.fallback_reason().unwrap();
+    assert!(reason.contains("szip"), "{reason}");
+    writer.write(&data).unwrap();
+    assert_eq!(ds.read::<f64, ndarray::Ix3>().unwrap(), data);
+}
+
+#[test]
+fn test_parallel_write_varlen_fallback() {
+    let file = memory_file().unwrap();
+    let data: Array1<VarLenUnicode> =
+        (0..50).map(|i| format!("value {i}").parse().unwrap()).collect();
+    let ds = file
+        .new_dataset::<VarLenUnicode>()
+        .shape(data.len())
+        .chunk(16)
+        .deflate(4)
+        .create("strings")
+        .unwrap();
+
+    // the elements are pointers in memory, so the library has to write them
+    let writer = ParallelChunkWriter::new(&ds, 2).unwrap();
+    let reason = writer.fallback_reason().unwrap();
+    assert!(reason.contains("converted by the library"), "{reason}");
+    writer.write(&data).unwrap();
+    assert_eq!(ds.read_1d::<VarLenUnicode>().unwrap(), data);
+}
@@ -1,4 +1,5 @@
 use std::mem;
+use std::path::Path;
 
 use hdf5::dataset::*;
 use hdf5::file::*;
@@ -11,8 +12,7 @@ macro_rules! test_pl {
     );
 
     ($ty:ident, $field:ident ($($arg:expr,)+): $($name:ident=$value:expr,)+) => ({
-        let mut b = $ty::build();
-        b.$field($($arg,)+);
+        let b = $ty::build().$field($($arg,)+);
         let fapl = b.finish()?;
         $(assert_eq!(fapl.$field().$name, $value);)+
         paste::paste! { $(assert_eq!(fapl.[<get_ $field>]()?.$name, $value);)+ }
@@ -27,8 +27,7 @@ macro_rules! test_pl {
     });
 
     ($ty:ident, $field:ident ($arg:expr): $value:expr) => ({
-        let mut b = $ty::build();
-        b.$field($arg);
+        let b = $ty::build().$field($arg);
         let fapl = b.finish()?;
         assert_eq!(fapl.$field(), $value);
         paste::paste! { assert_eq!(fapl.[<get_ $field>]()?, $value); }
@@ -47,8 +46,7 @@ macro_rules! test_pl_common {
 
         assert!(format!("{:?}", pl_default).starts_with(&format!("{:?}", $plc)));
 
-        let mut b = $cls::build();
-        let pl = $func(&mut b)?;
+        let pl = $func($cls::build())?;
         assert_eq!(pl.class()?, $plc);
         assert_eq!(pl, pl);
         assert_ne!(pl, pl_default);
@@ -74,7 +72,7 @@ type FCB = FileCreateBuilder;
 
 #[test]
 fn test_fcpl_common() -> hdf5::Result<()> {
-    test_pl_common!(FC, PropertyListClass::FileCreate, |b: &mut FCB| b.userblock(2048).finish());
+    test_pl_common!(FC, PropertyListClass::FileCreate, |b: FCB| b.userblock(2048).finish());
     Ok(())
 }
 
@@ -159,6 +157,16 @@ fn test_fcpl_attr_creation_order() -> hdf5::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_fcpl_link_creation_order() -> hdf5::Result<()> {
+    assert_eq!(FC::try_new()?.get_link_creation_order()?.bits(), 0);
+    assert_eq!(FC::try_new()?.link_creation_order().bits(), 0);
+    test_pl!(FC, link_creation_order: LinkCreationOrder::TRACKED);
+    test_pl!(FC, link_creation_order: LinkCreationOrder::TRACKED | LinkCreationOrder::INDEXED);
+    assert!(FCB::new().link_creation_order(LinkCreationOrder::INDEXED).finish().is_err());
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "1.10.1")]
 fn test_fcpl_set_file_space_page_size() -> hdf5::Result<()> {
@@ -182,14 +190,14 @@ type FAB = FileAccessBuilder;
 
 #[test]
 fn test_fapl_common() -> hdf5::Result<()> {
-    test_pl_common!(FA, PropertyListClass::FileAccess, |b: &mut FAB| b.sieve_buf_size(8).finish());
+    test_pl_common!(FA, PropertyListClass::FileAccess, |b: FAB| b.sieve_buf_size(8).finish());
     Ok(())
 }
 
 #[test]
 fn test_fapl_driver_sec2() -> hdf5::Result<()> {
     let mut b = FileAccess::build();
-    b.sec2();
+    b = b.sec2();
     check_matches!(b.finish()?.get_driver()?, (), FileDriver::Sec2);
     Ok(())
 }
@@ -197,7 +205,7 @@ fn test_fapl_driver_sec2() -> hdf5::Result<()> {
 #[test]
 fn test_fapl_driver_stdio() -> hdf5::Result<()> {
     let mut b = FileAccess::build();
-    b.stdio();
+    b = b.stdio();
     check_matches!(b.finish()?.get_driver()?, (), FileDriver::Stdio);
     Ok(())
 }
@@ -206,11 +214,15 @@ fn test_fapl_driver_stdio() -> hdf5::Result<()> {
 fn test_fapl_driver_log() -> hdf5::Result<()> {
     let mut b = FileAccess::build();
 
-    b.log();
-    check_matches!(b.finish()?.get_driver()?, (), FileDriver::Log);
+    b = b.log();
+    let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Log(d));
+    assert_eq!(d, LogDriver::default());
 
-    b.log_options(Some("abc"), LogFlags::TRUNCATE, 123);
-    check_matches!(b.finish()?.get_driver()?, (), FileDriver::Log);
+    b = b.log_options(Some("abc"), LogFlags::TRUNCATE, 123);
+    let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Log(d));
+    assert_eq!(d.path.as_deref(), Some(Path::new("abc")));
+    assert_eq!(d.flags, LogFlags::TRUNCATE);
+    assert_eq!(d.buf_size, 123);
 
     Ok(())
 }
@@ -219,31 +231,92 @@ fn test_fapl_driver_log() -> hdf5::Result<()> {
 fn test_fapl_driver_core() -> hdf5::Result<()> {
     let mut b = FileAccess::build();
 
-    b.core();
+    b = b.core();
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Core(d));
     assert_eq!(d.increment, 1024 * 1024);
     assert_eq!(d.filebacked, false);
     #[cfg(feature = "1.8.13")]
     assert_eq!(d.write_tracking, 0);
 
-    b.core_options(123, true);
+    b = b.core_options(123, true);
     #[cfg(feature = "1.8.13")]
-    b.write_tracking(456);
+    {
+        b = b.write_tracking(456);
+    }
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Core(d));
     assert_eq!(d.increment, 123);
     assert_eq!(d.filebacked, true);
     #[cfg(feature = "1.8.13")]
     assert_eq!(d.write_tracking, 456);
 
-    b.core_filebacked(false);
+    assert!(FileAccess::build().core_options(0, false).finish().is_err());
+
+    b = b.core_filebacked(false);
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Core(d));
     assert_eq!(d.increment, CoreDriver::default().increment);
     assert_eq!(d.filebacked, false);
 
-    b.core_filebacked(true);
+    b = b.core_filebacked(true);
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Core(d));
     assert_eq!(d.increment, CoreDriver::default().increment);
     assert_eq!(d.filebacked, true);
+    assert_eq!(b.finish()?.get_file_image()?, None);
+
+    b = b.core_image(b"\x89HDF");
+    let fapl = b.finish()?;
+    let d = check_matches!(fapl.get_driver()?, d, FileDriver::Core(d));
+    assert!(!d.filebacked);
+    assert_eq!(fapl.get_file_image()?.as_deref(), Some(&b"\x89HDF"[..]));
+    assert_eq!(FAB::from_plist(&fapl)?.finish()?.file_image().as_deref(), Some(&b"\x89HDF"[..]));
+
+    Ok(())
+}
+
+#[test]
+fn test_fapl_driver_round_trip() -> hdf5::Result<()> {
+    let image = {
+        let file = hdf5::File::create_in_memory("round trip")?;
+        file.new_dataset_builder().with_data(&[1, 2, 3]).create("x")?;
+        file.to_image()?
+    };
+    let core_image = CoreDriver { initial_image: Some(image), ..CoreDriver::default() };
+    let drivers = [
+        FileDriver::Sec2,
+        FileDriver::Stdio,
+        FileDriver::Log(LogDriver::default()),
+        FileDriver::Log(LogDriver {
+            path: Some("abc.log".into()),
+            flags: LogFlags::TRUNCATE | LogFlags::NUM_IO,
+            buf_size: 123,
+        }),
+        FileDriver::Core(CoreDriver::default()),
+        FileDriver::Core(CoreDriver { increment: 4096, filebacked: true, ..core_image.clone() }),
+        FileDriver::Core(core_image.clone()),
+        FileDriver::Family(FamilyDriver { member_size: 1 << 20 }),
+        FileDriver::Multi(MultiDriver::default()),
+        FileDriver::Split(SplitDriver { meta_ext: "-m.h5".into(), raw_ext: "-r.h5".into() }),
+    ];
+    for drv in &drivers {
+        let fapl = FileAccess::build().driver(drv).finish()?;
+        for read in [fapl.get_driver()?, FAB::from_plist(&fapl)?.finish()?.get_driver()?] {
+            match (drv, read) {
+                (FileDriver::Core(drv), FileDriver::Core(read)) => assert_eq!(&read, drv),
+                (FileDriver::Log(drv), FileDriver::Log(read)) => assert_eq!(&read, drv),
+                (FileDriver::Family(drv), FileDriver::Family(read)) => assert_eq!(&read, drv),
+                (FileDriver::Multi(drv), FileDriver::Multi(read)) => assert_eq!(&read, drv),
+                (FileDriver::Split(drv), FileDriver::Split(read)) => assert_eq!(&read, drv),
+                // see test_fapl_driver_split
+                (FileDriver::Split(_), FileDriver::Multi(_)) => {}
+                (drv, read) => assert_eq!(read.name(), drv.name()),
+            }
+        }
+    }
+
+    // the initial image of the Core driver is the contents of the opened file
+    let file = hdf5::File::with_options()
+        .with_fapl(|p| p.driver(&FileDriver::Core(core_image)))
+        .open("round trip image")?;
+    assert_eq!(file.dataset("x")?.read_raw::<i32>()?, [1, 2, 3]);
 
     Ok(())
 }
@@ -252,11 +325,11 @@ fn test_fapl_driver_core() -> hdf5::Result<()> {
 fn test_fapl_driver_family() -> hdf5::Result<()> {
     let mut b = FileAccess::build();
 
-    b.family();
+    b = b.family();
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Family(d));
     assert_eq!(d.member_size, 0);
 
-    b.family_options(123);
+    b = b.family_options(123);
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Family(d));
     assert_eq!(d.member_size, 123);
 
@@ -267,7 +340,7 @@ fn test_fapl_driver_family() -> hdf5::Result<()> {
 fn test_fapl_driver_multi() -> hdf5::Result<()> {
     let mut b = FileAccess::build();
 
-    b.multi();
+    b = b.multi();
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Multi(d));
     assert_eq!(d, MultiDriver::default());
 
@@ -285,7 +358,7 @@ fn test_fapl_driver_multi() -> hdf5::Result<()> {
         mem_lheap: 3,
         mem_object: 2,
     };
-    b.multi_options(&files, &layout, true);
+    b = b.multi_options(&files, &layout, true);
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Multi(d));
     assert_eq!(d.files, files);
     assert_eq!(d.layout, layout);
@@ -299,14 +372,14 @@ fn test_fapl_driver_multi() -> hdf5::Result<()> {
 fn test_fapl_driver_split() -> hdf5::Result<()> {
     let mut b = FileAccess::build();
 
-    b.split();
+    b = b.split();
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Split(d));
     assert_eq!(d, SplitDriver::default());
 
-    b.split_options(".foo", ".bar");
+    b = b.split_options(".foo", ".bar");
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Split(d));
-    assert_eq!(&d.meta_ext, ".foo");
-    assert_eq!(&d.raw_ext, ".bar");
+    assert_eq!(d.meta_ext, Path::new(".foo"));
+    assert_eq!(d.raw_ext, Path::new(".bar"));
 
     Ok(())
 }
@@ -327,7 +400,7 @@ fn test_fapl_driver_mpio() -> hdf5::Result<()> {
     let world_comm = unsafe { RSMPI_COMM_WORLD };
 
     let mut b = FileAccess::build();
-    b.mpio(world_comm, None);
+    b = b.mpio(world_comm, None);
 
     let driver = b.finish()?.get_driver()?;
     println!("{:?}", driver);
@@ -344,11 +417,11 @@ fn test_fapl_driver_mpio() -> hdf5::Result<()> {
 fn test_fapl_driver_direct() -> hdf5::Result<()> {
     let mut b = FileAccess::build();
 
-    b.direct();
+    b = b.direct();
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Direct(d));
     assert_eq!(d, DirectDriver::default());
 
-    b.direct_options(100, 200, 400);
+    b = b.direct_options(100, 200, 400);
     let d = check_matches!(b.finish()?.get_driver()?, d, FileDriver::Direct(d));
     assert_eq!(d.alignment, 100);
     assert_eq!(d.block_size, 200);
@@ -523,7 +596,6 @@ fn test_fapl_set_coll_metadata_write() -> hdf5::Result<()> {
 }
 
 #[test]
-#[cfg(feature = "1.10.2")]
 fn test_fapl_set_libver_bounds() -> hdf5::Result<()> {
     test_pl!(FA, libver_bounds: low = LibraryVersion::Earliest, high = LibraryVersion::V18);
     test_pl!(FA, libver_bounds: low = LibraryVersion::Earliest, high = LibraryVersion::V110);
@@ -532,16 +604,16 @@ fn test_fapl_set_libver_bounds() -> hdf5::Result<()> {
     test_pl!(FA, libver_bounds: low = LibraryVersion::V110, high = LibraryVersion::V110);
     let make_lvb = |lv| LibVerBounds { low: lv, high: LibraryVersion::latest() };
     let mut b = FAB::new();
-    b.libver_earliest();
+    b = b.libver_earliest();
     assert_eq!(b.finish()?.libver_bounds(), make_lvb(LibraryVersion::Earliest));
     assert_eq!(b.finish()?.libver(), LibraryVersion::Earliest);
-    b.libver_v18();
+    b = b.libver_v18();
     assert_eq!(b.finish()?.libver_bounds(), make_lvb(LibraryVersion::V18));
     assert_eq!(b.finish()?.libver(), LibraryVersion::V18);
-    b.libver_v110();
+    b = b.libver_v110();
     assert_eq!(b.finish()?.libver_bounds(), make_lvb(LibraryVersion::V110));
     assert_eq!(b.finish()?.libver(), LibraryVersion::V110);
-    b.libver_latest();
+    b = b.libver_latest();
     assert_eq!(b.finish()?.libver_bounds(), make_lvb(LibraryVersion::latest()));
     assert_eq!(b.finish()?.libver(), LibraryVersion::latest());
     Ok(())
@@ -577,7 +649,7 @@ type DAB = DatasetAccessBuilder;
 
 #[test]
 fn test_dapl_common() -> hdf5::Result<()> {
-    test_pl_common!(DA, PropertyListClass::DatasetAccess, |b: &mut DAB| b
+    test_pl_common!(DA, PropertyListClass::DatasetAccess, |b: DAB| b
         .chunk_cache(100, 200, 0.5)
         .finish());
     Ok(())
@@ -589,7 +661,7 @@ fn test_dapl_set_efile_prefix() -> hdf5::Result<()> {
     assert_eq!(DA::try_new()?.get_efile_prefix().unwrap(), "".to_owned());
     assert_eq!(DA::try_new()?.efile_prefix(), "".to_owned());
     let mut b = DA::build();
-    b.efile_prefix("foo");
+    b = b.efile_prefix("foo");
     assert_eq!(b.finish()?.get_efile_prefix()?, "foo".to_owned());
     Ok(())
 }
@@ -626,12 +698,62 @@ fn test_dapl_set_virtual_printf_gap() -> hdf5::Result<()> {
     Ok(())
 }
 
+type DX = DatasetTransfer;
+type DxBuilder = DatasetTransferBuilder;
+
+#[test]
+fn test_dxpl_common() -> hdf5::Result<()> {
+    test_pl_common!(DX, PropertyListClass::DataTransfer, |b: DxBuilder| b
+        .buffer_size(1 << 24)
+        .finish());
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_set_buffer_size() -> hdf5::Result<()> {
+    assert_eq!(DX::try_new()?.get_buffer_size()?, DEFAULT_BUFFER_SIZE);
+    assert_eq!(DX::try_new()?.buffer_size(), DEFAULT_BUFFER_SIZE);
+    test_pl!(DX, buffer_size: 1 << 24);
+    test_pl!(DX, buffer_size: 4096);
+    let pl = DxBuilder::new().buffer_size(12345).finish()?;
+    assert_eq!(DxBuilder::from_plist(&pl)?.finish()?.get_buffer_size()?, 12345);
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_set_edc_check() -> hdf5::Result<()> {
+    assert!(DX::try_new()?.get_edc_check()?);
+    test_pl!(DX, edc_check: false);
+    test_pl!(DX, edc_check: true);
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_set_hyper_vector_size() -> hdf5::Result<()> {
+    assert_eq!(DX::try_new()?.get_hyper_vector_size()?, DEFAULT_HYPER_VECTOR_SIZE);
+    test_pl!(DX, hyper_vector_size: 1);
+    test_pl!(DX, hyper_vector_size: 4096);
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_set_on_conversion_exception() -> hdf5::Result<()> {
+    use ConversionExceptionPolicy::{Abort, Count, Default};
+    assert_eq!(DX::try_new()?.get_on_conversion_exception()?, Default);
+    test_pl!(DX, on_conversion_exception: Abort);
+    test_pl!(DX, on_conversion_exception: Count);
+    test_pl!(DX, on_conversion_exception: Default);
+    let pl = DxBuilder::new().on_conversion_exception(Abort).finish()?;
+    assert_eq!(DxBuilder::from_plist(&pl)?.finish()?.on_conversion_exception(), Abort);
+    Ok(())
+}
+
 type DC = DatasetCreate;
 type DCB = DatasetCreateBuilder;
 
 #[test]
 fn test_dcpl_common() -> hdf5::Result<()> {
-    test_pl_common!(DC, PropertyListClass::DatasetCreate, |b: &mut DCB| b
+    test_pl_common!(DC, PropertyListClass::DatasetCreate, |b: DCB| b
         .layout(Layout::Compact)
         .finish());
     Ok(())
@@ -642,11 +764,11 @@ fn test_dcpl_set_chunk() -> hdf5::Result<()> {
     assert!(DC::try_new()?.get_chunk()?.is_none());
     assert_eq!(DCB::new().chunk(&[3, 7]).finish()?.get_chunk()?, Some(vec![3, 7]));
     assert_eq!(DCB::new().chunk((3, 7)).finish()?.chunk(), Some(vec![3, 7]));
-    let mut b = DCB::new().chunk([3, 7]).clone();
-    assert_eq!(b.layout(Layout::Contiguous).finish()?.layout(), Layout::Chunked);
-    assert_eq!(b.layout(Layout::Compact).finish()?.layout(), Layout::Chunked);
+    let b = DCB::new().chunk([3, 7]);
+    assert_eq!(b.clone().layout(Layout::Contiguous).finish()?.layout(), Layout::Chunked);
+    assert_eq!(b.clone().layout(Layout::Compact).finish()?.layout(), Layout::Chunked);
     #[cfg(feature = "1.10.0")]
-    assert_eq!(b.layout(Layout::Virtual).finish()?.layout(), Layout::Chunked);
+    assert_eq!(b.clone().layout(Layout::Virtual).finish()?.layout(), Layout::Chunked);
     assert!(b.no_chunk().finish()?.chunk().is_none());
     assert!(DCB::new().layout(Layout::Contiguous).finish()?.get_chunk()?.is_none());
     assert!(DCB::new().layout(Layout::Compact).finish()?.get_chunk()?.is_none());
@@ -672,15 +794,15 @@ fn test_dcpl_set_layout() -> hdf5::Result<()> {
 fn test_dcpl_set_chunk_opts() -> hdf5::Result<()> {
     assert!(DC::try_new()?.get_chunk_opts()?.is_none());
     let mut b = DCB::new();
-    assert!(b.layout(Layout::Contiguous).finish()?.get_chunk_opts()?.is_none());
-    assert!(b.layout(Layout::Compact).finish()?.get_chunk_opts()?.is_none());
+    assert!(b.clone().layout(Layout::Contiguous).finish()?.get_chunk_opts()?.is_none());
+    assert!(b.clone().layout(Layout::Compact).finish()?.get_chunk_opts()?.is_none());
     #[cfg(feature = "1.10.0")]
-    assert!(b.layout(Layout::Virtual).finish()?.get_chunk_opts()?.is_none());
-    b.layout(Layout::Chunked);
+    assert!(b.clone().layout(Layout::Virtual).finish()?.get_chunk_opts()?.is_none());
+    b = b.layout(Layout::Chunked);
     assert_eq!(b.finish()?.get_chunk_opts()?, Some(ChunkOpts::empty()));
-    b.chunk_opts(ChunkOpts::empty());
+    b = b.chunk_opts(ChunkOpts::empty());
     assert_eq!(b.finish()?.get_chunk_opts()?, Some(ChunkOpts::empty()));
-    b.chunk_opts(ChunkOpts::DONT_FILTER_PARTIAL_CHUNKS);
+    b = b.chunk_opts(ChunkOpts::DONT_FILTER_PARTIAL_CHUNKS);
     assert_eq!(b.finish()?.get_chunk_opts()?, Some(ChunkOpts::DONT_FILTER_PARTIAL_CHUNKS));
     Ok(())
 }
@@ -689,24 +811,24 @@ fn test_dcpl_set_chunk_opts() -> hdf5::Result<()> {
 fn test_dcpl_set_alloc_time() -> hdf5::Result<()> {
     check_matches!(DC::try_new()?.get_alloc_time()?, (), AllocTime::Late);
     let mut b = DCB::new();
-    b.alloc_time(None);
-    b.layout(Layout::Contiguous);
+    b = b.alloc_time(None);
+    b = b.layout(Layout::Contiguous);
     check_matches!(b.finish()?.get_alloc_time()?, (), AllocTime::Late);
-    b.layout(Layout::Compact);
+    b = b.layout(Layout::Compact);
     check_matches!(b.finish()?.get_alloc_time()?, (), AllocTime::Early);
-    b.layout(Layout::Chunked);
+    b = b.layout(Layout::Chunked);
     check_matches!(b.finish()?.get_alloc_time()?, (), AllocTime::Incr);
     #[cfg(feature = "1.10.0")]
     {
-        b.layout(Layout::Virtual);
+        b = b.layout(Layout::Virtual);
         check_matches!(b.finish()?.get_alloc_time()?, (), AllocTime::Incr);
     }
-    b.layout(Layout::Contiguous);
-    b.alloc_time(Some(AllocTime::Late));
+    b = b.layout(Layout::Contiguous);
+    b = b.alloc_time(Some(AllocTime::Late));
     check_matches!(b.finish()?.get_alloc_time()?, (), AllocTime::Late);
-    b.alloc_time(Some(AllocTime::Incr));
+    b = b.alloc_time(Some(AllocTime::Incr));
     check_matches!(b.finish()?.get_alloc_time()?, (), AllocTime::Incr);
-    b.alloc_time(Some(AllocTime::Early));
+    b = b.alloc_time(Some(AllocTime::Early));
     check_matches!(b.finish()?.get_alloc_time()?, (), AllocTime::Early);
     Ok(())
 }
@@ -729,7 +851,7 @@ fn test_dcpl_fill_value() -> hdf5::Result<()> {
     assert_eq!(DC::try_new()?.fill_value_as::<bool>(), Some(false));
 
     let mut b = DCB::new();
-    b.fill_value(1.23);
+    b = b.fill_value(1.23);
     let pl = b.finish()?;
     assert_eq!(pl.fill_value_defined(), FillValue::UserDefined);
     assert_eq!(pl.fill_value_as::<f64>(), Some(1.23));
@@ -750,9 +872,9 @@ fn test_dcpl_external() -> hdf5::Result<()> {
         .external("foo", 12, 0)
         .finish()?;
     let expected = vec![
-        ExternalFile { name: "bar".to_owned(), offset: 0, size: 1 },
-        ExternalFile { name: "baz".to_owned(), offset: 34, size: 100 },
-        ExternalFile { name: "foo".to_owned(), offset: 12, size: 0 },
+        ExternalFile { name: "bar".into(), offset: 0, size: 1 },
+        ExternalFile { name: "baz".into(), offset: 34, size: 100 },
+        ExternalFile { name: "foo".into(), offset: 12, size: 0 },
     ];
     assert_eq!(pl.get_external()?, expected);
     assert_eq!(pl.external(), expected);
@@ -852,12 +974,59 @@ fn test_dcpl_attr_creation_order() -> hdf5::Result<()> {
     Ok(())
 }
 
+type GC = GroupCreate;
+type GcBuilder = GroupCreateBuilder;
+
+#[test]
+fn test_gcpl_common() -> hdf5::Result<()> {
+    test_pl_common!(GC, PropertyListClass::GroupCreate, |b: GcBuilder| b
+        .est_link_info(1000, 16)
+        .finish());
+    Ok(())
+}
+
+#[test]
+fn test_gcpl_est_link_info() -> hdf5::Result<()> {
+    use hdf5::plist::group_create::EstLinkInfo;
+    assert_eq!(GC::try_new()?.get_est_link_info()?, EstLinkInfo::default());
+    assert_eq!(GC::try_new()?.est_link_info(), EstLinkInfo::default());
+    test_pl!(GC, est_link_info: est_num_entries = 100_000, est_name_len = 12);
+    test_pl!(GC, est_link_info: est_num_entries = 0, est_name_len = 0);
+    let pl = GcBuilder::new().est_link_info(500, 20).finish()?;
+    let expected = EstLinkInfo { est_num_entries: 500, est_name_len: 20 };
+    assert_eq!(GcBuilder::from_plist(&pl)?.finish()?.get_est_link_info()?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_gcpl_link_phase_change() -> hdf5::Result<()> {
+    use hdf5::plist::group_create::LinkPhaseChange;
+    assert_eq!(GC::try_new()?.get_link_phase_change()?, LinkPhaseChange::default());
+    assert_eq!(GC::try_new()?.link_phase_change(), LinkPhaseChange::default());
+    test_pl!(GC, link_phase_change: max_compact = 34, min_dense = 21);
+    test_pl!(GC, link_phase_change: max_compact = 0, min_dense = 0);
+    let pl = GcBuilder::new().link_phase_change(16, 8).finish()?;
+    let expected = LinkPhaseChange { max_compact: 16, min_dense: 8 };
+    assert_eq!(GcBuilder::from_plist(&pl)?.finish()?.get_link_phase_change()?, expected);
+    assert!(GcBuilder::new().link_phase_change(12, 34).finish().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_gcpl_link_creation_order() -> hdf5::Result<()> {
+    assert_eq!(GC::try_new()?.get_link_creation_order()?.bits(), 0);
+    test_pl!(GC, link_creation_order: LinkCreationOrder::TRACKED);
+    test_pl!(GC, link_creation_order: LinkCreationOrder::TRACKED | LinkCreationOrder::INDEXED);
+    assert!(GcBuilder::new().link_creation_order(LinkCreationOrder::INDEXED).finish().is_err());
+    Ok(())
+}
+
 type LC = LinkCreate;
 type LCB = LinkCreateBuilder;
 
 #[test]
 fn test_lcpl_common() -> hdf5::Result<()> {
-    test_pl_common!(LC, PropertyListClass::LinkCreate, |b: &mut LCB| b
+    test_pl_common!(LC, PropertyListClass::LinkCreate, |b: LCB| b
         .create_intermediate_group(true)
         .finish());
     Ok(())
@@ -911,3 +1080,108 @@ fn test_lcpl_char_encoding() -> hdf5::Result<()> {
     assert_eq!(LCB::from_plist(&pl)?.finish()?.get_char_encoding()?, CharEncoding::Utf8);
     Ok(())
 }
+
+type LA = LinkAccess;
+type LaBuilder = LinkAccessBuilder;
+
+#[test]
+fn test_lapl_common() -> hdf5::Result<()> {
+    test_pl_common!(LA, PropertyListClass::LinkAccess, |b: LaBuilder| b
+        .elink_prefix("foo")
+        .finish());
+    Ok(())
+}
+
+#[test]
+fn test_lapl_set_elink_prefix() -> hdf5::Result<()> {
+    assert_eq!(LA::try_new()?.get_elink_prefix()?, "");
+    assert_eq!(LA::try_new()?.elink_prefix(), "");
+    test_pl!(LA, elink_prefix("foo/bar"): "foo/bar");
+    let pl = LaBuilder::new().elink_prefix("foo").finish()?;
+    assert_eq!(LaBuilder::from_plist(&pl)?.finish()?.get_elink_prefix()?, "foo");
+    Ok(())
+}
+
+#[test]
+fn test_builder_chaining() -> hdf5::Result<()> {
+    use hdf5::plist::link_create::CharEncoding;
+
+    // every builder can be configured and finished in one expression, and round-trips
+    let dapl = DA::build().chunk_cache(100, 200, 0.5).efile_prefix("ext").finish()?;
+    assert_eq!(DAB::from_plist(&dapl)?.finish()?, dapl);
+    let dxpl = DX::build().buffer_size(4096).edc_check(false).hyper_vector_size(64).finish()?;
+    assert_eq!(DxBuilder::from_plist(&dxpl)?.finish()?, dxpl);
+    let dcpl = DC::build().chunk((4, 4)).shuffle().alloc_time(Some(AllocTime::Early)).finish()?;
+    assert_eq!(DCB::from_plist(&dcpl)?.finish()?, dcpl);
+    let fcpl = FC::build().userblock(512).sym_k(8, 4).obj_track_times(false).finish()?;
+    assert_eq!(FCB::from_plist(&fcpl)?.finish()?, fcpl);
+    let fapl =
+        FA::build().sieve_buf_size(8).meta_block_size(1024).core_filebacked(false).finish()?;
+    let copied = FAB::from_plist(&fapl)?.finish()?;
+    assert_eq!(copied.get_sieve_buf_size()?, 8);
+    assert_eq!(copied.get_meta_block_size()?, 1024);
+    check_matches!(copied.get_driver()?, (), FileDriver::Core(_));
+    let gcpl = GC::build().est_link_info(10, 20).link_phase_change(0, 0).finish()?;
+    assert_eq!(GcBuilder::from_plist(&gcpl)?.finish()?, gcpl);
+    let lcpl =
+        LC::build().create_intermediate_group(true).char_encoding(CharEncoding::Utf8).finish()?;
+    assert_eq!(LCB::from_plist(&lcpl)?.finish()?, lcpl);
+    let lapl = LA::build().elink_prefix("foo").finish()?;
+    assert_eq!(LaBuilder::from_plist(&lapl)?.finish()?, lapl);
+    let ocpypl = ObjectCopy::build().shallow_hierarchy(true).expand_soft_links(true).finish()?;
+    assert_eq!(ObjectCopyBuilder::from_plist(&ocpypl)?.finish()?, ocpypl);
+
+    // builders are reused by cloning them
+    let base = DC::build().chunk(10);
+    assert_eq!(base.clone().deflate(1).finish()?.chunk(), Some(vec![10]));
+    assert!(base.finish()?.get_filters()?.is_empty());
+
+    // the file and dataset builders forward to the property list builders
+    let file = File::with_options()
+        .with_fapl(|p| p.core_filebacked(false))
+        .with_fcpl(|p| p.userblock(512))
+        .close_degree(FileCloseDegree::Strong)
+        .create("chaining.h5")?;
+    assert_eq!(file.userblock(), 512);
+    let ds = file
+        .new_dataset::<i32>()
+        .with_dcpl(|p| p.chunk(4).shuffle())
+        .with_dapl(|p| p.chunk_cache(11, 22, 0.25))
+        .shape(8)
+        .create("ds")?;
+    assert_eq!(ds.dcpl()?.chunk(), Some(vec![4]));
+    assert_eq!(ds.dapl()?.chunk_cache().nslots, 11);
+    Ok(())
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_builder_mut_setters() -> hdf5::Result<()> {
+    // the `&mut` setters of earlier releases still compile, with deprecation warnings
+    let b = &mut FileAccess::build();
+    b.sec2().sieve_buf_size(8);
+    assert_eq!(b.finish()?.get_sieve_buf_size()?, 8);
+    let b = &mut DatasetCreate::build();
+    b.chunk(4).deflate(3);
+    assert_eq!(b.finish()?.chunk(), Some(vec![4]));
+
+    // setters chain on `&mut` references to builders, as returned by the deprecated accessors
+    fn configure(builder: &mut FileBuilder) {
+        builder.with_fcpl(|p| p.userblock(1024)).close_degree(FileCloseDegree::Strong);
+    }
+    let mut builder = File::with_options();
+    builder.fapl().core_filebacked(false).sieve_buf_size(8);
+    configure(&mut builder);
+    let file = builder.create("mut_setters.h5")?;
+    assert_eq!(file.userblock(), 1024);
+    assert_eq!(file.fapl()?.get_sieve_buf_size()?, 8);
+    assert_eq!(file.fapl()?.get_fclose_degree()?, FileCloseDegree::Strong);
+
+    let mut builder = file.new_dataset::<i32>().shape(8);
+    builder.dcpl().chunk(4).shuffle();
+    builder.dapl().chunk_cache(11, 22, 0.25);
+    let ds = builder.create("ds")?;
+    assert_eq!(ds.dcpl()?.chunk(), Some(vec![4]));
+    assert_eq!(ds.dapl()?.chunk_cache().nslots, 11);
+    Ok(())
+}
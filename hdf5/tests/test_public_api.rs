@@ -0,0 +1,69 @@
+//! Locks the stable surface of the crate: everything below must keep compiling when modules
+//! are reorganized. Most of it only needs to type-check, the test itself barely runs.
+
+#![allow(dead_code)]
+
+use hdf5::prelude::*;
+use hdf5::raw::{self, hid_t, hsize_t, H5I_INVALID_HID};
+use hdf5_rt as hdf5;
+
+fn prelude_surface(file: &File) -> Result<()> {
+    let group: Group = file.group("g")?;
+    let ds: Dataset = group.dataset("ds")?;
+    let _: &Container = &ds;
+    let _: &Location = &group;
+    let attr: Attribute = ds.attr("attr")?;
+    let dtype: Datatype = attr.dtype()?;
+    let _: TypeDescriptor = dtype.to_descriptor()?;
+    let space: Dataspace = ds.space()?;
+    let _ = space.shape();
+    let _: Vec<Filter> = ds.filters();
+    let _ = ds.read_slice_1d::<i32, _>(Selection::All)?;
+    let reference: ObjectReference2 = file.reference("g")?;
+    let _ = file.dereference(&reference)?;
+    let _: ObjectReference1 = file.reference("g")?;
+    let kind: ErrorKind = Error::from("error").kind();
+    assert_eq!(kind, ErrorKind::Other);
+    Ok(())
+}
+
+fn type_of<T: H5Type>() -> TypeDescriptor {
+    T::type_descriptor()
+}
+
+fn reference_type<R: ObjectReference>() -> TypeDescriptor {
+    R::type_descriptor()
+}
+
+fn raw_surface(file: File) -> std::result::Result<(), String> {
+    raw::init(None)?;
+    let _: bool = raw::is_initialized();
+    let _: Option<String> = raw::library_path();
+    let _: Option<raw::Version> = raw::hdf5_version();
+    let _: bool = raw::hdf5_version_at_least(1, 14, 0);
+    let id: hid_t = raw::into_id(file);
+    let _: File = unsafe { raw::from_id(id) }.map_err(|e| e.to_string())?;
+    let _: hsize_t = raw::sync(|| 0);
+    let _: (raw::herr_t, raw::htri_t, raw::hssize_t, raw::haddr_t) = (0, 0, 0, 0);
+    raw::shutdown()
+}
+
+#[allow(deprecated)]
+fn deprecated_paths(file: File) -> std::result::Result<(), String> {
+    hdf5::sys::init(None)?;
+    let _: bool = hdf5::sys::is_initialized();
+    let _: Option<String> = hdf5::sys::library_path();
+    let _: Option<raw::Version> = hdf5::sys::hdf5_version();
+    let _: bool = hdf5::sys::hdf5_version_at_least(1, 14, 0);
+    let _: File = unsafe { hdf5::from_id(raw::into_id(file)) }.map_err(|e| e.to_string())?;
+    hdf5::sys::shutdown()
+}
+
+#[test]
+fn test_public_api() {
+    let _ = (prelude_surface, raw_surface, deprecated_paths);
+    assert_eq!(type_of::<i32>(), TypeDescriptor::Integer(hdf5::types::IntSize::U4));
+    assert_eq!(reference_type::<ObjectReference1>(), ObjectReference1::type_descriptor());
+    assert_eq!(H5I_INVALID_HID, -1);
+    assert!(!raw::LIBRARY_PATH_ENV.is_empty());
+}
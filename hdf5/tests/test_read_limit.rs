@@ -0,0 +1,49 @@
+//! Tests for the read memory limit; kept in their own binary since the limit is process-wide.
+
+use hdf5::fixtures::memory_file;
+use hdf5::{Error, ErrorKind};
+use hdf5_rt as hdf5;
+use ndarray::s;
+
+const MIB: u64 = 1024 * 1024;
+
+#[test]
+fn test_read_limit() {
+    let file = memory_file().unwrap();
+    let ds = file.new_dataset::<u8>().shape(10 * MIB as usize).create("data").unwrap();
+    assert_eq!(hdf5::max_read_bytes(), None);
+    assert_eq!(ds.read_raw::<u8>().unwrap().len(), 10 * MIB as usize);
+
+    hdf5::set_max_read_bytes(Some(MIB));
+    assert_eq!(hdf5::max_read_bytes(), Some(MIB));
+    let err = ds.read_raw::<u8>().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AllocationLimitExceeded);
+    assert!(matches!(
+        err.root_cause(),
+        Error::AllocationLimitExceeded { size, limit } if *size == 10 * MIB && *limit == MIB
+    ));
+    let msg = err.to_string();
+    assert!(msg.contains("reading dataset '/data'"), "{msg}");
+    assert!(msg.contains(&format!("{} bytes", 10 * MIB)), "{msg}");
+    assert!(msg.contains(&format!("limit of {MIB} bytes")), "{msg}");
+    assert!(msg.contains("read_slice"), "{msg}");
+    assert_eq!(ds.read_slice_1d::<u8, _>(s![..2 * MIB as usize]).unwrap_err().kind(), err.kind());
+
+    // converted reads also account for the conversion buffer
+    assert!(ds.read_slice_1d::<u16, _>(s![..MIB as usize / 2]).is_err());
+    assert!(ds.read_slice_1d::<u16, _>(s![..MIB as usize / 4]).is_ok());
+
+    // slices under the limit can be read
+    let slice = ds.read_slice_1d::<u8, _>(s![..MIB as usize / 2]).unwrap();
+    assert_eq!(slice.len(), MIB as usize / 2);
+
+    // the per-reader limit takes precedence over the global one
+    assert_eq!(
+        ds.as_reader().max_bytes(20 * MIB).read_raw::<u8>().unwrap().len(),
+        10 * MIB as usize
+    );
+    hdf5::set_max_read_bytes(None);
+    let err = ds.as_reader().max_bytes(MIB).read_raw::<u8>().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AllocationLimitExceeded);
+    assert_eq!(ds.read_raw::<u8>().unwrap().len(), 10 * MIB as usize);
+}
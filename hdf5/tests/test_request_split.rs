@@ -0,0 +1,70 @@
+//! Tests for splitting large dataset reads and writes into several requests.
+
+use hdf5::dataset::IoKind;
+use hdf5::fixtures::memory_file;
+use hdf5::Dataset;
+use hdf5_rt as hdf5;
+use ndarray::{s, Array2};
+
+const KIB: usize = 1024;
+
+fn counts(ds: &Dataset, kind: IoKind) -> u64 {
+    let stats = ds.io_stats().unwrap();
+    match kind {
+        IoKind::Read => stats.reads,
+        IoKind::Write => stats.writes,
+    }
+}
+
+#[test]
+fn test_request_split() {
+    let file = memory_file().unwrap();
+    // 512 KiB of data, 8 KiB per row
+    let data = Array2::from_shape_fn((64, 1024), |(i, j)| (i * 1024 + j) as u64);
+    for (name, chunked) in [("contiguous", false), ("chunked", true)] {
+        let builder = file.new_dataset::<u64>().shape((64, 1024));
+        let ds = if chunked { builder.chunk((5, 300)) } else { builder }.create(name).unwrap();
+        ds.enable_io_stats();
+
+        ds.as_writer().max_request_size(Some(64 * KIB)).write(&data).unwrap();
+        assert_eq!(counts(&ds, IoKind::Write), 8);
+        // every request carries whole rows and at most the maximum size
+        let stats = ds.io_stats().unwrap();
+        assert!(stats.recent.iter().all(|r| r.bytes == 64 * KIB && r.bounds[1] == (0, 1024)));
+
+        let read = ds.as_reader().max_request_size(Some(64 * KIB)).read_2d::<u64>().unwrap();
+        assert_eq!(read, data);
+        assert_eq!(counts(&ds, IoKind::Read), 8);
+        assert_eq!(ds.read_2d::<u64>().unwrap(), data);
+        assert_eq!(counts(&ds, IoKind::Read), 9);
+
+        // converted reads are split by the size of the memory type
+        let reader = ds.as_reader().max_request_size(Some(64 * KIB));
+        assert_eq!(reader.read_2d::<u32>().unwrap(), data.mapv(|x| x as u32));
+        assert_eq!(counts(&ds, IoKind::Read), 13);
+
+        // strided selections are split by rows of the dataset
+        let sel = s![1..60;3, 5..900;7];
+        let reader = ds.as_reader().max_request_size(Some(KIB));
+        assert_eq!(reader.read_slice_2d::<u64, _>(sel).unwrap(), data.slice(sel));
+        assert_eq!(counts(&ds, IoKind::Read), 13 + 20);
+
+        let zeros = Array2::<u64>::zeros((64, 1024));
+        ds.as_writer().max_request_size(None).write(&zeros).unwrap();
+        assert_eq!(counts(&ds, IoKind::Write), 9);
+        let writer = ds.as_writer().max_request_size(Some(8 * KIB));
+        writer.write_slice(&data.slice(sel).to_owned(), sel).unwrap();
+        assert_eq!(counts(&ds, IoKind::Write), 9 + 20);
+        let mut expected = zeros;
+        expected.slice_mut(sel).assign(&data.slice(sel));
+        assert_eq!(ds.read_2d::<u64>().unwrap(), expected);
+
+        // the raw, 1-dimensional view of the dataset is split as well
+        ds.as_writer()
+            .max_request_size(Some(100 * KIB))
+            .write_raw(data.as_slice().unwrap())
+            .unwrap();
+        assert_eq!(counts(&ds, IoKind::Write), 29 + 6);
+        assert_eq!(ds.read_raw::<u64>().unwrap(), data.as_slice().unwrap());
+    }
+}
@@ -0,0 +1,67 @@
+//! Tests for configuring the library loader through the environment. The library can only be
+//! loaded once per process, so each case runs in a child process: this test binary, re-run
+//! with only `runtime_env_child` selected.
+
+use std::env;
+use std::process::Command;
+
+use hdf5::raw::{self, LIBRARY_PATH_ENV};
+use hdf5::File;
+use hdf5_rt as hdf5;
+
+const CASE_ENV: &str = "HDF5_RT_TEST_RUNTIME_ENV_CASE";
+const VALID_PATH_ENV: &str = "HDF5_RT_TEST_RUNTIME_ENV_LIB";
+const MISSING_LIBRARY: &str = "/nonexistent/libhdf5.so";
+
+/// Runs one case in the child process; does nothing unless run by `test_runtime_env`.
+#[test]
+fn runtime_env_child() {
+    let Ok(case) = env::var(CASE_ENV) else {
+        return;
+    };
+    let valid = env::var(VALID_PATH_ENV).unwrap();
+    let create_file = || {
+        let dir = tempfile::tempdir().unwrap();
+        File::create(dir.path().join("env.h5")).unwrap();
+    };
+    match case.as_str() {
+        "implicit" => {
+            // the first high-level call loads the library named by the environment
+            assert!(!raw::is_initialized());
+            create_file();
+            assert_eq!(raw::library_path(), Some(valid));
+        }
+        "explicit" => {
+            raw::init(Some(&valid)).unwrap();
+            create_file();
+            assert_eq!(raw::library_path(), Some(valid));
+        }
+        "missing" => {
+            let err = raw::init(None).unwrap_err();
+            assert!(err.contains(MISSING_LIBRARY) && err.contains(LIBRARY_PATH_ENV), "{err}");
+            assert!(!raw::is_initialized());
+        }
+        _ => panic!("unknown case {case}"),
+    }
+}
+
+fn run_child(case: &str, library: &str, valid: &str) {
+    let status = Command::new(env::current_exe().unwrap())
+        .args(["runtime_env_child", "--exact", "--nocapture", "--test-threads=1"])
+        .env(CASE_ENV, case)
+        .env(VALID_PATH_ENV, valid)
+        .env(LIBRARY_PATH_ENV, library)
+        .status()
+        .unwrap();
+    assert!(status.success(), "case {case} failed");
+}
+
+#[test]
+fn test_runtime_env() {
+    raw::init(None).unwrap();
+    let valid = raw::library_path().unwrap();
+    run_child("implicit", &valid, &valid);
+    // an explicit path takes precedence over the environment
+    run_child("explicit", MISSING_LIBRARY, &valid);
+    run_child("missing", MISSING_LIBRARY, &valid);
+}
@@ -0,0 +1,154 @@
+//! Tests for serialization of type descriptors, filters, selections and dataset specs.
+#![cfg(feature = "serde")]
+
+use hdf5::filters::{Filter, SZip, ScaleOffset};
+use hdf5::fixtures::memory_file;
+use hdf5::plist::file_access::ChunkCache;
+use hdf5::types::{CompoundType, EnumMember, EnumType, FloatSize, IntSize, TypeDescriptor};
+use hdf5::{DatasetSpec, FillSpec, Hyperslab, Selection};
+use hdf5_rt as hdf5;
+use ndarray::s;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+fn roundtrip<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(value: &T) {
+    let json = serde_json::to_string(value).unwrap();
+    assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value, "{json}");
+}
+
+#[test]
+fn test_type_descriptor_serde() {
+    let enum_type = TypeDescriptor::Enum(EnumType {
+        size: IntSize::U1,
+        signed: false,
+        members: vec![
+            EnumMember { name: "red".into(), value: 0 },
+            EnumMember { name: "green".into(), value: 1 },
+        ],
+    });
+    let compound = TypeDescriptor::Compound(CompoundType::from_fields_aligned(vec![
+        ("a", TypeDescriptor::Integer(IntSize::U2)),
+        ("b", TypeDescriptor::FixedArray(Box::new(TypeDescriptor::Float(FloatSize::U8)), 3)),
+        ("c", enum_type.clone()),
+        ("d", TypeDescriptor::VarLenUnicode),
+    ]));
+    for desc in [
+        TypeDescriptor::Unsigned(IntSize::U8),
+        TypeDescriptor::Boolean,
+        TypeDescriptor::FixedAscii(12),
+        TypeDescriptor::VarLenArray(Box::new(TypeDescriptor::Float(FloatSize::U4))),
+        enum_type,
+        compound,
+    ] {
+        roundtrip(&desc);
+    }
+    assert_eq!(
+        serde_json::to_string(&TypeDescriptor::Float(FloatSize::U8)).unwrap(),
+        r#"{"Float":8}"#
+    );
+    assert!(serde_json::from_str::<TypeDescriptor>(r#"{"Integer":3}"#).is_err());
+}
+
+#[test]
+fn test_filter_serde() {
+    for filter in [
+        Filter::Deflate(4),
+        Filter::Shuffle,
+        Filter::Fletcher32,
+        Filter::NBit,
+        Filter::SZip(SZip::NearestNeighbor, 16),
+        Filter::ScaleOffset(ScaleOffset::FloatDScale(3)),
+        Filter::ScaleOffset(ScaleOffset::Integer(0)),
+        #[cfg(feature = "lzf")]
+        Filter::LZF,
+        #[cfg(feature = "blosc")]
+        Filter::Blosc(hdf5::filters::Blosc::BloscLZ, 5, hdf5::filters::BloscShuffle::Bit),
+        #[cfg(feature = "zfp")]
+        Filter::zfp_rate(8.5, vec![16, 32], 8),
+        #[cfg(feature = "zfp")]
+        Filter::zfp_precision(20, vec![4, 8, 16], 4),
+        #[cfg(feature = "zfp")]
+        Filter::zfp_accuracy(1e-3, vec![8, 64], 8),
+        #[cfg(feature = "zfp")]
+        Filter::zfp_reversible(vec![10, 10], 4),
+        Filter::User(307, vec![1, 2, 3]),
+    ] {
+        roundtrip(&filter);
+    }
+    assert_eq!(
+        serde_json::to_string(&Filter::Deflate(6)).unwrap(),
+        r#"{"filter":"deflate","level":6}"#
+    );
+    let filters: Vec<Filter> =
+        serde_json::from_str(r#"[{"filter": "shuffle"}, {"filter": "deflate", "level": 9}]"#)
+            .unwrap();
+    assert_eq!(filters, [Filter::Shuffle, Filter::Deflate(9)]);
+    assert!(serde_json::from_str::<Filter>(r#"{"filter": "deflate", "level": 10}"#).is_err());
+    assert!(serde_json::from_str::<Filter>(r#"{"filter": "unknown"}"#).is_err());
+}
+
+#[test]
+#[cfg(not(feature = "lzf"))]
+fn test_filter_serde_without_plugin_feature() {
+    let filter: Filter = serde_json::from_str(r#"{"filter": "lzf"}"#).unwrap();
+    assert_eq!(filter, Filter::User(32000, vec![]));
+}
+
+#[test]
+fn test_selection_serde() {
+    roundtrip(&Selection::All);
+    roundtrip(&Selection::Points(ndarray::arr2(&[[0, 1], [2, 3], [4, 5]])));
+    roundtrip(&Selection::try_new(s![1..5;2, 3, ..]).unwrap());
+    roundtrip(&Hyperslab::try_new(s![..7;3]).unwrap());
+    let points: Selection = serde_json::from_str(r#"{"Points": [[1, 2], [3, 4]]}"#).unwrap();
+    assert_eq!(points, Selection::Points(ndarray::arr2(&[[1, 2], [3, 4]])));
+    assert!(serde_json::from_str::<Selection>(r#"{"Points": [[1, 2], [3]]}"#).is_err());
+}
+
+#[test]
+fn test_chunk_cache_serde() {
+    roundtrip(&ChunkCache { nslots: 521, nbytes: 1 << 20, w0: 0.75 });
+}
+
+#[test]
+fn test_dataset_spec_serde() {
+    let spec: DatasetSpec =
+        serde_json::from_str(r#"{"dtype": {"Integer": 4}, "shape": [5]}"#).unwrap();
+    assert_eq!(spec, DatasetSpec::new(TypeDescriptor::Integer(IntSize::U4), vec![5]));
+    let spec = DatasetSpec {
+        maxshape: Some(vec![None, Some(3)]),
+        chunks: Some(vec![10, 3]),
+        filters: vec![Filter::Shuffle, Filter::Deflate(4)],
+        fill: Some(FillSpec::Float(-1.0)),
+        ..DatasetSpec::new(TypeDescriptor::Float(FloatSize::U8), vec![100, 3])
+    };
+    roundtrip(&spec);
+}
+
+#[test]
+fn test_dataset_spec_create() {
+    let file = memory_file().unwrap();
+    let spec: DatasetSpec = serde_json::from_str(
+        r#"{
+            "dtype": {"Float": 8},
+            "shape": [100, 3],
+            "maxshape": [null, 3],
+            "chunks": [10, 3],
+            "filters": [{"filter": "shuffle"}, {"filter": "deflate", "level": 4}],
+            "fill": -1.0
+        }"#,
+    )
+    .unwrap();
+    let ds = spec.create(&file, "x").unwrap();
+    assert_eq!(ds.shape().unwrap(), [100, 3]);
+    assert_eq!(ds.read_raw::<f64>().unwrap()[0], -1.0);
+    assert_eq!(DatasetSpec::from_dataset(&ds).unwrap(), spec);
+
+    let spec = DatasetSpec::new(TypeDescriptor::Unsigned(IntSize::U2), vec![]);
+    let ds = spec.create(&file, "scalar").unwrap();
+    assert!(ds.is_scalar());
+    assert_eq!(DatasetSpec::from_dataset(&ds).unwrap(), spec);
+
+    let bad = DatasetSpec { fill: Some(FillSpec::Int(-1)), ..spec };
+    assert!(bad.create(&file, "bad").is_err());
+}
@@ -0,0 +1,59 @@
+//! Tests for using handles after the library has been shut down. Shutting down is final for
+//! the process, so this binary holds a single test.
+
+use hdf5::fixtures::memory_file;
+use hdf5::{raw, sys, ErrorKind, File, Result};
+use hdf5_rt as hdf5;
+
+fn assert_shut_down<T>(res: Result<T>) {
+    match res {
+        Ok(_) => panic!("expected an error after shutdown"),
+        Err(err) => assert_eq!(err.kind(), ErrorKind::LibraryShutDown, "{err}"),
+    }
+}
+
+#[test]
+fn test_use_after_shutdown() {
+    let file = memory_file().unwrap();
+    let group = file.create_group("g").unwrap();
+    let ds = group.new_dataset::<i32>().shape(4).create("ds").unwrap();
+    ds.write_raw(&[1, 2, 3, 4]).unwrap();
+    let attr = ds.new_attr::<f64>().create("attr").unwrap();
+    let dcpl = ds.dcpl().unwrap();
+    let dtype = ds.dtype().unwrap();
+    assert_eq!(sys::generation(), 0);
+
+    raw::shutdown().unwrap();
+    assert!(sys::is_shut_down());
+    assert_eq!(sys::generation(), 1);
+    // shutting down again does nothing
+    raw::shutdown().unwrap();
+    assert_eq!(sys::generation(), 1);
+
+    #[cfg(feature = "test-hooks")]
+    let calls = sys::ffi_call_count();
+    assert_shut_down(file.group("g"));
+    assert_shut_down(file.create_group("h"));
+    assert_shut_down(file.flush());
+    assert_shut_down(group.dataset("ds"));
+    assert_shut_down(ds.read_raw::<i32>());
+    assert_shut_down(ds.write_raw(&[4, 3, 2, 1]));
+    assert_shut_down(ds.attr("attr"));
+    assert_shut_down(attr.read_scalar::<f64>());
+    assert_shut_down(attr.write_scalar(&1.0));
+    // unchecked calls fail as well, but may do so with a less specific error
+    assert!(dtype.to_descriptor().is_err());
+    assert_shut_down(dcpl.copy());
+    assert_shut_down(dcpl.get_filters());
+    assert_shut_down(File::create("after-shutdown.h5"));
+    assert!(!ds.is_valid());
+    assert_eq!(ds.refcount(), 0);
+    assert_eq!(format!("{ds:?}"), "<HDF5 dataset: invalid id>");
+
+    let cloned = file.clone();
+    assert!(!cloned.is_valid());
+    drop((cloned, dtype, dcpl, attr, ds, group, file));
+    // nothing calls into the library after the shutdown
+    #[cfg(feature = "test-hooks")]
+    assert_eq!(sys::ffi_call_count(), calls);
+}
@@ -0,0 +1,66 @@
+//! Tests for the heap allocations of sliced reads; kept in their own binary since they count
+//! the allocations of the whole process.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use hdf5::fixtures::memory_file;
+use hdf5::Dataset;
+use hdf5_rt as hdf5;
+use ndarray::{ArrayD, IxDyn, SliceInfo, SliceInfoElem};
+
+/// Counts the allocations made through the global allocator.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const READS: u64 = 100;
+
+/// Returns the allocations per read of a slice taking index 1 along the first axis and every
+/// element along the others.
+fn allocations_per_read(ds: &Dataset) -> u64 {
+    let mut elems = vec![SliceInfoElem::from(..); ds.ndim()];
+    elems[0] = SliceInfoElem::Index(1);
+    let slice: SliceInfo<_, IxDyn, IxDyn> = SliceInfo::try_from(elems).unwrap();
+    let read = || ds.read_slice::<i32, _, IxDyn>(slice.clone()).unwrap();
+    let expected = read();
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..READS {
+        assert_eq!(read(), expected);
+    }
+    (ALLOCATIONS.load(Ordering::Relaxed) - before) / READS
+}
+
+#[test]
+fn test_slice_allocs() {
+    let file = memory_file().unwrap();
+    let create = |ndim: usize| {
+        let data = ArrayD::from_shape_fn(IxDyn(&vec![2; ndim]), |idx| idx[ndim - 1] as i32);
+        file.new_dataset_builder().with_data(&data).create(format!("rank{ndim}").as_str()).unwrap()
+    };
+    // the dimension buffers are kept inline up to rank 8 and spill to the heap above, so a
+    // read of rank 9 allocates at least the start, stride, count and block of its hyperslab
+    // on top of the allocations of a read of rank 8
+    let inline = allocations_per_read(&create(8));
+    let spilled = allocations_per_read(&create(9));
+    assert!(inline + 4 <= spilled, "{inline} allocations per read at rank 8, {spilled} at rank 9");
+}
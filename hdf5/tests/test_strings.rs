@@ -0,0 +1,127 @@
+//! Tests for reading and writing strings.
+
+use hdf5::fixtures::memory_file;
+use hdf5::types::{FixedAscii, FixedUnicode, TypeDescriptor, VarLenAscii, VarLenUnicode};
+use hdf5::StringTruncation;
+use hdf5_rt as hdf5;
+
+#[test]
+fn test_with_data_str() {
+    let file = memory_file().unwrap();
+    let ds = file.new_dataset_builder().with_data_str(vec!["a", "", "héllo"]).create("strs");
+    let ds = ds.unwrap();
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), TypeDescriptor::VarLenUnicode);
+    assert_eq!(ds.read_strings().unwrap(), ["a", "", "héllo"]);
+
+    let owned = vec!["x".to_owned(), "yz".to_owned()];
+    let ds = file.new_dataset_builder().with_data_str(&owned).chunk(1).create("owned").unwrap();
+    assert!(ds.is_chunked());
+    assert_eq!(ds.read_strings().unwrap(), owned);
+
+    let err = file.new_dataset_builder().with_data_str(["ok", "a\0b"]).create("null");
+    let err = err.unwrap_err().to_string();
+    assert!(err.contains("element 1") && err.contains("internal null"), "{err}");
+    assert!(!file.link_exists("null"));
+}
+
+#[test]
+fn test_with_data_fixed_str() {
+    let file = memory_file().unwrap();
+    let data = ["abc", "abcdef", "aéé"];
+    let ds = file
+        .new_dataset_builder()
+        .with_data_fixed_str::<4, _, _>(data, StringTruncation::Truncate)
+        .create("truncated")
+        .unwrap();
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), TypeDescriptor::FixedUnicode(4));
+    assert_eq!(ds.read_strings().unwrap(), ["abc", "abcd", "aé"]);
+    assert_eq!(ds.read_raw::<FixedUnicode<4>>().unwrap()[1].as_str(), "abcd");
+
+    let err = file
+        .new_dataset_builder()
+        .with_data_fixed_str::<4, _, _>(data, StringTruncation::Error)
+        .create("rejected")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("element 1") && err.contains("6 bytes"), "{err}");
+
+    let ds =
+        file.new_dataset_builder().with_data_fixed_str::<8, _, _>(data, StringTruncation::Error);
+    assert_eq!(ds.create("fits").unwrap().read_strings().unwrap(), data);
+}
+
+#[test]
+fn test_read_strings_types() {
+    let file = memory_file().unwrap();
+    let ascii = [VarLenAscii::from_ascii("ab").unwrap(), VarLenAscii::from_ascii("c").unwrap()];
+    let ds = file.new_dataset_builder().with_data(&ascii).create("vlen_ascii").unwrap();
+    assert_eq!(ds.read_strings().unwrap(), ["ab", "c"]);
+    let fixed = [FixedAscii::<3>::from_ascii("xy").unwrap()];
+    let ds = file.new_dataset_builder().with_data(&fixed).create("fixed_ascii").unwrap();
+    assert_eq!(ds.read_strings().unwrap(), ["xy"]);
+
+    let ds = file.new_dataset_builder().with_data(&[1_i32]).create("ints").unwrap();
+    assert!(ds.read_strings().unwrap_err().to_string().contains("expected a string type"));
+}
+
+#[test]
+fn test_read_strings_invalid_utf8() {
+    use hdf5::sys::{h5d::H5Dwrite, h5p::H5P_DEFAULT, h5s::H5S_ALL};
+
+    let file = memory_file().unwrap();
+    let ds = file.new_dataset::<VarLenUnicode>().shape(2).create("latin1").unwrap();
+    // write the pointers with the file type, so the library stores the bytes as they are
+    let data: [&[u8]; 2] = [b"ok\0", b"caf\xe9\0"];
+    let ptrs = data.map(<[u8]>::as_ptr);
+    let dtype = ds.dtype().unwrap();
+    let ret = unsafe {
+        H5Dwrite(ds.id(), dtype.id(), H5S_ALL, H5S_ALL, H5P_DEFAULT, ptrs.as_ptr().cast())
+    };
+    assert!(ret >= 0);
+    assert_eq!(ds.read_strings().unwrap(), ["ok", "caf\u{fffd}"]);
+}
+
+#[test]
+fn test_attr_write_str() {
+    let file = memory_file().unwrap();
+    let attr = file.new_attr::<VarLenUnicode>().create("vlen").unwrap();
+    attr.write_str("héllo").unwrap();
+    assert_eq!(attr.read_string().unwrap(), "héllo");
+    assert_eq!(attr.read_scalar::<VarLenUnicode>().unwrap().as_str(), "héllo");
+
+    let attr = file.new_attr::<FixedAscii<5>>().create("fixed").unwrap();
+    attr.write_str("abc").unwrap();
+    assert_eq!(attr.read_string().unwrap(), "abc");
+    assert!(attr.write_str("abcdef").is_err());
+    assert!(attr.write_str("é").is_err());
+
+    let attr = file.new_attr::<VarLenAscii>().shape(2).create("vector").unwrap();
+    assert!(attr.write_str("a").unwrap_err().to_string().contains("single-element"));
+}
+
+#[test]
+fn test_attr_read_string_lossy() {
+    let file = memory_file().unwrap();
+    let attr = file.new_attr::<FixedAscii<4>>().create("latin1").unwrap();
+    // SAFETY: the bytes are not ASCII, as written by some legacy tools
+    let value = unsafe { FixedAscii::<4>::from_ascii_unchecked(b"caf\xe9") };
+    attr.write_scalar(&value).unwrap();
+    assert_eq!(attr.read_string().unwrap(), "caf\u{fffd}");
+}
+
+#[test]
+fn test_attr_empty_strings() {
+    let file = memory_file().unwrap();
+    let attr = file.new_attr::<VarLenUnicode>().create("vlen").unwrap();
+    attr.write_str("").unwrap();
+    assert_eq!(attr.read_string().unwrap(), "");
+    assert_eq!(attr.read_scalar::<VarLenUnicode>().unwrap().as_str(), "");
+
+    let attr = file.new_attr::<FixedAscii<8>>().create("fixed").unwrap();
+    attr.write_str("").unwrap();
+    assert_eq!(attr.read_string().unwrap(), "");
+    assert!(attr.read_scalar::<FixedAscii<8>>().unwrap().is_empty());
+
+    let attr = file.new_attr::<VarLenUnicode>().shape(0).create("none").unwrap();
+    assert!(attr.read_strings().unwrap().is_empty());
+}
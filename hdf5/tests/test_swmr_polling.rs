@@ -0,0 +1,109 @@
+//! Tests for polling a dataset written under SWMR. HDF5 does not allow a process to open a
+//! file for SWMR reading while it has it open for writing, so the writer runs in a child
+//! process: this test binary, re-run with only `swmr_writer` selected.
+
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use hdf5::conventions::SWMR_GENERATION_ATTR;
+use hdf5::swmr::PollingReader;
+use hdf5::{File, OpenMode};
+use hdf5_rt as hdf5;
+use ndarray::{s, Array2};
+
+const WRITER_ENV: &str = "HDF5_RT_TEST_SWMR_WRITER";
+const COLS: usize = 3;
+
+fn rows(start: usize, end: usize) -> Array2<i64> {
+    Array2::from_shape_fn((end - start, COLS), |(i, j)| ((start + i) * 10 + j) as i64)
+}
+
+fn create_file(path: &Path, generation: u64, initial_rows: usize) {
+    let file = File::with_options().with_fapl(|p| p.libver_v110()).create(path).unwrap();
+    file.new_attr::<u64>().create(SWMR_GENERATION_ATTR).unwrap().write_scalar(&generation).unwrap();
+    let ds = file.new_dataset::<i64>().chunk((4, COLS)).shape((0.., COLS)).create("rows").unwrap();
+    ds.resize((initial_rows, COLS)).unwrap();
+    ds.write(&rows(0, initial_rows)).unwrap();
+}
+
+/// Appends the numbers of rows read from stdin to the dataset, acknowledging each batch on
+/// stdout; does nothing unless run as the writer of `test_polling_reader`.
+#[test]
+fn swmr_writer() {
+    let Ok(path) = env::var(WRITER_ENV) else {
+        return;
+    };
+    let file = File::with_options().with_fapl(|p| p.libver_v110()).open_rw(&path).unwrap();
+    let ds = file.dataset("rows").unwrap();
+    file.start_swmr().unwrap();
+    println!("ready");
+    let mut len = ds.shape().unwrap()[0];
+    for line in std::io::stdin().lock().lines() {
+        let count: usize = line.unwrap().trim().parse().unwrap();
+        if count == 0 {
+            break;
+        }
+        ds.resize((len + count, COLS)).unwrap();
+        ds.write_slice(&rows(len, len + count), s![len.., ..]).unwrap();
+        ds.flush().unwrap();
+        len += count;
+        println!("appended {len}");
+    }
+}
+
+#[test]
+fn test_polling_reader() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("swmr.h5");
+    create_file(&path, 1, 2);
+
+    let mut writer = Command::new(env::current_exe().unwrap())
+        .args(["swmr_writer", "--exact", "--nocapture", "--test-threads=1"])
+        .env(WRITER_ENV, &path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = writer.stdin.take().unwrap();
+    let mut stdout = BufReader::new(writer.stdout.take().unwrap()).lines();
+    // libtest prints its own lines around the test output
+    let mut wait_for = |prefix: &str| loop {
+        let line = stdout.next().expect("writer exited").unwrap();
+        if line.starts_with(prefix) {
+            break line;
+        }
+    };
+    wait_for("ready");
+
+    let file = File::open_as(&path, OpenMode::ReadSWMR).unwrap();
+    let mut reader = PollingReader::new(&file.dataset("rows").unwrap()).unwrap();
+    assert_eq!(reader.poll::<i64>().unwrap().unwrap(), rows(0, 2).into_dyn());
+    assert!(reader.poll::<i64>().unwrap().is_none());
+
+    let mut len = 2;
+    for count in [5, 1, 8] {
+        writeln!(stdin, "{count}").unwrap();
+        assert_eq!(wait_for("appended"), format!("appended {}", len + count));
+        let block = reader.poll::<i64>().unwrap().unwrap();
+        assert_eq!(block, rows(len, len + count).into_dyn());
+        assert!(reader.poll::<i64>().unwrap().is_none());
+        len += count;
+        assert_eq!(reader.rows_seen(), len);
+    }
+    assert!(!reader.generation_changed().unwrap());
+
+    writeln!(stdin, "0").unwrap();
+    assert!(writer.wait().unwrap().success());
+
+    // the writer restarts with a new file, which replaces the old one
+    let new_path = dir.path().join("swmr.new.h5");
+    create_file(&new_path, 2, 3);
+    fs::rename(&new_path, &path).unwrap();
+    assert!(reader.generation_changed().unwrap());
+    assert_eq!(reader.rows_seen(), 0);
+    assert_eq!(reader.poll::<i64>().unwrap().unwrap(), rows(0, 3).into_dyn());
+    assert!(!reader.generation_changed().unwrap());
+}
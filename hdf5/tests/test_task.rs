@@ -0,0 +1,102 @@
+//! Tests for the async offloading helpers.
+#![cfg(feature = "tokio")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use hdf5::task::{read_dataset_async, write_dataset_async, TaskPool};
+use hdf5::{File, Selection};
+use hdf5_rt as hdf5;
+use ndarray::{s, Array2};
+
+fn create_file(path: &std::path::Path, count: usize) -> Vec<Array2<f64>> {
+    let file = File::create(path).unwrap();
+    let arrays: Vec<_> = (0..count)
+        .map(|i| Array2::from_shape_fn((20, 30), |(j, k)| (i * 1000 + j * 30 + k) as f64))
+        .collect();
+    for (i, arr) in arrays.iter().enumerate() {
+        file.new_dataset_builder().with_data(arr).create(format!("data/{i}").as_str()).unwrap();
+    }
+    arrays
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_reads() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("reads.h5");
+    let arrays = create_file(&path, 8);
+
+    let handles: Vec<_> = (0..arrays.len())
+        .map(|i| {
+            let path = path.clone();
+            tokio::spawn(async move {
+                read_dataset_async::<f64, _>(&path, &format!("data/{i}"), Selection::All).await
+            })
+        })
+        .collect();
+    for (handle, arr) in handles.into_iter().zip(&arrays) {
+        assert_eq!(handle.await.unwrap().unwrap(), arr.clone().into_dyn());
+    }
+
+    // results match synchronous reads, including selections
+    let slice = read_dataset_async::<f64, _>(&path, "data/3", s![2..5, 7]).await.unwrap();
+    let file = File::open(&path).unwrap();
+    let expected = file.dataset("data/3").unwrap().read_slice_1d::<f64, _>(s![2..5, 7]).unwrap();
+    assert_eq!(slice, expected.into_dyn());
+    drop(file);
+
+    let err = read_dataset_async::<f64, _>(&path, "missing", Selection::All).await.unwrap_err();
+    assert!(err.to_string().contains("reading dataset 'missing'"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("writes.h5");
+    let arrays = create_file(&path, 1);
+
+    let row = ndarray::Array1::from_elem(30, -1.0);
+    write_dataset_async(&path, "data/0", row, s![4, ..]).await.unwrap();
+    let read = read_dataset_async::<f64, _>(&path, "data/0", Selection::All).await.unwrap();
+    let mut expected = arrays[0].clone();
+    expected.row_mut(4).fill(-1.0);
+    assert_eq!(read, expected.into_dyn());
+
+    let wrong = ndarray::Array1::<f64>::zeros(3);
+    assert!(write_dataset_async(&path, "data/0", wrong, s![4, ..]).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_pool_limit() {
+    assert!(TaskPool::new(0).is_err());
+    let pool = TaskPool::new(2).unwrap();
+    assert_eq!(pool.max_tasks(), 2);
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let (pool, running, peak) = (pool.clone(), running.clone(), peak.clone());
+            tokio::spawn(async move {
+                pool.spawn(move || {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(i)
+                })
+                .await
+            })
+        })
+        .collect();
+    for (i, handle) in handles.into_iter().enumerate() {
+        assert_eq!(handle.await.unwrap().unwrap(), i);
+    }
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+    assert_eq!(running.load(Ordering::SeqCst), 0);
+
+    // the global pool can no longer be resized once in use
+    TaskPool::global();
+    assert!(hdf5::task::set_max_blocking_tasks(8).is_err());
+    assert!(hdf5::task::set_max_blocking_tasks(0).is_err());
+}
@@ -0,0 +1,144 @@
+//! Tests for timestamp storage and retrieval.
+#![cfg(feature = "time")]
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hdf5::fixtures::memory_file;
+use hdf5::types::time::{TimeEncoding, Timestamp};
+use hdf5::types::{FixedAscii, IntSize, TypeDescriptor, VarLenUnicode};
+use hdf5_rt as hdf5;
+
+const ENCODINGS: [TimeEncoding; 2] = [TimeEncoding::EpochNanos, TimeEncoding::Iso8601];
+
+fn sample_times() -> Vec<SystemTime> {
+    vec![
+        UNIX_EPOCH,
+        UNIX_EPOCH - Duration::from_nanos(1),
+        UNIX_EPOCH - Duration::new(2_208_988_800, 250_000_000), // 0.25s before 1900
+        UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789),
+    ]
+}
+
+#[test]
+fn test_time_attr_roundtrip() {
+    let file = memory_file().unwrap();
+    let times = sample_times();
+    for (i, encoding) in ENCODINGS.into_iter().enumerate() {
+        let attr = file
+            .new_attr_builder()
+            .empty_as(&encoding.type_descriptor())
+            .shape(times.len())
+            .create(format!("times{i}").as_str())
+            .unwrap();
+        attr.write_times(&times).unwrap();
+        assert_eq!(attr.dtype().unwrap().to_descriptor().unwrap(), encoding.type_descriptor());
+        assert_eq!(attr.read_times::<SystemTime>().unwrap(), times);
+
+        let scalar = file
+            .new_attr_builder()
+            .empty_as(&encoding.type_descriptor())
+            .create(format!("time{i}").as_str())
+            .unwrap();
+        scalar.write_time(&times[2]).unwrap();
+        assert_eq!(scalar.read_time::<SystemTime>().unwrap(), times[2]);
+    }
+    assert_eq!(file.attr("times0").unwrap().read_raw::<i64>().unwrap()[1], -1);
+    let iso = file.attr("times1").unwrap().read_raw::<FixedAscii<32>>().unwrap();
+    assert_eq!(iso[1].as_str(), "1969-12-31T23:59:59.999999999Z");
+    assert_eq!(iso[2].as_str(), "1899-12-31T23:59:59.75Z");
+}
+
+#[test]
+fn test_time_dataset_roundtrip() {
+    let file = memory_file().unwrap();
+    let times = sample_times();
+    for (i, encoding) in ENCODINGS.into_iter().enumerate() {
+        let ds = file
+            .new_dataset_builder()
+            .empty_as(&encoding.type_descriptor())
+            .shape(times.len())
+            .create(format!("times{i}").as_str())
+            .unwrap();
+        ds.write_times(&times).unwrap();
+        assert_eq!(ds.read_times::<SystemTime>().unwrap(), times);
+    }
+
+    // the current encoding is process-wide, so only this test changes it
+    TimeEncoding::Iso8601.set_current();
+    let ds = file.create_time_dataset("iso", &times).unwrap();
+    let attr = ds.create_time_attr("created", &times[3..]).unwrap();
+    TimeEncoding::EpochNanos.set_current();
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), TypeDescriptor::FixedAscii(32));
+    assert_eq!(attr.dtype().unwrap().to_descriptor().unwrap(), TypeDescriptor::FixedAscii(32));
+    assert_eq!(ds.read_times::<SystemTime>().unwrap(), times);
+    assert_eq!(attr.read_time::<SystemTime>().unwrap(), times[3]);
+
+    let ds = file.create_time_dataset("nanos", &times).unwrap();
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), TypeDescriptor::Integer(IntSize::U8));
+    assert_eq!(ds.read_times::<SystemTime>().unwrap(), times);
+}
+
+#[test]
+fn test_time_read_foreign_encodings() {
+    let file = memory_file().unwrap();
+    let strings: Vec<VarLenUnicode> = ["1970-01-01T00:00:01Z", "1969-12-31 23:00:00.5-01:00"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    let ds = file.new_dataset_builder().with_data(&strings).create("strings").unwrap();
+    let expected = [UNIX_EPOCH + Duration::from_secs(1), UNIX_EPOCH + Duration::from_millis(500)];
+    assert_eq!(ds.read_times::<SystemTime>().unwrap(), expected);
+
+    let ds = file.new_dataset_builder().with_data(&[1_i32, -1]).create("i32").unwrap();
+    let expected = [UNIX_EPOCH + Duration::from_nanos(1), UNIX_EPOCH - Duration::from_nanos(1)];
+    assert_eq!(ds.read_times::<SystemTime>().unwrap(), expected);
+
+    let ds = file.new_dataset_builder().with_data(&[1.5_f64]).create("f64").unwrap();
+    let err = ds.read_times::<SystemTime>().unwrap_err().to_string();
+    assert!(err.contains("expected timestamps stored as integers or strings"), "{err}");
+
+    let bad = ["yesterday".parse::<VarLenUnicode>().unwrap()];
+    let ds = file.new_dataset_builder().with_data(&bad).create("bad").unwrap();
+    let err = ds.read_times::<SystemTime>().unwrap_err().to_string();
+    assert!(err.contains("invalid ISO-8601 timestamp"), "{err}");
+
+    let far = UNIX_EPOCH + Duration::from_secs(400 * 365 * 86_400);
+    assert!(far.to_unix_nanos().is_err());
+    let ds = file.new_dataset::<i64>().shape(1).create("far").unwrap();
+    assert!(ds.write_times(&[far]).is_err());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_time_chrono() {
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+    let file = memory_file().unwrap();
+    let naive = [
+        NaiveDate::from_ymd_opt(1969, 7, 20).unwrap().and_hms_nano_opt(20, 17, 40, 5).unwrap(),
+        NaiveDate::from_ymd_opt(2024, 2, 29).unwrap().and_hms_milli_opt(12, 0, 0, 125).unwrap(),
+    ];
+    let utc = naive.map(|t| t.and_utc());
+    for (i, encoding) in ENCODINGS.into_iter().enumerate() {
+        let ds = file
+            .new_dataset_builder()
+            .empty_as(&encoding.type_descriptor())
+            .shape(2)
+            .create(format!("ds{i}").as_str())
+            .unwrap();
+        ds.write_times(&utc).unwrap();
+        assert_eq!(ds.read_times::<DateTime<Utc>>().unwrap(), utc);
+        assert_eq!(ds.read_times::<NaiveDateTime>().unwrap(), naive);
+        let attr = ds.new_attr_builder().empty_as(&encoding.type_descriptor()).create("t").unwrap();
+        attr.write_time(&naive[0]).unwrap();
+        assert_eq!(attr.read_time::<DateTime<Utc>>().unwrap(), utc[0]);
+    }
+
+    // written as a string, read back as a `DateTime`
+    let iso = [naive[1].to_iso8601().unwrap()];
+    assert_eq!(iso[0].as_str(), "2024-02-29T12:00:00.125Z");
+    let ds = file.new_dataset_builder().with_data(&iso).create("iso").unwrap();
+    assert_eq!(ds.read_times::<DateTime<Utc>>().unwrap(), [utc[1]]);
+    let system = ds.read_time::<SystemTime>().unwrap();
+    assert_eq!(system.to_unix_nanos(), utc[1].to_unix_nanos());
+}
@@ -0,0 +1,131 @@
+//! Tests for writing and reading trees of values.
+
+use hdf5::conventions::LIST_LEN_ATTR;
+use hdf5::fixtures::memory_file;
+use hdf5::types::VarLenUnicode;
+use hdf5::{from_group, to_group, to_group_with, H5Value, ScalarStorage, TreeOptions};
+use hdf5_rt as hdf5;
+use indexmap::IndexMap;
+use ndarray::{arr1, arr2, Array1};
+
+fn map<const N: usize>(entries: [(&str, H5Value); N]) -> H5Value {
+    H5Value::Map(entries.into_iter().map(|(k, v)| (k.to_owned(), v)).collect::<IndexMap<_, _>>())
+}
+
+fn sample() -> H5Value {
+    let runs = (0..11)
+        .map(|i| {
+            map([
+                ("seed", H5Value::from(i as u64)),
+                ("energies", H5Value::from(Array1::from_shape_fn(4, |j| (i * 4 + j) as f64))),
+            ])
+        })
+        .collect::<Vec<_>>();
+    map([
+        ("name", H5Value::string("tci").unwrap()),
+        ("tolerance", H5Value::from(1e-8)),
+        (
+            "model",
+            map([
+                ("kind", H5Value::string("heisenberg").unwrap()),
+                ("couplings", H5Value::from(arr2(&[[1.0, 0.5], [0.5, 1.0]]))),
+                ("lattice", map([("size", H5Value::from(16_i32)), ("periodic", true.into())])),
+            ]),
+        ),
+        ("runs", H5Value::List(runs)),
+        ("labels", H5Value::array(&arr1(&["a".parse::<VarLenUnicode>().unwrap()]))),
+    ])
+}
+
+#[test]
+fn test_tree_roundtrip() {
+    let file = memory_file().unwrap();
+    let value = sample();
+    to_group(&file, "config", &value).unwrap();
+
+    // layout
+    let config = file.group("config").unwrap();
+    assert_eq!(
+        config.attr("name").unwrap().read_scalar::<VarLenUnicode>().unwrap().as_str(),
+        "tci"
+    );
+    assert_eq!(config.attr("tolerance").unwrap().read_scalar::<f64>().unwrap(), 1e-8);
+    assert_eq!(config.member_names().unwrap(), ["labels", "model", "runs"]);
+    let couplings = file.dataset("config/model/couplings").unwrap();
+    assert_eq!(couplings.read_2d::<f64>().unwrap(), arr2(&[[1.0, 0.5], [0.5, 1.0]]));
+    let lattice = file.group("config/model/lattice").unwrap();
+    assert_eq!(lattice.attr("size").unwrap().read_scalar::<i32>().unwrap(), 16);
+    let runs = file.group("config/runs").unwrap();
+    assert_eq!(runs.attr(LIST_LEN_ATTR).unwrap().read_scalar::<u64>().unwrap(), 11);
+    assert_eq!(runs.member_names().unwrap()[..3], ["00", "01", "02"]);
+    let energies = file.dataset("config/runs/10/energies").unwrap();
+    assert_eq!(energies.read_raw::<f64>().unwrap(), [40.0, 41.0, 42.0, 43.0]);
+
+    // value tree
+    let read = from_group(&config).unwrap();
+    assert_eq!(read, value);
+    assert_eq!(read.get("name").and_then(H5Value::to_str).as_deref(), Some("tci"));
+    let model = read.get("model").unwrap();
+    assert_eq!(
+        model.get("lattice").and_then(|l| l.get("periodic")).unwrap().to_scalar(),
+        Some(true)
+    );
+    let couplings = model.get("couplings").unwrap().to_array::<f64>().unwrap();
+    assert_eq!(couplings.shape(), [2, 2]);
+    let runs = read.get("runs").and_then(H5Value::as_list).unwrap();
+    assert_eq!(runs.len(), 11);
+    assert_eq!(runs[3].get("seed").unwrap().to_scalar::<u64>(), Some(3));
+    assert!(runs[3].get("seed").unwrap().to_scalar::<i64>().is_none());
+}
+
+#[test]
+fn test_tree_scalar_datasets() {
+    let file = memory_file().unwrap();
+    let value = sample();
+    let options = TreeOptions { scalars: ScalarStorage::Dataset };
+    to_group_with(&file, "config", &value, &options).unwrap();
+    let tolerance = file.dataset("config/tolerance").unwrap();
+    assert!(tolerance.is_scalar());
+    assert_eq!(tolerance.read_scalar::<f64>().unwrap(), 1e-8);
+    assert_eq!(from_group(&file.group("config").unwrap()).unwrap(), value);
+
+    // mixed element types and invalid names are rejected
+    let mut data = ndarray::Array1::from_elem(2, hdf5::types::OwnedDynValue::new(1_i32)).into_dyn();
+    data[1] = hdf5::types::OwnedDynValue::new(1.0_f64);
+    let mixed = H5Value::Array { dtype: <i32 as hdf5::H5Type>::type_descriptor(), data };
+    assert!(to_group(&file, "mixed", &mixed).is_err());
+    assert!(to_group(&file, "a/b", &H5Value::from(1_u8)).is_err());
+}
+
+#[test]
+fn test_tree_roundtrip_all_variants() {
+    let file = memory_file().unwrap();
+    let scalar = H5Value::from(7_i64);
+    let string = H5Value::string("text").unwrap();
+    let array = H5Value::from(arr2(&[[1_u16, 2], [3, 4]]));
+    let nested = map([("x", H5Value::from(0.5)), ("y", array.clone())]);
+    let list = H5Value::List(vec![
+        scalar.clone(),
+        string.clone(),
+        array.clone(),
+        nested.clone(),
+        H5Value::List(vec![H5Value::from(true), H5Value::List(vec![])]),
+    ]);
+    let value = map([
+        ("scalar", scalar),
+        ("string", string),
+        ("array", array),
+        ("map", nested),
+        ("empty_map", map([])),
+        ("list", list.clone()),
+        ("empty_list", H5Value::List(vec![])),
+    ]);
+
+    for (i, scalars) in [ScalarStorage::Attribute, ScalarStorage::Dataset].into_iter().enumerate() {
+        let name = format!("tree{i}");
+        to_group_with(&file, &name, &value, &TreeOptions { scalars }).unwrap();
+        let read = from_group(&file.group(&name).unwrap()).unwrap();
+        assert_eq!(read, value, "{scalars:?}");
+        assert_eq!(read.get("list"), Some(&list), "{scalars:?}");
+    }
+}
@@ -0,0 +1,165 @@
+//! Tests for UUID storage and retrieval.
+#![cfg(feature = "uuid")]
+
+use std::mem;
+
+use hdf5::fixtures::memory_file;
+use hdf5::types::uuid::{Uuid, UuidEncoding, UuidString, UUID_TAG};
+use hdf5::types::{CompoundField, CompoundType, OpaqueType, TypeDescriptor, VarLenUnicode};
+use hdf5::H5Type;
+use hdf5_rt as hdf5;
+
+const ENCODINGS: [UuidEncoding; 2] = [UuidEncoding::Opaque, UuidEncoding::String];
+
+fn sample_uuids() -> Vec<Uuid> {
+    vec![
+        Uuid::nil(),
+        Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8),
+        Uuid::from_u128(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210),
+        Uuid::max(),
+    ]
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Record {
+    id: Uuid,
+    value: f64,
+    parent: UuidString,
+}
+
+unsafe impl H5Type for Record {
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::Compound(CompoundType {
+            fields: vec![
+                CompoundField::typed::<Uuid>("id", mem::offset_of!(Record, id), 0),
+                CompoundField::typed::<f64>("value", mem::offset_of!(Record, value), 1),
+                CompoundField::typed::<UuidString>("parent", mem::offset_of!(Record, parent), 2),
+            ],
+            size: mem::size_of::<Record>(),
+        })
+    }
+}
+
+#[test]
+fn test_uuid_h5type_roundtrip() {
+    let file = memory_file().unwrap();
+    let uuids = sample_uuids();
+
+    let ds = file.new_dataset_builder().with_data(&uuids).create("opaque").unwrap();
+    let opaque = TypeDescriptor::Opaque(OpaqueType { size: 16, tag: UUID_TAG.into() });
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), opaque);
+    assert_eq!(ds.read_raw::<Uuid>().unwrap(), uuids);
+
+    let attr = ds.new_attr::<Uuid>().create("id").unwrap();
+    attr.write_scalar(&uuids[1]).unwrap();
+    assert_eq!(attr.read_scalar::<Uuid>().unwrap(), uuids[1]);
+
+    let strings: Vec<UuidString> = uuids.iter().copied().map(UuidString::from).collect();
+    let ds = file.new_dataset_builder().with_data(&strings).create("strings").unwrap();
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), TypeDescriptor::FixedAscii(36));
+    let read = ds.read_raw::<UuidString>().unwrap();
+    assert_eq!(read[1].as_str(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    assert_eq!(read.iter().map(|s| s.uuid().unwrap()).collect::<Vec<_>>(), uuids);
+
+    // arrays of UUIDs, as dataset elements and as attributes
+    let pairs = vec![[uuids[0], uuids[1]], [uuids[2], uuids[3]]];
+    let ds = file.new_dataset_builder().with_data(&pairs).create("pairs").unwrap();
+    assert_eq!(ds.read_raw::<[Uuid; 2]>().unwrap(), pairs);
+    let attr = ds.new_attr::<Uuid>().shape(4).create("all").unwrap();
+    attr.write_raw(&uuids).unwrap();
+    assert_eq!(attr.read_raw::<Uuid>().unwrap(), uuids);
+
+    // UUID fields inside compounds
+    let records: Vec<Record> = uuids
+        .iter()
+        .enumerate()
+        .map(|(i, &id)| Record { id, value: i as f64, parent: uuids[0].into() })
+        .collect();
+    let ds = file.new_dataset_builder().with_data(&records).create("records").unwrap();
+    assert_eq!(ds.read_raw::<Record>().unwrap(), records);
+}
+
+#[test]
+fn test_uuid_encodings() {
+    let file = memory_file().unwrap();
+    let uuids = sample_uuids();
+    for (i, encoding) in ENCODINGS.into_iter().enumerate() {
+        let ds = file
+            .new_dataset_builder()
+            .empty_as(&encoding.type_descriptor())
+            .shape(uuids.len())
+            .create(format!("uuids{i}").as_str())
+            .unwrap();
+        ds.write_uuids(&uuids).unwrap();
+        assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), encoding.type_descriptor());
+        assert_eq!(ds.read_uuids().unwrap(), uuids);
+
+        let attr =
+            ds.new_attr_builder().empty_as(&encoding.type_descriptor()).create("id").unwrap();
+        attr.write_uuid(&uuids[2]).unwrap();
+        assert_eq!(attr.read_uuid().unwrap(), uuids[2]);
+    }
+
+    // written as opaque values, read through the string type and vice versa
+    let opaque = file.dataset("uuids0").unwrap();
+    assert_eq!(opaque.read_raw::<Uuid>().unwrap(), uuids);
+    let strings = file.dataset("uuids1").unwrap();
+    let read = strings.read_raw::<UuidString>().unwrap();
+    assert_eq!(read.iter().map(|s| s.uuid().unwrap()).collect::<Vec<_>>(), uuids);
+    let ds = file.new_dataset_builder().with_data(&uuids).create("opaque").unwrap();
+    assert_eq!(ds.read_uuids().unwrap(), uuids);
+    let strings: Vec<UuidString> = uuids.iter().copied().map(UuidString::from).collect();
+    let ds = file.new_dataset_builder().with_data(&strings).create("strings").unwrap();
+    assert_eq!(ds.read_uuids().unwrap(), uuids);
+
+    // the current encoding is process-wide, so only this test changes it
+    UuidEncoding::String.set_current();
+    let ds = file.create_uuid_dataset("created", &uuids).unwrap();
+    let attr = ds.create_uuid_attr("parents", &uuids[..2]).unwrap();
+    UuidEncoding::Opaque.set_current();
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), TypeDescriptor::FixedAscii(36));
+    assert_eq!(attr.dtype().unwrap().to_descriptor().unwrap(), TypeDescriptor::FixedAscii(36));
+    assert_eq!(ds.read_uuids().unwrap(), uuids);
+    assert_eq!(attr.read_uuids().unwrap(), &uuids[..2]);
+
+    let ds = file.create_uuid_dataset("created_opaque", &uuids).unwrap();
+    assert_eq!(ds.dtype().unwrap().to_descriptor().unwrap(), Uuid::type_descriptor());
+    assert_eq!(ds.read_uuids().unwrap(), uuids);
+}
+
+#[test]
+fn test_uuid_read_foreign_encodings() {
+    let file = memory_file().unwrap();
+    let uuids = sample_uuids();
+
+    let strings: Vec<VarLenUnicode> = [
+        "{67e55044-10b1-426f-9247-bb680e5fe0c8}",
+        "urn:uuid:67E55044-10B1-426F-9247-BB680E5FE0C8",
+        "67e5504410b1426f9247bb680e5fe0c8",
+    ]
+    .iter()
+    .map(|s| s.parse().unwrap())
+    .collect();
+    let ds = file.new_dataset_builder().with_data(&strings).create("varlen").unwrap();
+    assert_eq!(ds.read_uuids().unwrap(), [uuids[1]; 3]);
+    ds.write_uuids(&uuids[..3]).unwrap();
+    assert_eq!(
+        ds.read_raw::<VarLenUnicode>().unwrap()[2].as_str(),
+        UuidString::new(uuids[2]).as_str()
+    );
+
+    let bytes: Vec<[u8; 16]> = uuids.iter().map(|u| *u.as_bytes()).collect();
+    let ds = file.new_dataset_builder().with_data(&bytes).create("bytes").unwrap();
+    assert_eq!(ds.read_uuids().unwrap(), uuids);
+
+    let ds = file.new_dataset_builder().with_data(&[1.5_f64]).create("f64").unwrap();
+    let err = ds.read_uuids().unwrap_err().to_string();
+    assert!(err.contains("expected UUIDs stored as 16-byte opaque values or strings"), "{err}");
+    assert!(ds.write_uuids(&uuids[..1]).is_err());
+
+    let bad = ["not a uuid".parse::<VarLenUnicode>().unwrap()];
+    let ds = file.new_dataset_builder().with_data(&bad).create("bad").unwrap();
+    let err = ds.read_uuids().unwrap_err().to_string();
+    assert!(err.contains("invalid UUID \"not a uuid\""), "{err}");
+}
@@ -0,0 +1,77 @@
+//! Tests for writing many attributes at once with `Location::write_attrs`, which count the
+//! acquisitions of the library lock with the `test-hooks` feature.
+#![cfg(feature = "test-hooks")]
+
+use hdf5::fixtures::memory_file;
+use hdf5::types::{OwnedDynValue, VarLenUnicode};
+use hdf5::{from_group, sync, Group, Result};
+use hdf5_rt as hdf5;
+
+const COUNT: usize = 500;
+
+fn name(i: usize) -> String {
+    format!("attr{i:03}")
+}
+
+fn label(i: usize) -> VarLenUnicode {
+    format!("value {i}").parse().unwrap()
+}
+
+fn write_individually(group: &Group) -> Result<()> {
+    for i in 0..COUNT {
+        let name = name(i);
+        match i % 3 {
+            0 => group.new_attr::<i64>().create(name.as_str())?.write_scalar(&(i as i64))?,
+            1 => group.new_attr::<f64>().create(name.as_str())?.write_scalar(&(i as f64))?,
+            _ => {
+                group.new_attr::<VarLenUnicode>().create(name.as_str())?.write_scalar(&label(i))?
+            }
+        }
+    }
+    Ok(())
+}
+
+fn batch_values() -> Vec<(String, OwnedDynValue)> {
+    // in reverse order, which must not matter
+    (0..COUNT)
+        .rev()
+        .map(|i| {
+            let value = match i % 3 {
+                0 => OwnedDynValue::from(i as i64),
+                1 => OwnedDynValue::from(i as f64),
+                _ => OwnedDynValue::from(label(i)),
+            };
+            (name(i), value)
+        })
+        .collect()
+}
+
+#[test]
+fn test_write_attrs_matches_individual_writes() {
+    let (file1, file2) = (memory_file().unwrap(), memory_file().unwrap());
+    let (group1, group2) = (file1.create_group("obj").unwrap(), file2.create_group("obj").unwrap());
+
+    let before = sync::lock_acquisitions();
+    write_individually(&group1).unwrap();
+    assert!(sync::lock_acquisitions() - before >= COUNT as u64);
+
+    let values = batch_values();
+    let attrs: Vec<_> = values.iter().map(|(name, value)| (name.as_str(), value.clone())).collect();
+    let before = sync::lock_acquisitions();
+    group2.write_attrs(&attrs).unwrap();
+    assert_eq!(sync::lock_acquisitions() - before, 1);
+
+    assert_eq!(from_group(&group1).unwrap(), from_group(&group2).unwrap());
+    assert_eq!(group1.attr_names().unwrap(), group2.attr_names().unwrap());
+    for name in group1.attr_names().unwrap() {
+        let (attr1, attr2) = (group1.attr(&name).unwrap(), group2.attr(&name).unwrap());
+        assert_eq!(attr1.dtype().unwrap(), attr2.dtype().unwrap(), "{name}");
+        assert_eq!(attr1.shape().unwrap(), attr2.shape().unwrap(), "{name}");
+    }
+
+    // rewriting the same values changes nothing
+    let before = sync::lock_acquisitions();
+    assert_eq!(group2.write_attrs_if_changed(&attrs).unwrap(), 0);
+    assert_eq!(sync::lock_acquisitions() - before, 1);
+    assert_eq!(from_group(&group1).unwrap(), from_group(&group2).unwrap());
+}